@@ -28,7 +28,7 @@ fn bench_apriori_1k(c: &mut Criterion) {
         b.iter(|| {
             let mut miner = RuleMiner::new(config.clone());
             miner.add_transactions(transactions.clone()).unwrap();
-            black_box(miner.mine_association_rules().unwrap())
+            black_box(miner.mine().unwrap().rules)
         });
     });
 }
@@ -41,7 +41,7 @@ fn bench_apriori_100(c: &mut Criterion) {
         b.iter(|| {
             let mut miner = RuleMiner::new(config.clone());
             miner.add_transactions(transactions.clone()).unwrap();
-            black_box(miner.mine_association_rules().unwrap())
+            black_box(miner.mine().unwrap().rules)
         });
     });
 }
@@ -1,13 +1,70 @@
-// Placeholder for FP-Growth benchmarks (to be implemented)
-use criterion::{criterion_group, criterion_main, Criterion};
+use chrono::Utc;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_rule_miner::mining::fpgrowth;
+use rust_rule_miner::Transaction;
 
-fn bench_placeholder(c: &mut Criterion) {
-    c.bench_function("fp_growth_placeholder", |b| {
-        b.iter(|| {
-            // To be implemented
+/// Baskets dominated by a few items present in almost every transaction,
+/// so conditional pattern bases carry very large path counts
+fn create_high_frequency_transactions(count: usize) -> Vec<Transaction> {
+    let staples = ["Milk", "Bread", "Eggs", "Butter"];
+    let extras = ["Coffee", "Tea", "Jam", "Cheese", "Apples", "Rice"];
+
+    (0..count)
+        .map(|i| {
+            let mut items: Vec<String> = staples
+                .iter()
+                .filter(|_| i % 20 != 0)
+                .map(|s| s.to_string())
+                .collect();
+            items.push(extras[i % extras.len()].to_string());
+            items.push(extras[(i / 3) % extras.len()].to_string());
+            Transaction::new(format!("tx{}", i), items, Utc::now())
+        })
+        .collect()
+}
+
+/// 10k baskets drawn from `catalog` items, giving wide trees whose size
+/// grows with the catalog
+fn create_catalog_transactions(catalog: usize) -> Vec<Transaction> {
+    (0..10_000)
+        .map(|i| {
+            let items = (0..5)
+                .map(|j| format!("sku{}", (i * 31 + j * j * 17) % catalog))
+                .collect();
+            Transaction::new(format!("tx{}", i), items, Utc::now())
+        })
+        .collect()
+}
+
+fn bench_fp_growth_high_frequency(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fp_growth_high_frequency");
+
+    for size in [1_000, 10_000, 50_000].iter() {
+        let transactions = create_high_frequency_transactions(*size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
+            b.iter(|| black_box(fpgrowth::find_frequent_itemsets(&transactions, 0.05).unwrap()));
         });
-    });
+    }
+
+    group.finish();
+}
+
+fn bench_fp_growth_catalog_size(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fp_growth_10k_by_catalog_size");
+
+    for catalog in [20, 100, 500].iter() {
+        let transactions = create_catalog_transactions(*catalog);
+        group.bench_with_input(BenchmarkId::from_parameter(catalog), catalog, |b, _| {
+            b.iter(|| black_box(fpgrowth::find_frequent_itemsets(&transactions, 0.002).unwrap()));
+        });
+    }
+
+    group.finish();
 }
 
-criterion_group!(benches, bench_placeholder);
+criterion_group!(
+    benches,
+    bench_fp_growth_high_frequency,
+    bench_fp_growth_catalog_size
+);
 criterion_main!(benches);
@@ -1,6 +1,9 @@
 use chrono::Utc;
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use rust_rule_miner::{MiningAlgorithm, MiningConfig, RuleMiner, Transaction};
+use rust_rule_miner::mining::apriori;
+use rust_rule_miner::{
+    Confidence, Lift, MetricSet, MiningAlgorithm, MiningConfig, RuleMiner, Support, Transaction,
+};
 
 fn generate_transactions(count: usize) -> Vec<Transaction> {
     let products = vec![
@@ -50,17 +53,19 @@ fn benchmark_apriori(c: &mut Criterion) {
 
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
             b.iter(|| {
-                let mut miner = RuleMiner::new(MiningConfig {
-                    min_support: 0.05,
-                    min_confidence: 0.6,
-                    min_lift: 1.2,
-                    max_time_gap: None,
-                    algorithm: MiningAlgorithm::Apriori,
-                });
+                let mut miner = RuleMiner::new(
+                    MiningConfig::builder()
+                        .min_support(Support::fraction(0.05).unwrap())
+                        .min_confidence(Confidence::fraction(0.6).unwrap())
+                        .min_lift(Lift::new(1.2).unwrap())
+                        .algorithm(MiningAlgorithm::Apriori)
+                        .build()
+                        .unwrap(),
+                );
                 miner
                     .add_transactions(black_box(transactions.clone()))
                     .unwrap();
-                let _rules = miner.mine_association_rules().unwrap();
+                let _rules = miner.mine().unwrap().rules;
             });
         });
     }
@@ -77,17 +82,19 @@ fn benchmark_memory_usage(c: &mut Criterion) {
 
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
             b.iter(|| {
-                let mut miner = RuleMiner::new(MiningConfig {
-                    min_support: 0.05,
-                    min_confidence: 0.6,
-                    min_lift: 1.2,
-                    max_time_gap: None,
-                    algorithm: MiningAlgorithm::Apriori,
-                });
+                let mut miner = RuleMiner::new(
+                    MiningConfig::builder()
+                        .min_support(Support::fraction(0.05).unwrap())
+                        .min_confidence(Confidence::fraction(0.6).unwrap())
+                        .min_lift(Lift::new(1.2).unwrap())
+                        .algorithm(MiningAlgorithm::Apriori)
+                        .build()
+                        .unwrap(),
+                );
                 miner
                     .add_transactions(black_box(transactions.clone()))
                     .unwrap();
-                let rules = miner.mine_association_rules().unwrap();
+                let rules = miner.mine().unwrap().rules;
                 black_box(rules);
             });
         });
@@ -104,17 +111,19 @@ fn benchmark_rule_generation(c: &mut Criterion) {
 
     group.bench_function("10k_transactions", |b| {
         b.iter(|| {
-            let mut miner = RuleMiner::new(MiningConfig {
-                min_support: 0.01, // Lower threshold for more rules
-                min_confidence: 0.5,
-                min_lift: 1.1,
-                max_time_gap: None,
-                algorithm: MiningAlgorithm::Apriori,
-            });
+            let mut miner = RuleMiner::new(
+                MiningConfig::builder()
+                    .min_support(Support::fraction(0.01).unwrap()) // Lower threshold for more rules
+                    .min_confidence(Confidence::fraction(0.5).unwrap())
+                    .min_lift(Lift::new(1.1).unwrap())
+                    .algorithm(MiningAlgorithm::Apriori)
+                    .build()
+                    .unwrap(),
+            );
             miner
                 .add_transactions(black_box(transactions.clone()))
                 .unwrap();
-            let rules = miner.mine_association_rules().unwrap();
+            let rules = miner.mine().unwrap().rules;
             black_box(rules.len());
         });
     });
@@ -122,10 +131,75 @@ fn benchmark_rule_generation(c: &mut Criterion) {
     group.finish();
 }
 
+fn benchmark_metric_sets(c: &mut Criterion) {
+    let mut group = c.benchmark_group("metric_sets");
+
+    let transactions = generate_transactions(10_000);
+
+    for (name, metrics) in [("core", MetricSet::CORE), ("all", MetricSet::ALL)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut miner = RuleMiner::new(
+                    MiningConfig::builder()
+                        .min_support(Support::fraction(0.01).unwrap())
+                        .min_confidence(Confidence::fraction(0.5).unwrap())
+                        .compute_metrics(metrics)
+                        .build()
+                        .unwrap(),
+                );
+                miner
+                    .add_transactions(black_box(transactions.clone()))
+                    .unwrap();
+                let rules = miner.mine().unwrap().rules;
+                black_box(rules.len());
+            });
+        });
+    }
+
+    group.finish();
+}
+
+/// Support counting on 10k transactions: scanning with
+/// `Transaction::contains_all` (how Apriori counted before the integer
+/// transaction index) against indexed Apriori over the same itemsets
+fn benchmark_support_counting(c: &mut Criterion) {
+    let mut group = c.benchmark_group("support_counting_10k");
+
+    let transactions = generate_transactions(10_000);
+    let itemsets: Vec<Vec<String>> = apriori::find_frequent_itemsets(&transactions, 0.01)
+        .unwrap()
+        .into_iter()
+        .map(|itemset| itemset.items)
+        .collect();
+
+    group.bench_function("contains_all_scan", |b| {
+        b.iter(|| {
+            let counts: Vec<usize> = itemsets
+                .iter()
+                .map(|itemset| {
+                    transactions
+                        .iter()
+                        .filter(|tx| tx.contains_all(itemset))
+                        .count()
+                })
+                .collect();
+            black_box(counts);
+        });
+    });
+
+    group.bench_function("indexed_apriori", |b| {
+        b.iter(|| black_box(apriori::find_frequent_itemsets(&transactions, 0.01).unwrap()));
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_apriori,
     benchmark_memory_usage,
-    benchmark_rule_generation
+    benchmark_rule_generation,
+    benchmark_metric_sets,
+    benchmark_support_counting
 );
 criterion_main!(benches);
@@ -0,0 +1,151 @@
+//! # rust-rule-miner-edge
+//!
+//! Rule evaluation for targets that cannot pull in the full miner: no
+//! dependencies, `no_std`, and no allocation. Rules are `const` data, usually
+//! generated by `rust_rule_miner::export::EdgeExporter`, and baskets are
+//! plain `&str` slices.
+//!
+//! The full crate re-exports this one as `rust_rule_miner::edge` and
+//! `AssociationRule::matches` uses `contains_all`, so a rule fires on the
+//! device exactly when it fires on the server.
+//!
+//! ```
+//! use rust_rule_miner_edge::{best, Rule};
+//!
+//! const RULES: &[Rule] = &[
+//!     Rule::new(&["Laptop"], &["Mouse"], 0.8),
+//!     Rule::new(&["Laptop", "Mouse"], &["Mousepad"], 0.9),
+//! ];
+//!
+//! let rule = best(RULES, &["Mouse", "Laptop"]).unwrap();
+//! assert_eq!(rule.consequent, ["Mousepad"]);
+//! ```
+
+#![no_std]
+
+/// A mined rule as `const` data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rule {
+    /// Items that must all be in the basket
+    pub antecedent: &'static [&'static str],
+
+    /// Items the rule proposes
+    pub consequent: &'static [&'static str],
+
+    /// Confidence (0.0 - 1.0), used to rank firing rules
+    pub confidence: f64,
+}
+
+impl Rule {
+    /// Create a rule; usable in `const` items
+    pub const fn new(
+        antecedent: &'static [&'static str],
+        consequent: &'static [&'static str],
+        confidence: f64,
+    ) -> Self {
+        Self {
+            antecedent,
+            consequent,
+            confidence,
+        }
+    }
+
+    /// Whether the antecedent is a subset of `basket`
+    pub fn matches(&self, basket: &[&str]) -> bool {
+        contains_all(basket, self.antecedent)
+    }
+
+    /// Whether the rule matches and proposes something not yet in `basket`
+    pub fn fires(&self, basket: &[&str]) -> bool {
+        self.matches(basket) && !contains_all(basket, self.consequent)
+    }
+}
+
+/// Whether every one of `items` is in `basket`
+///
+/// Linear scans, no hashing: baskets on the device are a handful of items.
+/// An empty `items` is contained in every basket.
+pub fn contains_all<B: AsRef<str>, I: AsRef<str>>(basket: &[B], items: &[I]) -> bool {
+    items
+        .iter()
+        .all(|item| basket.iter().any(|held| held.as_ref() == item.as_ref()))
+}
+
+/// Rules that fire for `basket`, in the order given
+pub fn matching<'a>(
+    rules: &'a [Rule],
+    basket: &'a [&'a str],
+) -> impl Iterator<Item = &'a Rule> + 'a {
+    rules.iter().filter(move |rule| rule.fires(basket))
+}
+
+/// Highest-confidence rule that fires for `basket`
+///
+/// Ties go to the earlier rule, so exporting rules best-first keeps the
+/// server's ranking.
+pub fn best<'a>(rules: &'a [Rule], basket: &[&str]) -> Option<&'a Rule> {
+    rules.iter().filter(|rule| rule.fires(basket)).fold(
+        None,
+        |best: Option<&Rule>, rule| match best {
+            Some(kept) if kept.confidence >= rule.confidence => Some(kept),
+            _ => Some(rule),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Shaped like `EdgeExporter` output
+    const RULES: &[Rule] = &[
+        Rule::new(&["Bread", "Butter"], &["Jam"], 0.9),
+        Rule::new(&["Bread"], &["Milk"], 0.75),
+        Rule::new(&["Milk"], &["Bread"], 0.75),
+        Rule::new(&["Coffee"], &["Sugar", "Cream"], 0.6),
+    ];
+
+    #[test]
+    fn test_contains_all() {
+        assert!(contains_all(&["A", "B", "C"], &["C", "A"]));
+        assert!(contains_all(&["A"], &[] as &[&str]));
+        assert!(!contains_all(&["A", "B"], &["A", "Z"]));
+        assert!(!contains_all(&[] as &[&str], &["A"]));
+    }
+
+    #[test]
+    fn test_rule_matching() {
+        let rule = RULES[0];
+        assert!(rule.matches(&["Butter", "Bread"]));
+        assert!(!rule.matches(&["Bread"]));
+        assert!(rule.fires(&["Bread", "Butter"]));
+        // Already holds the consequent
+        assert!(!rule.fires(&["Bread", "Butter", "Jam"]));
+    }
+
+    #[test]
+    fn test_matching_keeps_rule_order() {
+        let basket = ["Bread", "Butter", "Milk"];
+        let mut fired = matching(RULES, &basket);
+        assert_eq!(fired.next(), Some(&RULES[0]));
+        // Bread => Milk and Milk => Bread propose items already held
+        assert_eq!(fired.next(), None);
+
+        let basket = ["Coffee", "Sugar"];
+        assert_eq!(matching(RULES, &basket).count(), 1);
+    }
+
+    #[test]
+    fn test_best_rule() {
+        assert_eq!(best(RULES, &["Bread", "Butter"]), Some(&RULES[0]));
+        assert_eq!(best(RULES, &["Bread"]), Some(&RULES[1]));
+        assert_eq!(best(RULES, &["Tea"]), None);
+
+        // Equal confidence: the earlier rule wins
+        let tied = [
+            Rule::new(&["A"], &["B"], 0.5),
+            Rule::new(&["A"], &["C"], 0.5),
+        ];
+        assert_eq!(best(&tied, &["A"]).unwrap().consequent, ["B"]);
+    }
+}
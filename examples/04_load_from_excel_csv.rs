@@ -6,7 +6,7 @@
 
 use rust_rule_miner::{
     data_loader::{ColumnMapping, DataLoader},
-    MiningConfig, RuleMiner,
+    Confidence, Lift, MiningConfig, RuleMiner, Support,
 };
 use std::fs;
 use std::io::Write;
@@ -70,10 +70,9 @@ fn demo_csv_loading() -> Result<(), Box<dyn std::error::Error>> {
 
     let file_path = "/tmp/sample_transactions.csv";
 
-    // Load transactions using excelstream with standard column mapping
+    // Load transactions using excelstream in the standard layout
     // CSV format: transaction_id, items, timestamp (columns 0, 1, 2)
-    let mapping = ColumnMapping::simple(0, 1, 2);
-    let transactions = DataLoader::from_csv(file_path, mapping)?;
+    let transactions = DataLoader::from_csv_standard(file_path)?;
 
     println!("✓ Loaded {} transactions from CSV", transactions.len());
     println!();
@@ -96,22 +95,20 @@ fn demo_csv_mining() -> Result<(), Box<dyn std::error::Error>> {
 
     let file_path = "/tmp/sample_transactions.csv";
 
-    // Load transactions with standard column mapping
-    let mapping = ColumnMapping::simple(0, 1, 2);
-    let transactions = DataLoader::from_csv(file_path, mapping)?;
+    // Load transactions in the standard layout
+    let transactions = DataLoader::from_csv_standard(file_path)?;
 
     // Mining configuration
-    let config = MiningConfig {
-        min_support: 0.2,    // 20% - at least 3 out of 15 transactions
-        min_confidence: 0.7, // 70% confidence
-        min_lift: 1.2,       // Positive correlation
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.2)?) // 20% - at least 3 out of 15 transactions
+        .min_confidence(Confidence::fraction(0.7)?) // 70% confidence
+        .min_lift(Lift::new(1.2)?) // Positive correlation
+        .build()?;
 
     println!("Mining Configuration:");
-    println!("  Min Support: {:.0}%", config.min_support * 100.0);
-    println!("  Min Confidence: {:.0}%", config.min_confidence * 100.0);
-    println!("  Min Lift: {:.1}", config.min_lift);
+    println!("  Min Support: {:.0}%", config.min_support() * 100.0);
+    println!("  Min Confidence: {:.0}%", config.min_confidence() * 100.0);
+    println!("  Min Lift: {:.1}", config.min_lift());
     println!();
 
     // Mine association rules
@@ -119,7 +116,7 @@ fn demo_csv_mining() -> Result<(), Box<dyn std::error::Error>> {
     miner.add_transactions(transactions)?;
 
     println!("Mining rules...");
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!("✓ Found {} association rules", rules.len());
     println!();
@@ -257,15 +254,14 @@ fn demo_column_mapping() -> Result<(), Box<dyn std::error::Error>> {
     let mapping = ColumnMapping::multi_field(0, vec![1, 2], 5, "::".to_string());
     let transactions = DataLoader::from_csv(file_path, mapping)?;
 
-    let config = MiningConfig {
-        min_support: 0.3,
-        min_confidence: 0.7,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.3)?)
+        .min_confidence(Confidence::fraction(0.7)?)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(transactions)?;
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!("✓ Found {} rules with combined patterns", rules.len());
     if !rules.is_empty() {
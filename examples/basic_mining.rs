@@ -1,5 +1,7 @@
 use chrono::Utc;
-use rust_rule_miner::{export::GrlExporter, MiningConfig, RuleMiner, Transaction};
+use rust_rule_miner::{
+    export::GrlExporter, Confidence, Lift, MiningConfig, RuleMiner, Support, Transaction,
+};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Basic Rule Mining Example ===\n");
@@ -58,18 +60,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Loaded {} transactions\n", transactions.len());
 
     // 2. Configure mining parameters
-    let config = MiningConfig {
-        min_support: 0.3,    // 30% - pattern must appear in at least 30% of transactions
-        min_confidence: 0.7, // 70% - rule must be correct at least 70% of the time
-        min_lift: 1.2,       // 20% above random chance
-        max_time_gap: None,
-        algorithm: rust_rule_miner::MiningAlgorithm::Apriori,
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.3)?) // 30% - pattern must appear in at least 30% of transactions
+        .min_confidence(Confidence::fraction(0.7)?) // 70% - rule must be correct at least 70% of the time
+        .min_lift(Lift::new(1.2)?) // 20% above random chance
+        .algorithm(rust_rule_miner::MiningAlgorithm::Apriori)
+        .build()?;
 
     println!("Mining Configuration:");
-    println!("  Min Support: {:.1}%", config.min_support * 100.0);
-    println!("  Min Confidence: {:.1}%", config.min_confidence * 100.0);
-    println!("  Min Lift: {:.2}", config.min_lift);
+    println!("  Min Support: {:.1}%", config.min_support() * 100.0);
+    println!("  Min Confidence: {:.1}%", config.min_confidence() * 100.0);
+    println!("  Min Lift: {:.2}", config.min_lift());
     println!("  Algorithm: Apriori\n");
 
     // 3. Mine association rules
@@ -77,7 +78,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     miner.add_transactions(transactions)?;
 
     println!("Mining association rules...\n");
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     // 4. Display discovered rules
     println!("=== Discovered Rules ({}) ===\n", rules.len());
@@ -88,7 +89,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("  Confidence: {:.1}%", rule.metrics.confidence * 100.0);
         println!("  Support: {:.1}%", rule.metrics.support * 100.0);
         println!("  Lift: {:.2}", rule.metrics.lift);
-        println!("  Conviction: {:.2}", rule.metrics.conviction);
+        if let Some(conviction) = rule.metrics.conviction {
+            println!("  Conviction: {:.2}", conviction);
+        }
         println!("  Quality Score: {:.3}", rule.quality_score());
         println!(
             "  Interpretation: When {} is purchased, {} appears {:.1}% of the time",
@@ -12,11 +12,12 @@
 //! ```
 
 use rust_rule_miner::{
-    data_loader::{ColumnMapping, DataLoader},
+    data_loader::{ColumnMapping, DataLoader, LoaderOptions},
     export::GrlExporter,
-    MiningAlgorithm, MiningConfig, RuleMiner,
+    Confidence, Lift, MiningAlgorithm, MiningConfig, RuleMiner, Support, WindowAggregator,
 };
 use std::fs;
+use std::time::Duration;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Buyer Stock Data Mining ===\n");
@@ -83,19 +84,18 @@ fn mine_by_location(csv_path: &str) -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Mine with low support (locations may have different products)
-    let config = MiningConfig {
-        min_support: 0.01,   // 1% - rare patterns ok
-        min_confidence: 0.5, // 50% confidence
-        min_lift: 1.2,       // 20% lift
-        algorithm: MiningAlgorithm::Apriori,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.01)?) // 1% - rare patterns ok
+        .min_confidence(Confidence::fraction(0.5)?) // 50% confidence
+        .min_lift(Lift::new(1.2)?) // 20% lift
+        .algorithm(MiningAlgorithm::Apriori)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(transactions)?;
 
     println!("\nMining rules...");
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!("✓ Found {} association rules", rules.len());
 
@@ -143,18 +143,17 @@ fn mine_sku_by_location(csv_path: &str) -> Result<(), Box<dyn std::error::Error>
     let transactions = DataLoader::from_csv(csv_path, mapping)?;
     println!("✓ Loaded {} SKU transactions", transactions.len());
 
-    let config = MiningConfig {
-        min_support: 0.02,
-        min_confidence: 0.6,
-        min_lift: 1.5,
-        algorithm: MiningAlgorithm::Apriori,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.02)?)
+        .min_confidence(Confidence::fraction(0.6)?)
+        .min_lift(Lift::new(1.5)?)
+        .algorithm(MiningAlgorithm::Apriori)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(transactions)?;
 
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
     println!("✓ Found {} SKU association rules", rules.len());
 
     // Show statistics
@@ -192,23 +191,12 @@ fn mine_product_categories(csv_path: &str) -> Result<(), Box<dyn std::error::Err
     // In production, you'd have a proper category column
 
     let mapping = ColumnMapping::simple(1, 3, 14);
-    let mut transactions = DataLoader::from_csv(csv_path, mapping)?;
-
-    // Extract first word from product name as simple categorization
-    for tx in &mut transactions {
-        tx.items = tx
-            .items
-            .iter()
-            .map(|product| {
-                // Get first word (simple category extraction)
-                product
-                    .split_whitespace()
-                    .next()
-                    .unwrap_or(product)
-                    .to_string()
-            })
-            .collect();
-    }
+    let options = LoaderOptions::default()
+        // Skip soft-deleted stock records (is_deleted, column 4)
+        .with_row_filter(|row| row.get(4).is_none_or(|deleted| deleted != "true"))
+        // First word of the product name as a simple category
+        .with_item_transform(|product| product.split_whitespace().next().map(str::to_string));
+    let (transactions, _) = DataLoader::from_csv_with_options(csv_path, mapping, options)?;
 
     println!(
         "✓ Loaded and categorized {} transactions",
@@ -222,18 +210,17 @@ fn mine_product_categories(csv_path: &str) -> Result<(), Box<dyn std::error::Err
         );
     }
 
-    let config = MiningConfig {
-        min_support: 0.05,
-        min_confidence: 0.6,
-        min_lift: 1.3,
-        algorithm: MiningAlgorithm::Apriori,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.05)?)
+        .min_confidence(Confidence::fraction(0.6)?)
+        .min_lift(Lift::new(1.3)?)
+        .algorithm(MiningAlgorithm::Apriori)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(transactions)?;
 
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
     println!("\n✓ Found {} category association rules", rules.len());
 
     // Show insights
@@ -282,18 +269,18 @@ fn mine_combined_patterns(csv_path: &str) -> Result<(), Box<dyn std::error::Erro
         }
     }
 
-    let config = MiningConfig {
-        min_support: 0.001,  // Very low - combined patterns are rare
-        min_confidence: 0.8, // But require high confidence
-        min_lift: 2.0,       // And strong correlation
-        algorithm: MiningAlgorithm::Apriori,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.001)?) // Very low - combined patterns are rare
+        .allow_singleton_support(true) // Small files: 0.1% may be one row
+        .min_confidence(Confidence::fraction(0.8)?) // But require high confidence
+        .min_lift(Lift::new(2.0)?) // And strong correlation
+        .algorithm(MiningAlgorithm::Apriori)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(transactions)?;
 
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
     println!("\n✓ Found {} combined-pattern rules", rules.len());
 
     if !rules.is_empty() {
@@ -345,35 +332,16 @@ fn mine_time_windowed_patterns(csv_path: &str) -> Result<(), Box<dyn std::error:
     println!("Strategy: Group products by location + 1-hour time windows");
     println!();
 
-    use std::collections::HashMap;
-
     // Load all data first with standard mapping
     let mapping = ColumnMapping::simple(1, 3, 14);
     let raw_transactions = DataLoader::from_csv(csv_path, mapping)?;
     println!("✓ Loaded {} raw product updates", raw_transactions.len());
 
-    // Aggregate by location + hour
-    let mut aggregated: HashMap<String, Vec<String>> = HashMap::new();
-
-    for tx in raw_transactions {
-        // Create key: location + hour (rounded down)
-        let hour = tx.timestamp.format("%Y-%m-%d %H:00").to_string();
-        let key = format!("{}_{}", tx.id, hour);
-
-        // Add all items to this aggregated transaction
-        aggregated.entry(key).or_default().extend(tx.items);
-    }
-
-    // Convert back to Transaction objects
-    use chrono::Utc;
-    let aggregated_transactions: Vec<rust_rule_miner::Transaction> = aggregated
-        .into_iter()
-        .enumerate()
-        .map(|(idx, (_key, items))| {
-            rust_rule_miner::Transaction::new(format!("agg_{}", idx), items, Utc::now())
-        })
-        .filter(|tx| tx.items.len() > 1) // Only keep transactions with 2+ items
-        .collect();
+    // Aggregate by location + hour; each basket is stamped with its window start
+    let aggregated_transactions = WindowAggregator::new(Duration::from_secs(3600))
+        .with_group_by_id(true)
+        .with_min_items(2) // Only keep transactions with 2+ items
+        .aggregate(raw_transactions);
 
     println!(
         "✓ Aggregated into {} multi-item transactions",
@@ -384,6 +352,7 @@ fn mine_time_windowed_patterns(csv_path: &str) -> Result<(), Box<dyn std::error:
     if let Some(tx) = aggregated_transactions.first() {
         println!("\nSample aggregated transaction:");
         println!("  ID: {}", tx.id);
+        println!("  Window start: {}", tx.timestamp);
         println!("  Items: {} products", tx.items.len());
         println!(
             "  First 5: {:?}",
@@ -392,19 +361,18 @@ fn mine_time_windowed_patterns(csv_path: &str) -> Result<(), Box<dyn std::error:
     }
 
     // Mine with lower support to find patterns
-    let config = MiningConfig {
-        min_support: 0.005,  // 0.5% - very low for rare patterns
-        min_confidence: 0.6, // 60% confidence
-        min_lift: 2.0,       // Strong correlation
-        algorithm: MiningAlgorithm::Apriori,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.005)?) // 0.5% - very low for rare patterns
+        .min_confidence(Confidence::fraction(0.6)?) // 60% confidence
+        .min_lift(Lift::new(2.0)?) // Strong correlation
+        .algorithm(MiningAlgorithm::Apriori)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(aggregated_transactions)?;
 
     println!("\nMining association rules...");
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!("✓ Found {} multi-item association rules", rules.len());
 
@@ -11,10 +11,10 @@
 use rust_rule_miner::{
     data_loader::{ColumnMapping, DataLoader},
     export::GrlExporter,
-    MiningAlgorithm, MiningConfig, RuleMiner,
+    Confidence, Lift, MiningAlgorithm, MiningConfig, RuleMiner, Support, WindowAggregator,
 };
-use std::collections::HashMap;
 use std::fs;
+use std::time::Duration;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Multi-Item Pattern Mining ===\n");
@@ -28,27 +28,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Aggregate by location + hour window
     println!("\nAggregating by location + 1-hour time windows...");
-    let mut aggregated: HashMap<String, Vec<String>> = HashMap::new();
-
-    for tx in raw_transactions {
-        // Create key: location_id + hour
-        let hour = tx.timestamp.format("%Y-%m-%d %H:00").to_string();
-        let key = format!("loc{}_{}h", tx.id, hour);
-
-        // Collect all products in this window
-        aggregated.entry(key).or_default().extend(tx.items);
-    }
-
-    // Convert to multi-item transactions
-    use chrono::Utc;
-    let mut multi_item_txs: Vec<rust_rule_miner::Transaction> = aggregated
-        .into_iter()
-        .enumerate()
-        .map(|(idx, (_key, items))| {
-            rust_rule_miner::Transaction::new(format!("agg_{}", idx), items, Utc::now())
-        })
-        .filter(|tx| tx.items.len() >= 2) // Only keep transactions with 2+ products
-        .collect();
+    // Each basket is stamped with its window start and holds distinct, sorted products
+    let multi_item_txs = WindowAggregator::new(Duration::from_secs(3600))
+        .with_group_by_id(true)
+        .with_min_items(2) // Only keep transactions with 2+ products
+        .aggregate(raw_transactions);
 
     println!("✓ Created {} multi-item transactions", multi_item_txs.len());
 
@@ -64,32 +48,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Deduplicate items in each transaction
-    for tx in multi_item_txs.iter_mut() {
-        let mut unique_items: Vec<String> = tx.items.clone();
-        unique_items.sort();
-        unique_items.dedup();
-        tx.items = unique_items;
-    }
-
-    println!("Deduplicated items in transactions");
-
     // Use all transactions for comprehensive mining
     println!("Using all {} transactions for mining", multi_item_txs.len());
 
     // Mine with very high-quality thresholds for best patterns
     println!("\nMining association rules with very high-quality thresholds...");
-    let config = MiningConfig {
-        min_support: 0.2,     // 20% - only very frequent patterns
-        min_confidence: 0.70, // 70% - high confidence rules
-        min_lift: 2.0,        // 2.0 - strong correlations only
-        algorithm: MiningAlgorithm::FPGrowth,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.2)?) // 20% - only very frequent patterns
+        .min_confidence(Confidence::fraction(0.70)?) // 70% - high confidence rules
+        .min_lift(Lift::new(2.0)?) // 2.0 - strong correlations only
+        .algorithm(MiningAlgorithm::FPGrowth)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(multi_item_txs)?;
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!("✓ Found {} association rules", rules.len());
 
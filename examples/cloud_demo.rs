@@ -85,13 +85,13 @@ let transactions = DataLoader::from_s3(
 // let transactions = DataLoader::from_s3(..., mapping).await?;
 
 // 2. Mine patterns
-let mut miner = RuleMiner::new(MiningConfig {{
-    min_support: 0.05,
-    min_confidence: 0.7,
-    ..Default::default()
-}});
+let config = MiningConfig::builder()
+    .min_support(Support::fraction(0.05)?)
+    .min_confidence(Confidence::fraction(0.7)?)
+    .build()?;
+let mut miner = RuleMiner::new(config);
 miner.add_transactions(transactions)?;
-let rules = miner.mine_association_rules()?;
+let rules = miner.mine()?.rules;
 
 // 3. Export to GRL
 let grl = GrlExporter::to_grl(&rules);
@@ -18,9 +18,11 @@
 use rust_rule_miner::{
     data_loader::{ColumnMapping, DataLoader},
     export::GrlExporter,
-    GrlConfig, MiningAlgorithm, MiningConfig, RuleMiner, RuleTemplate,
+    Confidence, GrlConfig, Lift, MiningAlgorithm, MiningConfig, RuleMiner, RuleTemplate, Support,
+    WindowAggregator,
 };
 use std::fs;
+use std::time::Duration;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Diverse Rule Template Showcase ===\n");
@@ -34,32 +36,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("✓ Loaded {} product updates", raw_transactions.len());
 
     // Aggregate by location + hour window
-    use chrono::Utc;
-    use std::collections::HashMap;
-
-    let mut aggregated: HashMap<String, Vec<String>> = HashMap::new();
-    for tx in raw_transactions {
-        let hour = tx.timestamp.format("%Y-%m-%d %H:00").to_string();
-        let key = format!("loc{}_{}h", tx.id, hour);
-        aggregated.entry(key).or_default().extend(tx.items);
-    }
-
-    let mut multi_item_txs: Vec<rust_rule_miner::Transaction> = aggregated
-        .into_iter()
-        .enumerate()
-        .map(|(idx, (_key, items))| {
-            rust_rule_miner::Transaction::new(format!("agg_{}", idx), items, Utc::now())
-        })
-        .filter(|tx| tx.items.len() >= 2)
-        .collect();
-
-    // Deduplicate items
-    for tx in multi_item_txs.iter_mut() {
-        let mut unique_items: Vec<String> = tx.items.clone();
-        unique_items.sort();
-        unique_items.dedup();
-        tx.items = unique_items;
-    }
+    let multi_item_txs = WindowAggregator::new(Duration::from_secs(3600))
+        .with_group_by_id(true)
+        .with_min_items(2)
+        .aggregate(raw_transactions);
 
     println!(
         "✓ Created {} multi-item transactions\n",
@@ -67,17 +47,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     // Mine association rules once
-    let config = MiningConfig {
-        min_support: 0.2,
-        min_confidence: 0.70,
-        min_lift: 2.0,
-        algorithm: MiningAlgorithm::FPGrowth,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.2)?)
+        .min_confidence(Confidence::fraction(0.70)?)
+        .min_lift(Lift::new(2.0)?)
+        .algorithm(MiningAlgorithm::FPGrowth)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(multi_item_txs)?;
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!("✓ Found {} association rules\n", rules.len());
     println!("Now exporting to 8 different rule template formats...\n");
@@ -3,7 +3,8 @@
 /// excelstream provides automatic streaming with constant memory usage
 /// (~3-35 MB) regardless of file size - perfect for large datasets.
 use rust_rule_miner::{
-    data_loader::DataLoader, export::GrlExporter, MiningAlgorithm, MiningConfig, RuleMiner,
+    data_loader::DataLoader, export::GrlExporter, Confidence, Lift, MiningAlgorithm, MiningConfig,
+    RuleMiner, Support,
 };
 use std::fs;
 use std::io::Write;
@@ -25,20 +26,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("{}", "-".repeat(60));
     let start = std::time::Instant::now();
 
-    let mut miner = RuleMiner::new(MiningConfig {
-        min_support: 0.01,
-        min_confidence: 0.6,
-        min_lift: 1.2,
-        max_time_gap: None,
-        algorithm: MiningAlgorithm::Apriori,
-    });
+    let mut miner = RuleMiner::new(
+        MiningConfig::builder()
+            .min_support(Support::fraction(0.01)?)
+            .min_confidence(Confidence::fraction(0.6)?)
+            .min_lift(Lift::new(1.2)?)
+            .algorithm(MiningAlgorithm::Apriori)
+            .build()?,
+    );
 
     // DataLoader::from_csv uses excelstream internally for streaming
-    // ColumnMapping specifies which columns to mine: transaction_id, items, timestamp
+    // from_csv_standard reads the transaction_id, items, timestamp layout;
+    // pass a ColumnMapping to from_csv to mine other columns
     // For multi-field data (combining multiple columns), see examples/04_load_from_excel_csv.rs
     println!("Loading transactions...");
-    let mapping = rust_rule_miner::data_loader::ColumnMapping::simple(0, 1, 2);
-    let transactions = DataLoader::from_csv(csv_path, mapping)?;
+    let transactions = DataLoader::from_csv_standard(csv_path)?;
     println!("Loaded {} transactions", transactions.len());
     println!("Memory during load: ~3-35 MB (constant!)");
     println!();
@@ -46,7 +48,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     miner.add_transactions(transactions)?;
 
     println!("Mining association rules...");
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
     let elapsed = start.elapsed();
     println!("Found {} rules in {:.2?}", rules.len(), elapsed);
     println!();
@@ -4,7 +4,9 @@ use rust_rule_engine::rete::{
     grl_loader::GrlReteLoader,
     propagation::IncrementalEngine,
 };
-use rust_rule_miner::{export::GrlExporter, MiningConfig, RuleMiner, Transaction};
+use rust_rule_miner::{
+    export::GrlExporter, Confidence, Lift, MiningConfig, RuleMiner, Support, Transaction,
+};
 use std::fs;
 
 // NOTE: This example shows RETE engine integration (high performance).
@@ -71,16 +73,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ),
     ];
 
-    let config = MiningConfig {
-        min_support: 0.3,
-        min_confidence: 0.7,
-        min_lift: 1.2,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.3)?)
+        .min_confidence(Confidence::fraction(0.7)?)
+        .min_lift(Lift::new(1.2)?)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(historical_transactions)?;
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!(
         "✓ Discovered {} rules from {} transactions",
@@ -2,7 +2,9 @@ use chrono::Utc;
 /// Quick performance test to verify benchmark numbers
 ///
 /// This example measures actual runtime and memory for different dataset sizes
-use rust_rule_miner::{MiningAlgorithm, MiningConfig, RuleMiner, Transaction};
+use rust_rule_miner::{
+    Confidence, Lift, MiningAlgorithm, MiningConfig, RuleMiner, Support, Transaction,
+};
 use std::time::Instant;
 
 fn generate_transactions(count: usize) -> Vec<Transaction> {
@@ -57,16 +59,31 @@ fn test_performance(size: usize, algorithm: MiningAlgorithm) {
 
     // Mining
     let mine_start = Instant::now();
-    let mut miner = RuleMiner::new(MiningConfig {
-        min_support: 0.05,
-        min_confidence: 0.6,
-        min_lift: 1.2,
-        max_time_gap: None,
-        algorithm,
-    });
+    let mut miner = RuleMiner::new(
+        MiningConfig::builder()
+            .min_support(Support::fraction(0.05).unwrap())
+            .min_confidence(Confidence::fraction(0.6).unwrap())
+            .min_lift(Lift::new(1.2).unwrap())
+            .algorithm(algorithm)
+            .build()
+            .unwrap(),
+    );
 
     miner.add_transactions(transactions).unwrap();
-    let rules = miner.mine_association_rules().unwrap();
+    let profile = miner.dataset_profile(3);
+    println!(
+        "Unique items: {}, items per transaction: {}-{} (avg {:.1})",
+        profile.unique_item_count, profile.min_items, profile.max_items, profile.avg_items
+    );
+    for (item, count, support) in &profile.top_items {
+        println!(
+            "  {}: {} transactions ({:.1}%)",
+            item,
+            count,
+            support * 100.0
+        );
+    }
+    let rules = miner.mine().unwrap().rules;
     let mine_time = mine_start.elapsed();
 
     println!("Mining time: {:?}", mine_time);
@@ -27,13 +27,13 @@
 //!    cargo run --example postgres_stream_mining --features "postgres,engine"
 //!    ```
 
-use chrono::{DateTime, Utc};
 use rust_rule_miner::{
+    data_loader::{DataLoader, PgMapping},
     engine::{facts_from_cart, MiningRuleEngine},
-    MiningConfig, RuleMiner, Transaction,
+    Confidence, Lift, MiningConfig, RuleMiner, Support,
 };
 use std::env;
-use tokio_postgres::{NoTls, Row};
+use tokio_postgres::NoTls;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -45,7 +45,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     });
 
     println!("📊 Connecting to PostgreSQL database...");
-    let (client, connection) = tokio_postgres::connect(&database_url, NoTls).await?;
+    let (mut client, connection) = tokio_postgres::connect(&database_url, NoTls).await?;
 
     // Spawn connection
     tokio::spawn(async move {
@@ -71,42 +71,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ORDER BY t.transaction_date
     "#;
 
-    let rows = client.query(query, &[]).await?;
-    println!("✓ Retrieved {} transactions", rows.len());
-
-    // Convert database rows to Transaction objects
-    let mut transactions = Vec::new();
-    for row in rows {
-        let transaction = row_to_transaction(&row)?;
+    // Map result columns to transactions, fetching through a cursor
+    let mapping =
+        PgMapping::new("transaction_id", "items", "transaction_date").with_user_id("customer_id");
+    let transactions = DataLoader::from_postgres(&mut client, query, mapping).await?;
+    println!("✓ Retrieved {} transactions", transactions.len());
+    for transaction in &transactions {
         println!(
             "  - {} ({} items): {:?}",
             transaction.id,
             transaction.items.len(),
             transaction.items
         );
-        transactions.push(transaction);
     }
     println!();
 
     // ========== STEP 2: Mine Association Rules ==========
     println!("STEP 2: Mining association rules from transactions...\n");
 
-    let config = MiningConfig {
-        min_support: 0.3,
-        min_confidence: 0.6,
-        min_lift: 1.2,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.3)?)
+        .min_confidence(Confidence::fraction(0.6)?)
+        .min_lift(Lift::new(1.2)?)
+        .build()?;
 
     println!("Configuration:");
-    println!("  - Min Support: {}", config.min_support);
-    println!("  - Min Confidence: {}", config.min_confidence);
-    println!("  - Min Lift: {}", config.min_lift);
+    println!("  - Min Support: {}", config.min_support());
+    println!("  - Min Confidence: {}", config.min_confidence());
+    println!("  - Min Lift: {}", config.min_lift());
     println!();
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(transactions)?;
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!("✓ Discovered {} association rules:", rules.len());
     for (i, rule) in rules.iter().enumerate() {
@@ -206,15 +203,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-/// Convert a PostgreSQL row to a Transaction
-fn row_to_transaction(row: &Row) -> Result<Transaction, Box<dyn std::error::Error>> {
-    let transaction_id: String = row.try_get("transaction_id")?;
-    let timestamp: DateTime<Utc> = row.try_get::<_, DateTime<Utc>>("transaction_date")?;
-    let items: Vec<String> = row.try_get("items")?;
-
-    Ok(Transaction::new(&transaction_id, items, timestamp))
-}
-
 /// Test recommendation for a given shopping cart
 fn test_recommendation(
     engine: &mut MiningRuleEngine,
@@ -11,12 +11,12 @@
 //! cargo run --example simple_sku_reorder
 //! ```
 
-use chrono::Timelike;
 use rust_rule_miner::{
     data_loader::{ColumnMapping, DataLoader},
-    MiningAlgorithm, MiningConfig, RuleMiner,
+    Confidence, ItemNormalizer, Lift, MiningAlgorithm, MiningConfig, Preprocessing, RuleMiner,
+    Support, WindowAggregator,
 };
-use std::collections::HashMap;
+use std::time::Duration;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n╔══════════════════════════════════════════════════════════════════╗");
@@ -47,42 +47,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("💡 Ý tưởng: SKU bán trong cùng khung giờ = bán cùng nhau");
     println!("   Khung giờ: 4 tiếng (00-04h, 04-08h, 08-12h, ...)\n");
 
-    let mut time_windows: HashMap<String, Vec<String>> = HashMap::new();
+    // Mỗi transaction mang thời điểm bắt đầu khung giờ (không phải Utc::now())
+    let mut transactions = WindowAggregator::new(Duration::from_secs(4 * 3600)).aggregate(raw_data);
 
-    for record in raw_data {
-        if record.items.is_empty() || record.items[0].trim().is_empty() {
-            continue;
-        }
-
-        let hour = record.timestamp.hour();
-        let block = (hour / 4) * 4;
-        let time_key = record
-            .timestamp
-            .format(&format!("%Y-%m-%d {:02}h", block))
-            .to_string();
-
-        for sku in record.items {
-            let sku = sku.trim().to_string();
-            if !sku.is_empty() {
-                time_windows.entry(time_key.clone()).or_default().push(sku);
-            }
-        }
-    }
-
-    println!("✓ Nhóm được: {} khung giờ có SKU bán\n", time_windows.len());
+    println!("✓ Nhóm được: {} khung giờ có SKU bán\n", transactions.len());
 
     // Tạo transactions
-    use chrono::Utc;
-    let transactions: Vec<rust_rule_miner::Transaction> = time_windows
-        .into_iter()
-        .map(|(time, skus)| {
-            let mut unique = skus;
-            unique.sort();
-            unique.dedup();
-            rust_rule_miner::Transaction::new(time, unique, Utc::now())
-        })
-        .filter(|tx| tx.items.len() >= 2 && tx.items.len() <= 30)
-        .collect();
+    transactions.retain(|tx| tx.items.len() >= 2 && tx.items.len() <= 30);
 
     println!(
         "✓ Tạo được: {} transactions (khung giờ có 2-30 SKUs)\n",
@@ -113,17 +84,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("   • Confidence >= 70% (độ chắc chắn >= 70%)");
     println!("   • Lift >= 2.0 (tương quan mạnh gấp 2 lần)\n");
 
-    let config = MiningConfig {
-        min_support: 0.20,
-        min_confidence: 0.70,
-        min_lift: 2.0,
-        algorithm: MiningAlgorithm::FPGrowth,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.20)?)
+        .min_confidence(Confidence::fraction(0.70)?)
+        .min_lift(Lift::new(2.0)?)
+        .algorithm(MiningAlgorithm::FPGrowth)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
+    // Bỏ khoảng trắng thừa và SKU trùng trong cùng khung giờ
+    miner.set_preprocessing(
+        Preprocessing::default()
+            .with_normalizer(ItemNormalizer::default().with_trim(true))
+            .with_dedup_items(true),
+    );
     miner.add_transactions(transactions)?;
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!("✓ Tìm được: {} patterns đủ điều kiện\n", rules.len());
 
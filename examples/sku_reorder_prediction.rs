@@ -13,14 +13,14 @@
 //! cargo run --example sku_reorder_prediction
 //! ```
 
-use chrono::Timelike;
 use rust_rule_miner::{
     data_loader::{ColumnMapping, DataLoader},
-    export::GrlExporter,
-    GrlConfig, MiningAlgorithm, MiningConfig, RuleMiner, RuleTemplate,
+    export::{CsvConfig, CsvExporter, GrlExporter},
+    Confidence, GrlConfig, Lift, MiningAlgorithm, MiningConfig, RuleMiner, RuleTemplate, Support,
+    WindowAggregator,
 };
-use std::collections::HashMap;
 use std::fs;
+use std::time::Duration;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== SKU Reorder Prediction System ===\n");
@@ -44,47 +44,23 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Aggregate by time windows (4-hour blocks) to see which SKUs sold together
     // Use 4-hour windows to reduce transaction size for faster mining
     println!("📊 Step 2: Aggregate SKUs theo khung giờ 4h");
-    let mut time_window_sales: HashMap<String, Vec<String>> = HashMap::new();
-
-    for tx in transactions {
-        // Group by 4-hour blocks (00-04, 04-08, 08-12, 12-16, 16-20, 20-24)
-        let hour = tx.timestamp.hour();
-        let block = (hour / 4) * 4;
-        let time_key = tx
-            .timestamp
-            .format(&format!("%Y-%m-%d {:02}:00", block))
-            .to_string();
-
-        for sku in tx.items {
-            let sku_trimmed = sku.trim().to_string();
-            if !sku_trimmed.is_empty() {
-                time_window_sales
-                    .entry(time_key.clone())
-                    .or_default()
-                    .push(sku_trimmed);
-            }
-        }
-    }
-
-    // Convert to transactions (each 4-hour window = 1 transaction)
-    use chrono::Utc;
-    let mut sku_transactions: Vec<rust_rule_miner::Transaction> = time_window_sales
-        .into_iter()
-        .map(|(time_window, skus)| {
-            rust_rule_miner::Transaction::new(format!("window_{}", time_window), skus, Utc::now())
-        })
-        .collect();
-
-    // Deduplicate SKUs within each window
-    for tx in sku_transactions.iter_mut() {
-        let mut unique_skus: Vec<String> = tx.items.clone();
-        unique_skus.sort();
-        unique_skus.dedup();
-        tx.items = unique_skus;
-    }
+    let transactions = transactions.into_iter().map(|mut tx| {
+        tx.items = tx
+            .items
+            .iter()
+            .map(|sku| sku.trim().to_string())
+            .filter(|sku| !sku.is_empty())
+            .collect();
+        tx
+    });
 
-    // Filter to only windows with 2+ SKUs and limit to max 50 SKUs per transaction
-    sku_transactions.retain(|tx| tx.items.len() >= 2 && tx.items.len() <= 50);
+    // Group by 4-hour blocks (00-04, 04-08, 08-12, 12-16, 16-20, 20-24); each
+    // window becomes one transaction stamped with the block start. Keep only
+    // windows with 2+ SKUs and at most 50 SKUs per transaction.
+    let sku_transactions = WindowAggregator::new(Duration::from_secs(4 * 3600))
+        .with_min_items(2)
+        .with_max_items(50)
+        .aggregate(transactions);
 
     println!(
         "✓ Created {} time-window transactions",
@@ -109,17 +85,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📊 Step 3: Mine SKU association patterns");
     println!("Tìm patterns: SKU nào bán → SKU nào cần đặt hàng\n");
 
-    let config = MiningConfig {
-        min_support: 0.15,    // 15% - SKU patterns phải xuất hiện >= 15% ngày
-        min_confidence: 0.65, // 65% - độ tin cậy cao
-        min_lift: 2.0,        // 2.0 - tương quan mạnh
-        algorithm: MiningAlgorithm::FPGrowth,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.15)?) // 15% - SKU patterns phải xuất hiện >= 15% ngày
+        .min_confidence(Confidence::fraction(0.65)?) // 65% - độ tin cậy cao
+        .min_lift(Lift::new(2.0)?) // 2.0 - tương quan mạnh
+        .algorithm(MiningAlgorithm::FPGrowth)
+        .build()?;
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(sku_transactions)?;
-    let rules = miner.mine_association_rules()?;
+    let rules = miner.mine()?.rules;
 
     println!("✓ Found {} SKU reorder patterns\n", rules.len());
 
@@ -234,8 +209,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Generate CSV summary for purchase managers
     println!("\n4. CSV Summary (for Excel/Spreadsheet)");
-    let csv_content = generate_csv_summary(&sorted_rules);
-    fs::write("/tmp/sku_reorder_summary.csv", csv_content)?;
+    let top_rules = &sorted_rules[..sorted_rules.len().min(50)];
+    CsvExporter::write_csv(
+        top_rules,
+        "/tmp/sku_reorder_summary.csv",
+        &CsvConfig::default(),
+    )?;
     println!("   ✓ /tmp/sku_reorder_summary.csv");
 
     // Statistics
@@ -293,34 +272,3 @@ fn truncate(s: &str, max_len: usize) -> String {
         s.chars().take(max_len).collect::<String>() + "..."
     }
 }
-
-fn generate_csv_summary(rules: &[rust_rule_miner::AssociationRule]) -> String {
-    let mut csv =
-        String::from("Priority,Trigger_SKUs,Reorder_SKUs,Confidence_%,Support_%,Lift,Score\n");
-
-    for rule in rules.iter().take(50) {
-        let trigger_skus = rule.antecedent.join("; ");
-        let reorder_skus = rule.consequent.join("; ");
-        let score = (rule.metrics.confidence * rule.metrics.lift * 100.0) as i32;
-        let priority = if score > 400 {
-            "HIGH"
-        } else if score > 250 {
-            "MEDIUM"
-        } else {
-            "LOW"
-        };
-
-        csv.push_str(&format!(
-            "\"{}\",\"{}\",\"{}\",{:.1},{:.1},{:.2},{}\n",
-            priority,
-            trigger_skus,
-            reorder_skus,
-            rule.metrics.confidence * 100.0,
-            rule.metrics.support * 100.0,
-            rule.metrics.lift,
-            score
-        ));
-    }
-
-    csv
-}
@@ -0,0 +1,67 @@
+//! Mining an SPMF benchmark file
+//!
+//! Loads a dataset in SPMF format (one transaction per line, items as
+//! space-separated integers) and prints the number of frequent itemsets per
+//! size, for comparison with published counts on the same file and support.
+//! Defaults to the synthetic fixture bundled under `tests/fixtures/spmf`;
+//! pass the path of a downloaded file such as `retail.dat`, `kosarak.dat` or
+//! `T10I4D100K.dat` to mine a full benchmark.
+//!
+//! Usage: cargo run --example spmf_benchmark -- [PATH] [MIN_SUPPORT]
+
+use rust_rule_miner::{data_loader::DataLoader, MiningAlgorithm, MiningConfig, RuleMiner, Support};
+use std::time::Instant;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let path = args
+        .next()
+        .unwrap_or_else(|| "tests/fixtures/spmf/synthetic_300.dat".to_string());
+    let min_support: f64 = match args.next() {
+        Some(value) => value.parse()?,
+        None => 0.05,
+    };
+
+    let start = Instant::now();
+    let transactions = DataLoader::from_spmf(&path)?;
+    println!(
+        "Loaded {} transactions from {} in {:?}",
+        transactions.len(),
+        path,
+        start.elapsed()
+    );
+    println!("Minimum support: {:.2}%\n", min_support * 100.0);
+
+    for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(min_support)?)
+            .algorithm(algorithm)
+            .build()?;
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions.clone())?;
+
+        let start = Instant::now();
+        let itemsets = miner.mine_frequent_itemsets()?;
+        let elapsed = start.elapsed();
+
+        let mut per_size: Vec<usize> = Vec::new();
+        for itemset in &itemsets {
+            let size = itemset.items.len();
+            per_size.resize(per_size.len().max(size), 0);
+            per_size[size - 1] += 1;
+        }
+
+        println!(
+            "{:?}: {} frequent itemsets in {:?}",
+            algorithm,
+            itemsets.len(),
+            elapsed
+        );
+        for (size, count) in per_size.iter().enumerate() {
+            println!("  size {}: {}", size + 1, count);
+        }
+        println!();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,438 @@
+//! Time-window aggregation of transactions
+//!
+//! Sources such as stock updates record one item per row. Mining needs
+//! baskets, so `WindowAggregator` merges rows that fall in the same time
+//! bucket (and optionally share an id, user or custom key, see `GroupBy`) into
+//! one transaction.
+//!
+//! Merged transactions are stamped with the start of their bucket (or their
+//! earliest row), never the time of aggregation, so windowed data keeps its
+//! temporal order for later time-based analysis.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::analysis::{timestamp_audit, AuditPolicy};
+use crate::errors::Result;
+use crate::transaction::Transaction;
+
+/// Metadata key holding per-item source timestamps (see `with_keep_item_times`)
+pub const ITEM_TIMES_KEY: &str = "item_times";
+
+/// Item -> source timestamps of one window
+type WindowItems = BTreeMap<String, Vec<DateTime<Utc>>>;
+
+/// Key splitting a time window into separate transactions
+#[derive(Debug, Clone, Copy)]
+pub enum GroupBy {
+    /// One transaction per transaction id (e.g. a location) per window
+    TransactionId,
+    /// One transaction per user per window; rows without a user are grouped
+    /// by transaction id
+    UserId,
+    /// One transaction per key returned for each row
+    Custom(fn(&Transaction) -> String),
+}
+
+impl GroupBy {
+    fn key(&self, tx: &Transaction) -> String {
+        match self {
+            GroupBy::TransactionId => tx.id.clone(),
+            GroupBy::UserId => tx.user_id.clone().unwrap_or_else(|| tx.id.clone()),
+            GroupBy::Custom(key) => key(tx),
+        }
+    }
+}
+
+/// Merges transactions into fixed, epoch-aligned time windows
+///
+/// Windows are aligned to the Unix epoch in UTC, so 1-hour windows start on
+/// the hour and 4-hour windows at 00:00, 04:00, 08:00, ...
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::{Transaction, WindowAggregator};
+/// use chrono::{TimeZone, Utc};
+/// use std::time::Duration;
+///
+/// let at = |minute| Utc.with_ymd_and_hms(2024, 5, 1, 9, minute, 0).unwrap();
+/// let rows = vec![
+///     Transaction::new("store1", vec!["Milk".to_string()], at(5)),
+///     Transaction::new("store1", vec!["Bread".to_string()], at(40)),
+///     Transaction::new("store2", vec!["Milk".to_string()], at(10)),
+/// ];
+///
+/// let baskets = WindowAggregator::new(Duration::from_secs(3600))
+///     .with_group_by_id(true)
+///     .aggregate(rows);
+///
+/// assert_eq!(baskets.len(), 2);
+/// assert_eq!(baskets[0].items, vec!["Bread", "Milk"]);
+/// assert_eq!(baskets[0].timestamp, at(0)); // window start
+/// ```
+#[derive(Debug, Clone)]
+pub struct WindowAggregator {
+    window: Duration,
+    group_by: Option<GroupBy>,
+    min_items: usize,
+    max_items: Option<usize>,
+    keep_item_times: bool,
+    earliest_timestamps: bool,
+    audit: AuditPolicy,
+}
+
+impl WindowAggregator {
+    /// Aggregate into windows of the given width (a zero width is treated as 1ms)
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            group_by: None,
+            min_items: 1,
+            max_items: None,
+            keep_item_times: false,
+            earliest_timestamps: false,
+            audit: AuditPolicy::Off,
+        }
+    }
+
+    /// Also split windows by transaction id (e.g. one basket per location per hour)
+    pub fn with_group_by_id(mut self, group_by_id: bool) -> Self {
+        self.group_by = group_by_id.then_some(GroupBy::TransactionId);
+        self
+    }
+
+    /// Also split windows by the given key
+    pub fn with_group_by(mut self, group_by: GroupBy) -> Self {
+        self.group_by = Some(group_by);
+        self
+    }
+
+    /// Stamp merged transactions with their earliest row instead of the
+    /// window start
+    pub fn with_earliest_timestamps(mut self, earliest_timestamps: bool) -> Self {
+        self.earliest_timestamps = earliest_timestamps;
+        self
+    }
+
+    /// Drop merged transactions with fewer distinct items
+    pub fn with_min_items(mut self, min_items: usize) -> Self {
+        self.min_items = min_items;
+        self
+    }
+
+    /// Drop merged transactions with more distinct items
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Record each item's source timestamps in the `item_times` metadata
+    ///
+    /// The value maps item to an array of RFC 3339 timestamps, oldest first.
+    pub fn with_keep_item_times(mut self, keep_item_times: bool) -> Self {
+        self.keep_item_times = keep_item_times;
+        self
+    }
+
+    /// Audit row timestamps in `try_aggregate` before windowing them
+    ///
+    /// Windows built from `Utc::now()` fallback timestamps or future dates
+    /// are meaningless; see `analysis::timestamp_audit`. Off by default.
+    pub fn with_timestamp_audit(mut self, policy: AuditPolicy) -> Self {
+        self.audit = policy;
+        self
+    }
+
+    /// Start of the window containing `timestamp`
+    pub fn window_start(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let width = self.width_ms();
+        let start = timestamp.timestamp_millis().div_euclid(width) * width;
+        DateTime::from_timestamp_millis(start).unwrap_or(timestamp)
+    }
+
+    /// Audit the rows with the `with_timestamp_audit` policy, then merge
+    /// them as `aggregate` does
+    ///
+    /// Fails with `MiningError::InvalidTransaction` when the policy is
+    /// `Refuse` and the audit finds suspicious timestamps.
+    pub fn try_aggregate<I>(&self, transactions: I) -> Result<Vec<Transaction>>
+    where
+        I: IntoIterator<Item = Transaction>,
+    {
+        let transactions: Vec<Transaction> = transactions.into_iter().collect();
+        if self.audit != AuditPolicy::Off {
+            timestamp_audit(&transactions).enforce(self.audit)?;
+        }
+        Ok(self.aggregate(transactions))
+    }
+
+    /// Merge transactions, returned in (group key, window start) order
+    ///
+    /// Items are deduplicated and sorted. Merged ids are `<key>@<start>` when
+    /// grouping, otherwise the window start in RFC 3339. Never audits; see
+    /// `try_aggregate`.
+    pub fn aggregate<I>(&self, transactions: I) -> Vec<Transaction>
+    where
+        I: IntoIterator<Item = Transaction>,
+    {
+        let mut windows: BTreeMap<(String, DateTime<Utc>), WindowItems> = BTreeMap::new();
+        for tx in transactions {
+            let group = self
+                .group_by
+                .as_ref()
+                .map_or_else(String::new, |group_by| group_by.key(&tx));
+            let items = windows
+                .entry((group, self.window_start(tx.timestamp)))
+                .or_default();
+            for item in tx.items {
+                items.entry(item).or_default().push(tx.timestamp);
+            }
+        }
+
+        windows
+            .into_iter()
+            .filter(|(_, items)| {
+                items.len() >= self.min_items && self.max_items.is_none_or(|max| items.len() <= max)
+            })
+            .map(|((group, start), items)| {
+                let id = if self.group_by.is_some() {
+                    format!("{}@{}", group, start.to_rfc3339())
+                } else {
+                    start.to_rfc3339()
+                };
+                let timestamp = if self.earliest_timestamps {
+                    items.values().flatten().min().copied().unwrap_or(start)
+                } else {
+                    start
+                };
+                let mut metadata = HashMap::new();
+                if self.keep_item_times {
+                    metadata.insert(ITEM_TIMES_KEY.to_string(), item_times(&items));
+                }
+                Transaction::new(id, items.into_keys().collect(), timestamp).with_metadata(metadata)
+            })
+            .collect()
+    }
+
+    fn width_ms(&self) -> i64 {
+        (self.window.as_millis() as i64).max(1)
+    }
+}
+
+fn item_times(items: &WindowItems) -> Value {
+    items
+        .iter()
+        .map(|(item, times)| {
+            let mut times = times.clone();
+            times.sort();
+            let times = times
+                .iter()
+                .map(|t| Value::String(t.to_rfc3339()))
+                .collect();
+            (item.clone(), Value::Array(times))
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn row(id: &str, item: &str, hour: u32, minute: u32) -> Transaction {
+        Transaction::new(
+            id,
+            vec![item.to_string()],
+            Utc.with_ymd_and_hms(2024, 5, 1, hour, minute, 0).unwrap(),
+        )
+    }
+
+    fn rows() -> Vec<Transaction> {
+        vec![
+            row("loc1", "Milk", 9, 5),
+            row("loc1", "Bread", 9, 40),
+            row("loc1", "Milk", 9, 55),
+            row("loc2", "Eggs", 9, 15),
+            row("loc1", "Eggs", 10, 1),
+            row("loc2", "Milk", 11, 59),
+        ]
+    }
+
+    #[test]
+    fn test_timestamps_are_window_starts() {
+        let hourly = WindowAggregator::new(Duration::from_secs(3600)).aggregate(rows());
+        let starts: Vec<u32> = hourly
+            .iter()
+            .map(|tx| tx.timestamp.format("%H%M").to_string().parse().unwrap())
+            .collect();
+        assert_eq!(starts, vec![900, 1000, 1100]);
+        assert_eq!(hourly[0].items, vec!["Bread", "Eggs", "Milk"]);
+        assert_eq!(hourly[0].id, "2024-05-01T09:00:00+00:00");
+
+        // 4-hour windows start at 08:00, not at the first row or at "now"
+        let blocks = WindowAggregator::new(Duration::from_secs(4 * 3600)).aggregate(rows());
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(
+            blocks[0].timestamp,
+            Utc.with_ymd_and_hms(2024, 5, 1, 8, 0, 0).unwrap()
+        );
+        assert!(blocks[0].timestamp < Utc::now() - chrono::Duration::days(1));
+    }
+
+    #[test]
+    fn test_group_by_id_and_size_filters() {
+        let aggregator = WindowAggregator::new(Duration::from_secs(3600)).with_group_by_id(true);
+        let ids: Vec<String> = aggregator
+            .aggregate(rows())
+            .into_iter()
+            .map(|tx| tx.id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "loc1@2024-05-01T09:00:00+00:00",
+                "loc1@2024-05-01T10:00:00+00:00",
+                "loc2@2024-05-01T09:00:00+00:00",
+                "loc2@2024-05-01T11:00:00+00:00",
+            ]
+        );
+
+        let baskets = aggregator.with_min_items(2).aggregate(rows());
+        assert_eq!(baskets.len(), 1);
+        assert_eq!(baskets[0].items, vec!["Bread", "Milk"]);
+
+        let capped = WindowAggregator::new(Duration::from_secs(3600))
+            .with_max_items(2)
+            .aggregate(rows());
+        assert_eq!(capped.len(), 2);
+    }
+
+    #[test]
+    fn test_keep_item_times() {
+        let baskets = WindowAggregator::new(Duration::from_secs(3600))
+            .with_group_by_id(true)
+            .with_keep_item_times(true)
+            .aggregate(rows());
+
+        let times = &baskets[0].metadata[ITEM_TIMES_KEY];
+        assert_eq!(
+            times["Milk"],
+            serde_json::json!(["2024-05-01T09:05:00+00:00", "2024-05-01T09:55:00+00:00"])
+        );
+        assert_eq!(times["Bread"][0], "2024-05-01T09:40:00+00:00");
+
+        let plain = WindowAggregator::new(Duration::from_secs(3600)).aggregate(rows());
+        assert!(plain[0].metadata.is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_by_window() {
+        let at = |hour, minute, second| {
+            Utc.with_ymd_and_hms(2024, 5, 1, hour, minute, second)
+                .unwrap()
+        };
+        let buy = |user: &str, item: &str, time| {
+            Transaction::with_user(
+                format!("{}-{}", user, item),
+                vec![item.to_string()],
+                time,
+                user.to_string(),
+            )
+        };
+        let rows = vec![
+            buy("alice", "Milk", at(9, 20, 0)),
+            buy("alice", "Bread", at(9, 59, 59)),
+            buy("alice", "Milk", at(9, 10, 0)),
+            // Next window starts exactly on the hour
+            buy("alice", "Eggs", at(10, 0, 0)),
+            buy("bob", "Milk", at(9, 30, 0)),
+        ];
+
+        let baskets = crate::transaction::aggregate_by_window(
+            rows.clone(),
+            Duration::from_secs(3600),
+            GroupBy::UserId,
+        );
+        let summary: Vec<_> = baskets
+            .iter()
+            .map(|tx| (tx.id.as_str(), tx.items.clone(), tx.timestamp))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "alice@2024-05-01T09:00:00+00:00",
+                    vec!["Bread".to_string(), "Milk".to_string()],
+                    at(9, 10, 0)
+                ),
+                (
+                    "alice@2024-05-01T10:00:00+00:00",
+                    vec!["Eggs".to_string()],
+                    at(10, 0, 0)
+                ),
+                (
+                    "bob@2024-05-01T09:00:00+00:00",
+                    vec!["Milk".to_string()],
+                    at(9, 30, 0)
+                ),
+            ]
+        );
+
+        fn item_initial(tx: &Transaction) -> String {
+            tx.items[0][..1].to_string()
+        }
+        let by_initial = WindowAggregator::new(Duration::from_secs(2 * 3600))
+            .with_group_by(GroupBy::Custom(item_initial))
+            .with_earliest_timestamps(true)
+            .with_min_items(1)
+            .aggregate(rows);
+        let ids: Vec<_> = by_initial.iter().map(|tx| tx.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "B@2024-05-01T08:00:00+00:00",
+                "E@2024-05-01T10:00:00+00:00",
+                "M@2024-05-01T08:00:00+00:00"
+            ]
+        );
+        assert_eq!(by_initial[2].timestamp, at(9, 10, 0));
+    }
+
+    #[test]
+    fn test_timestamp_audit_policy() {
+        // Every row stamped at load time: the Utc::now() fallback fingerprint
+        let loaded = Utc::now();
+        let stamped: Vec<Transaction> = (0..20)
+            .map(|i| Transaction::new(format!("loc{}", i % 4), vec!["Milk".to_string()], loaded))
+            .collect();
+        let aggregator = WindowAggregator::new(Duration::from_secs(3600)).with_group_by_id(true);
+
+        let refused = aggregator
+            .clone()
+            .with_timestamp_audit(AuditPolicy::Refuse)
+            .try_aggregate(stamped.clone());
+        assert!(matches!(
+            refused,
+            Err(crate::errors::MiningError::InvalidTransaction(_))
+        ));
+
+        let warned = aggregator
+            .clone()
+            .with_timestamp_audit(AuditPolicy::Warn)
+            .try_aggregate(stamped.clone())
+            .unwrap();
+        assert_eq!(warned.len(), 4);
+        assert_eq!(aggregator.try_aggregate(stamped.clone()).unwrap().len(), 4);
+
+        // Spread-out rows pass
+        let spread = aggregator
+            .with_timestamp_audit(AuditPolicy::Refuse)
+            .try_aggregate(rows())
+            .unwrap();
+        assert_eq!(spread.len(), 4);
+    }
+}
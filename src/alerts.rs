@@ -0,0 +1,364 @@
+//! Alerts on business thresholds in freshly mined rules
+//!
+//! `evaluate()` compares a new rule set with a baseline (usually the rules
+//! deployed yesterday) and returns an `AlertEvent` for each `AlertRule` that
+//! fires: a watched rule crossing a metric threshold, appearing, or
+//! disappearing. Events serialize to JSON for webhooks; delivering them is
+//! left to the pipeline.
+//!
+//! Alert rules are read from a JSON array:
+//!
+//! ```json
+//! [
+//!   {"name": "fraud-confidence", "tag": "fraud:",
+//!    "condition": {"above": {"metric": "confidence", "value": 0.9}}},
+//!   {"name": "new-charger-rule", "item": "Charger", "condition": "appeared"},
+//!   {"name": "lost-mouse-rule", "item": "Mouse", "condition": "disappeared"}
+//! ]
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::deployment::{RuleDiff, RuleKey};
+use crate::errors::Result;
+use crate::types::AssociationRule;
+
+/// Metric an alert threshold applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertMetric {
+    Support,
+    Confidence,
+    Lift,
+}
+
+impl AlertMetric {
+    fn of(self, rule: &AssociationRule) -> f64 {
+        match self {
+            AlertMetric::Support => rule.metrics.support,
+            AlertMetric::Confidence => rule.metrics.confidence,
+            AlertMetric::Lift => rule.metrics.lift,
+        }
+    }
+}
+
+/// When an alert fires
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertCondition {
+    /// The metric rose above the value: true now, false in the baseline or
+    /// the rule is new
+    Above { metric: AlertMetric, value: f64 },
+    /// The metric fell below the value: true now, false in the baseline or
+    /// the rule is new
+    Below { metric: AlertMetric, value: f64 },
+    /// The rule is not in the baseline
+    Appeared,
+    /// The baseline rule is no longer mined
+    Disappeared,
+}
+
+/// A named condition on the rules matching an item predicate
+///
+/// With neither `item` nor `tag` set, every rule is watched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRule {
+    pub name: String,
+    /// Item that must appear on either side of the rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+    /// Prefix some item of the rule must start with, e.g. `"fraud:"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    pub condition: AlertCondition,
+}
+
+impl AlertRule {
+    /// Alert on every rule meeting `condition`
+    pub fn new(name: impl Into<String>, condition: AlertCondition) -> Self {
+        Self {
+            name: name.into(),
+            item: None,
+            tag: None,
+            condition,
+        }
+    }
+
+    /// Only watch rules containing `item`
+    pub fn with_item(mut self, item: impl Into<String>) -> Self {
+        self.item = Some(item.into());
+        self
+    }
+
+    /// Only watch rules with an item starting with `tag`
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Whether the rule is watched by this alert
+    pub fn matches(&self, rule: &AssociationRule) -> bool {
+        let mut items = rule.antecedent.iter().chain(&rule.consequent);
+        let has_item = self
+            .item
+            .as_ref()
+            .is_none_or(|item| items.clone().any(|i| i == item));
+        let has_tag = self
+            .tag
+            .as_ref()
+            .is_none_or(|tag| items.any(|i| i.starts_with(tag.as_str())));
+        has_item && has_tag
+    }
+}
+
+/// A fired alert
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertEvent {
+    /// Name of the `AlertRule` that fired
+    pub alert: String,
+    pub rule: RuleKey,
+    pub condition: AlertCondition,
+    /// Baseline value of the alert's metric (confidence for appearance
+    /// conditions), if the rule was in the baseline
+    pub before: Option<f64>,
+    /// New value, if the rule is still mined
+    pub after: Option<f64>,
+}
+
+impl fmt::Display for AlertEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: Option<f64>| v.map_or_else(|| "-".to_string(), |v| format!("{:.3}", v));
+        let what = match self.condition {
+            AlertCondition::Above { metric, value } => {
+                format!("{:?} above {}", metric, value).to_lowercase()
+            }
+            AlertCondition::Below { metric, value } => {
+                format!("{:?} below {}", metric, value).to_lowercase()
+            }
+            AlertCondition::Appeared => "appeared".to_string(),
+            AlertCondition::Disappeared => "disappeared".to_string(),
+        };
+        write!(
+            f,
+            "[{}] {} {} ({} -> {})",
+            self.alert,
+            self.rule,
+            what,
+            show(self.before),
+            show(self.after)
+        )
+    }
+}
+
+/// Read alert rules from a JSON array
+pub fn load_rules(path: impl AsRef<Path>) -> Result<Vec<AlertRule>> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+/// Alerts fired by `new_rules` relative to `baseline`
+///
+/// Events are grouped by alert rule, in `alert_rules` order, then by rule key.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::alerts::{evaluate, AlertCondition, AlertMetric, AlertRule};
+/// use rust_rule_miner::{AssociationRule, PatternMetrics};
+///
+/// let rule = |confidence| AssociationRule {
+///     antecedent: vec!["Laptop".to_string()],
+///     consequent: vec!["Mouse".to_string()],
+///     metrics: PatternMetrics::basic(confidence, 0.3, 1.5),
+/// };
+/// let alert = AlertRule::new(
+///     "mouse-confidence",
+///     AlertCondition::Above { metric: AlertMetric::Confidence, value: 0.9 },
+/// )
+/// .with_item("Mouse");
+///
+/// let events = evaluate(&[rule(0.95)], &[rule(0.8)], &[alert]);
+/// assert_eq!(events.len(), 1);
+/// assert_eq!(events[0].before, Some(0.8));
+/// ```
+pub fn evaluate(
+    new_rules: &[AssociationRule],
+    baseline: &[AssociationRule],
+    alert_rules: &[AlertRule],
+) -> Vec<AlertEvent> {
+    let diff = RuleDiff::between(baseline, new_rules);
+    let baseline_by_key: HashMap<RuleKey, &AssociationRule> =
+        baseline.iter().map(|r| (RuleKey::of(r), r)).collect();
+
+    let mut events = Vec::new();
+    for alert in alert_rules {
+        let event = |rule: &AssociationRule, before: Option<f64>, after: Option<f64>| AlertEvent {
+            alert: alert.name.clone(),
+            rule: RuleKey::of(rule),
+            condition: alert.condition,
+            before,
+            after,
+        };
+        let start = events.len();
+
+        match alert.condition {
+            AlertCondition::Above {
+                metric,
+                value: threshold,
+            }
+            | AlertCondition::Below {
+                metric,
+                value: threshold,
+            } => {
+                let is_above = matches!(alert.condition, AlertCondition::Above { .. });
+                let holds = |value: f64| {
+                    if is_above {
+                        value > threshold
+                    } else {
+                        value < threshold
+                    }
+                };
+                for rule in new_rules.iter().filter(|r| alert.matches(r)) {
+                    let after = metric.of(rule);
+                    let before = baseline_by_key
+                        .get(&RuleKey::of(rule))
+                        .map(|r| metric.of(r));
+                    if holds(after) && !before.is_some_and(holds) {
+                        events.push(event(rule, before, Some(after)));
+                    }
+                }
+            }
+            AlertCondition::Appeared => {
+                for rule in diff.added.iter().filter(|r| alert.matches(r)) {
+                    events.push(event(rule, None, Some(rule.metrics.confidence)));
+                }
+            }
+            AlertCondition::Disappeared => {
+                for rule in diff.removed.iter().filter(|r| alert.matches(r)) {
+                    events.push(event(rule, Some(rule.metrics.confidence), None));
+                }
+            }
+        }
+
+        events[start..].sort_by(|a, b| a.rule.cmp(&b.rule));
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+
+    #[test]
+    fn test_threshold_alert_fires_on_crossing_only() {
+        let baseline = vec![
+            test_rule(&["fraud:NewCard"], &["fraud:HighValue"], 0.85, 1.5),
+            test_rule(&["fraud:VPN"], &["fraud:Chargeback"], 0.95, 1.5),
+            test_rule(&["Laptop"], &["Mouse"], 0.7, 1.5),
+        ];
+        let new_rules = vec![
+            test_rule(&["fraud:NewCard"], &["fraud:HighValue"], 0.92, 1.5),
+            // Already above the threshold yesterday
+            test_rule(&["fraud:VPN"], &["fraud:Chargeback"], 0.97, 1.5),
+            // Not tagged
+            test_rule(&["Laptop"], &["Mouse"], 0.99, 1.5),
+            test_rule(&["fraud:Proxy"], &["fraud:HighValue"], 0.91, 1.5),
+        ];
+        let alerts = [AlertRule::new(
+            "fraud-confidence",
+            AlertCondition::Above {
+                metric: AlertMetric::Confidence,
+                value: 0.9,
+            },
+        )
+        .with_tag("fraud:")];
+
+        let events = evaluate(&new_rules, &baseline, &alerts);
+        let fired: Vec<_> = events.iter().map(|e| e.rule.to_string()).collect();
+        assert_eq!(
+            fired,
+            [
+                "fraud:NewCard => fraud:HighValue",
+                "fraud:Proxy => fraud:HighValue"
+            ]
+        );
+        assert_eq!(events[0].before, Some(0.85));
+        assert_eq!(events[0].after, Some(0.92));
+        assert_eq!(events[1].before, None);
+
+        let below = [AlertRule::new(
+            "weak-mouse",
+            AlertCondition::Below {
+                metric: AlertMetric::Confidence,
+                value: 0.65,
+            },
+        )
+        .with_item("Mouse")];
+        let events = evaluate(
+            &[test_rule(&["Laptop"], &["Mouse"], 0.6, 1.5)],
+            &baseline,
+            &below,
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].before, Some(0.7));
+        assert!(evaluate(
+            &[test_rule(&["Laptop"], &["Mouse"], 0.5, 1.5)],
+            &[test_rule(&["Laptop"], &["Mouse"], 0.6, 1.5)],
+            &below
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_appearance_and_disappearance_alerts() {
+        let baseline = vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.8, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.7, 1.5),
+        ];
+        let new_rules = vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.82, 1.5),
+            test_rule(&["Phone"], &["Charger"], 0.75, 1.5),
+        ];
+        let alerts = [
+            AlertRule::new("new-charger", AlertCondition::Appeared).with_item("Charger"),
+            AlertRule::new("new-mouse", AlertCondition::Appeared).with_item("Mouse"),
+            AlertRule::new("lost", AlertCondition::Disappeared),
+        ];
+
+        let events = evaluate(&new_rules, &baseline, &alerts);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].alert, "new-charger");
+        assert_eq!(events[0].rule.to_string(), "Phone => Charger");
+        assert_eq!((events[0].before, events[0].after), (None, Some(0.75)));
+        assert_eq!(events[1].alert, "lost");
+        assert_eq!(events[1].rule.to_string(), "Phone => Case");
+        assert_eq!((events[1].before, events[1].after), (Some(0.7), None));
+    }
+
+    #[test]
+    fn test_alert_rules_json_round_trip() {
+        let json = r#"[
+            {"name": "fraud", "tag": "fraud:", "condition": {"above": {"metric": "lift", "value": 3.0}}},
+            {"name": "lost-mouse", "item": "Mouse", "condition": "disappeared"}
+        ]"#;
+        let rules: Vec<AlertRule> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            rules[0].condition,
+            AlertCondition::Above {
+                metric: AlertMetric::Lift,
+                value: 3.0
+            }
+        );
+        assert_eq!(rules[1].item.as_deref(), Some("Mouse"));
+
+        let written = serde_json::to_string(&rules).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<AlertRule>>(&written).unwrap(),
+            rules
+        );
+    }
+}
@@ -0,0 +1,668 @@
+//! Data integrity checks for loaded transactions
+//!
+//! Time-based features assume sane timestamps. `timestamp_audit` looks for
+//! the fingerprints of bad timestamp data:
+//!
+//! - many rows in the same instant, as produced by the loader's `Utc::now()`
+//!   fallback for unparseable timestamps
+//! - rows dated in the future
+//! - ids whose order disagrees with their timestamps
+//!
+//! Time-based APIs run the audit and act on it through `AuditPolicy`: see
+//! `WindowAggregator::with_timestamp_audit` and
+//! `IncrementalMiner::with_timestamp_audit`.
+//!
+//! `DatasetQuality::assess` answers the broader question of whether a
+//! dataset is worth mining at all, folding the timestamp audit into a single
+//! score alongside basket shape and duplication.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::errors::{MiningError, Result};
+use crate::transaction::Transaction;
+
+/// What to do when an audit finds suspicious timestamps
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuditPolicy {
+    /// Skip the audit
+    Off,
+
+    /// Log a warning per finding and continue
+    #[default]
+    Warn,
+
+    /// Fail with `MiningError::InvalidTransaction`
+    Refuse,
+}
+
+/// Thresholds for `timestamp_audit_with`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimestampAuditConfig {
+    /// Timestamps within the same window of this width count as identical
+    pub resolution: Duration,
+
+    /// Share of rows in one window above which the fallback is suspected
+    pub duplicate_threshold: f64,
+
+    /// Datasets smaller than this are never flagged for duplicates
+    pub min_rows: usize,
+
+    /// How far past `now` a timestamp may be before it counts as future
+    pub future_tolerance: Duration,
+
+    /// Reference time for future checks (None = `Utc::now()`)
+    pub now: Option<DateTime<Utc>>,
+}
+
+impl Default for TimestampAuditConfig {
+    fn default() -> Self {
+        Self {
+            resolution: Duration::from_secs(1),
+            duplicate_threshold: 0.2,
+            min_rows: 10,
+            future_tolerance: Duration::from_secs(300),
+            now: None,
+        }
+    }
+}
+
+/// Findings of a timestamp audit
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimestampAudit {
+    /// Transactions audited
+    pub total: usize,
+
+    /// Start of the most crowded timestamp window and its row count
+    pub densest_window: Option<(DateTime<Utc>, usize)>,
+
+    /// Share of rows in the most crowded window (0.0 - 1.0)
+    pub duplicate_fraction: f64,
+
+    /// Whether the crowding matches the `Utc::now()` fallback fingerprint
+    pub fallback_suspected: bool,
+
+    /// Rows dated after the reference time
+    pub future_timestamps: usize,
+
+    /// Adjacent rows, in id order, whose timestamps go backwards
+    ///
+    /// Ids are ordered by their numeric suffix when every id has one
+    /// (`tx2` before `tx10`), otherwise lexicographically.
+    pub out_of_order: usize,
+
+    /// Earliest timestamp
+    pub earliest: Option<DateTime<Utc>>,
+
+    /// Latest timestamp
+    pub latest: Option<DateTime<Utc>>,
+}
+
+impl TimestampAudit {
+    /// Time between the earliest and latest timestamp
+    pub fn span(&self) -> chrono::Duration {
+        match (self.earliest, self.latest) {
+            (Some(earliest), Some(latest)) => latest - earliest,
+            _ => chrono::Duration::zero(),
+        }
+    }
+
+    /// Whether the data looks synthetic or corrupted
+    ///
+    /// Out-of-order ids alone are not suspicious, since many id schemes are
+    /// not chronological; they are reported for information only.
+    pub fn is_suspicious(&self) -> bool {
+        self.fallback_suspected || self.future_timestamps > 0
+    }
+
+    /// One human-readable line per suspicious finding
+    pub fn findings(&self) -> Vec<String> {
+        let mut findings = Vec::new();
+        if self.fallback_suspected {
+            if let Some((window, count)) = self.densest_window {
+                findings.push(format!(
+                    "{} of {} rows ({:.1}%) share the timestamp {}, which looks like a \
+                     load-time fallback for unparseable timestamps",
+                    count,
+                    self.total,
+                    self.duplicate_fraction * 100.0,
+                    window.to_rfc3339()
+                ));
+            }
+        }
+        if self.future_timestamps > 0 {
+            findings.push(format!(
+                "{} of {} rows have timestamps in the future",
+                self.future_timestamps, self.total
+            ));
+        }
+        findings
+    }
+
+    /// Apply a policy to the findings
+    ///
+    /// `Warn` logs each finding, `Refuse` fails if there are any.
+    pub fn enforce(&self, policy: AuditPolicy) -> Result<()> {
+        match policy {
+            AuditPolicy::Off => Ok(()),
+            AuditPolicy::Warn => {
+                for finding in self.findings() {
+                    log::warn!("Timestamp audit: {}", finding);
+                }
+                Ok(())
+            }
+            AuditPolicy::Refuse if self.is_suspicious() => Err(MiningError::InvalidTransaction(
+                format!("timestamp audit failed: {}", self.findings().join("; ")),
+            )),
+            AuditPolicy::Refuse => Ok(()),
+        }
+    }
+}
+
+/// Audit transaction timestamps with default thresholds
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::analysis::{timestamp_audit, AuditPolicy};
+/// use rust_rule_miner::Transaction;
+/// use chrono::{Duration, TimeZone, Utc};
+///
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+/// let transactions: Vec<Transaction> = (0..20)
+///     .map(|i| Transaction::new(format!("tx{}", i), vec!["A".to_string()], start + Duration::hours(i)))
+///     .collect();
+///
+/// let audit = timestamp_audit(&transactions);
+/// assert!(!audit.is_suspicious());
+/// assert_eq!(audit.span(), Duration::hours(19));
+/// audit.enforce(AuditPolicy::Refuse)?;
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+pub fn timestamp_audit(transactions: &[Transaction]) -> TimestampAudit {
+    timestamp_audit_with(transactions, &TimestampAuditConfig::default())
+}
+
+/// Audit transaction timestamps with custom thresholds
+pub fn timestamp_audit_with(
+    transactions: &[Transaction],
+    config: &TimestampAuditConfig,
+) -> TimestampAudit {
+    let total = transactions.len();
+    let now = config.now.unwrap_or_else(Utc::now);
+    let future_cutoff =
+        now + chrono::Duration::from_std(config.future_tolerance).unwrap_or_default();
+    let resolution_ms = (config.resolution.as_millis() as i64).max(1);
+
+    let mut windows: HashMap<i64, usize> = HashMap::new();
+    let mut future_timestamps = 0;
+    for tx in transactions {
+        let window = tx.timestamp.timestamp_millis().div_euclid(resolution_ms);
+        *windows.entry(window).or_insert(0) += 1;
+        if tx.timestamp > future_cutoff {
+            future_timestamps += 1;
+        }
+    }
+
+    // Ties go to the earliest window so the result is deterministic
+    let densest_window = windows
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+        .and_then(|(window, count)| {
+            DateTime::from_timestamp_millis(window * resolution_ms).map(|start| (start, count))
+        });
+    let duplicate_fraction = match (densest_window, total) {
+        (Some((_, count)), total) if total > 0 => count as f64 / total as f64,
+        _ => 0.0,
+    };
+    let fallback_suspected = total >= config.min_rows
+        && densest_window.is_some_and(|(_, count)| count > 1)
+        && duplicate_fraction > config.duplicate_threshold;
+
+    TimestampAudit {
+        total,
+        densest_window,
+        duplicate_fraction,
+        fallback_suspected,
+        future_timestamps,
+        out_of_order: count_out_of_order(transactions),
+        earliest: transactions.iter().map(|tx| tx.timestamp).min(),
+        latest: transactions.iter().map(|tx| tx.timestamp).max(),
+    }
+}
+
+/// Count adjacent id-ordered pairs whose timestamps decrease
+fn count_out_of_order(transactions: &[Transaction]) -> usize {
+    let mut ordered: Vec<&Transaction> = transactions.iter().collect();
+    let numeric: Option<Vec<(&str, u64)>> =
+        transactions.iter().map(|tx| split_id(&tx.id)).collect();
+    match numeric {
+        Some(_) => ordered.sort_by_key(|tx| split_id(&tx.id)),
+        None => ordered.sort_by(|a, b| a.id.cmp(&b.id)),
+    }
+
+    ordered
+        .windows(2)
+        .filter(|pair| pair[1].timestamp < pair[0].timestamp)
+        .count()
+}
+
+/// Split an id such as `tx42` into its prefix and numeric suffix
+fn split_id(id: &str) -> Option<(&str, u64)> {
+    let digits = id.len() - id.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return None;
+    }
+    let (prefix, number) = id.split_at(id.len() - digits);
+    number.parse().ok().map(|n| (prefix, n))
+}
+
+/// Scores at or above this are considered healthy
+pub const HEALTHY_QUALITY_SCORE: f64 = 0.8;
+
+/// Scores below this make meaningful rules unlikely
+pub const MINABLE_QUALITY_SCORE: f64 = 0.5;
+
+/// How serious a dataset quality finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// One problem found by `DatasetQuality::assess`
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityFinding {
+    pub severity: Severity,
+
+    /// What was measured
+    pub message: String,
+
+    /// What to do about it
+    pub remediation: String,
+}
+
+/// Whether a dataset is minable, as one score plus findings
+///
+/// The score (0.0 - 1.0) is the share of transactions with two or more
+/// items, since single-item baskets cannot produce rules, times the average
+/// of:
+///
+/// - the share of rows that are not duplicates (same id and items as an
+///   earlier row, e.g. a file loaded twice)
+/// - the share of rows with plausible timestamps (see `timestamp_audit`)
+/// - item reuse: 1 while distinct items are at most 10% of the transaction
+///   count, falling to 0 once every transaction has its own item
+///
+/// Compare against `HEALTHY_QUALITY_SCORE` and `MINABLE_QUALITY_SCORE`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetQuality {
+    /// Transactions assessed
+    pub total: usize,
+
+    /// Share of transactions holding exactly one distinct item
+    pub single_item_fraction: f64,
+
+    /// Share of rows repeating an earlier row's id and items
+    pub duplicate_fraction: f64,
+
+    /// Share of rows whose timestamps are neither in the future nor the
+    /// suspected `Utc::now()` load-time fallback
+    pub timestamp_validity: f64,
+
+    /// Distinct items per transaction
+    pub item_cardinality_ratio: f64,
+
+    /// Composite score (0.0 - 1.0)
+    pub score: f64,
+
+    /// Problems found, most severe first
+    pub findings: Vec<QualityFinding>,
+}
+
+impl DatasetQuality {
+    /// Assess a dataset before mining
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::analysis::{DatasetQuality, Severity};
+    /// use rust_rule_miner::Transaction;
+    /// use chrono::{Duration, TimeZone, Utc};
+    ///
+    /// // One item per row, as exported by a stock-update feed
+    /// let start = Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap();
+    /// let transactions: Vec<Transaction> = (0..50)
+    ///     .map(|i| Transaction::new(format!("tx{}", i), vec![format!("sku{}", i % 5)], start + Duration::minutes(i)))
+    ///     .collect();
+    ///
+    /// let quality = DatasetQuality::assess(&transactions);
+    /// assert!(!quality.is_minable());
+    /// assert_eq!(quality.findings[0].severity, Severity::Critical);
+    /// assert!(quality.findings[0].remediation.contains("WindowAggregator"));
+    /// ```
+    pub fn assess(transactions: &[Transaction]) -> Self {
+        let total = transactions.len();
+        if total == 0 {
+            return Self {
+                total,
+                single_item_fraction: 0.0,
+                duplicate_fraction: 0.0,
+                timestamp_validity: 0.0,
+                item_cardinality_ratio: 0.0,
+                score: 0.0,
+                findings: vec![QualityFinding {
+                    severity: Severity::Critical,
+                    message: "the dataset has no transactions".to_string(),
+                    remediation: "check the loader's column mapping and source filters".to_string(),
+                }],
+            };
+        }
+
+        let mut items = HashSet::new();
+        let mut rows = HashSet::new();
+        let mut single_item = 0;
+        let mut duplicates = 0;
+        for tx in transactions {
+            let mut basket: Vec<&str> = tx.items.iter().map(String::as_str).collect();
+            basket.sort_unstable();
+            basket.dedup();
+            if basket.len() == 1 {
+                single_item += 1;
+            }
+            items.extend(basket.iter().copied());
+            if !rows.insert((tx.id.as_str(), basket)) {
+                duplicates += 1;
+            }
+        }
+
+        let audit = timestamp_audit(transactions);
+        let fallback_rows = match audit.densest_window {
+            Some((_, count)) if audit.fallback_suspected => count,
+            _ => 0,
+        };
+        let invalid_timestamps = (fallback_rows + audit.future_timestamps).min(total);
+
+        let total_f = total as f64;
+        let single_item_fraction = single_item as f64 / total_f;
+        let duplicate_fraction = duplicates as f64 / total_f;
+        let timestamp_validity = 1.0 - invalid_timestamps as f64 / total_f;
+        let item_cardinality_ratio = items.len() as f64 / total_f;
+
+        let item_reuse = ((1.0 - item_cardinality_ratio) / 0.9).clamp(0.0, 1.0);
+        let score = (1.0 - single_item_fraction)
+            * ((1.0 - duplicate_fraction) + timestamp_validity + item_reuse)
+            / 3.0;
+
+        let mut findings = Vec::new();
+        if let Some(severity) = grade(single_item_fraction, 0.2, 0.5) {
+            findings.push(QualityFinding {
+                severity,
+                message: format!(
+                    "{:.0}% of transactions contain one item",
+                    single_item_fraction * 100.0
+                ),
+                remediation: "consider time-window aggregation into baskets; see WindowAggregator"
+                    .to_string(),
+            });
+        }
+        if let Some(severity) = grade(duplicate_fraction, 0.01, 0.2) {
+            findings.push(QualityFinding {
+                severity,
+                message: format!(
+                    "{} of {} rows repeat an earlier row's id and items",
+                    duplicates, total
+                ),
+                remediation: "check whether the source was loaded twice or joined \
+                              against a table with repeated keys"
+                    .to_string(),
+            });
+        }
+        if let Some(severity) = grade(1.0 - timestamp_validity, 0.0, 0.2) {
+            findings.push(QualityFinding {
+                severity,
+                message: format!(
+                    "{} of {} rows have implausible timestamps",
+                    invalid_timestamps, total
+                ),
+                remediation: "run analysis::timestamp_audit for details and fix the \
+                              timestamp column or format"
+                    .to_string(),
+            });
+        }
+        if let Some(severity) = grade(item_cardinality_ratio, 0.5, 1.0) {
+            findings.push(QualityFinding {
+                severity,
+                message: format!(
+                    "{} distinct items across {} transactions, so few items can reach \
+                     min_support",
+                    items.len(),
+                    total
+                ),
+                remediation: "normalize item names (ItemNormalizer) or mine categories \
+                              instead of individual products"
+                    .to_string(),
+            });
+        }
+        if audit.out_of_order > 0 {
+            findings.push(QualityFinding {
+                severity: Severity::Info,
+                message: format!(
+                    "{} adjacent rows in id order have decreasing timestamps",
+                    audit.out_of_order
+                ),
+                remediation: "harmless unless ids are assigned chronologically".to_string(),
+            });
+        }
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+
+        Self {
+            total,
+            single_item_fraction,
+            duplicate_fraction,
+            timestamp_validity,
+            item_cardinality_ratio,
+            score,
+            findings,
+        }
+    }
+
+    /// Score reaches `MINABLE_QUALITY_SCORE` and nothing is critical
+    pub fn is_minable(&self) -> bool {
+        self.score >= MINABLE_QUALITY_SCORE
+            && self
+                .findings
+                .iter()
+                .all(|finding| finding.severity < Severity::Critical)
+    }
+
+    /// Score reaches `HEALTHY_QUALITY_SCORE`
+    pub fn is_healthy(&self) -> bool {
+        self.score >= HEALTHY_QUALITY_SCORE
+    }
+}
+
+/// Severity of a measurement that is bad above `warning` and worse at `critical`
+fn grade(value: f64, warning: f64, critical: f64) -> Option<Severity> {
+    if value >= critical {
+        Some(Severity::Critical)
+    } else if value > warning {
+        Some(Severity::Warning)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn historical(count: usize) -> Vec<Transaction> {
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 8, 0, 0).unwrap();
+        (0..count)
+            .map(|i| {
+                Transaction::new(
+                    format!("tx{}", i),
+                    vec!["A".to_string()],
+                    start + chrono::Duration::minutes(37 * i as i64),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_history() {
+        let audit = timestamp_audit(&historical(50));
+        assert_eq!(audit.total, 50);
+        assert!(!audit.is_suspicious());
+        assert_eq!(audit.out_of_order, 0);
+        assert_eq!(audit.span(), chrono::Duration::minutes(37 * 49));
+        assert!(audit.findings().is_empty());
+    }
+
+    #[test]
+    fn test_detects_now_fallback_fingerprint() {
+        // Rows whose timestamps failed to parse get Utc::now() at load time,
+        // a few microseconds apart
+        let loaded_at = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let mut transactions = historical(30);
+        for i in 30..60 {
+            transactions.push(Transaction::new(
+                format!("tx{}", i),
+                vec!["B".to_string()],
+                loaded_at + chrono::Duration::microseconds(i),
+            ));
+        }
+
+        let config = TimestampAuditConfig {
+            now: Some(loaded_at + chrono::Duration::seconds(1)),
+            ..TimestampAuditConfig::default()
+        };
+        let audit = timestamp_audit_with(&transactions, &config);
+        assert!(audit.fallback_suspected);
+        assert_eq!(audit.densest_window, Some((loaded_at, 30)));
+        assert_eq!(audit.duplicate_fraction, 0.5);
+        assert_eq!(audit.future_timestamps, 0);
+        assert!(audit.findings()[0].contains("fallback"));
+
+        assert!(audit.enforce(AuditPolicy::Warn).is_ok());
+        assert!(audit.enforce(AuditPolicy::Off).is_ok());
+        let err = audit.enforce(AuditPolicy::Refuse).unwrap_err();
+        assert!(matches!(err, MiningError::InvalidTransaction(_)));
+    }
+
+    #[test]
+    fn test_future_timestamps() {
+        let mut transactions = historical(20);
+        let now = Utc.with_ymd_and_hms(2024, 3, 2, 0, 0, 0).unwrap();
+        transactions[5].timestamp = now + chrono::Duration::days(30);
+
+        let config = TimestampAuditConfig {
+            now: Some(now),
+            ..TimestampAuditConfig::default()
+        };
+        let audit = timestamp_audit_with(&transactions, &config);
+        assert_eq!(audit.future_timestamps, 1);
+        assert!(!audit.fallback_suspected);
+        assert!(audit.is_suspicious());
+        // tx5 jumps ahead, so tx5 -> tx6 goes backwards
+        assert_eq!(audit.out_of_order, 1);
+    }
+
+    #[test]
+    fn test_out_of_order_uses_numeric_ids() {
+        // Lexicographic order would put tx10 before tx2 and count inversions
+        let mut transactions = historical(12);
+        assert_eq!(count_out_of_order(&transactions), 0);
+
+        // Ids assigned in reverse chronological order
+        let count = transactions.len();
+        for (i, tx) in transactions.iter_mut().enumerate() {
+            tx.id = format!("tx{}", count - i);
+        }
+        assert_eq!(count_out_of_order(&transactions), count - 1);
+    }
+
+    #[test]
+    fn test_small_datasets_not_flagged() {
+        let now = Utc::now();
+        let transactions: Vec<Transaction> = (0..5)
+            .map(|i| Transaction::new(format!("tx{}", i), vec!["A".to_string()], now))
+            .collect();
+        assert!(!timestamp_audit(&transactions).fallback_suspected);
+    }
+
+    fn baskets(baskets: &[&[&str]]) -> Vec<Transaction> {
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 8, 0, 0).unwrap();
+        baskets
+            .iter()
+            .enumerate()
+            .map(|(i, items)| {
+                Transaction::new(
+                    format!("tx{}", i),
+                    items.iter().map(|s| s.to_string()).collect(),
+                    start + chrono::Duration::minutes(i as i64),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_healthy_dataset() {
+        let patterns: [&[&str]; 4] = [
+            &["Milk", "Bread"],
+            &["Milk", "Bread", "Butter"],
+            &["Beer", "Chips"],
+            &["Milk", "Eggs"],
+        ];
+        let transactions = baskets(&patterns.repeat(25));
+
+        let quality = DatasetQuality::assess(&transactions);
+        assert_eq!(quality.total, 100);
+        assert_eq!(quality.single_item_fraction, 0.0);
+        assert_eq!(quality.duplicate_fraction, 0.0);
+        assert_eq!(quality.timestamp_validity, 1.0);
+        assert_eq!(quality.item_cardinality_ratio, 0.06);
+        assert_eq!(quality.score, 1.0);
+        assert!(quality.findings.is_empty());
+        assert!(quality.is_healthy());
+        assert!(quality.is_minable());
+    }
+
+    #[test]
+    fn test_single_item_dataset() {
+        let patterns: [&[&str]; 3] = [&["Milk"], &["Bread"], &["Milk", "Milk"]];
+        let mut transactions = baskets(&patterns.repeat(20));
+        // The last ten rows were loaded twice
+        let reloaded: Vec<Transaction> = transactions[50..].to_vec();
+        transactions.extend(reloaded);
+
+        let quality = DatasetQuality::assess(&transactions);
+        assert_eq!(quality.total, 70);
+        assert_eq!(quality.single_item_fraction, 1.0);
+        assert!((quality.duplicate_fraction - 10.0 / 70.0).abs() < 1e-12);
+        assert_eq!(quality.score, 0.0);
+        assert!(!quality.is_minable());
+
+        let critical = &quality.findings[0];
+        assert_eq!(critical.severity, Severity::Critical);
+        assert!(critical
+            .message
+            .starts_with("100% of transactions contain one item"));
+        assert!(critical.remediation.contains("WindowAggregator"));
+        assert!(quality
+            .findings
+            .iter()
+            .any(|f| f.severity == Severity::Warning && f.message.contains("10 of 70 rows")));
+        assert_eq!(quality.findings.len(), 2);
+    }
+
+    #[test]
+    fn test_empty_dataset() {
+        let quality = DatasetQuality::assess(&[]);
+        assert_eq!(quality.score, 0.0);
+        assert!(!quality.is_minable());
+    }
+}
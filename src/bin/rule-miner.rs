@@ -0,0 +1,265 @@
+//! Command-line interface for rust-rule-miner
+//!
+//! ```text
+//! rule-miner mine --input transactions.csv [--min-support 0.1] [--output rules.json] [--estimate]
+//! rule-miner gate --config gate.toml --rules rules.json [--validation holdout.csv]
+//! rule-miner deploy --plan --current deployed.json --new nightly.json [--threshold 0.7] [--json]
+//! rule-miner explain --rules rules.json --item "Mouse" [--depth 2] [--children 10]
+//! rule-miner alerts --config alerts.json --baseline deployed.json --new nightly.json [--json]
+//! ```
+//!
+//! Rules are read from `JsonExporter` output (or a bare JSON array of
+//! `AssociationRule`). Transaction and validation CSVs use the standard
+//! 3-column layout (transaction_id, items, timestamp).
+
+use std::collections::HashMap;
+use std::process::ExitCode;
+
+use rust_rule_miner::data_loader::{ColumnMapping, DataLoader};
+use rust_rule_miner::deployment::{plan_with_options, PlanOptions};
+use rust_rule_miner::export::JsonExporter;
+use rust_rule_miner::gate::QualityGate;
+use rust_rule_miner::mining::{estimate, sample_transactions};
+use rust_rule_miner::rules::RuleSet;
+use rust_rule_miner::{
+    AssociationRule, Confidence, Lift, MiningAlgorithm, MiningConfig, MiningError, Result,
+    RuleMiner, Support,
+};
+
+const USAGE: &str = "\
+Usage: rule-miner <COMMAND> [OPTIONS]
+
+Commands:
+  mine    Mine association rules from a transactions CSV
+          --input <transactions.csv>  [--min-support <0.1>]  [--min-confidence <0.7>]
+          [--min-lift <1.0>]  [--algorithm <apriori|fpgrowth>]  [--output <rules.json>]
+          [--estimate [--sample <0.1>] [--seed <42>]]  project counts and runtime, then exit
+  gate    Check mined rules against a quality gate (exit code 1 on failure)
+          --config <gate.toml>  --rules <rules.json>  [--validation <transactions.csv>]
+  deploy  Preview a deployment (dry run); prints the plan hash to approve
+          --plan  --current <rules.json>  --new <rules.json>  [--threshold <0.7>]
+          [--json] [--color]
+  explain Print the rules leading to an item as a tree
+          --rules <rules.json>  --item <Mouse>  [--depth <2>]  [--children <10>]
+  alerts  Print the alerts fired by new rules against a baseline (exit code 1 if any)
+          --config <alerts.json>  --baseline <rules.json>  --new <rules.json>  [--json]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode> {
+    let Some((command, rest)) = args.split_first() else {
+        println!("{}", USAGE);
+        return Ok(ExitCode::from(2));
+    };
+
+    match command.as_str() {
+        "mine" => mine(&Options::parse(rest)?),
+        "gate" => gate(&Options::parse(rest)?),
+        "deploy" => deploy(&Options::parse(rest)?),
+        "explain" => explain(&Options::parse(rest)?),
+        "alerts" => alerts(&Options::parse(rest)?),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(ExitCode::SUCCESS)
+        }
+        other => Err(MiningError::InvalidConfig(format!(
+            "unknown command `{}`\n\n{}",
+            other, USAGE
+        ))),
+    }
+}
+
+fn mine(options: &Options) -> Result<ExitCode> {
+    let transactions =
+        DataLoader::from_csv(options.require("input")?, ColumnMapping::simple(0, 1, 2))?;
+
+    let mut builder = MiningConfig::builder();
+    if let Some(value) = options.get_parsed::<f64>("min-support")? {
+        builder = builder.min_support(Support::fraction(value)?);
+    }
+    if let Some(value) = options.get_parsed::<f64>("min-confidence")? {
+        builder = builder.min_confidence(Confidence::fraction(value)?);
+    }
+    if let Some(value) = options.get_parsed::<f64>("min-lift")? {
+        builder = builder.min_lift(Lift::new(value)?);
+    }
+    if let Some(algorithm) = options.get("algorithm") {
+        builder = builder.algorithm(match algorithm.to_lowercase().as_str() {
+            "apriori" => MiningAlgorithm::Apriori,
+            "fpgrowth" | "fp-growth" => MiningAlgorithm::FPGrowth,
+            other => {
+                return Err(MiningError::InvalidConfig(format!(
+                    "unknown --algorithm `{}` (expected apriori or fpgrowth)",
+                    other
+                )))
+            }
+        });
+    }
+    let config = builder.build()?;
+
+    if options.flag("estimate") {
+        let fraction = options.get_parsed("sample")?.unwrap_or(0.1);
+        let seed = options.get_parsed("seed")?.unwrap_or(42);
+        let sample = sample_transactions(&transactions, fraction, seed);
+        println!("{}", estimate(&sample, &config, transactions.len())?);
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut miner = RuleMiner::new(config);
+    miner.add_transactions(transactions)?;
+    let rules = miner.mine()?.rules;
+
+    let json = JsonExporter::to_json_pretty(&rules)?;
+    match options.get("output") {
+        Some(path) => {
+            std::fs::write(path, json)?;
+            eprintln!("Wrote {} rules to {}", rules.len(), path);
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn gate(options: &Options) -> Result<ExitCode> {
+    let gate = QualityGate::from_toml_file(options.require("config")?)?;
+    let rules = read_rules(options.require("rules")?)?;
+
+    let validation = options
+        .get("validation")
+        .map(|path| DataLoader::from_csv(path, ColumnMapping::simple(0, 1, 2)))
+        .transpose()?;
+
+    let report = gate.evaluate(&rules, validation.as_deref());
+    println!("{}", report);
+
+    Ok(if report.passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn deploy(options: &Options) -> Result<ExitCode> {
+    if !options.flag("plan") {
+        return Err(MiningError::InvalidConfig(
+            "only `deploy --plan` is supported; apply an approved plan with \
+             MiningRuleEngine::replace_rules_approved"
+                .to_string(),
+        ));
+    }
+
+    let current = read_rules(options.require("current")?)?;
+    let new_rules = read_rules(options.require("new")?)?;
+
+    let mut plan_options = PlanOptions::default();
+    if let Some(threshold) = options.get_parsed("threshold")? {
+        plan_options.confidence_threshold = threshold;
+    }
+
+    let plan = plan_with_options(&current, &new_rules, &plan_options);
+    if options.flag("json") {
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+    } else {
+        print!("{}", plan.render(options.flag("color")));
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn explain(options: &Options) -> Result<ExitCode> {
+    let rules = RuleSet::new(read_rules(options.require("rules")?)?);
+    let item = options.require("item")?;
+    let depth = options.get_parsed("depth")?.unwrap_or(2);
+    let children = options.get_parsed("children")?.unwrap_or(10);
+
+    print!("{}", rules.render_tree(item, depth, children));
+    Ok(ExitCode::SUCCESS)
+}
+
+fn alerts(options: &Options) -> Result<ExitCode> {
+    let alert_rules = rust_rule_miner::alerts::load_rules(options.require("config")?)?;
+    let baseline = read_rules(options.require("baseline")?)?;
+    let new_rules = read_rules(options.require("new")?)?;
+
+    let events = rust_rule_miner::alerts::evaluate(&new_rules, &baseline, &alert_rules);
+    if options.flag("json") {
+        println!("{}", serde_json::to_string_pretty(&events)?);
+    } else {
+        for event in &events {
+            println!("{}", event);
+        }
+    }
+
+    Ok(if events.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Read rules written by `JsonExporter`, or a bare JSON array of rules
+fn read_rules(path: &str) -> Result<Vec<AssociationRule>> {
+    let json = std::fs::read_to_string(path)?;
+    JsonExporter::from_json(&json).or_else(|_| Ok(serde_json::from_str(&json)?))
+}
+
+/// `--key value` options and `--flag` switches following a subcommand
+struct Options {
+    values: HashMap<String, String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut values = HashMap::new();
+        let mut iter = args.iter().peekable();
+
+        while let Some(arg) = iter.next() {
+            let key = arg.strip_prefix("--").ok_or_else(|| {
+                MiningError::InvalidConfig(format!("unexpected argument `{}`", arg))
+            })?;
+            // A switch is followed by another option (or nothing)
+            let value = match iter.peek() {
+                Some(next) if !next.starts_with("--") => iter.next().cloned(),
+                _ => None,
+            };
+            values.insert(key.to_string(), value.unwrap_or_default());
+        }
+
+        Ok(Self { values })
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    fn get_parsed<T: std::str::FromStr>(&self, key: &str) -> Result<Option<T>> {
+        self.get(key)
+            .map(|value| {
+                value.parse().map_err(|_| {
+                    MiningError::InvalidConfig(format!("invalid --{} `{}`", key, value))
+                })
+            })
+            .transpose()
+    }
+
+    fn require(&self, key: &str) -> Result<&str> {
+        self.get(key)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| MiningError::InvalidConfig(format!("missing required `--{}`", key)))
+    }
+}
@@ -0,0 +1,117 @@
+//! Object stores for Google Cloud Storage and Azure Blob Storage
+//!
+//! Both are `object_store` clients, which sign requests and find credentials
+//! the way the official SDKs do. `DataLoader::from_gcs` and
+//! `DataLoader::from_azure_blob` stream objects from the stores built here.
+
+use std::collections::HashMap;
+
+use object_store::azure::{AzureConfigKey, MicrosoftAzure, MicrosoftAzureBuilder};
+use object_store::gcp::{GoogleCloudStorage, GoogleCloudStorageBuilder, GoogleConfigKey};
+use object_store::ClientConfigKey;
+
+use crate::errors::{MiningError, Result};
+
+/// Client for `bucket`
+///
+/// With `STORAGE_EMULATOR_HOST` set, requests go to that emulator without
+/// credentials. Otherwise credentials come from the `GOOGLE_*` variables
+/// `object_store` reads, then Application Default Credentials: the
+/// `GOOGLE_APPLICATION_CREDENTIALS` file, else gcloud's
+/// `application_default_credentials.json`, else the metadata server.
+pub(crate) fn gcs_store(bucket: &str) -> Result<GoogleCloudStorage> {
+    let mut builder = GoogleCloudStorageBuilder::from_env().with_bucket_name(bucket);
+    if let Some(host) = env_var("STORAGE_EMULATOR_HOST") {
+        let host = if host.contains("://") {
+            host
+        } else {
+            format!("http://{}", host)
+        };
+        // How object_store addresses an emulator: a key file that turns
+        // OAuth off and overrides the base URL
+        let emulator = serde_json::json!({
+            "gcs_base_url": host.trim_end_matches('/'),
+            "disable_oauth": true,
+            "client_email": "",
+            "private_key": "",
+            "private_key_id": "",
+        });
+        builder = builder
+            .with_service_account_key(emulator.to_string())
+            .with_config(
+                GoogleConfigKey::Client(ClientConfigKey::AllowHttp),
+                host.starts_with("http://").to_string(),
+            );
+    }
+    builder
+        .build()
+        .map_err(|e| credential_error(format!("Cannot open GCS bucket `{}`: {}", bucket, e)))
+}
+
+/// Client for `container` in `account`
+///
+/// `AZURE_STORAGE_CONNECTION_STRING` is honoured for its account key, SAS
+/// and `BlobEndpoint` (such as Azurite's), as is the Azure CLI's
+/// `AZURE_STORAGE_KEY`. Otherwise credentials come from the `AZURE_*`
+/// variables `object_store` reads: `AZURE_STORAGE_ACCOUNT_KEY`,
+/// `AZURE_STORAGE_SAS_TOKEN`, a service principal in `AZURE_TENANT_ID`,
+/// `AZURE_CLIENT_ID` and `AZURE_CLIENT_SECRET`, workload identity, else the
+/// host's managed identity.
+pub(crate) fn azure_store(account: &str, container: &str) -> Result<MicrosoftAzure> {
+    let connection = env_var("AZURE_STORAGE_CONNECTION_STRING")
+        .map(|value| connection_settings(&value))
+        .unwrap_or_default();
+    if let Some(name) = connection
+        .get("AccountName")
+        .filter(|name| *name != account)
+    {
+        return Err(credential_error(format!(
+            "AZURE_STORAGE_CONNECTION_STRING is for account `{}`, not `{}`",
+            name, account
+        )));
+    }
+
+    let mut builder = MicrosoftAzureBuilder::from_env()
+        .with_account(account)
+        .with_container_name(container);
+    // AZURE_STORAGE_KEY is the Azure CLI's name for the account key
+    let key = connection
+        .get("AccountKey")
+        .cloned()
+        .or_else(|| env_var("AZURE_STORAGE_KEY"));
+    if let Some(key) = key {
+        builder = builder.with_access_key(key);
+    }
+    if let Some(sas) = connection.get("SharedAccessSignature") {
+        builder = builder.with_config(AzureConfigKey::SasKey, sas);
+    }
+    if let Some(endpoint) = connection.get("BlobEndpoint") {
+        builder = builder
+            .with_allow_http(endpoint.starts_with("http://"))
+            .with_endpoint(endpoint.clone());
+    }
+    builder.build().map_err(|e| {
+        credential_error(format!(
+            "Cannot open Azure container `{}/{}`: {}",
+            account, container, e
+        ))
+    })
+}
+
+/// `Key=Value` pairs of a storage connection string
+fn connection_settings(connection: &str) -> HashMap<String, String> {
+    connection
+        .split(';')
+        .filter_map(|setting| setting.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// A set, non-empty environment variable
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn credential_error(message: String) -> MiningError {
+    MiningError::DataLoadError(message)
+}
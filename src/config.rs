@@ -1,41 +1,704 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
 use std::time::Duration;
 
+use crate::errors::{MiningError, Result};
+use crate::transaction::Transaction;
+use crate::types::PatternMetrics;
+
+/// Minimum support threshold, stored as a fraction of transactions
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::config::Support;
+///
+/// assert_eq!(Support::percent(30)?, Support::fraction(0.3)?);
+/// assert!(Support::fraction(30.0).is_err()); // meant 30%?
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Support(f64);
+
+impl Support {
+    /// Support as a fraction in [0, 1] (e.g., 0.3 = 30% of transactions)
+    pub fn fraction(value: impl Into<f64>) -> Result<Self> {
+        fraction("min_support", "Support", value.into()).map(Self)
+    }
+
+    /// Support as a percentage in [0, 100]
+    pub fn percent(value: impl Into<f64>) -> Result<Self> {
+        percent("min_support", value.into()).map(Self)
+    }
+
+    /// Fraction of transactions
+    pub fn value(self) -> f64 {
+        self.0
+    }
+
+    /// Fewest transactions out of `total` an itemset must appear in
+    ///
+    /// `ceil(fraction × total)`, snapping products that are an integer up
+    /// to floating-point error: 0.07 × 100 is 7.000000000000001 in `f64`,
+    /// which a plain `ceil` would turn into 8.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::Support;
+    ///
+    /// assert_eq!(Support::fraction(0.07)?.min_count(100), 7);
+    /// assert_eq!(Support::fraction(0.07)?.min_count(101), 8);
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn min_count(self, total: usize) -> usize {
+        let exact = self.0 * total as f64;
+        let nearest = exact.round();
+        if (exact - nearest).abs() <= f64::EPSILON * 16.0 * nearest.max(1.0) {
+            nearest as usize
+        } else {
+            exact.ceil() as usize
+        }
+    }
+}
+
+/// Minimum confidence threshold, stored as a fraction
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Confidence(f64);
+
+impl Confidence {
+    /// Confidence as a fraction in [0, 1] (e.g., 0.7 = correct 70% of the time)
+    pub fn fraction(value: impl Into<f64>) -> Result<Self> {
+        fraction("min_confidence", "Confidence", value.into()).map(Self)
+    }
+
+    /// Confidence as a percentage in [0, 100]
+    pub fn percent(value: impl Into<f64>) -> Result<Self> {
+        percent("min_confidence", value.into()).map(Self)
+    }
+
+    /// Confidence as a fraction
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+/// Minimum lift threshold (1.0 = independent)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Lift(f64);
+
+impl Lift {
+    /// Lift must be finite and non-negative
+    pub fn new(value: impl Into<f64>) -> Result<Self> {
+        let value = value.into();
+        if value.is_finite() && value >= 0.0 {
+            Ok(Self(value))
+        } else {
+            Err(MiningError::InvalidConfig(format!(
+                "min_lift must be a finite, non-negative number, got {}",
+                value
+            )))
+        }
+    }
+
+    /// Lift ratio
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Support {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0 * 100.0)
+    }
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0 * 100.0)
+    }
+}
+
+impl fmt::Display for Lift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn fraction(field: &str, kind: &str, value: f64) -> Result<f64> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else if value > 1.0 && value <= 100.0 {
+        Err(MiningError::InvalidConfig(format!(
+            "{} must be a fraction in [0, 1], got {} (use {}::percent({}) for a percentage)",
+            field, value, kind, value
+        )))
+    } else {
+        Err(MiningError::InvalidConfig(format!(
+            "{} must be a fraction in [0, 1], got {}",
+            field, value
+        )))
+    }
+}
+
+fn percent(field: &str, value: f64) -> Result<f64> {
+    if (0.0..=100.0).contains(&value) {
+        Ok(value / 100.0)
+    } else {
+        Err(MiningError::InvalidConfig(format!(
+            "{} must be a percentage in [0, 100], got {}",
+            field, value
+        )))
+    }
+}
+
 /// Mining configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Build with `MiningConfig::builder()`, which takes typed thresholds and
+/// validates them. The struct is `#[non_exhaustive]` so settings can be
+/// added in minor releases; outside this crate it cannot be built with a
+/// struct literal. The raw threshold fields are deprecated but can still be
+/// read and assigned.
+///
+/// When deserialized, missing fields take their default and unknown fields
+/// are rejected, so config files only list what they change and typos fail
+/// loudly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
 pub struct MiningConfig {
     /// Minimum support threshold (0.0 - 1.0)
     /// Example: 0.1 = pattern must appear in at least 10% of transactions
+    #[deprecated(
+        note = "use MiningConfig::builder().min_support(Support::fraction(..)?) and min_support()"
+    )]
     pub min_support: f64,
 
     /// Minimum confidence threshold (0.0 - 1.0)
     /// Example: 0.7 = rule must be correct at least 70% of the time
+    #[deprecated(
+        note = "use MiningConfig::builder().min_confidence(Confidence::fraction(..)?) and min_confidence()"
+    )]
     pub min_confidence: f64,
 
     /// Minimum lift threshold
     /// Example: 1.2 = items must co-occur 20% more than random chance
+    #[deprecated(note = "use MiningConfig::builder().min_lift(Lift::new(..)?) and min_lift()")]
     pub min_lift: f64,
 
+    /// Minimum weighted support (0.0 - 1.0, None = no weighted filter)
+    /// Example: 0.2 = an itemset's weighted support must reach 20%, using the
+    /// weights from `RuleMiner::set_item_weights`
+    #[serde(default)]
+    pub min_weighted_support: Option<f64>,
+
+    /// Minimum leverage (-0.25 - 0.25, None = no filter)
+    #[serde(default)]
+    pub min_leverage: Option<f64>,
+
+    /// Minimum Jaccard coefficient (0.0 - 1.0, None = no filter)
+    #[serde(default)]
+    pub min_jaccard: Option<f64>,
+
+    /// Minimum cosine (0.0 - 1.0, None = no filter)
+    /// Null-invariant: unaffected by baskets holding neither side, so it
+    /// suits skewed data where lift is inflated by many unrelated baskets
+    #[serde(default)]
+    pub min_cosine: Option<f64>,
+
+    /// Minimum Kulczynski measure (0.0 - 1.0, None = no filter, null-invariant)
+    #[serde(default)]
+    pub min_kulczynski: Option<f64>,
+
+    /// Maximum chi-square p-value (0.0 - 1.0, None = no significance filter)
+    /// Example: 0.05 = drop rules whose antecedent/consequent association
+    /// could plausibly be chance, however high their lift
+    #[serde(default)]
+    pub max_p_value: Option<f64>,
+
+    /// Multiple testing correction applied with `max_p_value`
+    #[serde(default)]
+    pub p_value_correction: PValueCorrection,
+
     /// Maximum time gap for sequential patterns
     pub max_time_gap: Option<Duration>,
 
     /// Mining algorithm to use
     pub algorithm: MiningAlgorithm,
+
+    /// Maximum number of items in a frequent itemset (None = unlimited)
+    /// Example: 3 = Apriori/FP-Growth never extend itemsets beyond 3 items
+    #[serde(default)]
+    pub max_itemset_size: Option<usize>,
+
+    /// Maximum number of items on the left-hand side of a rule (None = unlimited)
+    #[serde(default)]
+    pub max_antecedent_size: Option<usize>,
+
+    /// Maximum number of items on the right-hand side of a rule (None = unlimited)
+    #[serde(default)]
+    pub max_consequent_size: Option<usize>,
+
+    /// Which frequent itemsets to keep for rule generation
+    /// Example: Closed = drop itemsets whose superset has identical support
+    #[serde(default)]
+    pub itemset_kind: ItemsetKind,
+
+    /// Which rule metrics to compute beyond confidence, support and lift
+    /// Example: MetricSet::CORE = skip conviction when only filtering on confidence
+    #[serde(default)]
+    pub compute_metrics: MetricSet,
+
+    /// Item include/exclude constraints, applied before itemset generation
+    /// Example: exclude "Plastic Bag", or only mine rules about a new SKU
+    #[serde(default)]
+    pub constraints: RuleConstraints,
+
+    /// Retry with Apriori when the configured algorithm fails
+    /// Example: true = an unimplemented algorithm mines with Apriori instead
+    /// of erroring; see `MiningStats::algorithm` for what actually ran
+    #[serde(default)]
+    pub fallback_to_apriori: bool,
+
+    /// Warn before mining when the dataset quality score is below this
+    /// (0.0 - 1.0, None = no check)
+    /// Example: 0.5 = log `DatasetQuality` findings for datasets unlikely to
+    /// yield meaningful rules
+    #[serde(default)]
+    pub min_quality_score: Option<f64>,
+
+    /// Drop rules recommending an item whose own support is above this
+    /// (0.0 - 1.0, None = no cap); such items stay usable in antecedents
+    /// Example: 0.4 = never recommend items already in over 40% of baskets,
+    /// which "anything => Bag" rules only restate
+    #[serde(default)]
+    pub max_consequent_base_rate: Option<f64>,
+
+    /// What one unit of support counts: a transaction row, an id or a user
+    /// Example: DistinctUsers = "bought together by 10% of customers"
+    /// instead of "in 10% of orders"
+    #[serde(default)]
+    pub support_universe: SupportUniverse,
+
+    /// Keep only the stronger direction when both A => B and B => A pass
+    /// Example: false = keep both, e.g. to show either item's complement;
+    /// rule engines fed both directions can loop
+    pub dedup_bidirectional: bool,
+
+    /// Drop rules implied by a more general rule with the same consequent
+    /// Example: true = drop {Laptop, Mouse} => {Hub} when {Laptop} => {Hub}
+    /// is at least as confident; see `RuleMiner::prune_redundant_rules`
+    #[serde(default)]
+    pub prune_redundant: bool,
+
+    /// Stop a mining run after this long and keep what was found (None = unlimited)
+    /// Example: 30s = a too-low min_support on dense data returns partial
+    /// rules instead of hanging; `MiningStats::truncated` reports it
+    #[serde(default)]
+    pub max_duration: Option<Duration>,
+
+    /// Stop itemset mining once this many frequent itemsets are found
+    /// (None = unlimited)
+    /// Example: 100_000 = bound memory; `MiningStats::truncated` reports it
+    #[serde(default)]
+    pub max_frequent_itemsets: Option<usize>,
+
+    /// Mine even when `min_support` is met by a single transaction, only
+    /// logging a warning
+    /// Example: true = accept that 0.001 of 500 transactions is 1 occurrence,
+    /// where one coincidental co-occurrence can become a rule with lift 40
+    #[serde(default)]
+    pub allow_singleton_support: bool,
+
+    /// Supporting transaction IDs kept per rule by
+    /// `RuleMiner::mine_association_rules_with_evidence` (None = off)
+    /// Example: 5 = up to five baskets holding both sides, to audit a rule
+    #[serde(default)]
+    pub collect_evidence: Option<usize>,
+
+    /// Transactions left out of mining by id; they stay loaded
+    /// Example: {"tx-9913"} = ignore a known test order
+    #[serde(default)]
+    pub excluded_transaction_ids: BTreeSet<String>,
+
+    /// Time ranges left out of mining (start inclusive, end exclusive),
+    /// written as `"start/end"` RFC 3339 strings in config files
+    /// Example: 2024-03-03T00:00:00Z/2024-03-04T00:00:00Z = skip the day of
+    /// a data incident in every run over that month
+    #[serde(default, with = "time_ranges")]
+    pub excluded_time_ranges: Vec<(DateTime<Utc>, DateTime<Utc>)>,
 }
 
 impl Default for MiningConfig {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             min_support: 0.1,    // 10%
             min_confidence: 0.7, // 70%
             min_lift: 1.0,       // No negative correlation
+            min_weighted_support: None,
+            min_leverage: None,
+            min_jaccard: None,
+            min_cosine: None,
+            min_kulczynski: None,
+            max_p_value: None,
+            p_value_correction: PValueCorrection::None,
             max_time_gap: None,
             algorithm: MiningAlgorithm::Apriori,
+            max_itemset_size: None,
+            max_antecedent_size: None,
+            max_consequent_size: None,
+            itemset_kind: ItemsetKind::All,
+            compute_metrics: MetricSet::default(),
+            constraints: RuleConstraints::default(),
+            fallback_to_apriori: false,
+            min_quality_score: None,
+            max_consequent_base_rate: None,
+            support_universe: SupportUniverse::Transactions,
+            dedup_bidirectional: true,
+            prune_redundant: false,
+            max_duration: None,
+            max_frequent_itemsets: None,
+            allow_singleton_support: false,
+            collect_evidence: None,
+            excluded_transaction_ids: BTreeSet::new(),
+            excluded_time_ranges: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(deprecated)]
+impl MiningConfig {
+    /// Start building a config from the defaults
+    pub fn builder() -> MiningConfigBuilder {
+        MiningConfigBuilder::default()
+    }
+
+    /// High-precision rules for production: 10% support, 80% confidence, lift 1.5
+    pub fn strict() -> Self {
+        Self {
+            min_support: 0.1,
+            min_confidence: 0.8,
+            min_lift: 1.5,
+            ..Self::default()
+        }
+    }
+
+    /// Wide net for discovery: 1% support, 50% confidence, any positive lift
+    pub fn exploratory() -> Self {
+        Self {
+            min_support: 0.01,
+            min_confidence: 0.5,
+            min_lift: 1.0,
+            ..Self::default()
+        }
+    }
+
+    /// Large catalogs where most items are rare: 0.1% support, 60% confidence,
+    /// lift 2.0 to cut noise, mined with FP-Growth
+    pub fn sparse_data() -> Self {
+        Self {
+            min_support: 0.001,
+            min_confidence: 0.6,
+            min_lift: 2.0,
+            algorithm: MiningAlgorithm::FPGrowth,
+            ..Self::default()
+        }
+    }
+
+    /// Minimum support as a fraction of transactions
+    pub fn min_support(&self) -> f64 {
+        self.min_support
+    }
+
+    /// Minimum support as a transaction count out of `total`
+    ///
+    /// All algorithms compare integer counts against this, so an itemset
+    /// sitting exactly on the threshold passes everywhere or nowhere.
+    pub fn min_support_count(&self, total: usize) -> usize {
+        Support(self.min_support).min_count(total)
+    }
+
+    /// Whether `transaction` is left out of mining by its id or timestamp
+    pub fn excludes(&self, transaction: &Transaction) -> bool {
+        self.excluded_transaction_ids.contains(&transaction.id)
+            || self
+                .excluded_time_ranges
+                .iter()
+                .any(|(start, end)| (*start..*end).contains(&transaction.timestamp))
+    }
+
+    /// Whether any transaction ids or time ranges are excluded
+    pub fn has_exclusions(&self) -> bool {
+        !self.excluded_transaction_ids.is_empty() || !self.excluded_time_ranges.is_empty()
+    }
+
+    /// Check that `min_support` of `total` transactions needs at least two
+    /// occurrences
+    ///
+    /// Below that a single coincidental co-occurrence becomes a rule. Fails
+    /// with `InvalidConfig`, or only warns with `allow_singleton_support`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{MiningConfig, Support};
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.001)?)
+    ///     .build()?;
+    /// assert!(config.check_support_count(500).is_err()); // 1 occurrence
+    /// assert!(config.check_support_count(5_000).is_ok()); // 5 occurrences
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn check_support_count(&self, total: usize) -> Result<()> {
+        let count = self.min_support_count(total);
+        if count >= 2 {
+            return Ok(());
+        }
+
+        let message = format!(
+            "min_support {} of {} transactions is {} occurrence{}, so one coincidental \
+             co-occurrence becomes a rule; raise min_support to at least {:.4} or set \
+             allow_singleton_support",
+            self.min_support,
+            total,
+            count,
+            if count == 1 { "" } else { "s" },
+            2.0 / total.max(1) as f64
+        );
+        if self.allow_singleton_support {
+            log::warn!("{}", message);
+            Ok(())
+        } else {
+            Err(MiningError::InvalidConfig(message))
+        }
+    }
+
+    /// Minimum confidence as a fraction
+    pub fn min_confidence(&self) -> f64 {
+        self.min_confidence
+    }
+
+    /// Minimum lift
+    pub fn min_lift(&self) -> f64 {
+        self.min_lift
+    }
+
+    /// Set the minimum support
+    pub fn set_min_support(&mut self, support: Support) {
+        self.min_support = support.value();
+    }
+
+    /// Set the minimum confidence
+    pub fn set_min_confidence(&mut self, confidence: Confidence) {
+        self.min_confidence = confidence.value();
+    }
+
+    /// Set the minimum lift
+    pub fn set_min_lift(&mut self, lift: Lift) {
+        self.min_lift = lift.value();
+    }
+
+    /// Load and validate a config file
+    ///
+    /// The format follows the extension: `.toml`, `.json`, or `.yaml`/`.yml`.
+    /// Errors name the file and, where possible, the failing field.
+    ///
+    /// ```toml
+    /// min_support = 0.05
+    /// min_confidence = 0.6
+    /// algorithm = "fp-growth"
+    /// max_antecedent_size = 2
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let in_file = |message: String| {
+            MiningError::InvalidConfig(format!("{}: {}", path.display(), message))
+        };
+
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| in_file(format!("cannot read file: {}", e)))?;
+        let value = match ConfigFormat::from_path(path)? {
+            ConfigFormat::Toml => toml::from_str::<serde_json::Value>(&text)
+                .map_err(|e| MiningError::InvalidConfig(e.to_string())),
+            ConfigFormat::Json => serde_json::from_str(&text).map_err(MiningError::from),
+            ConfigFormat::Yaml => serde_yaml::from_str::<serde_json::Value>(&text)
+                .map_err(|e| MiningError::InvalidConfig(e.to_string())),
+        }
+        .map_err(|e| in_file(e.to_string()))?;
+
+        let config: Self = serde_path_to_error::deserialize(value).map_err(|e| {
+            let field = e.path().to_string();
+            match field.as_str() {
+                "." => in_file(e.into_inner().to_string()),
+                _ => in_file(format!("field `{}`: {}", field, e.into_inner())),
+            }
+        })?;
+        config.validate().map_err(|e| match e {
+            MiningError::InvalidConfig(message) => in_file(message),
+            other => other,
+        })?;
+        Ok(config)
+    }
+
+    /// Write the config to a file, in the format given by the extension
+    ///
+    /// Unset optional fields are omitted from TOML, which has no null.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let value = serde_json::to_value(self)?;
+        let text = match ConfigFormat::from_path(path)? {
+            ConfigFormat::Toml => toml::to_string(self)
+                .map_err(|e| MiningError::InvalidConfig(format!("cannot write TOML: {}", e)))?,
+            ConfigFormat::Json => serde_json::to_string_pretty(&value)? + "\n",
+            ConfigFormat::Yaml => serde_yaml::to_string(&value)
+                .map_err(|e| MiningError::InvalidConfig(format!("cannot write YAML: {}", e)))?,
+        };
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Check that thresholds are in range
+    ///
+    /// Catches raw values set through the deprecated fields or deserialized
+    /// from files, e.g. `min_support = 30.0` meant as 30%.
+    pub fn validate(&self) -> Result<()> {
+        if let Some((start, end)) = self.excluded_time_ranges.iter().find(|(s, e)| s >= e) {
+            return Err(MiningError::InvalidConfig(format!(
+                "excluded time range must end after it starts, got {} to {}",
+                start.to_rfc3339(),
+                end.to_rfc3339()
+            )));
+        }
+        Support::fraction(self.min_support)?;
+        Confidence::fraction(self.min_confidence)?;
+        Lift::new(self.min_lift)?;
+        if let Some(min_weighted_support) = self.min_weighted_support {
+            Support::fraction(min_weighted_support)?;
+        }
+        for (name, threshold, min, max) in [
+            ("min_leverage", self.min_leverage, -0.25, 0.25),
+            ("min_jaccard", self.min_jaccard, 0.0, 1.0),
+            ("min_cosine", self.min_cosine, 0.0, 1.0),
+            ("min_kulczynski", self.min_kulczynski, 0.0, 1.0),
+            ("max_p_value", self.max_p_value, 0.0, 1.0),
+            ("min_quality_score", self.min_quality_score, 0.0, 1.0),
+            (
+                "max_consequent_base_rate",
+                self.max_consequent_base_rate,
+                0.0,
+                1.0,
+            ),
+        ] {
+            if threshold.is_some_and(|t| !(min..=max).contains(&t)) {
+                return Err(MiningError::InvalidConfig(format!(
+                    "{} must be between {} and {}, got {}",
+                    name,
+                    min,
+                    max,
+                    threshold.unwrap_or_default()
+                )));
+            }
+        }
+        if self.algorithm == MiningAlgorithm::Eclat && !self.fallback_to_apriori {
+            return Err(MiningError::InvalidConfig(
+                "Eclat is not implemented yet; use Apriori or FPGrowth, or set fallback_to_apriori"
+                    .to_string(),
+            ));
+        }
+        for (name, size) in [
+            ("max_antecedent_size", self.max_antecedent_size),
+            ("max_consequent_size", self.max_consequent_size),
+        ] {
+            if size == Some(0) {
+                return Err(MiningError::InvalidConfig(format!(
+                    "{} must be at least 1, or no rule can be generated",
+                    name
+                )));
+            }
+        }
+        self.constraints.validate()
+    }
+
+    /// Metrics to compute for rules: `compute_metrics`, plus
+    /// `MetricSet::EXTENDED` when an extended-metric threshold is set and
+    /// `MetricSet::SIGNIFICANCE` when `max_p_value` is
+    pub fn metric_set(&self) -> MetricSet {
+        let thresholds = [
+            self.min_leverage,
+            self.min_jaccard,
+            self.min_cosine,
+            self.min_kulczynski,
+        ];
+        let mut metrics = self.compute_metrics;
+        if thresholds.iter().any(Option::is_some) {
+            metrics |= MetricSet::EXTENDED;
+        }
+        if self.max_p_value.is_some() {
+            metrics |= MetricSet::SIGNIFICANCE;
+        }
+        metrics
+    }
+
+    /// Check rule metrics against the leverage/Jaccard/cosine/Kulczynski
+    /// thresholds and the uncorrected `max_p_value` (metrics that were not
+    /// computed fail a set threshold)
+    pub fn allows_metrics(&self, metrics: &PatternMetrics) -> bool {
+        [
+            (self.min_leverage, metrics.leverage),
+            (self.min_jaccard, metrics.jaccard),
+            (self.min_cosine, metrics.cosine),
+            (self.min_kulczynski, metrics.kulczynski),
+        ]
+        .iter()
+        .all(|(min, value)| min.is_none_or(|min| value.is_some_and(|v| v >= min)))
+            && self
+                .max_p_value
+                .is_none_or(|max| metrics.p_value.is_some_and(|p| p <= max))
+    }
+
+    /// Check whether a rule split satisfies the antecedent/consequent size caps
+    pub fn allows_split(&self, antecedent_len: usize, consequent_len: usize) -> bool {
+        self.max_antecedent_size
+            .is_none_or(|max| antecedent_len <= max)
+            && self
+                .max_consequent_size
+                .is_none_or(|max| consequent_len <= max)
+    }
+}
+
+/// File formats understood by `MiningConfig::from_file`/`to_file`
+enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    fn from_path(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("toml") => Ok(Self::Toml),
+            Some("json") => Ok(Self::Json),
+            Some("yaml" | "yml") => Ok(Self::Yaml),
+            _ => Err(MiningError::InvalidConfig(format!(
+                "{}: unsupported config format (expected .toml, .json or .yaml)",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Mining algorithm
+///
+/// Deserializes case-insensitively, ignoring `-`, `_` and spaces, so
+/// `"FPGrowth"`, `"fpgrowth"` and `"FP-Growth"` are all accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum MiningAlgorithm {
     /// Apriori algorithm (classic, easy to understand)
     Apriori,
@@ -47,3 +710,933 @@ pub enum MiningAlgorithm {
     #[allow(dead_code)]
     Eclat,
 }
+
+impl<'de> Deserialize<'de> for MiningAlgorithm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        let normalized: String = name
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match normalized.as_str() {
+            "apriori" => Ok(Self::Apriori),
+            "fpgrowth" => Ok(Self::FPGrowth),
+            "eclat" => Ok(Self::Eclat),
+            _ => Err(serde::de::Error::unknown_variant(
+                &name,
+                &["Apriori", "FPGrowth", "Eclat"],
+            )),
+        }
+    }
+}
+
+/// Kind of frequent itemsets produced by the mining layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ItemsetKind {
+    /// Every frequent itemset
+    #[default]
+    All,
+
+    /// Closed itemsets: no proper superset has the same support
+    Closed,
+
+    /// Maximal itemsets: no proper superset is frequent
+    Maximal,
+}
+
+/// What a unit of support counts, for `MiningConfig::support_universe`
+///
+/// Rows sharing a key are merged into one basket holding the union of their
+/// items before counting, and support is measured against the number of
+/// distinct keys. Merging happens before item constraints are applied.
+///
+/// # Example
+/// ```
+/// use chrono::Utc;
+/// use rust_rule_miner::config::SupportUniverse;
+/// use rust_rule_miner::Transaction;
+///
+/// let basket = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+/// let rows = vec![
+///     Transaction::with_user("o1", basket(&["Milk"]), Utc::now(), "alice"),
+///     Transaction::with_user("o2", basket(&["Bread"]), Utc::now(), "alice"),
+///     Transaction::with_user("o3", basket(&["Milk"]), Utc::now(), "bob"),
+/// ];
+///
+/// // Three orders, none holding Milk and Bread together...
+/// assert_eq!(SupportUniverse::Transactions.apply(&rows).len(), 3);
+///
+/// // ...but one of two customers bought both
+/// let users = SupportUniverse::DistinctUsers.apply(&rows);
+/// assert_eq!(users.len(), 2);
+/// assert_eq!(users[0].items, ["Milk", "Bread"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SupportUniverse {
+    /// Every row is a basket (support = share of rows)
+    #[default]
+    Transactions,
+
+    /// Rows with the same `Transaction::id` form one basket, e.g. order
+    /// lines exported one item per row (support = share of distinct ids)
+    DistinctIds,
+
+    /// Rows with the same `Transaction::user_id` form one basket (support =
+    /// share of distinct users); rows without a user count by their id
+    DistinctUsers,
+}
+
+impl SupportUniverse {
+    /// Transactions as baskets of this universe
+    ///
+    /// Merged baskets keep the first row's key, the earliest timestamp and
+    /// items in order of first appearance. Borrows for `Transactions`.
+    pub fn apply<'a>(&self, transactions: &'a [Transaction]) -> Cow<'a, [Transaction]> {
+        let key: fn(&Transaction) -> &str = match self {
+            SupportUniverse::Transactions => return Cow::Borrowed(transactions),
+            SupportUniverse::DistinctIds => |tx| &tx.id,
+            SupportUniverse::DistinctUsers => |tx| tx.user_id.as_deref().unwrap_or(&tx.id),
+        };
+
+        let mut baskets: Vec<Transaction> = Vec::new();
+        let mut seen: Vec<HashSet<&str>> = Vec::new();
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        for tx in transactions {
+            let position = *positions.entry(key(tx)).or_insert_with(|| {
+                let mut basket = tx.clone();
+                basket.items.clear();
+                baskets.push(basket);
+                seen.push(HashSet::new());
+                baskets.len() - 1
+            });
+            let basket = &mut baskets[position];
+            for item in &tx.items {
+                if seen[position].insert(item) {
+                    basket.items.push(item.clone());
+                }
+            }
+            basket.timestamp = basket.timestamp.min(tx.timestamp);
+        }
+        Cow::Owned(baskets)
+    }
+}
+
+/// Multiple testing correction for `MiningConfig::max_p_value`
+///
+/// Mining tests every candidate rule, so at p = 0.05 roughly one in twenty
+/// unrelated item pairs passes by chance. A correction accounts for the
+/// number of candidates tested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PValueCorrection {
+    /// Compare each raw p-value with `max_p_value`
+    #[default]
+    None,
+
+    /// Family-wise error control: p * candidates <= `max_p_value`
+    Bonferroni,
+
+    /// False discovery rate control (Benjamini-Hochberg step-up)
+    BenjaminiHochberg,
+}
+
+/// Item constraints on mined itemsets and rules
+///
+/// Excluded items are stripped from transactions and required items restrict
+/// mining to transactions containing them, so both cut runtime instead of
+/// filtering rules afterwards.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::config::RuleConstraints;
+/// use rust_rule_miner::MiningConfig;
+///
+/// let config = MiningConfig::builder()
+///     .constraints(
+///         RuleConstraints::new()
+///             .with_required_items(["New SKU"])
+///             .with_excluded_items(["Plastic Bag"]),
+///     )
+///     .build()?;
+/// assert!(config.constraints.allows_rule(&["Milk".to_string()], &["New SKU".to_string()]));
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleConstraints {
+    /// Every rule must mention at least one of these items (empty = no constraint)
+    pub required_items: Vec<String>,
+
+    /// Items removed from transactions before mining
+    pub excluded_items: Vec<String>,
+
+    /// Items that may appear only on the left-hand side of a rule
+    pub antecedent_only: Vec<String>,
+
+    /// Items that may appear only on the right-hand side of a rule
+    pub consequent_only: Vec<String>,
+}
+
+impl RuleConstraints {
+    /// No constraints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only mine rules mentioning at least one of these items
+    pub fn with_required_items<I: IntoIterator<Item = S>, S: Into<String>>(
+        mut self,
+        items: I,
+    ) -> Self {
+        self.required_items = items.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Strip these items from transactions before mining
+    pub fn with_excluded_items<I: IntoIterator<Item = S>, S: Into<String>>(
+        mut self,
+        items: I,
+    ) -> Self {
+        self.excluded_items = items.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Allow these items only in antecedents
+    pub fn with_antecedent_only<I: IntoIterator<Item = S>, S: Into<String>>(
+        mut self,
+        items: I,
+    ) -> Self {
+        self.antecedent_only = items.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Allow these items only in consequents
+    pub fn with_consequent_only<I: IntoIterator<Item = S>, S: Into<String>>(
+        mut self,
+        items: I,
+    ) -> Self {
+        self.consequent_only = items.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether no constraint is set
+    pub fn is_empty(&self) -> bool {
+        self.required_items.is_empty()
+            && self.excluded_items.is_empty()
+            && self.antecedent_only.is_empty()
+            && self.consequent_only.is_empty()
+    }
+
+    /// Reject contradictory constraints
+    pub fn validate(&self) -> Result<()> {
+        let conflicts = [
+            (
+                "required_items",
+                &self.required_items,
+                "excluded_items",
+                &self.excluded_items,
+            ),
+            (
+                "antecedent_only",
+                &self.antecedent_only,
+                "consequent_only",
+                &self.consequent_only,
+            ),
+            (
+                "antecedent_only",
+                &self.antecedent_only,
+                "excluded_items",
+                &self.excluded_items,
+            ),
+            (
+                "consequent_only",
+                &self.consequent_only,
+                "excluded_items",
+                &self.excluded_items,
+            ),
+        ];
+        for (name_a, a, name_b, b) in conflicts {
+            if let Some(item) = a.iter().find(|item| b.contains(item)) {
+                return Err(MiningError::InvalidConfig(format!(
+                    "constraints: `{}` is in both {} and {}",
+                    item, name_a, name_b
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Transactions as the mining algorithms should see them
+    ///
+    /// Drops excluded items and, when items are required, transactions that
+    /// contain none of them: itemsets with a required item can only be
+    /// counted in those. Support must still be computed against the original
+    /// transaction count. Borrows when there is nothing to change.
+    pub fn project<'a>(&self, transactions: &'a [Transaction]) -> Cow<'a, [Transaction]> {
+        if self.required_items.is_empty() && self.excluded_items.is_empty() {
+            return Cow::Borrowed(transactions);
+        }
+
+        Cow::Owned(
+            transactions
+                .iter()
+                .filter(|tx| self.required_items.is_empty() || self.mentions_required(&tx.items))
+                .map(|tx| {
+                    let mut tx = tx.clone();
+                    tx.items.retain(|item| !self.excluded_items.contains(item));
+                    tx
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether an itemset can yield rules under these constraints
+    pub fn allows_itemset(&self, items: &[String]) -> bool {
+        (self.required_items.is_empty() || self.mentions_required(items))
+            && !items.iter().any(|item| self.excluded_items.contains(item))
+    }
+
+    /// Whether a rule satisfies every constraint
+    pub fn allows_rule(&self, antecedent: &[String], consequent: &[String]) -> bool {
+        let both = || antecedent.iter().chain(consequent);
+        (self.required_items.is_empty() || both().any(|item| self.required_items.contains(item)))
+            && !both().any(|item| self.excluded_items.contains(item))
+            && !antecedent
+                .iter()
+                .any(|item| self.consequent_only.contains(item))
+            && !consequent
+                .iter()
+                .any(|item| self.antecedent_only.contains(item))
+    }
+
+    fn mentions_required(&self, items: &[String]) -> bool {
+        items.iter().any(|item| self.required_items.contains(item))
+    }
+}
+
+/// Set of rule metrics to compute, combined with `|`
+///
+/// Confidence, support and lift are always computed since the thresholds
+/// filter on them. Metrics outside the set are left as `None` in
+/// `PatternMetrics` and can be filled in later with `RuleMiner::recalibrate`.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::config::MetricSet;
+///
+/// let metrics = MetricSet::CONVICTION | MetricSet::EXTENDED;
+/// assert!(metrics.contains(MetricSet::EXTENDED));
+/// assert!(!metrics.contains(MetricSet::COUNTS));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MetricSet(u8);
+
+impl MetricSet {
+    /// Confidence, support and lift only
+    pub const CORE: Self = Self(0);
+
+    /// `PatternMetrics::conviction`
+    pub const CONVICTION: Self = Self(1);
+
+    /// `PatternMetrics::leverage`, `jaccard`, `cosine` and `kulczynski`
+    pub const EXTENDED: Self = Self(1 << 1);
+
+    /// Raw transaction counts in `PatternMetrics::counts`
+    pub const COUNTS: Self = Self(1 << 2);
+
+    /// Chi-square statistic and p-value (`PatternMetrics::chi_square`, `p_value`)
+    pub const SIGNIFICANCE: Self = Self(1 << 3);
+
+    /// Every metric
+    pub const ALL: Self = Self(0b1111);
+
+    /// Whether every metric in `other` is in this set
+    pub fn contains(self, other: MetricSet) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for MetricSet {
+    /// Core metrics plus conviction
+    fn default() -> Self {
+        Self::CONVICTION
+    }
+}
+
+impl std::ops::BitOr for MetricSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for MetricSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Builder for `MiningConfig` with typed, validated thresholds
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::config::{Confidence, Lift, Support};
+/// use rust_rule_miner::{MiningAlgorithm, MiningConfig, MiningConfigBuilder};
+///
+/// let config = MiningConfig::builder()
+///     .min_support(Support::percent(5)?)
+///     .min_confidence(Confidence::fraction(0.7)?)
+///     .min_lift(Lift::new(1.2)?)
+///     .algorithm(MiningAlgorithm::FPGrowth)
+///     .build()?;
+///
+/// assert_eq!(config.min_support(), 0.05);
+///
+/// // Or start from a preset
+/// let config = MiningConfigBuilder::from(MiningConfig::sparse_data())
+///     .max_itemset_size(3)
+///     .build()?;
+/// assert_eq!(config.algorithm, MiningAlgorithm::FPGrowth);
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MiningConfigBuilder {
+    config: MiningConfig,
+}
+
+impl From<MiningConfig> for MiningConfigBuilder {
+    /// Start from an existing config or preset, e.g. `MiningConfig::strict()`
+    fn from(config: MiningConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl MiningConfigBuilder {
+    /// Minimum support
+    pub fn min_support(mut self, support: Support) -> Self {
+        self.config.set_min_support(support);
+        self
+    }
+
+    /// Minimum confidence
+    pub fn min_confidence(mut self, confidence: Confidence) -> Self {
+        self.config.set_min_confidence(confidence);
+        self
+    }
+
+    /// Minimum lift
+    pub fn min_lift(mut self, lift: Lift) -> Self {
+        self.config.set_min_lift(lift);
+        self
+    }
+
+    /// Minimum weighted support (see `RuleMiner::set_item_weights`)
+    pub fn min_weighted_support(mut self, support: Support) -> Self {
+        self.config.min_weighted_support = Some(support.value());
+        self
+    }
+
+    /// Mining algorithm
+    pub fn algorithm(mut self, algorithm: MiningAlgorithm) -> Self {
+        self.config.algorithm = algorithm;
+        self
+    }
+
+    /// Maximum time gap for sequential patterns
+    pub fn max_time_gap(mut self, gap: Duration) -> Self {
+        self.config.max_time_gap = Some(gap);
+        self
+    }
+
+    /// Maximum number of items in a frequent itemset
+    pub fn max_itemset_size(mut self, size: usize) -> Self {
+        self.config.max_itemset_size = Some(size);
+        self
+    }
+
+    /// Maximum number of items on the left-hand side of a rule
+    pub fn max_antecedent_size(mut self, size: usize) -> Self {
+        self.config.max_antecedent_size = Some(size);
+        self
+    }
+
+    /// Maximum number of items on the right-hand side of a rule
+    pub fn max_consequent_size(mut self, size: usize) -> Self {
+        self.config.max_consequent_size = Some(size);
+        self
+    }
+
+    /// Which frequent itemsets to keep for rule generation
+    pub fn itemset_kind(mut self, kind: ItemsetKind) -> Self {
+        self.config.itemset_kind = kind;
+        self
+    }
+
+    /// Keep only the stronger direction of A => B / B => A pairs (default true)
+    pub fn dedup_bidirectional(mut self, dedup: bool) -> Self {
+        self.config.dedup_bidirectional = dedup;
+        self
+    }
+
+    /// Drop rules implied by a more general, at least as confident rule
+    pub fn prune_redundant(mut self, prune: bool) -> Self {
+        self.config.prune_redundant = prune;
+        self
+    }
+
+    /// Time budget for a mining run
+    pub fn max_duration(mut self, duration: Duration) -> Self {
+        self.config.max_duration = Some(duration);
+        self
+    }
+
+    /// Maximum number of frequent itemsets a mining run collects
+    pub fn max_frequent_itemsets(mut self, count: usize) -> Self {
+        self.config.max_frequent_itemsets = Some(count);
+        self
+    }
+
+    /// Warn instead of failing when `min_support` is met by one transaction
+    pub fn allow_singleton_support(mut self, allow: bool) -> Self {
+        self.config.allow_singleton_support = allow;
+        self
+    }
+
+    /// Supporting transaction IDs to keep per rule
+    pub fn collect_evidence(mut self, max_ids: usize) -> Self {
+        self.config.collect_evidence = Some(max_ids);
+        self
+    }
+
+    /// Leave transactions with these ids out of mining
+    pub fn exclude_transaction_ids<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config
+            .excluded_transaction_ids
+            .extend(ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Leave transactions from `start` (inclusive) to `end` (exclusive) out
+    /// of mining
+    pub fn exclude_time_range(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.config.excluded_time_ranges.push((start, end));
+        self
+    }
+
+    /// What one unit of support counts
+    pub fn support_universe(mut self, universe: SupportUniverse) -> Self {
+        self.config.support_universe = universe;
+        self
+    }
+
+    /// Which rule metrics to compute beyond confidence, support and lift
+    pub fn compute_metrics(mut self, metrics: MetricSet) -> Self {
+        self.config.compute_metrics = metrics;
+        self
+    }
+
+    /// Minimum leverage
+    pub fn min_leverage(mut self, leverage: f64) -> Self {
+        self.config.min_leverage = Some(leverage);
+        self
+    }
+
+    /// Minimum Jaccard coefficient
+    pub fn min_jaccard(mut self, jaccard: f64) -> Self {
+        self.config.min_jaccard = Some(jaccard);
+        self
+    }
+
+    /// Minimum cosine
+    pub fn min_cosine(mut self, cosine: f64) -> Self {
+        self.config.min_cosine = Some(cosine);
+        self
+    }
+
+    /// Minimum Kulczynski measure
+    pub fn min_kulczynski(mut self, kulczynski: f64) -> Self {
+        self.config.min_kulczynski = Some(kulczynski);
+        self
+    }
+
+    /// Maximum chi-square p-value
+    pub fn max_p_value(mut self, p_value: f64) -> Self {
+        self.config.max_p_value = Some(p_value);
+        self
+    }
+
+    /// Multiple testing correction applied with `max_p_value`
+    pub fn p_value_correction(mut self, correction: PValueCorrection) -> Self {
+        self.config.p_value_correction = correction;
+        self
+    }
+
+    /// Retry with Apriori when the configured algorithm fails
+    pub fn fallback_to_apriori(mut self, fallback: bool) -> Self {
+        self.config.fallback_to_apriori = fallback;
+        self
+    }
+
+    /// Warn when the dataset quality score is below this
+    pub fn min_quality_score(mut self, score: f64) -> Self {
+        self.config.min_quality_score = Some(score);
+        self
+    }
+
+    /// Never recommend items whose own support exceeds `base_rate`
+    pub fn max_consequent_base_rate(mut self, base_rate: f64) -> Self {
+        self.config.max_consequent_base_rate = Some(base_rate);
+        self
+    }
+
+    /// Item include/exclude constraints
+    pub fn constraints(mut self, constraints: RuleConstraints) -> Self {
+        self.config.constraints = constraints;
+        self
+    }
+
+    /// Validate and return the config
+    pub fn build(self) -> Result<MiningConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+/// Time ranges as `"start/end"` RFC 3339 strings, which flat config files
+/// can hold
+pub(crate) mod time_ranges {
+    use chrono::{DateTime, Utc};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    type Range = (DateTime<Utc>, DateTime<Utc>);
+
+    pub fn serialize<S: Serializer>(ranges: &[Range], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            ranges
+                .iter()
+                .map(|(start, end)| format!("{}/{}", start.to_rfc3339(), end.to_rfc3339())),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Range>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|range| {
+                let parse = |time: &str| {
+                    DateTime::parse_from_rfc3339(time.trim()).map(|t| t.with_timezone(&Utc))
+                };
+                range
+                    .split_once('/')
+                    .and_then(|(start, end)| Some((parse(start).ok()?, parse(end).ok()?)))
+                    .ok_or_else(|| {
+                        D::Error::custom(format!(
+                            "invalid time range `{}` (expected \"<RFC 3339>/<RFC 3339>\")",
+                            range
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_support_constructors() {
+        assert_eq!(Support::fraction(0.3).unwrap().value(), 0.3);
+        assert_eq!(Support::percent(30).unwrap().value(), 0.3);
+        assert_eq!(Support::fraction(0).unwrap().value(), 0.0);
+
+        let err = Support::fraction(30.0).unwrap_err().to_string();
+        assert!(err.contains("Support::percent(30)"), "{}", err);
+        assert!(Support::fraction(-0.1).is_err());
+        assert!(Support::fraction(f64::NAN).is_err());
+        assert!(Support::percent(101).is_err());
+    }
+
+    #[test]
+    fn test_confidence_and_lift_constructors() {
+        assert_eq!(Confidence::percent(70).unwrap().value(), 0.7);
+        assert_eq!(Confidence::fraction(1.0).unwrap().value(), 1.0);
+        assert!(Confidence::fraction(1.5).is_err());
+        assert!(Confidence::percent(-5).is_err());
+
+        assert_eq!(Lift::new(1.2).unwrap().value(), 1.2);
+        assert_eq!(Lift::new(3).unwrap().value(), 3.0);
+        assert!(Lift::new(-1.0).is_err());
+        assert!(Lift::new(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_builder_sets_thresholds() {
+        let config = MiningConfig::builder()
+            .min_support(Support::percent(5).unwrap())
+            .min_confidence(Confidence::fraction(0.6).unwrap())
+            .min_lift(Lift::new(1.5).unwrap())
+            .max_itemset_size(3)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.min_support(), 0.05);
+        assert_eq!(config.min_confidence(), 0.6);
+        assert_eq!(config.min_lift(), 1.5);
+        assert_eq!(config.max_itemset_size, Some(3));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_fields_still_work() {
+        // Set directly on a built config, as struct literals are unavailable
+        let mut config = MiningConfig::builder().build().unwrap();
+        config.min_support = 0.3;
+        config.min_confidence = 0.8;
+        assert_eq!(config.min_support(), 0.3);
+        assert_eq!(config.min_confidence, 0.8);
+        assert!(config.validate().is_ok());
+
+        let mut typo = MiningConfig::builder().build().unwrap();
+        typo.min_support = 30.0;
+        assert!(matches!(
+            typo.validate(),
+            Err(MiningError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let config = MiningConfig::builder().build().unwrap();
+        assert_eq!(config, MiningConfig::default());
+        assert_eq!(config.min_support(), 0.1);
+        assert_eq!(config.min_confidence(), 0.7);
+        assert_eq!(config.min_lift(), 1.0);
+        assert_eq!(config.algorithm, MiningAlgorithm::Apriori);
+        assert_eq!(config.max_time_gap, None);
+        assert_eq!(config.compute_metrics, MetricSet::default());
+    }
+
+    #[test]
+    fn test_builder_covers_every_field() {
+        let day = |d| DateTime::parse_from_rfc3339(d).unwrap().with_timezone(&Utc);
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.2).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .min_lift(Lift::new(1.5).unwrap())
+            .min_weighted_support(Support::fraction(0.1).unwrap())
+            .min_leverage(0.01)
+            .min_jaccard(0.1)
+            .min_cosine(0.1)
+            .min_kulczynski(0.1)
+            .max_p_value(0.05)
+            .p_value_correction(PValueCorrection::Bonferroni)
+            .max_time_gap(Duration::from_secs(3600))
+            .algorithm(MiningAlgorithm::FPGrowth)
+            .max_itemset_size(3)
+            .max_antecedent_size(2)
+            .max_consequent_size(1)
+            .itemset_kind(ItemsetKind::Closed)
+            .compute_metrics(MetricSet::ALL)
+            .constraints(RuleConstraints::new().with_excluded_items(["Bag"]))
+            .fallback_to_apriori(true)
+            .min_quality_score(0.5)
+            .max_consequent_base_rate(0.4)
+            .support_universe(SupportUniverse::DistinctUsers)
+            .dedup_bidirectional(false)
+            .prune_redundant(true)
+            .max_duration(Duration::from_secs(30))
+            .max_frequent_itemsets(1000)
+            .allow_singleton_support(true)
+            .collect_evidence(5)
+            .exclude_transaction_ids(["tx-9913"])
+            .exclude_time_range(day("2024-03-03T00:00:00Z"), day("2024-03-04T00:00:00Z"))
+            .build()
+            .unwrap();
+
+        // A field left at its default has no builder method above
+        let built = serde_json::to_value(&config).unwrap();
+        let default = serde_json::to_value(MiningConfig::default()).unwrap();
+        for (field, value) in default.as_object().unwrap() {
+            assert_ne!(&built[field], value, "no builder method sets `{}`", field);
+        }
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_config() {
+        let eclat = MiningConfig::builder()
+            .algorithm(MiningAlgorithm::Eclat)
+            .build();
+        assert!(matches!(eclat, Err(MiningError::InvalidConfig(_))));
+
+        let err = MiningConfig::builder()
+            .max_consequent_size(0)
+            .build()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("max_consequent_size"), "{}", err);
+    }
+
+    #[test]
+    fn test_presets() {
+        for preset in [
+            MiningConfig::strict(),
+            MiningConfig::exploratory(),
+            MiningConfig::sparse_data(),
+        ] {
+            assert!(preset.validate().is_ok());
+        }
+        assert!(
+            MiningConfig::strict().min_confidence() > MiningConfig::exploratory().min_confidence()
+        );
+        assert_eq!(
+            MiningConfig::sparse_data().algorithm,
+            MiningAlgorithm::FPGrowth
+        );
+
+        // Presets are a starting point for the builder
+        let config = MiningConfigBuilder::from(MiningConfig::strict())
+            .max_itemset_size(3)
+            .build()
+            .unwrap();
+        assert_eq!(config.min_support(), 0.1);
+        assert_eq!(config.max_itemset_size, Some(3));
+    }
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/config")
+            .join(name)
+    }
+
+    #[test]
+    fn test_from_file_formats_agree() {
+        let expected = MiningConfig::builder()
+            .min_support(Support::fraction(0.05).unwrap())
+            .min_confidence(Confidence::fraction(0.6).unwrap())
+            .min_lift(Lift::new(1.2).unwrap())
+            .algorithm(MiningAlgorithm::FPGrowth)
+            .max_antecedent_size(2)
+            .compute_metrics(MetricSet::ALL)
+            .max_time_gap(Duration::from_secs(3600))
+            .build()
+            .unwrap();
+
+        for name in ["mining.toml", "mining.json", "mining.yaml"] {
+            let config = MiningConfig::from_file(fixture(name)).unwrap();
+            assert_eq!(config, expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_from_file_errors_name_path_and_field() {
+        let err = MiningConfig::from_file(fixture("malformed.toml"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("malformed.toml"), "{}", err);
+        assert!(err.contains("line 2"), "{}", err);
+
+        let err = MiningConfig::from_file(fixture("bad_algorithm.yaml"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("bad_algorithm.yaml"), "{}", err);
+        assert!(err.contains("field `algorithm`"), "{}", err);
+        assert!(err.contains("k-means"), "{}", err);
+
+        let err = MiningConfig::from_file(fixture("missing.json"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("missing.json: cannot read file"), "{}", err);
+        assert!(MiningConfig::from_file(fixture("mining.ini")).is_err());
+    }
+
+    #[test]
+    fn test_from_file_validates() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!(
+            "rust_rule_miner_config_{}.json",
+            std::process::id()
+        ));
+
+        std::fs::write(&path, r#"{ "min_support": 30.0 }"#).unwrap();
+        let err = MiningConfig::from_file(&path).unwrap_err().to_string();
+        assert!(err.contains("min_support must be a fraction"), "{}", err);
+
+        std::fs::write(&path, r#"{ "min_suport": 0.3 }"#).unwrap();
+        let err = MiningConfig::from_file(&path).unwrap_err().to_string();
+        assert!(err.contains("unknown field `min_suport`"), "{}", err);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_to_file_round_trip() {
+        let config = MiningConfig::builder()
+            .min_support(Support::percent(2.5).unwrap())
+            .algorithm(MiningAlgorithm::FPGrowth)
+            .max_time_gap(Duration::from_millis(1500))
+            .itemset_kind(ItemsetKind::Closed)
+            .exclude_transaction_ids(["tx9"])
+            .exclude_time_range(
+                "2024-03-03T00:00:00Z".parse().unwrap(),
+                "2024-03-04T00:00:00Z".parse().unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        for extension in ["toml", "json", "yaml"] {
+            let path = std::env::temp_dir().join(format!(
+                "rust_rule_miner_config_round_trip_{}.{}",
+                std::process::id(),
+                extension
+            ));
+            config.to_file(&path).unwrap();
+            let loaded = MiningConfig::from_file(&path);
+            std::fs::remove_file(&path).ok();
+            assert_eq!(loaded.unwrap(), config, "{}", extension);
+        }
+    }
+
+    #[test]
+    fn test_algorithm_names_case_insensitive() {
+        for name in ["FPGrowth", "fpgrowth", "FP-Growth", "fp_growth"] {
+            let algorithm: MiningAlgorithm =
+                serde_json::from_value(serde_json::json!(name)).unwrap();
+            assert_eq!(algorithm, MiningAlgorithm::FPGrowth);
+        }
+        assert_eq!(
+            serde_json::to_value(MiningAlgorithm::FPGrowth).unwrap(),
+            "FPGrowth"
+        );
+    }
+
+    #[test]
+    fn test_constraints_validate_and_parse() {
+        let conflicting = MiningConfig::builder()
+            .constraints(
+                RuleConstraints::new()
+                    .with_required_items(["Milk"])
+                    .with_excluded_items(["Milk"]),
+            )
+            .build()
+            .unwrap_err()
+            .to_string();
+        assert!(conflicting.contains("`Milk` is in both required_items and excluded_items"));
+
+        let config: MiningConfig = toml::from_str(
+            "min_support = 0.05\n\n[constraints]\nexcluded_items = [\"Plastic Bag\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.constraints.excluded_items, vec!["Plastic Bag"]);
+        assert!(config.constraints.required_items.is_empty());
+
+        let constraints = RuleConstraints::new().with_consequent_only(["Promo"]);
+        let items = |names: &[&str]| names.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(constraints.allows_rule(&items(&["Milk"]), &items(&["Promo"])));
+        assert!(!constraints.allows_rule(&items(&["Promo"]), &items(&["Milk"])));
+    }
+}
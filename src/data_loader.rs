@@ -3,6 +3,9 @@
 //! Provides high-performance streaming loading of transaction data from:
 //! - Excel files (.xlsx) - ultra-low memory streaming
 //! - CSV files (.csv) - memory-efficient streaming
+//! - JSON Lines (.jsonl) and JSON array files, mapped by `JsonMapping`
+//! - SPMF/FIMI benchmark files (`retail.dat`, `kosarak.dat`, `T10I4D100K.dat`)
+//! - Parquet files (`parquet` feature), streamed batch by batch
 //!
 //! # Column Mapping (v0.2.0+)
 //!
@@ -10,7 +13,7 @@
 //! This provides full flexibility for any data schema.
 //!
 //! ```no_run
-//! use rust_rule_miner::data_loader::{DataLoader, ColumnMapping};
+//! use rust_rule_miner::data_loader::{BinnedColumn, DataLoader, ColumnMapping};
 //!
 //! // CSV: customer_id, product, category, price, location, timestamp
 //! //      0            1        2         3      4         5
@@ -23,6 +26,14 @@
 //! let mapping = ColumnMapping::multi_field(0, vec![1, 2], 5, "::".to_string());
 //! let transactions = DataLoader::from_csv("sales.csv", mapping)?;
 //! // Items: "Laptop::Electronics", "Mouse::Accessories"
+//!
+//! // Also mine the price as "price:low", "price:medium" or "price:high"
+//! let mapping = ColumnMapping::simple(0, 1, 5).with_binned_column(BinnedColumn::thresholds(
+//!     3,
+//!     "price:",
+//!     vec![(20.0, "low".to_string()), (100.0, "medium".to_string()), (f64::INFINITY, "high".to_string())],
+//! ));
+//! let transactions = DataLoader::from_csv("sales.csv", mapping)?;
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 //!
@@ -43,17 +54,46 @@
 //! ```
 
 use crate::errors::{MiningError, Result};
+use crate::normalize::ItemNormalizer;
+use crate::rng::{clock_seed, SplitMix64};
 use crate::Transaction;
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use excelstream::csv::CsvParser;
+use excelstream::fast_writer::StreamingZipReader;
 use excelstream::streaming_reader::StreamingReader;
 use excelstream::CsvReader;
-use std::path::Path;
+use flate2::bufread::MultiGzDecoder;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Boxed iterator of transactions for streaming consumers
+///
+/// Row-level errors are yielded as `Err` items so the caller decides whether
+/// to skip or abort.
+pub type TransactionStream = Box<dyn Iterator<Item = Result<Transaction>>>;
+
+/// Rows `DataLoader::stream_excel` reads ahead of its consumer
+const EXCEL_STREAM_BUFFER: usize = 1024;
+
+/// Leading bytes of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Raw rows of a delimited file; a read error ends it
+type CsvRows<'a> = Box<dyn Iterator<Item = Result<Vec<String>>> + 'a>;
 
 /// Column mapping configuration for flexible data loading
 ///
 /// Allows you to specify which columns to mine from your data,
 /// supporting multiple fields combined into patterns.
+///
+/// `#[non_exhaustive]` so options can be added in minor releases; outside
+/// this crate, start from `simple`, `multi_field` or `from_names` and adjust
+/// with the `with_*` methods.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ColumnMapping {
     /// Column index for transaction/group ID (0-based)
     pub transaction_id: usize,
@@ -63,6 +103,860 @@ pub struct ColumnMapping {
     pub timestamp: usize,
     /// Separator to combine multiple item columns (default: "::")
     pub field_separator: String,
+    /// Normalization applied to each loaded item (default: none)
+    pub normalizer: ItemNormalizer,
+    /// What to do when item columns hold different numbers of values
+    /// (default: `MultiFieldPolicy::Lenient`)
+    pub multi_field_policy: MultiFieldPolicy,
+    /// Numeric columns added to each row's items as bin labels (default: none)
+    pub binned_columns: Vec<BinnedColumn>,
+    /// How the timestamp column is parsed (default: `TimestampSpec::default()`)
+    pub timestamp_spec: TimestampSpec,
+    /// Column index for `Transaction::user_id` (default: none)
+    pub user_id_column: Option<usize>,
+    /// Column indices copied into `Transaction::metadata` under their keys
+    /// (default: none)
+    pub metadata_columns: Vec<(usize, String)>,
+}
+
+/// Numeric column mined as a discrete item such as `"price:high"`
+///
+/// Empty cells add no item. A value that is not a number skips the row with
+/// a warning, like any other unparseable row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinnedColumn {
+    /// Column index (0-based)
+    pub index: usize,
+    pub bins: Binning,
+    /// Prepended to the bin label, e.g. `"price:"`
+    pub label_prefix: String,
+}
+
+/// How a `BinnedColumn` maps values to labels
+#[derive(Debug, Clone, PartialEq)]
+pub enum Binning {
+    /// `(upper bound, label)` pairs in ascending order: a value takes the
+    /// first label whose bound it does not exceed, values above every bound
+    /// the last label
+    Thresholds(Vec<(f64, String)>),
+
+    /// This many equal-frequency bins over the loaded values, labelled `q1`
+    /// (lowest) to `qN`; equal values share a bin
+    Quantile(usize),
+}
+
+impl BinnedColumn {
+    /// Bin column `index` by fixed upper bounds
+    pub fn thresholds(
+        index: usize,
+        label_prefix: impl Into<String>,
+        bins: Vec<(f64, String)>,
+    ) -> Self {
+        Self {
+            index,
+            bins: Binning::Thresholds(bins),
+            label_prefix: label_prefix.into(),
+        }
+    }
+
+    /// Bin column `index` into `bins` quantiles of the loaded values
+    pub fn quantiles(index: usize, label_prefix: impl Into<String>, bins: usize) -> Self {
+        Self {
+            index,
+            bins: Binning::Quantile(bins),
+            label_prefix: label_prefix.into(),
+        }
+    }
+
+    /// Numeric value of this column in a row, None when the cell is empty
+    fn value(&self, row_values: &[String], row_idx: usize) -> Result<Option<f64>> {
+        let cell = row_values[self.index].trim();
+        if cell.is_empty() {
+            return Ok(None);
+        }
+        cell.parse::<f64>()
+            .ok()
+            .filter(|value| !value.is_nan())
+            .map(Some)
+            .ok_or_else(|| {
+                MiningError::DataLoadError(format!(
+                    "Row {} column {}: `{}` is not a number",
+                    row_idx, self.index, cell
+                ))
+            })
+    }
+
+    /// Item for a value under fixed thresholds (None for quantiles, which
+    /// need every value first)
+    fn threshold_item(&self, value: f64) -> Option<String> {
+        let Binning::Thresholds(bins) = &self.bins else {
+            return None;
+        };
+        let (_, label) = bins
+            .iter()
+            .find(|(bound, _)| value <= *bound)
+            .or(bins.last())?;
+        Some(format!("{}{}", self.label_prefix, label))
+    }
+}
+
+/// What to do when a row's item columns hold different numbers of values
+///
+/// Multi-field mappings zip columns by position, so `"Laptop,Mouse"` next to
+/// `"Electronics"` leaves Mouse without a category. Affected rows are listed
+/// in `LoadReport::uneven_rows` whatever the policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum MultiFieldPolicy {
+    /// Join the values each position has, logging a warning: the example
+    /// yields "Laptop::Electronics" and a partial "Mouse"
+    #[default]
+    Lenient,
+
+    /// Drop positions past the shortest column: only "Laptop::Electronics"
+    TruncateToShortest,
+
+    /// Fill missing values with a placeholder: `PadWithPlaceholder("?")`
+    /// yields "Mouse::?"
+    PadWithPlaceholder(String),
+
+    /// Skip the row
+    SkipRow,
+
+    /// Fail the load with `MiningError::InvalidTransaction`
+    Error,
+}
+
+/// What happened while loading a file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    /// Data rows read, excluding the header
+    pub rows_read: usize,
+    /// Transactions loaded
+    pub transactions: usize,
+    /// Data rows skipped as unparseable or by `MultiFieldPolicy::SkipRow`
+    pub skipped_rows: usize,
+    /// Data rows dropped by `LoaderOptions::row_filter`
+    pub filtered_rows: usize,
+    /// Row number and reason of each skipped row, unless
+    /// `LoaderOptions::collect_report` is off
+    pub skip_reasons: Vec<(usize, String)>,
+    /// File rows (the header being row 1) whose item columns held different
+    /// numbers of values
+    pub uneven_rows: Vec<usize>,
+    /// Rows whose timestamp did not parse and were stamped per
+    /// `InvalidTimestampPolicy::UseEpoch` or `UseNow`
+    pub timestamp_fallbacks: Vec<usize>,
+}
+
+/// Predicate on a row's raw cells; rows it rejects are not loaded
+pub type RowFilter = Arc<dyn Fn(&[String]) -> bool + Send + Sync>;
+
+/// Rewrite of one item; `None` drops it
+pub type ItemTransform = Arc<dyn Fn(String) -> Option<String> + Send + Sync>;
+
+/// How loaders treat rows they cannot parse, and which rows and items they
+/// keep
+///
+/// The default is lenient: bad rows are skipped with a warning and listed
+/// in the `LoadReport`.
+///
+/// # Example
+/// ```no_run
+/// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader, LoaderOptions};
+///
+/// // Fail on the first bad row, naming it
+/// let options = LoaderOptions::strict();
+/// let (transactions, _) =
+///     DataLoader::from_csv_with_options("sales.csv", ColumnMapping::simple(0, 1, 2), options)?;
+///
+/// // Tolerate a few bad rows, but not a broken export
+/// let options = LoaderOptions::default().with_max_errors(10);
+/// let (transactions, report) =
+///     DataLoader::from_csv_with_options("sales.csv", ColumnMapping::simple(0, 1, 2), options)?;
+/// for (row, reason) in &report.skip_reasons {
+///     eprintln!("row {}: {}", row, reason);
+/// }
+///
+/// // Drop soft-deleted rows and mine the first word of each product name
+/// let options = LoaderOptions::default()
+///     .with_row_filter(|row| row.get(4).is_none_or(|deleted| deleted != "true"))
+///     .with_item_transform(|product| product.split_whitespace().next().map(str::to_string));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone)]
+pub struct LoaderOptions {
+    /// Fail with `DataLoadError` at the first row that is skipped or
+    /// given a fallback timestamp, quoting its row number and content
+    pub strict: bool,
+    /// Fail once more than this many rows are skipped (default: no limit)
+    pub max_errors: usize,
+    /// Record why each row was skipped in `LoadReport::skip_reasons`
+    /// (default: on)
+    pub collect_report: bool,
+    /// Rows of delimited and spreadsheet files for which this returns false
+    /// are dropped before parsing and counted in `LoadReport::filtered_rows`
+    pub row_filter: Option<RowFilter>,
+    /// Applied to each item before normalization; items it drops or maps
+    /// onto an earlier item of the row are left out
+    pub item_transform: Option<ItemTransform>,
+    /// Stop after this many data rows are taken (default: all)
+    pub limit: Option<usize>,
+    /// Data rows to pass over before taking any, after the header
+    pub skip_rows: Option<usize>,
+    /// Take each remaining data row with this probability, in (0, 1]
+    pub sample: Option<f64>,
+    /// Seed for `sample`; without one each load draws a different sample
+    pub seed: Option<u64>,
+}
+
+impl Default for LoaderOptions {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            max_errors: usize::MAX,
+            collect_report: true,
+            row_filter: None,
+            item_transform: None,
+            limit: None,
+            skip_rows: None,
+            sample: None,
+            seed: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for LoaderOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoaderOptions")
+            .field("strict", &self.strict)
+            .field("max_errors", &self.max_errors)
+            .field("collect_report", &self.collect_report)
+            .field("row_filter", &self.row_filter.is_some())
+            .field("item_transform", &self.item_transform.is_some())
+            .field("limit", &self.limit)
+            .field("skip_rows", &self.skip_rows)
+            .field("sample", &self.sample)
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+impl LoaderOptions {
+    /// Fail at the first bad row
+    pub fn strict() -> Self {
+        Self {
+            strict: true,
+            ..Self::default()
+        }
+    }
+
+    /// Fail once more than `max_errors` rows are skipped
+    pub fn with_max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = max_errors;
+        self
+    }
+
+    /// Record skip reasons in the report, or only count skipped rows
+    pub fn with_collect_report(mut self, collect: bool) -> Self {
+        self.collect_report = collect;
+        self
+    }
+
+    /// Load only rows for which `filter` returns true
+    pub fn with_row_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&[String]) -> bool + Send + Sync + 'static,
+    {
+        self.row_filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Rewrite each item with `transform`, dropping those it maps to `None`
+    pub fn with_item_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(String) -> Option<String> + Send + Sync + 'static,
+    {
+        self.item_transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Load at most `limit` data rows, stopping the read there
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Pass over the first `skip_rows` data rows
+    pub fn with_skip_rows(mut self, skip_rows: usize) -> Self {
+        self.skip_rows = Some(skip_rows);
+        self
+    }
+
+    /// Load a random `fraction` of the data rows, each row drawn
+    /// independently so the read still streams
+    pub fn with_sample(mut self, fraction: f64) -> Self {
+        self.sample = Some(fraction);
+        self
+    }
+
+    /// Draw the same sample on every load
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Items after `item_transform`, without those it drops or duplicates
+    fn transform_items(&self, items: Vec<String>) -> Vec<String> {
+        let Some(transform) = &self.item_transform else {
+            return items;
+        };
+        let mut seen = HashSet::new();
+        items
+            .into_iter()
+            .filter_map(transform.as_ref())
+            .filter(|item| !item.is_empty() && seen.insert(item.clone()))
+            .collect()
+    }
+}
+
+/// Layout of a delimited text file
+///
+/// The default is a comma-delimited file with a header row and
+/// comma-separated items within a cell.
+///
+/// # Example
+/// ```no_run
+/// use rust_rule_miner::data_loader::{ColumnMapping, CsvOptions, DataLoader, LoaderOptions};
+///
+/// // order;items;at
+/// // o1;"Laptop,Mouse";15/01/2024
+/// let csv = CsvOptions::default().with_delimiter(b';');
+/// let (transactions, _) = DataLoader::from_csv_with_format(
+///     "orders_eu.csv",
+///     ColumnMapping::simple(0, 1, 2),
+///     csv,
+///     LoaderOptions::default(),
+/// )?;
+///
+/// // Tab-delimited, no header, items as "Laptop|Mouse"
+/// let csv = CsvOptions::default()
+///     .with_delimiter(b'\t')
+///     .with_header(false)
+///     .with_item_separator("|");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Column delimiter (default: `b','`)
+    pub delimiter: u8,
+    /// Quote character around cells holding the delimiter (default: `b'"'`)
+    pub quote: u8,
+    /// Whether the first row is a header to skip (default: true)
+    pub has_header: bool,
+    /// Separator between items within an item cell (default: ",")
+    pub item_separator: String,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            has_header: true,
+            item_separator: ",".to_string(),
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Split columns on `delimiter`, e.g. `b';'` or `b'\t'`
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Quote cells with `quote`
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Whether the first row is a header; without one it is data row 1
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /// Split item cells on `separator` instead of commas
+    pub fn with_item_separator(mut self, separator: impl Into<String>) -> Self {
+        self.item_separator = separator.into();
+        self
+    }
+}
+
+/// Request settings for `DataLoader::from_http_with_options` (requires
+/// `cloud` feature)
+///
+/// Requests fail on any status other than 2xx. Connection errors, timeouts,
+/// 429 and 5xx responses are retried up to `retries` times with a doubling
+/// delay, but only before the body starts arriving.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use rust_rule_miner::data_loader::{CsvOptions, HttpOptions};
+///
+/// let options = HttpOptions::default()
+///     .with_bearer_token("s3cr3t")
+///     .with_header("X-Tenant", "eu-west")
+///     .with_timeout(Duration::from_secs(30))
+///     .with_retries(3)
+///     .with_csv(CsvOptions::default().with_delimiter(b';'));
+/// ```
+#[cfg(feature = "cloud")]
+#[derive(Debug, Clone, Default)]
+pub struct HttpOptions {
+    /// Extra request headers as (name, value)
+    pub headers: Vec<(String, String)>,
+    /// Sent as `Authorization: Bearer <token>`
+    pub bearer_token: Option<String>,
+    /// Username and optional password sent as HTTP basic auth
+    pub basic_auth: Option<(String, Option<String>)>,
+    /// Limit on each attempt, body included (default: none)
+    pub timeout: Option<std::time::Duration>,
+    /// Attempts after the first on transient failures (default: 0)
+    pub retries: u32,
+    /// Layout of the CSV body
+    pub csv: CsvOptions,
+}
+
+#[cfg(feature = "cloud")]
+impl HttpOptions {
+    /// Send header `name: value` with the request
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Authenticate with a bearer token
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Authenticate with HTTP basic auth
+    pub fn with_basic_auth(
+        mut self,
+        username: impl Into<String>,
+        password: Option<impl Into<String>>,
+    ) -> Self {
+        self.basic_auth = Some((username.into(), password.map(Into::into)));
+        self
+    }
+
+    /// Give up on an attempt after `timeout`
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry transient failures up to `retries` times
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Parse the body per `csv`
+    pub fn with_csv(mut self, csv: CsvOptions) -> Self {
+        self.csv = csv;
+        self
+    }
+}
+
+/// How timestamp values are parsed
+///
+/// The default reads RFC 3339, Unix seconds and common day-first layouts
+/// (`15/01/2024 10:30:00`), takes naive datetimes as UTC and skips rows whose
+/// timestamp matches none of them.
+///
+/// # Example
+/// ```
+/// use chrono::FixedOffset;
+/// use rust_rule_miner::data_loader::{ColumnMapping, InvalidTimestampPolicy, TimestampSpec};
+///
+/// // US export in Eastern Standard Time: "01/15/2024 10:30"
+/// let spec = TimestampSpec::format("%m/%d/%Y %H:%M")
+///     .with_offset(FixedOffset::west_opt(5 * 3600).unwrap())
+///     .with_invalid_policy(InvalidTimestampPolicy::Error);
+/// let mapping = ColumnMapping::simple(0, 1, 2).with_timestamp_spec(spec);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampSpec {
+    pub format: TimestampFormat,
+    /// Offset of datetimes that carry none (default: UTC)
+    pub offset: FixedOffset,
+    /// Whether `TimestampFormat::Auto` reads `01/02/2024` as 1 February or
+    /// 2 January (default: `DateOrder::DayFirst`)
+    pub date_order: DateOrder,
+    /// What to do with values that do not parse (default:
+    /// `InvalidTimestampPolicy::SkipRow`)
+    pub invalid_policy: InvalidTimestampPolicy,
+}
+
+/// Layout of timestamp values
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TimestampFormat {
+    /// RFC 3339, Unix seconds, or a `%Y-%m-%d` or `DateOrder` layout with an
+    /// optional `%H:%M:%S` time
+    #[default]
+    Auto,
+
+    /// A chrono format string such as `"%d.%m.%Y %H:%M"`; date-only formats
+    /// give midnight
+    Custom(String),
+
+    /// Integer seconds since the Unix epoch
+    UnixSeconds,
+
+    /// Integer milliseconds since the Unix epoch
+    UnixMillis,
+}
+
+/// Day and month order of `TimestampFormat::Auto` slash and dash dates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DateOrder {
+    /// `15/01/2024`
+    #[default]
+    DayFirst,
+    /// `01/15/2024`
+    MonthFirst,
+}
+
+/// What to do with a timestamp that does not parse
+///
+/// `UseEpoch` and `UseNow` rows are listed in `LoadReport::timestamp_fallbacks`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InvalidTimestampPolicy {
+    /// Fail the load with `MiningError::InvalidTransaction`
+    Error,
+
+    /// Skip the row, like any other unparseable row
+    #[default]
+    SkipRow,
+
+    /// Use 1970-01-01T00:00:00Z, which time-based analysis can filter out
+    UseEpoch,
+
+    /// Use the load time; gaps and windows involving the row are meaningless
+    UseNow,
+}
+
+impl Default for TimestampSpec {
+    fn default() -> Self {
+        Self {
+            format: TimestampFormat::Auto,
+            offset: FixedOffset::east_opt(0).expect("zero offset is valid"),
+            date_order: DateOrder::DayFirst,
+            invalid_policy: InvalidTimestampPolicy::SkipRow,
+        }
+    }
+}
+
+impl TimestampSpec {
+    /// Parse with a chrono format string
+    pub fn format(format: impl Into<String>) -> Self {
+        Self {
+            format: TimestampFormat::Custom(format.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Parse integer seconds since the Unix epoch
+    pub fn unix_seconds() -> Self {
+        Self {
+            format: TimestampFormat::UnixSeconds,
+            ..Self::default()
+        }
+    }
+
+    /// Parse integer milliseconds since the Unix epoch
+    pub fn unix_millis() -> Self {
+        Self {
+            format: TimestampFormat::UnixMillis,
+            ..Self::default()
+        }
+    }
+
+    /// Read datetimes without an offset at `offset`
+    pub fn with_offset(mut self, offset: FixedOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Read `TimestampFormat::Auto` dates day-first or month-first
+    pub fn with_date_order(mut self, order: DateOrder) -> Self {
+        self.date_order = order;
+        self
+    }
+
+    /// Handle values that do not parse
+    pub fn with_invalid_policy(mut self, policy: InvalidTimestampPolicy) -> Self {
+        self.invalid_policy = policy;
+        self
+    }
+
+    /// `value` as UTC, or None if it does not parse
+    fn parse(&self, value: &str) -> Option<DateTime<Utc>> {
+        let naive = |layouts: &[&str]| {
+            layouts.iter().find_map(|layout| {
+                NaiveDateTime::parse_from_str(value, layout)
+                    .or_else(|_| {
+                        NaiveDate::parse_from_str(value, layout)
+                            .map(|date| date.and_hms_opt(0, 0, 0).expect("midnight is valid"))
+                    })
+                    .ok()
+            })
+        };
+        let local = |naive: NaiveDateTime| {
+            self.offset
+                .from_local_datetime(&naive)
+                .single()
+                .map(|dt| dt.with_timezone(&Utc))
+        };
+
+        match &self.format {
+            TimestampFormat::Auto => {
+                if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+                    return Some(dt.with_timezone(&Utc));
+                }
+                if let Ok(seconds) = value.parse::<i64>() {
+                    return DateTime::from_timestamp(seconds, 0);
+                }
+                let layouts: &[&str] = match self.date_order {
+                    DateOrder::DayFirst => &[
+                        "%Y-%m-%d %H:%M:%S%.f",
+                        "%Y/%m/%d %H:%M:%S%.f",
+                        "%d-%m-%Y %H:%M:%S%.f",
+                        "%d/%m/%Y %H:%M:%S%.f",
+                        "%Y-%m-%d",
+                        "%Y/%m/%d",
+                        "%d-%m-%Y",
+                        "%d/%m/%Y",
+                    ],
+                    DateOrder::MonthFirst => &[
+                        "%Y-%m-%d %H:%M:%S%.f",
+                        "%Y/%m/%d %H:%M:%S%.f",
+                        "%m-%d-%Y %H:%M:%S%.f",
+                        "%m/%d/%Y %H:%M:%S%.f",
+                        "%Y-%m-%d",
+                        "%Y/%m/%d",
+                        "%m-%d-%Y",
+                        "%m/%d/%Y",
+                    ],
+                };
+                naive(layouts).and_then(local)
+            }
+            TimestampFormat::Custom(format) => DateTime::parse_from_str(value, format)
+                .map(|dt| dt.with_timezone(&Utc))
+                .ok()
+                .or_else(|| naive(&[format]).and_then(local)),
+            TimestampFormat::UnixSeconds => value
+                .parse()
+                .ok()
+                .and_then(|seconds| DateTime::from_timestamp(seconds, 0)),
+            TimestampFormat::UnixMillis => {
+                value.parse().ok().and_then(DateTime::from_timestamp_millis)
+            }
+        }
+    }
+}
+
+/// A column chosen by `ColumnMapping::infer`
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferredColumn {
+    /// Position in the header
+    pub index: usize,
+    /// Header as written in the file
+    pub header: String,
+    /// How well the header matched, in (0, 1]: 1 for a specific name such
+    /// as `order_id`, less for generic names such as `id`, names that only
+    /// contain a known one and near misspellings
+    pub confidence: f64,
+}
+
+/// What `ColumnMapping::infer` chose for each role
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappingInference {
+    /// Column holding the transaction id
+    pub transaction_id: InferredColumn,
+    /// Column holding the items
+    pub items: InferredColumn,
+    /// Column holding the timestamp
+    pub timestamp: InferredColumn,
+}
+
+/// Header names recognised as transaction ids, with the confidence of an
+/// exact match
+const ID_HEADERS: &[(&str, f64)] = &[
+    ("transaction_id", 1.0),
+    ("order_id", 1.0),
+    ("basket_id", 1.0),
+    ("invoice_id", 1.0),
+    ("receipt_id", 1.0),
+    ("txn_id", 1.0),
+    ("tx_id", 1.0),
+    ("invoice_no", 1.0),
+    ("order_number", 1.0),
+    ("transaction", 0.9),
+    ("order", 0.9),
+    ("invoice", 0.9),
+    ("id", 0.8),
+];
+
+/// Header names recognised as items
+const ITEM_HEADERS: &[(&str, f64)] = &[
+    ("items", 1.0),
+    ("products", 1.0),
+    ("skus", 1.0),
+    ("item_list", 1.0),
+    ("product_list", 1.0),
+    ("basket", 1.0),
+    ("sku", 0.9),
+    ("item", 0.9),
+    ("product", 0.9),
+    ("product_id", 0.8),
+    ("item_id", 0.8),
+    ("product_name", 0.8),
+    ("item_name", 0.8),
+    ("stock_code", 0.8),
+    ("description", 0.6),
+];
+
+/// Header names recognised as timestamps
+const TIMESTAMP_HEADERS: &[(&str, f64)] = &[
+    ("timestamp", 1.0),
+    ("created_at", 1.0),
+    ("datetime", 1.0),
+    ("order_date", 1.0),
+    ("transaction_date", 1.0),
+    ("purchase_date", 1.0),
+    ("invoice_date", 1.0),
+    ("placed_at", 1.0),
+    ("ordered_at", 1.0),
+    ("purchased_at", 1.0),
+    ("event_time", 1.0),
+    ("date", 0.9),
+    ("ts", 0.9),
+    ("time", 0.8),
+    ("updated_at", 0.5),
+];
+
+/// Assignments scoring within this much of the best are too close to call
+const INFERENCE_MARGIN: f64 = 0.15;
+
+/// `header` in snake case: `InvoiceNo` and `Invoice No.` both give
+/// `invoice_no`
+fn header_key(header: &str) -> String {
+    let mut key = String::new();
+    let mut prev_lower = false;
+    for c in header.trim().chars() {
+        if c.is_alphanumeric() {
+            if c.is_uppercase() && prev_lower {
+                key.push('_');
+            }
+            key.extend(c.to_lowercase());
+            prev_lower = c.is_lowercase() || c.is_numeric();
+        } else {
+            if !key.is_empty() && !key.ends_with('_') {
+                key.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    key.trim_end_matches('_').to_string()
+}
+
+/// Levenshtein distance between `a` and `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != *cb);
+            row.push(substitute.min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+/// How well header `key` matches one of `names`, 0 if not at all
+///
+/// An exact name scores its confidence, a header holding a name of three or
+/// more characters as whole words (`customer_order_id`) 70% of it, and one
+/// edit away from a name of five or more characters (`timestmp`) 60%.
+fn header_score(key: &str, names: &[(&str, f64)]) -> f64 {
+    names
+        .iter()
+        .map(|&(name, confidence)| {
+            if key == name {
+                confidence
+            } else if name.len() >= 3 && format!("_{}_", key).contains(&format!("_{}_", name)) {
+                confidence * 0.7
+            } else if name.len() >= 5 && edit_distance(key, name) == 1 {
+                confidence * 0.6
+            } else {
+                0.0
+            }
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Check that `header` starts with the standard transaction_id, items,
+/// timestamp columns, under any name `ColumnMapping::infer` recognises exactly
+///
+/// `loader` names the function to pass a `ColumnMapping` to instead.
+fn check_standard_header(header: &[String], loader: &str) -> Result<()> {
+    let roles = [
+        ("transaction id", ID_HEADERS),
+        ("items", ITEM_HEADERS),
+        ("timestamp", TIMESTAMP_HEADERS),
+    ];
+    let mismatch =
+        roles
+            .iter()
+            .enumerate()
+            .find_map(|(index, (role, names))| match header.get(index) {
+                None => Some(format!("there is no column {}", index + 1)),
+                Some(name) if !names.iter().any(|(known, _)| header_key(name) == *known) => {
+                    Some(format!(
+                        "column {} `{}` does not look like the {}",
+                        index + 1,
+                        name,
+                        role
+                    ))
+                }
+                Some(_) => None,
+            });
+    match mismatch {
+        None => Ok(()),
+        Some(mismatch) => Err(MiningError::DataLoadError(format!(
+            "Header {} does not match the standard transaction_id, items, timestamp layout: \
+             {}; pass a ColumnMapping to {}, or let DataLoader::infer_mapping pick one",
+            if header.is_empty() {
+                "(empty)".to_string()
+            } else {
+                header
+                    .iter()
+                    .map(|name| format!("`{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+            mismatch,
+            loader
+        ))),
+    }
 }
 
 impl ColumnMapping {
@@ -83,6 +977,12 @@ impl ColumnMapping {
             item_columns: vec![item_column],
             timestamp,
             field_separator: "::".to_string(),
+            normalizer: ItemNormalizer::default(),
+            multi_field_policy: MultiFieldPolicy::default(),
+            binned_columns: Vec::new(),
+            timestamp_spec: TimestampSpec::default(),
+            user_id_column: None,
+            metadata_columns: Vec::new(),
         }
     }
 
@@ -113,51 +1013,2405 @@ impl ColumnMapping {
             item_columns,
             timestamp,
             field_separator,
+            normalizer: ItemNormalizer::default(),
+            multi_field_policy: MultiFieldPolicy::default(),
+            binned_columns: Vec::new(),
+            timestamp_spec: TimestampSpec::default(),
+            user_id_column: None,
+            metadata_columns: Vec::new(),
         }
     }
-}
-
-/// Data loader for Excel and CSV files using excelstream
-pub struct DataLoader;
 
-impl DataLoader {
-    /// Load transactions from Excel file (.xlsx) with custom column mapping
+    /// Create mapping from column names, given the file's columns in order
     ///
-    /// Uses excelstream for high-performance streaming with ~3-35 MB memory usage
-    /// regardless of file size.
+    /// Fails with `DataLoadError` if a name is not among `columns`.
     ///
-    /// First row is treated as header and skipped.
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::data_loader::ColumnMapping;
     ///
-    /// # Arguments
-    /// * `path` - Path to Excel file
-    /// * `sheet_index` - Sheet index (0-based) to read
-    /// * `mapping` - Column mapping configuration
+    /// let columns = ["order_id", "sku", "category", "placed_at"];
+    /// let mapping = ColumnMapping::from_names(&columns, "order_id", &["sku", "category"], "placed_at")?;
+    /// assert_eq!(mapping.item_columns, vec![1, 2]);
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn from_names<S: AsRef<str>>(
+        columns: &[S],
+        transaction_id: &str,
+        item_columns: &[&str],
+        timestamp: &str,
+    ) -> Result<Self> {
+        let index = |name: &str| {
+            columns
+                .iter()
+                .position(|column| column.as_ref() == name)
+                .ok_or_else(|| MiningError::DataLoadError(format!("No column named `{}`", name)))
+        };
+        Ok(Self::multi_field(
+            index(transaction_id)?,
+            item_columns
+                .iter()
+                .map(|name| index(name))
+                .collect::<Result<_>>()?,
+            index(timestamp)?,
+            "::".to_string(),
+        ))
+    }
+
+    /// Guess the transaction id, items and timestamp columns from a header
     ///
-    /// # Returns
-    /// Vector of transactions
+    /// Headers are compared in snake case against common names (`order_id`,
+    /// `InvoiceNo`, `items`, `sku`, `created_at`, `date` and the like), and
+    /// each role gets a different column. Fails with `DataLoadError` if no
+    /// column fits a role, or if two columns fit one about equally well,
+    /// listing the candidates; name them with `from_names` instead.
     ///
     /// # Example
-    /// ```no_run
-    /// use rust_rule_miner::data_loader::{DataLoader, ColumnMapping};
+    /// ```
+    /// use rust_rule_miner::data_loader::ColumnMapping;
     ///
-    /// // Standard format: transaction_id(0), items(1), timestamp(2)
-    /// let mapping = ColumnMapping::simple(0, 1, 2);
-    /// let transactions = DataLoader::from_excel("sales.xlsx", 0, mapping)?;
-    /// println!("Loaded {} transactions", transactions.len());
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// let (mapping, inference) = ColumnMapping::infer(&["InvoiceNo", "StockCode", "InvoiceDate"])?;
+    /// assert_eq!(mapping.item_columns, vec![1]);
+    /// assert_eq!(inference.transaction_id.header, "InvoiceNo");
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
     /// ```
-    pub fn from_excel<P: AsRef<Path>>(
-        path: P,
-        sheet_index: usize,
-        mapping: ColumnMapping,
-    ) -> Result<Vec<Transaction>> {
-        let mut reader = StreamingReader::open(path.as_ref())
-            .map_err(|e| MiningError::DataLoadError(format!("Failed to open Excel file: {}", e)))?;
+    pub fn infer<S: AsRef<str>>(columns: &[S]) -> Result<(Self, MappingInference)> {
+        let roles = [
+            ("transaction id", ID_HEADERS),
+            ("items", ITEM_HEADERS),
+            ("timestamp", TIMESTAMP_HEADERS),
+        ];
+        let keys: Vec<String> = columns.iter().map(|c| header_key(c.as_ref())).collect();
+        // Per role: (column, score) of every column that fits at all
+        let candidates: Vec<Vec<(usize, f64)>> = roles
+            .iter()
+            .map(|(_, names)| {
+                keys.iter()
+                    .enumerate()
+                    .map(|(index, key)| (index, header_score(key, names)))
+                    .filter(|&(_, score)| score > 0.0)
+                    .collect()
+            })
+            .collect();
+        let header_list = || {
+            columns
+                .iter()
+                .map(|c| format!("`{}`", c.as_ref()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if let Some(role) = candidates.iter().position(Vec::is_empty) {
+            return Err(MiningError::DataLoadError(format!(
+                "Cannot infer the column mapping: no column looks like the {} among {}",
+                roles[role].0,
+                header_list()
+            )));
+        }
 
-        let mut transactions = Vec::new();
-        let mut row_idx = 0;
+        // Every way of giving each role a different column, with its total
+        let mut assignments = Vec::new();
+        for &(id, id_score) in &candidates[0] {
+            for &(items, items_score) in &candidates[1] {
+                for &(ts, ts_score) in &candidates[2] {
+                    if id != items && id != ts && items != ts {
+                        assignments.push(([id, items, ts], [id_score, items_score, ts_score]));
+                    }
+                }
+            }
+        }
+        let total = |scores: &[f64; 3]| scores.iter().sum::<f64>();
+        let Some(best) = assignments
+            .iter()
+            .max_by(|a, b| total(&a.1).total_cmp(&total(&b.1)))
+            .cloned()
+        else {
+            return Err(MiningError::DataLoadError(format!(
+                "Cannot infer the column mapping: the transaction id, items and timestamp \
+                 need three different columns among {}",
+                header_list()
+            )));
+        };
 
-        for row_result in reader.rows_by_index(sheet_index).map_err(|e| {
+        // Roles that a near-best assignment gives to another column
+        let close: Vec<_> = assignments
+            .iter()
+            .filter(|(_, scores)| total(scores) >= total(&best.1) - INFERENCE_MARGIN)
+            .collect();
+        let mut ambiguous = Vec::new();
+        for (role, (name, _)) in roles.iter().enumerate() {
+            let mut options: Vec<(usize, f64)> = Vec::new();
+            for (indices, scores) in &close {
+                if !options.iter().any(|&(index, _)| index == indices[role]) {
+                    options.push((indices[role], scores[role]));
+                }
+            }
+            if options.len() > 1 {
+                options.sort_by(|a, b| b.1.total_cmp(&a.1));
+                let listed: Vec<String> = options
+                    .iter()
+                    .map(|&(index, score)| format!("`{}` ({:.2})", columns[index].as_ref(), score))
+                    .collect();
+                ambiguous.push(format!("the {} could be {}", name, listed.join(" or ")));
+            }
+        }
+        if !ambiguous.is_empty() {
+            return Err(MiningError::DataLoadError(format!(
+                "Cannot infer the column mapping: {}; name the columns with \
+                 ColumnMapping::from_names",
+                ambiguous.join(", ")
+            )));
+        }
+
+        let [id, items, ts] = best.0;
+        let chosen = |role: usize| InferredColumn {
+            index: best.0[role],
+            header: columns[best.0[role]].as_ref().to_string(),
+            confidence: best.1[role],
+        };
+        let inference = MappingInference {
+            transaction_id: chosen(0),
+            items: chosen(1),
+            timestamp: chosen(2),
+        };
+        Ok((Self::simple(id, items, ts), inference))
+    }
+
+    /// Normalize loaded items, e.g. `ItemNormalizer::standard()` to merge
+    /// NFC and NFD spellings; items that merge within a row are kept once
+    pub fn with_normalizer(mut self, normalizer: ItemNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Handle rows whose item columns hold different numbers of values
+    pub fn with_multi_field_policy(mut self, policy: MultiFieldPolicy) -> Self {
+        self.multi_field_policy = policy;
+        self
+    }
+
+    /// Add a numeric column's bin label to each row's items
+    pub fn with_binned_column(mut self, column: BinnedColumn) -> Self {
+        self.binned_columns.push(column);
+        self
+    }
+
+    /// Parse the timestamp column per `spec`
+    pub fn with_timestamp_spec(mut self, spec: TimestampSpec) -> Self {
+        self.timestamp_spec = spec;
+        self
+    }
+
+    /// Read each transaction's user id from `column`; empty cells leave it
+    /// unset
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::data_loader::ColumnMapping;
+    ///
+    /// // CSV: order_id, items, timestamp, customer_id, store
+    /// let mapping = ColumnMapping::simple(0, 1, 2)
+    ///     .with_user_id_column(3)
+    ///     .with_metadata_column(4, "store");
+    /// ```
+    pub fn with_user_id_column(mut self, column: usize) -> Self {
+        self.user_id_column = Some(column);
+        self
+    }
+
+    /// Copy `column` into metadata `key` as a string; empty cells are left
+    /// out
+    pub fn with_metadata_column(mut self, column: usize, key: impl Into<String>) -> Self {
+        self.metadata_columns.push((column, key.into()));
+        self
+    }
+}
+
+/// Field mapping for JSON and JSON Lines records
+///
+/// Fields are addressed by JSON pointer (`"/order/id"`). `items` points to
+/// an array (or a single value); with `item_fields`, each element is an
+/// object and its item is those sub-fields joined by `field_separator`.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::data_loader::JsonMapping;
+///
+/// // {"order": {"id": "o1"}, "lines": [{"sku": "Laptop", "category": "Electronics"}],
+/// //  "at": "2024-01-15T10:30:00Z", "customer": "c42"}
+/// let mapping = JsonMapping::new("/order/id", "/lines", "/at")
+///     .with_item_fields(["/sku", "/category"])
+///     .with_user_id("/customer");
+/// // Items: "Laptop::Electronics"
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonMapping {
+    /// Pointer to the transaction id (string or number)
+    pub transaction_id: String,
+    /// Pointer to the items array
+    pub items: String,
+    /// Pointers within each item element, joined into one item (default:
+    /// none, the element itself is the item)
+    pub item_fields: Vec<String>,
+    /// Pointer to the timestamp (a string or number, parsed per `timestamp_spec`)
+    pub timestamp: String,
+    /// Pointer to the user id (default: none)
+    pub user_id: Option<String>,
+    /// Metadata keys and the pointers they are copied from (default: none)
+    pub metadata: Vec<(String, String)>,
+    /// Separator joining `item_fields` (default: "::")
+    pub field_separator: String,
+    /// Normalization applied to each loaded item (default: none)
+    pub normalizer: ItemNormalizer,
+    /// How the timestamp is parsed (default: `TimestampSpec::default()`)
+    pub timestamp_spec: TimestampSpec,
+}
+
+impl JsonMapping {
+    /// Map transaction id, items and timestamp pointers
+    pub fn new(
+        transaction_id: impl Into<String>,
+        items: impl Into<String>,
+        timestamp: impl Into<String>,
+    ) -> Self {
+        Self {
+            transaction_id: transaction_id.into(),
+            items: items.into(),
+            item_fields: Vec::new(),
+            timestamp: timestamp.into(),
+            user_id: None,
+            metadata: Vec::new(),
+            field_separator: "::".to_string(),
+            normalizer: ItemNormalizer::default(),
+            timestamp_spec: TimestampSpec::default(),
+        }
+    }
+
+    /// Build each item from these sub-fields of the item elements
+    pub fn with_item_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.item_fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Read the user id from `pointer`
+    pub fn with_user_id(mut self, pointer: impl Into<String>) -> Self {
+        self.user_id = Some(pointer.into());
+        self
+    }
+
+    /// Copy the value at `pointer` into metadata `key`, when present
+    pub fn with_metadata(mut self, key: impl Into<String>, pointer: impl Into<String>) -> Self {
+        self.metadata.push((key.into(), pointer.into()));
+        self
+    }
+
+    /// Separator joining `item_fields`
+    pub fn with_field_separator(mut self, separator: impl Into<String>) -> Self {
+        self.field_separator = separator.into();
+        self
+    }
+
+    /// Normalize loaded items
+    pub fn with_normalizer(mut self, normalizer: ItemNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Parse the timestamp per `spec`
+    pub fn with_timestamp_spec(mut self, spec: TimestampSpec) -> Self {
+        self.timestamp_spec = spec;
+        self
+    }
+}
+
+/// Result columns read by `DataLoader::from_postgres` (requires `postgres`
+/// feature)
+///
+/// Columns are named as in the query's result. Items may be a `text[]`
+/// column or a text column of delimited items.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::data_loader::PgMapping;
+///
+/// // SELECT order_id, array_agg(sku) AS skus, placed_at, customer_id ...
+/// let mapping = PgMapping::new("order_id", "skus", "placed_at").with_user_id("customer_id");
+/// ```
+#[cfg(feature = "postgres")]
+#[derive(Debug, Clone)]
+pub struct PgMapping {
+    /// Transaction id column (text or integer)
+    pub transaction_id: String,
+    /// Items column: `text[]`, or text split on `item_separator`
+    pub items: String,
+    /// Timestamp column: `timestamptz`, `timestamp`, `date`, or text parsed
+    /// per `timestamp_spec`
+    pub timestamp: String,
+    /// User id column (default: none)
+    pub user_id: Option<String>,
+    /// Separator of items in a text items column (default: ",")
+    pub item_separator: String,
+    /// Normalization applied to each loaded item (default: none)
+    pub normalizer: ItemNormalizer,
+    /// How a text timestamp column is parsed (default: `TimestampSpec::default()`)
+    pub timestamp_spec: TimestampSpec,
+}
+
+#[cfg(feature = "postgres")]
+impl PgMapping {
+    /// Map transaction id, items and timestamp columns
+    pub fn new(
+        transaction_id: impl Into<String>,
+        items: impl Into<String>,
+        timestamp: impl Into<String>,
+    ) -> Self {
+        Self {
+            transaction_id: transaction_id.into(),
+            items: items.into(),
+            timestamp: timestamp.into(),
+            user_id: None,
+            item_separator: ",".to_string(),
+            normalizer: ItemNormalizer::default(),
+            timestamp_spec: TimestampSpec::default(),
+        }
+    }
+
+    /// Read the user id from `column`
+    pub fn with_user_id(mut self, column: impl Into<String>) -> Self {
+        self.user_id = Some(column.into());
+        self
+    }
+
+    /// Split a text items column on `separator`
+    pub fn with_item_separator(mut self, separator: impl Into<String>) -> Self {
+        self.item_separator = separator.into();
+        self
+    }
+
+    /// Normalize loaded items
+    pub fn with_normalizer(mut self, normalizer: ItemNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Parse a text timestamp column per `spec`
+    pub fn with_timestamp_spec(mut self, spec: TimestampSpec) -> Self {
+        self.timestamp_spec = spec;
+        self
+    }
+
+    /// Column mapping over a result with `columns`, failing with
+    /// `DataLoadError` on a missing or unsupported column
+    fn column_mapping(&self, columns: &[tokio_postgres::Column]) -> Result<ColumnMapping> {
+        let index = |name: &str| {
+            columns
+                .iter()
+                .position(|column| column.name() == name)
+                .ok_or_else(|| MiningError::DataLoadError(format!("No column named `{}`", name)))
+        };
+        let mut mapping = ColumnMapping::simple(
+            index(&self.transaction_id)?,
+            index(&self.items)?,
+            index(&self.timestamp)?,
+        )
+        .with_normalizer(self.normalizer)
+        .with_timestamp_spec(self.timestamp_spec.clone());
+        if let Some(user_id) = &self.user_id {
+            mapping = mapping.with_user_id_column(index(user_id)?);
+        }
+        for column in pg_mapped_columns(&mapping) {
+            let column = &columns[column];
+            if pg_kind(column.type_()).is_none() {
+                return Err(MiningError::DataLoadError(format!(
+                    "Column `{}` has unsupported type {}",
+                    column.name(),
+                    column.type_()
+                )));
+            }
+        }
+        Ok(mapping)
+    }
+}
+
+/// A JSON scalar as text (None for null, arrays and objects)
+fn json_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Index of the sheet named `name`, ignoring case but preferring an exact
+/// match
+fn sheet_index(sheet_names: &[String], name: &str) -> Result<usize> {
+    sheet_names
+        .iter()
+        .position(|sheet| sheet == name)
+        .or_else(|| {
+            let name = name.to_lowercase();
+            sheet_names
+                .iter()
+                .position(|sheet| sheet.to_lowercase() == name)
+        })
+        .ok_or_else(|| {
+            MiningError::DataLoadError(format!(
+                "No sheet named `{}`; the file has {}",
+                name,
+                sheet_names
+                    .iter()
+                    .map(|sheet| format!("`{}`", sheet))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+}
+
+/// Raw rows of delimited text read line by line from `reader`
+fn csv_line_rows<'a, R: BufRead + 'a>(reader: R, csv: &CsvOptions) -> CsvRows<'a> {
+    let parser = CsvParser::new(csv.delimiter, csv.quote);
+    let mut lines = reader.lines().enumerate();
+    let mut done = false;
+    Box::new(std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        match lines.next()? {
+            (_, Ok(line)) => Some(Ok(parser.parse_line(line.trim_end_matches('\r')))),
+            (idx, Err(e)) => {
+                done = true;
+                Some(Err(MiningError::DataLoadError(format!(
+                    "Failed to read row {}: {}",
+                    idx + 1,
+                    e
+                ))))
+            }
+        }
+    }))
+}
+
+/// Delay before the first HTTP retry; it doubles on each further one
+#[cfg(feature = "cloud")]
+const HTTP_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Kind of data in a cloud storage object
+#[cfg(feature = "cloud")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ObjectFormat {
+    Csv,
+    GzippedCsv,
+    Xlsx,
+}
+
+/// Format of the object `name`, from its extension
+#[cfg(feature = "cloud")]
+fn object_format(name: &str) -> Result<ObjectFormat> {
+    let lower = name.to_lowercase();
+    if lower.ends_with(".csv") {
+        Ok(ObjectFormat::Csv)
+    } else if lower.ends_with(".csv.gz") {
+        Ok(ObjectFormat::GzippedCsv)
+    } else if lower.ends_with(".xlsx") {
+        Ok(ObjectFormat::Xlsx)
+    } else {
+        Err(MiningError::DataLoadError(format!(
+            "Cannot tell the format of `{}`: expected a .csv, .csv.gz or .xlsx name",
+            name
+        )))
+    }
+}
+
+/// Raw CSV rows of an HTTP body split out as chunks arrive, numbered from 1
+#[cfg(feature = "cloud")]
+struct HttpLines {
+    /// Set when the body is gzip-encoded; holds decoded bytes not yet split
+    gunzip: Option<flate2::write::MultiGzDecoder<Vec<u8>>>,
+    /// Bytes after the last complete line
+    pending: Vec<u8>,
+    parser: CsvParser,
+    line: usize,
+}
+
+#[cfg(feature = "cloud")]
+impl HttpLines {
+    fn new(gzipped: bool, csv: &CsvOptions) -> Self {
+        Self {
+            gunzip: gzipped.then(|| flate2::write::MultiGzDecoder::new(Vec::new())),
+            pending: Vec::new(),
+            parser: CsvParser::new(csv.delimiter, csv.quote),
+            line: 0,
+        }
+    }
+
+    /// Rows completed by `chunk`
+    fn push(&mut self, chunk: &[u8]) -> Result<Vec<(usize, Vec<String>)>> {
+        use std::io::Write;
+
+        match &mut self.gunzip {
+            Some(gunzip) => {
+                gunzip.write_all(chunk).map_err(Self::gzip_error)?;
+                self.pending.append(gunzip.get_mut());
+            }
+            None => self.pending.extend_from_slice(chunk),
+        }
+        let Some(end) = self.pending.iter().rposition(|&b| b == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.pending.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.pending, rest);
+        complete[..end]
+            .split(|&b| b == b'\n')
+            .map(|line| self.parse(line))
+            .collect()
+    }
+
+    /// Rows left once the body has ended: lines the decoder still held and
+    /// a last line without a newline
+    fn finish(mut self) -> Result<Vec<(usize, Vec<String>)>> {
+        if let Some(mut gunzip) = self.gunzip.take() {
+            gunzip.try_finish().map_err(Self::gzip_error)?;
+            self.pending.append(gunzip.get_mut());
+        }
+        let pending = std::mem::take(&mut self.pending);
+        let pending = pending.strip_suffix(b"\n").unwrap_or(&pending);
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+        pending
+            .split(|&b| b == b'\n')
+            .map(|line| self.parse(line))
+            .collect()
+    }
+
+    fn parse(&mut self, line: &[u8]) -> Result<(usize, Vec<String>)> {
+        self.line += 1;
+        let line = std::str::from_utf8(line).map_err(|e| {
+            MiningError::DataLoadError(format!("Failed to read row {}: {}", self.line, e))
+        })?;
+        Ok((
+            self.line,
+            self.parser.parse_line(line.trim_end_matches('\r')),
+        ))
+    }
+
+    fn gzip_error(e: std::io::Error) -> MiningError {
+        MiningError::DataLoadError(format!("Failed to decompress response: {}", e))
+    }
+}
+
+/// Unique temporary path for a workbook copied out of `name`
+fn temp_workbook_path(name: &str) -> PathBuf {
+    let file_name = Path::new(name)
+        .file_name()
+        .map_or_else(|| "workbook.xlsx".into(), |name| name.to_string_lossy());
+    std::env::temp_dir().join(format!(
+        "rust_rule_miner_{}_{}_{}",
+        std::process::id(),
+        Utc::now().timestamp_nanos_opt().unwrap_or_default(),
+        file_name
+    ))
+}
+
+/// Why a row was skipped, without the `DataLoadError` prefix
+fn skip_reason(error: MiningError) -> String {
+    match error {
+        MiningError::DataLoadError(message) => message,
+        other => other.to_string(),
+    }
+}
+
+/// Data rows taken per `LoaderOptions::skip_rows`, `sample` and `limit`
+///
+/// Rows are passed over, then sampled, then counted against the limit.
+#[derive(Default)]
+struct RowWindow {
+    to_skip: usize,
+    remaining: Option<usize>,
+    sample: Option<(f64, SplitMix64)>,
+}
+
+impl RowWindow {
+    fn new(options: &LoaderOptions) -> Result<Self> {
+        if let Some(fraction) = options.sample {
+            if !(fraction > 0.0 && fraction <= 1.0) {
+                return Err(MiningError::InvalidConfig(format!(
+                    "sample must be a fraction in (0, 1], got {}",
+                    fraction
+                )));
+            }
+        }
+        Ok(Self {
+            to_skip: options.skip_rows.unwrap_or(0),
+            remaining: options.limit,
+            sample: options.sample.map(|fraction| {
+                let seed = options.seed.unwrap_or_else(clock_seed);
+                (fraction, SplitMix64::new(seed))
+            }),
+        })
+    }
+
+    /// Whether `limit` rows were taken, so reading can stop
+    fn exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+
+    /// Whether to take the next data row
+    fn take(&mut self) -> bool {
+        if self.to_skip > 0 {
+            self.to_skip -= 1;
+            return false;
+        }
+        if let Some((fraction, rng)) = &mut self.sample {
+            if rng.next_open01() >= *fraction {
+                return false;
+            }
+        }
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_sub(1);
+        }
+        true
+    }
+}
+
+/// Transactions and report accumulated row by row
+struct RowCollector {
+    transactions: Vec<Transaction>,
+    report: LoadReport,
+    options: LoaderOptions,
+    /// Rows to take, from `options`
+    window: RowWindow,
+    /// Separator between items within a cell
+    item_separator: String,
+    /// Per quantile-binned column: (transaction position, value)
+    quantile_values: Vec<(BinnedColumn, Vec<(usize, f64)>)>,
+}
+
+impl Default for RowCollector {
+    fn default() -> Self {
+        Self {
+            transactions: Vec::new(),
+            report: LoadReport::default(),
+            options: LoaderOptions::default(),
+            window: RowWindow::default(),
+            item_separator: ",".to_string(),
+            quantile_values: Vec::new(),
+        }
+    }
+}
+
+impl RowCollector {
+    /// Fails with `InvalidConfig` if `options.sample` is not in (0, 1]
+    fn new(options: LoaderOptions) -> Result<Self> {
+        Ok(Self {
+            window: RowWindow::new(&options)?,
+            options,
+            ..Self::default()
+        })
+    }
+
+    /// Split item cells on `separator` instead of commas
+    fn with_item_separator(mut self, separator: &str) -> Self {
+        self.item_separator = separator.to_string();
+        self
+    }
+
+    /// Count a skipped row, failing the load in strict mode or past
+    /// `max_errors`
+    fn skip(&mut self, row_idx: usize, reason: String, raw: &str) -> Result<()> {
+        if self.options.strict {
+            return Err(MiningError::DataLoadError(format!(
+                "{}; row {} was `{}`",
+                reason, row_idx, raw
+            )));
+        }
+        log::warn!("Skipping row {}: {}", row_idx, reason);
+        self.report.skipped_rows += 1;
+        if self.options.collect_report {
+            self.report.skip_reasons.push((row_idx, reason));
+        }
+        if self.report.skipped_rows > self.options.max_errors {
+            return Err(MiningError::DataLoadError(format!(
+                "{} rows skipped, more than the {} allowed; last was row {}",
+                self.report.skipped_rows, self.options.max_errors, row_idx
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fail in strict mode if the row just parsed was stamped with the load
+    /// time
+    fn check_strict(&mut self, row_idx: usize, raw: &str, fallbacks_before: usize) -> Result<()> {
+        if self.options.strict && self.report.timestamp_fallbacks.len() > fallbacks_before {
+            return Err(MiningError::DataLoadError(format!(
+                "Row {} timestamp did not parse; row {} was `{}`",
+                row_idx, row_idx, raw
+            )));
+        }
+        Ok(())
+    }
+
+    /// Parse one data row; only `MultiFieldPolicy::Error` and
+    /// `InvalidTimestampPolicy::Error` fail the load
+    fn push(
+        &mut self,
+        row_values: &[String],
+        row_idx: usize,
+        mapping: &ColumnMapping,
+    ) -> Result<()> {
+        self.push_with_lists(row_values, &[], row_idx, mapping)
+    }
+
+    /// `push` for rows whose list-typed cells are already split into items
+    fn push_with_lists(
+        &mut self,
+        row_values: &[String],
+        lists: &[Option<Vec<String>>],
+        row_idx: usize,
+        mapping: &ColumnMapping,
+    ) -> Result<()> {
+        self.report.rows_read += 1;
+        let raw = || row_values.join(",");
+        let fallbacks_before = self.report.timestamp_fallbacks.len();
+        let skipped_before = self.report.skipped_rows;
+        match DataLoader::parse_transaction_with_mapping(
+            row_values,
+            lists,
+            &self.item_separator,
+            row_idx,
+            mapping,
+            &self.options,
+            &mut self.report,
+        ) {
+            Ok(Some(tx)) => {
+                self.check_strict(row_idx, &raw(), fallbacks_before)?;
+                self.collect_quantile_values(row_values, row_idx, mapping);
+                self.transactions.push(tx);
+            }
+            Ok(None) if self.report.skipped_rows > skipped_before => {
+                // Counted by the parser under MultiFieldPolicy::SkipRow
+                self.report.skipped_rows = skipped_before;
+                let reason = "item columns hold different numbers of values".to_string();
+                self.skip(row_idx, reason, &raw())?;
+            }
+            Ok(None) => {} // Skip empty rows
+            Err(e @ MiningError::InvalidTransaction(_)) => return Err(e),
+            Err(e) => self.skip(row_idx, skip_reason(e), &raw())?,
+        }
+        Ok(())
+    }
+
+    /// Parse one JSON record, skipping it with a warning if it does not map;
+    /// only `InvalidTimestampPolicy::Error` fails the load
+    fn push_json(&mut self, record: &Value, row_idx: usize, mapping: &JsonMapping) -> Result<()> {
+        self.report.rows_read += 1;
+        let fallbacks_before = self.report.timestamp_fallbacks.len();
+        match DataLoader::parse_json_record(
+            record,
+            row_idx,
+            mapping,
+            &self.options,
+            &mut self.report,
+        ) {
+            Ok(Some(tx)) => {
+                self.check_strict(row_idx, &record.to_string(), fallbacks_before)?;
+                self.transactions.push(tx);
+            }
+            Ok(None) => {}
+            Err(e @ MiningError::InvalidTransaction(_)) => return Err(e),
+            Err(e) => self.skip(row_idx, skip_reason(e), &record.to_string())?,
+        }
+        Ok(())
+    }
+
+    /// Remember the values of quantile-binned columns for `finish`; the row
+    /// parsed, so they are numbers or empty
+    fn collect_quantile_values(
+        &mut self,
+        row_values: &[String],
+        row_idx: usize,
+        mapping: &ColumnMapping,
+    ) {
+        let position = self.transactions.len();
+        for column in &mapping.binned_columns {
+            if !matches!(column.bins, Binning::Quantile(_)) {
+                continue;
+            }
+            let Ok(Some(value)) = column.value(row_values, row_idx) else {
+                continue;
+            };
+            match self.quantile_values.iter_mut().find(|(c, _)| c == column) {
+                Some((_, values)) => values.push((position, value)),
+                None => self
+                    .quantile_values
+                    .push((column.clone(), vec![(position, value)])),
+            }
+        }
+    }
+
+    /// Add quantile bin labels now that every value is known
+    fn apply_quantiles(&mut self) {
+        for (column, values) in std::mem::take(&mut self.quantile_values) {
+            let Binning::Quantile(bins) = column.bins else {
+                continue;
+            };
+            let bins = bins.max(1);
+            let mut sorted: Vec<f64> = values.iter().map(|(_, value)| *value).collect();
+            sorted.sort_by(f64::total_cmp);
+            let cuts: Vec<f64> = (1..bins).map(|k| sorted[k * sorted.len() / bins]).collect();
+
+            for (position, value) in values {
+                let bin = cuts.iter().filter(|cut| value >= **cut).count() + 1;
+                let item = format!("{}q{}", column.label_prefix, bin);
+                let items = &mut self.transactions[position].items;
+                if !items.contains(&item) {
+                    items.push(item);
+                }
+            }
+        }
+    }
+
+    /// Loaded transactions, failing if there are none
+    fn finish(mut self, source: &str) -> Result<(Vec<Transaction>, LoadReport)> {
+        self.apply_quantiles();
+        if self.transactions.is_empty() {
+            return Err(MiningError::InsufficientData(format!(
+                "No valid transactions found in {}",
+                source
+            )));
+        }
+
+        let mut report = self.report;
+        report.transactions = self.transactions.len();
+        Ok((self.transactions, report))
+    }
+}
+
+/// Transactions parsed lazily from file rows, the first being the header if
+/// `has_header`
+///
+/// Rows that fail to parse are yielded as errors, as are read errors from
+/// `rows`, which should end after its first.
+fn transaction_stream<I>(
+    rows: I,
+    mapping: ColumnMapping,
+    has_header: bool,
+    item_separator: String,
+    options: LoaderOptions,
+) -> Result<TransactionStream>
+where
+    I: Iterator<Item = Result<Vec<String>>> + 'static,
+{
+    if mapping
+        .binned_columns
+        .iter()
+        .any(|column| matches!(column.bins, Binning::Quantile(_)))
+    {
+        return Err(MiningError::InvalidConfig(
+            "quantile bins need every value before labelling; use threshold bins when streaming"
+                .to_string(),
+        ));
+    }
+
+    let mut window = RowWindow::new(&options)?;
+    let mut report = LoadReport::default();
+    let mut rows = rows.enumerate().skip(usize::from(has_header));
+    Ok(Box::new(std::iter::from_fn(move || loop {
+        // Stop before reading past the limit
+        if window.exhausted() {
+            return None;
+        }
+        let (idx, row) = rows.next()?;
+        let row_values = match row {
+            Ok(row_values) => row_values,
+            Err(e) => return Some(Err(e)),
+        };
+        if !window.take() {
+            continue;
+        }
+        let parsed = DataLoader::parse_transaction_with_mapping(
+            &row_values,
+            &[],
+            &item_separator,
+            idx + 1,
+            &mapping,
+            &options,
+            &mut report,
+        );
+        if let Some(parsed) = parsed.transpose() {
+            return Some(parsed);
+        }
+    })))
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_reader(
+    path: &Path,
+) -> Result<parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder<std::fs::File>> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| MiningError::DataLoadError(format!("Failed to open Parquet file: {}", e)))?;
+    parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(file)
+        .map_err(|e| MiningError::DataLoadError(format!("Failed to read Parquet file: {}", e)))
+}
+
+/// Text of one Parquet cell, and its elements if the column is a list
+///
+/// Nulls read as empty. Timestamps are written as RFC 3339 in UTC so they
+/// parse back exactly.
+#[cfg(feature = "parquet")]
+fn parquet_cell(
+    column: &dyn arrow_array::Array,
+    row: usize,
+) -> Result<(String, Option<Vec<String>>)> {
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{
+        TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+        TimestampSecondType,
+    };
+    use arrow_schema::{DataType, TimeUnit};
+
+    let list = |values: arrow_array::ArrayRef| -> Result<Option<Vec<String>>> {
+        (0..values.len())
+            .filter(|&i| values.is_valid(i))
+            .map(|i| parquet_cell(values.as_ref(), i).map(|(value, _)| value))
+            .collect::<Result<_>>()
+            .map(Some)
+    };
+
+    if column.is_null(row) {
+        let list = matches!(
+            column.data_type(),
+            DataType::List(_) | DataType::LargeList(_)
+        );
+        return Ok((String::new(), list.then(Vec::new)));
+    }
+    let timestamp = match column.data_type() {
+        DataType::List(_) => return Ok((String::new(), list(column.as_list::<i32>().value(row))?)),
+        DataType::LargeList(_) => {
+            return Ok((String::new(), list(column.as_list::<i64>().value(row))?))
+        }
+        DataType::Timestamp(TimeUnit::Second, _) => column
+            .as_primitive::<TimestampSecondType>()
+            .value_as_datetime(row),
+        DataType::Timestamp(TimeUnit::Millisecond, _) => column
+            .as_primitive::<TimestampMillisecondType>()
+            .value_as_datetime(row),
+        DataType::Timestamp(TimeUnit::Microsecond, _) => column
+            .as_primitive::<TimestampMicrosecondType>()
+            .value_as_datetime(row),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => column
+            .as_primitive::<TimestampNanosecondType>()
+            .value_as_datetime(row),
+        _ => {
+            let value = arrow_cast::display::array_value_to_string(column, row).map_err(|e| {
+                MiningError::DataLoadError(format!("Unreadable Parquet value: {}", e))
+            })?;
+            return Ok((value, None));
+        }
+    };
+    let value = timestamp
+        .map(|naive| {
+            naive
+                .and_utc()
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+        })
+        .unwrap_or_default();
+    Ok((value, None))
+}
+
+/// Rows `DataLoader::from_postgres` fetches from its cursor at a time
+#[cfg(feature = "postgres")]
+const PG_FETCH_ROWS: i32 = 1024;
+
+/// How `pg_cell` reads a PostgreSQL column
+#[cfg(feature = "postgres")]
+#[derive(Debug, Clone, Copy)]
+enum PgKind {
+    Text,
+    SmallInt,
+    Int,
+    BigInt,
+    TextArray,
+    TimestampTz,
+    Timestamp,
+    Date,
+}
+
+#[cfg(feature = "postgres")]
+fn pg_kind(ty: &tokio_postgres::types::Type) -> Option<PgKind> {
+    use tokio_postgres::types::Type;
+
+    let kinds = [
+        (Type::TEXT, PgKind::Text),
+        (Type::VARCHAR, PgKind::Text),
+        (Type::BPCHAR, PgKind::Text),
+        (Type::NAME, PgKind::Text),
+        (Type::INT2, PgKind::SmallInt),
+        (Type::INT4, PgKind::Int),
+        (Type::INT8, PgKind::BigInt),
+        (Type::TEXT_ARRAY, PgKind::TextArray),
+        (Type::VARCHAR_ARRAY, PgKind::TextArray),
+        (Type::TIMESTAMPTZ, PgKind::TimestampTz),
+        (Type::TIMESTAMP, PgKind::Timestamp),
+        (Type::DATE, PgKind::Date),
+    ];
+    kinds
+        .into_iter()
+        .find(|(known, _)| known == ty)
+        .map(|(_, kind)| kind)
+}
+
+/// Result columns a mapping reads
+#[cfg(feature = "postgres")]
+fn pg_mapped_columns(mapping: &ColumnMapping) -> Vec<usize> {
+    let mut columns = vec![mapping.transaction_id, mapping.timestamp];
+    columns.extend(&mapping.item_columns);
+    columns.extend(mapping.user_id_column);
+    columns
+}
+
+/// Text of one PostgreSQL cell, and its elements if it is an array
+///
+/// Nulls read as empty; timestamps are written as RFC 3339 in UTC.
+#[cfg(feature = "postgres")]
+fn pg_cell(
+    row: &tokio_postgres::Row,
+    column: usize,
+    row_idx: usize,
+) -> Result<(String, Option<Vec<String>>)> {
+    let error = |e: tokio_postgres::Error| {
+        MiningError::DataLoadError(format!(
+            "Row {}: column `{}`: {}",
+            row_idx,
+            row.columns()[column].name(),
+            e
+        ))
+    };
+    let text = |value: Option<String>| (value.unwrap_or_default(), None);
+    let rfc3339 = |value: DateTime<Utc>| value.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true);
+
+    let kind = pg_kind(row.columns()[column].type_()).expect("mapped column types are checked");
+    Ok(match kind {
+        PgKind::Text => text(row.try_get(column).map_err(error)?),
+        PgKind::SmallInt => text(
+            row.try_get::<_, Option<i16>>(column)
+                .map_err(error)?
+                .map(|value| value.to_string()),
+        ),
+        PgKind::Int => text(
+            row.try_get::<_, Option<i32>>(column)
+                .map_err(error)?
+                .map(|value| value.to_string()),
+        ),
+        PgKind::BigInt => text(
+            row.try_get::<_, Option<i64>>(column)
+                .map_err(error)?
+                .map(|value| value.to_string()),
+        ),
+        PgKind::TextArray => {
+            let items: Vec<String> = row
+                .try_get::<_, Option<Vec<Option<String>>>>(column)
+                .map_err(error)?
+                .unwrap_or_default()
+                .into_iter()
+                .flatten()
+                .collect();
+            (items.join(","), Some(items))
+        }
+        PgKind::TimestampTz => text(
+            row.try_get::<_, Option<DateTime<Utc>>>(column)
+                .map_err(error)?
+                .map(rfc3339),
+        ),
+        PgKind::Timestamp => text(
+            row.try_get::<_, Option<NaiveDateTime>>(column)
+                .map_err(error)?
+                .map(|naive| rfc3339(naive.and_utc())),
+        ),
+        PgKind::Date => text(
+            row.try_get::<_, Option<NaiveDate>>(column)
+                .map_err(error)?
+                .map(|date| date.format("%Y-%m-%d").to_string()),
+        ),
+    })
+}
+
+/// Data loader for Excel and CSV files using excelstream
+pub struct DataLoader;
+
+impl DataLoader {
+    /// Load transactions from Excel file (.xlsx) with custom column mapping
+    ///
+    /// Uses excelstream for high-performance streaming with ~3-35 MB memory usage
+    /// regardless of file size.
+    ///
+    /// First row is treated as header and skipped.
+    ///
+    /// # Arguments
+    /// * `path` - Path to Excel file
+    /// * `sheet_index` - Sheet index (0-based) to read
+    /// * `mapping` - Column mapping configuration
+    ///
+    /// # Returns
+    /// Vector of transactions
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::{DataLoader, ColumnMapping};
+    ///
+    /// // Standard format: transaction_id(0), items(1), timestamp(2)
+    /// let mapping = ColumnMapping::simple(0, 1, 2);
+    /// let transactions = DataLoader::from_excel("sales.xlsx", 0, mapping)?;
+    /// println!("Loaded {} transactions", transactions.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_excel<P: AsRef<Path>>(
+        path: P,
+        sheet_index: usize,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        Self::from_excel_with_report(path, sheet_index, mapping)
+            .map(|(transactions, _)| transactions)
+    }
+
+    /// Load transactions from an Excel sheet in the standard layout
+    ///
+    /// Like `from_excel` with `ColumnMapping::simple(0, 1, 2)`, after checking
+    /// that the header names a transaction id, items and a timestamp in that
+    /// order (`transaction_id`, `items`, `timestamp`, or names such as
+    /// `order_id`, `products`, `created_at`). Fails with `DataLoadError`
+    /// naming the first column that does not fit.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::DataLoader;
+    ///
+    /// let transactions = DataLoader::from_excel_standard("sales.xlsx", 0)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_excel_standard<P: AsRef<Path>>(
+        path: P,
+        sheet_index: usize,
+    ) -> Result<Vec<Transaction>> {
+        let mut reader = StreamingReader::open(path.as_ref())
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open Excel file: {}", e)))?;
+        let header = reader
+            .rows_by_index(sheet_index)
+            .map_err(|e| {
+                MiningError::DataLoadError(format!("Failed to read sheet {}: {}", sheet_index, e))
+            })?
+            .next()
+            .transpose()
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to read row 1: {}", e)))?
+            .map(|row| row.to_strings())
+            .unwrap_or_default();
+        drop(reader);
+        check_standard_header(&header, "from_excel")?;
+        Self::from_excel(path, sheet_index, ColumnMapping::simple(0, 1, 2))
+    }
+
+    /// Load transactions from an Excel file, reporting skipped and uneven rows
+    ///
+    /// Like `from_excel`; the `LoadReport` lists rows affected by the
+    /// mapping's `MultiFieldPolicy`.
+    pub fn from_excel_with_report<P: AsRef<Path>>(
+        path: P,
+        sheet_index: usize,
+        mapping: ColumnMapping,
+    ) -> Result<(Vec<Transaction>, LoadReport)> {
+        Self::from_excel_with_options(path, sheet_index, mapping, LoaderOptions::default())
+    }
+
+    /// Load transactions from an Excel file, handling bad rows per `options`
+    pub fn from_excel_with_options<P: AsRef<Path>>(
+        path: P,
+        sheet_index: usize,
+        mapping: ColumnMapping,
+        options: LoaderOptions,
+    ) -> Result<(Vec<Transaction>, LoadReport)> {
+        let mut reader = StreamingReader::open(path.as_ref())
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open Excel file: {}", e)))?;
+
+        let mut rows = RowCollector::new(options)?;
+        Self::read_excel_sheet(&mut reader, sheet_index, &mapping, &mut rows)?;
+        rows.finish("Excel file")
+    }
+
+    /// Load transactions from the Excel sheet named `sheet_name`
+    ///
+    /// The name is matched case-insensitively, preferring an exact match.
+    /// An unknown name fails with `DataLoadError` listing the file's sheets.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader};
+    ///
+    /// let transactions =
+    ///     DataLoader::from_excel_sheet("sales.xlsx", "Q1 Orders", ColumnMapping::simple(0, 1, 2))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_excel_sheet<P: AsRef<Path>>(
+        path: P,
+        sheet_name: &str,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        let mut reader = StreamingReader::open(path.as_ref())
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open Excel file: {}", e)))?;
+        let sheet_index = sheet_index(&reader.sheet_names(), sheet_name)?;
+
+        let mut rows = RowCollector::default();
+        Self::read_excel_sheet(&mut reader, sheet_index, &mapping, &mut rows)?;
+        rows.finish("Excel file")
+            .map(|(transactions, _)| transactions)
+    }
+
+    /// Load transactions from every sheet of an Excel file, in sheet order
+    ///
+    /// Each sheet has its own header row. Transaction ids are prefixed with
+    /// their sheet's name (`"2023:tx1"`) so sheets reusing ids do not merge;
+    /// all sheets share `mapping`.
+    pub fn from_excel_all_sheets<P: AsRef<Path>>(
+        path: P,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        let mut reader = StreamingReader::open(path.as_ref())
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open Excel file: {}", e)))?;
+
+        let mut rows = RowCollector::default();
+        for (sheet_index, sheet_name) in reader.sheet_names().into_iter().enumerate() {
+            let start = rows.transactions.len();
+            Self::read_excel_sheet(&mut reader, sheet_index, &mapping, &mut rows)?;
+            for tx in &mut rows.transactions[start..] {
+                tx.id = format!("{}:{}", sheet_name, tx.id);
+            }
+        }
+        rows.finish("Excel file")
+            .map(|(transactions, _)| transactions)
+    }
+
+    /// Parse the rows of one sheet after its header into `rows`
+    fn read_excel_sheet(
+        reader: &mut StreamingReader,
+        sheet_index: usize,
+        mapping: &ColumnMapping,
+        rows: &mut RowCollector,
+    ) -> Result<()> {
+        let mut row_idx = 0;
+
+        for row_result in reader.rows_by_index(sheet_index).map_err(|e| {
+            MiningError::DataLoadError(format!("Failed to read sheet {}: {}", sheet_index, e))
+        })? {
+            if rows.window.exhausted() {
+                break;
+            }
+            let row = row_result.map_err(|e| {
+                MiningError::DataLoadError(format!("Failed to read row {}: {}", row_idx, e))
+            })?;
+
+            row_idx += 1;
+
+            // Skip header row
+            if row_idx == 1 || !rows.window.take() {
+                continue;
+            }
+
+            // Convert row to Vec<String>
+            let row_values = row.to_strings();
+            rows.push(&row_values, row_idx, mapping)?;
+        }
+        Ok(())
+    }
+
+    /// Stream transactions from an Excel sheet as they are read
+    ///
+    /// Like `stream_csv`. Rows are read on a background thread a bounded
+    /// number ahead of the consumer; dropping the stream stops it.
+    pub fn stream_excel<P: AsRef<Path>>(
+        path: P,
+        sheet_index: usize,
+        mapping: ColumnMapping,
+    ) -> Result<TransactionStream> {
+        Self::stream_excel_with_options(path, sheet_index, mapping, LoaderOptions::default())
+    }
+
+    /// Stream transactions from an Excel sheet, taking and transforming rows
+    /// per `options`
+    ///
+    /// As for `stream_csv_with_options`.
+    pub fn stream_excel_with_options<P: AsRef<Path>>(
+        path: P,
+        sheet_index: usize,
+        mapping: ColumnMapping,
+        options: LoaderOptions,
+    ) -> Result<TransactionStream> {
+        let mut reader = StreamingReader::open(path.as_ref())
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open Excel file: {}", e)))?;
+        if sheet_index >= reader.sheet_names().len() {
+            return Err(MiningError::DataLoadError(format!(
+                "Failed to read sheet {}: the file has {} sheets",
+                sheet_index,
+                reader.sheet_names().len()
+            )));
+        }
+
+        let (sender, receiver) = std::sync::mpsc::sync_channel(EXCEL_STREAM_BUFFER);
+        std::thread::spawn(move || {
+            let rows = match reader.rows_by_index(sheet_index) {
+                Ok(rows) => rows,
+                Err(e) => {
+                    let message = format!("Failed to read sheet {}: {}", sheet_index, e);
+                    sender.send(Err(MiningError::DataLoadError(message))).ok();
+                    return;
+                }
+            };
+            for (idx, row) in rows.enumerate() {
+                let row = row.map(|row| row.to_strings()).map_err(|e| {
+                    MiningError::DataLoadError(format!("Failed to read row {}: {}", idx + 1, e))
+                });
+                let failed = row.is_err();
+                // A closed channel means the stream was dropped
+                if sender.send(row).is_err() || failed {
+                    return;
+                }
+            }
+        });
+
+        transaction_stream(
+            receiver.into_iter(),
+            mapping,
+            true,
+            ",".to_string(),
+            options,
+        )
+    }
+
+    /// Load transactions from CSV file with custom column mapping
+    ///
+    /// Uses excelstream for high-performance streaming with constant memory usage.
+    /// Gzipped files (`.csv.gz`, or any file starting with the gzip magic
+    /// bytes) are decompressed as they are read.
+    ///
+    /// First row is treated as header and skipped.
+    ///
+    /// # Arguments
+    /// * `path` - Path to CSV file
+    /// * `mapping` - Column mapping configuration
+    ///
+    /// # Returns
+    /// Vector of transactions
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::{DataLoader, ColumnMapping};
+    ///
+    /// // Standard format: transaction_id(0), items(1), timestamp(2)
+    /// let mapping = ColumnMapping::simple(0, 1, 2);
+    /// let transactions = DataLoader::from_csv("transactions.csv", mapping)?;
+    /// println!("Loaded {} transactions", transactions.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_csv<P: AsRef<Path>>(path: P, mapping: ColumnMapping) -> Result<Vec<Transaction>> {
+        Self::from_csv_with_report(path, mapping).map(|(transactions, _)| transactions)
+    }
+
+    /// Load transactions from a CSV file in the standard layout
+    ///
+    /// Like `from_csv` with `ColumnMapping::simple(0, 1, 2)`, after checking
+    /// the header as `from_excel_standard` does.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::DataLoader;
+    ///
+    /// // transaction_id,items,timestamp
+    /// let transactions = DataLoader::from_csv_standard("transactions.csv")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_csv_standard<P: AsRef<Path>>(path: P) -> Result<Vec<Transaction>> {
+        let header = Self::csv_rows(path.as_ref(), &CsvOptions::default())?
+            .next()
+            .transpose()?
+            .unwrap_or_default();
+        check_standard_header(&header, "from_csv")?;
+        Self::from_csv(path, ColumnMapping::simple(0, 1, 2))
+    }
+
+    /// Load transactions from a CSV file, reporting skipped and uneven rows
+    ///
+    /// Like `from_csv`; the `LoadReport` lists rows affected by the
+    /// mapping's `MultiFieldPolicy`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader, MultiFieldPolicy};
+    ///
+    /// // product(1) and category(2) zipped into "Laptop::Electronics"
+    /// let mapping = ColumnMapping::multi_field(0, vec![1, 2], 3, "::".to_string())
+    ///     .with_multi_field_policy(MultiFieldPolicy::SkipRow);
+    /// let (transactions, report) = DataLoader::from_csv_with_report("sales.csv", mapping)?;
+    /// if !report.uneven_rows.is_empty() {
+    ///     eprintln!("Skipped uneven rows {:?}", report.uneven_rows);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_csv_with_report<P: AsRef<Path>>(
+        path: P,
+        mapping: ColumnMapping,
+    ) -> Result<(Vec<Transaction>, LoadReport)> {
+        Self::from_csv_with_options(path, mapping, LoaderOptions::default())
+    }
+
+    /// Load transactions from a CSV file, handling bad rows per `options`
+    ///
+    /// In strict mode the first row that fails to parse (or whose timestamp
+    /// falls back to the load time) aborts with `DataLoadError`; otherwise
+    /// the `LoadReport` lists skipped rows and why.
+    pub fn from_csv_with_options<P: AsRef<Path>>(
+        path: P,
+        mapping: ColumnMapping,
+        options: LoaderOptions,
+    ) -> Result<(Vec<Transaction>, LoadReport)> {
+        Self::from_csv_with_format(path, mapping, CsvOptions::default(), options)
+    }
+
+    /// Load transactions from a delimited text file laid out per `csv`
+    ///
+    /// Like `from_csv_with_options`, for files delimited by other than
+    /// commas, without a header row, or with items separated by other than
+    /// commas within a cell. Row numbers count the header, if any.
+    pub fn from_csv_with_format<P: AsRef<Path>>(
+        path: P,
+        mapping: ColumnMapping,
+        csv: CsvOptions,
+        options: LoaderOptions,
+    ) -> Result<(Vec<Transaction>, LoadReport)> {
+        let mut rows = RowCollector::new(options)?.with_item_separator(&csv.item_separator);
+        Self::collect_csv_rows(
+            Self::csv_rows(path.as_ref(), &csv)?,
+            &csv,
+            &mapping,
+            &mut rows,
+        )?;
+        rows.finish("CSV file")
+    }
+
+    /// Guess a CSV file's column mapping from its header row
+    ///
+    /// Reads only the header; see `ColumnMapping::infer` for how columns are
+    /// chosen and when it gives up.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::DataLoader;
+    ///
+    /// let (mapping, inference) = DataLoader::infer_mapping("orders.csv")?;
+    /// println!(
+    ///     "items from `{}` ({:.0}% sure)",
+    ///     inference.items.header,
+    ///     inference.items.confidence * 100.0
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn infer_mapping<P: AsRef<Path>>(path: P) -> Result<(ColumnMapping, MappingInference)> {
+        let header = Self::csv_rows(path.as_ref(), &CsvOptions::default())?
+            .next()
+            .transpose()?
+            .ok_or_else(|| MiningError::DataLoadError("CSV file is empty".to_string()))?;
+        ColumnMapping::infer(&header)
+    }
+
+    /// Load transactions from a CSV file, with the column mapping guessed
+    /// from its header by `infer_mapping`
+    pub fn from_csv_auto<P: AsRef<Path>>(path: P) -> Result<Vec<Transaction>> {
+        let (mapping, inference) = Self::infer_mapping(path.as_ref())?;
+        log::info!(
+            "Inferred columns: transaction id `{}`, items `{}`, timestamp `{}`",
+            inference.transaction_id.header,
+            inference.items.header,
+            inference.timestamp.header
+        );
+        Self::from_csv(path, mapping)
+    }
+
+    /// Parse raw CSV rows after the header, if any, into `rows`
+    fn collect_csv_rows(
+        raw_rows: impl Iterator<Item = Result<Vec<String>>>,
+        csv: &CsvOptions,
+        mapping: &ColumnMapping,
+        rows: &mut RowCollector,
+    ) -> Result<()> {
+        for (idx, row) in raw_rows.enumerate() {
+            if rows.window.exhausted() {
+                break;
+            }
+            let row_idx = idx + 1;
+
+            // Skip header row
+            if csv.has_header && row_idx == 1 {
+                row?;
+                continue;
+            }
+
+            let row = row?;
+            if rows.window.take() {
+                rows.push(&row, row_idx, mapping)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stream transactions from a CSV file as they are read
+    ///
+    /// Unlike `from_csv`, nothing is held beyond the current row, and rows
+    /// that fail to parse are yielded as errors rather than logged and
+    /// skipped: filter them out or stop at the first, as suits the caller.
+    /// The header row is skipped. Quantile-binned columns need every value
+    /// up front and are rejected with `InvalidConfig`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader};
+    /// use rust_rule_miner::{MiningConfig, RuleMiner};
+    ///
+    /// let mut miner = RuleMiner::new(MiningConfig::default());
+    /// let stream = DataLoader::stream_csv("transactions.csv", ColumnMapping::simple(0, 1, 2))?;
+    /// // Drop unparseable rows, mine the rest
+    /// miner.add_transactions_from_iter(stream.filter(Result::is_ok))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn stream_csv<P: AsRef<Path>>(
+        path: P,
+        mapping: ColumnMapping,
+    ) -> Result<TransactionStream> {
+        Self::stream_csv_with_options(path, mapping, LoaderOptions::default())
+    }
+
+    /// Stream transactions from a CSV file, taking and transforming rows per
+    /// `options`
+    ///
+    /// `limit`, `skip_rows`, `sample`, `row_filter` and `item_transform`
+    /// apply; `strict`, `max_errors` and `collect_report` do not, as errors
+    /// are yielded to the caller. Reading stops once `limit` rows are taken.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader, LoaderOptions};
+    ///
+    /// // A reproducible 1% sample of at most 50,000 rows
+    /// let options = LoaderOptions::default()
+    ///     .with_sample(0.01)
+    ///     .with_seed(42)
+    ///     .with_limit(50_000);
+    /// let stream =
+    ///     DataLoader::stream_csv_with_options("orders.csv", ColumnMapping::simple(0, 1, 2), options)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn stream_csv_with_options<P: AsRef<Path>>(
+        path: P,
+        mapping: ColumnMapping,
+        options: LoaderOptions,
+    ) -> Result<TransactionStream> {
+        Self::stream_csv_with_format(path, mapping, CsvOptions::default(), options)
+    }
+
+    /// Stream transactions from a delimited text file laid out per `csv`
+    pub fn stream_csv_with_format<P: AsRef<Path>>(
+        path: P,
+        mapping: ColumnMapping,
+        csv: CsvOptions,
+        options: LoaderOptions,
+    ) -> Result<TransactionStream> {
+        let rows = Self::csv_rows(path.as_ref(), &csv)?;
+        transaction_stream(rows, mapping, csv.has_header, csv.item_separator, options)
+    }
+
+    /// Raw rows of a CSV file, decompressed on the fly if it is gzipped
+    ///
+    /// Gzip is recognised by a `.gz` extension or the gzip magic bytes.
+    fn csv_rows(path: &Path, csv: &CsvOptions) -> Result<CsvRows<'static>> {
+        let open_error = |e: std::io::Error| {
+            MiningError::DataLoadError(format!("Failed to open CSV file: {}", e))
+        };
+        let mut file = BufReader::new(std::fs::File::open(path).map_err(open_error)?);
+        let gzipped = path.extension().is_some_and(|ext| ext == "gz")
+            || file
+                .fill_buf()
+                .map_err(open_error)?
+                .starts_with(&GZIP_MAGIC);
+        if gzipped {
+            return Ok(csv_line_rows(
+                BufReader::new(MultiGzDecoder::new(file)),
+                csv,
+            ));
+        }
+        drop(file);
+
+        // excelstream also unpacks .csv.zst and .csv.zip archives
+        let mut reader = CsvReader::open(path)
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open CSV file: {}", e)))?
+            .delimiter(csv.delimiter)
+            .quote_char(csv.quote);
+        let mut row_idx = 0;
+        let mut done = false;
+        Ok(Box::new(std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            row_idx += 1;
+            match reader.read_row() {
+                Ok(row) => row.map(Ok),
+                Err(e) => {
+                    done = true;
+                    Some(Err(MiningError::DataLoadError(format!(
+                        "Failed to read row {}: {}",
+                        row_idx, e
+                    ))))
+                }
+            }
+        })))
+    }
+
+    /// Load transactions from a CSV or XLSX file inside a zip archive
+    ///
+    /// `inner_file` names the archive entry; without it the archive must hold
+    /// exactly one `.csv` or `.xlsx` entry. CSV entries are decompressed as
+    /// they are parsed. XLSX entries are copied to a temporary file first,
+    /// since workbooks are read by path, and their first sheet is loaded.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader};
+    ///
+    /// let transactions =
+    ///     DataLoader::from_zip("export.zip", Some("orders.csv"), ColumnMapping::simple(0, 1, 2))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_zip<P: AsRef<Path>>(
+        path: P,
+        inner_file: Option<&str>,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        let zip_error = |e: &dyn std::fmt::Display| {
+            MiningError::DataLoadError(format!("Failed to read zip archive: {}", e))
+        };
+        let mut archive = StreamingZipReader::open(path.as_ref()).map_err(|e| zip_error(&e))?;
+
+        let is_data = |name: &str| {
+            let name = name.to_lowercase();
+            name.ends_with(".csv") || name.ends_with(".xlsx")
+        };
+        let entry = match inner_file {
+            Some(name) => name.to_string(),
+            None => {
+                let data: Vec<&str> = archive
+                    .entries()
+                    .iter()
+                    .map(|entry| entry.name.as_str())
+                    .filter(|name| is_data(name))
+                    .collect();
+                match data.as_slice() {
+                    [single] => single.to_string(),
+                    _ => {
+                        return Err(MiningError::DataLoadError(format!(
+                        "Expected one CSV or XLSX file in the archive, found {}: name one of {:?}",
+                        data.len(),
+                        data
+                    )))
+                    }
+                }
+            }
+        };
+        let reader = archive
+            .read_entry_streaming_by_name(&entry)
+            .map_err(|e| zip_error(&e))?;
+
+        if entry.to_lowercase().ends_with(".xlsx") {
+            return Self::from_extracted_xlsx(reader, &entry, mapping);
+        }
+        let csv = CsvOptions::default();
+        let mut rows = RowCollector::default();
+        Self::collect_csv_rows(
+            csv_line_rows(BufReader::new(reader), &csv),
+            &csv,
+            &mapping,
+            &mut rows,
+        )?;
+        rows.finish("zipped CSV file")
+            .map(|(transactions, _)| transactions)
+    }
+
+    /// Copy a zipped workbook to a temporary file and load its first sheet
+    fn from_extracted_xlsx(
+        mut reader: impl Read,
+        entry: &str,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        let temp_path = temp_workbook_path(entry);
+        let mut temp_file = std::fs::File::create(&temp_path)?;
+        let copied = std::io::copy(&mut reader, &mut temp_file);
+        drop(temp_file);
+        let result = copied
+            .map_err(MiningError::from)
+            .and_then(|_| Self::from_excel(&temp_path, 0, mapping));
+        std::fs::remove_file(&temp_path).ok();
+        result
+    }
+
+    /// Load transactions from a JSON Lines file (one JSON object per line)
+    ///
+    /// Blank lines are ignored; lines that are not valid JSON or lack a
+    /// mapped field are skipped with a warning.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::{DataLoader, JsonMapping};
+    ///
+    /// // {"id": "tx1", "items": ["Laptop", "Mouse"], "ts": "2024-01-15T10:30:00Z"}
+    /// let mapping = JsonMapping::new("/id", "/items", "/ts");
+    /// let transactions = DataLoader::from_jsonl("events.jsonl", mapping)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_jsonl<P: AsRef<Path>>(path: P, mapping: JsonMapping) -> Result<Vec<Transaction>> {
+        Self::from_jsonl_with_options(path, mapping, LoaderOptions::default())
+            .map(|(transactions, _)| transactions)
+    }
+
+    /// Load transactions from a JSON Lines file, handling bad lines per
+    /// `options`
+    pub fn from_jsonl_with_options<P: AsRef<Path>>(
+        path: P,
+        mapping: JsonMapping,
+        options: LoaderOptions,
+    ) -> Result<(Vec<Transaction>, LoadReport)> {
+        let file = std::fs::File::open(path.as_ref()).map_err(|e| {
+            MiningError::DataLoadError(format!("Failed to open JSON Lines file: {}", e))
+        })?;
+
+        let mut rows = RowCollector::new(options)?;
+        for (idx, line) in std::io::BufReader::new(file).lines().enumerate() {
+            if rows.window.exhausted() {
+                break;
+            }
+            let line = line.map_err(|e| {
+                MiningError::DataLoadError(format!("Failed to read line {}: {}", idx + 1, e))
+            })?;
+            if line.trim().is_empty() || !rows.window.take() {
+                continue;
+            }
+            match serde_json::from_str::<Value>(&line) {
+                Ok(record) => rows.push_json(&record, idx + 1, &mapping)?,
+                Err(e) => {
+                    rows.report.rows_read += 1;
+                    rows.skip(idx + 1, e.to_string(), &line)?;
+                }
+            }
+        }
+
+        rows.finish("JSON Lines file")
+    }
+
+    /// Load transactions from a JSON file holding one top-level array of
+    /// records
+    ///
+    /// Records that lack a mapped field are skipped with a warning.
+    pub fn from_json<P: AsRef<Path>>(path: P, mapping: JsonMapping) -> Result<Vec<Transaction>> {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open JSON file: {}", e)))?;
+        let Value::Array(records) = serde_json::from_str(&text)? else {
+            return Err(MiningError::DataLoadError(
+                "JSON file must hold a top-level array of records".to_string(),
+            ));
+        };
+
+        let mut rows = RowCollector::default();
+        for (idx, record) in records.iter().enumerate() {
+            rows.push_json(record, idx + 1, &mapping)?;
+        }
+
+        rows.finish("JSON file")
+            .map(|(transactions, _)| transactions)
+    }
+
+    /// Load a benchmark file in the SPMF/FIMI format
+    ///
+    /// One transaction per line, items as space-separated integers, as in
+    /// `retail.dat`, `kosarak.dat` or `T10I4D100K.dat`. Lines starting with
+    /// `#`, `%` or `@` (SPMF metadata) and blank lines are ignored; lines
+    /// holding anything but integers are skipped with a warning.
+    ///
+    /// The files have no ids or times: transactions are identified by line
+    /// number and stamped one second apart from the Unix epoch, in file order.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::DataLoader;
+    ///
+    /// let transactions = DataLoader::from_spmf("retail.dat")?;
+    /// println!("{} transactions", transactions.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_spmf<P: AsRef<Path>>(path: P) -> Result<Vec<Transaction>> {
+        let file = std::fs::File::open(path.as_ref())
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open SPMF file: {}", e)))?;
+
+        let mut rows = RowCollector::default();
+        for (idx, line) in std::io::BufReader::new(file).lines().enumerate() {
+            let line_no = idx + 1;
+            let line = line.map_err(|e| {
+                MiningError::DataLoadError(format!("Failed to read line {}: {}", line_no, e))
+            })?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(['#', '%', '@']) {
+                continue;
+            }
+
+            let items: Option<Vec<String>> = line
+                .split_whitespace()
+                .map(|token| token.parse::<u64>().ok().map(|item| item.to_string()))
+                .collect();
+            rows.report.rows_read += 1;
+            let Some(items) = items else {
+                rows.skip(line_no, "items must be integers".to_string(), line)?;
+                continue;
+            };
+            let timestamp = DateTime::from_timestamp(rows.transactions.len() as i64, 0)
+                .unwrap_or(DateTime::UNIX_EPOCH);
+            rows.transactions
+                .push(Transaction::new(line_no.to_string(), items, timestamp));
+        }
+
+        rows.finish("SPMF file")
+            .map(|(transactions, _)| transactions)
+    }
+
+    /// Map one JSON record to a transaction; None when it has no items
+    fn parse_json_record(
+        record: &Value,
+        row_idx: usize,
+        mapping: &JsonMapping,
+        options: &LoaderOptions,
+        report: &mut LoadReport,
+    ) -> Result<Option<Transaction>> {
+        let field = |pointer: &str| {
+            record.pointer(pointer).ok_or_else(|| {
+                MiningError::DataLoadError(format!("Record {} has no `{}`", row_idx, pointer))
+            })
+        };
+        let text = |pointer: &str| {
+            json_text(field(pointer)?).ok_or_else(|| {
+                MiningError::DataLoadError(format!(
+                    "Record {}: `{}` is not a string or number",
+                    row_idx, pointer
+                ))
+            })
+        };
+
+        let tx_id = text(&mapping.transaction_id)?;
+        if tx_id.is_empty() {
+            return Ok(None);
+        }
+
+        let elements = match field(&mapping.items)? {
+            Value::Array(elements) => elements.as_slice(),
+            single => std::slice::from_ref(single),
+        };
+        let items: Vec<String> = elements
+            .iter()
+            .filter_map(|element| {
+                if mapping.item_fields.is_empty() {
+                    return json_text(element);
+                }
+                let parts: Vec<String> = mapping
+                    .item_fields
+                    .iter()
+                    .filter_map(|pointer| element.pointer(pointer).and_then(json_text))
+                    .filter(|part| !part.is_empty())
+                    .collect();
+                Some(parts.join(&mapping.field_separator))
+            })
+            .filter(|item| !item.is_empty())
+            .collect();
+        let items = options.transform_items(items);
+        let items = mapping.normalizer.normalize_items(&items);
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        let timestamp = Self::parse_timestamp(
+            &text(&mapping.timestamp)?,
+            &mapping.timestamp_spec,
+            row_idx,
+            report,
+        )?;
+        let mut tx = Transaction::new(tx_id, items, timestamp);
+        if let Some(pointer) = &mapping.user_id {
+            tx.user_id = record.pointer(pointer).and_then(json_text);
+        }
+        let metadata: HashMap<String, Value> = mapping
+            .metadata
+            .iter()
+            .filter_map(|(key, pointer)| Some((key.clone(), record.pointer(pointer)?.clone())))
+            .collect();
+        Ok(Some(tx.with_metadata(metadata)))
+    }
+
+    /// Parse a row of values into a Transaction using column mapping
+    ///
+    /// Item cells are split on `item_separator`; `lists[i]`, if set, holds
+    /// the items of column `i` instead. Uneven item columns are recorded in
+    /// `report` and handled per `mapping.multi_field_policy`. Rows rejected
+    /// by `options.row_filter` give `None`, and items pass through
+    /// `options.item_transform`.
+    pub(crate) fn parse_transaction_with_mapping(
+        row_values: &[String],
+        lists: &[Option<Vec<String>>],
+        item_separator: &str,
+        row_idx: usize,
+        mapping: &ColumnMapping,
+        options: &LoaderOptions,
+        report: &mut LoadReport,
+    ) -> Result<Option<Transaction>> {
+        if let Some(filter) = &options.row_filter {
+            if !filter(row_values) {
+                report.filtered_rows += 1;
+                return Ok(None);
+            }
+        }
+
+        let column_items = |col_idx: usize| -> Vec<String> {
+            match lists.get(col_idx) {
+                Some(Some(list)) => list
+                    .iter()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+                _ => row_values[col_idx]
+                    .split(item_separator)
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            }
+        };
+
+        // Validate row has enough columns
+        let max_col = *[
+            mapping.transaction_id,
+            *mapping.item_columns.iter().max().unwrap_or(&0),
+            mapping.timestamp,
+            mapping
+                .binned_columns
+                .iter()
+                .map(|column| column.index)
+                .max()
+                .unwrap_or(0),
+            mapping.user_id_column.unwrap_or(0),
+            mapping
+                .metadata_columns
+                .iter()
+                .map(|(column, _)| *column)
+                .max()
+                .unwrap_or(0),
+        ]
+        .iter()
+        .max()
+        .unwrap_or(&0);
+
+        if row_values.len() <= max_col {
+            return Err(MiningError::DataLoadError(format!(
+                "Row {} has insufficient columns (expected at least {}, got {})",
+                row_idx,
+                max_col + 1,
+                row_values.len()
+            )));
+        }
+
+        // Extract transaction ID
+        let tx_id = row_values[mapping.transaction_id].trim();
+        if tx_id.is_empty() {
+            return Ok(None); // Skip empty transaction ID
+        }
+
+        // Extract and combine item columns
+        let items: Vec<String> = if mapping.item_columns.len() == 1 {
+            // Single column: split by the item separator (comma by default)
+            // CSV: "Laptop,Mouse,Keyboard"
+            column_items(mapping.item_columns[0])
+        } else {
+            // Multiple columns: split each and zip them together
+            // CSV columns:  "Laptop,Mouse"   "Electronics,Accessories"   "US,US"
+            // Result:       ["Laptop::Electronics::US", "Mouse::Accessories::US"]
+
+            let fields: Vec<Vec<String>> = mapping
+                .item_columns
+                .iter()
+                .map(|&col_idx| column_items(col_idx))
+                .collect();
+
+            let counts: Vec<usize> = fields.iter().map(Vec::len).collect();
+            let max_len = counts.iter().copied().max().unwrap_or(0);
+            if max_len == 0 {
+                return Ok(None); // Skip if no items in any field
+            }
+
+            // Mismatched field counts would leave partial composites
+            let min_len = counts.iter().copied().min().unwrap_or(0);
+            let mut placeholder = None;
+            let len = if min_len == max_len {
+                max_len
+            } else {
+                report.uneven_rows.push(row_idx);
+                match &mapping.multi_field_policy {
+                    MultiFieldPolicy::Lenient => {
+                        log::warn!(
+                            "Row {}: item columns hold {:?} values; joining partial items",
+                            row_idx,
+                            counts
+                        );
+                        max_len
+                    }
+                    MultiFieldPolicy::TruncateToShortest => min_len,
+                    MultiFieldPolicy::PadWithPlaceholder(value) => {
+                        placeholder = Some(value.as_str());
+                        max_len
+                    }
+                    MultiFieldPolicy::SkipRow => {
+                        report.skipped_rows += 1;
+                        return Ok(None);
+                    }
+                    MultiFieldPolicy::Error => {
+                        return Err(MiningError::InvalidTransaction(format!(
+                            "row {} item columns hold {:?} values",
+                            row_idx, counts
+                        )));
+                    }
+                }
+            };
+
+            // Zip fields together with separator
+            (0..len)
+                .map(|i| {
+                    fields
+                        .iter()
+                        .filter_map(|field| field.get(i).map(String::as_str).or(placeholder))
+                        .collect::<Vec<&str>>()
+                        .join(&mapping.field_separator)
+                })
+                .filter(|s| !s.is_empty())
+                .collect()
+        };
+
+        let items = options.transform_items(items);
+        let mut items = mapping.normalizer.normalize_items(&items);
+        if items.is_empty() {
+            return Ok(None);
+        }
+        for column in &mapping.binned_columns {
+            if let Some(item) = column
+                .value(row_values, row_idx)?
+                .and_then(|value| column.threshold_item(value))
+            {
+                if !items.contains(&item) {
+                    items.push(item);
+                }
+            }
+        }
+
+        // Extract timestamp
+        let timestamp = Self::parse_timestamp(
+            &row_values[mapping.timestamp],
+            &mapping.timestamp_spec,
+            row_idx,
+            report,
+        )?;
+
+        let cell =
+            |column: usize| Some(row_values[column].trim()).filter(|value| !value.is_empty());
+        let mut tx = Transaction::new(tx_id.to_string(), items, timestamp);
+        tx.user_id = mapping.user_id_column.and_then(cell).map(str::to_string);
+        let metadata: HashMap<String, Value> = mapping
+            .metadata_columns
+            .iter()
+            .filter_map(|(column, key)| {
+                Some((key.clone(), Value::String(cell(*column)?.to_string())))
+            })
+            .collect();
+        Ok(Some(tx.with_metadata(metadata)))
+    }
+
+    /// Parse a timestamp per `spec`, applying its `invalid_policy` when the
+    /// value does not parse
+    ///
+    /// `InvalidTimestampPolicy::SkipRow` returns a `DataLoadError` for the
+    /// caller to skip the row; `UseEpoch` and `UseNow` are recorded in
+    /// `report.timestamp_fallbacks`.
+    fn parse_timestamp(
+        timestamp_str: &str,
+        spec: &TimestampSpec,
+        row_idx: usize,
+        report: &mut LoadReport,
+    ) -> Result<DateTime<Utc>> {
+        let trimmed = timestamp_str.trim();
+        if let Some(timestamp) = spec.parse(trimmed) {
+            return Ok(timestamp);
+        }
+
+        let fallback = match spec.invalid_policy {
+            InvalidTimestampPolicy::Error => {
+                return Err(MiningError::InvalidTransaction(format!(
+                    "row {} timestamp `{}` did not parse",
+                    row_idx, trimmed
+                )));
+            }
+            InvalidTimestampPolicy::SkipRow => {
+                return Err(MiningError::DataLoadError(format!(
+                    "Row {} timestamp `{}` did not parse",
+                    row_idx, trimmed
+                )));
+            }
+            InvalidTimestampPolicy::UseEpoch => DateTime::UNIX_EPOCH,
+            InvalidTimestampPolicy::UseNow => Utc::now(),
+        };
+        log::warn!(
+            "Failed to parse timestamp '{}' at row {}, using {}",
+            trimmed,
+            row_idx,
+            fallback.to_rfc3339()
+        );
+        report.timestamp_fallbacks.push(row_idx);
+        Ok(fallback)
+    }
+
+    /// List all sheet names from an Excel file
+    pub fn list_sheets<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+        let reader = StreamingReader::open(path.as_ref())
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open Excel file: {}", e)))?;
+
+        Ok(reader.sheet_names().to_vec())
+    }
+
+    /// Load transactions from a Parquet file (requires `parquet` feature)
+    ///
+    /// Streams record batches, so memory stays bounded by the batch size
+    /// rather than the file. Columns are mapped by their position in the
+    /// file schema; use `parquet_columns` with `ColumnMapping::from_names`
+    /// to map by name.
+    ///
+    /// List columns (`List<Utf8>`) are taken as items directly, without
+    /// splitting on commas. The timestamp column may be a Parquet
+    /// timestamp, Unix seconds (`Int64`) or a string parsed per the
+    /// mapping's `TimestampSpec`; Parquet timestamps are read as RFC 3339,
+    /// which needs `TimestampFormat::Auto`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader};
+    ///
+    /// let columns = DataLoader::parquet_columns("orders.parquet")?;
+    /// let mapping = ColumnMapping::from_names(&columns, "order_id", &["skus"], "placed_at")?;
+    /// let transactions = DataLoader::from_parquet("orders.parquet", mapping)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "parquet")]
+    pub fn from_parquet<P: AsRef<Path>>(
+        path: P,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        let reader = parquet_reader(path.as_ref())?.build().map_err(|e| {
+            MiningError::DataLoadError(format!("Failed to read Parquet file: {}", e))
+        })?;
+
+        let mut rows = RowCollector::default();
+        let mut row_idx = 0;
+        for batch in reader {
+            let batch = batch.map_err(|e| {
+                MiningError::DataLoadError(format!("Failed to read row {}: {}", row_idx + 1, e))
+            })?;
+            for row in 0..batch.num_rows() {
+                row_idx += 1;
+                let mut row_values = Vec::with_capacity(batch.num_columns());
+                let mut lists = Vec::with_capacity(batch.num_columns());
+                for column in batch.columns() {
+                    let (value, list) = parquet_cell(column.as_ref(), row)?;
+                    row_values.push(value);
+                    lists.push(list);
+                }
+                rows.push_with_lists(&row_values, &lists, row_idx, &mapping)?;
+            }
+        }
+
+        rows.finish("Parquet file")
+            .map(|(transactions, _)| transactions)
+    }
+
+    /// Column names of a Parquet file, in schema order (requires `parquet`
+    /// feature)
+    #[cfg(feature = "parquet")]
+    pub fn parquet_columns<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+        Ok(parquet_reader(path.as_ref())?
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect())
+    }
+
+    /// Load transactions from a PostgreSQL query (requires `postgres` feature)
+    ///
+    /// The query runs in a transaction through a cursor fetched in batches,
+    /// so memory stays bounded by the batch rather than the result. Each
+    /// result row is one transaction; aggregate line items with
+    /// `array_agg` or `string_agg`. Rows that do not parse are skipped with a
+    /// warning, as in `from_csv`; errors name the row (counting from 1) and
+    /// column.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use rust_rule_miner::data_loader::{DataLoader, PgMapping};
+    ///
+    /// let (mut client, connection) =
+    ///     tokio_postgres::connect("postgresql://localhost/shop", tokio_postgres::NoTls).await?;
+    /// tokio::spawn(connection);
+    ///
+    /// let query = "SELECT o.id, array_agg(l.sku) AS skus, o.placed_at, o.customer_id \
+    ///              FROM orders o JOIN order_lines l ON l.order_id = o.id GROUP BY o.id";
+    /// let mapping = PgMapping::new("id", "skus", "placed_at").with_user_id("customer_id");
+    /// let transactions = DataLoader::from_postgres(&mut client, query, mapping).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "postgres")]
+    pub async fn from_postgres(
+        client: &mut tokio_postgres::Client,
+        query: &str,
+        mapping: PgMapping,
+    ) -> Result<Vec<Transaction>> {
+        let query_error = |e: tokio_postgres::Error| {
+            MiningError::DataLoadError(format!("PostgreSQL query failed: {}", e))
+        };
+        let transaction = client.transaction().await.map_err(query_error)?;
+        let statement = transaction.prepare(query).await.map_err(query_error)?;
+        let columns = mapping.column_mapping(statement.columns())?;
+        let mapped = pg_mapped_columns(&columns);
+        let portal = transaction
+            .bind(&statement, &[])
+            .await
+            .map_err(query_error)?;
+
+        let mut rows = RowCollector::default().with_item_separator(&mapping.item_separator);
+        let mut row_idx = 0;
+        loop {
+            let batch = transaction
+                .query_portal(&portal, PG_FETCH_ROWS)
+                .await
+                .map_err(query_error)?;
+            if batch.is_empty() {
+                break;
+            }
+            for row in &batch {
+                row_idx += 1;
+                let mut row_values = vec![String::new(); row.len()];
+                let mut lists = vec![None; row.len()];
+                for &column in &mapped {
+                    let (value, list) = pg_cell(row, column, row_idx)?;
+                    row_values[column] = value;
+                    lists[column] = list;
+                }
+                rows.push_with_lists(&row_values, &lists, row_idx, &columns)?;
+            }
+        }
+        transaction.commit().await.map_err(query_error)?;
+
+        rows.finish("PostgreSQL query")
+            .map(|(transactions, _)| transactions)
+    }
+
+    /// Connect to `url` without TLS and load transactions from `query`
+    /// (requires `postgres` feature)
+    ///
+    /// Like `from_postgres`; the connection is closed afterwards. Must run
+    /// inside a Tokio runtime.
+    #[cfg(feature = "postgres")]
+    pub async fn from_postgres_url(
+        url: &str,
+        query: &str,
+        mapping: PgMapping,
+    ) -> Result<Vec<Transaction>> {
+        let (mut client, connection) = tokio_postgres::connect(url, tokio_postgres::NoTls)
+            .await
+            .map_err(|e| {
+                MiningError::DataLoadError(format!("Failed to connect to PostgreSQL: {}", e))
+            })?;
+        let connection = tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                log::warn!("PostgreSQL connection error: {}", e);
+            }
+        });
+        let result = Self::from_postgres(&mut client, query, mapping).await;
+        drop(client);
+        connection.await.ok();
+        result
+    }
+
+    /// Load transactions from AWS S3 bucket (requires `cloud` feature)
+    ///
+    /// Streams directly from S3 with constant memory usage (~3-35 MB).
+    ///
+    /// # Arguments
+    /// * `bucket` - S3 bucket name
+    /// * `key` - S3 object key (file path in bucket)
+    /// * `region` - AWS region (e.g., "us-east-1")
+    /// * `sheet_index` - Sheet index (0-based) for Excel files
+    /// * `mapping` - Column mapping configuration
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use rust_rule_miner::data_loader::{DataLoader, ColumnMapping};
+    ///
+    /// // Standard format: transaction_id(0), items(1), timestamp(2)
+    /// let mapping = ColumnMapping::simple(0, 1, 2);
+    ///
+    /// // Load from S3
+    /// let transactions = DataLoader::from_s3(
+    ///     "my-data-bucket",
+    ///     "sales/2024/transactions.xlsx",
+    ///     "us-east-1",
+    ///     0,
+    ///     mapping
+    /// ).await?;
+    ///
+    /// println!("Loaded {} transactions from S3", transactions.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cloud")]
+    pub async fn from_s3(
+        bucket: &str,
+        key: &str,
+        region: &str,
+        sheet_index: usize,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        use excelstream::cloud::S3ExcelReader;
+
+        let mut reader = S3ExcelReader::builder()
+            .bucket(bucket)
+            .key(key)
+            .region(region)
+            .build()
+            .await
+            .map_err(|e| MiningError::DataLoadError(format!("Failed to open S3 file: {}", e)))?;
+
+        let mut rows = RowCollector::default();
+        let mut row_idx = 0;
+
+        for row_result in reader.rows_by_index(sheet_index).map_err(|e| {
             MiningError::DataLoadError(format!("Failed to read sheet {}: {}", sheet_index, e))
         })? {
             let row = row_result.map_err(|e| {
@@ -171,447 +3425,1860 @@ impl DataLoader {
                 continue;
             }
 
-            // Convert row to Vec<String>
-            let row_values = row.to_strings();
+            // Convert row to Vec<String>
+            let row_values = row.to_strings();
+            rows.push(&row_values, row_idx, &mapping)?;
+        }
+
+        rows.finish("S3 file").map(|(transactions, _)| transactions)
+    }
+
+    /// Load transactions from a Google Cloud Storage object (requires
+    /// `cloud` feature)
+    ///
+    /// `.csv` and `.csv.gz` objects are streamed; for `.xlsx` the workbook is
+    /// downloaded to a temporary file and its first sheet loaded. Credentials
+    /// come from Application Default Credentials: `GOOGLE_APPLICATION_CREDENTIALS`,
+    /// `gcloud auth application-default login`, or the metadata server on
+    /// GCP. `STORAGE_EMULATOR_HOST` points requests at an emulator.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader};
+    ///
+    /// let transactions = DataLoader::from_gcs(
+    ///     "my-data-bucket",
+    ///     "sales/2024/transactions.csv",
+    ///     ColumnMapping::simple(0, 1, 2),
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cloud")]
+    pub async fn from_gcs(
+        bucket: &str,
+        object: &str,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        let format = object_format(object)?;
+        let store = crate::cloud_storage::gcs_store(bucket)?;
+        Self::from_cloud_object(&store, object, format, mapping).await
+    }
+
+    /// Load transactions from an Azure Blob Storage blob (requires `cloud`
+    /// feature)
+    ///
+    /// Formats are as for `from_gcs`. Credentials are looked up as the Azure
+    /// CLI does: `AZURE_STORAGE_CONNECTION_STRING`, `AZURE_STORAGE_KEY`,
+    /// `AZURE_STORAGE_SAS_TOKEN`, a service principal in `AZURE_TENANT_ID`,
+    /// `AZURE_CLIENT_ID` and `AZURE_CLIENT_SECRET`, then managed identity.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader};
+    ///
+    /// let transactions = DataLoader::from_azure_blob(
+    ///     "mystorageaccount",
+    ///     "exports",
+    ///     "sales/2024/transactions.xlsx",
+    ///     ColumnMapping::simple(0, 1, 2),
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cloud")]
+    pub async fn from_azure_blob(
+        account: &str,
+        container: &str,
+        blob: &str,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        let format = object_format(blob)?;
+        let store = crate::cloud_storage::azure_store(account, container)?;
+        Self::from_cloud_object(&store, blob, format, mapping).await
+    }
+
+    /// Load transactions from HTTP URL (requires `cloud` feature)
+    ///
+    /// Streams the CSV body chunk by chunk, holding only the rows parsed so
+    /// far. Gzip-encoded responses are decompressed as they arrive. See
+    /// `from_http_with_options` for headers, authentication, timeouts and
+    /// retries.
+    ///
+    /// # Arguments
+    /// * `url` - HTTP URL to CSV file
+    /// * `mapping` - Column mapping configuration
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use rust_rule_miner::data_loader::{DataLoader, ColumnMapping};
+    ///
+    /// // Standard format: transaction_id(0), items(1), timestamp(2)
+    /// let mapping = ColumnMapping::simple(0, 1, 2);
+    ///
+    /// // Load from HTTP endpoint
+    /// let transactions = DataLoader::from_http(
+    ///     "https://example.com/data/transactions.csv",
+    ///     mapping
+    /// ).await?;
+    ///
+    /// println!("Loaded {} transactions from HTTP", transactions.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cloud")]
+    pub async fn from_http(url: &str, mapping: ColumnMapping) -> Result<Vec<Transaction>> {
+        Self::from_http_with_options(url, mapping, HttpOptions::default()).await
+    }
+
+    /// Load transactions from HTTP URL with request settings (requires
+    /// `cloud` feature)
+    ///
+    /// Like `from_http`, sending `options`' headers and credentials and
+    /// parsing the body per `options.csv`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use rust_rule_miner::data_loader::{ColumnMapping, DataLoader, HttpOptions};
+    ///
+    /// let options = HttpOptions::default()
+    ///     .with_bearer_token(std::env::var("EXPORT_TOKEN")?)
+    ///     .with_timeout(Duration::from_secs(60))
+    ///     .with_retries(3);
+    /// let transactions = DataLoader::from_http_with_options(
+    ///     "https://example.com/exports/orders.csv",
+    ///     ColumnMapping::simple(0, 1, 2),
+    ///     options,
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cloud")]
+    pub async fn from_http_with_options(
+        url: &str,
+        mapping: ColumnMapping,
+        options: HttpOptions,
+    ) -> Result<Vec<Transaction>> {
+        let response = Self::http_response(url, &options).await?;
+        Self::collect_http_csv(response, mapping, &options.csv).await
+    }
+
+    /// Parse a CSV response body as it streams in, decompressing it if
+    /// gzip-encoded
+    #[cfg(feature = "cloud")]
+    async fn collect_http_csv(
+        response: reqwest::Response,
+        mapping: ColumnMapping,
+        csv: &CsvOptions,
+    ) -> Result<Vec<Transaction>> {
+        let encoding = response
+            .headers()
+            .get(reqwest::header::CONTENT_ENCODING)
+            .map(|value| value.to_str().unwrap_or_default().trim().to_lowercase());
+        let gzipped = match encoding.as_deref() {
+            None | Some("") | Some("identity") => false,
+            Some("gzip") | Some("x-gzip") => true,
+            Some(other) => {
+                return Err(MiningError::DataLoadError(format!(
+                    "Unsupported Content-Encoding `{}`",
+                    other
+                )))
+            }
+        };
+        let chunks = futures::stream::try_unfold(response, |mut response| async move {
+            let chunk = response.chunk().await.map_err(|e| {
+                MiningError::DataLoadError(format!("Failed to read response: {}", e))
+            })?;
+            Ok(chunk.map(|chunk| (chunk, response)))
+        });
+        Self::collect_csv_chunks(Box::pin(chunks), mapping, csv, gzipped, "HTTP response").await
+    }
+
+    /// Parse CSV as its chunks arrive, decompressing them if `gzipped`
+    #[cfg(feature = "cloud")]
+    async fn collect_csv_chunks<S, B>(
+        mut chunks: S,
+        mapping: ColumnMapping,
+        csv: &CsvOptions,
+        gzipped: bool,
+        source: &str,
+    ) -> Result<Vec<Transaction>>
+    where
+        S: futures::Stream<Item = Result<B>> + Unpin,
+        B: AsRef<[u8]>,
+    {
+        use futures::StreamExt;
+
+        let mut lines = HttpLines::new(gzipped, csv);
+        let mut rows = RowCollector::default().with_item_separator(&csv.item_separator);
+        let mut collect = |batch: Vec<(usize, Vec<String>)>| -> Result<()> {
+            for (row_idx, row_values) in batch {
+                // Skip header row
+                if csv.has_header && row_idx == 1 {
+                    continue;
+                }
+                rows.push(&row_values, row_idx, &mapping)?;
+            }
+            Ok(())
+        };
+
+        while let Some(chunk) = chunks.next().await {
+            collect(lines.push(chunk?.as_ref())?)?;
+        }
+        collect(lines.finish()?)?;
+
+        rows.finish(source).map(|(transactions, _)| transactions)
+    }
+
+    /// Save a downloading workbook to a temporary file and load its first
+    /// sheet
+    #[cfg(feature = "cloud")]
+    async fn download_xlsx<S, B>(
+        mut chunks: S,
+        name: &str,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>>
+    where
+        S: futures::Stream<Item = Result<B>> + Unpin,
+        B: AsRef<[u8]>,
+    {
+        use futures::StreamExt;
+        use std::io::Write;
+
+        let temp_path = temp_workbook_path(name);
+        let mut temp_file = std::fs::File::create(&temp_path)?;
+        let mut copied = Ok(());
+        while let Some(chunk) = chunks.next().await {
+            if let Err(e) = chunk.and_then(|chunk| Ok(temp_file.write_all(chunk.as_ref())?)) {
+                copied = Err(e);
+                break;
+            }
+        }
+        drop(temp_file);
+        let result = copied.and_then(|_| Self::from_excel(&temp_path, 0, mapping));
+        std::fs::remove_file(&temp_path).ok();
+        result
+    }
+
+    /// Stream the object `name` from `store` and load it as `format`
+    #[cfg(feature = "cloud")]
+    async fn from_cloud_object(
+        store: &dyn object_store::ObjectStore,
+        name: &str,
+        format: ObjectFormat,
+        mapping: ColumnMapping,
+    ) -> Result<Vec<Transaction>> {
+        use futures::TryStreamExt;
+
+        let read_error = |e: object_store::Error| {
+            MiningError::DataLoadError(format!("Failed to read `{}`: {}", name, e))
+        };
+        let chunks = store
+            .get(&object_store::path::Path::from(name))
+            .await
+            .map_err(read_error)?
+            .into_stream()
+            .map_err(read_error);
+        match format {
+            ObjectFormat::Xlsx => Self::download_xlsx(chunks, name, mapping).await,
+            ObjectFormat::Csv | ObjectFormat::GzippedCsv => {
+                let gzipped = format == ObjectFormat::GzippedCsv;
+                Self::collect_csv_chunks(chunks, mapping, &CsvOptions::default(), gzipped, name)
+                    .await
+            }
+        }
+    }
+
+    /// Send the GET request, retrying transient failures per `options`
+    #[cfg(feature = "cloud")]
+    async fn http_response(url: &str, options: &HttpOptions) -> Result<reqwest::Response> {
+        let mut client = reqwest::Client::builder();
+        if let Some(timeout) = options.timeout {
+            client = client.timeout(timeout);
+        }
+        let client = client
+            .build()
+            .map_err(|e| MiningError::DataLoadError(format!("HTTP client failed: {}", e)))?;
+
+        let mut attempt = 0;
+        loop {
+            let mut request = client
+                .get(url)
+                .header(reqwest::header::ACCEPT_ENCODING, "gzip");
+            for (name, value) in &options.headers {
+                request = request.header(name.as_str(), value.as_str());
+            }
+            if let Some(token) = &options.bearer_token {
+                request = request.bearer_auth(token);
+            }
+            if let Some((username, password)) = &options.basic_auth {
+                request = request.basic_auth(username, password.as_ref());
+            }
 
-            match Self::parse_transaction_with_mapping(&row_values, row_idx, &mapping) {
-                Ok(Some(tx)) => transactions.push(tx),
-                Ok(None) => continue, // Skip empty rows
-                Err(e) => {
-                    log::warn!("Skipping row {}: {}", row_idx, e);
-                    continue;
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => {
+                    let status = response.status();
+                    let error = format!("HTTP request failed with status {}", status);
+                    if !(status.is_server_error()
+                        || status == reqwest::StatusCode::TOO_MANY_REQUESTS)
+                    {
+                        return Err(MiningError::DataLoadError(error));
+                    }
+                    error
+                }
+                Err(e) if e.is_builder() => {
+                    return Err(MiningError::DataLoadError(format!(
+                        "Invalid HTTP request: {}",
+                        e
+                    )))
                 }
+                Err(e) => format!("HTTP request failed: {}", e),
+            };
+
+            if attempt == options.retries {
+                return Err(MiningError::DataLoadError(if attempt == 0 {
+                    error
+                } else {
+                    format!("{} after {} retries", error, attempt)
+                }));
             }
+            log::warn!("Retrying {}: {}", url, error);
+            tokio::time::sleep(HTTP_RETRY_DELAY * (1 << attempt.min(5))).await;
+            attempt += 1;
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+
+    #[test]
+    fn test_csv_loading() {
+        // Create temporary CSV file
+        let csv_content = r#"transaction_id,items,timestamp
+tx1,"Laptop,Mouse",2024-01-15T10:30:00Z
+tx2,"Phone,Phone Case",2024-01-15T11:00:00Z
+tx3,"Tablet",2024-01-15T12:00:00Z
+"#;
+
+        let temp_file = "/tmp/test_transactions_excelstream.csv";
+        let mut file = fs::File::create(temp_file).unwrap();
+        file.write_all(csv_content.as_bytes()).unwrap();
 
-        if transactions.is_empty() {
-            return Err(MiningError::InsufficientData(
-                "No valid transactions found in Excel file".to_string(),
+        // Load transactions with column mapping
+        let mapping = ColumnMapping::simple(0, 1, 2);
+        let transactions = DataLoader::from_csv(temp_file, mapping).unwrap();
+
+        assert_eq!(transactions.len(), 3);
+        assert_eq!(transactions[0].id, "tx1");
+        assert_eq!(transactions[0].items, vec!["Laptop", "Mouse"]);
+        assert_eq!(transactions[1].items, vec!["Phone", "Phone Case"]);
+        assert_eq!(transactions[2].items, vec!["Tablet"]);
+
+        // Cleanup
+        fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_csv_formats() {
+        let load = |name: &str, content: &str, csv: CsvOptions| {
+            let path = std::env::temp_dir().join(format!(
+                "rust_rule_miner_{}_{}.csv",
+                name,
+                std::process::id()
             ));
-        }
+            fs::write(&path, content).unwrap();
+            let result = DataLoader::from_csv_with_format(
+                &path,
+                ColumnMapping::simple(0, 1, 2),
+                csv,
+                LoaderOptions::strict(),
+            );
+            fs::remove_file(&path).ok();
+            let (transactions, _) = result.unwrap();
+            transactions
+                .into_iter()
+                .map(|tx| (tx.id, tx.items))
+                .collect::<Vec<_>>()
+        };
+        let expected = vec![
+            (
+                "tx1".to_string(),
+                vec!["Laptop".to_string(), "Mouse".to_string()],
+            ),
+            ("tx2".to_string(), vec!["Phone".to_string()]),
+        ];
+
+        // Semicolon-delimited with comma-separated items, unquoted
+        let semicolon = CsvOptions::default().with_delimiter(b';');
+        let content = "id;items;at\ntx1;Laptop,Mouse;15/01/2024\ntx2;Phone;16/01/2024\n";
+        assert_eq!(load("semicolon", content, semicolon.clone()), expected);
+
+        // ...or with semicolon-separated items, quoted
+        let content = "id;items;at\ntx1;\"Laptop;Mouse\";15/01/2024\ntx2;Phone;16/01/2024\n";
+        assert_eq!(
+            load(
+                "semicolon_items",
+                content,
+                semicolon.with_item_separator(";")
+            ),
+            expected
+        );
+
+        // Tab-delimited, items separated by pipes and quoted with '
+        let tab = CsvOptions::default()
+            .with_delimiter(b'\t')
+            .with_quote(b'\'')
+            .with_item_separator("|");
+        let content = "id\titems\tat\ntx1\t'Laptop|Mouse'\t2024-01-15\ntx2\tPhone\t2024-01-16\n";
+        assert_eq!(load("tab", content, tab), expected);
 
-        Ok(transactions)
+        // Headerless: the first row is data
+        let headerless = CsvOptions::default().with_header(false);
+        let content = "tx1,\"Laptop,Mouse\",2024-01-15\ntx2,Phone,2024-01-16\n";
+        assert_eq!(load("headerless", content, headerless.clone()), expected);
+
+        let path = std::env::temp_dir().join(format!(
+            "rust_rule_miner_headerless_stream_{}.csv",
+            std::process::id()
+        ));
+        fs::write(&path, content).unwrap();
+        let streamed: Vec<String> = DataLoader::stream_csv_with_format(
+            &path,
+            ColumnMapping::simple(0, 1, 2),
+            headerless,
+            LoaderOptions::default(),
+        )
+        .unwrap()
+        .map(|tx| tx.unwrap().id)
+        .collect();
+        fs::remove_file(&path).ok();
+        assert_eq!(streamed, ["tx1", "tx2"]);
     }
 
-    /// Load transactions from CSV file with custom column mapping
-    ///
-    /// Uses excelstream for high-performance streaming with constant memory usage.
-    ///
-    /// First row is treated as header and skipped.
-    ///
-    /// # Arguments
-    /// * `path` - Path to CSV file
-    /// * `mapping` - Column mapping configuration
-    ///
-    /// # Returns
-    /// Vector of transactions
-    ///
-    /// # Example
-    /// ```no_run
-    /// use rust_rule_miner::data_loader::{DataLoader, ColumnMapping};
-    ///
-    /// // Standard format: transaction_id(0), items(1), timestamp(2)
-    /// let mapping = ColumnMapping::simple(0, 1, 2);
-    /// let transactions = DataLoader::from_csv("transactions.csv", mapping)?;
-    /// println!("Loaded {} transactions", transactions.len());
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
-    /// ```
-    pub fn from_csv<P: AsRef<Path>>(path: P, mapping: ColumnMapping) -> Result<Vec<Transaction>> {
-        let mut reader = CsvReader::open(path.as_ref())
-            .map_err(|e| MiningError::DataLoadError(format!("Failed to open CSV file: {}", e)))?;
+    #[test]
+    fn test_user_id_and_metadata_columns() {
+        let temp_file = "/tmp/test_transactions_user_metadata.csv";
+        fs::write(
+            temp_file,
+            "order,product,category,timestamp,customer,store\n\
+             o1,\"Laptop,Mouse\",\"Electronics,Accessories\",2024-01-15T10:30:00Z,c42,Berlin\n\
+             o2,Phone,Electronics,2024-01-15T11:00:00Z,,\n",
+        )
+        .unwrap();
 
-        let mut transactions = Vec::new();
-        let mut row_idx = 0;
+        let mapping = ColumnMapping::multi_field(0, vec![1, 2], 3, "::".to_string())
+            .with_user_id_column(4)
+            .with_metadata_column(5, "store")
+            .with_metadata_column(4, "customer");
+        let transactions = DataLoader::from_csv(temp_file, mapping).unwrap();
+        fs::remove_file(temp_file).ok();
 
-        for row_result in reader.rows() {
-            let row = row_result.map_err(|e| {
-                MiningError::DataLoadError(format!("Failed to read row {}: {}", row_idx, e))
-            })?;
+        assert_eq!(
+            transactions[0].items,
+            ["Laptop::Electronics", "Mouse::Accessories"]
+        );
+        assert_eq!(transactions[0].user_id.as_deref(), Some("c42"));
+        assert_eq!(transactions[0].metadata["store"], Value::from("Berlin"));
+        assert_eq!(transactions[0].metadata["customer"], Value::from("c42"));
 
-            row_idx += 1;
+        // Empty cells leave the user id unset and the metadata out
+        assert_eq!(transactions[1].items, ["Phone::Electronics"]);
+        assert_eq!(transactions[1].user_id, None);
+        assert!(transactions[1].metadata.is_empty());
+    }
 
-            // Skip header row
-            if row_idx == 1 {
-                continue;
+    #[test]
+    fn test_excel_sheets_by_name() {
+        use excelstream::fast_writer::UltraLowMemoryWorkbook;
+
+        let path = std::env::temp_dir().join(format!(
+            "rust_rule_miner_two_sheets_{}.xlsx",
+            std::process::id()
+        ));
+        let mut workbook = UltraLowMemoryWorkbook::new(&path).unwrap();
+        for (sheet, rows) in [
+            (
+                "North",
+                vec![["tx1", "Laptop,Mouse", "2024-01-15T10:30:00Z"]],
+            ),
+            (
+                "South",
+                vec![
+                    ["tx1", "Phone", "2024-01-15T11:00:00Z"],
+                    ["tx2", "Tablet", "2024-01-15T12:00:00Z"],
+                ],
+            ),
+        ] {
+            workbook.add_worksheet(sheet).unwrap();
+            workbook.write_row(&["id", "items", "timestamp"]).unwrap();
+            for row in rows {
+                workbook.write_row(&row).unwrap();
             }
+        }
+        workbook.close().unwrap();
+        let mapping = ColumnMapping::simple(0, 1, 2);
 
-            // Convert row to Vec<String>
-            let row_values: Vec<String> = row.into_iter().map(|v| v.to_string()).collect();
+        let south = DataLoader::from_excel_sheet(&path, "south", mapping.clone()).unwrap();
+        let ids: Vec<&str> = south.iter().map(|tx| tx.id.as_str()).collect();
+        assert_eq!(ids, ["tx1", "tx2"]);
 
-            match Self::parse_transaction_with_mapping(&row_values, row_idx, &mapping) {
-                Ok(Some(tx)) => transactions.push(tx),
-                Ok(None) => continue, // Skip empty rows
-                Err(e) => {
-                    log::warn!("Skipping row {}: {}", row_idx, e);
-                    continue;
-                }
+        match DataLoader::from_excel_sheet(&path, "West", mapping.clone()) {
+            Err(MiningError::DataLoadError(message)) => {
+                assert!(message.contains("`North`, `South`"), "{}", message)
             }
+            other => panic!("expected DataLoadError, got {:?}", other),
         }
 
-        if transactions.is_empty() {
-            return Err(MiningError::InsufficientData(
-                "No valid transactions found in CSV file".to_string(),
-            ));
+        // Each sheet's header skipped, ids prefixed by sheet
+        let all = DataLoader::from_excel_all_sheets(&path, mapping).unwrap();
+        std::fs::remove_file(&path).ok();
+        let ids: Vec<&str> = all.iter().map(|tx| tx.id.as_str()).collect();
+        assert_eq!(ids, ["North:tx1", "South:tx1", "South:tx2"]);
+        assert_eq!(all[0].items, ["Laptop", "Mouse"]);
+    }
+
+    /// Id, items and timestamp of each loaded transaction
+    fn tx_fields(loaded: Result<Vec<Transaction>>) -> Vec<(String, Vec<String>, DateTime<Utc>)> {
+        loaded
+            .unwrap()
+            .into_iter()
+            .map(|tx| (tx.id, tx.items, tx.timestamp))
+            .collect()
+    }
+
+    #[test]
+    fn test_compressed_csv() {
+        let fixture = |name: &str| {
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures")
+                .join(name)
+        };
+        let mapping = ColumnMapping::simple(0, 1, 2);
+        let expected = tx_fields(DataLoader::from_csv(fixture("orders.csv"), mapping.clone()));
+        assert_eq!(expected.len(), 4);
+
+        let gzipped = DataLoader::from_csv(fixture("orders.csv.gz"), mapping.clone());
+        assert_eq!(tx_fields(gzipped), expected);
+        let streamed = DataLoader::stream_csv(fixture("orders.csv.gz"), mapping.clone())
+            .unwrap()
+            .collect();
+        assert_eq!(tx_fields(streamed), expected);
+
+        // Recognised by its magic bytes without the extension
+        let renamed =
+            std::env::temp_dir().join(format!("rust_rule_miner_orders_{}.csv", std::process::id()));
+        fs::copy(fixture("orders.csv.gz"), &renamed).unwrap();
+        let sniffed = DataLoader::from_csv(&renamed, mapping.clone());
+        fs::remove_file(&renamed).ok();
+        assert_eq!(tx_fields(sniffed), expected);
+
+        // The archive's only CSV, found or named
+        let zipped = DataLoader::from_zip(fixture("orders.zip"), None, mapping.clone());
+        assert_eq!(tx_fields(zipped), expected);
+        let named =
+            DataLoader::from_zip(fixture("orders.zip"), Some("orders.csv"), mapping.clone());
+        assert_eq!(tx_fields(named), expected);
+        assert!(matches!(
+            DataLoader::from_zip(fixture("orders.zip"), Some("missing.csv"), mapping),
+            Err(MiningError::DataLoadError(_))
+        ));
+    }
+
+    #[test]
+    fn test_zipped_xlsx() {
+        use excelstream::fast_writer::{StreamingZipWriter, UltraLowMemoryWorkbook};
+
+        let temp = |name: &str| {
+            std::env::temp_dir().join(format!("rust_rule_miner_{}_{}", std::process::id(), name))
+        };
+        let (xlsx, zip) = (temp("orders.xlsx"), temp("orders_xlsx.zip"));
+        let mut workbook = UltraLowMemoryWorkbook::new(&xlsx).unwrap();
+        workbook.add_worksheet("Orders").unwrap();
+        workbook.write_row(&["id", "items", "timestamp"]).unwrap();
+        workbook
+            .write_row(&["tx1", "Laptop,Mouse", "2024-01-15T10:30:00Z"])
+            .unwrap();
+        workbook.close().unwrap();
+        let mut writer = StreamingZipWriter::new(&zip).unwrap();
+        writer.start_entry("export/orders.xlsx").unwrap();
+        writer.write_data(&fs::read(&xlsx).unwrap()).unwrap();
+        writer.finish().unwrap();
+
+        let mapping = ColumnMapping::simple(0, 1, 2);
+        let expected = tx_fields(DataLoader::from_excel(&xlsx, 0, mapping.clone()));
+        let zipped = DataLoader::from_zip(&zip, None, mapping);
+        fs::remove_file(&xlsx).ok();
+        fs::remove_file(&zip).ok();
+        assert_eq!(tx_fields(zipped), expected);
+    }
+
+    #[test]
+    fn test_stream_csv_into_miner() {
+        let temp_file = "/tmp/test_transactions_stream.csv";
+        fs::write(
+            temp_file,
+            "transaction_id,items,timestamp\n\
+             tx1,\"Laptop,Mouse\",2024-01-15T10:30:00Z\n\
+             tx2,\"Laptop,Mouse\",2024-01-15T11:00:00Z\n\
+             tx3\n\
+             tx4,\"Laptop,Mouse,Hub\",2024-01-15T12:00:00Z\n",
+        )
+        .unwrap();
+        let mapping = ColumnMapping::simple(0, 1, 2);
+
+        // The short row reaches the caller instead of being dropped
+        let mut miner = crate::RuleMiner::new(crate::MiningConfig::default());
+        let stream = DataLoader::stream_csv(temp_file, mapping.clone()).unwrap();
+        match miner.add_transactions_from_iter(stream) {
+            Err(MiningError::DataLoadError(message)) => assert!(message.contains("Row 4")),
+            other => panic!("expected DataLoadError, got {:?}", other),
         }
 
-        Ok(transactions)
+        let config = crate::MiningConfig::builder()
+            .min_support(crate::Support::fraction(0.5).unwrap())
+            .build()
+            .unwrap();
+        let mut miner = crate::RuleMiner::new(config);
+        let stream = DataLoader::stream_csv(temp_file, mapping).unwrap();
+        miner
+            .add_transactions_from_iter(stream.filter(Result::is_ok))
+            .unwrap();
+        let quantiles = ColumnMapping::simple(0, 1, 2)
+            .with_binned_column(BinnedColumn::quantiles(1, "price:", 3));
+        let rejected = DataLoader::stream_csv(temp_file, quantiles);
+        fs::remove_file(temp_file).ok();
+
+        let ids: Vec<&str> = miner
+            .transactions()
+            .iter()
+            .map(|tx| tx.id.as_str())
+            .collect();
+        assert_eq!(ids, ["tx1", "tx2", "tx4"]);
+        assert!(!miner.mine().unwrap().rules.is_empty());
+        assert!(matches!(rejected, Err(MiningError::InvalidConfig(_))));
     }
 
-    /// Parse a row of values into a Transaction using column mapping
-    pub(crate) fn parse_transaction_with_mapping(
-        row_values: &[String],
-        row_idx: usize,
-        mapping: &ColumnMapping,
-    ) -> Result<Option<Transaction>> {
-        // Validate row has enough columns
-        let max_col = *[
-            mapping.transaction_id,
-            *mapping.item_columns.iter().max().unwrap_or(&0),
-            mapping.timestamp,
+    #[test]
+    fn test_csv_loading_normalizes_items() {
+        // Row 1 is NFC, row 2 NFD with full-width Latin, row 3 repeats an item
+        let csv_content = "transaction_id,items,timestamp\n\
+tx1,\"C\u{E0} ph\u{EA},USB\",2024-01-15T10:30:00Z\n\
+tx2,\"Ca\u{300} phe\u{302},\u{FF35}\u{FF33}\u{FF22}\",2024-01-15T11:00:00Z\n\
+tx3,\"C\u{E0} ph\u{EA},Ca\u{300} phe\u{302}\",2024-01-15T12:00:00Z\n";
+
+        let temp_file = "/tmp/test_transactions_normalized.csv";
+        fs::write(temp_file, csv_content).unwrap();
+
+        let normalizer = ItemNormalizer::standard().with_width_folding(true);
+        let mapping = ColumnMapping::simple(0, 1, 2).with_normalizer(normalizer);
+        let transactions = DataLoader::from_csv(temp_file, mapping).unwrap();
+
+        assert_eq!(transactions[0].items, vec!["C\u{E0} ph\u{EA}", "USB"]);
+        assert_eq!(transactions[1].items, transactions[0].items);
+        assert_eq!(transactions[2].items, vec!["C\u{E0} ph\u{EA}"]);
+
+        fs::remove_file(temp_file).ok();
+    }
+
+    // Mouse has no category, Keyboard no region
+    fn uneven_row() -> Vec<String> {
+        [
+            "tx1",
+            "Laptop,Mouse,Keyboard",
+            "Electronics",
+            "US,US",
+            "2024-01-15",
         ]
         .iter()
-        .max()
-        .unwrap_or(&0);
+        .map(|s| s.to_string())
+        .collect()
+    }
 
-        if row_values.len() <= max_col {
-            return Err(MiningError::DataLoadError(format!(
-                "Row {} has insufficient columns (expected at least {}, got {})",
-                row_idx,
-                max_col + 1,
-                row_values.len()
-            )));
+    fn parse_uneven(policy: MultiFieldPolicy) -> (Result<Option<Transaction>>, LoadReport) {
+        let mapping = ColumnMapping::multi_field(0, vec![1, 2, 3], 4, "::".to_string())
+            .with_multi_field_policy(policy);
+        let mut report = LoadReport::default();
+        let parsed = DataLoader::parse_transaction_with_mapping(
+            &uneven_row(),
+            &[],
+            ",",
+            2,
+            &mapping,
+            &LoaderOptions::default(),
+            &mut report,
+        );
+        (parsed, report)
+    }
+
+    #[test]
+    fn test_multi_field_policies() {
+        for (policy, expected) in [
+            (
+                MultiFieldPolicy::Lenient,
+                Some(vec!["Laptop::Electronics::US", "Mouse::US", "Keyboard"]),
+            ),
+            (
+                MultiFieldPolicy::TruncateToShortest,
+                Some(vec!["Laptop::Electronics::US"]),
+            ),
+            (
+                MultiFieldPolicy::PadWithPlaceholder("?".to_string()),
+                Some(vec![
+                    "Laptop::Electronics::US",
+                    "Mouse::?::US",
+                    "Keyboard::?::?",
+                ]),
+            ),
+            (MultiFieldPolicy::SkipRow, None),
+        ] {
+            let (parsed, report) = parse_uneven(policy.clone());
+            let items = parsed.unwrap().map(|tx| tx.items);
+            let expected: Option<Vec<String>> =
+                expected.map(|items| items.iter().map(|s| s.to_string()).collect());
+            assert_eq!(items, expected, "{:?}", policy);
+            assert_eq!(report.uneven_rows, vec![2], "{:?}", policy);
+            assert_eq!(
+                report.skipped_rows,
+                usize::from(policy == MultiFieldPolicy::SkipRow)
+            );
         }
 
-        // Extract transaction ID
-        let tx_id = row_values[mapping.transaction_id].trim();
-        if tx_id.is_empty() {
-            return Ok(None); // Skip empty transaction ID
+        let (parsed, report) = parse_uneven(MultiFieldPolicy::Error);
+        assert!(matches!(parsed, Err(MiningError::InvalidTransaction(_))));
+        assert_eq!(report.uneven_rows, vec![2]);
+    }
+
+    #[test]
+    fn test_multi_field_load_report() {
+        let csv_content = "id,product,category,timestamp\n\
+tx1,\"Laptop,Mouse\",\"Electronics,Accessories\",2024-01-15T10:30:00Z\n\
+tx2,\"Laptop,Mouse\",Electronics,2024-01-15T11:00:00Z\n\
+tx3,Phone\n\
+tx4,Phone,Electronics,2024-01-15T12:00:00Z\n";
+        let temp_file = "/tmp/test_transactions_uneven.csv";
+        fs::write(temp_file, csv_content).unwrap();
+        let mapping = ColumnMapping::multi_field(0, vec![1, 2], 3, "::".to_string());
+
+        let (transactions, report) =
+            DataLoader::from_csv_with_report(temp_file, mapping.clone()).unwrap();
+        assert_eq!(transactions.len(), 3);
+        assert_eq!(transactions[1].items, vec!["Laptop::Electronics", "Mouse"]);
+        assert_eq!(
+            report,
+            LoadReport {
+                rows_read: 4,
+                transactions: 3,
+                skipped_rows: 1, // tx3 lacks columns
+                filtered_rows: 0,
+                skip_reasons: report.skip_reasons.clone(),
+                uneven_rows: vec![3],
+                timestamp_fallbacks: Vec::new(),
+            }
+        );
+        assert_eq!(report.skip_reasons[0].0, 4);
+
+        let strict = mapping.with_multi_field_policy(MultiFieldPolicy::Error);
+        match DataLoader::from_csv(temp_file, strict) {
+            Err(MiningError::InvalidTransaction(message)) => assert!(message.contains("row 3")),
+            other => panic!("expected InvalidTransaction, got {:?}", other),
         }
 
-        // Extract and combine item columns
-        let items: Vec<String> = if mapping.item_columns.len() == 1 {
-            // Single column: split by comma (traditional format)
-            // CSV: "Laptop,Mouse,Keyboard"
-            row_values[mapping.item_columns[0]]
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect()
-        } else {
-            // Multiple columns: split each and zip them together
-            // CSV columns:  "Laptop,Mouse"   "Electronics,Accessories"   "US,US"
-            // Result:       ["Laptop::Electronics::US", "Mouse::Accessories::US"]
+        fs::remove_file(temp_file).ok();
+    }
 
-            let fields: Vec<Vec<String>> = mapping
-                .item_columns
-                .iter()
-                .map(|&col_idx| {
-                    row_values[col_idx]
-                        .split(',')
-                        .map(|s| s.trim().to_string())
-                        .filter(|s| !s.is_empty())
-                        .collect()
-                })
-                .collect();
+    #[test]
+    fn test_loader_options_on_broken_rows() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/broken_orders.csv");
+        let mapping = ColumnMapping::simple(0, 1, 2);
+        let load = |options| DataLoader::from_csv_with_options(&path, mapping.clone(), options);
 
-            // Find the maximum length to handle mismatched field counts
-            let max_len = fields.iter().map(|f| f.len()).max().unwrap_or(0);
-            if max_len == 0 {
-                return Ok(None); // Skip if no items in any field
+        // Lenient: short rows and the bad timestamp skipped with reasons
+        let (transactions, report) = load(LoaderOptions::default()).unwrap();
+        let ids: Vec<&str> = transactions.iter().map(|tx| tx.id.as_str()).collect();
+        assert_eq!(ids, ["tx1", "tx4", "tx6"]);
+        assert_eq!(report.rows_read, 6);
+        assert_eq!(report.skipped_rows, 3);
+        let skipped: Vec<usize> = report.skip_reasons.iter().map(|(row, _)| *row).collect();
+        assert_eq!(skipped, [3, 4, 6]);
+        assert!(report.skip_reasons[0].1.contains("insufficient columns"));
+        assert!(report.skip_reasons[1].1.contains("`last tuesday`"));
+        assert!(report.timestamp_fallbacks.is_empty());
+
+        let (_, report) = load(LoaderOptions::default().with_collect_report(false)).unwrap();
+        assert_eq!(report.skipped_rows, 3);
+        assert!(report.skip_reasons.is_empty());
+
+        match load(LoaderOptions::default().with_max_errors(1)) {
+            Err(MiningError::DataLoadError(message)) => assert!(message.contains("row 4")),
+            other => panic!("expected DataLoadError, got {:?}", other),
+        }
+
+        // Strict: the first bad row aborts, quoted
+        match load(LoaderOptions::strict()) {
+            Err(MiningError::DataLoadError(message)) => {
+                assert!(
+                    message.starts_with("Row 3 has insufficient columns"),
+                    "{}",
+                    message
+                );
+                assert!(message.ends_with("row 3 was `tx2,Phone`"), "{}", message);
+            }
+            other => panic!("expected DataLoadError, got {:?}", other),
+        }
+        let temp_file = "/tmp/test_transactions_bad_timestamp.csv";
+        fs::write(
+            temp_file,
+            "id,items,ts\ntx1,Laptop,2024-01-15T10:30:00Z\ntx2,Mouse,soon\n",
+        )
+        .unwrap();
+        // Skipped by default, stamped with the load time here: strict
+        // refuses both
+        let use_now = mapping.clone().with_timestamp_spec(
+            TimestampSpec::default().with_invalid_policy(InvalidTimestampPolicy::UseNow),
+        );
+        for mapping in [mapping, use_now] {
+            match DataLoader::from_csv_with_options(temp_file, mapping, LoaderOptions::strict()) {
+                Err(MiningError::DataLoadError(message)) => {
+                    assert!(message.starts_with("Row 3 timestamp"), "{}", message)
+                }
+                other => panic!("expected DataLoadError, got {:?}", other),
             }
+        }
+        fs::remove_file(temp_file).ok();
+    }
 
-            // Zip fields together with separator
-            (0..max_len)
-                .map(|i| {
-                    fields
-                        .iter()
-                        .filter_map(|field| field.get(i).cloned())
-                        .collect::<Vec<String>>()
-                        .join(&mapping.field_separator)
-                })
-                .filter(|s| !s.is_empty())
-                .collect()
+    #[test]
+    fn test_row_filter_and_item_transform() {
+        use crate::config::{Confidence, Lift, MiningConfig, Support};
+        use crate::mining::RuleMiner;
+
+        let path = std::env::temp_dir().join(format!(
+            "rust_rule_miner_soft_deleted_{}.csv",
+            std::process::id()
+        ));
+        fs::write(
+            &path,
+            "id,items,ts,store,is_deleted\n\
+             o1,\"Coffee Beans,Milk Whole\",2024-01-15T10:00:00Z,s1,false\n\
+             o2,\"Coffee Ground,Milk Oat\",2024-01-15T11:00:00Z,s1,false\n\
+             o3,\"Coffee Beans,Milk Oat,Sample Sachet\",2024-01-15T12:00:00Z,s2,false\n\
+             o4,\"Tea Green,Milk Whole\",2024-01-15T13:00:00Z,s2,true\n\
+             o5,\"Tea Black,Milk Oat\",2024-01-15T14:00:00Z,s2,true\n\
+             o6,Bread Rye,2024-01-15T15:00:00Z,s1,false\n",
+        )
+        .unwrap();
+        let options = LoaderOptions::strict()
+            .with_row_filter(|row| row.get(4).is_none_or(|deleted| deleted != "true"))
+            .with_item_transform(|product| {
+                let category = product.split_whitespace().next()?;
+                (category != "Sample").then(|| category.to_string())
+            });
+        let (transactions, report) =
+            DataLoader::from_csv_with_options(&path, ColumnMapping::simple(0, 1, 2), options)
+                .unwrap();
+        fs::remove_file(&path).ok();
+
+        // Deleted rows never parse, and are not errors even in strict mode
+        let ids: Vec<&str> = transactions.iter().map(|tx| tx.id.as_str()).collect();
+        assert_eq!(ids, ["o1", "o2", "o3", "o6"]);
+        assert_eq!(report.rows_read, 6);
+        assert_eq!(report.filtered_rows, 2);
+        assert_eq!(report.skipped_rows, 0);
+        // "Coffee Beans" becomes "Coffee"; the sample is dropped
+        assert_eq!(transactions[2].items, ["Coffee", "Milk"]);
+        assert_eq!(transactions[3].items, ["Bread"]);
+
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.5).unwrap())
+            .min_confidence(Confidence::fraction(0.9).unwrap())
+            .min_lift(Lift::new(0.0).unwrap())
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        let rules = miner.mine().unwrap().rules;
+        let coffee_milk = rules
+            .iter()
+            .find(|rule| rule.antecedent == ["Coffee"] && rule.consequent == ["Milk"])
+            .unwrap();
+        assert_eq!(coffee_milk.metrics.support, 0.75);
+        assert_eq!(coffee_milk.metrics.confidence, 1.0);
+        assert!(rules.iter().all(|rule| !rule
+            .antecedent
+            .iter()
+            .chain(&rule.consequent)
+            .any(|item| item == "Tea" || item.contains(' '))));
+    }
+
+    #[test]
+    fn test_limit_skip_and_sample() {
+        use excelstream::fast_writer::UltraLowMemoryWorkbook;
+
+        let path = std::env::temp_dir().join(format!(
+            "rust_rule_miner_many_rows_{}.csv",
+            std::process::id()
+        ));
+        let mut csv = String::from("id,items,ts\n");
+        for i in 1..=5000 {
+            csv.push_str(&format!("tx{},\"Laptop,Mouse\",2024-01-15T10:30:00Z\n", i));
+        }
+        fs::write(&path, csv).unwrap();
+        let mapping = ColumnMapping::simple(0, 1, 2);
+        let load = |options: LoaderOptions| {
+            DataLoader::from_csv_with_options(&path, mapping.clone(), options).unwrap()
+        };
+        let ids = |transactions: &[Transaction]| -> Vec<String> {
+            transactions.iter().map(|tx| tx.id.clone()).collect()
         };
 
-        if items.is_empty() {
-            return Ok(None);
+        let (first, report) = load(LoaderOptions::default().with_limit(50));
+        assert_eq!(first.len(), 50);
+        assert_eq!(report.rows_read, 50);
+        assert_eq!(first[49].id, "tx50");
+
+        let (window, _) = load(LoaderOptions::default().with_skip_rows(100).with_limit(10));
+        assert_eq!(
+            ids(&window),
+            (101..=110).map(|i| format!("tx{}", i)).collect::<Vec<_>>()
+        );
+        let (tail, _) = load(LoaderOptions::default().with_skip_rows(4990));
+        assert_eq!(tail.len(), 10);
+
+        // 20% of 5000 rows: 1000 expected, standard deviation ~28
+        let sampled = |seed| load(LoaderOptions::default().with_sample(0.2).with_seed(seed)).0;
+        let sample = sampled(7);
+        assert!((900..=1100).contains(&sample.len()), "{}", sample.len());
+        assert_eq!(ids(&sampled(7)), ids(&sample));
+        assert_ne!(ids(&sampled(8)), ids(&sample));
+
+        // Streams take the same rows
+        let options = LoaderOptions::default().with_sample(0.2).with_seed(7);
+        let streamed: Vec<String> =
+            DataLoader::stream_csv_with_options(&path, mapping.clone(), options)
+                .unwrap()
+                .map(|tx| tx.unwrap().id)
+                .collect();
+        assert_eq!(streamed, ids(&sample));
+        let options = LoaderOptions::default().with_skip_rows(10).with_limit(3);
+        let streamed: Vec<String> =
+            DataLoader::stream_csv_with_options(&path, mapping.clone(), options)
+                .unwrap()
+                .map(|tx| tx.unwrap().id)
+                .collect();
+        assert_eq!(streamed, ["tx11", "tx12", "tx13"]);
+
+        for fraction in [0.0, 1.5, f64::NAN] {
+            match DataLoader::from_csv_with_options(
+                &path,
+                mapping.clone(),
+                LoaderOptions::default().with_sample(fraction),
+            ) {
+                Err(MiningError::InvalidConfig(message)) => assert!(message.contains("sample")),
+                other => panic!("expected InvalidConfig, got {:?}", other),
+            }
         }
 
-        // Extract timestamp
-        let timestamp = Self::parse_timestamp(&row_values[mapping.timestamp], row_idx)?;
+        fs::remove_file(&path).ok();
+
+        let xlsx = std::env::temp_dir().join(format!(
+            "rust_rule_miner_many_rows_{}.xlsx",
+            std::process::id()
+        ));
+        let mut workbook = UltraLowMemoryWorkbook::new(&xlsx).unwrap();
+        workbook.add_worksheet("Orders").unwrap();
+        workbook.write_row(&["id", "items", "ts"]).unwrap();
+        for i in 1..=20 {
+            let id = format!("tx{}", i);
+            workbook
+                .write_row(&[id.as_str(), "Laptop", "2024-01-15T10:30:00Z"])
+                .unwrap();
+        }
+        workbook.close().unwrap();
+        let options = LoaderOptions::default().with_skip_rows(15).with_limit(3);
+        let (from_excel, _) =
+            DataLoader::from_excel_with_options(&xlsx, 0, mapping.clone(), options.clone())
+                .unwrap();
+        assert_eq!(ids(&from_excel), ["tx16", "tx17", "tx18"]);
+        let streamed: Vec<String> =
+            DataLoader::stream_excel_with_options(&xlsx, 0, mapping, options)
+                .unwrap()
+                .map(|tx| tx.unwrap().id)
+                .collect();
+        assert_eq!(streamed, ["tx16", "tx17", "tx18"]);
+        fs::remove_file(&xlsx).ok();
+    }
+
+    #[test]
+    fn test_infer_mapping() {
+        let chosen = |headers: &[&str]| {
+            let (mapping, inference) = ColumnMapping::infer(headers).unwrap();
+            assert_eq!(mapping.transaction_id, inference.transaction_id.index);
+            (
+                mapping.transaction_id,
+                mapping.item_columns[0],
+                mapping.timestamp,
+                inference,
+            )
+        };
+
+        let (id, items, ts, inference) = chosen(&["transaction_id", "items", "timestamp"]);
+        assert_eq!((id, items, ts), (0, 1, 2));
+        assert_eq!(inference.items.confidence, 1.0);
+
+        // Online Retail (UCI): StockCode beats Description
+        let (id, items, ts, inference) = chosen(&[
+            "InvoiceNo",
+            "StockCode",
+            "Description",
+            "Quantity",
+            "InvoiceDate",
+            "UnitPrice",
+            "CustomerID",
+            "Country",
+        ]);
+        assert_eq!((id, items, ts), (0, 1, 4));
+        assert_eq!(inference.timestamp.header, "InvoiceDate");
+
+        // Order lines: order_id over the line's own id, created_at over updated_at
+        let (id, items, ts, _) =
+            chosen(&["id", "order_id", "sku", "qty", "created_at", "updated_at"]);
+        assert_eq!((id, items, ts), (1, 2, 4));
+
+        // Spaced names and a header holding a known name
+        let (id, items, ts, inference) = chosen(&["Order ID", "Products", "Purchase Timestamp"]);
+        assert_eq!((id, items, ts), (0, 1, 2));
+        assert!((inference.timestamp.confidence - 0.7).abs() < 1e-9);
+
+        // Misspellings
+        let (id, items, ts, inference) = chosen(&["txn_id", "prodcts", "timestmp"]);
+        assert_eq!((id, items, ts), (0, 1, 2));
+        assert!(inference.items.confidence < 1.0);
+
+        let error = ColumnMapping::infer(&["order_id", "product_id", "product_name", "created_at"])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("the items could be `product_id` (0.80) or `product_name` (0.80)"));
+        assert!(!error.contains("transaction id could"));
+
+        let error = ColumnMapping::infer(&["customer", "country", "amount", "date"])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("no column looks like the transaction id"));
+
+        let path =
+            std::env::temp_dir().join(format!("rust_rule_miner_auto_{}.csv", std::process::id()));
+        fs::write(
+            &path,
+            "Order Number,SKUs,Placed At\no1,\"Laptop,Mouse\",2024-01-15T10:30:00Z\n",
+        )
+        .unwrap();
+        let (mapping, _) = DataLoader::infer_mapping(&path).unwrap();
+        assert_eq!(mapping.item_columns, vec![1]);
+        assert_eq!(
+            tx_fields(DataLoader::from_csv_auto(&path)),
+            tx_fields(DataLoader::from_csv(&path, ColumnMapping::simple(0, 1, 2)))
+        );
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_standard_layout() {
+        use excelstream::fast_writer::UltraLowMemoryWorkbook;
+
+        let temp = |name: &str| {
+            std::env::temp_dir().join(format!("rust_rule_miner_{}_{}", std::process::id(), name))
+        };
+        let row = "tx1,\"Laptop,Mouse\",2024-01-15T10:30:00Z\n";
+        let (standard, renamed, swapped) = (
+            temp("standard.csv"),
+            temp("standard_renamed.csv"),
+            temp("standard_swapped.csv"),
+        );
+        fs::write(
+            &standard,
+            format!("transaction_id,items,timestamp\n{}", row),
+        )
+        .unwrap();
+        fs::write(&renamed, format!("Order ID,Products,Created At\n{}", row)).unwrap();
+        fs::write(&swapped, format!("order_id,qty,sku,created_at\n{}", row)).unwrap();
 
-        Ok(Some(Transaction::new(tx_id.to_string(), items, timestamp)))
-    }
+        let expected = tx_fields(DataLoader::from_csv(
+            &standard,
+            ColumnMapping::simple(0, 1, 2),
+        ));
+        assert_eq!(expected[0].1, ["Laptop", "Mouse"]);
+        assert_eq!(
+            tx_fields(DataLoader::from_csv_standard(&standard)),
+            expected
+        );
+        assert_eq!(tx_fields(DataLoader::from_csv_standard(&renamed)), expected);
+        let error = DataLoader::from_csv_standard(&swapped)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("`order_id`, `qty`, `sku`, `created_at`"));
+        assert!(error.contains("column 2 `qty` does not look like the items"));
+        assert!(error.contains("pass a ColumnMapping to from_csv"));
 
-    /// Parse timestamp from string (supports ISO 8601, Unix timestamp, and common datetime formats)
-    fn parse_timestamp(timestamp_str: &str, row_idx: usize) -> Result<DateTime<Utc>> {
-        let trimmed = timestamp_str.trim();
+        let xlsx = temp("standard.xlsx");
+        let mut workbook = UltraLowMemoryWorkbook::new(&xlsx).unwrap();
+        workbook.add_worksheet("Orders").unwrap();
+        workbook.write_row(&["id", "items", "date"]).unwrap();
+        workbook
+            .write_row(&["tx1", "Laptop,Mouse", "2024-01-15T10:30:00Z"])
+            .unwrap();
+        workbook.add_worksheet("Totals").unwrap();
+        workbook.write_row(&["region", "revenue"]).unwrap();
+        workbook.close().unwrap();
+        assert_eq!(
+            tx_fields(DataLoader::from_excel_standard(&xlsx, 0)),
+            expected
+        );
+        let error = DataLoader::from_excel_standard(&xlsx, 1)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("column 1 `region` does not look like the transaction id"));
+        assert!(error.contains("pass a ColumnMapping to from_excel"));
 
-        // Try parsing as ISO 8601 first (most common format)
-        if let Ok(dt) = DateTime::parse_from_rfc3339(trimmed) {
-            return Ok(dt.with_timezone(&Utc));
+        for path in [standard, renamed, swapped, xlsx] {
+            fs::remove_file(path).ok();
         }
+    }
+
+    #[test]
+    fn test_invalid_timestamp_policies() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/broken_orders.csv");
+        let load = |policy| {
+            let spec = TimestampSpec::default().with_invalid_policy(policy);
+            DataLoader::from_csv_with_report(
+                &path,
+                ColumnMapping::simple(0, 1, 2).with_timestamp_spec(spec),
+            )
+        };
 
-        // Try parsing as Unix timestamp (seconds)
-        if let Ok(unix_ts) = trimmed.parse::<i64>() {
-            if let Some(dt) = DateTime::from_timestamp(unix_ts, 0) {
-                return Ok(dt);
+        match load(InvalidTimestampPolicy::Error) {
+            Err(MiningError::InvalidTransaction(message)) => {
+                assert!(message.contains("row 4"), "{}", message)
             }
+            other => panic!("expected InvalidTransaction, got {:?}", other),
         }
 
-        // Try parsing as naive datetime formats
-        let formats = [
-            "%Y-%m-%d %H:%M:%S",
-            "%Y-%m-%d %H:%M:%S%.f",
-            "%Y/%m/%d %H:%M:%S",
-            "%d-%m-%Y %H:%M:%S",
-            "%d/%m/%Y %H:%M:%S",
-            "%Y-%m-%d",
-            "%Y/%m/%d",
-            "%d-%m-%Y",
-            "%d/%m/%Y",
+        let (transactions, report) = load(InvalidTimestampPolicy::SkipRow).unwrap();
+        assert!(transactions.iter().all(|tx| tx.id != "tx3"));
+        assert!(report.timestamp_fallbacks.is_empty());
+
+        let (transactions, report) = load(InvalidTimestampPolicy::UseEpoch).unwrap();
+        let tx3 = transactions.iter().find(|tx| tx.id == "tx3").unwrap();
+        assert_eq!(tx3.timestamp, DateTime::UNIX_EPOCH);
+        assert_eq!(report.timestamp_fallbacks, [4]);
+
+        let before = Utc::now();
+        let (transactions, report) = load(InvalidTimestampPolicy::UseNow).unwrap();
+        let tx3 = transactions.iter().find(|tx| tx.id == "tx3").unwrap();
+        assert!(tx3.timestamp >= before);
+        assert_eq!(report.timestamp_fallbacks, [4]);
+    }
+
+    #[test]
+    fn test_binned_price_column() {
+        let csv_content = "id,items,price,timestamp\n\
+tx1,\"Laptop,Mouse\",1200,2024-01-15T10:00:00Z\n\
+tx2,\"Laptop,Bag\",950.5,2024-01-15T11:00:00Z\n\
+tx3,\"Cable\",8,2024-01-15T12:00:00Z\n\
+tx4,\"Cable,Adapter\",15,2024-01-15T13:00:00Z\n\
+tx5,\"Mouse\",45,2024-01-15T14:00:00Z\n\
+tx6,\"Laptop\",1100,2024-01-15T15:00:00Z\n\
+tx7,\"Mouse\",n/a,2024-01-15T16:00:00Z\n\
+tx8,\"Cable\",,2024-01-15T17:00:00Z\n";
+        let temp_file = "/tmp/test_transactions_binned.csv";
+        fs::write(temp_file, csv_content).unwrap();
+
+        let levels = vec![
+            (20.0, "low".to_string()),
+            (100.0, "medium".to_string()),
+            (f64::INFINITY, "high".to_string()),
         ];
+        let mapping = ColumnMapping::simple(0, 1, 3)
+            .with_binned_column(BinnedColumn::thresholds(2, "price:", levels));
+        let (transactions, report) =
+            DataLoader::from_csv_with_report(temp_file, mapping.clone()).unwrap();
 
-        for format in &formats {
-            if let Ok(naive_dt) = NaiveDateTime::parse_from_str(trimmed, format) {
-                return Ok(DateTime::from_naive_utc_and_offset(naive_dt, Utc));
-            }
-        }
+        // tx7's price is not a number: skipped; tx8's is empty: no price item
+        assert_eq!(report.skipped_rows, 1);
+        let prices: Vec<Option<&str>> = transactions
+            .iter()
+            .map(|tx| {
+                tx.items
+                    .iter()
+                    .find(|i| i.starts_with("price:"))
+                    .map(String::as_str)
+            })
+            .collect();
+        assert_eq!(
+            prices,
+            [
+                Some("price:high"),
+                Some("price:high"),
+                Some("price:low"),
+                Some("price:low"),
+                Some("price:medium"),
+                Some("price:high"),
+                None,
+            ]
+        );
 
-        // Default to current time if parsing fails
-        log::warn!(
-            "Failed to parse timestamp '{}' at row {}, using current time",
-            trimmed,
-            row_idx
+        let config = crate::MiningConfig::builder()
+            .min_support(crate::Support::fraction(0.25).unwrap())
+            .min_confidence(crate::Confidence::fraction(0.9).unwrap())
+            .min_lift(crate::Lift::new(1.0).unwrap())
+            .build()
+            .unwrap();
+        let mut miner = crate::RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        let rules = miner.mine().unwrap().rules;
+        assert!(rules.iter().any(
+            |r| r.antecedent == ["Laptop"] && r.consequent == ["price:high"]
+                || r.antecedent == ["price:high"] && r.consequent == ["Laptop"]
+        ));
+
+        // Terciles of the six prices: {8, 15}, {45, 950.5}, {1100, 1200}
+        let tercile = ColumnMapping::simple(0, 1, 3)
+            .with_binned_column(BinnedColumn::quantiles(2, "price:", 3));
+        let transactions = DataLoader::from_csv(temp_file, tercile).unwrap();
+        let labels: Vec<&str> = transactions
+            .iter()
+            .filter_map(|tx| tx.items.iter().find(|i| i.starts_with("price:")))
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            labels,
+            ["price:q3", "price:q2", "price:q1", "price:q1", "price:q2", "price:q3"]
         );
-        Ok(Utc::now())
-    }
 
-    /// List all sheet names from an Excel file
-    pub fn list_sheets<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
-        let reader = StreamingReader::open(path.as_ref())
-            .map_err(|e| MiningError::DataLoadError(format!("Failed to open Excel file: {}", e)))?;
+        fs::remove_file(temp_file).ok();
+    }
 
-        Ok(reader.sheet_names().to_vec())
+    fn orders_mapping() -> JsonMapping {
+        JsonMapping::new("/order/id", "/order/lines", "/order/placed_at")
+            .with_item_fields(["/product/sku", "/category"])
+            .with_user_id("/customer/id")
+            .with_metadata("channel", "/order/channel")
     }
 
-    /// Load transactions from AWS S3 bucket (requires `cloud` feature)
-    ///
-    /// Streams directly from S3 with constant memory usage (~3-35 MB).
-    ///
-    /// # Arguments
-    /// * `bucket` - S3 bucket name
-    /// * `key` - S3 object key (file path in bucket)
-    /// * `region` - AWS region (e.g., "us-east-1")
-    /// * `sheet_index` - Sheet index (0-based) for Excel files
-    /// * `mapping` - Column mapping configuration
-    ///
-    /// # Example
-    /// ```no_run
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// use rust_rule_miner::data_loader::{DataLoader, ColumnMapping};
-    ///
-    /// // Standard format: transaction_id(0), items(1), timestamp(2)
-    /// let mapping = ColumnMapping::simple(0, 1, 2);
-    ///
-    /// // Load from S3
-    /// let transactions = DataLoader::from_s3(
-    ///     "my-data-bucket",
-    ///     "sales/2024/transactions.xlsx",
-    ///     "us-east-1",
-    ///     0,
-    ///     mapping
-    /// ).await?;
-    ///
-    /// println!("Loaded {} transactions from S3", transactions.len());
-    /// # Ok(())
-    /// # }
-    /// ```
-    #[cfg(feature = "cloud")]
-    pub async fn from_s3(
-        bucket: &str,
-        key: &str,
-        region: &str,
-        sheet_index: usize,
-        mapping: ColumnMapping,
-    ) -> Result<Vec<Transaction>> {
-        use excelstream::cloud::S3ExcelReader;
+    #[test]
+    fn test_jsonl_nested_fields() {
+        let jsonl = r#"{"order": {"id": "o1", "placed_at": "2024-01-15T10:30:00Z", "channel": "web", "lines": [{"product": {"sku": "Laptop"}, "category": "Electronics"}, {"product": {"sku": "Mouse"}, "category": "Accessories"}]}, "customer": {"id": 42}}
 
-        let mut reader = S3ExcelReader::builder()
-            .bucket(bucket)
-            .key(key)
-            .region(region)
-            .build()
-            .await
-            .map_err(|e| MiningError::DataLoadError(format!("Failed to open S3 file: {}", e)))?;
+{"order": {"id": "o2", "placed_at": 1705316400, "lines": [{"product": {"sku": "Phone"}}]}}
+{"order": {"id": "o3", "lines": [{"product": {"sku": "Tablet"}}]}}
+not json
+"#;
+        let temp_file = "/tmp/test_transactions_orders.jsonl";
+        fs::write(temp_file, jsonl).unwrap();
 
-        let mut transactions = Vec::new();
-        let mut row_idx = 0;
+        let transactions = DataLoader::from_jsonl(temp_file, orders_mapping()).unwrap();
+        fs::remove_file(temp_file).ok();
 
-        for row_result in reader.rows_by_index(sheet_index).map_err(|e| {
-            MiningError::DataLoadError(format!("Failed to read sheet {}: {}", sheet_index, e))
-        })? {
-            let row = row_result.map_err(|e| {
-                MiningError::DataLoadError(format!("Failed to read row {}: {}", row_idx, e))
-            })?;
+        // o3 has no timestamp and the last line is malformed: both skipped
+        assert_eq!(transactions.len(), 2);
+        let o1 = &transactions[0];
+        assert_eq!(o1.id, "o1");
+        assert_eq!(o1.items, vec!["Laptop::Electronics", "Mouse::Accessories"]);
+        assert_eq!(o1.user_id.as_deref(), Some("42"));
+        assert_eq!(o1.metadata["channel"], "web");
+        assert_eq!(o1.timestamp.to_rfc3339(), "2024-01-15T10:30:00+00:00");
 
-            row_idx += 1;
+        let o2 = &transactions[1];
+        assert_eq!(o2.items, vec!["Phone"]);
+        assert_eq!(o2.timestamp.timestamp(), 1705316400);
+        assert_eq!(o2.user_id, None);
+        assert!(o2.metadata.is_empty());
+    }
 
-            // Skip header row
-            if row_idx == 1 {
-                continue;
-            }
+    #[test]
+    fn test_json_array_of_plain_items() {
+        let json = r#"[
+            {"id": 1, "items": ["Laptop", "Mouse"], "ts": "2024-01-15 10:30:00"},
+            {"id": 2, "items": "Phone", "ts": "2024-01-15"},
+            {"id": 3, "items": [], "ts": "2024-01-15"}
+        ]"#;
+        let temp_file = "/tmp/test_transactions_array.json";
+        fs::write(temp_file, json).unwrap();
 
-            // Convert row to Vec<String>
-            let row_values = row.to_strings();
+        let transactions =
+            DataLoader::from_json(temp_file, JsonMapping::new("/id", "/items", "/ts")).unwrap();
+        fs::remove_file(temp_file).ok();
 
-            match Self::parse_transaction_with_mapping(&row_values, row_idx, &mapping) {
-                Ok(Some(tx)) => transactions.push(tx),
-                Ok(None) => continue,
-                Err(e) => {
-                    log::warn!("Skipping row {}: {}", row_idx, e);
-                    continue;
-                }
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].id, "1");
+        assert_eq!(transactions[0].items, vec!["Laptop", "Mouse"]);
+        assert_eq!(transactions[1].items, vec!["Phone"]);
+    }
+
+    fn spmf_fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/spmf")
+            .join(name)
+    }
+
+    /// Frequent itemsets per size (index 0 = 1-itemsets) with each algorithm
+    fn itemset_counts(name: &str, min_support: f64) -> Vec<Vec<usize>> {
+        let transactions = DataLoader::from_spmf(spmf_fixture(name)).unwrap();
+        [
+            crate::MiningAlgorithm::Apriori,
+            crate::MiningAlgorithm::FPGrowth,
+        ]
+        .into_iter()
+        .map(|algorithm| {
+            let config = crate::MiningConfig::builder()
+                .min_support(crate::Support::fraction(min_support).unwrap())
+                .algorithm(algorithm)
+                .build()
+                .unwrap();
+            let mut miner = crate::RuleMiner::new(config);
+            miner.add_transactions(transactions.clone()).unwrap();
+            let mut counts = Vec::new();
+            for itemset in miner.mine_frequent_itemsets().unwrap() {
+                let size = itemset.items.len();
+                counts.resize(counts.len().max(size), 0);
+                counts[size - 1] += 1;
             }
-        }
+            counts
+        })
+        .collect()
+    }
 
-        if transactions.is_empty() {
-            return Err(MiningError::InsufficientData(
-                "No valid transactions found in S3 file".to_string(),
-            ));
+    #[test]
+    fn test_spmf_loading() {
+        let transactions = DataLoader::from_spmf(spmf_fixture("pasquier99.dat")).unwrap();
+        assert_eq!(transactions.len(), 5);
+        assert_eq!(transactions[2].id, "3");
+        assert_eq!(transactions[2].items, vec!["1", "2", "3", "5"]);
+        assert!(transactions[0].timestamp < transactions[1].timestamp);
+
+        let temp_file = "/tmp/test_transactions.spmf";
+        fs::write(temp_file, "@CONVERTED_FROM_TEXT\n1 2\n\n3 x\n4\n").unwrap();
+        let transactions = DataLoader::from_spmf(temp_file).unwrap();
+        fs::remove_file(temp_file).ok();
+        let ids: Vec<&str> = transactions.iter().map(|tx| tx.id.as_str()).collect();
+        assert_eq!(ids, ["2", "5"]);
+    }
+
+    #[test]
+    fn test_spmf_fixture_itemset_counts() {
+        // Counts from tests/fixtures/spmf/README.md, found by brute force
+        for counts in itemset_counts("pasquier99.dat", 0.4) {
+            assert_eq!(counts, [4, 6, 4, 1]);
         }
+        for counts in itemset_counts("synthetic_300.dat", 0.05) {
+            assert_eq!(counts, [47, 35, 24, 3]);
+        }
+        for counts in itemset_counts("synthetic_300.dat", 0.1) {
+            assert_eq!(counts, [12, 13, 6, 1]);
+        }
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_round_trip() {
+        use arrow_array::builder::{ListBuilder, StringBuilder};
+        use arrow_array::{
+            ArrayRef, Int64Array, RecordBatch, StringArray, TimestampMillisecondArray,
+        };
+        use std::sync::Arc;
+
+        let mut skus = ListBuilder::new(StringBuilder::new());
+        skus.append_value([Some("Laptop"), Some("Cable, USB-C")]);
+        skus.append_value([Some("Mouse")]);
+        skus.append_null();
+        let millis = [1_705_314_600_123, 1_705_318_200_000, 1_705_321_800_000];
+        let batch = RecordBatch::try_from_iter([
+            (
+                "order_id",
+                Arc::new(StringArray::from(vec!["o1", "o2", "o3"])) as ArrayRef,
+            ),
+            ("skus", Arc::new(skus.finish()) as ArrayRef),
+            (
+                "placed_at",
+                Arc::new(TimestampMillisecondArray::from(millis.to_vec()).with_timezone("UTC")),
+            ),
+            (
+                "placed_unix",
+                Arc::new(Int64Array::from(vec![1_705_314_600, 1_705_318_200, 0])),
+            ),
+            (
+                "placed_text",
+                Arc::new(StringArray::from(vec![
+                    "2024-01-15T10:30:00Z",
+                    "2024-01-15 11:30:00",
+                    "",
+                ])),
+            ),
+        ])
+        .unwrap();
+
+        let path = std::env::temp_dir().join(format!(
+            "rust_rule_miner_orders_{}.parquet",
+            std::process::id()
+        ));
+        let mut writer = parquet::arrow::ArrowWriter::try_new(
+            fs::File::create(&path).unwrap(),
+            batch.schema(),
+            None,
+        )
+        .unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
 
-        Ok(transactions)
+        let columns = DataLoader::parquet_columns(&path).unwrap();
+        let load = |timestamp: &str| {
+            let mapping =
+                ColumnMapping::from_names(&columns, "order_id", &["skus"], timestamp).unwrap();
+            DataLoader::from_parquet(&path, mapping).unwrap()
+        };
+        let native = load("placed_at");
+        let unix = load("placed_unix");
+        let text = load("placed_text");
+        fs::remove_file(&path).ok();
+
+        // o3 has no items; list items keep their commas
+        assert_eq!(native.len(), 2);
+        assert_eq!(native[0].items, vec!["Laptop", "Cable, USB-C"]);
+        assert_eq!(native[1].items, vec!["Mouse"]);
+        assert_eq!(native[0].timestamp.timestamp_millis(), millis[0]);
+        assert_eq!(unix[0].timestamp.timestamp(), 1_705_314_600);
+        assert_eq!(text[1].timestamp, native[1].timestamp);
+        assert!(matches!(
+            ColumnMapping::from_names(&columns, "order", &["skus"], "placed_at"),
+            Err(MiningError::DataLoadError(_))
+        ));
     }
 
-    /// Load transactions from HTTP URL (requires `cloud` feature)
-    ///
-    /// Streams CSV data from HTTP endpoint with constant memory usage.
-    ///
-    /// # Arguments
-    /// * `url` - HTTP URL to CSV file
-    /// * `mapping` - Column mapping configuration
-    ///
-    /// # Example
-    /// ```no_run
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// use rust_rule_miner::data_loader::{DataLoader, ColumnMapping};
-    ///
-    /// // Standard format: transaction_id(0), items(1), timestamp(2)
-    /// let mapping = ColumnMapping::simple(0, 1, 2);
-    ///
-    /// // Load from HTTP endpoint
-    /// let transactions = DataLoader::from_http(
-    ///     "https://example.com/data/transactions.csv",
-    ///     mapping
-    /// ).await?;
-    ///
-    /// println!("Loaded {} transactions from HTTP", transactions.len());
-    /// # Ok(())
-    /// # }
-    /// ```
-    #[cfg(feature = "cloud")]
-    pub async fn from_http(url: &str, mapping: ColumnMapping) -> Result<Vec<Transaction>> {
-        // Download to temp file first, then use CsvReader
-        // (excelstream doesn't have direct HTTP CSV reader yet)
-        let response = reqwest::get(url)
+    /// Runs against the database at `RULE_MINER_TEST_DATABASE_URL`, if set
+    #[cfg(feature = "postgres")]
+    #[tokio::test]
+    async fn test_from_postgres() {
+        let Ok(url) = std::env::var("RULE_MINER_TEST_DATABASE_URL") else {
+            eprintln!("RULE_MINER_TEST_DATABASE_URL not set; skipping");
+            return;
+        };
+        let (mut client, connection) = tokio_postgres::connect(&url, tokio_postgres::NoTls)
             .await
-            .map_err(|e| MiningError::DataLoadError(format!("HTTP request failed: {}", e)))?;
-
-        let content = response
-            .text()
+            .unwrap();
+        tokio::spawn(connection);
+        client
+            .batch_execute(
+                "CREATE TEMP TABLE orders (id int, skus text[], sku_list text, \
+                     placed_at timestamptz, customer text, payload json);
+                 INSERT INTO orders VALUES
+                     (1, '{Laptop,Mouse}', 'Laptop;Mouse', '2024-01-15 10:30:00+00', 'c42', '{}'),
+                     (2, '{Phone}', 'Phone', '2024-01-15 11:00:00+00', NULL, '{}'),
+                     (3, '{Tablet}', 'Tablet', NULL, 'c7', '{}');",
+            )
             .await
-            .map_err(|e| MiningError::DataLoadError(format!("Failed to read response: {}", e)))?;
+            .unwrap();
+        let query = "SELECT * FROM orders ORDER BY id";
 
-        // Parse CSV from string
-        let mut transactions = Vec::new();
-        let mut row_idx = 0;
-
-        for line in content.lines() {
-            row_idx += 1;
+        // text[] items; the row without a timestamp is skipped
+        let mapping = PgMapping::new("id", "skus", "placed_at").with_user_id("customer");
+        let transactions = DataLoader::from_postgres(&mut client, query, mapping)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = transactions.iter().map(|tx| tx.id.as_str()).collect();
+        assert_eq!(ids, ["1", "2"]);
+        assert_eq!(transactions[0].items, ["Laptop", "Mouse"]);
+        assert_eq!(transactions[0].user_id.as_deref(), Some("c42"));
+        assert_eq!(transactions[1].user_id, None);
+        assert_eq!(transactions[0].timestamp.timestamp(), 1_705_314_600);
 
-            // Skip header
-            if row_idx == 1 {
-                continue;
-            }
+        // Delimited text items
+        let mapping = PgMapping::new("id", "sku_list", "placed_at").with_item_separator(";");
+        let transactions = DataLoader::from_postgres(&mut client, query, mapping)
+            .await
+            .unwrap();
+        assert_eq!(transactions[0].items, ["Laptop", "Mouse"]);
 
-            // Parse CSV row
-            let row_values: Vec<String> = line.split(',').map(|s| s.trim().to_string()).collect();
+        // Several cursor batches
+        let query = "SELECT g AS id, ARRAY['Tea'] AS skus, now() AS placed_at \
+                     FROM generate_series(1, 2500) g";
+        let transactions = DataLoader::from_postgres(
+            &mut client,
+            query,
+            PgMapping::new("id", "skus", "placed_at"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(transactions.len(), 2500);
+        assert_eq!(transactions[2499].id, "2500");
 
-            match Self::parse_transaction_with_mapping(&row_values, row_idx, &mapping) {
-                Ok(Some(tx)) => transactions.push(tx),
-                Ok(None) => continue,
-                Err(e) => {
-                    log::warn!("Skipping row {}: {}", row_idx, e);
-                    continue;
+        let query = "SELECT * FROM orders ORDER BY id";
+        for (mapping, column) in [
+            (PgMapping::new("id", "basket", "placed_at"), "`basket`"),
+            (PgMapping::new("id", "payload", "placed_at"), "`payload`"),
+        ] {
+            match DataLoader::from_postgres(&mut client, query, mapping).await {
+                Err(MiningError::DataLoadError(message)) => {
+                    assert!(message.contains(column), "{}", message)
                 }
+                other => panic!("expected DataLoadError, got {:?}", other),
             }
         }
+    }
 
-        if transactions.is_empty() {
-            return Err(MiningError::InsufficientData(
-                "No valid transactions found in HTTP response".to_string(),
-            ));
+    #[test]
+    fn test_timestamp_parsing() {
+        let mut report = LoadReport::default();
+        let auto = TimestampSpec::default();
+        let mut parse = |value: &str, spec: &TimestampSpec| {
+            DataLoader::parse_timestamp(value, spec, 1, &mut report)
+                .unwrap()
+                .to_rfc3339()
+        };
+
+        // ISO 8601
+        assert_eq!(
+            parse("2024-01-15T10:30:00Z", &auto),
+            "2024-01-15T10:30:00+00:00"
+        );
+        // Unix timestamp
+        assert_eq!(parse("1705314600", &auto), "2024-01-15T10:30:00+00:00");
+        // Naive datetimes and dates, day first
+        assert_eq!(
+            parse("2024-01-15 10:30:00", &auto),
+            "2024-01-15T10:30:00+00:00"
+        );
+        assert_eq!(
+            parse("2024/01/15 10:30:00", &auto),
+            "2024-01-15T10:30:00+00:00"
+        );
+        assert_eq!(parse("15-01-2024", &auto), "2024-01-15T00:00:00+00:00");
+        assert_eq!(parse("01/02/2024", &auto), "2024-02-01T00:00:00+00:00");
+
+        let month_first = TimestampSpec::default().with_date_order(DateOrder::MonthFirst);
+        assert_eq!(
+            parse("01/02/2024", &month_first),
+            "2024-01-02T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse("01/15/2024 10:30:00", &month_first),
+            "2024-01-15T10:30:00+00:00"
+        );
+
+        // Naive values in India Standard Time; explicit offsets win
+        let ist = TimestampSpec::format("%d.%m.%Y %H:%M")
+            .with_offset(FixedOffset::east_opt(5 * 3600 + 1800).unwrap());
+        assert_eq!(parse("15.01.2024 16:00", &ist), "2024-01-15T10:30:00+00:00");
+        let with_zone = TimestampSpec::format("%d.%m.%Y %H:%M %z")
+            .with_offset(FixedOffset::east_opt(5 * 3600 + 1800).unwrap());
+        assert_eq!(
+            parse("15.01.2024 11:30 +0100", &with_zone),
+            "2024-01-15T10:30:00+00:00"
+        );
+        let eastern =
+            TimestampSpec::default().with_offset(FixedOffset::west_opt(5 * 3600).unwrap());
+        assert_eq!(
+            parse("2024-01-15 05:30:00", &eastern),
+            "2024-01-15T10:30:00+00:00"
+        );
+
+        assert_eq!(
+            parse("1705314600000", &TimestampSpec::unix_millis()),
+            "2024-01-15T10:30:00+00:00"
+        );
+        assert_eq!(
+            parse("1705314600", &TimestampSpec::unix_seconds()),
+            "2024-01-15T10:30:00+00:00"
+        );
+        assert!(report.timestamp_fallbacks.is_empty());
+
+        // Unparseable values, per policy
+        let policy = |policy| TimestampSpec::default().with_invalid_policy(policy);
+        assert!(matches!(
+            DataLoader::parse_timestamp(
+                "next tuesday",
+                &policy(InvalidTimestampPolicy::Error),
+                7,
+                &mut report
+            ),
+            Err(MiningError::InvalidTransaction(_))
+        ));
+        assert!(matches!(
+            DataLoader::parse_timestamp("next tuesday", &auto, 7, &mut report),
+            Err(MiningError::DataLoadError(_))
+        ));
+        // A date in the wrong order does not parse either
+        assert!(DataLoader::parse_timestamp("01/15/2024", &auto, 7, &mut report).is_err());
+        assert!(report.timestamp_fallbacks.is_empty());
+
+        let epoch = DataLoader::parse_timestamp(
+            "next tuesday",
+            &policy(InvalidTimestampPolicy::UseEpoch),
+            7,
+            &mut report,
+        )
+        .unwrap();
+        assert_eq!(epoch, DateTime::UNIX_EPOCH);
+        DataLoader::parse_timestamp(
+            "next tuesday",
+            &policy(InvalidTimestampPolicy::UseNow),
+            8,
+            &mut report,
+        )
+        .unwrap();
+        assert_eq!(report.timestamp_fallbacks, [7, 8]);
+    }
+
+    /// Three orders laid out as transaction_id, items, timestamp
+    #[cfg(feature = "cloud")]
+    const ORDERS: &str = "transaction_id,items,timestamp\r\n\
+        tx1,\"Laptop,Mouse\",2024-01-15T10:30:00Z\r\n\
+        tx2,\"Phone,Phone Case\",2024-01-15T11:00:00Z\r\n\
+        tx3,Tablet,2024-01-15T12:00:00Z";
+
+    /// `ORDERS` as a gzip file
+    #[cfg(feature = "cloud")]
+    fn gzipped_orders() -> Vec<u8> {
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(ORDERS.as_bytes()).unwrap();
+        gzip.finish().unwrap()
+    }
+
+    /// `ORDERS` as a one-sheet workbook
+    #[cfg(feature = "cloud")]
+    fn orders_workbook() -> Vec<u8> {
+        use excelstream::fast_writer::UltraLowMemoryWorkbook;
+
+        let xlsx = temp_workbook_path("orders.xlsx");
+        let mut workbook = UltraLowMemoryWorkbook::new(&xlsx).unwrap();
+        workbook.add_worksheet("Orders").unwrap();
+        for line in ORDERS.lines() {
+            let cells = CsvParser::new(b',', b'"').parse_line(line);
+            let cells: Vec<&str> = cells.iter().map(String::as_str).collect();
+            workbook.write_row(&cells).unwrap();
         }
+        workbook.close().unwrap();
+        let bytes = fs::read(&xlsx).unwrap();
+        fs::remove_file(&xlsx).ok();
+        bytes
+    }
+
+    /// Serves `ORDERS` at `/orders.csv` and gzip-encoded at `/orders.csv.gz`
+    /// in small chunks to requests with bearer token `s3cr3t` or basic auth
+    /// `analyst:pw`; `/flaky` fails its first request with 503 and `/slow`
+    /// stalls
+    #[cfg(feature = "cloud")]
+    fn serve_orders() -> String {
+        use std::io::{BufRead, BufReader};
+        use std::net::TcpListener;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
 
-        Ok(transactions)
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let flaky_failed = Arc::new(AtomicBool::new(false));
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let flaky_failed = flaky_failed.clone();
+                std::thread::spawn(move || {
+                    let mut request = BufReader::new(stream.try_clone().unwrap()).lines();
+                    let path = request.next().unwrap().unwrap();
+                    let path = path.split(' ').nth(1).unwrap().to_string();
+                    let headers: Vec<String> = request
+                        .map_while(std::io::Result::ok)
+                        .take_while(|line| !line.is_empty())
+                        .collect();
+                    let authorized = headers.iter().any(|header| {
+                        header.eq_ignore_ascii_case("authorization: Bearer s3cr3t")
+                            || header.eq_ignore_ascii_case("authorization: Basic YW5hbHlzdDpwdw==")
+                    });
+
+                    let (status, encoding, body) = match path.as_str() {
+                        _ if !authorized => ("401 Unauthorized", "identity", Vec::new()),
+                        "/flaky" if !flaky_failed.swap(true, Ordering::SeqCst) => {
+                            ("503 Service Unavailable", "identity", Vec::new())
+                        }
+                        "/orders.csv" | "/flaky" => ("200 OK", "identity", ORDERS.into()),
+                        "/orders.csv.gz" => ("200 OK", "gzip", gzipped_orders()),
+                        "/slow" => {
+                            std::thread::sleep(std::time::Duration::from_secs(2));
+                            ("200 OK", "identity", ORDERS.into())
+                        }
+                        _ => ("404 Not Found", "identity", Vec::new()),
+                    };
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 {}\r\nContent-Encoding: {}\r\n\
+                         Transfer-Encoding: chunked\r\nConnection: close\r\n\r\n",
+                        status, encoding
+                    );
+                    // Chunks split rows and quoted cells
+                    for chunk in body.chunks(7) {
+                        let _ = write!(stream, "{:x}\r\n", chunk.len());
+                        let _ = stream.write_all(chunk);
+                        let _ = stream.write_all(b"\r\n");
+                        let _ = stream.flush();
+                    }
+                    let _ = stream.write_all(b"0\r\n\r\n");
+                });
+            }
+        });
+        format!("http://{}", address)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use std::io::Write;
+    #[cfg(feature = "cloud")]
+    #[tokio::test]
+    async fn test_from_http() {
+        let server = serve_orders();
+        let mapping = || ColumnMapping::simple(0, 1, 2);
+        let load = |path: &str, options: HttpOptions| {
+            let url = format!("{}{}", server, path);
+            async move { DataLoader::from_http_with_options(&url, mapping(), options).await }
+        };
+        let bearer = || HttpOptions::default().with_bearer_token("s3cr3t");
 
-    #[test]
-    fn test_csv_loading() {
-        // Create temporary CSV file
-        let csv_content = r#"transaction_id,items,timestamp
-tx1,"Laptop,Mouse",2024-01-15T10:30:00Z
-tx2,"Phone,Phone Case",2024-01-15T11:00:00Z
-tx3,"Tablet",2024-01-15T12:00:00Z
-"#;
+        let expected = vec![
+            (
+                "tx1".to_string(),
+                vec!["Laptop".to_string(), "Mouse".to_string()],
+                "2024-01-15T10:30:00Z".parse().unwrap(),
+            ),
+            (
+                "tx2".to_string(),
+                vec!["Phone".to_string(), "Phone Case".to_string()],
+                "2024-01-15T11:00:00Z".parse().unwrap(),
+            ),
+            (
+                "tx3".to_string(),
+                vec!["Tablet".to_string()],
+                "2024-01-15T12:00:00Z".parse().unwrap(),
+            ),
+        ];
+        assert_eq!(tx_fields(load("/orders.csv", bearer()).await), expected);
 
-        let temp_file = "/tmp/test_transactions_excelstream.csv";
-        let mut file = fs::File::create(temp_file).unwrap();
-        file.write_all(csv_content.as_bytes()).unwrap();
+        let basic = HttpOptions::default().with_basic_auth("analyst", Some("pw"));
+        assert_eq!(tx_fields(load("/orders.csv.gz", basic).await), expected);
 
-        // Load transactions with column mapping
-        let mapping = ColumnMapping::simple(0, 1, 2);
-        let transactions = DataLoader::from_csv(temp_file, mapping).unwrap();
+        let error = load("/orders.csv", HttpOptions::default())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("401 Unauthorized"), "{}", error);
 
-        assert_eq!(transactions.len(), 3);
-        assert_eq!(transactions[0].id, "tx1");
-        assert_eq!(transactions[0].items, vec!["Laptop", "Mouse"]);
-        assert_eq!(transactions[1].items, vec!["Phone", "Phone Case"]);
-        assert_eq!(transactions[2].items, vec!["Tablet"]);
+        let error = DataLoader::from_http(&format!("{}/orders.csv", server), mapping())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("401"), "{}", error);
 
-        // Cleanup
-        fs::remove_file(temp_file).ok();
+        // The first request fails with 503, the retry succeeds
+        let flaky = load("/flaky", bearer().with_retries(1)).await.unwrap();
+        assert_eq!(flaky.len(), 3);
+
+        let slow = bearer().with_timeout(std::time::Duration::from_millis(100));
+        assert!(load("/slow", slow).await.is_err());
     }
 
+    #[cfg(feature = "cloud")]
     #[test]
-    fn test_timestamp_parsing() {
-        // ISO 8601
-        let ts1 = DataLoader::parse_timestamp("2024-01-15T10:30:00Z", 1).unwrap();
-        assert_eq!(ts1.to_rfc3339(), "2024-01-15T10:30:00+00:00");
+    fn test_object_format() {
+        assert_eq!(object_format("orders.csv").unwrap(), ObjectFormat::Csv);
+        assert_eq!(
+            object_format("exports/2024/Orders.CSV.GZ").unwrap(),
+            ObjectFormat::GzippedCsv
+        );
+        assert_eq!(object_format("sales/q1.xlsx").unwrap(), ObjectFormat::Xlsx);
+        for name in ["orders.parquet", "orders.csv.zst", "orders", "csv"] {
+            let error = object_format(name).unwrap_err();
+            assert!(error.to_string().contains(name), "{}", error);
+        }
+    }
 
-        // Unix timestamp
-        let ts2 = DataLoader::parse_timestamp("1705316400", 1).unwrap();
-        assert!(ts2.timestamp() > 0);
+    #[cfg(feature = "cloud")]
+    #[tokio::test]
+    async fn test_cloud_object_dispatch() {
+        use object_store::memory::InMemory;
+        use object_store::path::Path as ObjectPath;
+        use object_store::ObjectStore;
+
+        let store = InMemory::new();
+        for (name, body) in [
+            ("orders.csv", ORDERS.as_bytes().to_vec()),
+            ("exports/orders.csv.gz", gzipped_orders()),
+            ("orders.xlsx", orders_workbook()),
+        ] {
+            store
+                .put(&ObjectPath::from(name), body.into())
+                .await
+                .unwrap();
+        }
+        let load = |name: &'static str| {
+            let store = &store;
+            async move {
+                let format = object_format(name)?;
+                let mapping = ColumnMapping::simple(0, 1, 2);
+                DataLoader::from_cloud_object(store, name, format, mapping).await
+            }
+        };
+
+        let csv = tx_fields(load("orders.csv").await);
+        assert_eq!(csv.len(), 3);
+        assert_eq!(csv[0].1, ["Laptop", "Mouse"]);
+        assert_eq!(tx_fields(load("exports/orders.csv.gz").await), csv);
+        assert_eq!(tx_fields(load("orders.xlsx").await), csv);
 
-        // Naive datetime
-        let ts3 = DataLoader::parse_timestamp("2024-01-15 10:30:00", 1).unwrap();
-        assert_eq!(ts3.format("%Y-%m-%d").to_string(), "2024-01-15");
+        let error = load("missing.csv").await.unwrap_err();
+        assert!(error.to_string().contains("missing.csv"), "{}", error);
+    }
 
-        // Alternative formats
-        let ts4 = DataLoader::parse_timestamp("2024/01/15 10:30:00", 1).unwrap();
-        assert_eq!(ts4.format("%Y-%m-%d").to_string(), "2024-01-15");
+    /// Loads the `bucket/object` in `RULE_MINER_TEST_GCS_OBJECT`, if set,
+    /// laid out as transaction_id, items, timestamp
+    #[cfg(feature = "cloud")]
+    #[tokio::test]
+    async fn test_from_gcs() {
+        let Ok(path) = std::env::var("RULE_MINER_TEST_GCS_OBJECT") else {
+            eprintln!("RULE_MINER_TEST_GCS_OBJECT not set; skipping");
+            return;
+        };
+        let (bucket, object) = path.split_once('/').unwrap();
+        let transactions = DataLoader::from_gcs(bucket, object, ColumnMapping::simple(0, 1, 2))
+            .await
+            .unwrap();
+        assert!(!transactions.is_empty());
+    }
 
-        let _ts5 = DataLoader::parse_timestamp("15-01-2024", 1).unwrap();
-        // Date parsing may default to current time if format not recognized
+    /// Loads the `account/container/blob` in `RULE_MINER_TEST_AZURE_BLOB`, if
+    /// set, laid out as transaction_id, items, timestamp
+    #[cfg(feature = "cloud")]
+    #[tokio::test]
+    async fn test_from_azure_blob() {
+        let Ok(path) = std::env::var("RULE_MINER_TEST_AZURE_BLOB") else {
+            eprintln!("RULE_MINER_TEST_AZURE_BLOB not set; skipping");
+            return;
+        };
+        let mut parts = path.splitn(3, '/');
+        let (account, container, blob) = (
+            parts.next().unwrap(),
+            parts.next().unwrap(),
+            parts.next().unwrap(),
+        );
+        let transactions =
+            DataLoader::from_azure_blob(account, container, blob, ColumnMapping::simple(0, 1, 2))
+                .await
+                .unwrap();
+        assert!(!transactions.is_empty());
     }
 }
@@ -0,0 +1,429 @@
+//! Dry-run planning for rule deployments
+//!
+//! `plan()` compares the rules currently deployed with a freshly mined set and
+//! produces a `DeploymentPlan`: what gets added, removed and changed, which
+//! rules cross a confidence threshold, and a hash of the new rule set. The
+//! hash is what a reviewer approves; `MiningRuleEngine::replace_rules_approved`
+//! refuses to deploy a rule set whose hash differs from the approved one.
+//!
+//! # Example
+//! ```
+//! use rust_rule_miner::deployment::{plan, plan_hash};
+//! # let current = Vec::new();
+//! # let new_rules = Vec::new();
+//!
+//! let plan = plan(&current, &new_rules);
+//! println!("{}", plan.render(false));
+//! assert_eq!(plan.hash, plan_hash(&new_rules));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{AssociationRule, ItemSet, PatternMetrics};
+
+/// Identity of a rule, independent of item order and metrics
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct RuleKey {
+    pub antecedent: ItemSet,
+    pub consequent: ItemSet,
+}
+
+impl RuleKey {
+    /// Key of a rule (items sorted)
+    pub fn of(rule: &AssociationRule) -> Self {
+        let mut antecedent = rule.antecedent.clone();
+        let mut consequent = rule.consequent.clone();
+        antecedent.sort();
+        consequent.sort();
+        Self {
+            antecedent,
+            consequent,
+        }
+    }
+}
+
+impl fmt::Display for RuleKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} => {}",
+            self.antecedent.join(", "),
+            self.consequent.join(", ")
+        )
+    }
+}
+
+/// A rule present in both sets whose metrics changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleChange {
+    pub key: RuleKey,
+    pub before: PatternMetrics,
+    pub after: PatternMetrics,
+}
+
+impl RuleChange {
+    pub fn confidence_delta(&self) -> f64 {
+        self.after.confidence - self.before.confidence
+    }
+
+    pub fn support_delta(&self) -> f64 {
+        self.after.support - self.before.support
+    }
+
+    pub fn lift_delta(&self) -> f64 {
+        self.after.lift - self.before.lift
+    }
+}
+
+/// Rule-level difference between two rule sets
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleDiff {
+    /// Rules only in the new set
+    pub added: Vec<AssociationRule>,
+    /// Rules only in the current set
+    pub removed: Vec<AssociationRule>,
+    /// Rules in both sets with different metrics
+    pub changed: Vec<RuleChange>,
+    /// Rules in both sets with identical metrics
+    pub unchanged: usize,
+}
+
+impl RuleDiff {
+    /// Compare `current` with `new`; rules are matched by `RuleKey`
+    pub fn between(current: &[AssociationRule], new: &[AssociationRule]) -> Self {
+        let current_by_key: HashMap<RuleKey, &AssociationRule> =
+            current.iter().map(|r| (RuleKey::of(r), r)).collect();
+        let new_by_key: HashMap<RuleKey, &AssociationRule> =
+            new.iter().map(|r| (RuleKey::of(r), r)).collect();
+
+        let mut diff = RuleDiff::default();
+
+        for rule in new {
+            let key = RuleKey::of(rule);
+            match current_by_key.get(&key) {
+                None => diff.added.push(rule.clone()),
+                Some(old) if same_metrics(&old.metrics, &rule.metrics) => diff.unchanged += 1,
+                Some(old) => diff.changed.push(RuleChange {
+                    key,
+                    before: old.metrics.clone(),
+                    after: rule.metrics.clone(),
+                }),
+            }
+        }
+
+        diff.removed = current
+            .iter()
+            .filter(|r| !new_by_key.contains_key(&RuleKey::of(r)))
+            .cloned()
+            .collect();
+
+        // Largest confidence movements first
+        diff.changed.sort_by(|a, b| {
+            b.confidence_delta()
+                .abs()
+                .partial_cmp(&a.confidence_delta().abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.key.cmp(&b.key))
+        });
+
+        diff
+    }
+
+    /// Check if the sets are identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn same_metrics(a: &PatternMetrics, b: &PatternMetrics) -> bool {
+    a.confidence == b.confidence && a.support == b.support && a.lift == b.lift
+}
+
+/// Direction a rule moved relative to the confidence threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Crossing {
+    /// Was below the threshold (or absent), now at or above it
+    Above,
+    /// Was at or above the threshold, now below it (or removed)
+    Below,
+}
+
+/// A rule whose confidence crossed the plan threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdCrossing {
+    pub key: RuleKey,
+    pub direction: Crossing,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+}
+
+/// Options for `plan_with_options`
+#[derive(Debug, Clone)]
+pub struct PlanOptions {
+    /// Confidence level whose crossings are reported
+    pub confidence_threshold: f64,
+    /// Changed rules listed in the rendered summary
+    pub top_changes: usize,
+}
+
+impl Default for PlanOptions {
+    fn default() -> Self {
+        Self {
+            confidence_threshold: 0.7,
+            top_changes: 10,
+        }
+    }
+}
+
+/// Machine-readable deployment plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentPlan {
+    pub diff: RuleDiff,
+    pub confidence_threshold: f64,
+    pub crossings: Vec<ThresholdCrossing>,
+    pub top_changes: usize,
+    /// Hash of the new rule set; approve this to allow deployment
+    pub hash: String,
+}
+
+/// Plan a deployment with default options
+pub fn plan(current: &[AssociationRule], new: &[AssociationRule]) -> DeploymentPlan {
+    plan_with_options(current, new, &PlanOptions::default())
+}
+
+/// Plan a deployment
+pub fn plan_with_options(
+    current: &[AssociationRule],
+    new: &[AssociationRule],
+    options: &PlanOptions,
+) -> DeploymentPlan {
+    let diff = RuleDiff::between(current, new);
+    let threshold = options.confidence_threshold;
+
+    let mut crossings = Vec::new();
+    let mut check = |key: RuleKey, before: Option<f64>, after: Option<f64>| {
+        let was_above = before.is_some_and(|c| c >= threshold);
+        let is_above = after.is_some_and(|c| c >= threshold);
+        if was_above != is_above {
+            crossings.push(ThresholdCrossing {
+                key,
+                direction: if is_above {
+                    Crossing::Above
+                } else {
+                    Crossing::Below
+                },
+                before,
+                after,
+            });
+        }
+    };
+
+    for rule in &diff.added {
+        check(RuleKey::of(rule), None, Some(rule.metrics.confidence));
+    }
+    for rule in &diff.removed {
+        check(RuleKey::of(rule), Some(rule.metrics.confidence), None);
+    }
+    for change in &diff.changed {
+        check(
+            change.key.clone(),
+            Some(change.before.confidence),
+            Some(change.after.confidence),
+        );
+    }
+    crossings.sort_by(|a, b| a.key.cmp(&b.key));
+
+    DeploymentPlan {
+        diff,
+        confidence_threshold: threshold,
+        crossings,
+        top_changes: options.top_changes,
+        hash: plan_hash(new),
+    }
+}
+
+/// Order-independent hash of a rule set (items and metrics)
+///
+/// Guards against deploying a different set than the one reviewed; it is not
+/// a cryptographic signature.
+pub fn plan_hash(rules: &[AssociationRule]) -> String {
+    let mut entries: Vec<String> = rules
+        .iter()
+        .map(|rule| {
+            let key = RuleKey::of(rule);
+            format!(
+                "{:?}=>{:?}|{:?}|{:?}|{:?}",
+                key.antecedent,
+                key.consequent,
+                rule.metrics.confidence,
+                rule.metrics.support,
+                rule.metrics.lift
+            )
+        })
+        .collect();
+    entries.sort();
+
+    format!(
+        "{:016x}",
+        crate::export::grl::fnv1a_hash(&entries.join("\n"))
+    )
+}
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+impl DeploymentPlan {
+    /// Check whether deploying would change anything
+    pub fn has_changes(&self) -> bool {
+        !self.diff.is_empty()
+    }
+
+    /// Render a human-readable summary, optionally with ANSI colors
+    pub fn render(&self, color: bool) -> String {
+        let paint = |code: &str, text: String| {
+            if color {
+                format!("{}{}{}", code, text, RESET)
+            } else {
+                text
+            }
+        };
+
+        let diff = &self.diff;
+        let mut out = String::new();
+        out.push_str(&format!(
+            "Deployment plan {}\n",
+            paint(YELLOW, self.hash.clone())
+        ));
+        out.push_str(&format!(
+            "  {}  {}  {}  {} unchanged\n",
+            paint(GREEN, format!("+{} added", diff.added.len())),
+            paint(RED, format!("-{} removed", diff.removed.len())),
+            paint(YELLOW, format!("~{} changed", diff.changed.len())),
+            diff.unchanged
+        ));
+
+        if !diff.changed.is_empty() {
+            out.push_str("\nTop changed rules:\n");
+            for change in diff.changed.iter().take(self.top_changes) {
+                out.push_str(&format!(
+                    "  ~ {}  confidence {:+.1}pp  support {:+.1}pp  lift {:+.2}\n",
+                    change.key,
+                    change.confidence_delta() * 100.0,
+                    change.support_delta() * 100.0,
+                    change.lift_delta()
+                ));
+            }
+        }
+
+        if !self.crossings.is_empty() {
+            out.push_str(&format!(
+                "\nCrossing confidence {:.0}%:\n",
+                self.confidence_threshold * 100.0
+            ));
+            for crossing in &self.crossings {
+                let fmt_conf = |c: Option<f64>| {
+                    c.map(|c| format!("{:.1}%", c * 100.0))
+                        .unwrap_or_else(|| "-".to_string())
+                };
+                let line = format!(
+                    "  {} {}  {} -> {}",
+                    if crossing.direction == Crossing::Above {
+                        "↑"
+                    } else {
+                        "↓"
+                    },
+                    crossing.key,
+                    fmt_conf(crossing.before),
+                    fmt_conf(crossing.after)
+                );
+                let code = if crossing.direction == Crossing::Above {
+                    GREEN
+                } else {
+                    RED
+                };
+                out.push_str(&paint(code, line));
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+}
+
+impl fmt::Display for DeploymentPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+
+    fn current() -> Vec<AssociationRule> {
+        vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.8, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.75, 1.5),
+            test_rule(&["Pen"], &["Paper"], 0.9, 1.5),
+        ]
+    }
+
+    fn new_rules() -> Vec<AssociationRule> {
+        vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.8, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.6, 1.5),
+            test_rule(&["Monitor"], &["Cable"], 0.85, 1.5),
+        ]
+    }
+
+    #[test]
+    fn test_diff() {
+        let diff = RuleDiff::between(&current(), &new_rules());
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.unchanged, 1);
+        assert!((diff.changed[0].confidence_delta() + 0.15).abs() < 1e-12);
+        assert!(RuleDiff::between(&current(), &current()).is_empty());
+    }
+
+    #[test]
+    fn test_plan_crossings_and_render() {
+        let plan = plan(&current(), &new_rules());
+
+        let crossings: Vec<(String, Crossing)> = plan
+            .crossings
+            .iter()
+            .map(|c| (c.key.to_string(), c.direction))
+            .collect();
+        assert_eq!(
+            crossings,
+            vec![
+                ("Monitor => Cable".to_string(), Crossing::Above),
+                ("Pen => Paper".to_string(), Crossing::Below),
+                ("Phone => Case".to_string(), Crossing::Below),
+            ]
+        );
+
+        let text = plan.render(false);
+        assert!(text.contains("+1 added  -1 removed  ~1 changed  1 unchanged"));
+        assert!(text.contains("Phone => Case  confidence -15.0pp"));
+        assert!(!text.contains('\x1b'));
+        assert!(plan.render(true).contains(GREEN));
+    }
+
+    #[test]
+    fn test_plan_hash_is_order_independent() {
+        let mut reversed = new_rules();
+        reversed.reverse();
+        assert_eq!(plan_hash(&new_rules()), plan_hash(&reversed));
+        assert_ne!(plan_hash(&new_rules()), plan_hash(&current()));
+    }
+}
@@ -14,7 +14,7 @@
 //!    No additional filtering is done here - the engine executes all loaded rules.
 //!
 //! **Important**: Mining criteria are configured in `MiningConfig` and applied during
-//! `mine_association_rules()`, not during engine execution.
+//! `mine()`, not during engine execution.
 //!
 //! # Features
 //!
@@ -27,20 +27,19 @@
 //! # Example
 //!
 //! ```rust,ignore
-//! use rust_rule_miner::{RuleMiner, MiningConfig, Transaction};
+//! use rust_rule_miner::{Confidence, Lift, MiningConfig, RuleMiner, Support, Transaction};
 //! use rust_rule_miner::engine::{MiningRuleEngine, facts_from_cart};
 //!
 //! // PHASE 1: Mine rules with quality criteria
-//! let config = MiningConfig {
-//!     min_support: 0.3,        // Filter: must appear in 30%+ transactions
-//!     min_confidence: 0.7,     // Filter: must be correct 70%+ of time
-//!     min_lift: 1.2,           // Filter: must be 20%+ better than random
-//!     ..Default::default()
-//! };
+//! let config = MiningConfig::builder()
+//!     .min_support(Support::fraction(0.3)?)       // Filter: must appear in 30%+ transactions
+//!     .min_confidence(Confidence::fraction(0.7)?) // Filter: must be correct 70%+ of time
+//!     .min_lift(Lift::new(1.2)?)                  // Filter: must be 20%+ better than random
+//!     .build()?;
 //!
 //! let mut miner = RuleMiner::new(config);
 //! miner.add_transactions(transactions)?;
-//! let rules = miner.mine_association_rules()?;  // ← Filtered by criteria
+//! let rules = miner.mine()?.rules;  // ← Filtered by criteria
 //!
 //! // PHASE 2: Execute filtered rules in real-time
 //! let mut engine = MiningRuleEngine::new("Recommendations");
@@ -51,7 +50,7 @@
 //! ```
 
 #[cfg(feature = "engine")]
-use rust_rule_engine::{Facts, GRLParser, KnowledgeBase, RustRuleEngine, Value};
+use rust_rule_engine::{Facts, GRLParser, KnowledgeBase, Rule, RustRuleEngine, Value};
 
 use crate::errors::{MiningError, Result};
 use crate::export::{GrlConfig, GrlExporter};
@@ -94,30 +93,83 @@ impl MiningRuleEngine {
     }
 
     /// Load mined association rules into the engine
+    ///
+    /// Fails with `MiningError::EngineLoadFailed` naming the first rule the
+    /// engine rejects.
     pub fn load_rules(&mut self, rules: &[AssociationRule]) -> Result<usize> {
-        // Generate GRL code with current configuration
-        let grl_code = GrlExporter::to_grl_with_config(rules, &self.grl_config);
-        let parsed_rules = GRLParser::parse_rules(&grl_code)
-            .map_err(|e| MiningError::ExportFailed(format!("Failed to parse GRL: {}", e)))?;
+        let parsed_rules = self.parse_rules(rules)?;
 
         let mut loaded_count = 0;
         for rule in parsed_rules {
-            self.engine
-                .knowledge_base()
-                .add_rule(rule)
-                .map_err(|e| MiningError::ExportFailed(format!("Failed to add rule: {}", e)))?;
+            add_rule(self.engine.knowledge_base(), rule)?;
             loaded_count += 1;
         }
 
         Ok(loaded_count)
     }
 
+    /// Replace all loaded rules with a new set
+    ///
+    /// The new rules are converted and parsed before the knowledge base is
+    /// touched, so on error the previously loaded rules stay in place.
+    pub fn replace_rules(&mut self, rules: &[AssociationRule]) -> Result<usize> {
+        let parsed_rules = self.parse_rules(rules)?;
+
+        let kb = self.engine.knowledge_base();
+        kb.clear();
+        for rule in parsed_rules {
+            add_rule(kb, rule)?;
+        }
+
+        Ok(kb.rule_count())
+    }
+
+    /// Convert rules to GRL and parse them back into engine rules
+    fn parse_rules(&self, rules: &[AssociationRule]) -> Result<Vec<Rule>> {
+        let grl_code = GrlExporter::try_to_grl_with_config(rules, &self.grl_config)?;
+        GRLParser::parse_rules(&grl_code).map_err(|e| {
+            // Re-parse rule by rule to name the culprit
+            let rule_name = rules.iter().enumerate().find_map(|(idx, rule)| {
+                let grl = GrlExporter::rule_to_grl(rule, idx, &self.grl_config);
+                GRLParser::parse_rules(&grl)
+                    .is_err()
+                    .then(|| GrlExporter::generate_rule_name(rule, idx, &self.grl_config))
+            });
+            MiningError::EngineLoadFailed {
+                rule_name,
+                source_msg: e.to_string(),
+            }
+        })
+    }
+
+    /// Replace rules only if they match a reviewed `DeploymentPlan`
+    ///
+    /// `approved_hash` is the `hash` of the plan that was reviewed; deploying
+    /// any other rule set is rejected.
+    pub fn replace_rules_approved(
+        &mut self,
+        rules: &[AssociationRule],
+        approved_hash: &str,
+    ) -> Result<usize> {
+        let hash = crate::deployment::plan_hash(rules);
+        if hash != approved_hash {
+            return Err(MiningError::InvalidConfig(format!(
+                "Rule set hash {} does not match approved plan {}",
+                hash, approved_hash
+            )));
+        }
+
+        self.replace_rules(rules)
+    }
+
     /// Execute rules against provided facts
     pub fn execute(&mut self, facts: &Facts) -> Result<ExecutionResult> {
-        let result = self
-            .engine
-            .execute(facts)
-            .map_err(|e| MiningError::ExportFailed(format!("Execution failed: {}", e)))?;
+        let result =
+            self.engine
+                .execute(facts)
+                .map_err(|e| MiningError::EngineExecutionFailed {
+                    source_msg: e.to_string(),
+                })?;
 
         Ok(ExecutionResult {
             rules_fired: result.rules_fired,
@@ -136,6 +188,16 @@ impl MiningRuleEngine {
     }
 }
 
+#[cfg(feature = "engine")]
+fn add_rule(kb: &KnowledgeBase, rule: Rule) -> Result<()> {
+    let rule_name = rule.name.clone();
+    kb.add_rule(rule)
+        .map_err(|e| MiningError::EngineLoadFailed {
+            rule_name: Some(rule_name),
+            source_msg: e.to_string(),
+        })
+}
+
 #[cfg(feature = "engine")]
 /// Result of rule execution
 #[derive(Debug, Clone)]
@@ -245,7 +307,9 @@ pub fn facts_from_items_with_metadata(
 #[cfg(feature = "engine")]
 mod tests {
     use super::*;
-    use crate::{MiningConfig, RuleMiner, Transaction};
+    use crate::export::grl::{GrlRuleRenderer, RuleTemplate};
+    use crate::types::test_rule;
+    use crate::{Confidence, MiningConfig, RuleMiner, Support, Transaction};
     use chrono::Utc;
 
     #[test]
@@ -270,15 +334,15 @@ mod tests {
             Transaction::new("tx4", vec!["Laptop".to_string()], Utc::now()),
         ];
 
-        let config = MiningConfig {
-            min_support: 0.5,
-            min_confidence: 0.7,
-            ..Default::default()
-        };
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.5).unwrap())
+            .min_confidence(Confidence::fraction(0.7).unwrap())
+            .build()
+            .unwrap();
 
         let mut miner = RuleMiner::new(config);
         miner.add_transactions(transactions).unwrap();
-        let rules = miner.mine_association_rules().unwrap();
+        let rules = miner.mine().unwrap().rules;
 
         // Should mine at least one rule (Laptop => Mouse)
         assert!(!rules.is_empty(), "No rules were mined");
@@ -302,4 +366,99 @@ mod tests {
             "Output facts missing"
         );
     }
+
+    #[test]
+    fn test_replace_rules_requires_approved_plan() {
+        let rule =
+            |antecedent: &str, consequent: &str| test_rule(&[antecedent], &[consequent], 0.9, 1.5);
+        let current = vec![rule("Laptop", "Mouse")];
+        let reviewed = vec![rule("Laptop", "Mouse"), rule("Phone", "Case")];
+        let tampered = vec![rule("Laptop", "Mouse"), rule("Phone", "Charger")];
+
+        let mut engine = MiningRuleEngine::new("Deploy");
+        engine.load_rules(&current).unwrap();
+
+        let plan = crate::deployment::plan(&current, &reviewed);
+
+        let result = engine.replace_rules_approved(&tampered, &plan.hash);
+        assert!(matches!(result, Err(MiningError::InvalidConfig(_))));
+        assert_eq!(engine.engine().knowledge_base().rule_count(), 1);
+
+        let loaded = engine
+            .replace_rules_approved(&reviewed, &plan.hash)
+            .unwrap();
+        assert_eq!(loaded, 2);
+    }
+
+    /// Default rendering, except for rules about `item`
+    #[derive(Debug)]
+    struct Sabotage {
+        item: &'static str,
+        conditions: &'static str,
+        actions: &'static str,
+    }
+
+    impl GrlRuleRenderer for Sabotage {
+        fn render_conditions(&self, rule: &AssociationRule, config: &GrlConfig) -> String {
+            if rule.antecedent.iter().any(|i| i == self.item) && !self.conditions.is_empty() {
+                return self.conditions.to_string();
+            }
+            RuleTemplate::Recommendation.render_conditions(rule, config)
+        }
+
+        fn render_actions(&self, rule: &AssociationRule, config: &GrlConfig) -> String {
+            if rule.antecedent.iter().any(|i| i == self.item) && !self.actions.is_empty() {
+                return self.actions.to_string();
+            }
+            RuleTemplate::Recommendation.render_actions(rule, config)
+        }
+    }
+
+    #[test]
+    fn test_parse_failure_names_rule() {
+        let config = GrlConfig::default().with_renderer(Sabotage {
+            item: "Phone",
+            conditions: "((( not grl",
+            actions: "",
+        });
+        let mut engine = MiningRuleEngine::with_config("Broken", config);
+        let rules = [
+            test_rule(&["Laptop"], &["Mouse"], 0.9, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.9, 1.5),
+        ];
+
+        match engine.load_rules(&rules) {
+            Err(MiningError::EngineLoadFailed {
+                rule_name,
+                source_msg,
+            }) => {
+                assert_eq!(rule_name.as_deref(), Some("Mined_1_Phone_Implies_Case"));
+                assert!(!source_msg.is_empty());
+            }
+            other => panic!("expected EngineLoadFailed, got {:?}", other.map(|_| ())),
+        }
+        assert_eq!(engine.engine().knowledge_base().rule_count(), 0);
+    }
+
+    #[test]
+    fn test_execution_failure_variant() {
+        let config = GrlConfig::default().with_renderer(Sabotage {
+            item: "Laptop",
+            conditions: "ShoppingCart.total > 100",
+            actions: "NoSuchHandler(\"boom\")",
+        });
+        let mut engine = MiningRuleEngine::with_config("Failing", config);
+        engine
+            .load_rules(&[test_rule(&["Laptop"], &["Mouse"], 0.9, 1.5)])
+            .unwrap();
+
+        let facts = facts_from_cart(vec!["Laptop".to_string()]);
+        facts.set("ShoppingCart.total", Value::Integer(999));
+        let result = engine.execute(&facts);
+        assert!(
+            matches!(result, Err(MiningError::EngineExecutionFailed { .. })),
+            "{:?}",
+            result.map(|r| r.rules_fired)
+        );
+    }
 }
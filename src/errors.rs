@@ -25,6 +25,21 @@ pub enum MiningError {
     #[error("Export failed: {0}")]
     ExportFailed(String),
 
+    /// Generated GRL could not be parsed or added to the rule engine
+    #[error(
+        "Engine load failed{}: {source_msg}",
+        rule_name.as_ref().map(|name| format!(" for rule `{}`", name)).unwrap_or_default()
+    )]
+    EngineLoadFailed {
+        /// GRL name of the offending rule, when it could be pinned down
+        rule_name: Option<String>,
+        source_msg: String,
+    },
+
+    /// The rule engine failed while executing loaded rules
+    #[error("Engine execution failed: {source_msg}")]
+    EngineExecutionFailed { source_msg: String },
+
     #[error("Data load error: {0}")]
     DataLoadError(String),
 }
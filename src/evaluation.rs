@@ -0,0 +1,461 @@
+//! Replaying historical baskets against recommendations
+//!
+//! `replay` walks a day (or any period) of real baskets in time order. For
+//! each basket it hides an item, asks the recommender about the rest, and
+//! records whether the hidden item came back. The report gives the hit rate
+//! per hour, so a rule set can be judged against the traffic it will serve,
+//! and per rule, so the rules that earn their place stand out from the ones
+//! that only add noise.
+//!
+//! Any `RecommendationSource` can be replayed: the pure `Recommender`, or,
+//! with the `engine` feature, a loaded `MiningRuleEngine` via `EngineSource`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::deployment::RuleKey;
+use crate::errors::Result;
+use crate::rules::{Recommendation, Recommender};
+use crate::transaction::Transaction;
+
+/// Which items of a basket are hidden from the recommender
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RevealPolicy {
+    /// Hide the last item added: one trial per basket
+    #[default]
+    LastItem,
+
+    /// Hide each distinct item in turn (leave-one-out): one trial per item
+    EachItem,
+}
+
+impl RevealPolicy {
+    /// (shown items, hidden item) trials for `items`
+    fn trials(&self, items: &[String]) -> Vec<(Vec<String>, String)> {
+        let hide = |hidden: &String| {
+            let shown: Vec<String> = items
+                .iter()
+                .filter(|item| *item != hidden)
+                .cloned()
+                .collect();
+            (shown, hidden.clone())
+        };
+        let trials: Vec<(Vec<String>, String)> = match self {
+            RevealPolicy::LastItem => items.last().map(hide).into_iter().collect(),
+            RevealPolicy::EachItem => {
+                let mut distinct: Vec<&String> = Vec::new();
+                for item in items {
+                    if !distinct.contains(&item) {
+                        distinct.push(item);
+                    }
+                }
+                distinct.into_iter().map(hide).collect()
+            }
+        };
+        // A basket of one repeated item leaves nothing to recommend from
+        trials
+            .into_iter()
+            .filter(|(shown, _)| !shown.is_empty())
+            .collect()
+    }
+}
+
+/// Something that recommends items for a partial basket
+pub trait RecommendationSource {
+    /// Up to `top_n` recommendations for `basket`, with the rules behind each
+    fn recommend(&mut self, basket: &[String], top_n: usize) -> Result<Vec<Recommendation>>;
+}
+
+impl RecommendationSource for Recommender<'_> {
+    fn recommend(&mut self, basket: &[String], top_n: usize) -> Result<Vec<Recommendation>> {
+        Ok(Recommender::recommend(self, basket, top_n))
+    }
+}
+
+/// Trials and hits of one hour of baskets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HourlyHitRate {
+    /// Start of the hour
+    pub hour: DateTime<Utc>,
+    pub trials: usize,
+    pub hits: usize,
+}
+
+impl HourlyHitRate {
+    /// Share of trials whose hidden item was recommended (0.0 without trials)
+    pub fn hit_rate(&self) -> f64 {
+        ratio(self.hits, self.trials)
+    }
+}
+
+/// How often a rule's recommendations were right
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleAttribution {
+    pub rule: RuleKey,
+    /// Trials in which the rule backed a recommendation
+    pub recommended: usize,
+    /// Of those, trials in which the recommended item was the hidden one
+    pub hits: usize,
+}
+
+impl RuleAttribution {
+    /// Share of the rule's recommendations that were hits
+    pub fn precision(&self) -> f64 {
+        ratio(self.hits, self.recommended)
+    }
+}
+
+/// Outcome of `replay`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayReport {
+    pub trials: usize,
+    pub hits: usize,
+    /// Hours with at least one trial, in time order
+    pub hourly: Vec<HourlyHitRate>,
+    /// Rules that backed a recommendation, most hits first
+    pub rules: Vec<RuleAttribution>,
+}
+
+impl ReplayReport {
+    /// Share of all trials whose hidden item was recommended
+    pub fn hit_rate(&self) -> f64 {
+        ratio(self.hits, self.trials)
+    }
+}
+
+fn ratio(hits: usize, trials: usize) -> f64 {
+    if trials == 0 {
+        0.0
+    } else {
+        hits as f64 / trials as f64
+    }
+}
+
+/// Replay `transactions` in time order against `source`
+///
+/// Each basket yields the trials `policy` picks; a trial is a hit when the
+/// hidden item is among the `top_n` recommendations for the shown items.
+/// Baskets left empty by hiding an item are skipped.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::evaluation::{replay, RevealPolicy};
+/// use rust_rule_miner::{AssociationRule, PatternMetrics, Recommender, Transaction};
+/// use chrono::Utc;
+///
+/// let rules = vec![AssociationRule {
+///     antecedent: vec!["Laptop".to_string()],
+///     consequent: vec!["Mouse".to_string()],
+///     metrics: PatternMetrics::basic(0.8, 0.3, 1.5),
+/// }];
+/// let day = vec![
+///     Transaction::new("tx1", vec!["Laptop".to_string(), "Mouse".to_string()], Utc::now()),
+///     Transaction::new("tx2", vec!["Laptop".to_string(), "Bag".to_string()], Utc::now()),
+/// ];
+///
+/// let report = replay(&mut Recommender::new(&rules), &day, 3, RevealPolicy::LastItem)?;
+/// assert_eq!(report.hit_rate(), 0.5);
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+pub fn replay(
+    source: &mut impl RecommendationSource,
+    transactions: &[Transaction],
+    top_n: usize,
+    policy: RevealPolicy,
+) -> Result<ReplayReport> {
+    let mut ordered: Vec<&Transaction> = transactions.iter().collect();
+    ordered.sort_by_key(|tx| tx.timestamp);
+
+    let mut report = ReplayReport::default();
+    let mut hourly: BTreeMap<DateTime<Utc>, (usize, usize)> = BTreeMap::new();
+    let mut rules: HashMap<RuleKey, (usize, usize)> = HashMap::new();
+
+    for tx in ordered {
+        let hour = tx
+            .timestamp
+            .duration_trunc(TimeDelta::hours(1))
+            .unwrap_or(tx.timestamp);
+        for (shown, hidden) in policy.trials(&tx.items) {
+            let recommendations = source.recommend(&shown, top_n)?;
+            let hit = recommendations.iter().any(|rec| rec.item == hidden);
+
+            let (trials, hits) = hourly.entry(hour).or_default();
+            *trials += 1;
+            report.trials += 1;
+            if hit {
+                *hits += 1;
+                report.hits += 1;
+            }
+
+            // A rule backing several recommendations counts once per trial
+            let mut backed: HashMap<RuleKey, bool> = HashMap::new();
+            for rec in &recommendations {
+                for rule in &rec.rules {
+                    *backed.entry(RuleKey::of(rule)).or_default() |= rec.item == hidden;
+                }
+            }
+            for (key, hit) in backed {
+                let (recommended, hits) = rules.entry(key).or_default();
+                *recommended += 1;
+                *hits += usize::from(hit);
+            }
+        }
+    }
+
+    report.hourly = hourly
+        .into_iter()
+        .map(|(hour, (trials, hits))| HourlyHitRate { hour, trials, hits })
+        .collect();
+    report.rules = rules
+        .into_iter()
+        .map(|(rule, (recommended, hits))| RuleAttribution {
+            rule,
+            recommended,
+            hits,
+        })
+        .collect();
+    report.rules.sort_by(|a, b| {
+        b.hits
+            .cmp(&a.hits)
+            .then_with(|| a.recommended.cmp(&b.recommended))
+            .then_with(|| a.rule.cmp(&b.rule))
+    });
+    Ok(report)
+}
+
+#[cfg(feature = "engine")]
+pub use engine_source::EngineSource;
+
+#[cfg(feature = "engine")]
+mod engine_source {
+    use rust_rule_engine::Value;
+
+    use super::RecommendationSource;
+    use crate::engine::{facts_from_items, MiningRuleEngine};
+    use crate::errors::Result;
+    use crate::rules::{Recommendation, ScoreAggregation};
+    use crate::types::AssociationRule;
+
+    /// A loaded `MiningRuleEngine` as a `RecommendationSource`
+    ///
+    /// Recommendations are the items the engine writes to
+    /// `GrlConfig::output_field`. The engine does not say which rule added
+    /// an item, so each is attributed to the `rules` (the set the engine
+    /// was loaded with) that match the basket and propose it, and scored by
+    /// their highest confidence. Each basket runs as a fresh session, with
+    /// the engine's `no-loop` tracking reset.
+    pub struct EngineSource<'a> {
+        engine: &'a mut MiningRuleEngine,
+        rules: &'a [AssociationRule],
+    }
+
+    impl<'a> EngineSource<'a> {
+        pub fn new(engine: &'a mut MiningRuleEngine, rules: &'a [AssociationRule]) -> Self {
+            Self { engine, rules }
+        }
+    }
+
+    impl RecommendationSource for EngineSource<'_> {
+        fn recommend(&mut self, basket: &[String], top_n: usize) -> Result<Vec<Recommendation>> {
+            let config = self.engine.grl_config().clone();
+            let facts = facts_from_items(basket.to_vec(), &config);
+            // Each basket is a new session; `no-loop` rules must fire again
+            self.engine.engine_mut().reset_no_loop_tracking();
+            let result = self.engine.execute(&facts)?;
+
+            let mut items: Vec<String> = Vec::new();
+            if let Some(Value::Array(values)) = result.get(&config.output_field) {
+                for value in values {
+                    if let Value::String(item) = value {
+                        if !basket.contains(&item) && !items.contains(&item) {
+                            items.push(item);
+                        }
+                    }
+                }
+            }
+
+            let aggregation = ScoreAggregation::Max;
+            let mut recommendations: Vec<Recommendation> = items
+                .into_iter()
+                .map(|item| {
+                    let rules: Vec<AssociationRule> = self
+                        .rules
+                        .iter()
+                        .filter(|rule| {
+                            rule.consequent.contains(&item)
+                                && rule.antecedent.iter().all(|a| basket.contains(a))
+                        })
+                        .cloned()
+                        .collect();
+                    let score = aggregation.score(&rules.iter().collect::<Vec<_>>());
+                    Recommendation {
+                        item,
+                        score,
+                        rules,
+                        aggregation,
+                    }
+                })
+                .collect();
+            recommendations.sort_by(|a, b| {
+                b.score
+                    .partial_cmp(&a.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.item.cmp(&b.item))
+            });
+            recommendations.truncate(top_n);
+            Ok(recommendations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_rule, AssociationRule};
+    use chrono::TimeZone;
+
+    fn rules() -> Vec<AssociationRule> {
+        vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.8, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.9, 1.5),
+            test_rule(&["Laptop"], &["Bag"], 0.6, 1.5),
+        ]
+    }
+
+    /// 09:00-10:00 all hits, 10:00-11:00 one of two, 11:00 none
+    fn scripted_day() -> Vec<Transaction> {
+        let at = |hour, minute| Utc.with_ymd_and_hms(2024, 3, 1, hour, minute, 0).unwrap();
+        let basket = |id: &str, items: &[&str], time| {
+            Transaction::new(id, items.iter().map(|s| s.to_string()).collect(), time)
+        };
+        // Given out of order; replay sorts by time
+        vec![
+            basket("t4", &["Phone", "Case"], at(10, 40)),
+            basket("t1", &["Laptop", "Mouse"], at(9, 5)),
+            basket("t2", &["Phone", "Case"], at(9, 50)),
+            basket("t3", &["Laptop", "Keyboard"], at(10, 15)),
+            basket("t5", &["Tea", "Cake"], at(11, 0)),
+            basket("t6", &["Mouse"], at(11, 30)),
+        ]
+    }
+
+    #[test]
+    fn test_hourly_hit_rate_and_attribution() {
+        let rules = rules();
+        let report = replay(
+            &mut Recommender::new(&rules),
+            &scripted_day(),
+            1,
+            RevealPolicy::LastItem,
+        )
+        .unwrap();
+
+        // The single-item basket gives no trial
+        assert_eq!((report.trials, report.hits), (5, 3));
+        assert_eq!(report.hit_rate(), 0.6);
+        let hourly: Vec<(u32, usize, usize)> = report
+            .hourly
+            .iter()
+            .map(|h| (chrono::Timelike::hour(&h.hour), h.trials, h.hits))
+            .collect();
+        assert_eq!(hourly, [(9, 2, 2), (10, 2, 1), (11, 1, 0)]);
+        assert_eq!(report.hourly[1].hit_rate(), 0.5);
+
+        // Top-1 for Laptop is always Mouse; Laptop => Bag never shows
+        let attribution: Vec<(String, usize, usize)> = report
+            .rules
+            .iter()
+            .map(|a| (a.rule.to_string(), a.recommended, a.hits))
+            .collect();
+        assert_eq!(
+            attribution,
+            [
+                ("Phone => Case".to_string(), 2, 2),
+                ("Laptop => Mouse".to_string(), 2, 1),
+            ]
+        );
+        assert_eq!(report.rules[1].precision(), 0.5);
+    }
+
+    #[test]
+    fn test_each_item_policy() {
+        let rules = rules();
+        let day = [Transaction::new(
+            "t1",
+            vec![
+                "Laptop".to_string(),
+                "Mouse".to_string(),
+                "Laptop".to_string(),
+            ],
+            Utc::now(),
+        )];
+        let report = replay(
+            &mut Recommender::new(&rules),
+            &day,
+            3,
+            RevealPolicy::EachItem,
+        )
+        .unwrap();
+
+        // Hiding Laptop leaves Mouse, which predicts nothing
+        assert_eq!((report.trials, report.hits), (2, 1));
+    }
+
+    /// Fires every rule: the native engine's `contains` only matches
+    /// strings, not the basket array
+    #[cfg(feature = "engine")]
+    #[derive(Debug)]
+    struct AlwaysFire;
+
+    #[cfg(feature = "engine")]
+    impl crate::export::grl::GrlRuleRenderer for AlwaysFire {
+        fn render_conditions(&self, _rule: &AssociationRule, config: &crate::GrlConfig) -> String {
+            format!("{} != \"\"", config.input_field)
+        }
+
+        fn render_actions(&self, rule: &AssociationRule, config: &crate::GrlConfig) -> String {
+            format!("{} += \"{}\"", config.output_field, rule.consequent[0])
+        }
+    }
+
+    #[cfg(feature = "engine")]
+    #[test]
+    fn test_engine_source() {
+        use crate::engine::MiningRuleEngine;
+        use crate::GrlDialect;
+
+        let rules = vec![test_rule(&["Laptop"], &["Mouse"], 0.8, 1.5)];
+        let config = crate::GrlConfig::default()
+            .with_dialect(GrlDialect::Rete)
+            .with_renderer(AlwaysFire);
+        let mut engine = MiningRuleEngine::with_config("Replay", config);
+        engine.load_rules(&rules).unwrap();
+
+        let day = [
+            Transaction::new(
+                "t1",
+                vec!["Laptop".to_string(), "Mouse".to_string()],
+                Utc::now(),
+            ),
+            Transaction::new(
+                "t2",
+                vec!["Phone".to_string(), "Mouse".to_string()],
+                Utc::now(),
+            ),
+        ];
+        let report = replay(
+            &mut EngineSource::new(&mut engine, &rules),
+            &day,
+            3,
+            RevealPolicy::LastItem,
+        )
+        .unwrap();
+
+        // Both hits, but only the Laptop basket matches the rule
+        assert_eq!((report.trials, report.hits), (2, 2));
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!((report.rules[0].recommended, report.rules[0].hits), (1, 1));
+    }
+}
@@ -0,0 +1,668 @@
+//! Export rules to AWS S3 (requires `cloud` feature)
+//!
+//! Uploads never expose partial objects: data is written with a multipart
+//! upload to `<key>.tmp`, copied to `<key>` once complete, and the temporary
+//! object is deleted. Each part is retried with exponential backoff, and a
+//! failed upload is aborted so no orphaned parts are billed.
+//!
+//! # Example
+//! ```no_run
+//! use rust_rule_miner::export::cloud::write_to_s3;
+//! use rust_rule_miner::export::{ExportFormat, GrlConfig};
+//! # async fn run(rules: Vec<rust_rule_miner::AssociationRule>) -> rust_rule_miner::Result<()> {
+//!
+//! write_to_s3("rules-bucket", "nightly/rules.grl", &ExportFormat::Grl(GrlConfig::default()), &rules)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::time::Duration;
+
+use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use serde::Serialize;
+
+use super::ExportFormat;
+use crate::errors::{MiningError, Result};
+use crate::types::AssociationRule;
+
+/// Minimum S3 part size (all parts but the last must be at least 5 MiB)
+pub const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Suffix of the temporary object written before the final rename
+pub const TEMP_SUFFIX: &str = ".tmp";
+
+/// Storage operations needed for an atomic multipart upload
+///
+/// Implemented for S3 by `S3Store`; tests substitute an in-memory store.
+pub trait MultipartStore {
+    /// Start a multipart upload, returning its upload id
+    fn create_multipart(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> impl Future<Output = Result<String>> + Send;
+
+    /// Upload one part (numbered from 1), returning its ETag
+    fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: i32,
+        data: Vec<u8>,
+    ) -> impl Future<Output = Result<String>> + Send;
+
+    /// Complete an upload from `(part_number, etag)` pairs
+    fn complete_multipart(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: Vec<(i32, String)>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Abort an upload and discard its parts
+    fn abort_multipart(
+        &self,
+        key: &str,
+        upload_id: &str,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Server-side copy within the store
+    fn copy_object(&self, from: &str, to: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// Delete an object
+    fn delete_object(&self, key: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// Smallest part the store accepts before the last one
+    ///
+    /// `UploadOptions::part_size` is raised to this.
+    fn min_part_size(&self) -> usize {
+        1
+    }
+}
+
+/// Upload tuning
+#[derive(Debug, Clone)]
+pub struct UploadOptions {
+    /// Bytes per multipart part, raised to the store's minimum
+    /// (`MIN_PART_SIZE` for S3)
+    pub part_size: usize,
+    /// Attempts per operation, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on every further retry
+    pub retry_delay: Duration,
+}
+
+impl Default for UploadOptions {
+    fn default() -> Self {
+        Self {
+            part_size: 8 * 1024 * 1024,
+            max_attempts: 3,
+            retry_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Summary of a completed upload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadReport {
+    /// Final object key
+    pub key: String,
+    pub bytes: usize,
+    pub parts: usize,
+    /// Retries needed across all operations
+    pub retries: u32,
+}
+
+/// S3 bucket implementing `MultipartStore`
+pub struct S3Store {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+}
+
+impl S3Store {
+    /// Wrap an existing client
+    pub fn new(client: aws_sdk_s3::Client, bucket: impl Into<String>) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+        }
+    }
+
+    /// Create a client from the standard AWS environment/config chain
+    pub async fn from_env(bucket: impl Into<String>) -> Self {
+        let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+        Self::new(aws_sdk_s3::Client::new(&config), bucket)
+    }
+}
+
+fn s3_error<E: std::error::Error>(action: &str, key: &str, error: E) -> MiningError {
+    MiningError::ExportFailed(format!(
+        "S3 {} failed for '{}': {}",
+        action,
+        key,
+        DisplayErrorContext(error)
+    ))
+}
+
+/// `CopySource` for `key` in `bucket`: S3 wants it URL-encoded
+fn copy_source(bucket: &str, key: &str) -> String {
+    let mut source = format!("{}/", bucket);
+    for byte in key.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                source.push(byte as char)
+            }
+            _ => source.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    source
+}
+
+impl MultipartStore for S3Store {
+    async fn create_multipart(&self, key: &str, content_type: &str) -> Result<String> {
+        let output = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(|e| s3_error("create multipart upload", key, e))?;
+
+        output.upload_id().map(str::to_string).ok_or_else(|| {
+            MiningError::ExportFailed(format!("S3 returned no upload id for '{}'", key))
+        })
+    }
+
+    async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: i32,
+        data: Vec<u8>,
+    ) -> Result<String> {
+        let output = self
+            .client
+            .upload_part()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .part_number(part_number)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| s3_error("upload part", key, e))?;
+
+        Ok(output.e_tag().unwrap_or_default().to_string())
+    }
+
+    async fn complete_multipart(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: Vec<(i32, String)>,
+    ) -> Result<()> {
+        let parts = parts
+            .into_iter()
+            .map(|(number, etag)| {
+                CompletedPart::builder()
+                    .part_number(number)
+                    .e_tag(etag)
+                    .build()
+            })
+            .collect();
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .map_err(|e| s3_error("complete multipart upload", key, e))?;
+        Ok(())
+    }
+
+    async fn abort_multipart(&self, key: &str, upload_id: &str) -> Result<()> {
+        self.client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .send()
+            .await
+            .map_err(|e| s3_error("abort multipart upload", key, e))?;
+        Ok(())
+    }
+
+    async fn copy_object(&self, from: &str, to: &str) -> Result<()> {
+        self.client
+            .copy_object()
+            .bucket(&self.bucket)
+            .copy_source(copy_source(&self.bucket, from))
+            .key(to)
+            .send()
+            .await
+            .map_err(|e| s3_error("copy", to, e))?;
+        Ok(())
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| s3_error("delete", key, e))?;
+        Ok(())
+    }
+
+    fn min_part_size(&self) -> usize {
+        MIN_PART_SIZE
+    }
+}
+
+/// Export rules to `s3://bucket/key` using the default AWS config
+pub async fn write_to_s3(
+    bucket: &str,
+    key: &str,
+    format: &ExportFormat,
+    rules: &[AssociationRule],
+) -> Result<UploadReport> {
+    let store = S3Store::from_env(bucket).await;
+    write_rules(&store, key, format, rules, &UploadOptions::default()).await
+}
+
+/// Write a JSON manifest (e.g. `PrivacyManifest`) to `s3://bucket/key`
+pub async fn write_manifest_to_s3<T: Serialize>(
+    bucket: &str,
+    key: &str,
+    manifest: &T,
+) -> Result<UploadReport> {
+    let store = S3Store::from_env(bucket).await;
+    write_manifest(&store, key, manifest, &UploadOptions::default()).await
+}
+
+/// Export rules to any `MultipartStore`
+pub async fn write_rules<S: MultipartStore>(
+    store: &S,
+    key: &str,
+    format: &ExportFormat,
+    rules: &[AssociationRule],
+    options: &UploadOptions,
+) -> Result<UploadReport> {
+    let body = format.render(rules)?;
+    write_atomic(
+        store,
+        key,
+        body.into_bytes(),
+        format.content_type(),
+        options,
+    )
+    .await
+}
+
+/// Write a JSON manifest to any `MultipartStore`
+pub async fn write_manifest<S: MultipartStore, T: Serialize>(
+    store: &S,
+    key: &str,
+    manifest: &T,
+    options: &UploadOptions,
+) -> Result<UploadReport> {
+    let body = serde_json::to_vec_pretty(manifest)?;
+    write_atomic(store, key, body, "application/json", options).await
+}
+
+/// Multipart upload to `<key>.tmp`, then copy to `key` and delete the temp object
+pub async fn write_atomic<S: MultipartStore>(
+    store: &S,
+    key: &str,
+    data: Vec<u8>,
+    content_type: &str,
+    options: &UploadOptions,
+) -> Result<UploadReport> {
+    let temp_key = format!("{}{}", key, TEMP_SUFFIX);
+    let mut retries = 0;
+    let bytes = data.len();
+
+    let upload_id = with_retry(options, &mut retries, || {
+        store.create_multipart(&temp_key, content_type)
+    })
+    .await?;
+
+    let parts = match upload_parts(store, &temp_key, &upload_id, &data, options, &mut retries).await
+    {
+        Ok(parts) => parts,
+        Err(e) => {
+            if let Err(abort) = store.abort_multipart(&temp_key, &upload_id).await {
+                log::warn!("Failed to abort upload of '{}': {}", temp_key, abort);
+            }
+            return Err(e);
+        }
+    };
+    let part_count = parts.len();
+
+    with_retry(options, &mut retries, || {
+        store.complete_multipart(&temp_key, &upload_id, parts.clone())
+    })
+    .await?;
+
+    // Consumers only ever see the final key once it is complete
+    with_retry(options, &mut retries, || store.copy_object(&temp_key, key)).await?;
+
+    if let Err(e) = with_retry(options, &mut retries, || store.delete_object(&temp_key)).await {
+        log::warn!(
+            "Uploaded '{}' but failed to delete '{}': {}",
+            key,
+            temp_key,
+            e
+        );
+    }
+
+    Ok(UploadReport {
+        key: key.to_string(),
+        bytes,
+        parts: part_count,
+        retries,
+    })
+}
+
+async fn upload_parts<S: MultipartStore>(
+    store: &S,
+    key: &str,
+    upload_id: &str,
+    data: &[u8],
+    options: &UploadOptions,
+    retries: &mut u32,
+) -> Result<Vec<(i32, String)>> {
+    let part_size = options.part_size.max(store.min_part_size());
+    let mut parts = Vec::new();
+
+    // An empty export still needs one (empty) part
+    let chunks: Vec<&[u8]> = if data.is_empty() {
+        vec![data]
+    } else {
+        data.chunks(part_size).collect()
+    };
+
+    for (idx, chunk) in chunks.into_iter().enumerate() {
+        let part_number = idx as i32 + 1;
+        let etag = with_retry(options, retries, || {
+            store.upload_part(key, upload_id, part_number, chunk.to_vec())
+        })
+        .await?;
+        parts.push((part_number, etag));
+    }
+
+    Ok(parts)
+}
+
+async fn with_retry<T, F, Fut>(options: &UploadOptions, retries: &mut u32, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delay = options.retry_delay;
+    let mut attempt = 1;
+
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < options.max_attempts => {
+                log::warn!("Attempt {} failed, retrying: {}", attempt, e);
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+                *retries += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PatternMetrics;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Uploaded `(part_number, bytes)` of an in-progress upload
+    type Parts = Vec<(i32, Vec<u8>)>;
+
+    /// In-memory store recording every call
+    #[derive(Default)]
+    struct MockStore {
+        calls: Mutex<Vec<String>>,
+        uploads: Mutex<HashMap<String, Parts>>,
+        objects: Mutex<HashMap<String, Vec<u8>>>,
+        /// Number of upload_part calls that fail before succeeding
+        failing_parts: Mutex<u32>,
+        min_part_size: usize,
+    }
+
+    impl MockStore {
+        fn record(&self, call: String) {
+            self.calls.lock().unwrap().push(call);
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl MultipartStore for MockStore {
+        fn min_part_size(&self) -> usize {
+            self.min_part_size.max(1)
+        }
+
+        async fn create_multipart(&self, key: &str, content_type: &str) -> Result<String> {
+            self.record(format!("create {} {}", key, content_type));
+            self.uploads
+                .lock()
+                .unwrap()
+                .insert("upload-1".to_string(), Vec::new());
+            Ok("upload-1".to_string())
+        }
+
+        async fn upload_part(
+            &self,
+            _key: &str,
+            upload_id: &str,
+            part_number: i32,
+            data: Vec<u8>,
+        ) -> Result<String> {
+            {
+                let mut failing = self.failing_parts.lock().unwrap();
+                if *failing > 0 {
+                    *failing -= 1;
+                    self.record(format!("part {} failed", part_number));
+                    return Err(MiningError::ExportFailed("connection reset".to_string()));
+                }
+            }
+            self.record(format!("part {} {}", part_number, data.len()));
+            self.uploads
+                .lock()
+                .unwrap()
+                .get_mut(upload_id)
+                .unwrap()
+                .push((part_number, data));
+            Ok(format!("etag-{}", part_number))
+        }
+
+        async fn complete_multipart(
+            &self,
+            key: &str,
+            upload_id: &str,
+            parts: Vec<(i32, String)>,
+        ) -> Result<()> {
+            self.record(format!("complete {} {}", key, parts.len()));
+            let mut uploaded = self.uploads.lock().unwrap().remove(upload_id).unwrap();
+            uploaded.sort_by_key(|(n, _)| *n);
+            let body = uploaded.into_iter().flat_map(|(_, d)| d).collect();
+            self.objects.lock().unwrap().insert(key.to_string(), body);
+            Ok(())
+        }
+
+        async fn abort_multipart(&self, key: &str, upload_id: &str) -> Result<()> {
+            self.record(format!("abort {}", key));
+            self.uploads.lock().unwrap().remove(upload_id);
+            Ok(())
+        }
+
+        async fn copy_object(&self, from: &str, to: &str) -> Result<()> {
+            self.record(format!("copy {} {}", from, to));
+            let mut objects = self.objects.lock().unwrap();
+            let body = objects.get(from).cloned().unwrap();
+            objects.insert(to.to_string(), body);
+            Ok(())
+        }
+
+        async fn delete_object(&self, key: &str) -> Result<()> {
+            self.record(format!("delete {}", key));
+            self.objects.lock().unwrap().remove(key);
+            Ok(())
+        }
+    }
+
+    fn options(part_size: usize) -> UploadOptions {
+        UploadOptions {
+            part_size,
+            max_attempts: 3,
+            retry_delay: Duration::ZERO,
+        }
+    }
+
+    fn rules() -> Vec<AssociationRule> {
+        vec![AssociationRule::new(
+            ["Laptop"],
+            ["Mouse"],
+            PatternMetrics::basic(0.8, 0.5, 1.5),
+        )]
+    }
+
+    #[tokio::test]
+    async fn test_multipart_chunking_and_rename() {
+        let store = MockStore::default();
+        let data: Vec<u8> = (0..25u8).collect();
+
+        let report = write_atomic(&store, "out/rules.bin", data.clone(), "a/b", &options(10))
+            .await
+            .unwrap();
+
+        assert_eq!(report.parts, 3);
+        assert_eq!(report.bytes, 25);
+        assert_eq!(
+            store.calls(),
+            vec![
+                "create out/rules.bin.tmp a/b",
+                "part 1 10",
+                "part 2 10",
+                "part 3 5",
+                "complete out/rules.bin.tmp 3",
+                "copy out/rules.bin.tmp out/rules.bin",
+                "delete out/rules.bin.tmp",
+            ]
+        );
+
+        let objects = store.objects.lock().unwrap();
+        assert_eq!(objects.get("out/rules.bin"), Some(&data));
+        assert!(!objects.contains_key("out/rules.bin.tmp"));
+    }
+
+    #[tokio::test]
+    async fn test_part_size_raised_to_store_minimum() {
+        let store = MockStore {
+            min_part_size: 10,
+            ..Default::default()
+        };
+        let report = write_atomic(&store, "k", vec![0; 25], "a/b", &options(4))
+            .await
+            .unwrap();
+        assert_eq!(report.parts, 3);
+        assert!(store.calls().contains(&"part 1 10".to_string()));
+    }
+
+    #[test]
+    fn test_copy_source_is_encoded() {
+        assert_eq!(
+            copy_source("rules", "nightly/rules v2+é?.grl.tmp"),
+            "rules/nightly/rules%20v2%2B%C3%A9%3F.grl.tmp"
+        );
+        assert_eq!(copy_source("rules", "a/100%.tmp"), "rules/a/100%25.tmp");
+    }
+
+    #[tokio::test]
+    async fn test_part_retry() {
+        let store = MockStore::default();
+        *store.failing_parts.lock().unwrap() = 2;
+
+        let report = write_atomic(&store, "k", vec![1; 4], "a/b", &options(10))
+            .await
+            .unwrap();
+
+        assert_eq!(report.retries, 2);
+        assert_eq!(store.objects.lock().unwrap().get("k"), Some(&vec![1; 4]));
+    }
+
+    #[tokio::test]
+    async fn test_failed_upload_is_aborted_and_never_renamed() {
+        let store = MockStore::default();
+        *store.failing_parts.lock().unwrap() = 5;
+
+        let result = write_atomic(&store, "k", vec![1; 4], "a/b", &options(10)).await;
+
+        assert!(matches!(result, Err(MiningError::ExportFailed(_))));
+        let calls = store.calls();
+        assert_eq!(calls.last().unwrap(), "abort k.tmp");
+        assert!(!calls.iter().any(|c| c.starts_with("copy")));
+        assert!(store.objects.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_content_type_per_format() {
+        let store = MockStore::default();
+        write_rules(
+            &store,
+            "r.json",
+            &ExportFormat::Json,
+            &rules(),
+            &options(1024),
+        )
+        .await
+        .unwrap();
+        assert_eq!(store.calls()[0], "create r.json.tmp application/json");
+
+        let body = store
+            .objects
+            .lock()
+            .unwrap()
+            .get("r.json")
+            .cloned()
+            .unwrap();
+        let parsed = crate::export::JsonExporter::from_json(&String::from_utf8(body).unwrap());
+        assert_eq!(parsed.unwrap().len(), 1);
+
+        let store = MockStore::default();
+        write_manifest(
+            &store,
+            "m.json",
+            &serde_json::json!({"noised": true}),
+            &options(1024),
+        )
+        .await
+        .unwrap();
+        assert_eq!(store.calls()[0], "create m.json.tmp application/json");
+    }
+}
@@ -0,0 +1,262 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::errors::Result;
+use crate::rules::{Provenance, SourcedRule};
+use crate::types::{AssociationRule, PatternMetrics};
+
+/// Column header written by `CsvExporter`
+pub const CSV_HEADER: &str =
+    "antecedent,consequent,support,confidence,lift,conviction,quality_score";
+
+/// Configuration for CSV export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvConfig {
+    /// Separator used to join multi-item antecedents/consequents in one cell
+    pub item_separator: String,
+}
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            item_separator: "; ".to_string(),
+        }
+    }
+}
+
+impl CsvConfig {
+    /// Set the separator for multi-item sets
+    pub fn with_item_separator(mut self, separator: impl Into<String>) -> Self {
+        self.item_separator = separator.into();
+        self
+    }
+}
+
+/// Export association rules to CSV for spreadsheet review
+///
+/// Fields containing commas, quotes or line breaks are quoted per RFC 4180.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::export::{CsvConfig, CsvExporter};
+/// # let rules = Vec::new();
+///
+/// let csv = CsvExporter::to_csv(&rules);
+/// assert!(csv.starts_with("antecedent,consequent,support"));
+///
+/// // Stream straight to a file
+/// let config = CsvConfig::default().with_item_separator(" | ");
+/// # let path = std::env::temp_dir().join("rust_rule_miner_csv_doc.csv");
+/// CsvExporter::write_csv(&rules, &path, &config)?;
+/// # std::fs::remove_file(&path).ok();
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+pub struct CsvExporter;
+
+impl CsvExporter {
+    /// Export rules with default config
+    pub fn to_csv(rules: &[AssociationRule]) -> String {
+        Self::to_csv_with_config(rules, &CsvConfig::default())
+    }
+
+    /// Export rules with custom config
+    pub fn to_csv_with_config(rules: &[AssociationRule], config: &CsvConfig) -> String {
+        let mut buffer = Vec::new();
+        Self::write_to(&mut buffer, rules, config).expect("writing to a Vec cannot fail");
+        String::from_utf8(buffer).expect("CSV output is valid UTF-8")
+    }
+
+    /// Write rules to a file without building the whole CSV in memory
+    pub fn write_csv<P: AsRef<Path>>(
+        rules: &[AssociationRule],
+        path: P,
+        config: &CsvConfig,
+    ) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        Self::write_to(&mut writer, rules, config)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write rules as CSV to any writer
+    pub fn write_to<W: Write>(
+        writer: &mut W,
+        rules: &[AssociationRule],
+        config: &CsvConfig,
+    ) -> Result<()> {
+        writeln!(writer, "{}", CSV_HEADER)?;
+
+        for rule in rules {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{}",
+                escape_field(&rule.antecedent.join(&config.item_separator)),
+                escape_field(&rule.consequent.join(&config.item_separator)),
+                rule.metrics.support,
+                rule.metrics.confidence,
+                rule.metrics.lift,
+                rule.metrics
+                    .conviction
+                    .map_or_else(String::new, |c| c.to_string()),
+                rule.quality_score()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CsvExporter {
+    /// Export `RuleSet::intersection` or `union` output with both sets'
+    /// metrics side by side
+    ///
+    /// Each side gets support, confidence and lift columns prefixed with its
+    /// label, e.g. `web_confidence,store_confidence`; a `source` column
+    /// names the set holding the rule, or `both`. Metrics of a set that
+    /// lacks the rule are blank.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::export::{CsvConfig, CsvExporter};
+    /// use rust_rule_miner::RuleSet;
+    /// # let (web, store) = (RuleSet::default(), RuleSet::default());
+    ///
+    /// let common = web.intersection(&store);
+    /// let csv = CsvExporter::sourced_to_csv(&common, "web", "store", &CsvConfig::default());
+    /// assert!(csv.starts_with("antecedent,consequent,source,web_support,"));
+    /// ```
+    pub fn sourced_to_csv(
+        rules: &[SourcedRule],
+        left_label: &str,
+        right_label: &str,
+        config: &CsvConfig,
+    ) -> String {
+        let mut csv = String::from("antecedent,consequent,source");
+        for label in [left_label, right_label] {
+            for metric in ["support", "confidence", "lift"] {
+                csv.push(',');
+                csv.push_str(&escape_field(&format!("{}_{}", label, metric)));
+            }
+        }
+        csv.push('\n');
+
+        for rule in rules {
+            let source = match rule.provenance() {
+                Provenance::Left => left_label,
+                Provenance::Right => right_label,
+                Provenance::Both => "both",
+            };
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                escape_field(&rule.antecedent.join(&config.item_separator)),
+                escape_field(&rule.consequent.join(&config.item_separator)),
+                escape_field(source),
+                metric_fields(rule.left.as_ref()),
+                metric_fields(rule.right.as_ref())
+            ));
+        }
+        csv
+    }
+}
+
+/// `support,confidence,lift`, blank when the set lacks the rule
+fn metric_fields(metrics: Option<&PatternMetrics>) -> String {
+    metrics.map_or_else(
+        || ",,".to_string(),
+        |m| format!("{},{},{}", m.support, m.confidence, m.lift),
+    )
+}
+
+/// Quote a field if it contains a delimiter, quote or line break
+pub(crate) fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+    use excelstream::CsvReader;
+
+    fn tricky_rules() -> Vec<AssociationRule> {
+        vec![
+            test_rule(&["Cable, USB-C", "Hub"], &["Monitor 27\""], 0.75, 1.5),
+            test_rule(&["Laptop"], &["Mouse"], 0.75, 1.5),
+        ]
+    }
+
+    #[test]
+    fn test_quoting() {
+        let csv = CsvExporter::to_csv(&tricky_rules());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("\"Cable, USB-C; Hub\",\"Monitor 27\"\"\","));
+        assert!(lines[2].starts_with("Laptop,Mouse,0.2,0.75,1.5,2,"));
+    }
+
+    #[test]
+    fn test_missing_conviction_is_blank() {
+        let mut rule = test_rule(&["Laptop"], &["Mouse"], 0.75, 1.5);
+        rule.metrics.conviction = None;
+        let csv = CsvExporter::to_csv(&[rule]);
+        assert!(csv
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("Laptop,Mouse,0.2,0.75,1.5,,"));
+    }
+
+    #[test]
+    fn test_write_csv_parses_back() {
+        let path = std::env::temp_dir().join(format!(
+            "rust_rule_miner_csv_test_{}.csv",
+            std::process::id()
+        ));
+        let config = CsvConfig::default().with_item_separator(" | ");
+        CsvExporter::write_csv(&tricky_rules(), &path, &config).unwrap();
+
+        let mut reader = CsvReader::open(&path).unwrap();
+        let rows: Vec<Vec<String>> = reader
+            .rows()
+            .map(|row| row.unwrap().into_iter().map(|v| v.to_string()).collect())
+            .collect();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].len(), 7);
+        assert_eq!(rows[1][0], "Cable, USB-C | Hub");
+        assert_eq!(rows[1][1], "Monitor 27\"");
+        assert_eq!(rows[1][3], "0.75");
+        assert_eq!(rows[2][0], "Laptop");
+    }
+
+    #[test]
+    fn test_sourced_csv() {
+        use crate::rules::RuleSet;
+
+        let web = RuleSet::new(tricky_rules());
+        let mut in_store = test_rule(&["Laptop"], &["Mouse"], 0.75, 1.5);
+        in_store.metrics.confidence = 0.5;
+        let store = RuleSet::new(vec![in_store, test_rule(&["Phone"], &["Case"], 0.75, 1.5)]);
+
+        let csv =
+            CsvExporter::sourced_to_csv(&web.union(&store), "web", "store", &CsvConfig::default());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "antecedent,consequent,source,web_support,web_confidence,web_lift,\
+             store_support,store_confidence,store_lift"
+        );
+        assert!(
+            lines[1].starts_with("\"Cable, USB-C; Hub\",\"Monitor 27\"\"\",web,0.2,0.75,1.5,,,")
+        );
+        assert_eq!(lines[2], "Laptop,Mouse,both,0.2,0.75,1.5,0.2,0.5,1.5");
+        assert_eq!(lines[3], "Phone,Case,store,,,,0.2,0.75,1.5");
+    }
+}
@@ -0,0 +1,291 @@
+//! Delta exports against a previously deployed rule set
+//!
+//! Nightly rule sets are mostly identical to the deployed one. `RuleDelta`
+//! splits freshly mined rules into unchanged, changed and new groups so a
+//! review queue only sees what moved; each group can be written as its own
+//! file in any `ExportFormat`, next to a `manifest.json` that records the
+//! baseline's hash.
+//!
+//! Rules are matched by `RuleKey` (item order does not matter). A matched rule
+//! counts as changed once its confidence, support or lift moves by more than
+//! `DeltaConfig::threshold`; smaller movements are treated as noise.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::deployment::{plan_hash, RuleChange, RuleKey};
+use crate::errors::Result;
+use crate::export::ExportFormat;
+use crate::rules::RuleSet;
+use crate::types::{AssociationRule, PatternMetrics};
+
+/// Options for `RuleDelta::split`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltaConfig {
+    /// Largest absolute metric movement still considered unchanged
+    /// Example: 0.05 = a confidence move from 0.80 to 0.84 is unchanged
+    pub threshold: f64,
+}
+
+impl Default for DeltaConfig {
+    fn default() -> Self {
+        Self { threshold: 0.01 }
+    }
+}
+
+impl DeltaConfig {
+    /// Set the metric-delta threshold for "changed"
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+/// Summary of a delta export, written as `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaManifest {
+    pub generated: DateTime<Utc>,
+    /// `deployment::plan_hash` of the baseline (None = no baseline, all rules new)
+    pub baseline_hash: Option<String>,
+    pub baseline_rules: usize,
+    pub threshold: f64,
+    pub unchanged: usize,
+    pub new: usize,
+    /// Matched rules that moved past the threshold, with before/after metrics
+    pub changed: Vec<RuleChange>,
+    /// Baseline rules missing from the new set
+    pub removed: Vec<RuleKey>,
+}
+
+/// Rules split by how they differ from a baseline
+#[derive(Debug, Clone)]
+pub struct RuleDelta {
+    pub unchanged: Vec<AssociationRule>,
+    /// New versions of changed rules
+    pub changed: Vec<AssociationRule>,
+    pub new: Vec<AssociationRule>,
+    pub manifest: DeltaManifest,
+}
+
+impl RuleDelta {
+    /// Split `rules` against `baseline`, preserving the order of `rules`
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::export::{DeltaConfig, RuleDelta};
+    /// use rust_rule_miner::RuleSet;
+    /// # let deployed = Vec::new();
+    /// # let mined = Vec::new();
+    ///
+    /// let baseline = RuleSet::new(deployed);
+    /// let delta = RuleDelta::split(&mined, Some(&baseline), &DeltaConfig::default());
+    /// assert!(delta.changed.is_empty() && delta.new.is_empty());
+    /// ```
+    pub fn split(
+        rules: &[AssociationRule],
+        baseline: Option<&RuleSet>,
+        config: &DeltaConfig,
+    ) -> Self {
+        let baseline_rules = baseline.map(RuleSet::as_slice).unwrap_or_default();
+        let by_key: HashMap<RuleKey, &AssociationRule> = baseline_rules
+            .iter()
+            .map(|rule| (RuleKey::of(rule), rule))
+            .collect();
+
+        let mut unchanged = Vec::new();
+        let mut changed = Vec::new();
+        let mut new = Vec::new();
+        let mut changes = Vec::new();
+        for rule in rules {
+            let key = RuleKey::of(rule);
+            match by_key.get(&key) {
+                None => new.push(rule.clone()),
+                Some(old) if within(&old.metrics, &rule.metrics, config.threshold) => {
+                    unchanged.push(rule.clone())
+                }
+                Some(old) => {
+                    changes.push(RuleChange {
+                        key,
+                        before: old.metrics.clone(),
+                        after: rule.metrics.clone(),
+                    });
+                    changed.push(rule.clone());
+                }
+            }
+        }
+
+        let current: HashSet<RuleKey> = rules.iter().map(RuleKey::of).collect();
+        let mut removed: Vec<RuleKey> = by_key
+            .into_keys()
+            .filter(|key| !current.contains(key))
+            .collect();
+        removed.sort();
+
+        let manifest = DeltaManifest {
+            generated: Utc::now(),
+            baseline_hash: baseline.map(|set| plan_hash(set.as_slice())),
+            baseline_rules: baseline_rules.len(),
+            threshold: config.threshold,
+            unchanged: unchanged.len(),
+            new: new.len(),
+            changed: changes,
+            removed,
+        };
+
+        Self {
+            unchanged,
+            changed,
+            new,
+            manifest,
+        }
+    }
+
+    /// Render each group in `format`, as `(file name, contents)` pairs
+    ///
+    /// Files are `unchanged.<ext>`, `changed.<ext>`, `new.<ext>` and
+    /// `manifest.json`.
+    pub fn render(&self, format: &ExportFormat) -> Result<Vec<(String, String)>> {
+        let extension = format.extension();
+        let mut files = Vec::new();
+        for (name, rules) in [
+            ("unchanged", &self.unchanged),
+            ("changed", &self.changed),
+            ("new", &self.new),
+        ] {
+            files.push((format!("{}.{}", name, extension), format.render(rules)?));
+        }
+        files.push((
+            "manifest.json".to_string(),
+            serde_json::to_string_pretty(&self.manifest)?,
+        ));
+        Ok(files)
+    }
+
+    /// Write the rendered files into `dir` (created if missing)
+    pub fn write_files<P: AsRef<Path>>(
+        &self,
+        dir: P,
+        format: &ExportFormat,
+    ) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        self.render(format)?
+            .into_iter()
+            .map(|(name, contents)| {
+                let path = dir.join(name);
+                std::fs::write(&path, contents)?;
+                Ok(path)
+            })
+            .collect()
+    }
+}
+
+fn within(before: &PatternMetrics, after: &PatternMetrics, threshold: f64) -> bool {
+    [
+        (before.confidence, after.confidence),
+        (before.support, after.support),
+        (before.lift, after.lift),
+    ]
+    .iter()
+    .all(|(b, a)| (a - b).abs() <= threshold || (a.is_infinite() && a == b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{GrlConfig, JsonExporter};
+    use crate::types::test_rule;
+
+    fn baseline() -> RuleSet {
+        RuleSet::new(vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.80, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.60, 1.5),
+            test_rule(&["Milk", "Bread"], &["Butter"], 0.70, 1.5),
+            test_rule(&["Pen"], &["Paper"], 0.90, 1.5),
+        ])
+    }
+
+    fn nightly() -> Vec<AssociationRule> {
+        vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.80, 1.5),
+            // Moved past the threshold
+            test_rule(&["Phone"], &["Case"], 0.72, 1.5),
+            // Same rule, item order differs, confidence within threshold
+            test_rule(&["Bread", "Milk"], &["Butter"], 0.73, 1.5),
+            test_rule(&["Tent"], &["Stove"], 0.65, 1.5),
+        ]
+    }
+
+    #[test]
+    fn test_split_against_baseline() {
+        let baseline = baseline();
+        let config = DeltaConfig::default().with_threshold(0.05);
+        let delta = RuleDelta::split(&nightly(), Some(&baseline), &config);
+
+        assert_eq!(delta.unchanged.len(), 2);
+        assert_eq!(delta.changed.len(), 1);
+        assert_eq!(delta.changed[0].antecedent, vec!["Phone"]);
+        assert_eq!(delta.new.len(), 1);
+        assert_eq!(delta.new[0].antecedent, vec!["Tent"]);
+
+        let manifest = &delta.manifest;
+        assert_eq!(
+            manifest.baseline_hash.as_deref(),
+            Some(plan_hash(baseline.as_slice()).as_str())
+        );
+        assert_eq!(manifest.baseline_rules, 4);
+        assert!((manifest.changed[0].confidence_delta() - 0.12).abs() < 1e-9);
+        assert_eq!(manifest.removed.len(), 1);
+        assert_eq!(manifest.removed[0].to_string(), "Pen => Paper");
+    }
+
+    #[test]
+    fn test_without_baseline_everything_is_new() {
+        let delta = RuleDelta::split(&nightly(), None, &DeltaConfig::default());
+        assert_eq!(delta.new.len(), 4);
+        assert!(delta.unchanged.is_empty() && delta.changed.is_empty());
+        assert_eq!(delta.manifest.baseline_hash, None);
+    }
+
+    #[test]
+    fn test_write_files() {
+        let baseline = baseline();
+        let delta = RuleDelta::split(
+            &nightly(),
+            Some(&baseline),
+            &DeltaConfig::default().with_threshold(0.05),
+        );
+        let dir =
+            std::env::temp_dir().join(format!("rust_rule_miner_delta_{}", std::process::id()));
+
+        let paths = delta
+            .write_files(&dir, &ExportFormat::Grl(GrlConfig::default()))
+            .unwrap();
+        let names: Vec<String> = paths
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["unchanged.grl", "changed.grl", "new.grl", "manifest.json"]
+        );
+
+        let changed = std::fs::read_to_string(dir.join("changed.grl")).unwrap();
+        assert!(changed.contains("Phone") && !changed.contains("Laptop"));
+        let manifest: DeltaManifest =
+            serde_json::from_str(&std::fs::read_to_string(dir.join("manifest.json")).unwrap())
+                .unwrap();
+        assert_eq!(manifest.baseline_hash, delta.manifest.baseline_hash);
+
+        delta.write_files(&dir, &ExportFormat::Json).unwrap();
+        let new = std::fs::read_to_string(dir.join("new.json")).unwrap();
+        assert_eq!(
+            JsonExporter::from_json(&new).unwrap()[0].antecedent,
+            vec!["Tent"]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
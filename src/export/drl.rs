@@ -0,0 +1,216 @@
+use crate::types::AssociationRule;
+use chrono::Utc;
+
+/// Configuration for Drools DRL export
+///
+/// Mirrors `GrlConfig`'s input/output fields, but as Java fact classes: the
+/// input fact holds a collection of items, and each recommendation is
+/// inserted as a new output fact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrlConfig {
+    /// DRL package declaration (e.g., "com.example.rules")
+    pub package: String,
+    /// Fully qualified input fact class (e.g., "com.example.Cart")
+    pub input_class: String,
+    /// Collection field on the input fact holding the items
+    pub input_field: String,
+    /// Fully qualified output fact class (e.g., "com.example.Recommendation")
+    pub output_class: String,
+    /// String field on the output fact holding the recommended item
+    pub output_field: String,
+}
+
+impl Default for DrlConfig {
+    fn default() -> Self {
+        Self {
+            package: "rules.mined".to_string(),
+            input_class: "com.example.Cart".to_string(),
+            input_field: "items".to_string(),
+            output_class: "com.example.Recommendation".to_string(),
+            output_field: "item".to_string(),
+        }
+    }
+}
+
+impl DrlConfig {
+    /// Create a config for the given fact classes and fields
+    pub fn new(
+        input_class: impl Into<String>,
+        input_field: impl Into<String>,
+        output_class: impl Into<String>,
+        output_field: impl Into<String>,
+    ) -> Self {
+        Self {
+            input_class: input_class.into(),
+            input_field: input_field.into(),
+            output_class: output_class.into(),
+            output_field: output_field.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the package declaration
+    pub fn with_package(mut self, package: impl Into<String>) -> Self {
+        self.package = package.into();
+        self
+    }
+}
+
+/// Export association rules to Drools `.drl` format
+///
+/// Each rule matches an input fact containing every antecedent item and
+/// inserts one output fact per consequent item not already recommended.
+/// The output class needs a `(String item, double confidence)` constructor.
+/// Salience is the confidence as a percentage.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::export::{DrlConfig, DrlExporter};
+/// # let rules = Vec::new();
+///
+/// let config = DrlConfig::default().with_package("com.shop.rules");
+/// let drl = DrlExporter::to_drl_with_config(&rules, &config);
+/// assert!(drl.starts_with("package com.shop.rules;"));
+/// ```
+pub struct DrlExporter;
+
+impl DrlExporter {
+    /// Convert association rules to DRL (uses default config)
+    pub fn to_drl(rules: &[AssociationRule]) -> String {
+        Self::to_drl_with_config(rules, &DrlConfig::default())
+    }
+
+    /// Convert association rules to DRL with custom configuration
+    pub fn to_drl_with_config(rules: &[AssociationRule], config: &DrlConfig) -> String {
+        let mut drl = String::new();
+
+        drl.push_str(&format!("package {};\n\n", config.package));
+        drl.push_str(&format!("import {};\n", config.input_class));
+        if config.output_class != config.input_class {
+            drl.push_str(&format!("import {};\n", config.output_class));
+        }
+        drl.push('\n');
+        drl.push_str("// Auto-generated rules from pattern mining\n");
+        drl.push_str(&format!("// Generated: {}\n", Utc::now()));
+        drl.push_str(&format!("// Total rules: {}\n\n", rules.len()));
+
+        for (idx, rule) in rules.iter().enumerate() {
+            drl.push_str(&Self::rule_to_drl(rule, idx, config));
+            drl.push('\n');
+        }
+
+        drl
+    }
+
+    fn rule_to_drl(rule: &AssociationRule, idx: usize, config: &DrlConfig) -> String {
+        let input = simple_class_name(&config.input_class);
+        let output = simple_class_name(&config.output_class);
+        let name = format!(
+            "Mined_{}_{}_Implies_{}",
+            idx,
+            rule.antecedent.join("_"),
+            rule.consequent.join("_")
+        );
+
+        let conditions = rule
+            .antecedent
+            .iter()
+            .map(|item| format!("{} contains {}", config.input_field, quote(item)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut when = vec![format!("$input : {}( {} )", input, conditions)];
+        let mut then = Vec::new();
+        for item in &rule.consequent {
+            when.push(format!(
+                "not {}( {} == {} )",
+                output,
+                config.output_field,
+                quote(item)
+            ));
+            then.push(format!(
+                "insert( new {}( {}, {:.4} ) );",
+                output,
+                quote(item),
+                rule.metrics.confidence
+            ));
+        }
+
+        format!(
+            "// Confidence: {:.1}% | Support: {:.1}% | Lift: {:.2}\n\
+             rule {}\n    salience {}\n    no-loop true\nwhen\n    {}\nthen\n    {}\nend\n",
+            rule.metrics.confidence * 100.0,
+            rule.metrics.support * 100.0,
+            rule.metrics.lift,
+            quote(&name),
+            (rule.metrics.confidence * 100.0) as i32,
+            when.join("\n    "),
+            then.join("\n    ")
+        )
+    }
+}
+
+/// Last segment of a fully qualified class name
+fn simple_class_name(class: &str) -> &str {
+    class.rsplit('.').next().unwrap_or(class)
+}
+
+/// Quote a DRL (Java) string literal
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+
+    #[test]
+    fn test_drl_structure() {
+        let config = DrlConfig::new("com.shop.Basket", "products", "com.shop.Suggestion", "sku")
+            .with_package("com.shop.rules");
+        let drl = DrlExporter::to_drl_with_config(
+            &[test_rule(&["Laptop", "Mouse"], &["Hub"], 0.857, 1.43)],
+            &config,
+        );
+
+        assert!(drl.starts_with("package com.shop.rules;\n"));
+        assert!(drl.contains("import com.shop.Basket;\nimport com.shop.Suggestion;\n"));
+        assert!(drl.contains("rule \"Mined_0_Laptop_Mouse_Implies_Hub\"\n    salience 85\n"));
+        assert!(drl.contains(
+            "when\n    $input : Basket( products contains \"Laptop\", products contains \"Mouse\" )\n"
+        ));
+        assert!(drl.contains("    not Suggestion( sku == \"Hub\" )\nthen\n"));
+        assert!(drl.contains("    insert( new Suggestion( \"Hub\", 0.8570 ) );\nend\n"));
+        assert_eq!(drl.matches("\nrule ").count(), 1);
+        assert_eq!(drl.matches("\nend\n").count(), 1);
+    }
+
+    #[test]
+    fn test_drl_escaping() {
+        let drl =
+            DrlExporter::to_drl(&[test_rule(&["Monitor 27\""], &["C:\\Drivers"], 0.857, 1.43)]);
+
+        assert!(drl.contains("Cart( items contains \"Monitor 27\\\"\" )"));
+        assert!(drl.contains("insert( new Recommendation( \"C:\\\\Drivers\", 0.8570 ) );"));
+        assert!(drl.contains("rule \"Mined_0_Monitor 27\\\"_Implies_C:\\\\Drivers\""));
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+    }
+}
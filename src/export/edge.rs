@@ -0,0 +1,107 @@
+use crate::types::AssociationRule;
+
+/// Export association rules as `const` Rust data for `rust-rule-miner-edge`
+///
+/// The output is a Rust module body declaring one `&[Rule]` constant, ready
+/// to `include!` in firmware that depends only on the edge crate. Rules keep
+/// the given order; pass them best-first, since `edge::best` breaks
+/// confidence ties by position.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::export::EdgeExporter;
+/// # let rules = Vec::new();
+///
+/// let source = EdgeExporter::to_rust(&rules, "RULES");
+/// assert!(source.contains("pub const RULES: &[Rule] = &["));
+/// ```
+pub struct EdgeExporter;
+
+impl EdgeExporter {
+    /// Rust source declaring `pub const <const_name>: &[Rule]`
+    pub fn to_rust(rules: &[AssociationRule], const_name: &str) -> String {
+        let mut source = format!(
+            "// Generated by rust-rule-miner: {} rules\n\
+             use rust_rule_miner_edge::Rule;\n\n\
+             pub const {}: &[Rule] = &[\n",
+            rules.len(),
+            const_name
+        );
+        for rule in rules {
+            source.push_str(&format!(
+                "    Rule::new({}, {}, {:?}),\n",
+                str_slice(&rule.antecedent),
+                str_slice(&rule.consequent),
+                rule.metrics.confidence
+            ));
+        }
+        source.push_str("];\n");
+        source
+    }
+}
+
+/// `&["a", "b"]` with Rust string escaping
+fn str_slice(items: &[String]) -> String {
+    let quoted: Vec<String> = items.iter().map(|item| format!("{:?}", item)).collect();
+    format!("&[{}]", quoted.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::{self, Rule};
+    use crate::types::test_rule;
+
+    fn rules() -> Vec<AssociationRule> {
+        vec![
+            test_rule(&["Bread", "Butter"], &["Jam"], 0.9, 1.5),
+            test_rule(&["Bread"], &["Milk"], 0.75, 1.5),
+        ]
+    }
+
+    // What `to_rust(&rules(), "RULES")` declares
+    const RULES: &[Rule] = &[
+        Rule::new(&["Bread", "Butter"], &["Jam"], 0.9),
+        Rule::new(&["Bread"], &["Milk"], 0.75),
+    ];
+
+    #[test]
+    fn test_generated_source() {
+        let source = EdgeExporter::to_rust(&rules(), "RULES");
+        assert_eq!(
+            source,
+            "// Generated by rust-rule-miner: 2 rules\n\
+             use rust_rule_miner_edge::Rule;\n\n\
+             pub const RULES: &[Rule] = &[\n    \
+             Rule::new(&[\"Bread\", \"Butter\"], &[\"Jam\"], 0.9),\n    \
+             Rule::new(&[\"Bread\"], &[\"Milk\"], 0.75),\n\
+             ];\n"
+        );
+    }
+
+    #[test]
+    fn test_escaping() {
+        let source = EdgeExporter::to_rust(
+            &[test_rule(&["Monitor 27\""], &["C:\\Drivers"], 1.0, 1.5)],
+            "R",
+        );
+        assert!(source.contains(r#"Rule::new(&["Monitor 27\""], &["C:\\Drivers"], 1.0),"#));
+    }
+
+    #[test]
+    fn test_generated_rules_match_like_full_rules() {
+        let full = rules();
+        for basket in [
+            vec!["Bread"],
+            vec!["Butter", "Bread"],
+            vec!["Bread", "Milk"],
+            vec!["Jam"],
+        ] {
+            let owned: Vec<String> = basket.iter().map(|s| s.to_string()).collect();
+            for (edge_rule, full_rule) in RULES.iter().zip(&full) {
+                assert_eq!(edge_rule.matches(&basket), full_rule.matches(&owned));
+            }
+        }
+        assert_eq!(edge::best(RULES, &["Butter", "Bread"]), Some(&RULES[0]));
+    }
+}
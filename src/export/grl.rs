@@ -1,5 +1,12 @@
-use crate::types::AssociationRule;
+use super::smoke::{SmokeCheck, VocabularyMismatch};
+use crate::errors::{MiningError, Result};
+use crate::mining::RuleEvidence;
+use crate::types::{AssociationRule, NegativeRule};
 use chrono::Utc;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Rule template types for different use cases
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +29,68 @@ pub enum RuleTemplate {
     InventoryAlert,
 }
 
+/// Renders the `when` and `then` bodies of an exported rule
+///
+/// Implement this to plug a custom action (or condition) body into
+/// `GrlExporter` via `GrlConfig::with_renderer`. The rule header, metric
+/// comments and trailing log action are still generated by the exporter.
+/// Multiple conditions or actions are joined with `" &&\n        "` and
+/// `";\n        "` respectively; the final `;` is added by the exporter.
+pub trait GrlRuleRenderer: fmt::Debug + Send + Sync {
+    /// Body of the `when` block
+    fn render_conditions(&self, rule: &AssociationRule, config: &GrlConfig) -> String;
+
+    /// Body of the `then` block, before the log action
+    fn render_actions(&self, rule: &AssociationRule, config: &GrlConfig) -> String;
+}
+
+impl GrlRuleRenderer for RuleTemplate {
+    fn render_conditions(&self, rule: &AssociationRule, config: &GrlConfig) -> String {
+        GrlExporter::generate_conditions_with_negation(&rule.antecedent, &rule.consequent, config)
+    }
+
+    fn render_actions(&self, rule: &AssociationRule, config: &GrlConfig) -> String {
+        GrlExporter::generate_actions(&rule.consequent, *self, config)
+    }
+}
+
+/// Target GRL dialect for generated rules
+///
+/// | Template       | Legacy | V1 | Rete |
+/// |----------------|--------|----|------|
+/// | Recommendation | ✓      | ✓  | ✓    |
+/// | Classification | ✓      | ✓  | ✓    |
+/// | Scoring        | ✓      | ✓  | ✓    |
+/// | Alert          | ✓      | ✓  |      |
+/// | Validation     | ✓      | ✓  |      |
+/// | MultiAction    | ✓      | ✓  |      |
+/// | FraudDetection | ✓      | ✓  |      |
+/// | InventoryAlert | ✓      | ✓  |      |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrlDialect {
+    /// Original output, unchanged from earlier releases
+    #[default]
+    Legacy,
+    /// rust-rule-engine 1.x native parser: no commas/parentheses inside string
+    /// arguments, assignments instead of `+=` on numbers, no inline `if` blocks
+    V1,
+    /// GrlReteLoader: only field assignments and array appends, no custom calls
+    Rete,
+}
+
+impl GrlDialect {
+    /// Check whether a template can be rendered in this dialect
+    pub fn supports(&self, template: RuleTemplate) -> bool {
+        match self {
+            GrlDialect::Legacy | GrlDialect::V1 => true,
+            GrlDialect::Rete => matches!(
+                template,
+                RuleTemplate::Recommendation | RuleTemplate::Classification | RuleTemplate::Scoring
+            ),
+        }
+    }
+}
+
 /// Configuration for GRL export
 #[derive(Debug, Clone)]
 pub struct GrlConfig {
@@ -33,6 +102,13 @@ pub struct GrlConfig {
     pub template: RuleTemplate,
     /// Custom action prefix (for MultiAction template)
     pub action_prefix: Option<String>,
+    /// Maximum item length used in rule names and comments (None = unlimited)
+    /// Longer items are truncated with a hash suffix; conditions keep the full item.
+    pub max_item_length: Option<usize>,
+    /// GRL dialect to generate
+    pub dialect: GrlDialect,
+    /// Custom renderer used instead of `template` (None = use the template)
+    pub renderer: Option<Arc<dyn GrlRuleRenderer>>,
 }
 
 impl Default for GrlConfig {
@@ -42,6 +118,9 @@ impl Default for GrlConfig {
             output_field: "Recommendation.items".to_string(),
             template: RuleTemplate::Recommendation,
             action_prefix: None,
+            max_item_length: None,
+            dialect: GrlDialect::Legacy,
+            renderer: None,
         }
     }
 }
@@ -54,6 +133,9 @@ impl GrlConfig {
             output_field: output_field.into(),
             template: RuleTemplate::Recommendation,
             action_prefix: None,
+            max_item_length: None,
+            dialect: GrlDialect::Legacy,
+            renderer: None,
         }
     }
 
@@ -69,6 +151,46 @@ impl GrlConfig {
         self
     }
 
+    /// Set the target GRL dialect
+    pub fn with_dialect(mut self, dialect: GrlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Render rule bodies with a custom renderer instead of the template
+    pub fn with_renderer(mut self, renderer: impl GrlRuleRenderer + 'static) -> Self {
+        self.renderer = Some(Arc::new(renderer));
+        self
+    }
+
+    /// Renderer in effect: the custom renderer if set, otherwise the template
+    pub fn renderer(&self) -> &dyn GrlRuleRenderer {
+        match &self.renderer {
+            Some(renderer) => renderer.as_ref(),
+            None => &self.template,
+        }
+    }
+
+    /// Check that the template is supported by the target dialect
+    ///
+    /// Custom renderers are responsible for their own dialect compatibility.
+    pub fn validate(&self) -> Result<()> {
+        if self.renderer.is_some() || self.dialect.supports(self.template) {
+            Ok(())
+        } else {
+            Err(MiningError::ExportFailed(format!(
+                "Template {:?} is not supported by the {:?} GRL dialect",
+                self.template, self.dialect
+            )))
+        }
+    }
+
+    /// Set maximum item length for rule names and comments
+    pub fn with_max_item_length(mut self, max_len: usize) -> Self {
+        self.max_item_length = Some(max_len);
+        self
+    }
+
     /// Create config for shopping cart recommendations
     pub fn shopping_cart() -> Self {
         Self::default()
@@ -81,6 +203,9 @@ impl GrlConfig {
             output_field: "AlertSystem".to_string(),
             template: RuleTemplate::Alert,
             action_prefix: None,
+            max_item_length: None,
+            dialect: GrlDialect::Legacy,
+            renderer: None,
         }
     }
 
@@ -94,6 +219,9 @@ impl GrlConfig {
             output_field: category_field.into(),
             template: RuleTemplate::Classification,
             action_prefix: None,
+            max_item_length: None,
+            dialect: GrlDialect::Legacy,
+            renderer: None,
         }
     }
 
@@ -104,6 +232,9 @@ impl GrlConfig {
             output_field: score_field.into(),
             template: RuleTemplate::Scoring,
             action_prefix: None,
+            max_item_length: None,
+            dialect: GrlDialect::Legacy,
+            renderer: None,
         }
     }
 
@@ -114,6 +245,9 @@ impl GrlConfig {
             output_field: "FraudScore".to_string(),
             template: RuleTemplate::FraudDetection,
             action_prefix: None,
+            max_item_length: None,
+            dialect: GrlDialect::Legacy,
+            renderer: None,
         }
     }
 
@@ -124,6 +258,9 @@ impl GrlConfig {
             output_field: "InventoryAlert".to_string(),
             template: RuleTemplate::InventoryAlert,
             action_prefix: None,
+            max_item_length: None,
+            dialect: GrlDialect::Legacy,
+            renderer: None,
         }
     }
 
@@ -134,6 +271,9 @@ impl GrlConfig {
             output_field: "Analysis.recommendations".to_string(),
             template: RuleTemplate::Recommendation,
             action_prefix: None,
+            max_item_length: None,
+            dialect: GrlDialect::Legacy,
+            renderer: None,
         }
     }
 
@@ -153,7 +293,276 @@ impl GrlExporter {
     }
 
     /// Convert association rules to GRL code with custom configuration
+    ///
+    /// Templates unsupported by the configured dialect fall back to v1 syntax;
+    /// use `try_to_grl_with_config` to reject them instead.
     pub fn to_grl_with_config(rules: &[AssociationRule], config: &GrlConfig) -> String {
+        Self::to_grl_with_report(rules, config).0
+    }
+
+    /// Convert association rules to GRL code, failing fast if the template is
+    /// not supported by the configured dialect
+    pub fn try_to_grl_with_config(rules: &[AssociationRule], config: &GrlConfig) -> Result<String> {
+        config.validate()?;
+        Ok(Self::to_grl_with_config(rules, config))
+    }
+
+    /// Convert association rules to GRL code and report export warnings
+    ///
+    /// The report lists rules whose names were shortened because an item
+    /// exceeded `GrlConfig::max_item_length`.
+    pub fn to_grl_with_report(
+        rules: &[AssociationRule],
+        config: &GrlConfig,
+    ) -> (String, ExportReport) {
+        Self::render(rules, config, None, None)
+    }
+
+    /// Convert association rules to GRL code, listing each rule's supporting
+    /// transaction IDs in an `// Evidence:` comment above it
+    ///
+    /// `evidence` comes from `RuleMiner::mine_association_rules_with_evidence`;
+    /// rules it has no IDs for get no comment.
+    pub fn to_grl_with_evidence(
+        rules: &[AssociationRule],
+        config: &GrlConfig,
+        evidence: &RuleEvidence,
+    ) -> String {
+        Self::render(rules, config, None, Some(evidence)).0
+    }
+
+    /// Convert association rules to GRL code, smoke-checking each rule
+    /// against a sample of recent baskets
+    ///
+    /// Rules whose antecedent never appears in the sample are listed in
+    /// `ExportReport::dead_rules` and either annotated with a `// DEAD RULE`
+    /// comment or, with `SmokeCheck::exclude_dead`, left out. Items that only
+    /// match the sample after normalization are reported as vocabulary
+    /// mismatches.
+    pub fn to_grl_with_smoke_check(
+        rules: &[AssociationRule],
+        config: &GrlConfig,
+        check: &SmokeCheck,
+    ) -> (String, ExportReport) {
+        let live: Vec<bool> = rules
+            .iter()
+            .map(|rule| check.sample.is_live(rule))
+            .collect();
+
+        let (grl, mut report) = if check.exclude_dead {
+            let kept: Vec<AssociationRule> = rules
+                .iter()
+                .zip(&live)
+                .filter(|(_, live)| **live)
+                .map(|(rule, _)| rule.clone())
+                .collect();
+            Self::render(&kept, config, None, None)
+        } else {
+            Self::render(rules, config, Some(&live), None)
+        };
+
+        for (idx, rule) in rules.iter().enumerate().filter(|(idx, _)| !live[*idx]) {
+            let rule_name = Self::generate_rule_name(rule, idx, config);
+            report.warnings.push(format!(
+                "Rule '{}' is dead: antecedent not seen in any of {} sample baskets{}",
+                rule_name,
+                check.sample.len(),
+                if check.exclude_dead { "; excluded" } else { "" }
+            ));
+            report.dead_rules.push(rule_name);
+        }
+        if check.exclude_dead {
+            report.excluded_rules = report.dead_rules.len();
+        }
+
+        report.vocabulary_mismatches = check.sample.vocabulary_mismatches(rules);
+        for mismatch in &report.vocabulary_mismatches {
+            report.warnings.push(format!(
+                "Item '{}' not in sample baskets; did you mean '{}'?",
+                mismatch.item, mismatch.suggestion
+            ));
+        }
+
+        (grl, report)
+    }
+
+    /// Convert association rules to one GRL document per group
+    ///
+    /// `group_fn` names the group of each rule (e.g. the service that owns
+    /// the consequent's category). Each document has its own header, rule
+    /// count and numbering, and keeps the rules in their input order.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::export::{GrlConfig, GrlExporter};
+    /// use rust_rule_miner::{AssociationRule, PatternMetrics};
+    ///
+    /// let rule = |a: &str, c: &str| AssociationRule {
+    ///     antecedent: vec![a.to_string()],
+    ///     consequent: vec![c.to_string()],
+    ///     metrics: PatternMetrics::basic(0.8, 0.3, 1.5),
+    /// };
+    /// let rules = vec![rule("Laptop", "stock:Charger"), rule("Laptop", "shop:Mouse")];
+    ///
+    /// let docs = GrlExporter::to_grl_grouped(&rules, &GrlConfig::default(), |rule| {
+    ///     rule.consequent[0].split(':').next().unwrap_or_default().to_string()
+    /// });
+    /// assert_eq!(docs.len(), 2);
+    /// assert!(docs["stock"].contains("stock:Charger") && !docs["stock"].contains("shop:Mouse"));
+    /// ```
+    pub fn to_grl_grouped<F>(
+        rules: &[AssociationRule],
+        config: &GrlConfig,
+        group_fn: F,
+    ) -> HashMap<String, String>
+    where
+        F: Fn(&AssociationRule) -> String,
+    {
+        let mut groups: HashMap<String, Vec<AssociationRule>> = HashMap::new();
+        for rule in rules {
+            groups.entry(group_fn(rule)).or_default().push(rule.clone());
+        }
+
+        groups
+            .into_iter()
+            .map(|(group, rules)| {
+                let grl = Self::to_grl_with_config(&rules, config);
+                (group, grl)
+            })
+            .collect()
+    }
+
+    /// Write one `<group>.grl` file per group into `dir` (created if missing)
+    ///
+    /// Characters other than ASCII letters, digits, `-` and `_` in group
+    /// names become `_`; fails if two groups end up with the same file name.
+    /// Returns the written paths sorted by file name.
+    pub fn write_grouped<P, F>(
+        rules: &[AssociationRule],
+        config: &GrlConfig,
+        group_fn: F,
+        dir: P,
+    ) -> Result<Vec<PathBuf>>
+    where
+        P: AsRef<Path>,
+        F: Fn(&AssociationRule) -> String,
+    {
+        let mut files: Vec<(String, String, String)> =
+            Self::to_grl_grouped(rules, config, group_fn)
+                .into_iter()
+                .map(|(group, grl)| (group_file_name(&group), group, grl))
+                .collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        if let Some(pair) = files.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(MiningError::ExportFailed(format!(
+                "groups '{}' and '{}' both map to file '{}'",
+                pair[0].1, pair[1].1, pair[0].0
+            )));
+        }
+
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        files
+            .into_iter()
+            .map(|(name, _, grl)| {
+                let path = dir.join(name);
+                std::fs::write(&path, grl)?;
+                Ok(path)
+            })
+            .collect()
+    }
+
+    /// Convert negative rules (A => NOT B) to GRL code
+    ///
+    /// A rule fires when the antecedent is in `input_field` and the absent
+    /// item is not, and appends the absent item to `output_field`; point it
+    /// at a list such as `Exclusions.items` to suppress unlikely items. The
+    /// template and custom renderer do not apply.
+    pub fn negative_rules_to_grl(rules: &[NegativeRule], config: &GrlConfig) -> String {
+        let mut grl = String::new();
+        grl.push_str("// Auto-generated negative rules from pattern mining\n");
+        grl.push_str(&format!("// Generated: {}\n", Utc::now()));
+        grl.push_str(&format!("// Total rules: {}\n", rules.len()));
+        grl.push_str(&format!("// Input field: {}\n", config.input_field));
+        grl.push_str(&format!("// Output field: {}\n", config.output_field));
+        grl.push('\n');
+
+        for (idx, rule) in rules.iter().enumerate() {
+            grl.push_str(&Self::negative_rule_to_grl(rule, idx, config));
+            grl.push('\n');
+        }
+        grl
+    }
+
+    fn negative_rule_to_grl(rule: &NegativeRule, idx: usize, config: &GrlConfig) -> String {
+        let antecedent = Self::display_items(&rule.antecedent, config);
+        let absent = Self::display_item(&rule.absent, config);
+        let rule_name = format!(
+            "Mined_Negative_{}_{}_Excludes_{}",
+            idx,
+            antecedent
+                .iter()
+                .map(|s| s.replace(' ', "_"))
+                .collect::<Vec<_>>()
+                .join("_"),
+            absent.replace(' ', "_")
+        );
+
+        let mut conditions: Vec<String> = rule
+            .antecedent
+            .iter()
+            .map(|item| format!("{} contains \"{}\"", config.input_field, item))
+            .collect();
+        conditions.push(format!(
+            "!({} contains \"{}\")",
+            config.input_field, rule.absent
+        ));
+        conditions.push(format!(
+            "!({} contains \"{}\")",
+            config.output_field, rule.absent
+        ));
+
+        format!(
+            r#"// Negative rule #{}: {} => NOT {}
+// Confidence: {:.1}% | Support: {:.1}% | Lift: {:.2}
+// Interpretation: When {} present, {} is absent {:.1}% of the time
+rule "{}" salience {} no-loop {{
+    when
+        {}
+    then
+        {} += "{}";
+{}}}
+"#,
+            idx + 1,
+            antecedent.join(", "),
+            absent,
+            rule.metrics.confidence * 100.0,
+            rule.metrics.support * 100.0,
+            rule.metrics.lift,
+            antecedent.join(", "),
+            absent,
+            rule.metrics.confidence * 100.0,
+            rule_name,
+            (rule.metrics.confidence * 100.0) as i32,
+            conditions.join(" &&\n        "),
+            config.output_field,
+            rule.absent,
+            Self::generate_log_action(&rule_name, rule.metrics.confidence, config)
+        )
+    }
+
+    /// Render rules, annotating those flagged as not live and those with
+    /// evidence
+    fn render(
+        rules: &[AssociationRule],
+        config: &GrlConfig,
+        live: Option<&[bool]>,
+        evidence: Option<&RuleEvidence>,
+    ) -> (String, ExportReport) {
+        let mut report = ExportReport {
+            rule_count: rules.len(),
+            ..Default::default()
+        };
         let mut grl = String::new();
 
         // Header
@@ -166,60 +575,111 @@ impl GrlExporter {
 
         // Generate each rule
         for (idx, rule) in rules.iter().enumerate() {
-            grl.push_str(&Self::rule_to_grl(rule, idx, config));
+            let rule_grl = Self::rule_to_grl(rule, idx, config);
+
+            if Self::has_long_items(rule, config) {
+                let rule_name = Self::generate_rule_name(rule, idx, config);
+                report.warnings.push(format!(
+                    "Rule '{}' has items longer than {} characters; name truncated",
+                    rule_name,
+                    config.max_item_length.unwrap_or_default()
+                ));
+                report.truncated_rules.push(rule_name);
+            }
+
+            if live.is_some_and(|live| !live[idx]) {
+                grl.push_str("// DEAD RULE: antecedent not seen in sample baskets\n");
+            }
+            if let Some(ids) = evidence
+                .and_then(|evidence| evidence.get(rule))
+                .filter(|ids| !ids.is_empty())
+            {
+                grl.push_str(&format!("// Evidence: {}\n", ids.join(", ")));
+            }
+            grl.push_str(&rule_grl);
             grl.push('\n');
         }
 
-        grl
+        (grl, report)
+    }
+
+    /// Check whether any item of the rule exceeds the configured max length
+    fn has_long_items(rule: &AssociationRule, config: &GrlConfig) -> bool {
+        config.max_item_length.is_some_and(|max| {
+            rule.antecedent
+                .iter()
+                .chain(&rule.consequent)
+                .any(|item| item.chars().count() > max)
+        })
+    }
+
+    /// Shorten an item for display, keeping a hash suffix for uniqueness
+    fn display_item(item: &str, config: &GrlConfig) -> String {
+        match config.max_item_length {
+            Some(max) => shorten_item(item, max),
+            None => item.to_string(),
+        }
+    }
+
+    /// Join items for display in comments and rule names
+    fn display_items(items: &[String], config: &GrlConfig) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| Self::display_item(item, config))
+            .collect()
     }
 
     /// Convert a single rule to GRL format
-    fn rule_to_grl(rule: &AssociationRule, idx: usize, config: &GrlConfig) -> String {
-        let rule_name = Self::generate_rule_name(rule, idx);
+    pub(crate) fn rule_to_grl(rule: &AssociationRule, idx: usize, config: &GrlConfig) -> String {
+        let rule_name = Self::generate_rule_name(rule, idx, config);
         let salience = (rule.metrics.confidence * 100.0) as i32;
+        let antecedent_str = Self::display_items(&rule.antecedent, config).join(", ");
+        let consequent_str = Self::display_items(&rule.consequent, config).join(", ");
 
         format!(
             r#"// Rule #{}: {} => {}
-// Confidence: {:.1}% | Support: {:.1}% | Lift: {:.2} | Conviction: {:.2}
+// Confidence: {:.1}% | Support: {:.1}% | Lift: {:.2} | Conviction: {}
 // Interpretation: When {} present, {} appears {:.1}% of the time
 rule "{}" salience {} no-loop {{
     when
         {}
     then
         {};
-        LogMessage("Rule fired: {} (confidence: {:.1}%)");
-}}
+{}}}
 "#,
             idx + 1,
-            rule.antecedent.join(", "),
-            rule.consequent.join(", "),
+            antecedent_str,
+            consequent_str,
             rule.metrics.confidence * 100.0,
             rule.metrics.support * 100.0,
             rule.metrics.lift,
-            rule.metrics.conviction,
-            rule.antecedent.join(", "),
-            rule.consequent.join(", "),
+            rule.metrics
+                .conviction
+                .map_or_else(|| "n/a".to_string(), |c| format!("{:.2}", c)),
+            antecedent_str,
+            consequent_str,
             rule.metrics.confidence * 100.0,
             rule_name,
             salience,
-            Self::generate_conditions_with_negation(&rule.antecedent, &rule.consequent, config),
-            Self::generate_actions(&rule.consequent, config),
-            rule_name,
-            rule.metrics.confidence * 100.0
+            config.renderer().render_conditions(rule, config),
+            config.renderer().render_actions(rule, config),
+            Self::generate_log_action(&rule_name, rule.metrics.confidence, config)
         )
     }
 
     /// Generate rule name from antecedent and consequent
-    fn generate_rule_name(rule: &AssociationRule, idx: usize) -> String {
-        let antecedent_str = rule
-            .antecedent
+    pub(crate) fn generate_rule_name(
+        rule: &AssociationRule,
+        idx: usize,
+        config: &GrlConfig,
+    ) -> String {
+        let antecedent_str = Self::display_items(&rule.antecedent, config)
             .iter()
             .map(|s| s.replace(' ', "_"))
             .collect::<Vec<_>>()
             .join("_");
 
-        let consequent_str = rule
-            .consequent
+        let consequent_str = Self::display_items(&rule.consequent, config)
             .iter()
             .map(|s| s.replace(' ', "_"))
             .collect::<Vec<_>>()
@@ -242,9 +702,81 @@ rule "{}" salience {} no-loop {{
         conditions.join(" &&\n        ")
     }
 
-    /// Generate actions from consequent items based on template
-    fn generate_actions(items: &[String], config: &GrlConfig) -> String {
-        match config.template {
+    /// Generate the trailing log action for a rule (empty for RETE)
+    fn generate_log_action(rule_name: &str, confidence: f64, config: &GrlConfig) -> String {
+        match config.dialect {
+            GrlDialect::Legacy => format!(
+                "        LogMessage(\"Rule fired: {} (confidence: {:.1}%)\");\n",
+                rule_name,
+                confidence * 100.0
+            ),
+            // The v1 parser ends string arguments at the first ')'
+            GrlDialect::V1 => format!(
+                "        LogMessage(\"Rule fired: {} - confidence {:.1}%\");\n",
+                rule_name,
+                confidence * 100.0
+            ),
+            // Custom calls need registered functions in RETE; keep the rule self-contained
+            GrlDialect::Rete => String::new(),
+        }
+    }
+
+    /// Generate actions from consequent items based on template and dialect
+    fn generate_actions(items: &[String], template: RuleTemplate, config: &GrlConfig) -> String {
+        match config.dialect {
+            GrlDialect::Legacy => Self::generate_legacy_actions(items, template, config),
+            GrlDialect::V1 | GrlDialect::Rete => Self::generate_v1_actions(items, template, config),
+        }
+    }
+
+    /// Generate actions avoiding constructs the v1 parser mangles
+    ///
+    /// String arguments never contain commas or parentheses, numeric outputs
+    /// use assignment instead of `+=`, and no inline comments or `if` blocks
+    /// are emitted.
+    fn generate_v1_actions(items: &[String], template: RuleTemplate, config: &GrlConfig) -> String {
+        let output = &config.output_field;
+
+        match template {
+            RuleTemplate::Scoring => items
+                .iter()
+                .enumerate()
+                .map(|(i, _)| {
+                    let weight = 10.0 / (i + 1) as f64; // Decreasing weights
+                    format!("{} = {} + {:.1}", output, output, weight)
+                })
+                .collect::<Vec<_>>()
+                .join(";\n        "),
+            RuleTemplate::Alert => {
+                let items_str = items.join(" | ");
+                format!(
+                    "{}.triggerAlert(\"Pattern detected: {}\");\n        {}.addDetails(\"Items: {}\")",
+                    output, items_str, output, items_str
+                )
+            }
+            RuleTemplate::Validation => format!(
+                "ValidationError.addError(\"Missing required items: {}\");\n        ValidationError.setInvalid(true)",
+                items.join(" | ")
+            ),
+            RuleTemplate::FraudDetection => {
+                let risk_score = items.len() * 15; // Higher for more indicators
+                let mut actions = vec![format!("{} = {} + {}", output, output, risk_score)];
+                actions.extend(items.iter().map(|item| format!("FraudFlags.add(\"{}\")", item)));
+                actions.join(";\n        ")
+            }
+            // Recommendation, Classification, MultiAction and InventoryAlert
+            // already use v1-compatible syntax
+            _ => Self::generate_legacy_actions(items, template, config),
+        }
+    }
+
+    /// Generate actions using the original (legacy) syntax
+    fn generate_legacy_actions(
+        items: &[String],
+        template: RuleTemplate,
+        config: &GrlConfig,
+    ) -> String {
+        match template {
             RuleTemplate::Recommendation => {
                 // Add items to recommendation list
                 items
@@ -349,25 +881,76 @@ rule "{}" salience {} no-loop {{
     }
 }
 
+/// Warnings collected while exporting rules
+#[derive(Debug, Clone, Default)]
+pub struct ExportReport {
+    /// Number of rules exported
+    pub rule_count: usize,
+    /// Names of rules whose names were truncated due to long items
+    pub truncated_rules: Vec<String>,
+    /// Human-readable warnings
+    pub warnings: Vec<String>,
+    /// Names of rules whose antecedent never appears in the smoke-check sample
+    pub dead_rules: Vec<String>,
+    /// Number of dead rules left out of the export
+    pub excluded_rules: usize,
+    /// Rule items that match the sample only after normalization
+    pub vocabulary_mismatches: Vec<VocabularyMismatch>,
+}
+
+impl ExportReport {
+    /// Check if the export produced any warnings
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+}
+
+/// 64-bit FNV-1a hash (stable across runs and Rust versions)
+pub(crate) fn fnv1a_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// First `max_len` characters of `item` plus a hash suffix, so distinct
+/// long items stay distinct; shorter items are returned as is
+pub(crate) fn shorten_item(item: &str, max_len: usize) -> String {
+    if item.chars().count() <= max_len {
+        return item.to_string();
+    }
+    let prefix: String = item.chars().take(max_len).collect();
+    format!("{}_{:08x}", prefix, fnv1a_hash(item) as u32)
+}
+
+/// File name for a rule group: unsafe characters replaced by `_`
+fn group_file_name(group: &str) -> String {
+    let stem: String = group
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = if stem.is_empty() { "_" } else { &stem };
+    format!("{}.grl", stem)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::export::smoke::BasketSample;
     use crate::types::PatternMetrics;
 
     #[test]
     fn test_grl_generation() {
-        let rule = AssociationRule {
-            antecedent: vec!["Laptop".to_string()],
-            consequent: vec!["Mouse".to_string()],
-            metrics: PatternMetrics {
-                confidence: 0.857,
-                support: 0.6,
-                lift: 1.43,
-                conviction: 2.33,
-                avg_time_gap: None,
-                time_variance: None,
-            },
-        };
+        let rule = AssociationRule::new(
+            ["Laptop"],
+            ["Mouse"],
+            PatternMetrics::basic(0.857, 0.6, 1.43),
+        );
 
         let grl = GrlExporter::to_grl(&[rule]);
 
@@ -381,18 +964,11 @@ mod tests {
 
     #[test]
     fn test_multi_item_rule() {
-        let rule = AssociationRule {
-            antecedent: vec!["Laptop".to_string(), "Mouse".to_string()],
-            consequent: vec!["USB Hub".to_string()],
-            metrics: PatternMetrics {
-                confidence: 0.75,
-                support: 0.45,
-                lift: 1.88,
-                conviction: 1.71,
-                avg_time_gap: None,
-                time_variance: None,
-            },
-        };
+        let rule = AssociationRule::new(
+            ["Laptop", "Mouse"],
+            ["USB Hub"],
+            PatternMetrics::basic(0.75, 0.45, 1.88),
+        );
 
         let grl = GrlExporter::to_grl(&[rule]);
 
@@ -401,4 +977,456 @@ mod tests {
         assert!(grl.contains("USB Hub"));
         assert!(grl.contains("&&")); // Multiple conditions
     }
+
+    fn long_item_rules() -> Vec<AssociationRule> {
+        let long_a = format!("{}A", "x".repeat(999));
+        let long_b = format!("{}B", "x".repeat(999));
+        let metrics = PatternMetrics::basic(0.8, 0.5, 1.5);
+
+        vec![
+            AssociationRule {
+                antecedent: vec![long_a.clone()],
+                consequent: vec!["Mouse".to_string()],
+                metrics: metrics.clone(),
+            },
+            AssociationRule {
+                antecedent: vec![long_b],
+                consequent: vec!["Mouse".to_string()],
+                metrics: metrics.clone(),
+            },
+            AssociationRule {
+                antecedent: vec!["Laptop".to_string()],
+                consequent: vec!["Mouse".to_string()],
+                metrics,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_long_items_truncated_in_names() {
+        let rules = long_item_rules();
+        let config = GrlConfig::default().with_max_item_length(32);
+
+        let (grl, report) = GrlExporter::to_grl_with_report(&rules, &config);
+
+        // Conditions keep the full item for correctness
+        assert!(grl.contains(&format!("contains \"{}\"", rules[0].antecedent[0])));
+
+        let names: Vec<&str> = grl
+            .lines()
+            .filter_map(|line| line.strip_prefix("rule \""))
+            .map(|line| line.split('"').next().unwrap())
+            .collect();
+        assert_eq!(names.len(), 3);
+        assert!(names.iter().all(|name| name.len() < 80));
+
+        // Same 32-char prefix, but the hash suffix keeps the names unique
+        let name_a = GrlExporter::display_item(&rules[0].antecedent[0], &config);
+        let name_b = GrlExporter::display_item(&rules[1].antecedent[0], &config);
+        assert_ne!(name_a, name_b);
+
+        assert_eq!(report.rule_count, 3);
+        assert_eq!(report.truncated_rules.len(), 2);
+        assert!(report.has_warnings());
+    }
+
+    #[test]
+    fn test_no_truncation_by_default() {
+        let rules = long_item_rules();
+        let (grl, report) = GrlExporter::to_grl_with_report(&rules, &GrlConfig::default());
+
+        assert!(!report.has_warnings());
+        assert!(grl.contains(&format!("Mined_0_{}_Implies_Mouse", rules[0].antecedent[0])));
+    }
+
+    #[cfg(feature = "engine")]
+    #[test]
+    fn test_truncated_grl_parses() {
+        use rust_rule_engine::GRLParser;
+
+        let rules = long_item_rules();
+        let config = GrlConfig::default().with_max_item_length(32);
+        let grl = GrlExporter::to_grl_with_config(&rules, &config);
+
+        let parsed = GRLParser::parse_rules(&grl).unwrap();
+        assert_eq!(parsed.len(), 3);
+    }
+
+    fn two_item_rule() -> AssociationRule {
+        AssociationRule::new(
+            ["Laptop"],
+            ["Mouse", "Hub"],
+            PatternMetrics::basic(0.8, 0.5, 1.5),
+        )
+    }
+
+    #[test]
+    fn test_legacy_dialect_is_default() {
+        let rule = two_item_rule();
+        let legacy = GrlConfig::default().with_dialect(GrlDialect::Legacy);
+
+        assert_eq!(GrlConfig::default().dialect, GrlDialect::Legacy);
+        assert!(GrlExporter::to_grl_with_config(&[rule], &legacy).contains(
+            "LogMessage(\"Rule fired: Mined_0_Laptop_Implies_Mouse_Hub (confidence: 80.0%)\")"
+        ));
+    }
+
+    #[test]
+    fn test_v1_dialect_avoids_unsafe_constructs() {
+        let rule = two_item_rule();
+
+        let fraud = GrlConfig::fraud_detection("Transaction.items").with_dialect(GrlDialect::V1);
+        let grl = GrlExporter::to_grl_with_config(std::slice::from_ref(&rule), &fraud);
+        assert!(grl.contains("FraudScore = FraudScore + 30"));
+        assert!(!grl.contains("if ("));
+
+        let alert = GrlConfig::alert("Transaction.items").with_dialect(GrlDialect::V1);
+        let grl = GrlExporter::to_grl_with_config(&[rule], &alert);
+        assert!(grl.contains("Pattern detected: Mouse | Hub"));
+    }
+
+    #[test]
+    fn test_rete_dialect_rejects_unsupported_templates() {
+        let rule = two_item_rule();
+
+        for template in [
+            RuleTemplate::Alert,
+            RuleTemplate::Validation,
+            RuleTemplate::MultiAction,
+            RuleTemplate::FraudDetection,
+            RuleTemplate::InventoryAlert,
+        ] {
+            let config = GrlConfig::default()
+                .with_template(template)
+                .with_dialect(GrlDialect::Rete);
+            let result = GrlExporter::try_to_grl_with_config(std::slice::from_ref(&rule), &config);
+            assert!(matches!(result, Err(MiningError::ExportFailed(_))));
+        }
+
+        let config = GrlConfig::default().with_dialect(GrlDialect::Rete);
+        let grl = GrlExporter::try_to_grl_with_config(&[rule], &config).unwrap();
+        assert!(!grl.contains("LogMessage"));
+    }
+
+    #[cfg(feature = "engine")]
+    #[test]
+    fn test_dialect_engine_round_trip() {
+        use rust_rule_engine::types::ActionType;
+        use rust_rule_engine::GRLParser;
+
+        let rule = two_item_rule();
+
+        for dialect in [GrlDialect::Legacy, GrlDialect::V1] {
+            let config = GrlConfig::default().with_dialect(dialect);
+            let grl =
+                GrlExporter::try_to_grl_with_config(std::slice::from_ref(&rule), &config).unwrap();
+            let parsed = GRLParser::parse_rules(&grl).unwrap();
+            assert_eq!(parsed.len(), 1);
+            assert!(parsed[0]
+                .actions
+                .iter()
+                .any(|action| matches!(action, ActionType::Append { .. })));
+        }
+
+        // V1 keeps every alert string argument intact
+        let config = GrlConfig::alert("Transaction.items").with_dialect(GrlDialect::V1);
+        let grl = GrlExporter::try_to_grl_with_config(&[rule], &config).unwrap();
+        let parsed = GRLParser::parse_rules(&grl).unwrap();
+        assert!(parsed[0].actions.iter().all(|action| match action {
+            ActionType::Custom { params, .. } => params.len() == 1,
+            _ => true,
+        }));
+    }
+
+    #[cfg(feature = "engine")]
+    #[test]
+    fn test_rete_dialect_round_trip() {
+        use rust_rule_engine::rete::facts::{FactValue, TypedFacts};
+        use rust_rule_engine::rete::grl_loader::GrlReteLoader;
+        use rust_rule_engine::rete::IncrementalEngine;
+
+        let config = GrlConfig::default().with_dialect(GrlDialect::Rete);
+        let grl = GrlExporter::try_to_grl_with_config(&[two_item_rule()], &config).unwrap();
+
+        let mut engine = IncrementalEngine::new();
+        assert_eq!(
+            GrlReteLoader::load_from_string(&grl, &mut engine).unwrap(),
+            1
+        );
+
+        let mut cart = TypedFacts::new();
+        cart.set(
+            "items",
+            FactValue::Array(vec![FactValue::String("Laptop".to_string())]),
+        );
+        let mut recommendations = TypedFacts::new();
+        recommendations.set("items", FactValue::Array(vec![]));
+
+        engine.insert("ShoppingCart".to_string(), cart);
+        engine.insert("Recommendation".to_string(), recommendations);
+
+        assert_eq!(engine.fire_all().len(), 1);
+    }
+
+    #[derive(Debug)]
+    struct ApiRenderer;
+
+    impl GrlRuleRenderer for ApiRenderer {
+        fn render_conditions(&self, rule: &AssociationRule, config: &GrlConfig) -> String {
+            RuleTemplate::Recommendation.render_conditions(rule, config)
+        }
+
+        fn render_actions(&self, rule: &AssociationRule, _config: &GrlConfig) -> String {
+            rule.consequent
+                .iter()
+                .map(|item| {
+                    format!(
+                        "CatalogApi.recommend(\"{}\", {:.2})",
+                        item, rule.metrics.confidence
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(";\n        ")
+        }
+    }
+
+    fn without_timestamp(grl: &str) -> String {
+        grl.lines()
+            .filter(|line| !line.starts_with("// Generated:"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_custom_renderer() {
+        // Alert is unsupported by RETE, but the custom renderer takes over
+        let config = GrlConfig::default()
+            .with_template(RuleTemplate::Alert)
+            .with_dialect(GrlDialect::Rete)
+            .with_renderer(ApiRenderer);
+        let grl = GrlExporter::try_to_grl_with_config(&[two_item_rule()], &config).unwrap();
+
+        assert!(grl.contains("CatalogApi.recommend(\"Mouse\", 0.80);\n"));
+        assert!(grl.contains("CatalogApi.recommend(\"Hub\", 0.80);\n"));
+        assert!(grl.contains("ShoppingCart.items contains \"Laptop\""));
+        assert!(!grl.contains("triggerAlert"));
+    }
+
+    #[test]
+    fn test_template_renderer_matches_template() {
+        let rules = [two_item_rule()];
+        for template in [
+            RuleTemplate::Recommendation,
+            RuleTemplate::Scoring,
+            RuleTemplate::FraudDetection,
+            RuleTemplate::InventoryAlert,
+        ] {
+            for dialect in [GrlDialect::Legacy, GrlDialect::V1] {
+                let config = GrlConfig::default()
+                    .with_template(template)
+                    .with_dialect(dialect);
+                let plugged = config.clone().with_renderer(template);
+
+                assert_eq!(
+                    without_timestamp(&GrlExporter::to_grl_with_config(&rules, &config)),
+                    without_timestamp(&GrlExporter::to_grl_with_config(&rules, &plugged))
+                );
+            }
+        }
+    }
+
+    /// Four rules, half of them dead against `smoke_sample`
+    fn smoke_rules() -> Vec<AssociationRule> {
+        let metrics = two_item_rule().metrics;
+        let rule = |antecedent: &[&str], consequent: &str| {
+            AssociationRule::new(antecedent.iter().copied(), [consequent], metrics.clone())
+        };
+
+        vec![
+            rule(&["Laptop"], "Mouse"),
+            rule(&["Tablet"], "Pen"),
+            rule(&["Phone"], "Case"),
+            // Both items are in the sample, but never in the same basket
+            rule(&["Laptop", "Phone"], "Hub"),
+        ]
+    }
+
+    fn smoke_sample() -> BasketSample {
+        BasketSample::new(vec![
+            vec!["Laptop", "Mouse"],
+            vec!["Phone"],
+            vec!["Monitor"],
+        ])
+    }
+
+    #[test]
+    fn test_smoke_check_annotates_dead_rules() {
+        let check = SmokeCheck::new(smoke_sample());
+        let (grl, report) =
+            GrlExporter::to_grl_with_smoke_check(&smoke_rules(), &GrlConfig::default(), &check);
+
+        assert_eq!(report.rule_count, 4);
+        assert_eq!(report.excluded_rules, 0);
+        assert_eq!(
+            report.dead_rules,
+            vec![
+                "Mined_1_Tablet_Implies_Pen",
+                "Mined_3_Laptop_Phone_Implies_Hub"
+            ]
+        );
+        assert_eq!(grl.matches("rule \"").count(), 4);
+        assert_eq!(grl.matches("// DEAD RULE").count(), 2);
+        assert!(
+            grl.contains("// DEAD RULE: antecedent not seen in sample baskets\n// Rule #2: Tablet")
+        );
+        assert!(report.vocabulary_mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_smoke_check_excludes_dead_rules() {
+        let check = SmokeCheck::new(smoke_sample()).with_exclude_dead(true);
+        let (grl, report) =
+            GrlExporter::to_grl_with_smoke_check(&smoke_rules(), &GrlConfig::default(), &check);
+
+        assert_eq!(report.rule_count, 2);
+        assert_eq!(report.excluded_rules, 2);
+        assert_eq!(report.dead_rules.len(), 2);
+        assert_eq!(grl.matches("rule \"").count(), 2);
+        assert!(!grl.contains("DEAD RULE"));
+        assert!(!grl.contains("Tablet"));
+        assert!(report.warnings.iter().all(|w| w.ends_with("; excluded")));
+    }
+
+    #[test]
+    fn test_smoke_check_reports_vocabulary_mismatch() {
+        let mut rules = smoke_rules();
+        rules[0].antecedent = vec!["electronics:laptop".to_string()];
+
+        let check = SmokeCheck::new(smoke_sample());
+        let (_, report) =
+            GrlExporter::to_grl_with_smoke_check(&rules, &GrlConfig::default(), &check);
+
+        assert_eq!(report.dead_rules.len(), 3);
+        assert_eq!(report.vocabulary_mismatches.len(), 1);
+        assert_eq!(report.vocabulary_mismatches[0].suggestion, "Laptop");
+        assert!(report.warnings.contains(
+            &"Item 'electronics:laptop' not in sample baskets; did you mean 'Laptop'?".to_string()
+        ));
+    }
+
+    fn negative_rule() -> NegativeRule {
+        NegativeRule {
+            antecedent: vec!["Gaming Console".to_string()],
+            absent: "Office Chair".to_string(),
+            metrics: PatternMetrics {
+                confidence: 0.95,
+                support: 0.4,
+                lift: 1.6,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_negative_rule_renders_negated_condition() {
+        let config = GrlConfig::custom("ShoppingCart.items", "Exclusions.items");
+        let grl = GrlExporter::negative_rules_to_grl(&[negative_rule()], &config);
+
+        assert!(grl.contains("// Negative rule #1: Gaming Console => NOT Office Chair"));
+        assert!(grl.contains(
+            "ShoppingCart.items contains \"Gaming Console\" &&\n        \
+             !(ShoppingCart.items contains \"Office Chair\") &&\n        \
+             !(Exclusions.items contains \"Office Chair\")"
+        ));
+        assert!(grl.contains("Exclusions.items += \"Office Chair\";"));
+        assert!(grl.contains("rule \"Mined_Negative_0_Gaming_Console_Excludes_Office_Chair\""));
+    }
+
+    #[cfg(feature = "engine")]
+    #[test]
+    fn test_negative_grl_parses() {
+        use rust_rule_engine::GRLParser;
+
+        let grl = GrlExporter::negative_rules_to_grl(&[negative_rule()], &GrlConfig::default());
+        assert_eq!(GRLParser::parse_rules(&grl).unwrap().len(), 1);
+    }
+
+    fn routed_rules() -> Vec<AssociationRule> {
+        let rule = |antecedent: &str, consequent: &str| AssociationRule {
+            antecedent: vec![antecedent.to_string()],
+            consequent: vec![consequent.to_string()],
+            metrics: PatternMetrics {
+                confidence: 0.8,
+                support: 0.3,
+                lift: 1.5,
+                ..Default::default()
+            },
+        };
+        vec![
+            rule("Laptop", "inventory/Charger"),
+            rule("Laptop", "storefront/Mouse"),
+            rule("Phone", "inventory/Case"),
+            rule("Camera", "storefront/Lens"),
+            rule("Tablet", "inventory/Stylus"),
+        ]
+    }
+
+    fn consequent_prefix(rule: &AssociationRule) -> String {
+        rule.consequent[0]
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    #[test]
+    fn test_grouped_by_consequent_prefix() {
+        let docs =
+            GrlExporter::to_grl_grouped(&routed_rules(), &GrlConfig::default(), consequent_prefix);
+        assert_eq!(docs.len(), 2);
+
+        let inventory = &docs["inventory"];
+        assert!(inventory.contains("// Total rules: 3"));
+        assert!(inventory.contains("// Rule #3: Tablet => inventory/Stylus"));
+        assert!(!inventory.contains("storefront/"));
+
+        let storefront = &docs["storefront"];
+        assert!(storefront.contains("// Total rules: 2"));
+        assert!(storefront.contains("// Rule #1: Laptop => storefront/Mouse"));
+        assert!(!storefront.contains("inventory/"));
+    }
+
+    #[test]
+    fn test_write_grouped() {
+        let dir =
+            std::env::temp_dir().join(format!("rust_rule_miner_grouped_{}", std::process::id()));
+        let paths = GrlExporter::write_grouped(
+            &routed_rules(),
+            &GrlConfig::default(),
+            consequent_prefix,
+            &dir,
+        )
+        .unwrap();
+        let names: Vec<String> = paths
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["inventory.grl", "storefront.grl"]);
+
+        let inventory = std::fs::read_to_string(dir.join("inventory.grl")).unwrap();
+        assert!(inventory.contains("inventory/Case") && !inventory.contains("storefront/"));
+
+        // Full consequents as groups: "inventory/Case" and "inventory_Case" collide
+        let mut rules = routed_rules();
+        rules[4].consequent = vec!["inventory_Case".to_string()];
+        let err = GrlExporter::write_grouped(
+            &rules,
+            &GrlConfig::default(),
+            |rule| rule.consequent[0].clone(),
+            &dir,
+        )
+        .unwrap_err();
+        assert!(matches!(err, MiningError::ExportFailed(_)));
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
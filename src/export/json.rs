@@ -0,0 +1,298 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+use crate::export::privacy::{apply_privacy, PrivacyConfig, PrivacyManifest};
+use crate::types::{AssociationRule, PatternMetrics, RuleCounts};
+
+/// JSON document written by `JsonExporter`
+///
+/// ```json
+/// {
+///   "generated": "2024-01-01T00:00:00Z",
+///   "total_rules": 1,
+///   "rules": [
+///     {
+///       "antecedent": ["Laptop"],
+///       "consequent": ["Mouse"],
+///       "confidence": 0.857,
+///       "support": 0.6,
+///       "lift": 1.43,
+///       "conviction": 2.33,
+///       "avg_time_gap_ms": null,
+///       "time_variance_ms": null
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Infinite conviction (confidence of 100%) is written as `null`, since JSON
+/// has no representation for infinity. Metrics that were not computed (see
+/// `MiningConfig::compute_metrics`) are omitted; `leverage`, `jaccard`,
+/// `cosine`, `kulczynski`, `counts` and `weighted_support` appear only when
+/// computed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRuleDocument {
+    pub generated: DateTime<Utc>,
+    pub total_rules: usize,
+    pub rules: Vec<JsonRule>,
+    /// Present only when metrics were noised (see `export::privacy`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<PrivacyManifest>,
+}
+
+/// Flattened rule as stored in JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRule {
+    pub antecedent: Vec<String>,
+    pub consequent: Vec<String>,
+    pub confidence: f64,
+    pub support: f64,
+    pub lift: f64,
+    /// Omitted when not computed, `null` when infinite
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "present"
+    )]
+    pub conviction: Option<Option<f64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leverage: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jaccard: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cosine: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kulczynski: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counts: Option<RuleCounts>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weighted_support: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chi_square: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_value: Option<f64>,
+    pub avg_time_gap_ms: Option<u64>,
+    pub time_variance_ms: Option<u64>,
+}
+
+impl From<&AssociationRule> for JsonRule {
+    fn from(rule: &AssociationRule) -> Self {
+        let m = &rule.metrics;
+        Self {
+            antecedent: rule.antecedent.clone(),
+            consequent: rule.consequent.clone(),
+            confidence: m.confidence,
+            support: m.support,
+            lift: m.lift,
+            conviction: m.conviction.map(|c| c.is_finite().then_some(c)),
+            leverage: m.leverage,
+            jaccard: m.jaccard,
+            cosine: m.cosine,
+            kulczynski: m.kulczynski,
+            counts: m.counts,
+            weighted_support: m.weighted_support,
+            chi_square: m.chi_square,
+            p_value: m.p_value,
+            avg_time_gap_ms: m.avg_time_gap.map(|d| d.as_millis() as u64),
+            time_variance_ms: m.time_variance.map(|d| d.as_millis() as u64),
+        }
+    }
+}
+
+impl From<JsonRule> for AssociationRule {
+    fn from(rule: JsonRule) -> Self {
+        Self {
+            antecedent: rule.antecedent,
+            consequent: rule.consequent,
+            metrics: PatternMetrics {
+                confidence: rule.confidence,
+                support: rule.support,
+                lift: rule.lift,
+                conviction: rule.conviction.map(|c| c.unwrap_or(f64::INFINITY)),
+                leverage: rule.leverage,
+                jaccard: rule.jaccard,
+                cosine: rule.cosine,
+                kulczynski: rule.kulczynski,
+                counts: rule.counts,
+                weighted_support: rule.weighted_support,
+                chi_square: rule.chi_square,
+                p_value: rule.p_value,
+                avg_time_gap: rule.avg_time_gap_ms.map(Duration::from_millis),
+                time_variance: rule.time_variance_ms.map(Duration::from_millis),
+            },
+        }
+    }
+}
+
+/// Tell a present `null` apart from a missing key
+fn present<'de, D>(deserializer: D) -> std::result::Result<Option<Option<f64>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<f64>::deserialize(deserializer).map(Some)
+}
+
+/// Export association rules to JSON
+pub struct JsonExporter;
+
+impl JsonExporter {
+    /// Serialize rules to compact JSON
+    pub fn to_json(rules: &[AssociationRule]) -> Result<String> {
+        Ok(serde_json::to_string(&Self::document(rules, None))?)
+    }
+
+    /// Serialize rules to indented JSON
+    pub fn to_json_pretty(rules: &[AssociationRule]) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&Self::document(rules, None))?)
+    }
+
+    /// Serialize rules with differentially private metrics
+    ///
+    /// Low-count rules are suppressed and the document carries a `privacy`
+    /// manifest describing the noise.
+    pub fn to_json_with_privacy(
+        rules: &[AssociationRule],
+        total_transactions: usize,
+        privacy: &PrivacyConfig,
+    ) -> Result<String> {
+        let noised = apply_privacy(rules, total_transactions, privacy)?;
+        let document = Self::document(&noised.rules, Some(noised.manifest));
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    /// Parse a document written by `to_json`/`to_json_pretty` back into rules
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::export::JsonExporter;
+    ///
+    /// let json = JsonExporter::to_json(&[])?;
+    /// let rules = JsonExporter::from_json(&json)?;
+    /// assert!(rules.is_empty());
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn from_json(json: &str) -> Result<Vec<AssociationRule>> {
+        let document: JsonRuleDocument = serde_json::from_str(json)?;
+        Ok(document.rules.into_iter().map(Into::into).collect())
+    }
+
+    fn document(rules: &[AssociationRule], privacy: Option<PrivacyManifest>) -> JsonRuleDocument {
+        JsonRuleDocument {
+            generated: Utc::now(),
+            total_rules: rules.len(),
+            rules: rules.iter().map(JsonRule::from).collect(),
+            privacy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> Vec<AssociationRule> {
+        vec![
+            AssociationRule {
+                antecedent: vec!["Laptop".to_string()],
+                consequent: vec!["Mouse".to_string()],
+                metrics: PatternMetrics {
+                    confidence: 0.857,
+                    support: 0.6,
+                    lift: 1.43,
+                    conviction: Some(2.33),
+                    leverage: None,
+                    jaccard: None,
+                    cosine: None,
+                    kulczynski: None,
+                    counts: None,
+                    weighted_support: None,
+                    chi_square: None,
+                    p_value: None,
+                    avg_time_gap: Some(Duration::from_millis(90_000)),
+                    time_variance: Some(Duration::from_millis(1_500)),
+                },
+            },
+            AssociationRule {
+                antecedent: vec!["Phone".to_string(), "Case \"Pro\"".to_string()],
+                consequent: vec!["Charger".to_string()],
+                metrics: PatternMetrics::basic(1.0, 0.2, 2.5),
+            },
+        ]
+    }
+
+    fn assert_same(a: &[AssociationRule], b: &[AssociationRule]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert_eq!(x.antecedent, y.antecedent);
+            assert_eq!(x.consequent, y.consequent);
+            assert_eq!(x.metrics.confidence, y.metrics.confidence);
+            assert_eq!(x.metrics.support, y.metrics.support);
+            assert_eq!(x.metrics.lift, y.metrics.lift);
+            assert_eq!(x.metrics.conviction, y.metrics.conviction);
+            assert_eq!(x.metrics.avg_time_gap, y.metrics.avg_time_gap);
+            assert_eq!(x.metrics.time_variance, y.metrics.time_variance);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let original = rules();
+
+        let compact = JsonExporter::to_json(&original).unwrap();
+        assert_same(&JsonExporter::from_json(&compact).unwrap(), &original);
+
+        let pretty = JsonExporter::to_json_pretty(&original).unwrap();
+        assert_same(&JsonExporter::from_json(&pretty).unwrap(), &original);
+    }
+
+    #[test]
+    fn test_envelope() {
+        let json = JsonExporter::to_json(&rules()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["total_rules"], 2);
+        assert!(value["generated"].is_string());
+        assert_eq!(value["rules"][0]["avg_time_gap_ms"], 90_000);
+        assert!(value["rules"][1]["conviction"].is_null());
+        assert!(value.get("privacy").is_none());
+        assert!(value["rules"][0].get("leverage").is_none());
+    }
+
+    #[test]
+    fn test_missing_metrics_omitted() {
+        let mut rules = rules();
+        rules[0].metrics.conviction = None;
+        rules[1].metrics.leverage = Some(0.12);
+        rules[1].metrics.counts = Some(RuleCounts {
+            antecedent: 20,
+            consequent: 40,
+            joint: 20,
+            total: 100,
+        });
+
+        let json = JsonExporter::to_json(&rules).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["rules"][0].get("conviction").is_none());
+        assert_eq!(value["rules"][1]["leverage"], 0.12);
+        assert_eq!(value["rules"][1]["counts"]["joint"], 20);
+
+        let parsed = JsonExporter::from_json(&json).unwrap();
+        assert_eq!(parsed[0].metrics.conviction, None);
+        assert_eq!(parsed[1].metrics.conviction, Some(f64::INFINITY));
+        assert_eq!(parsed[1].metrics.counts, rules[1].metrics.counts);
+    }
+
+    #[test]
+    fn test_privacy_manifest() {
+        let privacy = PrivacyConfig::new(1.0).with_min_count(50).with_seed(1);
+        let json = JsonExporter::to_json_with_privacy(&rules(), 1000, &privacy).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["privacy"]["noised"], true);
+        assert_eq!(value["privacy"]["suppressed_rules"], 0);
+        assert_eq!(JsonExporter::from_json(&json).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{MiningError, Result};
+use crate::types::FrequentItemset;
+
+/// Configuration for lattice export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatticeConfig {
+    /// Refuse to export lattices with more nodes than this
+    pub max_nodes: usize,
+}
+
+impl Default for LatticeConfig {
+    fn default() -> Self {
+        Self { max_nodes: 10_000 }
+    }
+}
+
+impl LatticeConfig {
+    /// Set the node limit
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+}
+
+/// Itemset node in the lattice
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatticeNode {
+    pub id: usize,
+    /// Items in canonical (sorted) order
+    pub items: Vec<String>,
+    pub size: usize,
+    pub support: f64,
+}
+
+/// Subset edge from a (k-1)-itemset to a k-itemset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatticeLink {
+    pub source: usize,
+    pub target: usize,
+}
+
+/// Frequent-itemset lattice in node-link form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lattice {
+    pub nodes: Vec<LatticeNode>,
+    pub links: Vec<LatticeLink>,
+}
+
+/// Export the frequent-itemset lattice as JSON (nodes/links) or GraphML
+///
+/// Edges connect each k-itemset to its (k-1)-subsets that are also in the
+/// input. They are found by looking up every subset in a hash index of
+/// canonical itemsets, so cost is O(n·k) rather than O(n²).
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::export::{LatticeConfig, LatticeExporter};
+/// use rust_rule_miner::FrequentItemset;
+///
+/// let itemsets = vec![
+///     FrequentItemset::from_count(vec!["A".into()], 6, 10),
+///     FrequentItemset::from_count(vec!["B".into()], 5, 10),
+///     FrequentItemset::from_count(vec!["A".into(), "B".into()], 4, 10),
+/// ];
+///
+/// let lattice = LatticeExporter::build(&itemsets, &LatticeConfig::default())?;
+/// assert_eq!(lattice.links.len(), 2);
+///
+/// let graphml = LatticeExporter::to_graphml(&itemsets, &LatticeConfig::default())?;
+/// assert!(graphml.contains("<graphml"));
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+pub struct LatticeExporter;
+
+impl LatticeExporter {
+    /// Build the lattice; duplicate itemsets keep their first occurrence
+    pub fn build(itemsets: &[FrequentItemset], config: &LatticeConfig) -> Result<Lattice> {
+        let mut index: HashMap<Vec<String>, usize> = HashMap::with_capacity(itemsets.len());
+        let mut nodes = Vec::with_capacity(itemsets.len());
+
+        for itemset in itemsets {
+            let mut items = itemset.items.clone();
+            items.sort();
+            items.dedup();
+            if items.is_empty() || index.contains_key(&items) {
+                continue;
+            }
+
+            if nodes.len() == config.max_nodes {
+                return Err(MiningError::ExportFailed(format!(
+                    "lattice exceeds the node limit of {} (raise LatticeConfig::max_nodes \
+                     or increase min_support)",
+                    config.max_nodes
+                )));
+            }
+
+            let id = nodes.len();
+            index.insert(items.clone(), id);
+            nodes.push(LatticeNode {
+                id,
+                size: items.len(),
+                items,
+                support: itemset.support,
+            });
+        }
+
+        let mut links = Vec::new();
+        let mut subset = Vec::new();
+        for node in nodes.iter().filter(|node| node.size > 1) {
+            for skip in 0..node.size {
+                subset.clear();
+                subset.extend(
+                    node.items
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != skip)
+                        .map(|(_, item)| item.clone()),
+                );
+                if let Some(&source) = index.get(&subset) {
+                    links.push(LatticeLink {
+                        source,
+                        target: node.id,
+                    });
+                }
+            }
+        }
+
+        Ok(Lattice { nodes, links })
+    }
+
+    /// Export as node-link JSON (`{"nodes": [...], "links": [...]}`)
+    pub fn to_json(itemsets: &[FrequentItemset], config: &LatticeConfig) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&Self::build(
+            itemsets, config,
+        )?)?)
+    }
+
+    /// Export as GraphML for Gephi, Cytoscape, networkx, etc.
+    pub fn to_graphml(itemsets: &[FrequentItemset], config: &LatticeConfig) -> Result<String> {
+        let lattice = Self::build(itemsets, config)?;
+        let mut out = String::new();
+
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str(
+            "  <key id=\"items\" for=\"node\" attr.name=\"items\" attr.type=\"string\"/>\n",
+        );
+        out.push_str("  <key id=\"size\" for=\"node\" attr.name=\"size\" attr.type=\"int\"/>\n");
+        out.push_str(
+            "  <key id=\"support\" for=\"node\" attr.name=\"support\" attr.type=\"double\"/>\n",
+        );
+        out.push_str("  <graph id=\"lattice\" edgedefault=\"directed\">\n");
+
+        for node in &lattice.nodes {
+            let _ = writeln!(out, "    <node id=\"n{}\">", node.id);
+            let _ = writeln!(
+                out,
+                "      <data key=\"items\">{}</data>",
+                escape_xml(&node.items.join(", "))
+            );
+            let _ = writeln!(out, "      <data key=\"size\">{}</data>", node.size);
+            let _ = writeln!(out, "      <data key=\"support\">{}</data>", node.support);
+            out.push_str("    </node>\n");
+        }
+
+        for (i, link) in lattice.links.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\"/>",
+                i, link.source, link.target
+            );
+        }
+
+        out.push_str("  </graph>\n</graphml>\n");
+        Ok(out)
+    }
+}
+
+/// Escape text for XML content or attribute values
+///
+/// Control characters XML 1.0 cannot represent at all are dropped.
+pub(crate) fn escape_xml(s: &str) -> String {
+    s.chars()
+        .filter(|c| {
+            !matches!(c, '\u{0}'..='\u{8}' | '\u{b}' | '\u{c}' | '\u{e}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}')
+        })
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every non-empty subset of `items`, as frequent itemsets
+    fn power_set(items: &[&str]) -> Vec<FrequentItemset> {
+        (1u32..(1 << items.len()))
+            .map(|mask| FrequentItemset {
+                items: items
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << i) != 0)
+                    .map(|(_, item)| item.to_string())
+                    .collect(),
+                support: 1.0 / mask.count_ones() as f64,
+                count: 0,
+            })
+            .collect()
+    }
+
+    fn binomial(n: usize, k: usize) -> usize {
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    #[test]
+    fn test_full_lattice_edge_count() {
+        let items = ["A", "B", "C", "D", "E"];
+        let lattice =
+            LatticeExporter::build(&power_set(&items), &LatticeConfig::default()).unwrap();
+
+        // Each k-itemset has k parents: sum over k >= 2 of k * C(n, k)
+        let n = items.len();
+        let expected: usize = (2..=n).map(|k| k * binomial(n, k)).sum();
+        assert_eq!(lattice.nodes.len(), (1 << n) - 1);
+        assert_eq!(lattice.links.len(), expected);
+        assert_eq!(expected, 75);
+
+        for link in &lattice.links {
+            let parent = &lattice.nodes[link.source];
+            let child = &lattice.nodes[link.target];
+            assert_eq!(parent.size + 1, child.size);
+            assert!(parent.items.iter().all(|item| child.items.contains(item)));
+        }
+    }
+
+    #[test]
+    fn test_missing_subsets_and_unsorted_input() {
+        let itemsets = vec![
+            FrequentItemset {
+                items: vec!["B".into(), "A".into()],
+                support: 0.4,
+                count: 4,
+            },
+            FrequentItemset {
+                items: vec!["A".into()],
+                support: 0.6,
+                count: 6,
+            },
+            // Duplicate in a different order is ignored
+            FrequentItemset {
+                items: vec!["A".into(), "B".into()],
+                support: 0.4,
+                count: 4,
+            },
+        ];
+
+        let lattice = LatticeExporter::build(&itemsets, &LatticeConfig::default()).unwrap();
+        assert_eq!(lattice.nodes.len(), 2);
+        assert_eq!(lattice.nodes[0].items, vec!["A", "B"]);
+        // {B} is absent, so only {A} -> {A, B}
+        assert_eq!(
+            lattice.links,
+            vec![LatticeLink {
+                source: 1,
+                target: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_node_limit() {
+        let itemsets = power_set(&["A", "B", "C", "D"]);
+
+        let config = LatticeConfig::default().with_max_nodes(15);
+        assert!(LatticeExporter::build(&itemsets, &config).is_ok());
+
+        let config = LatticeConfig::default().with_max_nodes(14);
+        assert!(matches!(
+            LatticeExporter::to_json(&itemsets, &config),
+            Err(MiningError::ExportFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_json_and_graphml_output() {
+        let itemsets = power_set(&["Milk & Eggs", "<Bread>"]);
+        let config = LatticeConfig::default();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&LatticeExporter::to_json(&itemsets, &config).unwrap()).unwrap();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(json["links"].as_array().unwrap().len(), 2);
+
+        let graphml = LatticeExporter::to_graphml(&itemsets, &config).unwrap();
+        assert_eq!(graphml.matches("<node ").count(), 3);
+        assert_eq!(graphml.matches("<edge ").count(), 2);
+        assert!(graphml.contains("&lt;Bread&gt;, Milk &amp; Eggs"));
+    }
+}
@@ -1,3 +1,72 @@
+#[cfg(feature = "cloud")]
+pub mod cloud;
+pub mod csv;
+pub mod delta;
+pub mod drl;
+pub mod edge;
 pub mod grl;
+pub mod json;
+pub mod lattice;
+pub mod privacy;
+pub mod report;
+pub mod smoke;
+pub mod sql;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
 
-pub use grl::{GrlConfig, GrlExporter};
+pub use csv::{CsvConfig, CsvExporter};
+pub use delta::{DeltaConfig, DeltaManifest, RuleDelta};
+pub use drl::{DrlConfig, DrlExporter};
+pub use edge::EdgeExporter;
+pub use grl::{ExportReport, GrlConfig, GrlDialect, GrlExporter, GrlRuleRenderer};
+pub use json::JsonExporter;
+pub use lattice::{Lattice, LatticeConfig, LatticeExporter};
+pub use privacy::{Epsilon, PrivacyConfig, PrivacyManifest};
+pub use report::{ReportExporter, ReportOptions, ReportSortBy};
+pub use smoke::{BasketSample, SmokeCheck, VocabularyMismatch};
+pub use sql::{SqlConfig, SqlExporter};
+#[cfg(feature = "xlsx")]
+pub use xlsx::{XlsxConfig, XlsxExporter};
+
+use crate::errors::Result;
+use crate::types::AssociationRule;
+
+/// Export format selector for destinations that accept any exporter
+#[derive(Debug, Clone)]
+pub enum ExportFormat {
+    Grl(GrlConfig),
+    Json,
+    Csv(CsvConfig),
+    Drl(DrlConfig),
+}
+
+impl ExportFormat {
+    /// Render rules in this format
+    pub fn render(&self, rules: &[AssociationRule]) -> Result<String> {
+        match self {
+            ExportFormat::Grl(config) => GrlExporter::try_to_grl_with_config(rules, config),
+            ExportFormat::Json => JsonExporter::to_json_pretty(rules),
+            ExportFormat::Csv(config) => Ok(CsvExporter::to_csv_with_config(rules, config)),
+            ExportFormat::Drl(config) => Ok(DrlExporter::to_drl_with_config(rules, config)),
+        }
+    }
+
+    /// File extension (without dot)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Grl(_) => "grl",
+            ExportFormat::Json => "json",
+            ExportFormat::Csv(_) => "csv",
+            ExportFormat::Drl(_) => "drl",
+        }
+    }
+
+    /// MIME type for uploads
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Grl(_) | ExportFormat::Drl(_) => "text/plain; charset=utf-8",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Csv(_) => "text/csv; charset=utf-8",
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! Differential-privacy noise for exported rule metrics
+//!
+//! Mining is always exact. This module perturbs the numbers that leave the
+//! process, so shared exports don't reveal exact low counts.
+//!
+//! # Model
+//!
+//! For each rule the three underlying counts (antecedent, consequent and
+//! joint) are recovered from its metrics and the transaction total. Adding or
+//! removing one transaction changes each count by at most 1, so the L1
+//! sensitivity of the count triple is 3. Each count gets Laplace noise with
+//! scale `3 / epsilon`, which makes the release of one rule's metrics
+//! `epsilon`-differentially private. Metrics are then recomputed from the
+//! noised counts. Releasing `k` rules composes to `k * epsilon` in the worst
+//! case; pick epsilon with the rule count in mind.
+//!
+//! Rules whose true joint count is below `min_count` are suppressed before
+//! any noise is drawn.
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{MiningError, Result};
+use crate::rng::{clock_seed, SplitMix64};
+use crate::types::AssociationRule;
+
+/// Counts a single transaction can change per rule (antecedent, consequent, joint)
+const SENSITIVITY: f64 = 3.0;
+
+/// Privacy budget per released rule (must be > 0; smaller = more noise)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Epsilon(pub f64);
+
+/// Opt-in noise settings for exports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyConfig {
+    pub epsilon: Epsilon,
+
+    /// Suppress rules whose true joint count is below this
+    pub min_count: usize,
+
+    /// RNG seed (None = seeded from the clock)
+    pub seed: Option<u64>,
+}
+
+impl PrivacyConfig {
+    /// Create config with `epsilon`, a suppression floor of 10 and random seed
+    pub fn new(epsilon: f64) -> Self {
+        Self {
+            epsilon: Epsilon(epsilon),
+            min_count: 10,
+            seed: None,
+        }
+    }
+
+    /// Set the suppression floor
+    pub fn with_min_count(mut self, min_count: usize) -> Self {
+        self.min_count = min_count;
+        self
+    }
+
+    /// Use a fixed seed for reproducible noise
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Check that epsilon is positive and finite
+    pub fn validate(&self) -> Result<()> {
+        if !(self.epsilon.0.is_finite() && self.epsilon.0 > 0.0) {
+            return Err(MiningError::InvalidConfig(format!(
+                "Privacy epsilon must be positive, got {}",
+                self.epsilon.0
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Export manifest entry describing how metrics were perturbed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrivacyManifest {
+    /// Always true for noised exports
+    pub noised: bool,
+    pub epsilon: f64,
+    pub sensitivity: f64,
+    pub mechanism: String,
+    pub min_count: usize,
+    /// Number of rules dropped for falling below `min_count`
+    pub suppressed_rules: usize,
+}
+
+/// Rules with noised metrics plus the manifest to publish alongside them
+#[derive(Debug, Clone)]
+pub struct NoisedRules {
+    pub rules: Vec<AssociationRule>,
+    pub manifest: PrivacyManifest,
+}
+
+/// Apply suppression and Laplace noise to rule metrics
+///
+/// `total_transactions` is the size of the dataset the rules were mined from.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::export::privacy::{apply_privacy, PrivacyConfig};
+/// # let rules = Vec::new();
+///
+/// let config = PrivacyConfig::new(1.0).with_min_count(20).with_seed(7);
+/// let noised = apply_privacy(&rules, 10_000, &config)?;
+/// assert!(noised.manifest.noised);
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+pub fn apply_privacy(
+    rules: &[AssociationRule],
+    total_transactions: usize,
+    config: &PrivacyConfig,
+) -> Result<NoisedRules> {
+    config.validate()?;
+    if total_transactions == 0 {
+        return Err(MiningError::InsufficientData(
+            "Cannot noise metrics without a transaction total".to_string(),
+        ));
+    }
+
+    let total = total_transactions as f64;
+    let scale = SENSITIVITY / config.epsilon.0;
+    let mut rng = SplitMix64::new(config.seed.unwrap_or_else(clock_seed));
+
+    let mut noised = Vec::with_capacity(rules.len());
+    let mut suppressed_rules = 0;
+
+    for rule in rules {
+        let counts = RuleCounts::from_rule(rule, total);
+        if counts.joint < config.min_count as f64 {
+            suppressed_rules += 1;
+            continue;
+        }
+
+        // Noised counts are kept consistent: joint <= antecedent, consequent <= total
+        let noisy = |count: f64, rng: &mut SplitMix64| {
+            (count + rng.laplace(scale)).round().clamp(1.0, total) as usize
+        };
+        let antecedent = noisy(counts.antecedent, &mut rng);
+        let consequent = noisy(counts.consequent, &mut rng);
+        let joint = noisy(counts.joint, &mut rng)
+            .min(antecedent)
+            .min(consequent);
+
+        let mut metrics = crate::mining::metrics_from_counts(
+            antecedent,
+            consequent,
+            joint,
+            total,
+            rule.metrics.computed(),
+        );
+        metrics.avg_time_gap = rule.metrics.avg_time_gap;
+        metrics.time_variance = rule.metrics.time_variance;
+
+        noised.push(AssociationRule {
+            antecedent: rule.antecedent.clone(),
+            consequent: rule.consequent.clone(),
+            metrics,
+        });
+    }
+
+    Ok(NoisedRules {
+        rules: noised,
+        manifest: PrivacyManifest {
+            noised: true,
+            epsilon: config.epsilon.0,
+            sensitivity: SENSITIVITY,
+            mechanism: "laplace".to_string(),
+            min_count: config.min_count,
+            suppressed_rules,
+        },
+    })
+}
+
+/// Counts recovered from a rule's metrics
+struct RuleCounts {
+    antecedent: f64,
+    consequent: f64,
+    joint: f64,
+}
+
+impl RuleCounts {
+    fn from_rule(rule: &AssociationRule, total: f64) -> Self {
+        let m = &rule.metrics;
+        let joint = (m.support * total).round();
+        let antecedent = if m.confidence > 0.0 {
+            (joint / m.confidence).round()
+        } else {
+            joint
+        };
+        // lift = confidence / P(consequent)
+        let consequent = if m.lift > 0.0 {
+            (m.confidence * total / m.lift).round()
+        } else {
+            joint
+        };
+
+        Self {
+            antecedent,
+            consequent,
+            joint,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MetricSet;
+    use crate::mining::metrics_from_counts;
+
+    fn rule(items: (&str, &str), counts: (usize, usize, usize), total: f64) -> AssociationRule {
+        AssociationRule {
+            antecedent: vec![items.0.to_string()],
+            consequent: vec![items.1.to_string()],
+            metrics: metrics_from_counts(counts.0, counts.1, counts.2, total, MetricSet::default()),
+        }
+    }
+
+    fn rules() -> Vec<AssociationRule> {
+        vec![
+            rule(("Laptop", "Mouse"), (400, 500, 300), 1000.0),
+            rule(("Phone", "Case"), (200, 250, 150), 1000.0),
+            rule(("Rare", "Item"), (5, 8, 3), 1000.0),
+        ]
+    }
+
+    #[test]
+    fn test_suppresses_low_count_rules() {
+        let config = PrivacyConfig::new(1.0).with_min_count(10).with_seed(42);
+        let noised = apply_privacy(&rules(), 1000, &config).unwrap();
+
+        assert_eq!(noised.rules.len(), 2);
+        assert!(!noised.rules.iter().any(|r| r.antecedent == ["Rare"]));
+        assert_eq!(noised.manifest.suppressed_rules, 1);
+        assert!(noised.manifest.noised);
+        assert_eq!(noised.manifest.sensitivity, SENSITIVITY);
+    }
+
+    #[test]
+    fn test_noised_metrics_differ_from_exact() {
+        let exact = rules();
+        let config = PrivacyConfig::new(0.5).with_min_count(10).with_seed(42);
+        let noised = apply_privacy(&exact, 1000, &config).unwrap();
+
+        for (n, e) in noised.rules.iter().zip(&exact) {
+            assert_eq!(n.antecedent, e.antecedent);
+            assert_ne!(
+                (n.metrics.support, n.metrics.confidence, n.metrics.lift),
+                (e.metrics.support, e.metrics.confidence, e.metrics.lift)
+            );
+            // Noise is calibrated, so large counts stay in the right ballpark
+            assert!((n.metrics.confidence - e.metrics.confidence).abs() < 0.2);
+            assert!(n.metrics.confidence <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_seeded_noise_is_reproducible() {
+        let config = PrivacyConfig::new(1.0).with_seed(7);
+        let a = apply_privacy(&rules(), 1000, &config).unwrap();
+        let b = apply_privacy(&rules(), 1000, &config).unwrap();
+
+        for (x, y) in a.rules.iter().zip(&b.rules) {
+            assert_eq!(x.metrics.support, y.metrics.support);
+            assert_eq!(x.metrics.confidence, y.metrics.confidence);
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_epsilon() {
+        assert!(apply_privacy(&rules(), 1000, &PrivacyConfig::new(0.0)).is_err());
+        assert!(apply_privacy(&rules(), 1000, &PrivacyConfig::new(f64::NAN)).is_err());
+    }
+}
@@ -0,0 +1,472 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use crate::mining::stats::MiningStats;
+use crate::types::AssociationRule;
+
+/// Metric used to rank rules in the report's top-N table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReportSortBy {
+    Confidence,
+    Support,
+    Lift,
+    /// `AssociationRule::quality_score`
+    #[default]
+    QualityScore,
+}
+
+impl ReportSortBy {
+    fn key(self, rule: &AssociationRule) -> f64 {
+        match self {
+            ReportSortBy::Confidence => rule.metrics.confidence,
+            ReportSortBy::Support => rule.metrics.support,
+            ReportSortBy::Lift => rule.metrics.lift,
+            ReportSortBy::QualityScore => rule.quality_score(),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ReportSortBy::Confidence => "confidence",
+            ReportSortBy::Support => "support",
+            ReportSortBy::Lift => "lift",
+            ReportSortBy::QualityScore => "quality score",
+        }
+    }
+}
+
+/// Options for `ReportExporter`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportOptions {
+    /// Number of rules (and items) listed in the top-N tables
+    pub top_n: usize,
+
+    /// Metric the top-N rules table is sorted by (descending)
+    pub sort_by: ReportSortBy,
+
+    /// Heading of the report
+    pub title: String,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            top_n: 10,
+            sort_by: ReportSortBy::default(),
+            title: "Mining Report".to_string(),
+        }
+    }
+}
+
+impl ReportOptions {
+    /// Set how many rules and items the top-N tables list
+    pub fn with_top_n(mut self, top_n: usize) -> Self {
+        self.top_n = top_n;
+        self
+    }
+
+    /// Set the metric the rules table is sorted by
+    pub fn with_sort_by(mut self, sort_by: ReportSortBy) -> Self {
+        self.sort_by = sort_by;
+        self
+    }
+
+    /// Set the report heading
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+}
+
+/// Render a human-readable mining report as Markdown or HTML
+///
+/// The report has a summary (rule count, average confidence and lift, the
+/// items appearing in most rules), the top-N rules with their metrics, and a
+/// breakdown of rules by antecedent size.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::export::{ReportExporter, ReportOptions, ReportSortBy};
+/// use rust_rule_miner::mining::MiningStats;
+/// # let rules = Vec::new();
+///
+/// let options = ReportOptions::default()
+///     .with_top_n(5)
+///     .with_sort_by(ReportSortBy::Lift);
+/// let markdown = ReportExporter::to_markdown(&rules, &MiningStats::default(), &options);
+/// assert!(markdown.starts_with("# Mining Report"));
+/// ```
+pub struct ReportExporter;
+
+impl ReportExporter {
+    /// Render the report as Markdown
+    pub fn to_markdown(
+        rules: &[AssociationRule],
+        stats: &MiningStats,
+        options: &ReportOptions,
+    ) -> String {
+        let report = Report::build(rules, stats, options);
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# {}\n", options.title);
+        for table in &report.tables {
+            let _ = writeln!(out, "## {}\n", table.heading);
+            if table.rows.is_empty() {
+                let _ = writeln!(out, "_None_\n");
+                continue;
+            }
+            let _ = writeln!(out, "| {} |", table.columns.join(" | "));
+            let _ = writeln!(
+                out,
+                "|{}|",
+                table
+                    .columns
+                    .iter()
+                    .map(|_| "---")
+                    .collect::<Vec<_>>()
+                    .join("|")
+            );
+            for row in &table.rows {
+                let cells: Vec<String> = row.iter().map(|cell| escape_markdown(cell)).collect();
+                let _ = writeln!(out, "| {} |", cells.join(" | "));
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Render the report as a self-contained HTML page
+    pub fn to_html(
+        rules: &[AssociationRule],
+        stats: &MiningStats,
+        options: &ReportOptions,
+    ) -> String {
+        let report = Report::build(rules, stats, options);
+        let title = escape_html(&options.title);
+        let mut out = String::new();
+
+        let _ = writeln!(out, "<!DOCTYPE html>");
+        let _ = writeln!(out, "<html lang=\"en\">");
+        let _ = writeln!(out, "<head>");
+        let _ = writeln!(out, "<meta charset=\"utf-8\">");
+        let _ = writeln!(out, "<title>{}</title>", title);
+        let _ = writeln!(out, "<style>{}</style>", HTML_STYLE);
+        let _ = writeln!(out, "</head>");
+        let _ = writeln!(out, "<body>");
+        let _ = writeln!(out, "<h1>{}</h1>", title);
+        for table in &report.tables {
+            let _ = writeln!(out, "<h2>{}</h2>", escape_html(&table.heading));
+            if table.rows.is_empty() {
+                let _ = writeln!(out, "<p><em>None</em></p>");
+                continue;
+            }
+            let _ = writeln!(out, "<table>");
+            let _ = write!(out, "<tr>");
+            for column in &table.columns {
+                let _ = write!(out, "<th>{}</th>", escape_html(column));
+            }
+            let _ = writeln!(out, "</tr>");
+            for row in &table.rows {
+                let _ = write!(out, "<tr>");
+                for cell in row {
+                    let _ = write!(out, "<td>{}</td>", escape_html(cell));
+                }
+                let _ = writeln!(out, "</tr>");
+            }
+            let _ = writeln!(out, "</table>");
+        }
+        let _ = writeln!(out, "</body>");
+        let _ = writeln!(out, "</html>");
+
+        out
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:sans-serif;margin:2em}\
+table{border-collapse:collapse;margin-bottom:1.5em}\
+th,td{border:1px solid #ccc;padding:4px 8px;text-align:left}\
+th{background:#f0f0f0}";
+
+/// Format-independent report content, rendered by each output format
+struct Report {
+    tables: Vec<Table>,
+}
+
+struct Table {
+    heading: String,
+    columns: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Report {
+    fn build(rules: &[AssociationRule], stats: &MiningStats, options: &ReportOptions) -> Self {
+        Self {
+            tables: vec![
+                Self::summary(rules, stats),
+                Self::top_items(rules, options.top_n),
+                Self::top_rules(rules, options),
+                Self::by_antecedent_size(rules),
+            ],
+        }
+    }
+
+    fn summary(rules: &[AssociationRule], stats: &MiningStats) -> Table {
+        let avg_confidence = average(rules.iter().map(|r| r.metrics.confidence));
+        // Infinite lift would swamp the average
+        let avg_lift = average(
+            rules
+                .iter()
+                .map(|r| r.metrics.lift)
+                .filter(|lift| lift.is_finite()),
+        );
+
+        let rows = vec![
+            vec!["Rules".to_string(), rules.len().to_string()],
+            vec![
+                "Transactions processed".to_string(),
+                stats.transactions_processed.to_string(),
+            ],
+            vec![
+                "Frequent itemsets".to_string(),
+                stats.frequent_itemsets_count.to_string(),
+            ],
+            vec![
+                "Average confidence".to_string(),
+                avg_confidence.map_or_else(|| "-".to_string(), percent),
+            ],
+            vec![
+                "Average lift".to_string(),
+                avg_lift.map_or_else(|| "-".to_string(), decimal),
+            ],
+        ];
+
+        Table {
+            heading: "Summary".to_string(),
+            columns: vec!["Metric", "Value"],
+            rows,
+        }
+    }
+
+    fn top_items(rules: &[AssociationRule], top_n: usize) -> Table {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for rule in rules {
+            for item in rule.antecedent.iter().chain(&rule.consequent) {
+                *counts.entry(item.as_str()).or_insert(0) += 1;
+            }
+        }
+        let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        counts.truncate(top_n);
+
+        Table {
+            heading: "Top items".to_string(),
+            columns: vec!["Item", "Rules"],
+            rows: counts
+                .into_iter()
+                .map(|(item, count)| vec![item.to_string(), count.to_string()])
+                .collect(),
+        }
+    }
+
+    fn top_rules(rules: &[AssociationRule], options: &ReportOptions) -> Table {
+        let mut ranked: Vec<&AssociationRule> = rules.iter().collect();
+        ranked.sort_by(|a, b| {
+            options
+                .sort_by
+                .key(b)
+                .partial_cmp(&options.sort_by.key(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        ranked.truncate(options.top_n);
+
+        Table {
+            heading: format!("Top {} rules by {}", ranked.len(), options.sort_by.label()),
+            columns: vec![
+                "#",
+                "Antecedent",
+                "Consequent",
+                "Confidence",
+                "Support",
+                "Lift",
+            ],
+            rows: ranked
+                .into_iter()
+                .enumerate()
+                .map(|(idx, rule)| {
+                    vec![
+                        (idx + 1).to_string(),
+                        rule.antecedent.join(", "),
+                        rule.consequent.join(", "),
+                        percent(rule.metrics.confidence),
+                        percent(rule.metrics.support),
+                        decimal(rule.metrics.lift),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    fn by_antecedent_size(rules: &[AssociationRule]) -> Table {
+        let mut sizes: BTreeMap<usize, usize> = BTreeMap::new();
+        for rule in rules {
+            *sizes.entry(rule.antecedent.len()).or_insert(0) += 1;
+        }
+
+        Table {
+            heading: "Rules by antecedent size".to_string(),
+            columns: vec!["Antecedent size", "Rules", "Share"],
+            rows: sizes
+                .into_iter()
+                .map(|(size, count)| {
+                    vec![
+                        size.to_string(),
+                        count.to_string(),
+                        percent(count as f64 / rules.len() as f64),
+                    ]
+                })
+                .collect(),
+        }
+    }
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+fn percent(value: f64) -> String {
+    format!("{:.1}%", value * 100.0)
+}
+
+fn decimal(value: f64) -> String {
+    if value.is_finite() {
+        format!("{:.2}", value)
+    } else {
+        "inf".to_string()
+    }
+}
+
+fn escape_markdown(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+
+    fn sample_rules() -> Vec<AssociationRule> {
+        vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.9, 1.2),
+            test_rule(&["Laptop", "Mouse"], &["Keyboard"], 0.7, 2.4),
+            test_rule(&["Phone"], &["Case"], 0.8, 1.8),
+        ]
+    }
+
+    /// Data rows of the markdown table following `heading`
+    fn table_rows(markdown: &str, heading: &str) -> Vec<Vec<String>> {
+        markdown
+            .split("\n## ")
+            .find(|section| section.starts_with(heading))
+            .unwrap()
+            .lines()
+            .filter(|line| line.starts_with('|'))
+            .skip(2)
+            .map(|line| {
+                line.trim_matches('|')
+                    .split(" | ")
+                    .map(|cell| cell.trim().to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_markdown_rules_table_matches_rules() {
+        let rules = sample_rules();
+        let options = ReportOptions::default().with_sort_by(ReportSortBy::Confidence);
+        let markdown = ReportExporter::to_markdown(&rules, &MiningStats::default(), &options);
+
+        let rows = table_rows(&markdown, "Top 3 rules by confidence");
+        assert_eq!(rows.len(), rules.len());
+        assert_eq!(
+            rows[0],
+            vec!["1", "Laptop", "Mouse", "90.0%", "20.0%", "1.20"]
+        );
+        assert_eq!(
+            rows[1],
+            vec!["2", "Phone", "Case", "80.0%", "20.0%", "1.80"]
+        );
+        assert_eq!(
+            rows[2],
+            vec!["3", "Laptop, Mouse", "Keyboard", "70.0%", "20.0%", "2.40"]
+        );
+    }
+
+    #[test]
+    fn test_top_n_and_sort_by_lift() {
+        let rules = sample_rules();
+        let options = ReportOptions::default()
+            .with_top_n(1)
+            .with_sort_by(ReportSortBy::Lift);
+        let markdown = ReportExporter::to_markdown(&rules, &MiningStats::default(), &options);
+
+        let rows = table_rows(&markdown, "Top 1 rules by lift");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][2], "Keyboard");
+
+        let items = table_rows(&markdown, "Top items");
+        assert_eq!(items, vec![vec!["Laptop", "2"]]);
+    }
+
+    #[test]
+    fn test_summary_and_distribution() {
+        let rules = sample_rules();
+        let stats = MiningStats {
+            transactions_processed: 50,
+            ..MiningStats::default()
+        };
+        let markdown = ReportExporter::to_markdown(&rules, &stats, &ReportOptions::default());
+
+        let summary = table_rows(&markdown, "Summary");
+        assert_eq!(summary[0], vec!["Rules", "3"]);
+        assert_eq!(summary[1], vec!["Transactions processed", "50"]);
+        assert_eq!(summary[3], vec!["Average confidence", "80.0%"]);
+        assert_eq!(summary[4], vec!["Average lift", "1.80"]);
+
+        let sizes = table_rows(&markdown, "Rules by antecedent size");
+        assert_eq!(
+            sizes,
+            vec![vec!["1", "2", "66.7%"], vec!["2", "1", "33.3%"]]
+        );
+    }
+
+    #[test]
+    fn test_html_is_self_contained_and_escaped() {
+        let rules = vec![test_rule(&["<script>"], &["A&B"], 0.9, 1.5)];
+        let html =
+            ReportExporter::to_html(&rules, &MiningStats::default(), &ReportOptions::default());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<td>&lt;script&gt;</td><td>A&amp;B</td><td>90.0%</td>"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn test_empty_rules() {
+        let markdown =
+            ReportExporter::to_markdown(&[], &MiningStats::default(), &ReportOptions::default());
+        assert!(markdown.contains("| Average confidence | - |"));
+        assert!(markdown.contains("## Top 0 rules by quality score\n\n_None_"));
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::normalize::{CaseFold, ItemNormalizer, UnicodeForm};
+use crate::transaction::Transaction;
+use crate::types::AssociationRule;
+
+/// Sample of recent baskets used to smoke-check rules before deployment
+///
+/// A rule is *live* if every antecedent item appears together in at least one
+/// basket; otherwise it can never fire on data like the sample.
+#[derive(Debug, Clone, Default)]
+pub struct BasketSample {
+    baskets: Vec<HashSet<String>>,
+    /// Normalized item -> first (sorted) vocabulary item with that form
+    normalized: BTreeMap<String, String>,
+    vocabulary: HashSet<String>,
+}
+
+impl BasketSample {
+    /// Build a sample from raw baskets
+    pub fn new<B, I>(baskets: B) -> Self
+    where
+        B: IntoIterator<Item = I>,
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let baskets: Vec<HashSet<String>> = baskets
+            .into_iter()
+            .map(|basket| basket.into_iter().map(Into::into).collect())
+            .collect();
+
+        let vocabulary: HashSet<String> = baskets.iter().flatten().cloned().collect();
+        let mut sorted: Vec<&String> = vocabulary.iter().collect();
+        sorted.sort();
+
+        let mut normalized = BTreeMap::new();
+        for item in sorted {
+            normalized
+                .entry(normalize_item(item))
+                .or_insert_with(|| item.clone());
+        }
+
+        Self {
+            baskets,
+            normalized,
+            vocabulary,
+        }
+    }
+
+    /// Build a sample from transactions
+    pub fn from_transactions(transactions: &[Transaction]) -> Self {
+        Self::new(transactions.iter().map(|tx| tx.items.iter().cloned()))
+    }
+
+    /// Number of baskets in the sample
+    pub fn len(&self) -> usize {
+        self.baskets.len()
+    }
+
+    /// Check if the sample has no baskets
+    pub fn is_empty(&self) -> bool {
+        self.baskets.is_empty()
+    }
+
+    /// Check if the rule's antecedent appears in at least one basket
+    pub fn is_live(&self, rule: &AssociationRule) -> bool {
+        self.baskets
+            .iter()
+            .any(|basket| rule.antecedent.iter().all(|item| basket.contains(item)))
+    }
+
+    /// Rule items missing from the sample that match a sample item once case,
+    /// separators and namespace prefixes are ignored
+    ///
+    /// Catches exports built with a different item format than live data,
+    /// e.g. `electronics:usb_hub` in rules vs `USB Hub` in baskets.
+    pub fn vocabulary_mismatches(&self, rules: &[AssociationRule]) -> Vec<VocabularyMismatch> {
+        let mut seen = HashSet::new();
+        let mut mismatches = Vec::new();
+
+        for item in rules
+            .iter()
+            .flat_map(|rule| rule.antecedent.iter().chain(&rule.consequent))
+        {
+            if self.vocabulary.contains(item) || !seen.insert(item.as_str()) {
+                continue;
+            }
+            if let Some(suggestion) = self.normalized.get(&normalize_item(item)) {
+                mismatches.push(VocabularyMismatch {
+                    item: item.clone(),
+                    suggestion: suggestion.clone(),
+                });
+            }
+        }
+
+        mismatches
+    }
+}
+
+/// Rule item that only matches the sample vocabulary after normalization
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VocabularyMismatch {
+    /// Item as written in the rule
+    pub item: String,
+    /// Closest item seen in the sample
+    pub suggestion: String,
+}
+
+/// Export-time smoke check against a basket sample
+#[derive(Debug, Clone)]
+pub struct SmokeCheck {
+    pub sample: BasketSample,
+    /// Drop dead rules from the export instead of only annotating them
+    pub exclude_dead: bool,
+}
+
+impl SmokeCheck {
+    /// Annotate dead rules without excluding them
+    pub fn new(sample: BasketSample) -> Self {
+        Self {
+            sample,
+            exclude_dead: false,
+        }
+    }
+
+    /// Drop dead rules from the export
+    pub fn with_exclude_dead(mut self, exclude_dead: bool) -> Self {
+        self.exclude_dead = exclude_dead;
+        self
+    }
+}
+
+const VOCABULARY_NORMALIZER: ItemNormalizer = ItemNormalizer {
+    form: UnicodeForm::Nfkc,
+    case_fold: CaseFold::Unicode,
+    fold_width: true,
+    trim: true,
+};
+
+/// Fold Unicode variants (NFKC, full width), lowercase, drop any namespace
+/// prefix (`a:b`, `a/b`) and separators
+fn normalize_item(item: &str) -> String {
+    let item = VOCABULARY_NORMALIZER.normalize(item);
+    let name = item.rsplit([':', '/']).next().unwrap_or(&item);
+    name.chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '_' | '-' | '.'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_item() {
+        assert_eq!(normalize_item("electronics:usb_hub"), "usbhub");
+        assert_eq!(normalize_item("USB Hub"), "usbhub");
+        assert_eq!(normalize_item("store/Wi-Fi"), "wifi");
+        assert_eq!(normalize_item("ＵＳＢ\u{3000}Ｈｕｂ"), "usbhub");
+        assert_eq!(normalize_item("Ca\u{300} phe\u{302}"), "c\u{E0}ph\u{EA}");
+    }
+
+    #[test]
+    fn test_vocabulary_mismatches() {
+        let sample = BasketSample::new(vec![vec!["USB Hub", "Laptop"]]);
+        let rule = AssociationRule::new(
+            ["electronics:usb_hub", "Laptop"],
+            ["Unknown"],
+            crate::types::PatternMetrics::basic(0.8, 0.5, 1.5),
+        );
+
+        assert!(!sample.is_live(&rule));
+        assert_eq!(
+            sample.vocabulary_mismatches(&[rule.clone(), rule]),
+            vec![VocabularyMismatch {
+                item: "electronics:usb_hub".to_string(),
+                suggestion: "USB Hub".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use crate::errors::{MiningError, Result};
+use crate::types::AssociationRule;
+
+/// Table and column names used by `SqlExporter`
+///
+/// Names are written as-is, so they must be valid identifiers in the target
+/// database (quote them here if needed, e.g. `"\"Rules\""`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlConfig {
+    /// Target table for INSERT statements
+    pub table: String,
+    /// Column holding the antecedent as a JSON array
+    pub antecedent_column: String,
+    /// Column holding the consequent as a JSON array
+    pub consequent_column: String,
+    pub support_column: String,
+    pub confidence_column: String,
+    pub lift_column: String,
+    pub conviction_column: String,
+    /// Column matched against antecedent items in CASE expressions
+    pub item_column: String,
+    /// Alias for the CASE expression
+    pub case_alias: String,
+    /// Rows per INSERT statement
+    pub batch_size: usize,
+}
+
+impl Default for SqlConfig {
+    fn default() -> Self {
+        Self {
+            table: "association_rules".to_string(),
+            antecedent_column: "antecedent".to_string(),
+            consequent_column: "consequent".to_string(),
+            support_column: "support".to_string(),
+            confidence_column: "confidence".to_string(),
+            lift_column: "lift".to_string(),
+            conviction_column: "conviction".to_string(),
+            item_column: "item".to_string(),
+            case_alias: "recommendation".to_string(),
+            batch_size: 500,
+        }
+    }
+}
+
+impl SqlConfig {
+    /// Set the target table
+    pub fn with_table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    /// Set the column used in CASE expressions
+    pub fn with_item_column(mut self, column: impl Into<String>) -> Self {
+        self.item_column = column.into();
+        self
+    }
+
+    /// Set the number of rows per INSERT statement
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+/// Export association rules as SQL for warehouse materialization
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::export::{SqlConfig, SqlExporter};
+/// # let rules = Vec::new();
+///
+/// let config = SqlConfig::default().with_table("analytics.rules").with_batch_size(100);
+/// let ddl = SqlExporter::create_table(&config);
+/// let inserts = SqlExporter::to_inserts(&rules, &config)?;
+/// let case = SqlExporter::to_case_expression(&rules, &config);
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+pub struct SqlExporter;
+
+impl SqlExporter {
+    /// `CREATE TABLE` statement matching the INSERT layout
+    pub fn create_table(config: &SqlConfig) -> String {
+        format!(
+            "CREATE TABLE {} (\n    {} TEXT NOT NULL,\n    {} TEXT NOT NULL,\n    \
+             {} DOUBLE PRECISION NOT NULL,\n    {} DOUBLE PRECISION NOT NULL,\n    \
+             {} DOUBLE PRECISION NOT NULL,\n    {} DOUBLE PRECISION\n);\n",
+            config.table,
+            config.antecedent_column,
+            config.consequent_column,
+            config.support_column,
+            config.confidence_column,
+            config.lift_column,
+            config.conviction_column
+        )
+    }
+
+    /// INSERT statements, `batch_size` rows each
+    ///
+    /// Antecedent and consequent are stored as JSON arrays. Infinite
+    /// conviction is written as `NULL`.
+    pub fn to_inserts(rules: &[AssociationRule], config: &SqlConfig) -> Result<String> {
+        if config.batch_size == 0 {
+            return Err(MiningError::InvalidConfig(
+                "SQL batch_size must be at least 1".to_string(),
+            ));
+        }
+
+        let mut sql = String::new();
+        for batch in rules.chunks(config.batch_size) {
+            sql.push_str(&format!(
+                "INSERT INTO {} ({}, {}, {}, {}, {}, {}) VALUES\n",
+                config.table,
+                config.antecedent_column,
+                config.consequent_column,
+                config.support_column,
+                config.confidence_column,
+                config.lift_column,
+                config.conviction_column
+            ));
+
+            let rows: Vec<String> = batch
+                .iter()
+                .map(|rule| {
+                    Ok(format!(
+                        "    ({}, {}, {}, {}, {}, {})",
+                        quote_literal(&serde_json::to_string(&rule.antecedent)?),
+                        quote_literal(&serde_json::to_string(&rule.consequent)?),
+                        number(rule.metrics.support),
+                        number(rule.metrics.confidence),
+                        number(rule.metrics.lift),
+                        rule.metrics
+                            .conviction
+                            .map_or_else(|| "NULL".to_string(), number)
+                    ))
+                })
+                .collect::<Result<_>>()?;
+
+            sql.push_str(&rows.join(",\n"));
+            sql.push_str(";\n");
+        }
+
+        Ok(sql)
+    }
+
+    /// A `CASE` expression mapping `item_column` to the recommended item(s)
+    ///
+    /// Only single-item antecedents can be matched against one column; other
+    /// rules are listed as skipped in a comment. WHEN branches are ordered by
+    /// confidence (highest first) since the first match wins.
+    pub fn to_case_expression(rules: &[AssociationRule], config: &SqlConfig) -> String {
+        let mut candidates: Vec<&AssociationRule> = rules
+            .iter()
+            .filter(|rule| rule.antecedent.len() == 1)
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.metrics
+                .confidence
+                .partial_cmp(&a.metrics.confidence)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut sql = String::new();
+        let skipped = rules.len() - candidates.len();
+        if skipped > 0 {
+            sql.push_str(&format!(
+                "-- skipped {} rule(s) with multi-item antecedents\n",
+                skipped
+            ));
+        }
+
+        sql.push_str("CASE\n");
+        for rule in candidates {
+            sql.push_str(&format!(
+                "    WHEN {} = {} THEN {}\n",
+                config.item_column,
+                quote_literal(&rule.antecedent[0]),
+                quote_literal(&rule.consequent.join(", "))
+            ));
+        }
+        sql.push_str(&format!("    ELSE NULL\nEND AS {}\n", config.case_alias));
+
+        sql
+    }
+}
+
+/// Quote a SQL string literal, doubling single quotes
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Render a metric, using NULL for non-finite values
+fn number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "NULL".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+
+    #[test]
+    fn test_insert_escaping() {
+        let rules = vec![test_rule(
+            &["Kellogg's Corn Flakes"],
+            &["Ben & Jerry's"],
+            1.0,
+            1.5,
+        )];
+        let sql = SqlExporter::to_inserts(&rules, &SqlConfig::default()).unwrap();
+
+        assert!(sql.starts_with(
+            "INSERT INTO association_rules (antecedent, consequent, support, confidence, lift, conviction) VALUES\n"
+        ));
+        assert!(sql.contains(
+            "    ('[\"Kellogg''s Corn Flakes\"]', '[\"Ben & Jerry''s\"]', 0.2, 1, 1.5, NULL);\n"
+        ));
+        assert_eq!(sql.matches('\'').count() % 2, 0);
+    }
+
+    #[test]
+    fn test_insert_batching() {
+        let rules: Vec<_> = (0..5)
+            .map(|i| test_rule(&[&format!("item{}", i)], &["x"], 0.5, 1.5))
+            .collect();
+
+        let config = SqlConfig::default().with_batch_size(2);
+        let sql = SqlExporter::to_inserts(&rules, &config).unwrap();
+        assert_eq!(sql.matches("INSERT INTO").count(), 3);
+        assert_eq!(sql.matches("    ('").count(), 5);
+
+        assert!(matches!(
+            SqlExporter::to_inserts(&rules, &config.with_batch_size(0)),
+            Err(MiningError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_case_expression() {
+        let rules = vec![
+            test_rule(&["O'Brien's Ale"], &["Pretzels"], 0.6, 1.5),
+            test_rule(&["Laptop"], &["Mouse", "Pad"], 0.9, 1.5),
+            test_rule(&["Laptop", "Mouse"], &["Hub"], 0.8, 1.5),
+        ];
+        let config = SqlConfig::default().with_item_column("p.product_name");
+        let sql = SqlExporter::to_case_expression(&rules, &config);
+
+        assert_eq!(
+            sql,
+            "-- skipped 1 rule(s) with multi-item antecedents\n\
+             CASE\n    \
+             WHEN p.product_name = 'Laptop' THEN 'Mouse, Pad'\n    \
+             WHEN p.product_name = 'O''Brien''s Ale' THEN 'Pretzels'\n    \
+             ELSE NULL\nEND AS recommendation\n"
+        );
+    }
+
+    #[test]
+    fn test_create_table() {
+        let ddl = SqlExporter::create_table(&SqlConfig::default().with_table("dw.rules"));
+        assert!(ddl.starts_with("CREATE TABLE dw.rules (\n"));
+        assert!(ddl.contains("    conviction DOUBLE PRECISION\n);"));
+    }
+}
@@ -0,0 +1,427 @@
+use std::path::Path;
+
+use excelstream::fast_writer::{StreamingZipReader, StreamingZipWriter, UltraLowMemoryWorkbook};
+use excelstream::{CellStyle, CellValue, StyledCell};
+
+use crate::config::MiningConfig;
+use crate::errors::{MiningError, Result};
+use crate::mining::stats::MiningStats;
+use crate::types::AssociationRule;
+
+/// Name of the sheet holding dataset stats and mining config
+pub const SUMMARY_SHEET: &str = "Summary";
+
+/// Name of the sheet holding one rule per row
+pub const RULES_SHEET: &str = "Rules";
+
+/// Header row of the rules sheet
+pub const RULES_HEADER: [&str; 7] = [
+    "Antecedent",
+    "Consequent",
+    "Confidence",
+    "Support",
+    "Lift",
+    "Priority",
+    "Tags",
+];
+
+/// Lift at or above which a rule is tagged `high-lift`
+const HIGH_LIFT: f64 = 2.0;
+
+/// Configuration for xlsx export
+#[derive(Debug, Clone, PartialEq)]
+pub struct XlsxConfig {
+    /// Separator used to join multi-item antecedents/consequents in one cell
+    pub item_separator: String,
+
+    /// Minimum confidence for the `High` priority bucket
+    pub high_priority: f64,
+
+    /// Minimum confidence for the `Medium` priority bucket
+    pub medium_priority: f64,
+}
+
+impl Default for XlsxConfig {
+    fn default() -> Self {
+        Self {
+            item_separator: "; ".to_string(),
+            high_priority: 0.8,
+            medium_priority: 0.6,
+        }
+    }
+}
+
+impl XlsxConfig {
+    /// Set the separator for multi-item sets
+    pub fn with_item_separator(mut self, separator: impl Into<String>) -> Self {
+        self.item_separator = separator.into();
+        self
+    }
+
+    /// Set the confidence cut-offs for the `High` and `Medium` buckets
+    pub fn with_priority_thresholds(mut self, high: f64, medium: f64) -> Self {
+        self.high_priority = high;
+        self.medium_priority = medium;
+        self
+    }
+
+    /// Priority bucket for a rule, based on its confidence
+    pub fn priority(&self, rule: &AssociationRule) -> &'static str {
+        if rule.metrics.confidence >= self.high_priority {
+            "High"
+        } else if rule.metrics.confidence >= self.medium_priority {
+            "Medium"
+        } else {
+            "Low"
+        }
+    }
+}
+
+/// Export association rules as a decision-table workbook for business review
+///
+/// The workbook has a `Summary` sheet (dataset stats and mining config) and a
+/// `Rules` sheet with one rule per row. Confidence and support use a
+/// percentage format and the header row of each sheet is frozen.
+///
+/// Items are always written as text cells, so an item such as `=SUM(A1:A9)`
+/// shows up verbatim instead of being evaluated as a formula.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::export::{XlsxConfig, XlsxExporter};
+/// use rust_rule_miner::mining::MiningStats;
+/// use rust_rule_miner::MiningConfig;
+/// # let rules = Vec::new();
+///
+/// # let path = std::env::temp_dir().join("rust_rule_miner_xlsx_doc.xlsx");
+/// let stats = MiningStats::default();
+/// XlsxExporter::write_xlsx(&rules, &stats, &MiningConfig::default(), &path)?;
+/// # std::fs::remove_file(&path).ok();
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+pub struct XlsxExporter;
+
+impl XlsxExporter {
+    /// Write the workbook with default config
+    pub fn write_xlsx<P: AsRef<Path>>(
+        rules: &[AssociationRule],
+        stats: &MiningStats,
+        mining_config: &MiningConfig,
+        path: P,
+    ) -> Result<()> {
+        Self::write_xlsx_with_config(rules, stats, mining_config, path, &XlsxConfig::default())
+    }
+
+    /// Write the workbook with custom config
+    pub fn write_xlsx_with_config<P: AsRef<Path>>(
+        rules: &[AssociationRule],
+        stats: &MiningStats,
+        mining_config: &MiningConfig,
+        path: P,
+        config: &XlsxConfig,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let mut workbook = UltraLowMemoryWorkbook::new(path).map_err(export_error)?;
+
+        workbook
+            .add_worksheet(SUMMARY_SHEET)
+            .map_err(export_error)?;
+        for row in Self::summary_rows(rules, stats, mining_config) {
+            workbook.write_row_styled(&row).map_err(export_error)?;
+        }
+
+        workbook.add_worksheet(RULES_SHEET).map_err(export_error)?;
+        workbook
+            .write_row_styled(&header(&RULES_HEADER))
+            .map_err(export_error)?;
+        for rule in rules {
+            workbook
+                .write_row_styled(&Self::rule_row(rule, config))
+                .map_err(export_error)?;
+        }
+
+        workbook.close().map_err(export_error)?;
+        freeze_header_rows(path)
+    }
+
+    fn summary_rows(
+        rules: &[AssociationRule],
+        stats: &MiningStats,
+        mining_config: &MiningConfig,
+    ) -> Vec<Vec<StyledCell>> {
+        let count = |n: usize| StyledCell::default_style(CellValue::Int(n as i64));
+        let max_size = match mining_config.max_itemset_size {
+            Some(size) => count(size),
+            None => text("unlimited"),
+        };
+
+        let rows = vec![
+            (
+                "Transactions processed",
+                count(stats.transactions_processed),
+            ),
+            ("Frequent itemsets", count(stats.frequent_itemsets_count)),
+            ("Rules generated", count(stats.rules_generated)),
+            ("Rules exported", count(rules.len())),
+            ("Algorithm", text(&format!("{:?}", mining_config.algorithm))),
+            (
+                "Min support",
+                number(mining_config.min_support(), CellStyle::NumberPercentage),
+            ),
+            (
+                "Min confidence",
+                number(mining_config.min_confidence(), CellStyle::NumberPercentage),
+            ),
+            (
+                "Min lift",
+                number(mining_config.min_lift(), CellStyle::NumberDecimal),
+            ),
+            ("Max itemset size", max_size),
+        ];
+
+        std::iter::once(header(&["Metric", "Value"]))
+            .chain(
+                rows.into_iter()
+                    .map(|(label, value)| vec![text(label), value]),
+            )
+            .collect()
+    }
+
+    fn rule_row(rule: &AssociationRule, config: &XlsxConfig) -> Vec<StyledCell> {
+        let lift = if rule.metrics.lift.is_finite() {
+            number(rule.metrics.lift, CellStyle::NumberDecimal)
+        } else {
+            StyledCell::default_style(CellValue::Empty)
+        };
+
+        vec![
+            text(&rule.antecedent.join(&config.item_separator)),
+            text(&rule.consequent.join(&config.item_separator)),
+            number(rule.metrics.confidence, CellStyle::NumberPercentage),
+            number(rule.metrics.support, CellStyle::NumberPercentage),
+            lift,
+            text(config.priority(rule)),
+            text(&tags(rule).join(", ")),
+        ]
+    }
+}
+
+/// Descriptive tags for a rule, used to filter the rules sheet
+fn tags(rule: &AssociationRule) -> Vec<&'static str> {
+    let mut tags = Vec::new();
+    if rule.metrics.lift >= HIGH_LIFT {
+        tags.push("high-lift");
+    } else if rule.metrics.lift < 1.0 {
+        tags.push("negative-correlation");
+    }
+    if rule.antecedent.len() > 1 {
+        tags.push("multi-item");
+    }
+    tags
+}
+
+fn header(labels: &[&str]) -> Vec<StyledCell> {
+    labels
+        .iter()
+        .map(|label| StyledCell::new(CellValue::String(label.to_string()), CellStyle::HeaderBold))
+        .collect()
+}
+
+/// Text cell; never a formula, whatever the content looks like
+fn text(value: &str) -> StyledCell {
+    StyledCell::default_style(CellValue::String(value.to_string()))
+}
+
+fn number(value: f64, style: CellStyle) -> StyledCell {
+    StyledCell::new(CellValue::Float(value), style)
+}
+
+fn export_error(e: impl std::fmt::Display) -> MiningError {
+    MiningError::ExportFailed(format!("xlsx: {}", e))
+}
+
+/// Pane definition freezing the first row of a sheet
+const FROZEN_HEADER: &str = "<sheetViews><sheetView workbookViewId=\"0\">\
+<pane ySplit=\"1\" topLeftCell=\"A2\" activePane=\"bottomLeft\" state=\"frozen\"/>\
+</sheetView></sheetViews>";
+
+/// Freeze the header row of every worksheet
+///
+/// The excelstream writer has no sheet view support, so the finished archive
+/// is re-packed with a `<sheetViews>` element added ahead of `<sheetData>`.
+fn freeze_header_rows(path: &Path) -> Result<()> {
+    let mut reader = StreamingZipReader::open(path).map_err(export_error)?;
+    let names: Vec<String> = reader.entries().iter().map(|e| e.name.clone()).collect();
+    let mut entries = Vec::with_capacity(names.len());
+    for name in names {
+        let mut data = reader.read_entry_by_name(&name).map_err(export_error)?;
+        if name.starts_with("xl/worksheets/") && name.ends_with(".xml") {
+            data = insert_sheet_views(&data)?;
+        }
+        entries.push((name, data));
+    }
+    drop(reader);
+
+    let mut writer = StreamingZipWriter::new(path).map_err(export_error)?;
+    for (name, data) in &entries {
+        writer.start_entry(name).map_err(export_error)?;
+        writer.write_data(data).map_err(export_error)?;
+    }
+    writer.finish().map_err(export_error)?;
+    Ok(())
+}
+
+fn insert_sheet_views(sheet_xml: &[u8]) -> Result<Vec<u8>> {
+    let xml = std::str::from_utf8(sheet_xml).map_err(export_error)?;
+    match xml.find("<sheetData") {
+        Some(pos) => {
+            let mut out = String::with_capacity(xml.len() + FROZEN_HEADER.len());
+            out.push_str(&xml[..pos]);
+            out.push_str(FROZEN_HEADER);
+            out.push_str(&xml[pos..]);
+            Ok(out.into_bytes())
+        }
+        None => Err(MiningError::ExportFailed(
+            "xlsx: worksheet has no <sheetData> element".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+    use excelstream::streaming_reader::StreamingReader;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "rust_rule_miner_{}_{}.xlsx",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn read_sheet(path: &Path, sheet: &str) -> Vec<Vec<CellValue>> {
+        let mut reader = StreamingReader::open(path).unwrap();
+        reader
+            .rows(sheet)
+            .unwrap()
+            .map(|row| row.unwrap().cells)
+            .collect()
+    }
+
+    #[test]
+    fn test_rules_sheet_round_trip() {
+        let rules = vec![
+            test_rule(&["Laptop", "Mouse"], &["Keyboard"], 0.85, 2.5),
+            test_rule(&["Phone"], &["Case"], 0.65, f64::INFINITY),
+        ];
+        let path = temp_path("xlsx_rules");
+        XlsxExporter::write_xlsx(
+            &rules,
+            &MiningStats::default(),
+            &MiningConfig::default(),
+            &path,
+        )
+        .unwrap();
+
+        let reader = StreamingReader::open(&path).unwrap();
+        assert_eq!(reader.sheet_names(), vec![SUMMARY_SHEET, RULES_SHEET]);
+        drop(reader);
+
+        let rows = read_sheet(&path, RULES_SHEET);
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(rows.len(), 3);
+        let header: Vec<String> = rows[0].iter().map(|c| c.as_string()).collect();
+        assert_eq!(header, RULES_HEADER);
+
+        assert_eq!(rows[1][0], CellValue::String("Laptop; Mouse".to_string()));
+        assert_eq!(rows[1][1], CellValue::String("Keyboard".to_string()));
+        assert_eq!(rows[1][2].as_f64(), Some(0.85));
+        assert_eq!(rows[1][3].as_f64(), Some(0.2));
+        assert_eq!(rows[1][4].as_f64(), Some(2.5));
+        assert_eq!(rows[1][5], CellValue::String("High".to_string()));
+        assert_eq!(
+            rows[1][6],
+            CellValue::String("high-lift, multi-item".to_string())
+        );
+
+        assert_eq!(rows[2][5], CellValue::String("Medium".to_string()));
+        assert!(rows[2][4].is_empty());
+    }
+
+    #[test]
+    fn test_summary_sheet_round_trip() {
+        let stats = MiningStats {
+            transactions_processed: 120,
+            frequent_itemsets_count: 14,
+            rules_generated: 9,
+            ..MiningStats::default()
+        };
+        let path = temp_path("xlsx_summary");
+        XlsxExporter::write_xlsx(&[], &stats, &MiningConfig::default(), &path).unwrap();
+        let rows = read_sheet(&path, SUMMARY_SHEET);
+        std::fs::remove_file(&path).ok();
+
+        let value = |label: &str| {
+            rows.iter()
+                .find(|row| row[0].as_string() == label)
+                .map(|row| row[1].clone())
+                .unwrap()
+        };
+        assert_eq!(value("Transactions processed").as_f64(), Some(120.0));
+        assert_eq!(value("Rules exported").as_f64(), Some(0.0));
+        assert_eq!(value("Min support").as_f64(), Some(0.1));
+        assert_eq!(value("Algorithm"), CellValue::String("Apriori".to_string()));
+        assert_eq!(
+            value("Max itemset size"),
+            CellValue::String("unlimited".to_string())
+        );
+    }
+
+    #[test]
+    fn test_formula_like_items_are_text() {
+        let rules = vec![test_rule(
+            &["=SUM(A1:A9)"],
+            &["+cmd|' /C calc'!A0"],
+            0.9,
+            1.5,
+        )];
+        let path = temp_path("xlsx_formula");
+        XlsxExporter::write_xlsx(
+            &rules,
+            &MiningStats::default(),
+            &MiningConfig::default(),
+            &path,
+        )
+        .unwrap();
+        let rows = read_sheet(&path, RULES_SHEET);
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(rows[1][0], CellValue::String("=SUM(A1:A9)".to_string()));
+        assert_eq!(
+            rows[1][1],
+            CellValue::String("+cmd|' /C calc'!A0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_header_row_is_frozen() {
+        let path = temp_path("xlsx_frozen");
+        XlsxExporter::write_xlsx(
+            &[],
+            &MiningStats::default(),
+            &MiningConfig::default(),
+            &path,
+        )
+        .unwrap();
+        let mut zip = StreamingZipReader::open(&path).unwrap();
+        let sheet = zip.read_entry_by_name("xl/worksheets/sheet2.xml").unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let sheet = String::from_utf8(sheet).unwrap();
+        let pane = sheet.find("state=\"frozen\"").unwrap();
+        assert!(pane < sheet.find("<sheetData").unwrap());
+    }
+}
@@ -0,0 +1,313 @@
+//! Quality gates for validating mined rules before deployment
+//!
+//! A `QualityGate` is a set of optional criteria. `evaluate()` checks every
+//! configured criterion and returns a `GateReport` with a pass/fail entry per
+//! criterion and an overall verdict, so nightly pipelines can refuse to ship
+//! a bad rule set.
+//!
+//! Gates can be loaded from TOML:
+//!
+//! ```toml
+//! min_rules = 10
+//! max_rules = 500
+//! min_avg_confidence = 0.75
+//! blocklist = ["Gift Card", "Returns"]
+//! min_coverage = 0.3
+//! ```
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{MiningError, Result};
+use crate::rules::RuleSet;
+use crate::transaction::Transaction;
+use crate::types::AssociationRule;
+
+/// Configurable quality criteria for a rule set
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QualityGate {
+    /// Minimum number of rules
+    pub min_rules: Option<usize>,
+
+    /// Maximum number of rules
+    pub max_rules: Option<usize>,
+
+    /// Minimum mean confidence across all rules
+    pub min_avg_confidence: Option<f64>,
+
+    /// Items that must not appear in any rule
+    pub blocklist: Vec<String>,
+
+    /// Minimum fraction of validation transactions matched by at least one rule
+    /// Example: 0.3 = rules fire for 30% of validation baskets
+    pub min_coverage: Option<f64>,
+}
+
+impl QualityGate {
+    /// Create a gate with no criteria (always passes)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a gate from TOML text
+    pub fn from_toml_str(input: &str) -> Result<Self> {
+        toml::from_str(input).map_err(|e| MiningError::InvalidConfig(e.to_string()))
+    }
+
+    /// Load a gate from a TOML file
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+
+    /// Evaluate all configured criteria
+    ///
+    /// `validation` is required for the coverage criterion; without it that
+    /// criterion fails, since it cannot be verified.
+    pub fn evaluate(
+        &self,
+        rules: &[AssociationRule],
+        validation: Option<&[Transaction]>,
+    ) -> GateReport {
+        let mut checks = Vec::new();
+
+        if let Some(min) = self.min_rules {
+            checks.push(GateCheck::new(
+                "min_rules",
+                rules.len() >= min,
+                format!("{} rules (minimum {})", rules.len(), min),
+            ));
+        }
+
+        if let Some(max) = self.max_rules {
+            checks.push(GateCheck::new(
+                "max_rules",
+                rules.len() <= max,
+                format!("{} rules (maximum {})", rules.len(), max),
+            ));
+        }
+
+        if let Some(min) = self.min_avg_confidence {
+            let avg = if rules.is_empty() {
+                0.0
+            } else {
+                rules.iter().map(|r| r.metrics.confidence).sum::<f64>() / rules.len() as f64
+            };
+            checks.push(GateCheck::new(
+                "min_avg_confidence",
+                avg >= min,
+                format!("average confidence {:.3} (minimum {:.3})", avg, min),
+            ));
+        }
+
+        if !self.blocklist.is_empty() {
+            let offending = rules
+                .iter()
+                .filter(|r| {
+                    r.antecedent
+                        .iter()
+                        .chain(&r.consequent)
+                        .any(|item| self.blocklist.contains(item))
+                })
+                .count();
+            checks.push(GateCheck::new(
+                "blocklist",
+                offending == 0,
+                format!("{} rules mention blocklisted items", offending),
+            ));
+        }
+
+        if let Some(min) = self.min_coverage {
+            checks.push(match validation {
+                Some(transactions) => {
+                    let coverage = coverage(rules, transactions);
+                    GateCheck::new(
+                        "min_coverage",
+                        coverage >= min,
+                        format!(
+                            "coverage {:.1}% (minimum {:.1}%)",
+                            coverage * 100.0,
+                            min * 100.0
+                        ),
+                    )
+                }
+                None => GateCheck::new(
+                    "min_coverage",
+                    false,
+                    "no validation set provided".to_string(),
+                ),
+            });
+        }
+
+        GateReport::new(checks)
+    }
+}
+
+/// Fraction of transactions for which at least one rule fires
+pub fn coverage(rules: &[AssociationRule], transactions: &[Transaction]) -> f64 {
+    if transactions.is_empty() {
+        return 0.0;
+    }
+
+    let rule_set = RuleSet::new(rules.to_vec());
+    let covered = transactions
+        .iter()
+        .filter(|tx| !rule_set.matching(&tx.items).is_empty())
+        .count();
+
+    covered as f64 / transactions.len() as f64
+}
+
+/// Outcome of a single gate criterion
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateCheck {
+    /// Criterion name (matches the config key)
+    pub name: String,
+    pub passed: bool,
+    /// Human-readable measured value vs threshold
+    pub detail: String,
+}
+
+impl GateCheck {
+    fn new(name: &str, passed: bool, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            passed,
+            detail,
+        }
+    }
+}
+
+/// Result of evaluating a `QualityGate`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateReport {
+    pub checks: Vec<GateCheck>,
+    /// Overall verdict: true only if every check passed
+    pub passed: bool,
+}
+
+impl GateReport {
+    fn new(checks: Vec<GateCheck>) -> Self {
+        let passed = checks.iter().all(|c| c.passed);
+        Self { checks, passed }
+    }
+
+    /// Names of the failed criteria
+    pub fn failures(&self) -> Vec<&str> {
+        self.checks
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.name.as_str())
+            .collect()
+    }
+}
+
+impl std::fmt::Display for GateReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for check in &self.checks {
+            let status = if check.passed { "PASS" } else { "FAIL" };
+            writeln!(f, "[{}] {}: {}", status, check.name, check.detail)?;
+        }
+        write!(
+            f,
+            "Verdict: {}",
+            if self.passed { "PASSED" } else { "FAILED" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+    use chrono::Utc;
+
+    fn validation() -> Vec<Transaction> {
+        [
+            vec!["Laptop", "Mouse"],
+            vec!["Laptop"],
+            vec!["Phone", "Case"],
+            vec!["Pen"],
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, items)| {
+            Transaction::new(
+                format!("v{}", i),
+                items.iter().map(|s| s.to_string()).collect(),
+                Utc::now(),
+            )
+        })
+        .collect()
+    }
+
+    fn gate() -> QualityGate {
+        QualityGate::from_toml_str(
+            r#"
+            min_rules = 2
+            max_rules = 10
+            min_avg_confidence = 0.7
+            blocklist = [
+                "Gift Card", # never recommend
+            ]
+            min_coverage = 0.5
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_passing_rule_set() {
+        let rules = vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.9, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.8, 1.5),
+        ];
+
+        let report = gate().evaluate(&rules, Some(&validation()));
+
+        assert!(report.passed, "{}", report);
+        assert_eq!(report.checks.len(), 5);
+        assert!(report.failures().is_empty());
+    }
+
+    #[test]
+    fn test_failing_two_gates() {
+        let rules = vec![
+            test_rule(&["Laptop"], &["Gift Card"], 0.9, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.3, 1.5),
+            test_rule(&["Laptop"], &["Mouse"], 0.4, 1.5),
+        ];
+
+        let report = gate().evaluate(&rules, Some(&validation()));
+
+        assert!(!report.passed);
+        assert_eq!(report.failures(), vec!["min_avg_confidence", "blocklist"]);
+        assert!(report.to_string().contains("[FAIL] blocklist"));
+        assert!(report.to_string().ends_with("Verdict: FAILED"));
+    }
+
+    #[test]
+    fn test_coverage_requires_validation_set() {
+        let rules = vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.9, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.8, 1.5),
+        ];
+
+        let report = gate().evaluate(&rules, None);
+
+        assert_eq!(report.failures(), vec!["min_coverage"]);
+        assert_eq!(coverage(&rules, &validation()), 0.75);
+    }
+
+    #[test]
+    fn test_unknown_toml_key_rejected() {
+        assert!(QualityGate::from_toml_str("min_rule = 3").is_err());
+        assert!(
+            QualityGate::from_toml_str("")
+                .unwrap()
+                .evaluate(&[], None)
+                .passed
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! Writing a `CooccurrenceGraph` for graph tools such as Gephi
+//!
+//! GraphML carries each item as a node label with its support, and each
+//! edge's support and lift as separate attributes. The edge CSV has one
+//! `source,target,support,lift` row per edge, naming items directly, which
+//! Gephi imports as an edge table.
+
+use std::fmt::Write as _;
+
+use petgraph::graph::{EdgeReference, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+use super::{CooccurrenceGraph, PairWeight};
+use crate::export::csv::escape_field;
+use crate::export::lattice::escape_xml;
+
+/// Which edges and nodes to write
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GraphExportOptions {
+    /// Leave out edges with a lower pair support
+    pub min_support: f64,
+    /// Leave out edges with a lower lift
+    pub min_lift: f64,
+    /// Keep only this many nodes, those with the most remaining edges
+    /// (ties to higher support), and the edges between them
+    pub max_nodes: Option<usize>,
+}
+
+impl GraphExportOptions {
+    /// Set the pair support threshold
+    pub fn with_min_support(mut self, min_support: f64) -> Self {
+        self.min_support = min_support;
+        self
+    }
+
+    /// Set the lift threshold
+    pub fn with_min_lift(mut self, min_lift: f64) -> Self {
+        self.min_lift = min_lift;
+        self
+    }
+
+    /// Cap the node count by degree
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+}
+
+/// Export the whole graph as GraphML
+pub fn to_graphml(graph: &CooccurrenceGraph) -> String {
+    to_graphml_with_options(graph, &GraphExportOptions::default())
+}
+
+/// Export the nodes and edges kept by `options` as GraphML
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::graph::export::{to_graphml_with_options, GraphExportOptions};
+/// use rust_rule_miner::graph::CooccurrenceGraph;
+/// use rust_rule_miner::FrequentItemset;
+///
+/// let graph = CooccurrenceGraph::from_itemsets(&[
+///     FrequentItemset::from_count(vec!["Fish & Chips".into()], 5, 10),
+///     FrequentItemset::from_count(vec!["Vinegar".into()], 4, 10),
+///     FrequentItemset::from_count(vec!["Fish & Chips".into(), "Vinegar".into()], 4, 10),
+/// ]);
+///
+/// let graphml = to_graphml_with_options(&graph, &GraphExportOptions::default().with_min_lift(1.5));
+/// assert!(graphml.contains("Fish &amp; Chips"));
+/// assert!(graphml.contains("<edge "));
+/// ```
+pub fn to_graphml_with_options(graph: &CooccurrenceGraph, options: &GraphExportOptions) -> String {
+    let (nodes, edges) = select(graph, options);
+    let mut out = String::new();
+
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    out.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+    out.push_str(
+        "  <key id=\"item_support\" for=\"node\" attr.name=\"support\" attr.type=\"double\"/>\n",
+    );
+    out.push_str(
+        "  <key id=\"support\" for=\"edge\" attr.name=\"support\" attr.type=\"double\"/>\n",
+    );
+    out.push_str("  <key id=\"lift\" for=\"edge\" attr.name=\"lift\" attr.type=\"double\"/>\n");
+    out.push_str("  <graph id=\"cooccurrence\" edgedefault=\"undirected\">\n");
+
+    let g = graph.graph();
+    for node in nodes {
+        let _ = writeln!(out, "    <node id=\"n{}\">", node.index());
+        let _ = writeln!(
+            out,
+            "      <data key=\"label\">{}</data>",
+            escape_xml(&g[node].item)
+        );
+        let _ = writeln!(
+            out,
+            "      <data key=\"item_support\">{}</data>",
+            g[node].support
+        );
+        out.push_str("    </node>\n");
+    }
+
+    for edge in edges {
+        let _ = writeln!(
+            out,
+            "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">",
+            edge.id().index(),
+            edge.source().index(),
+            edge.target().index()
+        );
+        let _ = writeln!(
+            out,
+            "      <data key=\"support\">{}</data>",
+            edge.weight().support
+        );
+        let _ = writeln!(
+            out,
+            "      <data key=\"lift\">{}</data>",
+            edge.weight().lift
+        );
+        out.push_str("    </edge>\n");
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Export every edge as CSV
+pub fn to_edge_csv(graph: &CooccurrenceGraph) -> String {
+    to_edge_csv_with_options(graph, &GraphExportOptions::default())
+}
+
+/// Export the edges kept by `options` as `source,target,support,lift` CSV
+pub fn to_edge_csv_with_options(graph: &CooccurrenceGraph, options: &GraphExportOptions) -> String {
+    let (_, edges) = select(graph, options);
+    let g = graph.graph();
+    let mut out = String::from("source,target,support,lift\n");
+    for edge in edges {
+        let _ = writeln!(
+            out,
+            "{},{},{},{}",
+            escape_field(&g[edge.source()].item),
+            escape_field(&g[edge.target()].item),
+            edge.weight().support,
+            edge.weight().lift
+        );
+    }
+    out
+}
+
+/// Nodes and edges kept by `options`, in graph order
+///
+/// Edges are filtered first, so degrees for `max_nodes` count only edges
+/// that pass the thresholds.
+fn select<'a>(
+    graph: &'a CooccurrenceGraph,
+    options: &GraphExportOptions,
+) -> (Vec<NodeIndex>, Vec<EdgeReference<'a, PairWeight>>) {
+    let g = graph.graph();
+    let mut edges: Vec<_> = g
+        .edge_references()
+        .filter(|edge| {
+            edge.weight().support >= options.min_support && edge.weight().lift >= options.min_lift
+        })
+        .collect();
+
+    let mut nodes: Vec<NodeIndex> = g.node_indices().collect();
+    if let Some(max_nodes) = options.max_nodes.filter(|&max| max < nodes.len()) {
+        let mut degree = vec![0usize; g.node_count()];
+        for edge in &edges {
+            degree[edge.source().index()] += 1;
+            degree[edge.target().index()] += 1;
+        }
+        nodes.sort_by(|&a, &b| {
+            degree[b.index()]
+                .cmp(&degree[a.index()])
+                .then(g[b].support.total_cmp(&g[a].support))
+                .then(a.cmp(&b))
+        });
+        nodes.truncate(max_nodes);
+        nodes.sort();
+
+        let mut kept = vec![false; g.node_count()];
+        for node in &nodes {
+            kept[node.index()] = true;
+        }
+        edges.retain(|edge| kept[edge.source().index()] && kept[edge.target().index()]);
+    }
+
+    (nodes, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FrequentItemset;
+
+    fn set(items: &[&str], count: usize) -> FrequentItemset {
+        FrequentItemset::from_count(items.iter().map(|s| s.to_string()).collect(), count, 100)
+    }
+
+    /// A hub with three spokes, plus a pair on its own; names need escaping
+    fn graph() -> CooccurrenceGraph {
+        CooccurrenceGraph::from_itemsets(&[
+            set(&["Fish & Chips"], 50),
+            set(&["<Vinegar>"], 20),
+            set(&["\"Mushy\" Peas"], 25),
+            set(&["Salt, Sea"], 40),
+            set(&["Tea"], 10),
+            set(&["Milk"], 10),
+            // Lifts 2.0, 1.6, 0.5 and about 3
+            set(&["Fish & Chips", "<Vinegar>"], 20),
+            set(&["Fish & Chips", "\"Mushy\" Peas"], 20),
+            set(&["Fish & Chips", "Salt, Sea"], 10),
+            set(&["Tea", "Milk"], 3),
+        ])
+    }
+
+    /// Parse `xml`, failing on malformed input or unbalanced elements;
+    /// returns the unescaped node labels
+    fn parse_labels(xml: &str) -> Vec<String> {
+        use xmlparser::{ElementEnd, Token, Tokenizer};
+
+        let mut open = Vec::new();
+        let mut labels = Vec::new();
+        let mut in_label = false;
+        for token in Tokenizer::from(xml) {
+            match token.expect("well-formed XML") {
+                Token::ElementStart { local, .. } => open.push(local.as_str()),
+                Token::Attribute { local, value, .. } => {
+                    in_label = local.as_str() == "key" && value.as_str() == "label";
+                }
+                Token::ElementEnd { end, .. } => match end {
+                    ElementEnd::Open => {}
+                    ElementEnd::Close(_, local) => {
+                        assert_eq!(open.pop(), Some(local.as_str()));
+                        in_label = false;
+                    }
+                    ElementEnd::Empty => {
+                        open.pop();
+                    }
+                },
+                Token::Text { text } if in_label => labels.push(
+                    text.as_str()
+                        .replace("&lt;", "<")
+                        .replace("&gt;", ">")
+                        .replace("&quot;", "\"")
+                        .replace("&apos;", "'")
+                        .replace("&amp;", "&"),
+                ),
+                _ => {}
+            }
+        }
+        assert!(open.is_empty(), "unclosed elements {:?}", open);
+        labels
+    }
+
+    #[test]
+    fn test_graphml_is_well_formed() {
+        let graphml = to_graphml(&graph());
+        let mut labels = parse_labels(&graphml);
+        labels.sort();
+        assert_eq!(
+            labels,
+            [
+                "\"Mushy\" Peas",
+                "<Vinegar>",
+                "Fish & Chips",
+                "Milk",
+                "Salt, Sea",
+                "Tea"
+            ]
+        );
+        assert_eq!(graphml.matches("<edge ").count(), 4);
+        assert!(graphml.contains("<data key=\"lift\">2</data>"));
+        assert!(graphml.contains("<data key=\"support\">0.2</data>"));
+
+        // Control characters cannot appear in XML 1.0 and are dropped
+        let bell = CooccurrenceGraph::from_itemsets(&[set(&["Bell\u{7}"], 1)]);
+        assert_eq!(parse_labels(&to_graphml(&bell)), ["Bell"]);
+    }
+
+    #[test]
+    fn test_edge_csv_escapes_items() {
+        let csv = to_edge_csv(&graph());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "source,target,support,lift");
+        assert_eq!(lines.len(), 5);
+        assert!(lines.contains(&"\"\"\"Mushy\"\" Peas\",Fish & Chips,0.2,1.6"));
+        assert!(lines.contains(&"Fish & Chips,\"Salt, Sea\",0.1,0.5"));
+    }
+
+    #[test]
+    fn test_filtered_edges_are_absent() {
+        let graph = graph();
+        let options = GraphExportOptions::default().with_min_lift(1.0);
+        let csv = to_edge_csv_with_options(&graph, &options);
+        assert!(!csv.contains("Salt, Sea"));
+        assert_eq!(csv.lines().count(), 4);
+        let graphml = to_graphml_with_options(&graph, &options);
+        parse_labels(&graphml);
+        assert_eq!(graphml.matches("<edge ").count(), 3);
+        assert!(!graphml.contains("<data key=\"lift\">0.5</data>"));
+
+        let options = GraphExportOptions::default().with_min_support(0.15);
+        let csv = to_edge_csv_with_options(&graph, &options);
+        assert_eq!(csv.lines().count(), 3);
+        assert!(!csv.contains("Salt, Sea") && !csv.contains("Tea"));
+
+        // The hub, then its neighbours with the highest support; the other
+        // nodes fall away with their edges
+        let options = GraphExportOptions::default().with_max_nodes(3);
+        let graphml = to_graphml_with_options(&graph, &options);
+        let mut labels = parse_labels(&graphml);
+        labels.sort();
+        assert_eq!(labels, ["\"Mushy\" Peas", "Fish & Chips", "Salt, Sea"]);
+        assert_eq!(graphml.matches("<edge ").count(), 2);
+        let csv = to_edge_csv_with_options(&graph, &options);
+        assert!(!csv.contains("Vinegar") && !csv.contains("Tea"));
+    }
+}
@@ -1,7 +1,19 @@
-// Graph-based pattern matching (placeholder for future implementation)
+//! Item co-occurrence as a graph
+//!
+//! `CooccurrenceGraph` turns frequent itemsets into an undirected petgraph
+//! graph: one node per frequent item carrying its support, one edge per
+//! frequent pair carrying the pair's support and lift. `export` writes it
+//! as GraphML or an edge-list CSV for tools such as Gephi.
 
+pub mod export;
+
+use std::collections::HashMap;
+
+use petgraph::graph::{NodeIndex, UnGraph};
 use serde::{Deserialize, Serialize};
 
+use crate::types::FrequentItemset;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntityGraph {
     // Placeholder - will use petgraph in future
@@ -18,3 +30,138 @@ impl Default for EntityGraph {
         Self::new()
     }
 }
+
+/// An item in a `CooccurrenceGraph`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemNode {
+    pub item: String,
+    pub support: f64,
+}
+
+/// How often two items occur together
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairWeight {
+    /// Fraction of transactions holding both items
+    pub support: f64,
+    /// Pair support over the product of the items' supports
+    pub lift: f64,
+}
+
+/// Items as nodes, joined by an edge when they occur together
+///
+/// Built from frequent itemsets: 1-itemsets give the nodes, 2-itemsets the
+/// edges. The underlying petgraph graph is available for traversal.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::graph::CooccurrenceGraph;
+/// use rust_rule_miner::FrequentItemset;
+///
+/// let graph = CooccurrenceGraph::from_itemsets(&[
+///     FrequentItemset::from_count(vec!["Laptop".into()], 5, 10),
+///     FrequentItemset::from_count(vec!["Mouse".into()], 4, 10),
+///     FrequentItemset::from_count(vec!["Laptop".into(), "Mouse".into()], 4, 10),
+/// ]);
+///
+/// assert_eq!(graph.node_count(), 2);
+/// let pair = graph.graph().edge_weights().next().unwrap();
+/// assert!((pair.lift - 2.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CooccurrenceGraph {
+    graph: UnGraph<ItemNode, PairWeight>,
+}
+
+impl CooccurrenceGraph {
+    /// Build the graph from frequent itemsets
+    ///
+    /// Nodes are added in item order. A pair whose items lack a 1-itemset
+    /// has no lift and is left out, as are larger itemsets and repeats.
+    pub fn from_itemsets(itemsets: &[FrequentItemset]) -> Self {
+        let mut singles: Vec<&FrequentItemset> =
+            itemsets.iter().filter(|set| set.items.len() == 1).collect();
+        singles.sort_by(|a, b| a.items[0].cmp(&b.items[0]));
+        singles.dedup_by(|a, b| a.items[0] == b.items[0]);
+
+        let mut graph = UnGraph::with_capacity(singles.len(), 0);
+        let mut index: HashMap<&str, NodeIndex> = HashMap::with_capacity(singles.len());
+        for single in singles {
+            let node = graph.add_node(ItemNode {
+                item: single.items[0].clone(),
+                support: single.support,
+            });
+            index.insert(single.items[0].as_str(), node);
+        }
+
+        for pair in itemsets.iter().filter(|set| set.items.len() == 2) {
+            let (Some(&a), Some(&b)) = (
+                index.get(pair.items[0].as_str()),
+                index.get(pair.items[1].as_str()),
+            ) else {
+                continue;
+            };
+            if a == b || graph.contains_edge(a, b) {
+                continue;
+            }
+            let (a, b) = (a.min(b), a.max(b));
+            let lift = pair.support / (graph[a].support * graph[b].support);
+            graph.add_edge(
+                a,
+                b,
+                PairWeight {
+                    support: pair.support,
+                    lift,
+                },
+            );
+        }
+
+        Self { graph }
+    }
+
+    /// The underlying graph
+    pub fn graph(&self) -> &UnGraph<ItemNode, PairWeight> {
+        &self.graph
+    }
+
+    /// Number of items
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Number of item pairs
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_itemsets() {
+        let set = |items: &[&str], count| {
+            FrequentItemset::from_count(items.iter().map(|s| s.to_string()).collect(), count, 10)
+        };
+        let graph = CooccurrenceGraph::from_itemsets(&[
+            set(&["Mouse"], 4),
+            set(&["Laptop"], 5),
+            set(&["Mouse", "Laptop"], 4),
+            // Repeated in the other order, and with an item that has no node
+            set(&["Laptop", "Mouse"], 4),
+            set(&["Laptop", "Dock"], 2),
+            set(&["Laptop", "Mouse", "Pad"], 1),
+        ]);
+
+        let items: Vec<&str> = graph
+            .graph()
+            .node_weights()
+            .map(|node| node.item.as_str())
+            .collect();
+        assert_eq!(items, ["Laptop", "Mouse"]);
+        assert_eq!(graph.edge_count(), 1);
+        let pair = graph.graph().edge_weights().next().unwrap();
+        assert_eq!(pair.support, 0.4);
+        assert!((pair.lift - 0.4 / (0.5 * 0.4)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,138 @@
+//! Stable numeric item ids
+//!
+//! An `Interner` numbers items in the order it first sees them and never
+//! renumbers or forgets one, so the id → item mapping only grows. Saved after
+//! a run and loaded into the next one (`RuleMiner::set_interner`), it keeps
+//! every known item's id across nightly runs; only unseen items get fresh
+//! ids. Downstream systems can then send compact id lists instead of item
+//! names.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::errors::{MiningError, Result};
+use crate::types::AssociationRule;
+
+/// Append-only mapping between items and `u32` ids
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::Interner;
+///
+/// let mut interner = Interner::new();
+/// assert_eq!(interner.intern("Laptop"), 0);
+/// assert_eq!(interner.intern("Mouse"), 1);
+/// assert_eq!(interner.intern("Laptop"), 0);
+///
+/// let path = std::env::temp_dir().join("rust_rule_miner_interner_doc.json");
+/// interner.save(&path)?;
+/// let mut next_run = Interner::load(&path)?;
+/// assert_eq!(next_run.intern("Keyboard"), 2);
+/// assert_eq!(next_run.id("Mouse"), Some(1));
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Interner {
+    /// Item of each id
+    items: Vec<String>,
+    ids: HashMap<String, u32>,
+}
+
+impl Interner {
+    /// Empty interner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of `item`, assigning the next free id if it is new
+    pub fn intern(&mut self, item: &str) -> u32 {
+        if let Some(&id) = self.ids.get(item) {
+            return id;
+        }
+        let id = self.items.len() as u32;
+        self.items.push(item.to_string());
+        self.ids.insert(item.to_string(), id);
+        id
+    }
+
+    /// Id of `item`, if it has one
+    pub fn id(&self, item: &str) -> Option<u32> {
+        self.ids.get(item).copied()
+    }
+
+    /// Item with id `id`
+    pub fn item(&self, id: u32) -> Option<&str> {
+        self.items.get(id as usize).map(String::as_str)
+    }
+
+    /// Ids of `items` in order, or None if any item has no id
+    pub fn ids(&self, items: &[String]) -> Option<Vec<u32>> {
+        items.iter().map(|item| self.id(item)).collect()
+    }
+
+    /// Antecedent and consequent ids of `rule`, or None if an item has no id
+    pub fn encode_rule(&self, rule: &AssociationRule) -> Option<(Vec<u32>, Vec<u32>)> {
+        Some((self.ids(&rule.antecedent)?, self.ids(&rule.consequent)?))
+    }
+
+    /// Number of items with an id (also the next id to assign)
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Items in id order
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Write the mapping as a JSON array of items, index = id
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, serde_json::to_string(&self.items)? + "\n")?;
+        Ok(())
+    }
+
+    /// Read a mapping written by `save`
+    ///
+    /// Fails with `DataLoadError` if an item appears twice, since its id
+    /// would be ambiguous.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let items: Vec<String> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let mut interner = Self::new();
+        for item in &items {
+            if interner.id(item).is_some() {
+                return Err(MiningError::DataLoadError(format!(
+                    "item `{}` appears twice in interner file {}",
+                    item,
+                    path.display()
+                )));
+            }
+            interner.intern(item);
+        }
+        Ok(interner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_rejects_duplicates() {
+        let path = std::env::temp_dir().join(format!(
+            "rust_rule_miner_interner_dup_{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, r#"["A", "B", "A"]"#).unwrap();
+        let result = Interner::load(&path);
+        std::fs::remove_file(&path).ok();
+        match result {
+            Err(MiningError::DataLoadError(message)) => assert!(message.contains("`A`")),
+            other => panic!("expected DataLoadError, got {:?}", other),
+        }
+    }
+}
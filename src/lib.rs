@@ -6,38 +6,61 @@
 //! ## Quick Start
 //!
 //! ```rust
-//! use rust_rule_miner::{RuleMiner, Transaction, MiningConfig, MiningAlgorithm};
+//! use rust_rule_miner::{
+//!     Confidence, Lift, MiningAlgorithm, MiningConfig, RuleMiner, Support, Transaction,
+//! };
 //! use chrono::Utc;
 //!
 //! // Load transactions
 //! let transactions = vec![
 //!     Transaction::new("tx1", vec!["Laptop".to_string(), "Mouse".to_string()], Utc::now()),
 //!     Transaction::new("tx2", vec!["Laptop".to_string(), "Keyboard".to_string()], Utc::now()),
+//!     Transaction::new("tx3", vec!["Laptop".to_string(), "Mouse".to_string()], Utc::now()),
+//!     Transaction::new("tx4", vec!["Phone".to_string(), "Case".to_string()], Utc::now()),
 //! ];
 //!
 //! // Configure mining
-//! let config = MiningConfig {
-//!     min_support: 0.3,
-//!     min_confidence: 0.7,
-//!     min_lift: 1.0,
-//!     max_time_gap: None,
-//!     algorithm: MiningAlgorithm::Apriori,
-//! };
+//! let config = MiningConfig::builder()
+//!     .min_support(Support::fraction(0.3)?)
+//!     .min_confidence(Confidence::fraction(0.7)?)
+//!     .min_lift(Lift::new(1.0)?)
+//!     .algorithm(MiningAlgorithm::Apriori)
+//!     .build()?;
 //!
 //! // Mine rules
 //! let mut miner = RuleMiner::new(config);
 //! miner.add_transactions(transactions).unwrap();
-//! let rules = miner.mine_association_rules().unwrap();
+//! let rules = miner.mine().unwrap().rules;
+//! # Ok::<(), rust_rule_miner::MiningError>(())
 //! ```
+//!
+//! Thresholds are typed: `Support::fraction(30.0)` is rejected with a hint to
+//! use `Support::percent(30)`, instead of silently mining nothing.
 
 pub mod config;
 pub mod errors;
+pub mod interner;
 pub mod transaction;
 pub mod types;
 
 // Mining algorithms
 pub mod mining;
 
+// Transaction preprocessing and data integrity checks
+pub mod aggregate;
+pub mod analysis;
+pub mod normalize;
+
+// Rule filtering, queries and quality gates
+pub use rust_rule_miner_edge as edge;
+pub mod alerts;
+pub mod gate;
+pub mod rules;
+pub mod scoring;
+
+// Seedable PRNG for sampling and noise
+mod rng;
+
 // Export formats
 pub mod export;
 
@@ -48,19 +71,47 @@ pub mod graph;
 pub mod data_loader;
 pub use data_loader::ColumnMapping;
 
+// GCS and Azure Blob requests for the data loader
+#[cfg(feature = "cloud")]
+mod cloud_storage;
+
+// Deployment planning
+pub mod deployment;
+
+// Replaying historical baskets against recommendations
+pub mod evaluation;
+
+// Anonymized reproduction datasets
+pub mod testing;
+
+// Lock-free rule serving
+#[cfg(feature = "snapshot")]
+pub mod serving;
+
 // Rule engine integration
 #[cfg(feature = "engine")]
 pub mod engine;
 
 // Re-exports
-pub use config::{MiningAlgorithm, MiningConfig};
+pub use aggregate::{GroupBy, WindowAggregator};
+pub use config::{
+    Confidence, ItemsetKind, Lift, MetricSet, MiningAlgorithm, MiningConfig, MiningConfigBuilder,
+    PValueCorrection, RuleConstraints, Support, SupportUniverse,
+};
 pub use errors::{MiningError, Result};
-pub use export::grl::{GrlConfig, RuleTemplate};
-pub use mining::RuleMiner;
+pub use export::grl::{GrlConfig, GrlDialect, RuleTemplate};
+pub use interner::Interner;
+pub use mining::{MiningOutcome, MiningResult, RuleEvidence, RuleMiner};
+pub use normalize::{ItemNormalizer, Preprocessing};
+pub use rules::{
+    recommend, NoMatchExplanation, Provenance, RecContext, Recommendation, RecommendationFilter,
+    Recommender, RuleSet, ScoreAggregation, SourcedRule,
+};
+pub use scoring::{QualityScorer, WeightedScorer};
 pub use transaction::Transaction;
 pub use types::{
-    AssociationRule, FrequentItemset, ItemSet, Pattern, PatternMetrics, PatternType,
-    SequentialPattern,
+    AssociationRule, FrequentItemset, ItemSet, NegativeRule, Pattern, PatternMetrics, PatternType,
+    RuleCounts, SequentialPattern,
 };
 
 #[cfg(test)]
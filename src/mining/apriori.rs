@@ -1,4 +1,9 @@
+use crate::config::{MiningConfig, Support};
 use crate::errors::Result;
+use crate::mining::budget::Budget;
+use crate::mining::index::TransactionIndex;
+use crate::mining::progress::{Progress, ProgressPhase};
+use crate::mining::stats::{Phase, PhaseTimer, PhaseTimings};
 use crate::transaction::Transaction;
 use crate::types::{FrequentItemset, ItemSet};
 use std::collections::{HashMap, HashSet};
@@ -8,40 +13,107 @@ pub fn find_frequent_itemsets(
     transactions: &[Transaction],
     min_support: f64,
 ) -> Result<Vec<FrequentItemset>> {
-    let total_transactions = transactions.len() as f64;
-    let min_support_count = (min_support * total_transactions).ceil() as usize;
+    let mut config = MiningConfig::default();
+    config.set_min_support(Support::fraction(min_support)?);
+    find_frequent_itemsets_with_config(transactions, &config)
+}
+
+/// Find frequent itemsets using Apriori, honoring `min_support`,
+/// `max_itemset_size`, the run limits and item `constraints` from the config
+pub fn find_frequent_itemsets_with_config(
+    transactions: &[Transaction],
+    config: &MiningConfig,
+) -> Result<Vec<FrequentItemset>> {
+    find_frequent_itemsets_timed(
+        transactions,
+        config,
+        &mut PhaseTimings::default(),
+        &mut Vec::new(),
+        &mut Progress::none(),
+        &mut Budget::new(config),
+    )
+}
+
+/// Apriori recording item counting and per-level times into `timings` and
+/// the number of candidates counted per level into `candidates`, reporting
+/// to `progress`
+///
+/// Once `budget` is spent the current level's counted candidates are kept
+/// and no further level is generated.
+pub(crate) fn find_frequent_itemsets_timed(
+    transactions: &[Transaction],
+    config: &MiningConfig,
+    timings: &mut PhaseTimings,
+    candidates: &mut Vec<usize>,
+    progress: &mut Progress<'_>,
+    budget: &mut Budget,
+) -> Result<Vec<FrequentItemset>> {
+    let total_transactions = transactions.len();
+    let min_support_count = config.min_support_count(total_transactions);
+    let transactions = &*config.constraints.project(transactions);
+    let index = TransactionIndex::new(transactions);
 
     let mut frequent_itemsets = Vec::new();
 
     // Level 1: Individual items
+    let mut level_timer = PhaseTimer::start(Phase::AprioriLevel(1));
     let mut current_level = generate_1_itemsets(transactions);
+    let mut k = 1;
 
     while !current_level.is_empty() {
+        // Stop extending itemsets beyond the configured size
+        if config.max_itemset_size.is_some_and(|max| k > max) {
+            break;
+        }
+
         // Count support for each candidate
-        let counts = count_support(transactions, &current_level);
+        candidates.push(current_level.len());
+        let counts = count_support(&index, &current_level, k, progress, budget)?;
 
         // Filter by minimum support
-        let frequent_k: Vec<_> = counts
+        let mut frequent_k: Vec<_> = counts
             .into_iter()
             .filter(|(_, count)| *count >= min_support_count)
             .collect();
+        if k == 1 {
+            // Level 1 is the single-item count
+            timings.item_counting = level_timer.elapsed();
+        }
 
         if frequent_k.is_empty() {
+            timings.itemset_levels.push(level_timer.elapsed());
             break;
         }
 
+        // Past the itemset limit keep the best-supported of this level
+        if budget.exhausted(frequent_itemsets.len() + frequent_k.len()) {
+            frequent_k.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            frequent_k.truncate(budget.limit().saturating_sub(frequent_itemsets.len()));
+        }
+
         // Add to results with support as fraction
         for (itemset, count) in &frequent_k {
-            frequent_itemsets.push(FrequentItemset {
-                items: itemset.clone(),
-                support: *count as f64 / total_transactions,
-            });
+            frequent_itemsets.push(FrequentItemset::from_count(
+                itemset.clone(),
+                *count,
+                total_transactions,
+            ));
+        }
+
+        if budget.truncated() {
+            timings.itemset_levels.push(level_timer.elapsed());
+            break;
         }
 
         // Generate next level candidates (k+1 itemsets from k itemsets)
-        current_level = generate_candidates(&frequent_k);
+        current_level = generate_candidates(&frequent_k, k + 1, progress, budget)?;
+        k += 1;
+        let level_time = level_timer.restart(Phase::AprioriLevel(k));
+        timings.itemset_levels.push(level_time);
     }
 
+    // Itemsets without a required item were only needed to build candidates
+    frequent_itemsets.retain(|itemset| config.constraints.allows_itemset(&itemset.items));
     Ok(frequent_itemsets)
 }
 
@@ -58,26 +130,47 @@ fn generate_1_itemsets(transactions: &[Transaction]) -> Vec<ItemSet> {
     items.into_iter().map(|item| vec![item]).collect()
 }
 
-/// Count support for itemsets
-fn count_support(transactions: &[Transaction], itemsets: &[ItemSet]) -> HashMap<ItemSet, usize> {
+/// Count support for the candidates of `level`, stopping early once
+/// `budget` is spent
+fn count_support(
+    index: &TransactionIndex,
+    itemsets: &[ItemSet],
+    level: usize,
+    progress: &mut Progress<'_>,
+    budget: &mut Budget,
+) -> Result<HashMap<ItemSet, usize>> {
     let mut counts = HashMap::new();
+    let phase = ProgressPhase::SupportCounting { level };
 
-    for itemset in itemsets {
-        let count = transactions
-            .iter()
-            .filter(|tx| tx.contains_all(itemset))
-            .count();
-        counts.insert(itemset.clone(), count);
+    for (done, itemset) in itemsets.iter().enumerate() {
+        progress.checkpoint(phase, done, itemsets.len())?;
+        if budget.spent() {
+            return Ok(counts);
+        }
+        counts.insert(itemset.clone(), index.count(itemset));
     }
+    progress.checkpoint(phase, itemsets.len(), itemsets.len())?;
 
-    counts
+    Ok(counts)
 }
 
-/// Generate (k+1)-itemsets from k-itemsets
-fn generate_candidates(frequent_k: &[(ItemSet, usize)]) -> Vec<ItemSet> {
+/// Generate the `level`-itemsets from the frequent (level-1)-itemsets
+///
+/// Returns no candidates once `budget` is spent.
+fn generate_candidates(
+    frequent_k: &[(ItemSet, usize)],
+    level: usize,
+    progress: &mut Progress<'_>,
+    budget: &mut Budget,
+) -> Result<Vec<ItemSet>> {
     let mut candidates = Vec::new();
+    let phase = ProgressPhase::CandidateGeneration { level };
 
     for i in 0..frequent_k.len() {
+        progress.checkpoint(phase, i, frequent_k.len())?;
+        if budget.spent() {
+            return Ok(Vec::new());
+        }
         for j in (i + 1)..frequent_k.len() {
             let (set1, _) = &frequent_k[i];
             let (set2, _) = &frequent_k[j];
@@ -97,11 +190,13 @@ fn generate_candidates(frequent_k: &[(ItemSet, usize)]) -> Vec<ItemSet> {
         }
     }
 
+    progress.checkpoint(phase, frequent_k.len(), frequent_k.len())?;
+
     // Remove duplicates
     candidates.sort();
     candidates.dedup();
 
-    candidates
+    Ok(candidates)
 }
 
 /// Check if two itemsets can be joined
@@ -163,7 +258,15 @@ mod tests {
             vec!["A".to_string(), "B".to_string()],
         ];
 
-        let counts = count_support(&transactions, &itemsets);
+        let index = TransactionIndex::new(&transactions);
+        let counts = count_support(
+            &index,
+            &itemsets,
+            1,
+            &mut Progress::none(),
+            &mut Budget::new(&MiningConfig::default()),
+        )
+        .unwrap();
 
         assert_eq!(counts.get(&vec!["A".to_string()]), Some(&3));
         assert_eq!(counts.get(&vec!["B".to_string()]), Some(&3));
@@ -213,4 +316,19 @@ mod tests {
         // No 2-itemsets should have >= 75% support
         assert!(frequent.iter().all(|f| f.items.len() == 1));
     }
+
+    #[test]
+    fn test_apriori_max_itemset_size() {
+        let transactions = create_test_transactions();
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.25).unwrap())
+            .max_itemset_size(2)
+            .build()
+            .unwrap();
+        let frequent = find_frequent_itemsets_with_config(&transactions, &config).unwrap();
+
+        // {A,B,C} is frequent at 25% but exceeds the size limit
+        assert!(frequent.iter().any(|f| f.items.len() == 2));
+        assert!(frequent.iter().all(|f| f.items.len() <= 2));
+    }
 }
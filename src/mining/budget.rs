@@ -0,0 +1,95 @@
+//! Time and size limits for a mining run
+//!
+//! `MiningConfig::max_duration` and `max_frequent_itemsets` bound runs that a
+//! too-low `min_support` would otherwise keep going for hours. Apriori's
+//! candidate loops, FP-Growth's recursion and rule generation check the
+//! budget as they go; once it is spent they stop and keep what they found,
+//! and the run reports `MiningStats::truncated`.
+
+use std::time::Instant;
+
+use crate::config::MiningConfig;
+
+/// Limits of one mining run, started when the run starts
+#[derive(Debug, Clone)]
+pub(crate) struct Budget {
+    deadline: Option<Instant>,
+    max_itemsets: Option<usize>,
+    truncated: bool,
+}
+
+impl Budget {
+    pub(crate) fn new(config: &MiningConfig) -> Self {
+        Self {
+            deadline: config
+                .max_duration
+                .and_then(|duration| Instant::now().checked_add(duration)),
+            max_itemsets: config.max_frequent_itemsets,
+            truncated: false,
+        }
+    }
+
+    /// Whether the budget is spent, checking the clock; once spent it stays
+    /// spent
+    pub(crate) fn spent(&mut self) -> bool {
+        if !self.truncated
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.truncated = true;
+        }
+        self.truncated
+    }
+
+    /// Whether mining must stop with `found` frequent itemsets collected
+    ///
+    /// More than `max_frequent_itemsets` spends the budget; callers keep
+    /// `limit()` of them.
+    pub(crate) fn exhausted(&mut self, found: usize) -> bool {
+        if self.max_itemsets.is_some_and(|max| found > max) {
+            self.truncated = true;
+        }
+        self.spent()
+    }
+
+    /// Frequent itemsets to keep (`usize::MAX` without a limit)
+    pub(crate) fn limit(&self) -> usize {
+        self.max_itemsets.unwrap_or(usize::MAX)
+    }
+
+    /// Whether a limit cut the run short
+    pub(crate) fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_limits() {
+        let mut unlimited = Budget::new(&MiningConfig::default());
+        assert!(!unlimited.exhausted(usize::MAX));
+        assert_eq!(unlimited.limit(), usize::MAX);
+
+        let config = MiningConfig::builder()
+            .max_frequent_itemsets(10)
+            .build()
+            .unwrap();
+        let mut budget = Budget::new(&config);
+        assert!(!budget.exhausted(10));
+        assert!(budget.exhausted(11));
+        // Stays spent
+        assert!(budget.exhausted(0));
+        assert!(budget.truncated());
+
+        let config = MiningConfig::builder()
+            .max_duration(Duration::ZERO)
+            .build()
+            .unwrap();
+        assert!(Budget::new(&config).spent());
+    }
+}
@@ -0,0 +1,137 @@
+//! Closed and maximal frequent itemset filtering
+//!
+//! Dense datasets produce many redundant itemsets: when {A,B} and {A,B,C}
+//! have identical support, {A,B} carries no extra information. Filtering to
+//! closed (or maximal) itemsets shrinks the lattice before rule generation.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::config::ItemsetKind;
+use crate::types::{FrequentItemset, ItemSet};
+
+/// Reduce frequent itemsets to the requested kind
+///
+/// Only supersets one item larger need to be checked: by anti-monotonicity of
+/// support, if any frequent superset (with equal support) exists, one of size
+/// k+1 exists as well.
+pub fn filter_itemsets(itemsets: Vec<FrequentItemset>, kind: ItemsetKind) -> Vec<FrequentItemset> {
+    if kind == ItemsetKind::All {
+        return itemsets;
+    }
+
+    // Canonical (sorted) key -> support, keeping the first occurrence
+    let mut supports: HashMap<ItemSet, f64> = HashMap::new();
+    for itemset in &itemsets {
+        supports
+            .entry(canonical(&itemset.items))
+            .or_insert(itemset.support);
+    }
+
+    let mut has_frequent_superset: HashSet<ItemSet> = HashSet::new();
+    let mut has_equal_superset: HashSet<ItemSet> = HashSet::new();
+
+    for (superset, support) in &supports {
+        if superset.len() < 2 {
+            continue;
+        }
+
+        for skip in 0..superset.len() {
+            let subset: ItemSet = superset
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != skip)
+                .map(|(_, item)| item.clone())
+                .collect();
+
+            if let Some(subset_support) = supports.get(&subset) {
+                if *subset_support == *support {
+                    has_equal_superset.insert(subset.clone());
+                }
+                has_frequent_superset.insert(subset);
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    itemsets
+        .into_iter()
+        .filter(|itemset| {
+            let key = canonical(&itemset.items);
+            let keep = match kind {
+                ItemsetKind::All => true,
+                ItemsetKind::Closed => !has_equal_superset.contains(&key),
+                ItemsetKind::Maximal => !has_frequent_superset.contains(&key),
+            };
+            keep && seen.insert(key)
+        })
+        .collect()
+}
+
+/// Sorted copy of an itemset used as lookup key
+fn canonical(items: &[String]) -> ItemSet {
+    let mut key = items.to_vec();
+    key.sort();
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn itemset(items: &[&str], support: f64) -> FrequentItemset {
+        FrequentItemset {
+            items: items.iter().map(|s| s.to_string()).collect(),
+            support,
+            count: 0,
+        }
+    }
+
+    fn lattice() -> Vec<FrequentItemset> {
+        // {A,B} and {A,B,C} have equal support, so {A,B} is not closed
+        vec![
+            itemset(&["A"], 0.8),
+            itemset(&["B"], 0.6),
+            itemset(&["C"], 0.6),
+            itemset(&["D"], 0.4),
+            itemset(&["A", "B"], 0.6),
+            itemset(&["A", "C"], 0.6),
+            itemset(&["B", "C"], 0.6),
+            itemset(&["A", "D"], 0.4),
+            itemset(&["A", "B", "C"], 0.6),
+        ]
+    }
+
+    fn keys(itemsets: &[FrequentItemset]) -> Vec<ItemSet> {
+        let mut keys: Vec<ItemSet> = itemsets.iter().map(|i| canonical(&i.items)).collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_closed_collapses_equal_support_subsets() {
+        let closed = filter_itemsets(lattice(), ItemsetKind::Closed);
+
+        let expected: Vec<ItemSet> = vec![
+            vec!["A".to_string()],
+            vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            vec!["A".to_string(), "D".to_string()],
+        ];
+        assert_eq!(keys(&closed), expected);
+    }
+
+    #[test]
+    fn test_maximal_keeps_only_frequent_borders() {
+        let maximal = filter_itemsets(lattice(), ItemsetKind::Maximal);
+
+        let expected: Vec<ItemSet> = vec![
+            vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            vec!["A".to_string(), "D".to_string()],
+        ];
+        assert_eq!(keys(&maximal), expected);
+    }
+
+    #[test]
+    fn test_all_is_unchanged() {
+        assert_eq!(filter_itemsets(lattice(), ItemsetKind::All).len(), 9);
+    }
+}
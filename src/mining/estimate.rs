@@ -0,0 +1,341 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{MiningConfig, Support};
+use crate::errors::{MiningError, Result};
+use crate::rng::SplitMix64;
+use crate::transaction::Transaction;
+
+use super::RuleMiner;
+
+/// z-score for the two-sided 95% bounds
+const Z_95: f64 = 1.96;
+
+/// A projected count with 95% bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountEstimate {
+    pub expected: usize,
+    pub low: usize,
+    pub high: usize,
+}
+
+impl fmt::Display for CountEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "~{} ({} - {})", self.expected, self.low, self.high)
+    }
+}
+
+/// Projected frequent itemsets of one size
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelEstimate {
+    /// Itemset size (k)
+    pub size: usize,
+    pub itemsets: CountEstimate,
+}
+
+/// Cheap projection of what a full mining run will produce
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    pub sample_size: usize,
+    pub full_dataset_size: usize,
+    /// Frequent itemsets per size, smallest first
+    pub levels: Vec<LevelEstimate>,
+    pub frequent_itemsets: CountEstimate,
+    pub rules: CountEstimate,
+    /// Time spent mining the sample
+    pub sample_runtime: Duration,
+    /// Measured cost per candidate itemset per transaction
+    pub per_candidate_cost: Duration,
+    /// Expected candidates x full dataset size x per-candidate cost
+    pub projected_runtime: Duration,
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Estimate from {} of {} transactions (95% bounds)",
+            self.sample_size, self.full_dataset_size
+        )?;
+        for level in &self.levels {
+            writeln!(f, "  {}-itemsets:        {}", level.size, level.itemsets)?;
+        }
+        writeln!(f, "  Frequent itemsets: {}", self.frequent_itemsets)?;
+        writeln!(f, "  Rules:             {}", self.rules)?;
+        writeln!(f, "  Sample runtime:    {:?}", self.sample_runtime)?;
+        write!(f, "  Projected runtime: {:?}", self.projected_runtime)
+    }
+}
+
+/// Estimate itemset and rule counts for a full run from a sample
+///
+/// Supports are relative, so counts found in a representative sample carry
+/// over to the full dataset. The uncertainty comes from itemsets whose true
+/// support is close to `min_support`: the sample is mined once at a lowered
+/// threshold, and itemsets (and rules) are counted at `min_support` plus or
+/// minus 1.96 standard errors of a sampled proportion. Confidence sampling
+/// error is not included in the bounds.
+///
+/// Use `sample_transactions` to draw a seeded sample.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::mining::{estimate, sample_transactions};
+/// use rust_rule_miner::{MiningConfig, Transaction};
+/// use chrono::Utc;
+///
+/// let transactions: Vec<Transaction> = (0..1000)
+///     .map(|i| Transaction::new(format!("tx{}", i), vec!["A".into(), "B".into()], Utc::now()))
+///     .collect();
+///
+/// let sample = sample_transactions(&transactions, 0.1, 42);
+/// let estimate = estimate(&sample, &MiningConfig::default(), transactions.len())?;
+/// assert_eq!(estimate.frequent_itemsets.expected, 3);
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+pub fn estimate(
+    sample: &[Transaction],
+    config: &MiningConfig,
+    full_dataset_size: usize,
+) -> Result<Estimate> {
+    config.validate()?;
+    config.check_support_count(full_dataset_size)?;
+    if sample.is_empty() {
+        return Err(MiningError::InsufficientData(
+            "Cannot estimate from an empty sample".to_string(),
+        ));
+    }
+
+    let n = sample.len() as f64;
+    let full = full_dataset_size.max(sample.len()) as f64;
+    let support = config.min_support();
+
+    // Standard error of a sampled proportion, with finite population correction
+    let fpc = if full > 1.0 {
+        (full - n) / (full - 1.0)
+    } else {
+        0.0
+    };
+    let margin = Z_95 * (support * (1.0 - support) / n * fpc).sqrt();
+    // Don't let a tiny sample drag the threshold so low the sample run explodes
+    let floor = (support - margin).max(support / 2.0);
+    let ceiling = support + margin;
+
+    let mut sample_config = config.clone();
+    sample_config.set_min_support(Support::fraction(floor)?);
+    // The guardrail is for the full run, checked above; a small sample at
+    // the lowered threshold may well need only one occurrence
+    sample_config.allow_singleton_support = true;
+
+    let mut miner = RuleMiner::new(sample_config);
+    miner.add_transactions(sample.to_vec())?;
+    let start = Instant::now();
+    let result = miner.mine_with_support_table(usize::MAX)?;
+    let sample_runtime = start.elapsed();
+
+    let itemsets = result
+        .support_table
+        .as_ref()
+        .map(|table| table.itemsets())
+        .unwrap_or_default();
+
+    // Compare on counts to avoid float noise right at the threshold
+    let expected_count = config.min_support_count(sample.len());
+    let low_count = match ceiling {
+        ceiling if ceiling > 1.0 => usize::MAX,
+        ceiling => Support::fraction(ceiling)?.min_count(sample.len()),
+    };
+    let count = |counts: &mut dyn Iterator<Item = usize>| {
+        let mut bounds = CountEstimate {
+            expected: 0,
+            low: 0,
+            high: 0,
+        };
+        for count in counts {
+            bounds.high += 1;
+            if count >= expected_count {
+                bounds.expected += 1;
+            }
+            if count >= low_count {
+                bounds.low += 1;
+            }
+        }
+        bounds
+    };
+
+    let max_size = itemsets.iter().map(|i| i.items.len()).max().unwrap_or(0);
+    let levels: Vec<LevelEstimate> = (1..=max_size)
+        .map(|size| LevelEstimate {
+            size,
+            itemsets: count(
+                &mut itemsets
+                    .iter()
+                    .filter(|i| i.items.len() == size)
+                    .map(|i| i.count),
+            ),
+        })
+        .collect();
+    let frequent_itemsets = count(&mut itemsets.iter().map(|i| i.count));
+    let rules = count(
+        &mut result
+            .rules
+            .iter()
+            .map(|r| (r.metrics.support * n).round() as usize),
+    );
+
+    let candidates = frequent_itemsets.high.max(1) as f64;
+    let per_candidate_cost = sample_runtime.div_f64(candidates * n);
+    let projected_runtime =
+        per_candidate_cost.mul_f64(frequent_itemsets.expected.max(1) as f64 * full);
+
+    Ok(Estimate {
+        sample_size: sample.len(),
+        full_dataset_size,
+        levels,
+        frequent_itemsets,
+        rules,
+        sample_runtime,
+        per_candidate_cost,
+        projected_runtime,
+    })
+}
+
+/// Draw a reproducible Bernoulli sample (each transaction kept with
+/// probability `fraction`)
+pub fn sample_transactions(
+    transactions: &[Transaction],
+    fraction: f64,
+    seed: u64,
+) -> Vec<Transaction> {
+    let mut rng = SplitMix64::new(seed);
+    transactions
+        .iter()
+        .filter(|_| rng.next_open01() < fraction)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Confidence, Lift};
+    use chrono::Utc;
+
+    /// Items with fixed inclusion probabilities; `D` only appears with `A`
+    fn synthetic(count: usize, seed: u64) -> Vec<Transaction> {
+        let mut rng = SplitMix64::new(seed);
+        let probabilities = [("A", 0.6), ("B", 0.5), ("C", 0.4), ("E", 0.05)];
+
+        (0..count)
+            .map(|i| {
+                let mut items: Vec<String> = probabilities
+                    .iter()
+                    .filter(|(_, p)| rng.next_open01() < *p)
+                    .map(|(item, _)| item.to_string())
+                    .collect();
+                if items.iter().any(|item| item == "A") && rng.next_open01() < 0.7 {
+                    items.push("D".to_string());
+                }
+                Transaction::new(format!("tx{}", i), items, Utc::now())
+            })
+            .collect()
+    }
+
+    fn config() -> MiningConfig {
+        MiningConfig::builder()
+            .min_support(Support::fraction(0.15).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .min_lift(Lift::new(0.0).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn within(estimate: CountEstimate, actual: usize) -> bool {
+        let tolerance = (actual as f64 * 0.2).ceil() as usize;
+        estimate.expected.abs_diff(actual) <= tolerance
+            && estimate.low <= actual
+            && actual <= estimate.high
+    }
+
+    #[test]
+    fn test_ten_percent_sample_matches_full_run() {
+        let transactions = synthetic(5000, 1);
+        let mut miner = RuleMiner::new(config());
+        miner.add_transactions(transactions.clone()).unwrap();
+        let actual_rules = miner.mine().unwrap().rules.len();
+        let actual_itemsets = miner.mine_frequent_itemsets().unwrap();
+
+        let sample = sample_transactions(&transactions, 0.1, 7);
+        assert!((400..600).contains(&sample.len()));
+
+        let estimate = estimate(&sample, &config(), transactions.len()).unwrap();
+        assert!(
+            within(estimate.frequent_itemsets, actual_itemsets.len()),
+            "{:?} vs {}",
+            estimate.frequent_itemsets,
+            actual_itemsets.len()
+        );
+        assert!(
+            within(estimate.rules, actual_rules),
+            "{:?} vs {}",
+            estimate.rules,
+            actual_rules
+        );
+
+        for level in &estimate.levels {
+            let actual = actual_itemsets
+                .iter()
+                .filter(|i| i.items.len() == level.size)
+                .count();
+            assert!(within(level.itemsets, actual), "level {}", level.size);
+        }
+        assert!(estimate.projected_runtime >= estimate.sample_runtime);
+    }
+
+    #[test]
+    fn test_sample_is_reproducible() {
+        let transactions = synthetic(200, 3);
+        let a = sample_transactions(&transactions, 0.25, 11);
+        let b = sample_transactions(&transactions, 0.25, 11);
+        assert_eq!(
+            a.iter().map(|t| &t.id).collect::<Vec<_>>(),
+            b.iter().map(|t| &t.id).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_empty_sample_rejected() {
+        assert!(matches!(
+            estimate(&[], &config(), 100),
+            Err(MiningError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_small_sample_of_valid_config() {
+        // 2% of 1,000 is 20 occurrences; the lowered 1% of 100 is only one
+        let transactions = synthetic(1000, 5);
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.02).unwrap())
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config.clone());
+        miner.add_transactions(transactions.clone()).unwrap();
+        assert!(miner.mine().is_ok());
+
+        let sample = sample_transactions(&transactions, 0.1, 3);
+        assert!(estimate(&sample, &config, transactions.len()).is_ok());
+
+        // A config the full run would reject is rejected up front
+        let singleton = MiningConfig::builder()
+            .min_support(Support::fraction(0.001).unwrap())
+            .build()
+            .unwrap();
+        assert!(matches!(
+            estimate(&sample, &singleton, transactions.len()),
+            Err(MiningError::InvalidConfig(_))
+        ));
+    }
+}
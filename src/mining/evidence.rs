@@ -0,0 +1,67 @@
+//! Transactions behind mined rules
+//!
+//! A rule's metrics say how often its items co-occur, not where. With
+//! `MiningConfig::collect_evidence` set, `RuleMiner::mine_association_rules_with_evidence`
+//! also returns the IDs of the first transactions holding both sides of each
+//! rule, so a surprising rule can be checked against the baskets that
+//! produced it.
+
+use std::collections::HashMap;
+
+use crate::deployment::RuleKey;
+use crate::transaction::Transaction;
+use crate::types::AssociationRule;
+
+use super::index::TransactionIndex;
+
+/// Supporting transaction IDs of each mined rule
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleEvidence {
+    ids: HashMap<RuleKey, Vec<String>>,
+}
+
+impl RuleEvidence {
+    /// Up to `max_ids` IDs per rule of the transactions holding its
+    /// antecedent and consequent, in transaction order
+    pub(crate) fn collect(
+        rules: &[AssociationRule],
+        transactions: &[Transaction],
+        index: &TransactionIndex,
+        max_ids: usize,
+    ) -> Self {
+        let ids = rules
+            .iter()
+            .map(|rule| {
+                let items = rule.antecedent.iter().chain(&rule.consequent);
+                let ids = index.encode(items).map_or_else(Vec::new, |encoded| {
+                    index
+                        .matching(&encoded)
+                        .take(max_ids)
+                        .map(|position| transactions[position].id.clone())
+                        .collect()
+                });
+                (RuleKey::of(rule), ids)
+            })
+            .collect();
+        Self { ids }
+    }
+
+    /// Supporting transaction IDs of `rule`, if it was mined
+    pub fn get(&self, rule: &AssociationRule) -> Option<&[String]> {
+        self.ids.get(&RuleKey::of(rule)).map(Vec::as_slice)
+    }
+
+    /// Rules with evidence and their transaction IDs, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&RuleKey, &[String])> {
+        self.ids.iter().map(|(key, ids)| (key, ids.as_slice()))
+    }
+
+    /// Number of rules with evidence
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
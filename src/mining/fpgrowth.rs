@@ -1,7 +1,11 @@
+use crate::config::{MiningConfig, Support};
 use crate::errors::Result;
+use crate::mining::budget::Budget;
+use crate::mining::progress::{Progress, ProgressPhase};
+use crate::mining::stats::{Phase, PhaseTimer, PhaseTimings};
 use crate::transaction::Transaction;
 use crate::types::{FrequentItemset, ItemSet};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Find all frequent itemsets using FP-Growth algorithm
 ///
@@ -13,13 +17,53 @@ pub fn find_frequent_itemsets(
     transactions: &[Transaction],
     min_support: f64,
 ) -> Result<Vec<FrequentItemset>> {
-    let total_transactions = transactions.len() as f64;
-    let min_support_count = (min_support * total_transactions).ceil() as usize;
+    let mut config = MiningConfig::default();
+    config.set_min_support(Support::fraction(min_support)?);
+    find_frequent_itemsets_with_config(transactions, &config)
+}
+
+/// Find frequent itemsets using FP-Growth, honoring `min_support`,
+/// `max_itemset_size`, the run limits and item `constraints` from the config
+pub fn find_frequent_itemsets_with_config(
+    transactions: &[Transaction],
+    config: &MiningConfig,
+) -> Result<Vec<FrequentItemset>> {
+    find_frequent_itemsets_timed(
+        transactions,
+        config,
+        &mut PhaseTimings::default(),
+        &mut Progress::none(),
+        &mut Budget::new(config),
+    )
+}
+
+/// FP-Growth recording the item-counting pass into `timings`, reporting to
+/// `progress`
+///
+/// Once `budget` is spent pattern growth stops and the patterns found so
+/// far are kept.
+pub(crate) fn find_frequent_itemsets_timed(
+    transactions: &[Transaction],
+    config: &MiningConfig,
+    timings: &mut PhaseTimings,
+    progress: &mut Progress<'_>,
+    budget: &mut Budget,
+) -> Result<Vec<FrequentItemset>> {
+    let max_size = config.max_itemset_size.unwrap_or(usize::MAX);
+    if max_size == 0 {
+        return Ok(Vec::new());
+    }
 
-    // Step 1: Count item frequencies
+    let total_transactions = transactions.len();
+    let min_support_count = config.min_support_count(total_transactions);
+    let transactions = &*config.constraints.project(transactions);
+
+    // Step 1: Count item frequencies (once per transaction, like Apriori)
+    let item_timer = PhaseTimer::start(Phase::ItemCounting);
     let mut item_counts: HashMap<String, usize> = HashMap::new();
     for tx in transactions {
-        for item in &tx.items {
+        let unique: HashSet<&String> = tx.items.iter().collect();
+        for item in unique {
             *item_counts.entry(item.clone()).or_insert(0) += 1;
         }
     }
@@ -29,6 +73,7 @@ pub fn find_frequent_itemsets(
         .into_iter()
         .filter(|(_, count)| *count >= min_support_count)
         .collect();
+    timings.item_counting = item_timer.stop();
 
     // Sort by frequency (descending) for FP-Tree efficiency
     frequent_items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
@@ -42,7 +87,8 @@ pub fn find_frequent_itemsets(
 
     // Step 3: Build FP-Tree
     let mut fp_tree = FPTree::new();
-    for tx in transactions {
+    for (done, tx) in transactions.iter().enumerate() {
+        progress.checkpoint(ProgressPhase::TreeBuilding, done, transactions.len())?;
         // Filter and sort items by frequency order
         let mut ordered_items: Vec<String> = tx
             .items
@@ -52,25 +98,41 @@ pub fn find_frequent_itemsets(
             .collect();
 
         ordered_items.sort_by_key(|item| freq_order.get(item).unwrap());
+        ordered_items.dedup();
 
         if !ordered_items.is_empty() {
             fp_tree.insert_transaction(&ordered_items);
         }
     }
 
-    // Step 4: Mine patterns from FP-Tree
-    let mut frequent_itemsets = Vec::new();
+    // Step 4: Mine patterns from FP-Tree, keyed by canonical (sorted) itemset
+    // so each itemset is reported exactly once
+    let mut patterns: HashMap<ItemSet, usize> = frequent_items
+        .iter()
+        .map(|(item, count)| (vec![item.clone()], *count))
+        .collect();
 
-    // Add 1-itemsets
-    for (item, count) in &frequent_items {
-        frequent_itemsets.push(FrequentItemset {
-            items: vec![item.clone()],
-            support: *count as f64 / total_transactions,
-        });
-    }
+    progress.checkpoint(
+        ProgressPhase::TreeBuilding,
+        transactions.len(),
+        transactions.len(),
+    )?;
 
     // Mine larger itemsets using FP-Growth
-    for (item, _) in frequent_items.iter().rev() {
+    let growth_total = if max_size < 2 {
+        0
+    } else {
+        frequent_items.len()
+    };
+    for (done, (item, _)) in frequent_items.iter().rev().enumerate() {
+        if max_size < 2 {
+            break;
+        }
+        progress.checkpoint(ProgressPhase::PatternGrowth, done, growth_total)?;
+        if budget.exhausted(patterns.len()) {
+            break;
+        }
+
         // Build conditional pattern base
         let conditional_patterns = fp_tree.get_conditional_pattern_base(item);
 
@@ -78,35 +140,58 @@ pub fn find_frequent_itemsets(
             // Build conditional FP-Tree
             let mut cond_tree = FPTree::new();
             for (pattern, count) in &conditional_patterns {
-                for _ in 0..*count {
-                    cond_tree.insert_transaction(pattern);
-                }
+                cond_tree.insert_transaction_with_count(pattern, *count);
             }
 
             // Mine conditional tree
-            let cond_patterns =
-                mine_conditional_tree(&cond_tree, vec![item.clone()], min_support_count);
-
-            for (itemset, count) in cond_patterns {
-                frequent_itemsets.push(FrequentItemset {
-                    items: itemset,
-                    support: count as f64 / total_transactions,
-                });
-            }
+            mine_conditional_tree(
+                &cond_tree,
+                vec![item.clone()],
+                min_support_count,
+                max_size,
+                &mut patterns,
+                budget,
+            );
         }
     }
 
+    progress.checkpoint(ProgressPhase::PatternGrowth, growth_total, growth_total)?;
+
+    let mut frequent_itemsets: Vec<FrequentItemset> = patterns
+        .into_iter()
+        .filter(|(itemset, _)| config.constraints.allows_itemset(itemset))
+        .map(|(items, count)| FrequentItemset::from_count(items, count, total_transactions))
+        .collect();
+
+    // Deterministic order: by size, then support (descending), then items;
+    // past the itemset limit the tail is dropped
+    frequent_itemsets.sort_by(|a, b| {
+        a.items
+            .len()
+            .cmp(&b.items.len())
+            .then_with(|| {
+                b.support
+                    .partial_cmp(&a.support)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| a.items.cmp(&b.items))
+    });
+    frequent_itemsets.truncate(budget.limit());
     Ok(frequent_itemsets)
 }
 
-/// Mine patterns from conditional FP-Tree
+/// Mine patterns from conditional FP-Tree into `patterns`
+///
+/// Patterns extending `base_pattern` are recorded with their support count
+/// under their sorted items, until `budget` is spent.
 fn mine_conditional_tree(
     tree: &FPTree,
     base_pattern: Vec<String>,
     min_support_count: usize,
-) -> Vec<(ItemSet, usize)> {
-    let mut patterns = Vec::new();
-
+    max_size: usize,
+    patterns: &mut HashMap<ItemSet, usize>,
+    budget: &mut Budget,
+) {
     // Get all items and their counts from the tree
     let item_counts = tree.get_item_counts();
 
@@ -119,12 +204,21 @@ fn mine_conditional_tree(
     frequent_items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
 
     for (item, count) in &frequent_items {
+        if budget.exhausted(patterns.len()) {
+            return;
+        }
+
         // Create new pattern by adding this item to base
         let mut new_pattern = base_pattern.clone();
         new_pattern.push(item.clone());
         new_pattern.sort(); // Ensure canonical order
 
-        patterns.push((new_pattern.clone(), *count));
+        patterns.insert(new_pattern.clone(), *count);
+
+        // Don't extend patterns beyond the size limit
+        if new_pattern.len() >= max_size {
+            continue;
+        }
 
         // Build conditional pattern base for this item
         let cond_patterns = tree.get_conditional_pattern_base(item);
@@ -133,121 +227,130 @@ fn mine_conditional_tree(
             // Build conditional tree
             let mut cond_tree = FPTree::new();
             for (pattern, pattern_count) in &cond_patterns {
-                for _ in 0..*pattern_count {
-                    cond_tree.insert_transaction(pattern);
-                }
+                cond_tree.insert_transaction_with_count(pattern, *pattern_count);
             }
 
             // Recursively mine
-            let nested_patterns = mine_conditional_tree(&cond_tree, new_pattern, min_support_count);
-            patterns.extend(nested_patterns);
+            mine_conditional_tree(
+                &cond_tree,
+                new_pattern,
+                min_support_count,
+                max_size,
+                patterns,
+                budget,
+            );
         }
     }
-
-    patterns
 }
 
+/// Index of a node in `FPTree::nodes`
+type NodeId = usize;
+
+/// The root node, which holds no item
+const ROOT: NodeId = 0;
+
 /// FP-Tree node
 #[derive(Debug, Clone)]
 struct FPNode {
     item: Option<String>,
     count: usize,
-    children: HashMap<String, FPNode>,
+    parent: Option<NodeId>,
+    children: HashMap<String, NodeId>,
 }
 
 impl FPNode {
-    fn new(item: Option<String>) -> Self {
+    fn new(item: Option<String>, parent: Option<NodeId>) -> Self {
         Self {
             item,
             count: 0,
+            parent,
             children: HashMap::new(),
         }
     }
 }
 
 /// FP-Tree structure
+///
+/// Nodes live in an arena and point to their parent, and the header table
+/// links every node holding an item, so conditional pattern bases are read
+/// by walking up from those nodes instead of scanning the whole tree.
 #[derive(Debug)]
 struct FPTree {
-    root: FPNode,
+    nodes: Vec<FPNode>,
+    header: HashMap<String, Vec<NodeId>>,
 }
 
 impl FPTree {
     fn new() -> Self {
         Self {
-            root: FPNode::new(None),
+            nodes: vec![FPNode::new(None, None)],
+            header: HashMap::new(),
         }
     }
 
     /// Insert a transaction into the FP-Tree
     fn insert_transaction(&mut self, items: &[String]) {
-        let mut current = &mut self.root;
+        self.insert_transaction_with_count(items, 1);
+    }
 
-        for item in items {
-            let exists = current.children.contains_key(item);
-
-            if exists {
-                current.children.get_mut(item).unwrap().count += 1;
-            } else {
-                let mut new_node = FPNode::new(Some(item.clone()));
-                new_node.count = 1;
-                current.children.insert(item.clone(), new_node);
-            }
+    /// Insert a path that occurred `count` times, in a single pass
+    fn insert_transaction_with_count(&mut self, items: &[String], count: usize) {
+        let mut current = ROOT;
 
-            current = current.children.get_mut(item).unwrap();
+        for item in items {
+            let child = match self.nodes[current].children.get(item) {
+                Some(&child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes
+                        .push(FPNode::new(Some(item.clone()), Some(current)));
+                    self.nodes[current].children.insert(item.clone(), child);
+                    self.header.entry(item.clone()).or_default().push(child);
+                    child
+                }
+            };
+            self.nodes[child].count += count;
+            current = child;
         }
     }
 
     /// Get conditional pattern base for an item
-    /// Returns list of (prefix_path, count) tuples
+    /// Returns list of (prefix_path, count) tuples, prefixes ordered root first
     fn get_conditional_pattern_base(&self, item: &str) -> Vec<(Vec<String>, usize)> {
-        let mut patterns = Vec::new();
-        let mut current_path = Vec::new();
-
-        // Recursively find all paths ending with the target item
-        Self::collect_paths_for_item(&self.root, item, &mut current_path, &mut patterns);
+        let Some(node_ids) = self.header.get(item) else {
+            return Vec::new();
+        };
 
-        patterns
-    }
+        let mut patterns = Vec::new();
+        for &node_id in node_ids {
+            let mut path = Vec::new();
+            let mut parent = self.nodes[node_id].parent;
+            while let Some(id) = parent {
+                let node = &self.nodes[id];
+                if let Some(item) = &node.item {
+                    path.push(item.clone());
+                }
+                parent = node.parent;
+            }
 
-    /// Recursively collect all paths ending with target item
-    fn collect_paths_for_item(
-        node: &FPNode,
-        target_item: &str,
-        current_path: &mut Vec<String>,
-        patterns: &mut Vec<(Vec<String>, usize)>,
-    ) {
-        // Check if any child matches target item
-        if let Some(child) = node.children.get(target_item) {
-            // Found a match - add the current path (excluding target) with count
-            if !current_path.is_empty() {
-                patterns.push((current_path.clone(), child.count));
+            if !path.is_empty() {
+                path.reverse();
+                patterns.push((path, self.nodes[node_id].count));
             }
         }
 
-        // Recursively search all children
-        for (item, child) in &node.children {
-            current_path.push(item.clone());
-            Self::collect_paths_for_item(child, target_item, current_path, patterns);
-            current_path.pop();
-        }
+        patterns
     }
 
     /// Get item counts from the tree
     fn get_item_counts(&self) -> HashMap<String, usize> {
-        let mut counts = HashMap::new();
-        Self::count_items_recursive(&self.root, &mut counts);
-        counts
-    }
-
-    /// Recursively count items in the tree
-    fn count_items_recursive(node: &FPNode, counts: &mut HashMap<String, usize>) {
-        if let Some(item) = &node.item {
-            *counts.entry(item.clone()).or_insert(0) += node.count;
-        }
-
-        for child in node.children.values() {
-            Self::count_items_recursive(child, counts);
-        }
+        self.header
+            .iter()
+            .map(|(item, node_ids)| {
+                let count = node_ids.iter().map(|&id| self.nodes[id].count).sum();
+                (item.clone(), count)
+            })
+            .collect()
     }
 }
 
@@ -290,4 +393,127 @@ mod tests {
         assert!(frequent.iter().any(|f| f.items == vec!["B".to_string()]));
         assert!(frequent.iter().any(|f| f.items == vec!["C".to_string()]));
     }
+
+    #[test]
+    fn test_fpgrowth_max_itemset_size() {
+        let transactions = create_test_transactions();
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.25).unwrap())
+            .max_itemset_size(2)
+            .build()
+            .unwrap();
+        let frequent = find_frequent_itemsets_with_config(&transactions, &config).unwrap();
+
+        assert!(frequent.iter().any(|f| f.items.len() == 2));
+        assert!(frequent.iter().all(|f| f.items.len() <= 2));
+    }
+
+    #[test]
+    fn test_insert_with_count_matches_repeated_inserts() {
+        let paths: Vec<(Vec<String>, usize)> = vec![
+            (vec!["A".to_string(), "B".to_string()], 3),
+            (vec!["A".to_string(), "C".to_string()], 2),
+            (vec!["B".to_string(), "C".to_string()], 4),
+        ];
+
+        let mut repeated = FPTree::new();
+        let mut counted = FPTree::new();
+        for (path, count) in &paths {
+            for _ in 0..*count {
+                repeated.insert_transaction(path);
+            }
+            counted.insert_transaction_with_count(path, *count);
+        }
+
+        assert_eq!(repeated.get_item_counts(), counted.get_item_counts());
+        let mut expected = repeated.get_conditional_pattern_base("C");
+        let mut actual = counted.get_conditional_pattern_base("C");
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual,
+            vec![(vec!["A".to_string()], 2), (vec!["B".to_string()], 4)]
+        );
+    }
+
+    #[test]
+    fn test_header_table_links_every_node() {
+        let mut tree = FPTree::new();
+        tree.insert_transaction(&["A".to_string(), "B".to_string(), "C".to_string()]);
+        tree.insert_transaction(&["A".to_string(), "C".to_string()]);
+        tree.insert_transaction(&["B".to_string(), "C".to_string()]);
+
+        // C hangs off three different prefixes
+        assert_eq!(tree.header["C"].len(), 3);
+        assert_eq!(tree.get_item_counts()["C"], 3);
+
+        let mut base = tree.get_conditional_pattern_base("C");
+        base.sort();
+        assert_eq!(
+            base,
+            vec![
+                (vec!["A".to_string()], 1),
+                (vec!["A".to_string(), "B".to_string()], 1),
+                (vec!["B".to_string()], 1),
+            ]
+        );
+        assert!(tree.get_conditional_pattern_base("A").is_empty());
+        assert!(tree.get_conditional_pattern_base("Z").is_empty());
+    }
+
+    /// Random baskets over `items` items, with occasional repeated items
+    fn random_transactions(seed: u64, count: usize, items: u64) -> Vec<Transaction> {
+        let mut rng = crate::rng::SplitMix64::new(seed);
+        (0..count)
+            .map(|tx| {
+                let len = 1 + rng.next_u64() % 6;
+                let basket = (0..len)
+                    .map(|_| format!("i{}", rng.next_u64() % items))
+                    .collect();
+                Transaction::new(format!("tx{}", tx), basket, Utc::now())
+            })
+            .collect()
+    }
+
+    fn support_map(itemsets: &[FrequentItemset]) -> HashMap<ItemSet, f64> {
+        itemsets
+            .iter()
+            .map(|itemset| (itemset.items.clone(), itemset.support))
+            .collect()
+    }
+
+    #[test]
+    fn test_fpgrowth_matches_apriori_on_random_data() {
+        for seed in 0..8 {
+            let transactions = random_transactions(seed, 120, 8);
+            for min_support in [0.05, 0.1, 0.2] {
+                let config = MiningConfig::builder()
+                    .min_support(Support::fraction(min_support).unwrap())
+                    .build()
+                    .unwrap();
+                let fpgrowth = find_frequent_itemsets_with_config(&transactions, &config).unwrap();
+                let apriori = crate::mining::apriori::find_frequent_itemsets_with_config(
+                    &transactions,
+                    &config,
+                )
+                .unwrap();
+
+                // Each itemset once, in canonical order
+                let unique: HashSet<&ItemSet> = fpgrowth.iter().map(|f| &f.items).collect();
+                assert_eq!(unique.len(), fpgrowth.len(), "seed {}", seed);
+                assert!(fpgrowth
+                    .iter()
+                    .all(|f| f.items.windows(2).all(|w| w[0] < w[1])));
+
+                assert_eq!(
+                    support_map(&fpgrowth),
+                    support_map(&apriori),
+                    "seed {} min_support {}",
+                    seed,
+                    min_support
+                );
+            }
+        }
+    }
 }
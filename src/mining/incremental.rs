@@ -0,0 +1,652 @@
+//! Incremental mining over a sliding window of transactions
+//!
+//! Continuous sources would otherwise re-mine from scratch every hour.
+//! `IncrementalMiner` keeps the window's transactions together with per-item
+//! and per-pair counts, updated as transactions arrive and leave, so
+//! 1-item => 1-item rules come straight from the counts without a rescan.
+//! Transactions the config excludes (by id or time range) stay in the window
+//! but are left out of the counts.
+//!
+//! # Approximation
+//!
+//! The counts are exact for the window, so `pair_rules` matches what
+//! `RuleMiner` reports for 1 => 1 rules on the same transactions. It is an
+//! approximation of a full run in what it leaves out:
+//! - rules with more than one item on either side
+//! - `support_universe` (every transaction is one basket), item weights and
+//!   `itemset_kind`
+//!
+//! `mine_association_rules` only answers from the counts when
+//! `max_itemset_size` is at most 2; otherwise it mines the window in full.
+//!
+//! Pair counts cover every co-occurring pair, frequent or not, since an
+//! infrequent pair can become frequent as the window slides; memory grows
+//! with the number of distinct pairs in the window.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::analysis::{timestamp_audit, AuditPolicy};
+use crate::config::MiningConfig;
+use crate::errors::{MiningError, Result};
+use crate::mining::RuleMiner;
+use crate::transaction::Transaction;
+use crate::types::AssociationRule;
+
+/// Miner keeping item and pair counts of a sliding transaction window
+///
+/// Transactions leave the window oldest first (by timestamp) once they are
+/// older than `max_age` relative to the newest transaction seen, or once the
+/// window holds more than `max_transactions`.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::mining::incremental::IncrementalMiner;
+/// use rust_rule_miner::{Confidence, MiningConfig, Support, Transaction};
+/// use chrono::{Duration, TimeZone, Utc};
+///
+/// let config = MiningConfig::builder()
+///     .min_support(Support::fraction(0.8)?)
+///     .min_confidence(Confidence::fraction(0.8)?)
+///     .build()?;
+/// let mut miner = IncrementalMiner::new(config)
+///     .with_max_age(std::time::Duration::from_secs(3600));
+///
+/// let start = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap();
+/// let basket = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+/// miner.add_transaction(Transaction::new("t1", basket(&["Tea", "Cake"]), start))?;
+/// miner.add_transaction(Transaction::new("t2", basket(&["Tea", "Cake"]), start))?;
+/// assert_eq!(miner.pair_rules()?.len(), 1);
+///
+/// // Two hours later the morning baskets have left the window
+/// let later = start + Duration::hours(2);
+/// miner.add_transaction(Transaction::new("t3", basket(&["Beer"]), later))?;
+/// assert_eq!(miner.len(), 1);
+/// assert_eq!(miner.pair_count("Tea", "Cake"), 0);
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalMiner {
+    config: MiningConfig,
+    max_age: Option<Duration>,
+    max_transactions: Option<usize>,
+    audit: AuditPolicy,
+    /// Window contents, oldest first; the sequence number keeps arrival
+    /// order among equal timestamps
+    window: BTreeMap<(DateTime<Utc>, u64), Transaction>,
+    next_seq: u64,
+    newest: Option<DateTime<Utc>>,
+    /// Window transactions the config does not exclude
+    counted: usize,
+    item_counts: HashMap<String, usize>,
+    /// Keyed by the item pair in sorted order
+    pair_counts: HashMap<(String, String), usize>,
+    evicted: usize,
+}
+
+impl IncrementalMiner {
+    /// Create a miner with an unbounded window
+    pub fn new(config: MiningConfig) -> Self {
+        Self {
+            config,
+            max_age: None,
+            max_transactions: None,
+            audit: AuditPolicy::Off,
+            window: BTreeMap::new(),
+            next_seq: 0,
+            newest: None,
+            counted: 0,
+            item_counts: HashMap::new(),
+            pair_counts: HashMap::new(),
+            evicted: 0,
+        }
+    }
+
+    /// Evict transactions older than `max_age` before the newest one
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Keep at most `max_transactions`, evicting the oldest
+    pub fn with_max_transactions(mut self, max_transactions: usize) -> Self {
+        self.max_transactions = Some(max_transactions);
+        self
+    }
+
+    /// Audit the window's timestamps before mining it
+    ///
+    /// Eviction by `max_age` trusts timestamps, so a window of `Utc::now()`
+    /// fallbacks or future dates never ages out as intended; see
+    /// `analysis::timestamp_audit`. Off by default.
+    pub fn with_timestamp_audit(mut self, policy: AuditPolicy) -> Self {
+        self.audit = policy;
+        self
+    }
+
+    /// Add a transaction to the window, evicting what falls out of it
+    pub fn add_transaction(&mut self, transaction: Transaction) -> Result<()> {
+        let timestamp = transaction.timestamp;
+        self.count(&transaction, true);
+        self.window.insert((timestamp, self.next_seq), transaction);
+        self.next_seq += 1;
+        self.advance_to(timestamp);
+        Ok(())
+    }
+
+    /// Add several transactions, in order
+    pub fn add_transactions<I>(&mut self, transactions: I) -> Result<()>
+    where
+        I: IntoIterator<Item = Transaction>,
+    {
+        for transaction in transactions {
+            self.add_transaction(transaction)?;
+        }
+        Ok(())
+    }
+
+    /// Evict as if a transaction stamped `now` had arrived
+    ///
+    /// Lets an idle stream age out its window, e.g. on a timer.
+    pub fn advance_to(&mut self, now: DateTime<Utc>) {
+        let newest = self.newest.map_or(now, |newest| newest.max(now));
+        self.newest = Some(newest);
+
+        if let Some(cutoff) = self
+            .max_age
+            .and_then(|age| newest.checked_sub_signed(chrono::Duration::from_std(age).ok()?))
+        {
+            while self
+                .window
+                .first_key_value()
+                .is_some_and(|((timestamp, _), _)| *timestamp < cutoff)
+            {
+                self.evict_oldest();
+            }
+        }
+        if let Some(max) = self.max_transactions {
+            while self.window.len() > max {
+                self.evict_oldest();
+            }
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some((_, transaction)) = self.window.pop_first() {
+            self.count(&transaction, false);
+            self.evicted += 1;
+        }
+    }
+
+    /// Add (or remove) a transaction's items and pairs to the counts,
+    /// unless the config excludes it
+    fn count(&mut self, transaction: &Transaction, add: bool) {
+        if self.config.excludes(transaction) {
+            return;
+        }
+        if add {
+            self.counted += 1;
+        } else {
+            self.counted -= 1;
+        }
+        let mut items: Vec<&String> = transaction
+            .items
+            .iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        items.sort();
+
+        for item in &items {
+            update(&mut self.item_counts, (*item).clone(), add);
+        }
+        for i in 0..items.len() {
+            for j in (i + 1)..items.len() {
+                update(
+                    &mut self.pair_counts,
+                    (items[i].clone(), items[j].clone()),
+                    add,
+                );
+            }
+        }
+    }
+
+    /// Transactions in the window
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Check if the window holds no transactions
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Transactions evicted so far
+    pub fn evicted(&self) -> usize {
+        self.evicted
+    }
+
+    /// Counted window transactions holding `item`
+    pub fn item_count(&self, item: &str) -> usize {
+        self.item_counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// Counted window transactions holding both items
+    pub fn pair_count(&self, a: &str, b: &str) -> usize {
+        let key = if a <= b { (a, b) } else { (b, a) };
+        self.pair_counts
+            .get(&(key.0.to_string(), key.1.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Window transactions, oldest first
+    pub fn transactions(&self) -> impl Iterator<Item = &Transaction> {
+        self.window.values()
+    }
+
+    /// 1-item => 1-item rules from the maintained counts, without a rescan
+    ///
+    /// Thresholds, constraints, `max_consequent_base_rate`, significance and
+    /// bidirectional filtering follow the config, as in `TwoPassMiner`.
+    pub fn pair_rules(&self) -> Result<Vec<AssociationRule>> {
+        self.config.validate()?;
+        self.audit_window()?;
+        if self.counted == 0 {
+            return Err(MiningError::InsufficientData(
+                "No included transactions in the window".to_string(),
+            ));
+        }
+        if self.config.max_itemset_size.is_some_and(|max| max < 2) {
+            return Ok(Vec::new());
+        }
+
+        let total = self.counted;
+        self.config.check_support_count(total)?;
+        let min_support_count = self.config.min_support_count(total);
+        let mut rules = Vec::new();
+        let mut tested = 0;
+        let mut base_rate = super::BaseRateFilter::new(
+            &self.config,
+            self.item_counts
+                .iter()
+                .map(|(item, count)| (item.as_str(), *count)),
+            total,
+        );
+
+        for ((item_a, item_b), &both_count) in &self.pair_counts {
+            if both_count < min_support_count {
+                continue;
+            }
+            for (antecedent, consequent) in [(item_a, item_b), (item_b, item_a)] {
+                let antecedent = std::slice::from_ref(antecedent);
+                let consequent = std::slice::from_ref(consequent);
+                if !self.config.constraints.allows_rule(antecedent, consequent) {
+                    continue;
+                }
+                tested += 1;
+                let metrics = super::metrics_from_counts(
+                    self.item_count(&antecedent[0]),
+                    self.item_count(&consequent[0]),
+                    both_count,
+                    total as f64,
+                    self.config.metric_set(),
+                );
+
+                if metrics.confidence >= self.config.min_confidence()
+                    && metrics.lift >= self.config.min_lift()
+                    && self.config.allows_metrics(&metrics)
+                    && !base_rate.suppresses(consequent)
+                {
+                    rules.push(AssociationRule {
+                        antecedent: antecedent.to_vec(),
+                        consequent: consequent.to_vec(),
+                        metrics,
+                    });
+                }
+            }
+        }
+        if base_rate.suppressed() > 0 {
+            log::info!(
+                "{} rules dropped by max_consequent_base_rate",
+                base_rate.suppressed()
+            );
+        }
+        let mut rules = super::significance::correct(rules, tested, &self.config);
+
+        // Same ordering and bidirectional filtering as TwoPassMiner
+        rules.sort_by(|a, b| {
+            b.quality_score()
+                .partial_cmp(&a.quality_score())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.antecedent.cmp(&b.antecedent))
+        });
+        Ok(super::filter_bidirectional_rules(rules, &self.config))
+    }
+
+    /// Rules for the current window
+    ///
+    /// Answers from the counts (`pair_rules`) when `max_itemset_size` is at
+    /// most 2, and otherwise mines the window's transactions with
+    /// `RuleMiner`.
+    pub fn mine_association_rules(&self) -> Result<Vec<AssociationRule>> {
+        if self.config.max_itemset_size.is_some_and(|max| max <= 2) {
+            return self.pair_rules();
+        }
+
+        self.audit_window()?;
+        let mut miner = RuleMiner::new(self.config.clone());
+        miner.add_transactions(self.transactions().cloned().collect())?;
+        miner.mine().map(|outcome| outcome.rules)
+    }
+
+    /// Apply the `with_timestamp_audit` policy to the window
+    fn audit_window(&self) -> Result<()> {
+        if self.audit == AuditPolicy::Off {
+            return Ok(());
+        }
+        let window: Vec<Transaction> = self.transactions().cloned().collect();
+        timestamp_audit(&window).enforce(self.audit)
+    }
+}
+
+/// Increment or decrement a count, dropping it at zero
+fn update<K: std::hash::Hash + Eq>(counts: &mut HashMap<K, usize>, key: K, add: bool) {
+    if add {
+        *counts.entry(key).or_insert(0) += 1;
+    } else if let Some(count) = counts.get_mut(&key) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Confidence, Support};
+    use chrono::TimeZone;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 9, minute, 0).unwrap()
+    }
+
+    fn tx(id: &str, items: &[&str], minute: u32) -> Transaction {
+        Transaction::new(
+            id,
+            items.iter().map(|s| s.to_string()).collect(),
+            at(minute),
+        )
+    }
+
+    fn config() -> MiningConfig {
+        MiningConfig::builder()
+            .min_support(Support::fraction(0.3).unwrap())
+            .min_confidence(Confidence::fraction(0.6).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn fixture() -> Vec<Transaction> {
+        vec![
+            tx("t1", &["Laptop", "Mouse", "Keyboard"], 0),
+            tx("t2", &["Laptop", "Mouse"], 1),
+            tx("t3", &["Laptop", "Mouse", "Hub"], 2),
+            tx("t4", &["Laptop", "Keyboard"], 3),
+            tx("t5", &["Phone", "Case"], 4),
+            tx("t6", &["Phone", "Case", "Charger"], 5),
+            tx("t7", &["Phone", "Charger", "Charger"], 6),
+        ]
+    }
+
+    #[test]
+    fn test_pair_rules_match_full_mining() {
+        let mut incremental = IncrementalMiner::new(config());
+        incremental.add_transactions(fixture()).unwrap();
+        let pair_rules = incremental.pair_rules().unwrap();
+
+        let mut miner = RuleMiner::new(config());
+        miner.add_transactions(fixture()).unwrap();
+        let full: Vec<AssociationRule> = miner
+            .mine()
+            .unwrap()
+            .rules
+            .into_iter()
+            .filter(|r| r.antecedent.len() == 1 && r.consequent.len() == 1)
+            .collect();
+
+        assert!(!full.is_empty());
+        assert_eq!(pair_rules.len(), full.len());
+        for rule in &full {
+            let other = pair_rules
+                .iter()
+                .find(|r| r.antecedent == rule.antecedent && r.consequent == rule.consequent)
+                .expect("rule missing from pair rules");
+            assert!((other.metrics.confidence - rule.metrics.confidence).abs() < 1e-12);
+            assert!((other.metrics.support - rule.metrics.support).abs() < 1e-12);
+            assert!((other.metrics.lift - rule.metrics.lift).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_max_transactions_evicts_counts() {
+        // 50% keeps two occurrences for the three transactions left
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.5).unwrap())
+            .min_confidence(Confidence::fraction(0.6).unwrap())
+            .build()
+            .unwrap();
+        let mut miner = IncrementalMiner::new(config).with_max_transactions(3);
+        miner.add_transactions(fixture()).unwrap();
+
+        // Only t5..t7 remain
+        assert_eq!(miner.len(), 3);
+        assert_eq!(miner.evicted(), 4);
+        assert_eq!(miner.item_count("Laptop"), 0);
+        assert_eq!(miner.pair_count("Mouse", "Laptop"), 0);
+        assert_eq!(miner.item_count("Charger"), 2);
+        assert_eq!(miner.pair_count("Case", "Phone"), 2);
+        assert!(!miner.pair_counts.keys().any(|(a, _)| a == "Laptop"));
+
+        let rules = miner.pair_rules().unwrap();
+        assert!(!rules.is_empty());
+        assert!(rules
+            .iter()
+            .all(|r| !r.antecedent.contains(&"Laptop".to_string())
+                && !r.consequent.contains(&"Laptop".to_string())));
+    }
+
+    #[test]
+    fn test_max_age_evicts_by_timestamp() {
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.3).unwrap())
+            .min_confidence(Confidence::fraction(0.6).unwrap())
+            .allow_singleton_support(true)
+            .build()
+            .unwrap();
+        let mut miner = IncrementalMiner::new(config).with_max_age(Duration::from_secs(3 * 60));
+        // Arrives out of order: t2 is older than t1
+        miner
+            .add_transaction(tx("t1", &["Tea", "Cake"], 10))
+            .unwrap();
+        miner
+            .add_transaction(tx("t2", &["Tea", "Cake"], 8))
+            .unwrap();
+        assert_eq!(miner.pair_count("Tea", "Cake"), 2);
+        // One direction of Tea <=> Cake survives bidirectional filtering
+        assert_eq!(miner.pair_rules().unwrap().len(), 1);
+
+        // 12:00 evicts 8:00 (older than 9:00) but keeps 10:00
+        miner
+            .add_transaction(tx("t3", &["Beer", "Chips"], 12))
+            .unwrap();
+        assert_eq!(miner.len(), 2);
+        assert_eq!(miner.pair_count("Tea", "Cake"), 1);
+
+        // An idle stream ages out on its own
+        miner.advance_to(at(20));
+        assert!(miner.is_empty());
+        assert_eq!(miner.item_count("Tea"), 0);
+        assert!(matches!(
+            miner.pair_rules(),
+            Err(MiningError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_longer_itemsets_fall_back_to_full_mining() {
+        let mut miner = IncrementalMiner::new(config());
+        miner
+            .add_transactions((0..4).map(|minute| tx("t", &["Tea", "Cake", "Milk"], minute)))
+            .unwrap();
+        let rules = miner.mine_association_rules().unwrap();
+        assert!(rules
+            .iter()
+            .any(|r| r.antecedent.len() + r.consequent.len() > 2));
+
+        let mut pairs_only = IncrementalMiner::new(
+            MiningConfig::builder()
+                .min_support(Support::fraction(0.3).unwrap())
+                .min_confidence(Confidence::fraction(0.6).unwrap())
+                .max_itemset_size(2)
+                .build()
+                .unwrap(),
+        );
+        pairs_only.add_transactions(fixture()).unwrap();
+        let rules = pairs_only.mine_association_rules().unwrap();
+        assert_eq!(rules.len(), pairs_only.pair_rules().unwrap().len());
+    }
+
+    #[test]
+    fn test_base_rate_cap_matches_full_mining() {
+        // Bag is in 10 of 12 baskets; Tea => Bag only restates that
+        let baskets: Vec<Transaction> = (0..12)
+            .map(|i| match i {
+                0..=3 => tx(&format!("t{}", i), &["Tea", "Bag"], i),
+                4..=9 => tx(&format!("t{}", i), &["Bag"], i),
+                _ => tx(&format!("t{}", i), &["Milk"], i),
+            })
+            .collect();
+        let capped = |cap: Option<f64>| {
+            let mut builder = MiningConfig::builder()
+                .min_support(Support::fraction(0.3).unwrap())
+                .min_confidence(Confidence::fraction(0.6).unwrap())
+                .max_itemset_size(2);
+            if let Some(cap) = cap {
+                builder = builder.max_consequent_base_rate(cap);
+            }
+            builder.build().unwrap()
+        };
+
+        let mut uncapped = IncrementalMiner::new(capped(None));
+        uncapped.add_transactions(baskets.clone()).unwrap();
+        assert_eq!(uncapped.mine_association_rules().unwrap().len(), 1);
+
+        let mut incremental = IncrementalMiner::new(capped(Some(0.4)));
+        incremental.add_transactions(baskets.clone()).unwrap();
+        let mut miner = RuleMiner::new(capped(Some(0.4)));
+        miner.add_transactions(baskets).unwrap();
+        assert!(miner.mine().unwrap().rules.is_empty());
+        assert!(incremental.mine_association_rules().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_singleton_support_rejected() {
+        // 30% of two transactions is a single occurrence
+        let mut miner = IncrementalMiner::new(config());
+        miner
+            .add_transactions([tx("t1", &["Tea", "Cake"], 0), tx("t2", &["Beer"], 1)])
+            .unwrap();
+        assert!(matches!(
+            miner.pair_rules(),
+            Err(MiningError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_exclusions_left_out_of_counts() {
+        let config = |excluded: bool| {
+            let mut builder = MiningConfig::builder()
+                .min_support(Support::fraction(0.3).unwrap())
+                .min_confidence(Confidence::fraction(0.6).unwrap())
+                .max_itemset_size(2);
+            if excluded {
+                builder = builder
+                    .exclude_transaction_ids((0..5).map(|i| format!("ab{}", i)))
+                    .exclude_time_range(at(5), at(10));
+            }
+            builder.build().unwrap()
+        };
+        // Ten A,B baskets (five excluded by id, five by time) and ten C,D
+        let baskets: Vec<Transaction> = (0..10)
+            .map(|i| tx(&format!("ab{}", i), &["A", "B"], i))
+            .chain((0..10).map(|i| tx(&format!("cd{}", i), &["C", "D"], 20 + i)))
+            .collect();
+
+        let mut miner = RuleMiner::new(config(true));
+        miner.add_transactions(baskets.clone()).unwrap();
+        let full = miner.mine().unwrap().rules;
+
+        let mut incremental = IncrementalMiner::new(config(true));
+        incremental.add_transactions(baskets.clone()).unwrap();
+        assert_eq!(incremental.len(), 20);
+        assert_eq!(incremental.item_count("A"), 0);
+        assert_eq!(incremental.item_count("C"), 10);
+        let rules = incremental.mine_association_rules().unwrap();
+        assert_eq!(rules.len(), full.len());
+        assert!(rules
+            .iter()
+            .all(|r| r.antecedent != ["A"] && r.antecedent != ["B"]));
+        // Support is relative to the included transactions only
+        assert!(rules
+            .iter()
+            .all(|r| (r.metrics.support - 1.0).abs() < 1e-12));
+
+        let mut unexcluded = IncrementalMiner::new(config(false));
+        unexcluded.add_transactions(baskets).unwrap();
+        assert_eq!(unexcluded.mine_association_rules().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_timestamp_audit_policy() {
+        // Every basket stamped at load time: the Utc::now() fallback fingerprint
+        let loaded = Utc::now();
+        let baskets: Vec<Transaction> = (0..20)
+            .map(|i| {
+                let items = if i % 2 == 0 {
+                    vec!["Tea", "Cake"]
+                } else {
+                    vec!["Beer"]
+                };
+                Transaction::new(
+                    format!("t{}", i),
+                    items.into_iter().map(String::from).collect(),
+                    loaded,
+                )
+            })
+            .collect();
+
+        let mut refused = IncrementalMiner::new(config()).with_timestamp_audit(AuditPolicy::Refuse);
+        refused.add_transactions(baskets.clone()).unwrap();
+        assert!(matches!(
+            refused.pair_rules(),
+            Err(MiningError::InvalidTransaction(_))
+        ));
+        assert!(matches!(
+            refused.mine_association_rules(),
+            Err(MiningError::InvalidTransaction(_))
+        ));
+
+        let mut warned = IncrementalMiner::new(config()).with_timestamp_audit(AuditPolicy::Warn);
+        warned.add_transactions(baskets).unwrap();
+        assert!(!warned.pair_rules().unwrap().is_empty());
+
+        // Spread-out timestamps pass
+        let mut spread = IncrementalMiner::new(config()).with_timestamp_audit(AuditPolicy::Refuse);
+        spread.add_transactions(fixture()).unwrap();
+        assert!(spread.pair_rules().is_ok());
+    }
+}
@@ -0,0 +1,125 @@
+//! Integer-encoded transactions for fast support counting
+//!
+//! Support counting asks "does this transaction hold all of these items?"
+//! millions of times. `Transaction::contains_all` answers with string
+//! comparisons; `TransactionIndex` maps every item to an integer id once and
+//! stores each transaction as a sorted id list, so a subset check becomes a
+//! single merge over integers.
+
+use crate::interner::Interner;
+use crate::transaction::Transaction;
+
+/// Transactions as sorted, de-duplicated item ids
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TransactionIndex {
+    ids: Interner,
+    transactions: Vec<Vec<u32>>,
+}
+
+impl TransactionIndex {
+    pub(crate) fn new(transactions: &[Transaction]) -> Self {
+        let mut ids = Interner::new();
+        let transactions = transactions
+            .iter()
+            .map(|tx| {
+                let mut encoded: Vec<u32> = tx.items.iter().map(|item| ids.intern(item)).collect();
+                encoded.sort_unstable();
+                encoded.dedup();
+                encoded
+            })
+            .collect();
+
+        Self { ids, transactions }
+    }
+
+    /// Sorted ids of `items`, or None if any item never occurs
+    pub(crate) fn encode<'a, I>(&self, items: I) -> Option<Vec<u32>>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let mut encoded = items
+            .into_iter()
+            .map(|item| self.ids.id(item))
+            .collect::<Option<Vec<u32>>>()?;
+        encoded.sort_unstable();
+        encoded.dedup();
+        Some(encoded)
+    }
+
+    /// Number of transactions holding every item
+    pub(crate) fn count(&self, items: &[String]) -> usize {
+        self.encode(items)
+            .map_or(0, |encoded| self.count_encoded(&encoded))
+    }
+
+    /// Number of transactions holding every id of a sorted id list
+    pub(crate) fn count_encoded(&self, encoded: &[u32]) -> usize {
+        self.transactions
+            .iter()
+            .filter(|tx| is_subset(encoded, tx))
+            .count()
+    }
+
+    /// Positions of the transactions holding every id of a sorted id list
+    pub(crate) fn matching<'a>(&'a self, encoded: &'a [u32]) -> impl Iterator<Item = usize> + 'a {
+        self.transactions
+            .iter()
+            .enumerate()
+            .filter(|(_, tx)| is_subset(encoded, tx))
+            .map(|(position, _)| position)
+    }
+}
+
+/// Whether sorted `needle` is a subset of sorted `haystack`
+fn is_subset(needle: &[u32], haystack: &[u32]) -> bool {
+    if needle.len() > haystack.len() {
+        return false;
+    }
+    let mut rest = haystack.iter();
+    needle
+        .iter()
+        .all(|id| rest.by_ref().find(|other| **other >= *id) == Some(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_counts_match_contains_all() {
+        let basket = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        let transactions = vec![
+            Transaction::new("t1", basket(&["A", "B", "C"]), Utc::now()),
+            Transaction::new("t2", basket(&["C", "A", "A"]), Utc::now()),
+            Transaction::new("t3", basket(&["B", "D"]), Utc::now()),
+            Transaction::new("t4", basket(&[]), Utc::now()),
+        ];
+        let index = TransactionIndex::new(&transactions);
+
+        for itemset in [
+            basket(&["A"]),
+            basket(&["C", "A"]),
+            basket(&["A", "B", "C"]),
+            basket(&["B", "D"]),
+            basket(&["A", "D"]),
+            basket(&["A", "Z"]),
+            basket(&[]),
+        ] {
+            let expected = transactions
+                .iter()
+                .filter(|tx| tx.contains_all(&itemset))
+                .count();
+            assert_eq!(index.count(&itemset), expected, "{:?}", itemset);
+        }
+    }
+
+    #[test]
+    fn test_is_subset() {
+        assert!(is_subset(&[], &[1, 2]));
+        assert!(is_subset(&[1, 3], &[0, 1, 2, 3]));
+        assert!(!is_subset(&[1, 4], &[0, 1, 2, 3]));
+        assert!(!is_subset(&[2], &[0, 1, 3]));
+        assert!(!is_subset(&[0, 1], &[1]));
+    }
+}
@@ -0,0 +1,244 @@
+//! Combining rules mined on separate shards
+//!
+//! Mining each region's transactions on its own and merging the rules gives
+//! the same support, confidence and lift as mining everything at once, for
+//! rules every shard found. A rule's transaction counts are recovered per
+//! shard, from `PatternMetrics::counts` when mined with `MetricSet::COUNTS`
+//! and from support, confidence and lift otherwise, then summed.
+//!
+//! A rule missing from some shards fell below a threshold there, so its counts
+//! in those shards are unknown. Its confidence and lift cover the shards that
+//! found it, its support counts only their co-occurrences against every
+//! shard's transactions (a lower bound), and `MergedRule::partial` is set.
+
+use std::collections::BTreeMap;
+
+use crate::deployment::RuleKey;
+use crate::types::{AssociationRule, RuleCounts};
+
+use super::metrics_from_counts;
+
+/// A rule combined from one or more shards
+#[derive(Debug, Clone)]
+pub struct MergedRule {
+    /// The rule with metrics recomputed from the summed counts
+    pub rule: AssociationRule,
+    /// Indices of the shards that found the rule, ascending
+    pub shards: Vec<usize>,
+    /// Found in only some shards: support is a lower bound, and confidence
+    /// and lift describe those shards only
+    pub partial: bool,
+}
+
+/// Merge rules mined on separate shards, given with each shard's transaction
+/// count
+///
+/// Rules with the same antecedent and consequent (in any item order) are
+/// combined; the result is ranked by `AssociationRule::quality_score`.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::mining::merge_rule_sets;
+/// use rust_rule_miner::{AssociationRule, PatternMetrics};
+///
+/// let rule = |support, confidence, lift| AssociationRule {
+///     antecedent: vec!["Laptop".to_string()],
+///     consequent: vec!["Mouse".to_string()],
+///     metrics: PatternMetrics::basic(confidence, support, lift),
+/// };
+/// // 20 of 100 EU baskets and 60 of 300 US baskets hold both
+/// let merged = merge_rule_sets(vec![
+///     (vec![rule(0.2, 0.5, 1.25)], 100),
+///     (vec![rule(0.2, 0.75, 1.5)], 300),
+/// ]);
+///
+/// assert_eq!(merged[0].rule.metrics.support, 80.0 / 400.0);
+/// assert!(!merged[0].partial);
+/// ```
+pub fn merge_rule_sets(shards: Vec<(Vec<AssociationRule>, usize)>) -> Vec<MergedRule> {
+    let total: usize = shards.iter().map(|(_, count)| count).sum();
+    let shard_count = shards.len();
+
+    // Per rule: the first shard's rule and each shard's counts
+    let mut found: BTreeMap<RuleKey, (AssociationRule, Vec<(usize, RuleCounts)>)> = BTreeMap::new();
+    for (shard, (rules, transactions)) in shards.into_iter().enumerate() {
+        for rule in rules {
+            let counts = shard_counts(&rule, transactions);
+            let (_, per_shard) = found
+                .entry(RuleKey::of(&rule))
+                .or_insert_with(|| (rule, Vec::new()));
+            // A shard listing a rule twice counts once
+            if per_shard.last().is_none_or(|(last, _)| *last != shard) {
+                per_shard.push((shard, counts));
+            }
+        }
+    }
+
+    let mut merged: Vec<MergedRule> = found
+        .into_values()
+        .map(|(rule, per_shard)| {
+            let sum = |count: fn(&RuleCounts) -> usize| -> usize {
+                per_shard.iter().map(|(_, counts)| count(counts)).sum()
+            };
+            let joint = sum(|counts| counts.joint);
+            let mut metrics = metrics_from_counts(
+                sum(|counts| counts.antecedent),
+                sum(|counts| counts.consequent),
+                joint,
+                sum(|counts| counts.total) as f64,
+                rule.metrics.computed(),
+            );
+            metrics.support = if total > 0 {
+                joint as f64 / total as f64
+            } else {
+                0.0
+            };
+            metrics.avg_time_gap = rule.metrics.avg_time_gap;
+            metrics.time_variance = rule.metrics.time_variance;
+
+            MergedRule {
+                rule: AssociationRule { metrics, ..rule },
+                partial: per_shard.len() < shard_count,
+                shards: per_shard.into_iter().map(|(shard, _)| shard).collect(),
+            }
+        })
+        .collect();
+
+    merged.sort_by(|a, b| {
+        b.rule
+            .quality_score()
+            .partial_cmp(&a.rule.quality_score())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    merged
+}
+
+/// Transaction counts behind `rule` in a shard of `total` transactions
+///
+/// Without recorded counts: joint = support × total, antecedent = joint /
+/// confidence and consequent = total × confidence / lift.
+fn shard_counts(rule: &AssociationRule, total: usize) -> RuleCounts {
+    if let Some(counts) = rule.metrics.counts {
+        return counts;
+    }
+    let metrics = &rule.metrics;
+    let joint = metrics.support * total as f64;
+    let antecedent = if metrics.confidence > 0.0 {
+        joint / metrics.confidence
+    } else {
+        0.0
+    };
+    let consequent = if metrics.lift > 0.0 {
+        total as f64 * metrics.confidence / metrics.lift
+    } else {
+        0.0
+    };
+    RuleCounts {
+        antecedent: antecedent.round() as usize,
+        consequent: consequent.round() as usize,
+        joint: joint.round() as usize,
+        total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Confidence, Lift, MetricSet, MiningConfig, Support};
+    use crate::mining::RuleMiner;
+    use crate::transaction::Transaction;
+    use chrono::Utc;
+
+    fn shard(prefix: &str, baskets: &[(&[&str], usize)]) -> Vec<Transaction> {
+        baskets
+            .iter()
+            .flat_map(|(items, copies)| std::iter::repeat_n(*items, *copies))
+            .enumerate()
+            .map(|(i, items)| {
+                Transaction::new(
+                    format!("{}{}", prefix, i),
+                    items.iter().map(|s| s.to_string()).collect(),
+                    Utc::now(),
+                )
+            })
+            .collect()
+    }
+
+    fn mine(transactions: Vec<Transaction>, metrics: MetricSet) -> Vec<AssociationRule> {
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.2).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .min_lift(Lift::new(0.0).unwrap())
+            .compute_metrics(metrics)
+            .dedup_bidirectional(false)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        miner.mine().unwrap().rules
+    }
+
+    fn laptop_mouse(merged: &[MergedRule]) -> &MergedRule {
+        merged
+            .iter()
+            .find(|m| m.rule.antecedent == ["Laptop"] && m.rule.consequent == ["Mouse"])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rule_in_two_shards_matches_mining_everything() {
+        let eu = shard(
+            "eu",
+            &[(&["Laptop", "Mouse"], 4), (&["Laptop"], 2), (&["Tea"], 4)],
+        );
+        let us = shard(
+            "us",
+            &[(&["Laptop", "Mouse"], 9), (&["Mouse"], 3), (&["Coffee"], 8)],
+        );
+        let mut all = eu.clone();
+        all.extend(us.clone());
+
+        for metrics in [MetricSet::CORE, MetricSet::COUNTS] {
+            let merged = merge_rule_sets(vec![
+                (mine(eu.clone(), metrics), eu.len()),
+                (mine(us.clone(), metrics), us.len()),
+            ]);
+            let rule = laptop_mouse(&merged);
+            assert_eq!(rule.shards, [0, 1]);
+            assert!(!rule.partial);
+
+            // 13 of 30 baskets, as if mined together
+            let whole = mine(all.clone(), metrics);
+            let expected = &whole
+                .iter()
+                .find(|r| r.antecedent == ["Laptop"] && r.consequent == ["Mouse"])
+                .unwrap()
+                .metrics;
+            assert!((rule.rule.metrics.support - 13.0 / 30.0).abs() < 1e-12);
+            assert!((rule.rule.metrics.support - expected.support).abs() < 1e-12);
+            assert!((rule.rule.metrics.confidence - expected.confidence).abs() < 1e-12);
+            assert!((rule.rule.metrics.lift - expected.lift).abs() < 1e-12);
+            assert_eq!(rule.rule.metrics.counts, expected.counts);
+        }
+    }
+
+    #[test]
+    fn test_rule_in_one_shard_is_partial() {
+        let eu = shard("eu", &[(&["Tea", "Cake"], 4), (&["Laptop"], 6)]);
+        let us = shard("us", &[(&["Coffee"], 10)]);
+        let merged = merge_rule_sets(vec![
+            (mine(eu.clone(), MetricSet::CORE), eu.len()),
+            (mine(us, MetricSet::CORE), 10),
+        ]);
+
+        let tea = merged
+            .iter()
+            .find(|m| m.rule.antecedent == ["Tea"])
+            .unwrap();
+        assert!(tea.partial);
+        assert_eq!(tea.shards, [0]);
+        // Support over all 20 baskets; confidence from the EU shard
+        assert_eq!(tea.rule.metrics.support, 0.2);
+        assert_eq!(tea.rule.metrics.confidence, 1.0);
+    }
+}
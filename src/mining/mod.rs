@@ -1,17 +1,74 @@
 pub mod apriori;
+mod budget;
+pub mod closed;
+pub mod estimate;
+pub mod evidence;
 pub mod fpgrowth;
+pub mod incremental;
+mod index;
+pub mod merge;
+mod negative;
+pub mod progress;
+pub mod registry;
+pub mod result;
+pub mod significance;
 pub mod stats;
+pub mod two_pass;
+pub mod weighted;
 
-use crate::config::MiningConfig;
+pub use estimate::{estimate, sample_transactions, CountEstimate, Estimate, LevelEstimate};
+pub use evidence::RuleEvidence;
+pub use merge::{merge_rule_sets, MergedRule};
+pub use progress::{MiningProgress, ProgressPhase};
+pub use registry::{MinerRegistry, TenantMiner, TenantUsage};
+pub use result::{MiningOutcome, MiningResult, SupportTable};
+pub use stats::{
+    AlgorithmReport, DatasetProfile, ExclusionManifest, LongTailReport, MiningStats, PhaseTimings,
+};
+
+use crate::analysis::DatasetQuality;
+use crate::config::{MetricSet, MiningAlgorithm, MiningConfig, SupportUniverse};
 use crate::errors::{MiningError, Result};
+use crate::interner::Interner;
+use crate::normalize::Preprocessing;
+use crate::scoring::{QualityScorer, SharedScorer};
 use crate::transaction::Transaction;
-use crate::types::{AssociationRule, FrequentItemset, ItemSet, PatternMetrics};
+use crate::types::{
+    AssociationRule, FrequentItemset, ItemSet, NegativeRule, PatternMetrics, RuleCounts,
+};
+use budget::Budget;
+use chrono::{DateTime, Utc};
+use index::TransactionIndex;
+use progress::Progress;
+use stats::{Phase, PhaseTimer};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::ops::ControlFlow;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use weighted::WeightTable;
 
 /// Main rule mining engine
 pub struct RuleMiner {
     config: MiningConfig,
     transactions: Vec<Transaction>,
-    stats: stats::MiningStats,
+    item_weights: Option<HashMap<String, f64>>,
+    scorer: SharedScorer,
+    /// Baskets under `MiningConfig::support_universe`, without excluded
+    /// transactions, built on first use after transactions change
+    universe: OnceLock<Vec<Transaction>>,
+    /// Built on first use after transactions change
+    index: OnceLock<TransactionIndex>,
+    stats: MiningStats,
+    /// Running hash of the transactions added, part of the cache key
+    fingerprint: u64,
+    /// Last `mine()` outcome and the cache key it was mined under
+    cache: Mutex<Option<(u64, MiningOutcome)>>,
+    /// Stable item ids, kept up to date once `set_interner` is called
+    interner: Option<Interner>,
+    preprocessing: Preprocessing,
+    /// Keys of the loaded transactions, with `Preprocessing::dedup_transactions`
+    seen: HashSet<u64>,
 }
 
 impl RuleMiner {
@@ -20,8 +77,159 @@ impl RuleMiner {
         Self {
             config,
             transactions: Vec::new(),
-            stats: stats::MiningStats::default(),
+            item_weights: None,
+            scorer: SharedScorer::default(),
+            universe: OnceLock::new(),
+            index: OnceLock::new(),
+            stats: MiningStats::default(),
+            fingerprint: 0,
+            cache: Mutex::new(None),
+            interner: None,
+            preprocessing: Preprocessing::default(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Rank rules with `scorer` instead of `AssociationRule::quality_score`
+    ///
+    /// The ranking orders mined rules; support tables from
+    /// `mine_with_support_table` inherit it.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::scoring::LiftNormalization;
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, WeightedScorer};
+    ///
+    /// let mut miner = RuleMiner::new(MiningConfig::default());
+    /// miner.set_scorer(WeightedScorer::new(0.6, 0.3, 0.1).with_lift(LiftNormalization::Saturating));
+    /// ```
+    pub fn set_scorer(&mut self, scorer: impl QualityScorer + 'static) {
+        self.scorer = SharedScorer::new(scorer);
+        self.clear_cache();
+    }
+
+    /// Weight items (e.g. by price) for weighted support
+    ///
+    /// Rules then carry `metrics.weighted_support`, and
+    /// `MiningConfig::min_weighted_support` prunes itemsets by it. Items
+    /// missing from the map weigh `weighted::DEFAULT_ITEM_WEIGHT`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, Support, Transaction};
+    /// use chrono::Utc;
+    /// use std::collections::HashMap;
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.3)?)
+    ///     .min_weighted_support(Support::fraction(0.5)?)
+    ///     .build()?;
+    /// let mut miner = RuleMiner::new(config);
+    /// miner.set_item_weights(HashMap::from([
+    ///     ("Laptop".to_string(), 2000.0),
+    ///     ("Dock".to_string(), 300.0),
+    ///     ("Cable".to_string(), 2.0),
+    /// ]))?;
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("t1", vec!["Laptop".to_string(), "Dock".to_string()], Utc::now()),
+    ///     Transaction::new("t2", vec!["Laptop".to_string(), "Dock".to_string()], Utc::now()),
+    ///     Transaction::new("t3", vec!["Cable".to_string()], Utc::now()),
+    ///     Transaction::new("t4", vec!["Cable".to_string()], Utc::now()),
+    ///     Transaction::new("t5", vec!["Cable".to_string()], Utc::now()),
+    ///     Transaction::new("t6", vec!["Cable".to_string()], Utc::now()),
+    /// ])?;
+    ///
+    /// // Cable is in 4 of 6 baskets but weighs little; Laptop outweighs it
+    /// let itemsets = miner.mine_frequent_itemsets()?;
+    /// assert!(itemsets.iter().any(|i| i.items == ["Laptop"]));
+    /// assert!(itemsets.iter().all(|i| i.items != ["Cable"]));
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn set_item_weights(&mut self, weights: HashMap<String, f64>) -> Result<()> {
+        if let Some((item, weight)) = weights
+            .iter()
+            .find(|(_, weight)| !weight.is_finite() || **weight < 0.0)
+        {
+            return Err(MiningError::InvalidConfig(format!(
+                "weight of `{}` must be a non-negative number, got {}",
+                item, weight
+            )));
+        }
+        self.item_weights = Some(weights);
+        self.clear_cache();
+        Ok(())
+    }
+
+    /// Give items stable ids from `interner`, e.g. one loaded from the
+    /// previous run
+    ///
+    /// Items already loaded and every item added later keep the id the
+    /// interner has for them; unseen items get the next free id. Save
+    /// `interner()` after the run so the next one continues the numbering.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{Interner, MiningConfig, RuleMiner, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// // Yesterday's run numbered Laptop 0 and Mouse 1
+    /// let mut yesterday = Interner::new();
+    /// yesterday.intern("Laptop");
+    /// yesterday.intern("Mouse");
+    ///
+    /// let mut miner = RuleMiner::new(MiningConfig::default());
+    /// miner.set_interner(yesterday);
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("tx1", vec!["Keyboard".to_string(), "Mouse".to_string()], Utc::now()),
+    /// ])?;
+    ///
+    /// let interner = miner.interner().unwrap();
+    /// assert_eq!(interner.id("Mouse"), Some(1));
+    /// assert_eq!(interner.id("Keyboard"), Some(2));
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn set_interner(&mut self, mut interner: Interner) {
+        for transaction in &self.transactions {
+            for item in &transaction.items {
+                interner.intern(item);
+            }
         }
+        self.interner = Some(interner);
+    }
+
+    /// Stable item ids, if `set_interner` was called
+    pub fn interner(&self) -> Option<&Interner> {
+        self.interner.as_ref()
+    }
+
+    /// Clean transactions added from now on: normalize items and drop
+    /// duplicates
+    ///
+    /// Transactions already loaded are left as they are, but a later
+    /// duplicate of one is still dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::normalize::Preprocessing;
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let mut miner = RuleMiner::new(MiningConfig::default());
+    /// miner.set_preprocessing(Preprocessing::clean());
+    /// let row = Transaction::new("tx1", vec!["Laptop ".to_string()], Utc::now());
+    /// miner.add_transactions(vec![row.clone(), row])?;
+    ///
+    /// assert_eq!(miner.transaction_count(), 1);
+    /// assert_eq!(miner.transactions()[0].items, ["laptop"]);
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn set_preprocessing(&mut self, preprocessing: Preprocessing) {
+        self.preprocessing = preprocessing;
+        self.seen = if preprocessing.dedup_transactions {
+            self.transactions.iter().map(duplicate_key).collect()
+        } else {
+            HashSet::new()
+        };
     }
 
     /// Add transactions to mine
@@ -31,7 +239,10 @@ impl RuleMiner {
                 "No transactions provided".to_string(),
             ));
         }
-        self.transactions.extend(transactions);
+        for transaction in transactions {
+            self.push(transaction);
+        }
+        self.invalidate();
         Ok(())
     }
 
@@ -51,11 +262,12 @@ impl RuleMiner {
     /// let transaction = Transaction::new("tx1".to_string(), vec!["A".to_string()], Utc::now());
     /// miner.add_transaction(transaction)?;
     ///
-    /// let rules = miner.mine_association_rules()?;
+    /// let rules = miner.mine()?.rules;
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn add_transaction(&mut self, transaction: Transaction) -> Result<()> {
-        self.transactions.push(transaction);
+        self.push(transaction);
+        self.invalidate();
         Ok(())
     }
 
@@ -69,22 +281,21 @@ impl RuleMiner {
     ///
     /// let mut miner = RuleMiner::new(MiningConfig::default());
     ///
-    /// // Load from CSV and add to miner
+    /// // Stream rows from CSV into the miner; the first bad row aborts
     /// let mapping = ColumnMapping::simple(0, 1, 2);
-    /// let transactions = DataLoader::from_csv("file.csv", mapping)?;
-    /// miner.add_transactions_from_iter(transactions.into_iter().map(Ok))?;
+    /// miner.add_transactions_from_iter(DataLoader::stream_csv("file.csv", mapping)?)?;
     ///
-    /// let rules = miner.mine_association_rules()?;
+    /// let rules = miner.mine()?.rules;
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn add_transactions_from_iter<I>(&mut self, iter: I) -> Result<()>
     where
         I: Iterator<Item = Result<Transaction>>,
     {
+        self.invalidate();
         let mut count = 0;
         for transaction_result in iter {
-            let transaction = transaction_result?;
-            self.transactions.push(transaction);
+            self.push(transaction_result?);
             count += 1;
         }
 
@@ -97,210 +308,1246 @@ impl RuleMiner {
         Ok(())
     }
 
+    /// Append the transactions of `other`, e.g. a miner loaded from another
+    /// shard
+    ///
+    /// Only transactions move over; `other`'s config, scorer and item weights
+    /// are dropped.
+    pub fn merge(&mut self, other: RuleMiner) {
+        for transaction in other.transactions {
+            self.push(transaction);
+        }
+        self.invalidate();
+    }
+
     /// Get transaction count
     pub fn transaction_count(&self) -> usize {
         self.transactions.len()
     }
 
-    /// Mine association rules using configured algorithm
-    pub fn mine_association_rules(&mut self) -> Result<Vec<AssociationRule>> {
-        if self.transactions.is_empty() {
-            return Err(MiningError::InsufficientData(
-                "No transactions to mine".to_string(),
-            ));
-        }
+    /// Transactions loaded so far, in the order they were added
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
 
-        // Step 1: Find frequent itemsets
-        let frequent_itemsets = match self.config.algorithm {
-            crate::config::MiningAlgorithm::Apriori => {
-                apriori::find_frequent_itemsets(&self.transactions, self.config.min_support)?
+    /// Remove every transaction, keeping the config, scorer and item weights
+    ///
+    /// Resets `stats()`, which described the removed data.
+    pub fn clear_transactions(&mut self) {
+        self.retain_transactions(|_| false);
+    }
+
+    /// Remove transactions timestamped before `timestamp`, returning how many
+    /// were removed
+    ///
+    /// Retraining on a sliding window is this followed by `add_transactions`
+    /// with the newest data. Resets `stats()` when anything is removed.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, Transaction};
+    /// use chrono::{Duration, Utc};
+    ///
+    /// let now = Utc::now();
+    /// let mut miner = RuleMiner::new(MiningConfig::default());
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("old", vec!["A".to_string()], now - Duration::days(40)),
+    ///     Transaction::new("new", vec!["B".to_string()], now),
+    /// ])?;
+    ///
+    /// assert_eq!(miner.remove_transactions_before(now - Duration::days(30)), 1);
+    /// assert_eq!(miner.transactions()[0].id, "new");
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn remove_transactions_before(&mut self, timestamp: DateTime<Utc>) -> usize {
+        self.retain_transactions(|transaction| transaction.timestamp >= timestamp)
+    }
+
+    /// Keep only the transactions `keep` accepts, returning how many were
+    /// removed
+    ///
+    /// Resets `stats()` when anything is removed.
+    pub fn retain_transactions<F>(&mut self, mut keep: F) -> usize
+    where
+        F: FnMut(&Transaction) -> bool,
+    {
+        let before = self.transactions.len();
+        self.transactions.retain(|transaction| keep(transaction));
+        let removed = before - self.transactions.len();
+        if removed > 0 {
+            self.fingerprint = self.transactions.iter().fold(0, fingerprint);
+            if self.preprocessing.dedup_transactions {
+                self.seen = self.transactions.iter().map(duplicate_key).collect();
             }
-            crate::config::MiningAlgorithm::FPGrowth => {
-                fpgrowth::find_frequent_itemsets(&self.transactions, self.config.min_support)?
+            self.invalidate();
+            self.stats = MiningStats::default();
+        }
+        removed
+    }
+
+    /// Assess whether the loaded transactions are worth mining
+    ///
+    /// With `MiningConfig::min_quality_score` set, mining runs this and logs
+    /// the findings when the score falls short.
+    pub fn dataset_quality(&self) -> DatasetQuality {
+        DatasetQuality::assess(&self.transactions)
+    }
+
+    fn check_quality(&self) {
+        let Some(min_score) = self.config.min_quality_score else {
+            return;
+        };
+        let quality = self.dataset_quality();
+        if quality.score < min_score {
+            log::warn!(
+                "Dataset quality score {:.2} is below {:.2}",
+                quality.score,
+                min_score
+            );
+            for finding in &quality.findings {
+                log::warn!(
+                    "{:?}: {}; {}",
+                    finding.severity,
+                    finding.message,
+                    finding.remediation
+                );
             }
-            _ => {
-                return Err(MiningError::MiningFailed(
-                    "Algorithm not yet implemented".to_string(),
-                ))
+        }
+    }
+
+    /// Mine association rules using the configured algorithm
+    ///
+    /// Takes `&self`, so one miner behind an `Arc` can answer concurrent
+    /// queries. The outcome is cached under a hash of the config and the
+    /// transactions: until either changes, calls return it without mining
+    /// again, with `stats.cached` set. A scorer or item weights set since
+    /// also clear the cache.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, Support, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.5)?)
+    ///     .build()?;
+    /// let mut miner = RuleMiner::new(config);
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("tx1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("tx2", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("tx3", vec!["A".to_string()], Utc::now()),
+    /// ])?;
+    ///
+    /// let miner = Arc::new(miner);
+    /// let queries: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let miner = Arc::clone(&miner);
+    ///         thread::spawn(move || miner.mine().map(|outcome| outcome.rules.len()))
+    ///     })
+    ///     .collect();
+    /// for query in queries {
+    ///     assert_eq!(query.join().unwrap()?, 1);
+    /// }
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn mine(&self) -> Result<MiningOutcome> {
+        let key = self.cache_key();
+        if let Some((cached_key, outcome)) = &*self.lock_cache() {
+            if *cached_key == key {
+                let mut outcome = outcome.clone();
+                outcome.stats.cached = true;
+                return Ok(outcome);
             }
-        };
+        }
 
-        self.stats.frequent_itemsets_count = frequent_itemsets.len();
+        let mut stats = MiningStats::default();
+        let (_, rules) =
+            self.mine_itemsets_and_rules(&mut Progress::none(), &mut |_| {}, &mut stats)?;
+        let outcome = MiningOutcome { rules, stats };
+        *self.lock_cache() = Some((key, outcome.clone()));
+        Ok(outcome)
+    }
 
-        // Step 2: Generate association rules
-        let mut rules = self.generate_association_rules(&frequent_itemsets)?;
+    /// Mine association rules, keeping the stats for `stats()`
+    #[deprecated(note = "use mine(), which takes &self and returns the stats with the rules")]
+    pub fn mine_association_rules(&mut self) -> Result<Vec<AssociationRule>> {
+        let outcome = self.mine()?;
+        self.stats = outcome.stats;
+        Ok(outcome.rules)
+    }
+
+    /// Mine association rules, reporting progress to `callback`
+    ///
+    /// The callback runs at checkpoints in itemset mining and rule
+    /// generation (see `MiningProgress`); returning `ControlFlow::Break`
+    /// stops the run with `MiningError::MiningFailed("cancelled")`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::ops::ControlFlow;
+    /// use std::time::{Duration, Instant};
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let mut miner = RuleMiner::new(MiningConfig::default());
+    /// let transactions = (0..100)
+    ///     .map(|i| Transaction::new(format!("tx{}", i), vec!["A".to_string(), "B".to_string()], Utc::now()))
+    ///     .collect();
+    /// miner.add_transactions(transactions)?;
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(60);
+    /// let rules = miner.mine_association_rules_with_progress(|progress| {
+    ///     eprintln!("{:?}: {:.0}%", progress.phase, progress.fraction() * 100.0);
+    ///     if Instant::now() > deadline {
+    ///         ControlFlow::Break(())
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// })?;
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn mine_association_rules_with_progress<F>(
+        &mut self,
+        mut callback: F,
+    ) -> Result<Vec<AssociationRule>>
+    where
+        F: FnMut(MiningProgress) -> ControlFlow<()>,
+    {
+        let mut progress = Progress::new(&mut callback);
+        let (_, rules) = self.recording(|miner, stats| {
+            miner.mine_itemsets_and_rules(&mut progress, &mut |_| {}, stats)
+        })?;
+        Ok(rules)
+    }
+
+    /// Mine association rules, passing each to `on_rule` as soon as it
+    /// passes the thresholds
+    ///
+    /// Streamed rules arrive unordered, before the mine finishes, so a
+    /// consumer can start writing them right away. The returned outcome is
+    /// the same sorted set `mine()` returns. P-value correction,
+    /// bidirectional dedup and `prune_redundant` need every rule and run
+    /// post-hoc on that set only, so `on_rule` may see rules it lacks.
+    /// Streaming always mines; it neither reads nor fills the `mine()` cache.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, Support, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.5)?)
+    ///     .build()?;
+    /// let mut miner = RuleMiner::new(config);
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("tx1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("tx2", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("tx3", vec!["A".to_string()], Utc::now()),
+    /// ])?;
+    ///
+    /// // One JSON line per rule, written while mining
+    /// let mut ndjson = String::new();
+    /// let outcome = miner.mine_association_rules_streaming(|rule| {
+    ///     ndjson.push_str(&serde_json::to_string(&rule).unwrap());
+    ///     ndjson.push('\n');
+    /// })?;
+    /// assert!(ndjson.lines().count() >= outcome.rules.len());
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn mine_association_rules_streaming<F>(&self, mut on_rule: F) -> Result<MiningOutcome>
+    where
+        F: FnMut(AssociationRule),
+    {
+        let mut stats = MiningStats::default();
+        let (_, rules) = self.mine_itemsets_and_rules(
+            &mut Progress::none(),
+            &mut |rule| on_rule(rule.clone()),
+            &mut stats,
+        )?;
+        Ok(MiningOutcome { rules, stats })
+    }
+
+    /// Mine association rules with the IDs of up to
+    /// `MiningConfig::collect_evidence` transactions supporting each
+    ///
+    /// A rule's evidence are transactions holding both its antecedent and
+    /// consequent, in the order they were added (baskets of the support
+    /// universe when one is configured). Uses the `mine()` cache. Fails with
+    /// `InvalidConfig` when `collect_evidence` is not set.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, Support, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.5)?)
+    ///     .collect_evidence(5)
+    ///     .build()?;
+    /// let mut miner = RuleMiner::new(config);
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("tx1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("tx2", vec!["A".to_string()], Utc::now()),
+    ///     Transaction::new("tx3", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    /// ])?;
+    ///
+    /// let (outcome, evidence) = miner.mine_association_rules_with_evidence()?;
+    /// let ids = evidence.get(&outcome.rules[0]).unwrap();
+    /// assert_eq!(ids, ["tx1", "tx3"]);
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn mine_association_rules_with_evidence(&self) -> Result<(MiningOutcome, RuleEvidence)> {
+        let max_ids = self.config.collect_evidence.ok_or_else(|| {
+            MiningError::InvalidConfig(
+                "collect_evidence must be set to mine rules with evidence".to_string(),
+            )
+        })?;
+        let outcome = self.mine()?;
+        let evidence = RuleEvidence::collect(&outcome.rules, self.baskets(), self.index(), max_ids);
+        Ok((outcome, evidence))
+    }
 
-        // Step 3: Filter bidirectional rules to prevent infinite loops
-        rules = self.filter_bidirectional_rules(rules);
+    /// Mine negative rules (A => NOT B)
+    ///
+    /// Antecedents are frequent itemsets and absent items frequent single
+    /// items; a rule is kept when P(¬B | A) reaches `min_confidence` and its
+    /// lift against ¬B reaches `min_lift`.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{Confidence, Lift, MiningConfig, RuleMiner, Support, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.3)?)
+    ///     .min_confidence(Confidence::fraction(0.9)?)
+    ///     .min_lift(Lift::new(1.2)?)
+    ///     .build()?;
+    /// let mut miner = RuleMiner::new(config);
+    /// let basket = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("t1", basket(&["Console", "Game"]), Utc::now()),
+    ///     Transaction::new("t2", basket(&["Console", "Game"]), Utc::now()),
+    ///     Transaction::new("t3", basket(&["Chair", "Desk"]), Utc::now()),
+    ///     Transaction::new("t4", basket(&["Chair", "Desk"]), Utc::now()),
+    /// ])?;
+    ///
+    /// let rules = miner.mine_negative_rules()?;
+    /// assert!(rules
+    ///     .iter()
+    ///     .any(|r| r.antecedent == ["Console"] && r.absent == "Chair"));
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn mine_negative_rules(&mut self) -> Result<Vec<NegativeRule>> {
+        self.recording(|miner, stats| {
+            let frequent_itemsets = miner.find_frequent_itemsets(
+                &mut Progress::none(),
+                &mut Budget::new(&miner.config),
+                stats,
+            )?;
+            miner.update_long_tail(stats);
+            Ok(negative::generate_negative_rules(
+                miner.baskets(),
+                &frequent_itemsets,
+                &miner.config,
+            ))
+        })
+    }
 
-        self.stats.rules_generated = rules.len();
+    /// Mine association rules and retain the frequent-itemset support table
+    ///
+    /// The table lets `MiningResult::rules_at()` re-filter rules at different
+    /// thresholds without re-mining. It is only kept when the number of
+    /// frequent itemsets is at most `max_itemsets`, so memory stays bounded.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{Confidence, MiningConfig, RuleMiner, Support, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.5)?)
+    ///     .min_confidence(Confidence::fraction(0.5)?)
+    ///     .build()?;
+    /// let mut miner = RuleMiner::new(config);
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("tx1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("tx2", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("tx3", vec!["A".to_string()], Utc::now()),
+    /// ])?;
+    ///
+    /// let result = miner.mine_with_support_table(10_000)?;
+    /// let strict = result.rules_at(0.9, 1.0, 0.5)?;
+    /// assert!(strict.len() <= result.rules.len());
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn mine_with_support_table(&mut self, max_itemsets: usize) -> Result<MiningResult> {
+        let (frequent_itemsets, rules) = self.recording(|miner, stats| {
+            miner.mine_itemsets_and_rules(&mut Progress::none(), &mut |_| {}, stats)
+        })?;
 
-        Ok(rules)
+        let support_table = if self.stats.truncated {
+            // A cut-short run may lack subsets of its itemsets
+            log::warn!("Support table not retained: mining run was truncated");
+            None
+        } else if !self.config.constraints.required_items.is_empty() {
+            // Itemsets without a required item are never counted, so the
+            // table could not supply antecedent/consequent counts
+            log::warn!("Support table not retained: required_items constraint is set");
+            None
+        } else if frequent_itemsets.len() <= max_itemsets {
+            Some(
+                SupportTable::new(frequent_itemsets, self.baskets().len(), &self.config)
+                    .with_scorer(self.scorer.clone()),
+            )
+        } else {
+            log::warn!(
+                "Support table not retained: {} frequent itemsets exceed cap of {}",
+                frequent_itemsets.len(),
+                max_itemsets
+            );
+            None
+        };
+
+        Ok(MiningResult {
+            rules,
+            support_table,
+        })
     }
 
-    /// Filter out bidirectional rules that could cause infinite loops
-    /// For rules like A=>B and B=>A, keep only the one with higher confidence
-    fn filter_bidirectional_rules(&self, rules: Vec<AssociationRule>) -> Vec<AssociationRule> {
-        let mut filtered = Vec::new();
-        let mut seen_pairs = std::collections::HashSet::new();
+    /// Mine frequent itemsets using the configured algorithm
+    ///
+    /// Results are sorted by support (descending), ties broken by items.
+    /// Applies `MiningConfig::itemset_kind`, so with `Closed` or `Maximal`
+    /// this returns the reduced lattice that rules are generated from.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, Support, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.5)?)
+    ///     .build()?;
+    /// let mut miner = RuleMiner::new(config);
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("tx1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("tx2", vec!["A".to_string()], Utc::now()),
+    ///     Transaction::new("tx3", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("tx4", vec!["A".to_string()], Utc::now()),
+    /// ])?;
+    ///
+    /// let itemsets = miner.mine_frequent_itemsets()?;
+    /// assert_eq!(itemsets[0].items, vec!["A".to_string()]);
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn mine_frequent_itemsets(&mut self) -> Result<Vec<FrequentItemset>> {
+        self.recording(|miner, stats| {
+            let frequent_itemsets = miner.find_frequent_itemsets(
+                &mut Progress::none(),
+                &mut Budget::new(&miner.config),
+                stats,
+            )?;
+            miner.update_long_tail(stats);
+            let mut frequent_itemsets =
+                closed::filter_itemsets(frequent_itemsets, miner.config.itemset_kind);
 
-        // Already sorted by quality score from generate_association_rules
+            frequent_itemsets.sort_by(|a, b| {
+                b.support
+                    .partial_cmp(&a.support)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.items.cmp(&b.items))
+            });
 
-        for rule in rules {
-            // Create canonical pair representation (sorted to be order-independent)
-            let mut pair = vec![rule.antecedent.clone(), rule.consequent.clone()];
-            pair.sort();
-            let pair_key = format!("{:?}", pair);
-
-            if !seen_pairs.contains(&pair_key) {
-                seen_pairs.insert(pair_key);
-                filtered.push(rule);
-            }
-        }
+            stats.frequent_itemsets_count = frequent_itemsets.len();
 
-        filtered
+            Ok(frequent_itemsets)
+        })
     }
 
-    /// Generate association rules from frequent itemsets
-    fn generate_association_rules(
-        &self,
-        frequent_itemsets: &[FrequentItemset],
-    ) -> Result<Vec<AssociationRule>> {
-        let mut rules = Vec::new();
+    /// Run `mine` with fresh stats and keep them for `stats()`, even when
+    /// it fails
+    fn recording<T>(
+        &mut self,
+        mine: impl FnOnce(&Self, &mut MiningStats) -> Result<T>,
+    ) -> Result<T> {
+        let mut stats = MiningStats::default();
+        let result = mine(self, &mut stats);
+        self.stats = stats;
+        result
+    }
 
-        for itemset in frequent_itemsets {
-            if itemset.items.len() < 2 {
-                continue; // Need at least 2 items for a rule
-            }
+    /// Dispatch to the configured algorithm (all frequent itemsets, unfiltered)
+    ///
+    /// With `fallback_to_apriori`, a `MiningFailed` error from another
+    /// algorithm is retried with Apriori; `stats().algorithm` records both.
+    fn find_frequent_itemsets(
+        &self,
+        progress: &mut Progress<'_>,
+        budget: &mut Budget,
+        stats: &mut MiningStats,
+    ) -> Result<Vec<FrequentItemset>> {
+        self.config.validate()?;
+        if self.transactions.is_empty() {
+            return Err(MiningError::InsufficientData(
+                "No transactions to mine".to_string(),
+            ));
+        }
+        self.check_quality();
 
-            // Generate all possible splits: A → B where A ∪ B = itemset
-            for antecedent in self.generate_non_empty_subsets(&itemset.items) {
-                let consequent: ItemSet = itemset
-                    .items
+        // Stats describe this run only
+        let excluded = if self.config.has_exclusions() {
+            self.transactions
+                .iter()
+                .filter(|tx| self.config.excludes(tx))
+                .count()
+        } else {
+            0
+        };
+        *stats = MiningStats {
+            transactions_processed: self.transactions.len() - excluded,
+            min_support_count: self.config.min_support_count(self.baskets().len()),
+            excluded_transactions: excluded,
+            exclusions: self.config.has_exclusions().then(|| ExclusionManifest {
+                transaction_ids: self
+                    .config
+                    .excluded_transaction_ids
                     .iter()
-                    .filter(|item| !antecedent.contains(item))
                     .cloned()
-                    .collect();
+                    .collect(),
+                time_ranges: self.config.excluded_time_ranges.clone(),
+            }),
+            ..Default::default()
+        };
+        self.config.check_support_count(self.baskets().len())?;
 
-                if consequent.is_empty() {
-                    continue;
+        let timer = PhaseTimer::start(Phase::Itemsets);
+        let mut phases = PhaseTimings::default();
+        let mut candidates = Vec::new();
+        let attempted = self.config.algorithm;
+        let mut report = stats::AlgorithmReport {
+            attempted,
+            used: attempted,
+            fallback_reason: None,
+        };
+        let mut itemsets =
+            match self.run_algorithm(attempted, &mut phases, &mut candidates, progress, budget) {
+                Err(MiningError::MiningFailed(reason))
+                    if self.config.fallback_to_apriori && attempted != MiningAlgorithm::Apriori =>
+                {
+                    log::warn!(
+                        "{:?} failed ({}); falling back to Apriori",
+                        attempted,
+                        reason
+                    );
+                    report.used = MiningAlgorithm::Apriori;
+                    report.fallback_reason = Some(reason);
+                    phases = PhaseTimings::default();
+                    candidates.clear();
+                    self.run_algorithm(
+                        MiningAlgorithm::Apriori,
+                        &mut phases,
+                        &mut candidates,
+                        progress,
+                        budget,
+                    )
                 }
+                result => result,
+            }?;
+        stats.algorithm = Some(report);
 
-                // Calculate metrics
-                let metrics = self.calculate_metrics(&antecedent, &consequent, itemset.support);
+        if let Some(min_weighted_support) = self.config.min_weighted_support {
+            self.weight_table()
+                .retain(&mut itemsets, min_weighted_support);
+        }
+        phases.itemsets = timer.stop();
+        stats.phases = phases;
+        stats.apriori_candidates = candidates;
+        stats.truncated = budget.truncated();
+        Ok(itemsets)
+    }
 
-                // Filter by thresholds
-                if metrics.confidence >= self.config.min_confidence
-                    && metrics.lift >= self.config.min_lift
-                {
-                    rules.push(AssociationRule {
-                        antecedent: antecedent.clone(),
-                        consequent: consequent.clone(),
-                        metrics,
-                    });
-                }
-            }
+    fn run_algorithm(
+        &self,
+        algorithm: MiningAlgorithm,
+        phases: &mut PhaseTimings,
+        candidates: &mut Vec<usize>,
+        progress: &mut Progress<'_>,
+        budget: &mut Budget,
+    ) -> Result<Vec<FrequentItemset>> {
+        match algorithm {
+            MiningAlgorithm::Apriori => apriori::find_frequent_itemsets_timed(
+                self.baskets(),
+                &self.config,
+                phases,
+                candidates,
+                progress,
+                budget,
+            ),
+            MiningAlgorithm::FPGrowth => fpgrowth::find_frequent_itemsets_timed(
+                self.baskets(),
+                &self.config,
+                phases,
+                progress,
+                budget,
+            ),
+            _ => Err(MiningError::MiningFailed(format!(
+                "{:?} is not implemented yet",
+                algorithm
+            ))),
         }
+    }
 
-        // Sort by quality score
-        rules.sort_by(|a, b| {
-            b.quality_score()
-                .partial_cmp(&a.quality_score())
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+    /// Transactions as baskets of the configured support universe, minus
+    /// the configured exclusions
+    fn baskets(&self) -> &[Transaction] {
+        let universe = self.config.support_universe;
+        if universe == SupportUniverse::Transactions && !self.config.has_exclusions() {
+            return &self.transactions;
+        }
+        self.universe.get_or_init(|| {
+            if !self.config.has_exclusions() {
+                return universe.apply(&self.transactions).into_owned();
+            }
+            let included: Vec<Transaction> = self
+                .transactions
+                .iter()
+                .filter(|tx| !self.config.excludes(tx))
+                .cloned()
+                .collect();
+            universe.apply(&included).into_owned()
+        })
+    }
 
-        Ok(rules)
+    fn invalidate(&mut self) {
+        self.universe = OnceLock::new();
+        self.index = OnceLock::new();
     }
 
-    /// Generate all non-empty subsets of an itemset
-    fn generate_non_empty_subsets(&self, items: &[String]) -> Vec<ItemSet> {
-        let mut subsets = Vec::new();
-        let n = items.len();
+    /// Preprocess and load `transaction`, unless it is a duplicate
+    fn push(&mut self, mut transaction: Transaction) {
+        self.preprocessing.prepare(&mut transaction);
+        if self.preprocessing.dedup_transactions && !self.seen.insert(duplicate_key(&transaction)) {
+            return;
+        }
+        self.record(&transaction);
+        self.transactions.push(transaction);
+    }
 
-        // Generate all possible combinations (2^n - 1, excluding empty set and full set)
-        for i in 1..(1 << n) - 1 {
-            let mut subset = Vec::new();
-            for (j, item) in items.iter().enumerate() {
-                if (i & (1 << j)) != 0 {
-                    subset.push(item.clone());
-                }
+    /// Fold `transaction` into the fingerprint of the loaded transactions
+    /// and give its items ids
+    fn record(&mut self, transaction: &Transaction) {
+        self.fingerprint = fingerprint(self.fingerprint, transaction);
+        if let Some(interner) = &mut self.interner {
+            for item in &transaction.items {
+                interner.intern(item);
             }
-            subsets.push(subset);
         }
-
-        subsets
     }
 
-    /// Calculate metrics for a rule
-    fn calculate_metrics(
-        &self,
-        antecedent: &ItemSet,
-        consequent: &ItemSet,
-        both_support: f64,
-    ) -> PatternMetrics {
-        let total = self.transactions.len() as f64;
-
-        // Count occurrences
-        let antecedent_count = self
-            .transactions
-            .iter()
-            .filter(|tx| tx.contains_all(antecedent))
-            .count() as f64;
-
-        let consequent_count = self
-            .transactions
-            .iter()
-            .filter(|tx| tx.contains_all(consequent))
-            .count() as f64;
+    /// Key of the `mine()` cache: the config and the transactions
+    fn cache_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        format!("{:?}", self.config).hash(&mut hasher);
+        self.transactions.len().hash(&mut hasher);
+        self.fingerprint.hash(&mut hasher);
+        hasher.finish()
+    }
 
-        let both_count = self
-            .transactions
-            .iter()
-            .filter(|tx| tx.contains_all(antecedent) && tx.contains_all(consequent))
-            .count() as f64;
+    fn lock_cache(&self) -> MutexGuard<'_, Option<(u64, MiningOutcome)>> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
 
-        // Calculate metrics
-        let confidence = if antecedent_count > 0.0 {
-            both_count / antecedent_count
-        } else {
-            0.0
-        };
+    /// Drop the cached outcome after a change the cache key does not cover
+    fn clear_cache(&mut self) {
+        *self.cache.get_mut().unwrap_or_else(|e| e.into_inner()) = None;
+    }
 
-        let support = both_support;
+    /// Integer-encoded transactions for support counting
+    fn index(&self) -> &TransactionIndex {
+        self.index
+            .get_or_init(|| TransactionIndex::new(self.baskets()))
+    }
 
-        let p_consequent = consequent_count / total;
-        let lift = if p_consequent > 0.0 {
-            confidence / p_consequent
-        } else {
-            0.0
-        };
+    fn weight_table(&self) -> WeightTable<'_> {
+        WeightTable::new(self.item_weights.as_ref(), self.baskets())
+    }
 
-        let conviction = if confidence < 1.0 && p_consequent < 1.0 {
-            (1.0 - p_consequent) / (1.0 - confidence)
-        } else {
-            f64::INFINITY
-        };
+    /// Report on single items excluded by min_support
+    ///
+    /// Lists the `top_n` near-miss items. After a mining run the same report
+    /// (with `stats::DEFAULT_NEAR_MISSES` items) is available in `stats()`.
+    pub fn long_tail_report(&self, top_n: usize) -> stats::LongTailReport {
+        stats::LongTailReport::from_transactions(self.baskets(), self.config.min_support(), top_n)
+    }
 
-        PatternMetrics {
-            confidence,
-            support,
-            lift,
-            conviction,
-            avg_time_gap: None,
-            time_variance: None,
-        }
+    /// Each item with its transaction count and support, most frequent first
+    ///
+    /// Counts follow the configured support universe, so supports match
+    /// the ones mining uses.
+    pub fn item_frequencies(&self) -> Vec<(String, usize, f64)> {
+        stats::item_frequencies(self.baskets())
+    }
+
+    /// Size, item and time profile of the loaded transactions, with the
+    /// `top_n` most frequent items
+    ///
+    /// Useful for choosing `min_support`: an item needs support at least
+    /// `min_support` to appear in any rule.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{MiningConfig, RuleMiner, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let mut miner = RuleMiner::new(MiningConfig::default());
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("tx1", vec!["Laptop".to_string(), "Mouse".to_string()], Utc::now()),
+    ///     Transaction::new("tx2", vec!["Mouse".to_string()], Utc::now()),
+    /// ])?;
+    ///
+    /// let profile = miner.dataset_profile(5);
+    /// assert_eq!(profile.unique_item_count, 2);
+    /// assert_eq!(profile.avg_items, 1.5);
+    /// assert_eq!(profile.top_items[0], ("Mouse".to_string(), 2, 1.0));
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn dataset_profile(&self, top_n: usize) -> DatasetProfile {
+        DatasetProfile::from_transactions(self.baskets(), top_n)
+    }
+
+    fn update_long_tail(&self, stats: &mut MiningStats) {
+        stats.long_tail = Some(self.long_tail_report(stats::DEFAULT_NEAR_MISSES));
+    }
+
+    /// Run the configured algorithm and generate filtered, sorted rules
+    ///
+    /// Returns all frequent itemsets (before closed/maximal filtering) so
+    /// callers can cache complete support counts. `on_rule` sees each rule
+    /// as it passes the thresholds, before sorting and post-hoc filters.
+    fn mine_itemsets_and_rules(
+        &self,
+        progress: &mut Progress<'_>,
+        on_rule: &mut dyn FnMut(&AssociationRule),
+        stats: &mut MiningStats,
+    ) -> Result<(Vec<FrequentItemset>, Vec<AssociationRule>)> {
+        // Step 1: Find frequent itemsets
+        let mut budget = Budget::new(&self.config);
+        let frequent_itemsets = self.find_frequent_itemsets(progress, &mut budget, stats)?;
+        self.update_long_tail(stats);
+        let rule_itemsets =
+            closed::filter_itemsets(frequent_itemsets.clone(), self.config.itemset_kind);
+
+        stats.frequent_itemsets_count = rule_itemsets.len();
+
+        // Step 2: Generate association rules
+        let timer = PhaseTimer::start(Phase::RuleGeneration);
+        let mut rules =
+            self.generate_association_rules(&rule_itemsets, progress, on_rule, &mut budget, stats)?;
+        stats.phases.rule_generation = timer.stop();
+        stats.truncated = budget.truncated();
+
+        // Step 3: Sort by quality score
+        let timer = PhaseTimer::start(Phase::Sorting);
+        self.scorer.sort(&mut rules);
+        stats.phases.sorting = timer.stop();
+
+        // Step 4: Filter bidirectional rules to prevent infinite loops
+        let timer = PhaseTimer::start(Phase::Filtering);
+        rules = filter_bidirectional_rules(rules, &self.config);
+        let generated = rules.len();
+        if self.config.prune_redundant {
+            rules = Self::prune_redundant_rules(rules);
+        }
+        stats.redundant_rules_pruned = generated - rules.len();
+        stats.phases.filtering = timer.stop();
+
+        stats.rules_generated = rules.len();
+
+        Ok((frequent_itemsets, rules))
+    }
+
+    /// Drop rules implied by a more general rule
+    ///
+    /// A rule is redundant when another rule with the same consequent and a
+    /// strict subset of its antecedent has at least its confidence: the
+    /// general rule fires on every basket the specific one does and is at
+    /// least as sure. Survivors keep their order. Mining applies this when
+    /// `MiningConfig::prune_redundant` is set.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{AssociationRule, PatternMetrics, RuleMiner};
+    ///
+    /// let rule = |antecedent: &[&str], confidence| AssociationRule {
+    ///     antecedent: antecedent.iter().map(|s| s.to_string()).collect(),
+    ///     consequent: vec!["Hub".to_string()],
+    ///     metrics: PatternMetrics::basic(confidence, 0.2, 1.5),
+    /// };
+    /// let rules = vec![rule(&["Laptop", "Mouse"], 0.8), rule(&["Laptop"], 0.8)];
+    ///
+    /// let kept = RuleMiner::prune_redundant_rules(rules);
+    /// assert_eq!(kept.len(), 1);
+    /// assert_eq!(kept[0].antecedent, ["Laptop"]);
+    /// ```
+    pub fn prune_redundant_rules(rules: Vec<AssociationRule>) -> Vec<AssociationRule> {
+        let mut by_consequent: HashMap<ItemSet, Vec<usize>> = HashMap::new();
+        for (i, rule) in rules.iter().enumerate() {
+            let mut consequent = rule.consequent.clone();
+            consequent.sort();
+            by_consequent.entry(consequent).or_default().push(i);
+        }
+
+        let redundant: std::collections::HashSet<usize> = by_consequent
+            .values()
+            .flat_map(|family| {
+                family.iter().copied().filter(|&i| {
+                    let rule = &rules[i];
+                    family.iter().any(|&j| {
+                        let general = &rules[j];
+                        general.antecedent.len() < rule.antecedent.len()
+                            && general.metrics.confidence >= rule.metrics.confidence
+                            && general
+                                .antecedent
+                                .iter()
+                                .all(|item| rule.antecedent.contains(item))
+                    })
+                })
+            })
+            .collect();
+
+        rules
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !redundant.contains(i))
+            .map(|(_, rule)| rule)
+            .collect()
+    }
+
+    /// Generate association rules from frequent itemsets (unsorted)
+    fn generate_association_rules(
+        &self,
+        frequent_itemsets: &[FrequentItemset],
+        progress: &mut Progress<'_>,
+        on_rule: &mut dyn FnMut(&AssociationRule),
+        budget: &mut Budget,
+        stats: &mut MiningStats,
+    ) -> Result<Vec<AssociationRule>> {
+        let mut rules = Vec::new();
+        let mut tested = 0;
+        let total = frequent_itemsets.len();
+        let mut base_rate = match self.config.max_consequent_base_rate {
+            Some(_) => BaseRateFilter::new(
+                &self.config,
+                stats::item_counts(self.baskets()),
+                self.baskets().len(),
+            ),
+            None => BaseRateFilter::default(),
+        };
+
+        for (done, itemset) in frequent_itemsets.iter().enumerate() {
+            progress.checkpoint(ProgressPhase::RuleGeneration, done, total)?;
+            if budget.spent() {
+                break; // Keep the rules found so far
+            }
+            if itemset.items.len() < 2 {
+                continue; // Need at least 2 items for a rule
+            }
+
+            // Generate all possible splits: A → B where A ∪ B = itemset
+            for antecedent in generate_non_empty_subsets(&itemset.items) {
+                let consequent: ItemSet = itemset
+                    .items
+                    .iter()
+                    .filter(|item| !antecedent.contains(item))
+                    .cloned()
+                    .collect();
+
+                if consequent.is_empty() {
+                    continue;
+                }
+
+                // Skip splits that violate the size caps or item constraints
+                if !self.config.allows_split(antecedent.len(), consequent.len())
+                    || !self
+                        .config
+                        .constraints
+                        .allows_rule(&antecedent, &consequent)
+                {
+                    continue;
+                }
+
+                // Calculate metrics
+                tested += 1;
+                let metrics =
+                    self.calculate_metrics(&antecedent, &consequent, self.config.metric_set());
+
+                // Filter by thresholds
+                if metrics.confidence >= self.config.min_confidence()
+                    && metrics.lift >= self.config.min_lift()
+                    && self.config.allows_metrics(&metrics)
+                {
+                    if base_rate.suppresses(&consequent) {
+                        continue;
+                    }
+                    let rule = AssociationRule {
+                        antecedent: antecedent.clone(),
+                        consequent: consequent.clone(),
+                        metrics,
+                    };
+                    on_rule(&rule);
+                    rules.push(rule);
+                }
+            }
+        }
+        progress.checkpoint(ProgressPhase::RuleGeneration, total, total)?;
+        stats.base_rate_suppressed = base_rate.suppressed();
+        Ok(significance::correct(rules, tested, &self.config))
+    }
+
+    /// Calculate metrics for a rule
+    fn calculate_metrics(
+        &self,
+        antecedent: &ItemSet,
+        consequent: &ItemSet,
+        metric_set: MetricSet,
+    ) -> PatternMetrics {
+        // Count occurrences
+        let index = self.index();
+        let antecedent_count = index.count(antecedent);
+        let consequent_count = index.count(consequent);
+        let both_count = index
+            .encode(antecedent.iter().chain(consequent))
+            .map_or(0, |both| index.count_encoded(&both));
+
+        let mut metrics = metrics_from_counts(
+            antecedent_count,
+            consequent_count,
+            both_count,
+            self.baskets().len() as f64,
+            metric_set,
+        );
+        if self.item_weights.is_some() {
+            let items: ItemSet = antecedent.iter().chain(consequent).cloned().collect();
+            metrics.weighted_support = Some(
+                self.weight_table()
+                    .weighted_support(&items, both_count as f64),
+            );
+        }
+        metrics
+    }
+
+    /// Recompute rule metrics against the loaded transactions
+    ///
+    /// Fills in metrics that were skipped by `MiningConfig::compute_metrics`;
+    /// metrics a rule already has are recomputed as well.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{MetricSet, MiningConfig, RuleMiner, Support, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.5)?)
+    ///     .compute_metrics(MetricSet::CORE)
+    ///     .build()?;
+    /// let mut miner = RuleMiner::new(config);
+    /// miner.add_transactions(vec![
+    ///     Transaction::new("t1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("t2", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    ///     Transaction::new("t3", vec!["A".to_string(), "B".to_string()], Utc::now()),
+    /// ])?;
+    /// let mut rules = miner.mine()?.rules;
+    /// assert!(rules[0].metrics.leverage.is_none());
+    ///
+    /// miner.recalibrate(&mut rules, MetricSet::EXTENDED);
+    /// assert!(rules[0].metrics.leverage.is_some());
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn recalibrate(&self, rules: &mut [AssociationRule], metrics: MetricSet) {
+        for rule in rules {
+            let metric_set = metrics | rule.metrics.computed();
+            let mut updated =
+                self.calculate_metrics(&rule.antecedent, &rule.consequent, metric_set);
+            updated.avg_time_gap = rule.metrics.avg_time_gap;
+            updated.time_variance = rule.metrics.time_variance;
+            rule.metrics = updated;
+        }
     }
 
     /// Get mining statistics
-    pub fn stats(&self) -> &stats::MiningStats {
+    ///
+    /// Describes the last run of a `&mut self` mining method; `mine()`
+    /// returns its statistics in `MiningOutcome::stats` instead.
+    pub fn stats(&self) -> &MiningStats {
         &self.stats
     }
 }
 
+/// `seed` with the fields of `transaction` that mining reads folded in
+fn fingerprint(seed: u64, transaction: &Transaction) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    transaction.id.hash(&mut hasher);
+    transaction.timestamp.hash(&mut hasher);
+    transaction.items.hash(&mut hasher);
+    transaction.user_id.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Identity of a transaction for `Preprocessing::dedup_transactions`: its id
+/// and item set
+fn duplicate_key(transaction: &Transaction) -> u64 {
+    let mut items: Vec<&String> = transaction.items.iter().collect();
+    items.sort();
+    items.dedup();
+    let mut hasher = DefaultHasher::new();
+    transaction.id.hash(&mut hasher);
+    items.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Keep the stronger direction of each A=>B / B=>A pair
+///
+/// The direction with the higher confidence wins, ties go to the higher lift
+/// and then to the smaller antecedent, so the result does not depend on the
+/// input order. Survivors keep their relative order. Returns `rules`
+/// unchanged when `MiningConfig::dedup_bidirectional` is off.
+pub(crate) fn filter_bidirectional_rules(
+    rules: Vec<AssociationRule>,
+    config: &MiningConfig,
+) -> Vec<AssociationRule> {
+    if !config.dedup_bidirectional {
+        return rules;
+    }
+
+    let sorted = |items: &[String]| {
+        let mut items = items.to_vec();
+        items.sort();
+        items
+    };
+    let mut best: HashMap<(ItemSet, ItemSet), usize> = HashMap::new();
+    for (i, rule) in rules.iter().enumerate() {
+        let sides = (sorted(&rule.antecedent), sorted(&rule.consequent));
+        let key = if sides.0 <= sides.1 {
+            sides
+        } else {
+            (sides.1, sides.0)
+        };
+        best.entry(key)
+            .and_modify(|kept| {
+                if stronger_direction(rule, &rules[*kept]) {
+                    *kept = i;
+                }
+            })
+            .or_insert(i);
+    }
+
+    let keep: std::collections::HashSet<usize> = best.into_values().collect();
+    rules
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| keep.contains(i))
+        .map(|(_, rule)| rule)
+        .collect()
+}
+
+/// Whether `rule` beats the opposite direction `other`
+fn stronger_direction(rule: &AssociationRule, other: &AssociationRule) -> bool {
+    let (a, b) = (&rule.metrics, &other.metrics);
+    a.confidence
+        .total_cmp(&b.confidence)
+        .then(a.lift.total_cmp(&b.lift))
+        .then_with(|| other.antecedent.len().cmp(&rule.antecedent.len()))
+        .then_with(|| {
+            let mut ours = rule.antecedent.clone();
+            let mut theirs = other.antecedent.clone();
+            ours.sort();
+            theirs.sort();
+            theirs.cmp(&ours)
+        })
+        .is_gt()
+}
+
+/// Drops rules whose consequent holds an item more common than
+/// `MiningConfig::max_consequent_base_rate`, counting what it drops
+///
+/// `RuleMiner`, `SupportTable::rules_at`, `TwoPassMiner` and
+/// `IncrementalMiner::pair_rules` all apply it to rules that passed the
+/// thresholds, so they agree.
+#[derive(Debug, Default)]
+pub(crate) struct BaseRateFilter {
+    dominant: HashSet<String>,
+    suppressed: usize,
+}
+
+impl BaseRateFilter {
+    /// Filter for items with the given basket counts out of `total` baskets
+    pub(crate) fn new<'a>(
+        config: &MiningConfig,
+        item_counts: impl IntoIterator<Item = (&'a str, usize)>,
+        total: usize,
+    ) -> Self {
+        let dominant = match config.max_consequent_base_rate {
+            Some(cap) => item_counts
+                .into_iter()
+                .filter(|(_, count)| *count as f64 / total as f64 > cap)
+                .map(|(item, _)| item.to_string())
+                .collect(),
+            None => HashSet::new(),
+        };
+        Self {
+            dominant,
+            suppressed: 0,
+        }
+    }
+
+    /// Whether a rule with this consequent is dropped; counts it if so
+    pub(crate) fn suppresses(&mut self, consequent: &[String]) -> bool {
+        let dropped = consequent
+            .iter()
+            .any(|item| self.dominant.contains(item.as_str()));
+        self.suppressed += usize::from(dropped);
+        dropped
+    }
+
+    /// Rules dropped so far
+    pub(crate) fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+}
+
+/// Generate all non-empty proper subsets of an itemset
+fn generate_non_empty_subsets(items: &[String]) -> Vec<ItemSet> {
+    let mut subsets = Vec::new();
+    let n = items.len();
+
+    // Generate all possible combinations (2^n - 1, excluding empty set and full set)
+    for i in 1..(1 << n) - 1 {
+        let mut subset = Vec::new();
+        for (j, item) in items.iter().enumerate() {
+            if (i & (1 << j)) != 0 {
+                subset.push(item.clone());
+            }
+        }
+        subsets.push(subset);
+    }
+
+    subsets
+}
+
+/// Calculate rule metrics from raw support counts
+///
+/// Confidence, support and lift are always computed; other metrics only when
+/// included in `metric_set`.
+pub(crate) fn metrics_from_counts(
+    antecedent_count: usize,
+    consequent_count: usize,
+    both_count: usize,
+    total: f64,
+    metric_set: MetricSet,
+) -> PatternMetrics {
+    let confidence = if antecedent_count > 0 {
+        both_count as f64 / antecedent_count as f64
+    } else {
+        0.0
+    };
+    let support = both_count as f64 / total;
+
+    let p_consequent = consequent_count as f64 / total;
+    let lift = if p_consequent > 0.0 {
+        confidence / p_consequent
+    } else {
+        0.0
+    };
+
+    let conviction = metric_set.contains(MetricSet::CONVICTION).then(|| {
+        if confidence < 1.0 && p_consequent < 1.0 {
+            (1.0 - p_consequent) / (1.0 - confidence)
+        } else {
+            f64::INFINITY
+        }
+    });
+
+    let (leverage, jaccard, cosine, kulczynski) = if metric_set.contains(MetricSet::EXTENDED) {
+        let p_antecedent = antecedent_count as f64 / total;
+        let either = p_antecedent + p_consequent - support;
+        let jaccard = if either > 0.0 { support / either } else { 0.0 };
+        let both_sides = p_antecedent * p_consequent;
+        let cosine = if both_sides > 0.0 {
+            support / both_sides.sqrt()
+        } else {
+            0.0
+        };
+        // P(A | B); confidence is P(B | A)
+        let reverse = if consequent_count > 0 {
+            both_count as f64 / consequent_count as f64
+        } else {
+            0.0
+        };
+        (
+            Some(support - both_sides),
+            Some(jaccard),
+            Some(cosine),
+            Some((confidence + reverse) / 2.0),
+        )
+    } else {
+        (None, None, None, None)
+    };
+
+    let (chi_square, p_value) = if metric_set.contains(MetricSet::SIGNIFICANCE) {
+        let chi_square = significance::chi_square(
+            antecedent_count,
+            consequent_count,
+            both_count,
+            total as usize,
+        );
+        (
+            Some(chi_square),
+            Some(significance::chi_square_p_value(chi_square)),
+        )
+    } else {
+        (None, None)
+    };
+
+    let counts = metric_set
+        .contains(MetricSet::COUNTS)
+        .then_some(RuleCounts {
+            antecedent: antecedent_count,
+            consequent: consequent_count,
+            joint: both_count,
+            total: total as usize,
+        });
+
+    PatternMetrics {
+        confidence,
+        support,
+        lift,
+        conviction,
+        leverage,
+        jaccard,
+        cosine,
+        kulczynski,
+        counts,
+        weighted_support: None,
+        chi_square,
+        p_value,
+        avg_time_gap: None,
+        time_variance: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{
+        Confidence, Lift, MiningAlgorithm, MiningConfigBuilder, RuleConstraints, Support,
+    };
+    use crate::types::test_rule;
     use chrono::Utc;
+    use std::time::Duration;
 
     #[test]
     fn test_basic_mining() {
@@ -310,17 +1557,1627 @@ mod tests {
             Transaction::new("tx3", vec!["A".to_string(), "C".to_string()], Utc::now()),
         ];
 
-        let config = MiningConfig {
-            min_support: 0.5,
-            min_confidence: 0.6,
-            min_lift: 1.0,
-            ..Default::default()
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.5).unwrap())
+            .min_confidence(Confidence::fraction(0.6).unwrap())
+            .min_lift(Lift::new(1.0).unwrap())
+            .build()
+            .unwrap();
+
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+
+        let outcome = miner.mine().unwrap();
+        assert!(!outcome.rules.is_empty());
+
+        // C (1 of 3 transactions) falls below min_support
+        let long_tail = outcome.stats.long_tail.as_ref().unwrap();
+        assert_eq!(long_tail.excluded_item_count, 1);
+        assert_eq!(long_tail.near_misses[0].0, "C");
+    }
+
+    fn metrics_miner(metrics: MetricSet) -> RuleMiner {
+        let transactions = vec![
+            Transaction::new("tx1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+            Transaction::new("tx2", vec!["A".to_string(), "B".to_string()], Utc::now()),
+            Transaction::new("tx3", vec!["A".to_string(), "C".to_string()], Utc::now()),
+            Transaction::new("tx4", vec!["B".to_string(), "C".to_string()], Utc::now()),
+        ];
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.5).unwrap())
+            .min_confidence(Confidence::fraction(0.6).unwrap())
+            .min_lift(Lift::new(0.0).unwrap())
+            .compute_metrics(metrics)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        miner
+    }
+
+    #[test]
+    fn test_core_metrics_skip_optional_fields() {
+        let rules = metrics_miner(MetricSet::CORE).mine().unwrap().rules;
+        assert!(!rules.is_empty());
+        for rule in &rules {
+            assert_eq!(rule.metrics.computed(), MetricSet::CORE);
+            assert!(rule.metrics.conviction.is_none());
+            assert!(rule.metrics.confidence > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_recalibrate_fills_gaps() {
+        let miner = metrics_miner(MetricSet::CORE);
+        let mut rules = miner.mine().unwrap().rules;
+        let full = metrics_miner(MetricSet::ALL).mine().unwrap().rules;
+
+        miner.recalibrate(&mut rules, MetricSet::EXTENDED | MetricSet::COUNTS);
+
+        // A => B: 2 of 3 A-baskets hold B, B is in 3 of 4 baskets
+        let rule = rules
+            .iter()
+            .find(|r| r.antecedent == ["A"] && r.consequent == ["B"])
+            .unwrap();
+        assert_eq!(
+            rule.metrics.computed(),
+            MetricSet::EXTENDED | MetricSet::COUNTS
+        );
+        let counts = rule.metrics.counts.unwrap();
+        assert_eq!(
+            (counts.antecedent, counts.consequent, counts.joint),
+            (3, 3, 2)
+        );
+        assert!((rule.metrics.leverage.unwrap() - (0.5 - 0.75 * 0.75)).abs() < 1e-12);
+        assert!((rule.metrics.jaccard.unwrap() - 0.5).abs() < 1e-12);
+
+        let expected = full
+            .iter()
+            .find(|r| r.antecedent == rule.antecedent && r.consequent == rule.consequent)
+            .unwrap();
+        assert_eq!(rule.metrics.leverage, expected.metrics.leverage);
+        assert_eq!(rule.metrics.counts, expected.metrics.counts);
+        assert!(rule.metrics.conviction.is_none());
+    }
+
+    #[test]
+    fn test_recalibrate_keeps_significance() {
+        // A and B always together, C alone: chi-square 20 for A => B
+        let transactions: Vec<Transaction> = (0..20)
+            .map(|i| {
+                let items = if i < 10 { vec!["A", "B"] } else { vec!["C"] };
+                Transaction::new(
+                    format!("tx{}", i),
+                    items.into_iter().map(String::from).collect(),
+                    Utc::now(),
+                )
+            })
+            .collect();
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.5).unwrap())
+            .min_confidence(Confidence::fraction(0.6).unwrap())
+            .min_lift(Lift::new(1.0).unwrap())
+            .max_p_value(0.99)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        let mut rules = miner.mine().unwrap().rules;
+        assert!(!rules.is_empty());
+        let mined = rules.clone();
+
+        miner.recalibrate(&mut rules, MetricSet::EXTENDED);
+
+        for (rule, before) in rules.iter().zip(&mined) {
+            assert!(rule.metrics.computed().contains(MetricSet::SIGNIFICANCE));
+            assert_eq!(rule.metrics.chi_square, before.metrics.chi_square);
+            assert_eq!(rule.metrics.p_value, before.metrics.p_value);
+            assert!(rule.metrics.leverage.is_some());
+        }
+    }
+
+    #[test]
+    fn test_null_invariant_metrics() {
+        // {A,B} {A,B} {A,C} {A} {B}: P(A) = 0.8, P(B) = 0.6, P(AB) = 0.4
+        let metrics = metrics_from_counts(4, 3, 2, 5.0, MetricSet::EXTENDED);
+        let close = |value: Option<f64>, expected: f64| {
+            assert!((value.unwrap() - expected).abs() < 1e-12, "{:?}", value)
         };
+        close(metrics.leverage, 0.4 - 0.8 * 0.6);
+        close(metrics.jaccard, 0.4 / (0.8 + 0.6 - 0.4));
+        close(metrics.cosine, 0.4 / (0.8f64 * 0.6).sqrt());
+        close(metrics.kulczynski, (2.0 / 4.0 + 2.0 / 3.0) / 2.0);
+
+        let core = metrics_from_counts(4, 3, 2, 5.0, MetricSet::CORE);
+        assert!(core.cosine.is_none() && core.kulczynski.is_none());
+
+        // Metrics written before cosine/kulczynski existed still load
+        let old: PatternMetrics = serde_json::from_str(
+            r#"{"confidence":0.5,"support":0.4,"lift":0.83,"conviction":0.8,"leverage":-0.08}"#,
+        )
+        .unwrap();
+        assert_eq!(old.cosine, None);
+        assert_eq!(old.kulczynski, None);
+    }
+
+    #[test]
+    fn test_extended_metric_thresholds() {
+        let basket = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        let transactions = vec![
+            Transaction::new("t1", basket(&["A", "B"]), Utc::now()),
+            Transaction::new("t2", basket(&["A", "B"]), Utc::now()),
+            Transaction::new("t3", basket(&["A", "C"]), Utc::now()),
+            Transaction::new("t4", basket(&["A", "C"]), Utc::now()),
+            Transaction::new("t5", basket(&["A", "C"]), Utc::now()),
+            Transaction::new("t6", basket(&["B"]), Utc::now()),
+        ];
+        let mine = |builder: crate::config::MiningConfigBuilder| {
+            let config = builder
+                .min_support(Support::fraction(0.3).unwrap())
+                .min_confidence(Confidence::fraction(0.5).unwrap())
+                .min_lift(Lift::new(0.0).unwrap())
+                .compute_metrics(MetricSet::CORE)
+                .build()
+                .unwrap();
+            let mut miner = RuleMiner::new(config);
+            miner.add_transactions(transactions.clone()).unwrap();
+            miner.mine().unwrap().rules
+        };
+
+        // C => A: cosine 3 / sqrt(5 * 3) = 0.77; B => A: 2 / sqrt(5 * 3) = 0.52
+        let all = mine(MiningConfig::builder());
+        assert!(all.iter().any(|r| r.antecedent == ["B"]));
+        let filtered = mine(MiningConfig::builder().min_cosine(0.6));
+        assert!(filtered.iter().all(|r| r.antecedent != ["B"]));
+        let c_to_a = filtered.iter().find(|r| r.antecedent == ["C"]).unwrap();
+        assert!((c_to_a.metrics.cosine.unwrap() - 3.0 / 15f64.sqrt()).abs() < 1e-12);
+        // Kulczynski of C => A: (1 + 3/5) / 2 = 0.8
+        assert!(mine(MiningConfig::builder().min_kulczynski(0.81)).is_empty());
+
+        let invalid = MiningConfig::builder().min_leverage(0.5).build();
+        assert!(matches!(invalid, Err(MiningError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_rule_size_limits() {
+        use crate::config::MiningAlgorithm;
+
+        let items: Vec<String> = ["A", "B", "C", "D", "E"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let transactions: Vec<Transaction> = (0..4)
+            .map(|i| Transaction::new(format!("tx{}", i), items.clone(), Utc::now()))
+            .collect();
+
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let config = MiningConfig::builder()
+                .min_support(Support::fraction(0.5).unwrap())
+                .min_confidence(Confidence::fraction(0.5).unwrap())
+                .min_lift(Lift::new(0.0).unwrap())
+                .algorithm(algorithm)
+                .max_itemset_size(3)
+                .max_antecedent_size(2)
+                .max_consequent_size(1)
+                .build()
+                .unwrap();
+
+            let mut miner = RuleMiner::new(config);
+            miner.add_transactions(transactions.clone()).unwrap();
+            let rules = miner.mine().unwrap().rules;
+
+            assert!(!rules.is_empty());
+            for rule in &rules {
+                assert!(rule.antecedent.len() + rule.consequent.len() <= 3);
+                assert!(rule.antecedent.len() <= 2);
+                assert_eq!(rule.consequent.len(), 1);
+            }
+            assert!(rules.iter().any(|r| r.antecedent.len() == 2));
+        }
+    }
+
+    #[test]
+    fn test_closed_itemsets_reduce_rules() {
+        use crate::config::ItemsetKind;
+
+        // B and C only ever appear together with A, so every itemset
+        // containing B or C has the same support as {A,B,C}
+        let transactions = vec![
+            Transaction::new(
+                "tx1",
+                vec!["A".to_string(), "B".to_string(), "C".to_string()],
+                Utc::now(),
+            ),
+            Transaction::new(
+                "tx2",
+                vec!["A".to_string(), "B".to_string(), "C".to_string()],
+                Utc::now(),
+            ),
+            Transaction::new("tx3", vec!["A".to_string()], Utc::now()),
+        ];
+
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.5).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .min_lift(Lift::new(0.0).unwrap())
+            .itemset_kind(ItemsetKind::Closed)
+            .build()
+            .unwrap();
 
         let mut miner = RuleMiner::new(config);
         miner.add_transactions(transactions).unwrap();
 
-        let rules = miner.mine_association_rules().unwrap();
+        let itemsets = miner.mine_frequent_itemsets().unwrap();
+        let mut keys: Vec<ItemSet> = itemsets.iter().map(|i| i.items.clone()).collect();
+        keys.sort();
+
+        // {B}, {C}, {A,B}, {A,C}, {B,C} all collapse into {A,B,C}
+        assert_eq!(
+            keys,
+            vec![
+                vec!["A".to_string()],
+                vec!["A".to_string(), "B".to_string(), "C".to_string()],
+            ]
+        );
+        assert_eq!(miner.stats().frequent_itemsets_count, 2);
+
+        // Rules only come from the closed 3-itemset
+        let rules = miner.mine().unwrap().rules;
+        assert!(!rules.is_empty());
+        assert!(rules
+            .iter()
+            .all(|r| r.antecedent.len() + r.consequent.len() == 3));
+    }
+
+    #[test]
+    fn test_mine_frequent_itemsets_both_algorithms() {
+        let transactions = vec![
+            Transaction::new(
+                "tx1",
+                vec!["A".to_string(), "B".to_string(), "C".to_string()],
+                Utc::now(),
+            ),
+            Transaction::new("tx2", vec!["A".to_string(), "B".to_string()], Utc::now()),
+            Transaction::new("tx3", vec!["A".to_string(), "C".to_string()], Utc::now()),
+            Transaction::new("tx4", vec!["A".to_string()], Utc::now()),
+        ];
+
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let mut miner = RuleMiner::new(
+                MiningConfig::builder()
+                    .min_support(Support::fraction(0.5).unwrap())
+                    .algorithm(algorithm)
+                    .build()
+                    .unwrap(),
+            );
+            miner.add_transactions(transactions.clone()).unwrap();
+
+            let itemsets = miner.mine_frequent_itemsets().unwrap();
+
+            // A (100%) first, then the 50% itemsets
+            assert_eq!(itemsets[0].items, vec!["A".to_string()]);
+            assert_eq!(itemsets[0].support, 1.0);
+            assert!(itemsets.windows(2).all(|w| w[0].support >= w[1].support));
+            assert!(itemsets
+                .iter()
+                .any(|i| i.items == vec!["A".to_string(), "B".to_string()]));
+            assert_eq!(miner.stats().frequent_itemsets_count, itemsets.len());
+        }
+    }
+
+    #[test]
+    fn test_mine_frequent_itemsets_empty() {
+        let mut miner = RuleMiner::new(MiningConfig::default());
+        assert!(matches!(
+            miner.mine_frequent_itemsets(),
+            Err(MiningError::InsufficientData(_))
+        ));
+    }
+
+    fn constrained_basket_data() -> Vec<Transaction> {
+        let mut rng = crate::rng::SplitMix64::new(7);
+        (0..300)
+            .map(|i| {
+                let mut items: Vec<String> = (0..10)
+                    .filter(|_| rng.next_u64().is_multiple_of(3))
+                    .map(|n| format!("Item{}", n))
+                    .collect();
+                items.push("Plastic Bag".to_string());
+                if i % 20 == 0 {
+                    items.push("New SKU".to_string());
+                    items.push("Item0".to_string());
+                }
+                items.sort();
+                items.dedup();
+                Transaction::new(format!("tx{}", i), items, Utc::now())
+            })
+            .collect()
+    }
+
+    fn constrained_miner(algorithm: MiningAlgorithm, constraints: RuleConstraints) -> RuleMiner {
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.02).unwrap())
+            .min_confidence(Confidence::fraction(0.3).unwrap())
+            .min_lift(Lift::new(0.0).unwrap())
+            .max_itemset_size(3)
+            .algorithm(algorithm)
+            .constraints(constraints)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(constrained_basket_data()).unwrap();
+        miner
+    }
+
+    #[test]
+    fn test_excluded_items_never_mined() {
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let baseline = constrained_miner(algorithm, RuleConstraints::new())
+                .mine()
+                .unwrap()
+                .rules;
+            assert!(baseline
+                .iter()
+                .any(|r| r.antecedent.contains(&"Plastic Bag".to_string())
+                    || r.consequent.contains(&"Plastic Bag".to_string())));
+
+            let mut miner = constrained_miner(
+                algorithm,
+                RuleConstraints::new().with_excluded_items(["Plastic Bag"]),
+            );
+            let itemsets = miner.mine_frequent_itemsets().unwrap();
+            assert!(itemsets
+                .iter()
+                .all(|i| !i.items.contains(&"Plastic Bag".to_string())));
+
+            let rules = miner.mine().unwrap().rules;
+            assert!(!rules.is_empty());
+            for rule in &rules {
+                assert!(
+                    !rule.antecedent.contains(&"Plastic Bag".to_string()),
+                    "{:?}",
+                    rule
+                );
+                assert!(
+                    !rule.consequent.contains(&"Plastic Bag".to_string()),
+                    "{:?}",
+                    rule
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_required_items_match_filtered_full_mining() {
+        let required = RuleConstraints::new().with_required_items(["New SKU"]);
+        let mentions = |rule: &AssociationRule| {
+            rule.antecedent.contains(&"New SKU".to_string())
+                || rule.consequent.contains(&"New SKU".to_string())
+        };
+
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let full = constrained_miner(algorithm, RuleConstraints::new())
+                .mine()
+                .unwrap()
+                .rules;
+            let focused = constrained_miner(algorithm, required.clone())
+                .mine()
+                .unwrap()
+                .rules;
+
+            assert!(!focused.is_empty());
+            assert!(focused.iter().all(mentions));
+
+            // Same rules and metrics as mining everything and filtering after
+            let key = |r: &AssociationRule| format!("{:?}", r);
+            let mut expected: Vec<String> = full.iter().filter(|r| mentions(r)).map(key).collect();
+            let mut actual: Vec<String> = focused.iter().map(key).collect();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_required_items_count_fewer_candidates() {
+        let candidates = |constraints: RuleConstraints| {
+            constrained_miner(MiningAlgorithm::Apriori, constraints)
+                .mine()
+                .unwrap()
+                .stats
+                .apriori_candidates
+        };
+
+        // Only 1 in 20 transactions has the focus item, so the focused run
+        // counts candidates built from far fewer transactions
+        let full = candidates(RuleConstraints::new());
+        let focused = candidates(RuleConstraints::new().with_required_items(["New SKU"]));
+        assert_eq!(full.len(), focused.len());
+        assert!(
+            focused.iter().zip(&full).all(|(f, a)| f <= a),
+            "focused {:?} vs full {:?}",
+            focused,
+            full
+        );
+        assert!(
+            focused.iter().sum::<usize>() < full.iter().sum::<usize>(),
+            "focused {:?} vs full {:?}",
+            focused,
+            full
+        );
+    }
+
+    #[test]
+    fn test_antecedent_and_consequent_only() {
+        let constraints = RuleConstraints::new()
+            .with_antecedent_only(["Item1"])
+            .with_consequent_only(["Item2"]);
+        let rules = constrained_miner(MiningAlgorithm::FPGrowth, constraints)
+            .mine()
+            .unwrap()
+            .rules;
+
+        assert!(rules
+            .iter()
+            .any(|r| r.antecedent.contains(&"Item1".to_string())));
+        for rule in &rules {
+            assert!(
+                !rule.consequent.contains(&"Item1".to_string()),
+                "{:?}",
+                rule
+            );
+            assert!(
+                !rule.antecedent.contains(&"Item2".to_string()),
+                "{:?}",
+                rule
+            );
+        }
+    }
+
+    fn inventory_miner(config: MiningConfig) -> RuleMiner {
+        let mut transactions = Vec::new();
+        let mut push = |items: &[&str], times: usize| {
+            for _ in 0..times {
+                let id = format!("tx{}", transactions.len());
+                let items = items.iter().map(|s| s.to_string()).collect();
+                transactions.push(Transaction::new(id, items, Utc::now()));
+            }
+        };
+        push(&["Laptop", "Dock"], 3);
+        push(&["Cable", "Charger"], 5);
+        push(&["Pen"], 2);
+
+        let mut miner = RuleMiner::new(config);
+        miner
+            .set_item_weights(HashMap::from([
+                ("Laptop".to_string(), 2000.0),
+                ("Dock".to_string(), 1500.0),
+                ("Cable".to_string(), 2.0),
+                ("Charger".to_string(), 3.0),
+            ]))
+            .unwrap();
+        miner.add_transactions(transactions).unwrap();
+        miner
+    }
+
+    #[test]
+    fn test_weighted_support_keeps_heavy_items() {
+        let builder = MiningConfig::builder()
+            .min_support(Support::fraction(0.2).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap());
+        let has_rule =
+            |rules: &[AssociationRule], item: &str| rules.iter().any(|r| r.antecedent == [item]);
+
+        // Classic support: Cable/Charger (50%) beats Laptop/Dock (30%)
+        let rules = inventory_miner(builder.clone().build().unwrap())
+            .mine()
+            .unwrap()
+            .rules;
+        assert!(has_rule(&rules, "Laptop") || has_rule(&rules, "Dock"));
+        assert!(has_rule(&rules, "Cable") || has_rule(&rules, "Charger"));
+
+        // Total weight 3 * 2000 + 5 * 3 + 2 * 1 (Pen is unweighted)
+        let heavy = rules
+            .iter()
+            .find(|r| r.antecedent.contains(&"Laptop".to_string()) || r.antecedent == ["Dock"])
+            .unwrap();
+        assert_eq!(heavy.metrics.weighted_support, Some(1500.0 * 3.0 / 6017.0));
+        let light = rules
+            .iter()
+            .find(|r| r.antecedent == ["Cable"] || r.antecedent == ["Charger"])
+            .unwrap();
+        assert_eq!(light.metrics.weighted_support, Some(2.0 * 5.0 / 6017.0));
+
+        // Weighted threshold: the heavy pair survives, the light pair does not
+        let config = builder
+            .min_weighted_support(Support::fraction(0.2).unwrap())
+            .build()
+            .unwrap();
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let config = MiningConfigBuilder::from(config.clone())
+                .algorithm(algorithm)
+                .build()
+                .unwrap();
+            let mut miner = inventory_miner(config);
+            let rules = miner.mine().unwrap().rules;
+            assert!(has_rule(&rules, "Laptop") || has_rule(&rules, "Dock"));
+            assert!(!has_rule(&rules, "Cable") && !has_rule(&rules, "Charger"));
+
+            let itemsets = miner.mine_frequent_itemsets().unwrap();
+            assert!(itemsets.iter().any(|i| i.items == ["Laptop"]));
+            assert!(itemsets.iter().all(|i| i.items != ["Cable"]));
+        }
+    }
+
+    #[test]
+    fn test_item_weights_validated() {
+        let config = MiningConfig::builder()
+            .allow_singleton_support(true)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        let err = miner
+            .set_item_weights(HashMap::from([("Cable".to_string(), -2.0)]))
+            .unwrap_err();
+        assert!(err.to_string().contains("Cable"), "{}", err);
+        let mut overweight = MiningConfig::builder().build().unwrap();
+        overweight.min_weighted_support = Some(1.5);
+        assert!(overweight.validate().is_err());
+
+        // No weights, no weighted metric
+        miner
+            .add_transactions(vec![
+                Transaction::new("t1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+                Transaction::new("t2", vec!["A".to_string(), "B".to_string()], Utc::now()),
+            ])
+            .unwrap();
+        let rules = miner.mine().unwrap().rules;
+        assert_eq!(rules[0].metrics.weighted_support, None);
+    }
+
+    #[test]
+    fn test_fallback_to_apriori() {
+        let transactions = vec![
+            Transaction::new("t1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+            Transaction::new("t2", vec!["A".to_string(), "B".to_string()], Utc::now()),
+            Transaction::new("t3", vec!["A".to_string()], Utc::now()),
+        ];
+        let base = MiningConfig::builder()
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .allow_singleton_support(true)
+            .build()
+            .unwrap();
+        let miner = |fallback: bool| {
+            // Built with the fallback, which validation requires for Eclat,
+            // then switched off to reach the mining-time error
+            let mut config = MiningConfigBuilder::from(base.clone())
+                .algorithm(MiningAlgorithm::Eclat)
+                .fallback_to_apriori(true)
+                .build()
+                .unwrap();
+            config.fallback_to_apriori = fallback;
+            let mut miner = RuleMiner::new(config);
+            miner.add_transactions(transactions.clone()).unwrap();
+            miner
+        };
+
+        // Without the flag the unimplemented algorithm is an error
+        let mut strict = miner(false);
+        assert!(strict.mine_frequent_itemsets().is_err());
+        assert!(strict.stats().algorithm.is_none());
+
+        let outcome = miner(true).mine().unwrap();
+        let rules = outcome.rules;
         assert!(!rules.is_empty());
+        let report = outcome.stats.algorithm.as_ref().unwrap();
+        assert!(report.fell_back());
+        assert_eq!(report.attempted, MiningAlgorithm::Eclat);
+        assert_eq!(report.used, MiningAlgorithm::Apriori);
+        assert!(report.fallback_reason.as_ref().unwrap().contains("Eclat"));
+
+        // Same rules as mining with Apriori directly
+        let mut apriori = RuleMiner::new(base.clone());
+        apriori.add_transactions(transactions.clone()).unwrap();
+        assert_eq!(
+            format!("{:?}", apriori.mine().unwrap().rules),
+            format!("{:?}", rules)
+        );
+
+        // No fallback when the configured algorithm succeeds
+        let mut fpgrowth = RuleMiner::new(
+            MiningConfigBuilder::from(base)
+                .algorithm(MiningAlgorithm::FPGrowth)
+                .fallback_to_apriori(true)
+                .build()
+                .unwrap(),
+        );
+        fpgrowth.add_transactions(transactions).unwrap();
+        fpgrowth.mine_frequent_itemsets().unwrap();
+        let report = fpgrowth.stats().algorithm.as_ref().unwrap();
+        assert!(!report.fell_back());
+        assert_eq!(report.used, MiningAlgorithm::FPGrowth);
+    }
+
+    #[test]
+    fn test_phase_timings() {
+        // 400 baskets over 12 items, dense enough for several Apriori levels
+        let transactions: Vec<Transaction> = (0..400)
+            .map(|tx| {
+                let items = (0..12)
+                    .filter(|item| (tx * 7 + item * 3) % (item + 2) != 0)
+                    .map(|item| format!("item{}", item))
+                    .collect();
+                Transaction::new(format!("tx{}", tx), items, Utc::now())
+            })
+            .collect();
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.3).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .max_itemset_size(3)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        let outcome = miner.mine().unwrap();
+        assert!(!outcome.rules.is_empty());
+
+        let phases = &outcome.stats.phases;
+        assert!(phases.item_counting > Duration::ZERO);
+        assert!(phases.itemsets > Duration::ZERO);
+        assert!(phases.rule_generation > Duration::ZERO);
+        assert!(phases.sorting > Duration::ZERO);
+        assert!(phases.filtering > Duration::ZERO);
+        assert_eq!(phases.itemset_levels.len(), 3);
+        assert!(phases.itemset_levels.iter().all(|t| *t > Duration::ZERO));
+        assert!(phases.item_counting <= phases.itemset_levels[0]);
+
+        // Levels make up the itemset phase, up to bookkeeping around them
+        let levels: Duration = phases.itemset_levels.iter().sum();
+        assert!(levels <= phases.itemsets);
+        assert!(levels.as_secs_f64() >= phases.itemsets.as_secs_f64() * 0.8);
+    }
+
+    #[test]
+    fn test_run_counters() {
+        let basket = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        let transactions = vec![
+            Transaction::new("t1", basket(&["A", "B", "C"]), Utc::now()),
+            Transaction::new("t2", basket(&["A", "B"]), Utc::now()),
+            Transaction::new("t3", basket(&["A", "C"]), Utc::now()),
+            Transaction::new("t4", basket(&["B", "C"]), Utc::now()),
+            Transaction::new("t5", basket(&["D"]), Utc::now()),
+        ];
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.4).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .min_lift(Lift::new(0.0).unwrap())
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        let outcome = miner.mine().unwrap();
+
+        let stats = &outcome.stats;
+        assert_eq!(stats.transactions_processed, 5);
+        assert_eq!(stats.min_support_count, 2);
+        // A, B, C, D; then AB, AC, BC; then ABC, which is infrequent
+        assert_eq!(stats.apriori_candidates, [4, 3, 1]);
+        assert_eq!(stats.frequent_itemsets_count, 6);
+        assert_eq!(stats.rules_generated, outcome.rules.len());
+        assert!(stats.itemset_mining_duration() > Duration::ZERO);
+        assert!(stats.rule_generation_duration() > Duration::ZERO);
+
+        // The next run starts from scratch
+        miner.mine_frequent_itemsets().unwrap();
+        let stats = miner.stats();
+        assert_eq!(stats.rules_generated, 0);
+        assert_eq!(stats.rule_generation_duration(), Duration::ZERO);
+        assert_eq!(stats.apriori_candidates, [4, 3, 1]);
+    }
+
+    #[test]
+    fn test_mine_cache_invalidation() {
+        let mut miner = metrics_miner(MetricSet::CORE);
+        let first = miner.mine().unwrap();
+        assert!(!first.stats.cached);
+
+        // Nothing changed: the same outcome, without mining
+        let second = miner.mine().unwrap();
+        assert!(second.stats.cached);
+        assert_eq!(format!("{:?}", second.rules), format!("{:?}", first.rules));
+        assert_eq!(second.stats.phases, first.stats.phases);
+
+        // New data invalidates
+        miner
+            .add_transaction(Transaction::new(
+                "t_new",
+                vec!["A".to_string(), "C".to_string()],
+                Utc::now(),
+            ))
+            .unwrap();
+        let third = miner.mine().unwrap();
+        assert!(!third.stats.cached);
+        assert_eq!(
+            third.stats.transactions_processed,
+            first.stats.transactions_processed + 1
+        );
+        assert!(miner.mine().unwrap().stats.cached);
+
+        // So do config, scorer and weight changes
+        miner.config.dedup_bidirectional = false;
+        assert!(!miner.mine().unwrap().stats.cached);
+        miner.set_scorer(|rule: &AssociationRule| rule.metrics.lift);
+        assert!(!miner.mine().unwrap().stats.cached);
+        miner.set_item_weights(HashMap::new()).unwrap();
+        assert!(!miner.mine().unwrap().stats.cached);
+    }
+
+    #[test]
+    fn test_evicting_transactions_changes_support() {
+        let now = Utc::now();
+        let tx = |id: usize, items: &[&str], days_ago: i64| {
+            Transaction::new(
+                format!("tx{}", id),
+                items.iter().map(|s| s.to_string()).collect(),
+                now - chrono::Duration::days(days_ago),
+            )
+        };
+        let mut transactions: Vec<Transaction> =
+            (0..6).map(|i| tx(i, &["Beer", "Chips"], 40)).collect();
+        transactions.extend((6..10).map(|i| tx(i, &["Beer", "Chips"], 1)));
+        transactions.extend((10..16).map(|i| tx(i, &["Tea", "Cake"], 1)));
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.3).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+
+        let support = |rules: &[AssociationRule], item: &str| {
+            rules
+                .iter()
+                .find(|rule| {
+                    rule.antecedent
+                        .iter()
+                        .chain(&rule.consequent)
+                        .any(|i| i == item)
+                })
+                .map(|rule| rule.metrics.support)
+        };
+        let before = miner.mine().unwrap();
+        assert_eq!(support(&before.rules, "Beer"), Some(10.0 / 16.0));
+        assert_eq!(support(&before.rules, "Tea"), Some(6.0 / 16.0));
+
+        // The 40-day-old baskets leave the window
+        let removed = miner.remove_transactions_before(now - chrono::Duration::days(30));
+        assert_eq!(removed, 6);
+        assert_eq!(miner.transactions().len(), 10);
+        assert_eq!(miner.stats().transactions_processed, 0);
+        let after = miner.mine().unwrap();
+        assert!(!after.stats.cached);
+        assert_eq!(after.stats.transactions_processed, 10);
+        assert_eq!(support(&after.rules, "Beer"), Some(0.4));
+        assert_eq!(support(&after.rules, "Tea"), Some(0.6));
+
+        // Nothing older left: no change, the cached outcome stands
+        assert_eq!(
+            miner.remove_transactions_before(now - chrono::Duration::days(30)),
+            0
+        );
+        assert!(miner.mine().unwrap().stats.cached);
+
+        assert_eq!(
+            miner.retain_transactions(|tx| !tx.items.contains(&"Tea".to_string())),
+            6
+        );
+        let rules = miner.mine().unwrap().rules;
+        assert_eq!(support(&rules, "Beer"), Some(1.0));
+        assert_eq!(support(&rules, "Tea"), None);
+
+        miner.clear_transactions();
+        assert!(miner.transactions().is_empty());
+        assert!(matches!(
+            miner.mine(),
+            Err(MiningError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_merge_miners() {
+        let mut eu = metrics_miner(MetricSet::CORE);
+        let us = metrics_miner(MetricSet::COUNTS);
+        let before = eu.mine().unwrap();
+
+        eu.merge(us);
+        assert_eq!(eu.transaction_count(), 8);
+        // The receiving miner's config stays
+        assert_eq!(eu.config.compute_metrics, MetricSet::CORE);
+        let after = eu.mine().unwrap();
+        assert!(!after.stats.cached);
+        assert_eq!(after.stats.transactions_processed, 8);
+        // Every basket doubled: same rules and metrics
+        assert_eq!(format!("{:?}", after.rules), format!("{:?}", before.rules));
+    }
+
+    #[test]
+    fn test_interner_ids_stable_across_runs() {
+        let path = std::env::temp_dir().join(format!(
+            "rust_rule_miner_interner_runs_{}.json",
+            std::process::id()
+        ));
+        let basket = |id: &str, items: &[&str]| {
+            Transaction::new(
+                id,
+                items.iter().map(|s| s.to_string()).collect(),
+                Utc::now(),
+            )
+        };
+
+        // Run 1: ids follow first appearance
+        let mut first = RuleMiner::new(MiningConfig::default());
+        first
+            .add_transactions(vec![basket("t1", &["Laptop", "Mouse"])])
+            .unwrap();
+        first.set_interner(Interner::new());
+        first
+            .add_transactions(vec![basket("t2", &["Mouse", "Keyboard"])])
+            .unwrap();
+        let saved = first.interner().unwrap().clone();
+        assert_eq!(saved.items(), ["Laptop", "Mouse", "Keyboard"]);
+        saved.save(&path).unwrap();
+
+        // Run 2: new data, some items gone, one new
+        let mut second = RuleMiner::new(MiningConfig::default());
+        second.set_interner(Interner::load(&path).unwrap());
+        std::fs::remove_file(&path).ok();
+        second
+            .add_transactions(vec![
+                basket("t3", &["Monitor", "Keyboard"]),
+                basket("t4", &["Mouse"]),
+            ])
+            .unwrap();
+        let interner = second.interner().unwrap();
+        for item in saved.items() {
+            assert_eq!(interner.id(item), saved.id(item));
+        }
+        assert_eq!(interner.id("Monitor"), Some(3));
+        assert_eq!(interner.item(3), Some("Monitor"));
+        assert_eq!(interner.len(), 4);
+    }
+
+    #[test]
+    fn test_rule_evidence() {
+        let mut miner = progress_miner(MiningAlgorithm::FPGrowth);
+        assert!(matches!(
+            miner.mine_association_rules_with_evidence(),
+            Err(MiningError::InvalidConfig(_))
+        ));
+
+        miner.config.collect_evidence = Some(3);
+        let (outcome, evidence) = miner.mine_association_rules_with_evidence().unwrap();
+        assert!(!outcome.rules.is_empty());
+        assert_eq!(evidence.len(), outcome.rules.len());
+        for rule in &outcome.rules {
+            // Every rule has at least 150 supporting baskets; 3 are kept
+            let ids = evidence.get(rule).unwrap();
+            assert_eq!(ids.len(), 3);
+            for id in ids {
+                let tx = miner.transactions().iter().find(|tx| &tx.id == id).unwrap();
+                assert!(tx.contains_all(&rule.antecedent));
+                assert!(tx.contains_all(&rule.consequent));
+            }
+        }
+
+        let grl = crate::export::GrlExporter::to_grl_with_evidence(
+            &outcome.rules,
+            &Default::default(),
+            &evidence,
+        );
+        let first = evidence.get(&outcome.rules[0]).unwrap().join(", ");
+        assert!(grl.contains(&format!("// Evidence: {}\n// Rule #1:", first)));
+    }
+
+    #[test]
+    fn test_exclusions_leave_supports_untouched() {
+        use chrono::TimeZone;
+
+        let tx = |id: String, items: &[&str], day| {
+            Transaction::new(
+                id,
+                items.iter().map(|s| s.to_string()).collect(),
+                Utc.with_ymd_and_hms(2024, 3, day, 12, 0, 0).unwrap(),
+            )
+        };
+        let mut transactions = Vec::new();
+        for i in 0..3 {
+            transactions.push(tx(format!("ok{}", i), &["Laptop", "Mouse"], 2));
+            transactions.push(tx(format!("tea{}", i), &["Tea"], 2));
+        }
+        // Incident on March 3rd, plus one known bad order on the 2nd
+        for i in 0..4 {
+            transactions.push(tx(format!("incident{}", i), &["Laptop", "Tea"], 3));
+        }
+        transactions.push(tx("bad".to_string(), &["Laptop", "Tea"], 2));
+
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.3).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .min_lift(Lift::new(0.0).unwrap())
+            .exclude_transaction_ids(["bad"])
+            .exclude_time_range(
+                Utc.with_ymd_and_hms(2024, 3, 3, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap(),
+            )
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        let outcome = miner.mine().unwrap();
+
+        // Stored, but mined as if only the six clean baskets were loaded
+        assert_eq!(miner.transaction_count(), 11);
+        assert_eq!(outcome.stats.transactions_processed, 6);
+        assert_eq!(outcome.stats.excluded_transactions, 5);
+        let laptop_mouse = outcome
+            .rules
+            .iter()
+            .find(|r| r.antecedent == ["Laptop"] && r.consequent == ["Mouse"])
+            .unwrap();
+        assert_eq!(laptop_mouse.metrics.support, 0.5);
+        assert_eq!(laptop_mouse.metrics.confidence, 1.0);
+        assert!(!outcome.rules.iter().any(|r| r.consequent == ["Tea"]));
+
+        let manifest = outcome.stats.exclusions.as_ref().unwrap();
+        assert_eq!(manifest.transaction_ids, ["bad"]);
+        let json = serde_json::to_value(&outcome.stats).unwrap();
+        assert_eq!(
+            json["exclusions"]["time_ranges"],
+            serde_json::json!(["2024-03-03T00:00:00+00:00/2024-03-04T00:00:00+00:00"])
+        );
+    }
+
+    #[test]
+    fn test_max_consequent_base_rate() {
+        // Bag is in 8 of 10 baskets
+        let baskets: [&[&str]; 10] = [
+            &["Laptop", "Mouse", "Bag"],
+            &["Laptop", "Mouse", "Bag"],
+            &["Laptop", "Mouse", "Bag"],
+            &["Laptop", "Mouse"],
+            &["Bag", "Charger"],
+            &["Bag", "Charger"],
+            &["Bag", "Charger"],
+            &["Bag", "Mouse"],
+            &["Bag"],
+            &["Charger"],
+        ];
+        let mine = |cap: Option<f64>| {
+            let mut builder = MiningConfig::builder()
+                .min_support(Support::fraction(0.3).unwrap())
+                .min_confidence(Confidence::fraction(0.5).unwrap())
+                .min_lift(Lift::new(0.0).unwrap())
+                .dedup_bidirectional(false);
+            if let Some(cap) = cap {
+                builder = builder.max_consequent_base_rate(cap);
+            }
+            let mut miner = RuleMiner::new(builder.build().unwrap());
+            miner
+                .add_transactions(
+                    baskets
+                        .iter()
+                        .enumerate()
+                        .map(|(i, items)| {
+                            Transaction::new(
+                                format!("tx{}", i),
+                                items.iter().map(|s| s.to_string()).collect(),
+                                Utc::now(),
+                            )
+                        })
+                        .collect(),
+                )
+                .unwrap();
+            miner.mine().unwrap()
+        };
+        let has_bag = |items: &ItemSet| items.iter().any(|item| item == "Bag");
+
+        let uncapped = mine(None);
+        let bag_consequents = uncapped
+            .rules
+            .iter()
+            .filter(|rule| has_bag(&rule.consequent))
+            .count();
+        assert!(bag_consequents > 0);
+        assert_eq!(uncapped.stats.base_rate_suppressed, 0);
+
+        let capped = mine(Some(0.5));
+        assert!(!capped.rules.iter().any(|rule| has_bag(&rule.consequent)));
+        assert!(capped.rules.iter().any(|rule| has_bag(&rule.antecedent)));
+        assert_eq!(capped.stats.base_rate_suppressed, bag_consequents);
+        assert_eq!(capped.rules.len(), uncapped.rules.len() - bag_consequents);
+
+        assert!(MiningConfig::builder()
+            .max_consequent_base_rate(40.0)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_base_rate_cap_matches_across_paths() {
+        // Bag is in 6 of 8 baskets
+        let baskets: [&[&str]; 8] = [
+            &["Laptop", "Bag"],
+            &["Laptop", "Mouse", "Bag"],
+            &["Phone", "Case", "Bag"],
+            &["Phone", "Case", "Bag"],
+            &["Phone", "Case"],
+            &["Laptop", "Mouse"],
+            &["Charger", "Bag"],
+            &["Mouse", "Bag"],
+        ];
+        let transactions: Vec<Transaction> = baskets
+            .iter()
+            .enumerate()
+            .map(|(i, items)| {
+                Transaction::new(
+                    format!("tx{}", i),
+                    items.iter().map(|s| s.to_string()).collect(),
+                    Utc::now(),
+                )
+            })
+            .collect();
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.25).unwrap())
+            .min_confidence(Confidence::fraction(0.3).unwrap())
+            .min_lift(Lift::new(0.0).unwrap())
+            .max_itemset_size(2)
+            .max_consequent_base_rate(0.5)
+            .build()
+            .unwrap();
+        let keys = |rules: &[AssociationRule]| {
+            let mut keys: Vec<_> = rules
+                .iter()
+                .map(|rule| (rule.antecedent.clone(), rule.consequent.clone()))
+                .collect();
+            keys.sort();
+            keys
+        };
+
+        let mut miner = RuleMiner::new(config.clone());
+        miner.add_transactions(transactions.clone()).unwrap();
+        let result = miner.mine_with_support_table(1000).unwrap();
+        assert!(miner.stats().base_rate_suppressed > 0);
+        assert!(!result.rules.is_empty());
+        assert!(!result
+            .rules
+            .iter()
+            .any(|rule| rule.consequent.contains(&"Bag".to_string())));
+
+        let replayed = result.rules_at(0.3, 0.0, 0.25).unwrap();
+        assert_eq!(keys(&replayed), keys(&result.rules));
+
+        let two_pass =
+            two_pass::TwoPassMiner::mine(|| transactions.iter().cloned().map(Ok), &config).unwrap();
+        assert_eq!(keys(&two_pass), keys(&result.rules));
+    }
+
+    #[test]
+    fn test_streaming_rules() {
+        let key = |rule: &AssociationRule| (rule.antecedent.clone(), rule.consequent.clone());
+
+        // Without post-hoc filters every streamed rule is kept
+        let mut miner = progress_miner(MiningAlgorithm::FPGrowth);
+        miner.config.dedup_bidirectional = false;
+        let mut streamed = Vec::new();
+        let outcome = miner
+            .mine_association_rules_streaming(|rule| streamed.push(key(&rule)))
+            .unwrap();
+        assert!(!outcome.rules.is_empty());
+        assert_eq!(streamed.len(), outcome.rules.len());
+        let mut kept: Vec<_> = outcome.rules.iter().map(key).collect();
+        streamed.sort();
+        kept.sort();
+        assert_eq!(streamed, kept);
+
+        // Bidirectional dedup only applies to the returned set
+        let miner = progress_miner(MiningAlgorithm::FPGrowth);
+        let mut streamed = Vec::new();
+        let outcome = miner
+            .mine_association_rules_streaming(|rule| streamed.push(key(&rule)))
+            .unwrap();
+        assert!(streamed.len() > outcome.rules.len());
+        assert!(outcome
+            .rules
+            .iter()
+            .all(|rule| streamed.contains(&key(rule))));
+        assert_eq!(
+            format!("{:?}", outcome.rules),
+            format!("{:?}", miner.mine().unwrap().rules)
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_mine_keeps_stats() {
+        let mut miner = metrics_miner(MetricSet::CORE);
+        let rules = miner.mine_association_rules().unwrap();
+        assert_eq!(miner.stats().rules_generated, rules.len());
+        assert_eq!(miner.stats().transactions_processed, 4);
+    }
+
+    fn progress_miner(algorithm: MiningAlgorithm) -> RuleMiner {
+        // 500 baskets over 10 items
+        let transactions: Vec<Transaction> = (0..500)
+            .map(|tx| {
+                let items = (0..10)
+                    .filter(|item| (tx + item) % 3 != 0)
+                    .map(|item| format!("item{}", item))
+                    .collect();
+                Transaction::new(format!("tx{}", tx), items, Utc::now())
+            })
+            .collect();
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.3).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .algorithm(algorithm)
+            .max_itemset_size(3)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        miner
+    }
+
+    #[test]
+    fn test_progress_callback_fires() {
+        for (algorithm, expected) in [
+            (
+                MiningAlgorithm::Apriori,
+                vec![
+                    ProgressPhase::SupportCounting { level: 1 },
+                    ProgressPhase::CandidateGeneration { level: 2 },
+                    ProgressPhase::SupportCounting { level: 2 },
+                    ProgressPhase::CandidateGeneration { level: 3 },
+                    ProgressPhase::SupportCounting { level: 3 },
+                    // Level 4 is joined, then cut by max_itemset_size
+                    ProgressPhase::CandidateGeneration { level: 4 },
+                    ProgressPhase::RuleGeneration,
+                ],
+            ),
+            (
+                MiningAlgorithm::FPGrowth,
+                vec![
+                    ProgressPhase::TreeBuilding,
+                    ProgressPhase::PatternGrowth,
+                    ProgressPhase::RuleGeneration,
+                ],
+            ),
+        ] {
+            let mut reports = Vec::new();
+            let rules = progress_miner(algorithm)
+                .mine_association_rules_with_progress(|progress| {
+                    reports.push(progress);
+                    ControlFlow::Continue(())
+                })
+                .unwrap();
+            assert_eq!(
+                rules.len(),
+                progress_miner(algorithm).mine().unwrap().rules.len()
+            );
+
+            let mut phases: Vec<ProgressPhase> = reports.iter().map(|r| r.phase).collect();
+            phases.dedup();
+            assert_eq!(phases, expected, "{:?}", algorithm);
+            // Every phase runs to completion, never past it
+            assert!(reports.iter().all(|r| r.processed <= r.total));
+            for phase in expected {
+                let last = reports.iter().rev().find(|r| r.phase == phase).unwrap();
+                assert_eq!(last.processed, last.total, "{:?}", phase);
+            }
+        }
+    }
+
+    #[test]
+    fn test_progress_cancellation_stops_early() {
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let mut miner = progress_miner(algorithm);
+            let mut reports = Vec::new();
+            let result = miner.mine_association_rules_with_progress(|progress| {
+                reports.push(progress);
+                if progress.fraction() >= 0.5 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+
+            match result {
+                Err(MiningError::MiningFailed(message)) => {
+                    assert_eq!(message, progress::CANCELLED)
+                }
+                other => panic!("expected cancellation, got {:?}", other.map(|r| r.len())),
+            }
+            // Stopped halfway through the first phase
+            let last = reports.last().unwrap();
+            assert!(last.processed < last.total, "{:?}", last);
+            assert!(reports.iter().all(|r| r.phase == reports[0].phase));
+            assert_eq!(miner.stats().rules_generated, 0);
+        }
+    }
+
+    #[test]
+    fn test_max_duration_truncates() {
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let unbounded = progress_miner(algorithm).mine().unwrap();
+            assert!(!unbounded.rules.is_empty());
+            assert!(!unbounded.stats.truncated);
+
+            let mut miner = progress_miner(algorithm);
+            miner.config.max_duration = Some(Duration::ZERO);
+            let outcome = miner.mine().unwrap();
+
+            assert!(outcome.stats.truncated, "{:?}", algorithm);
+            assert!(
+                outcome.rules.len() < unbounded.rules.len(),
+                "{:?}",
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_frequent_itemsets_truncates() {
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let all = progress_miner(algorithm).mine_frequent_itemsets().unwrap();
+
+            // Ten frequent items, then two of the pairs
+            let mut miner = progress_miner(algorithm);
+            miner.config.max_frequent_itemsets = Some(12);
+            let itemsets = miner.mine_frequent_itemsets().unwrap();
+            assert_eq!(itemsets.len(), 12, "{:?}", algorithm);
+            assert!(itemsets
+                .iter()
+                .all(|itemset| all.iter().any(|kept| kept.items == itemset.items)));
+            assert!(miner.stats().truncated);
+
+            // Rules come from the kept itemsets only
+            let outcome = miner.mine().unwrap();
+            assert!(outcome.stats.truncated);
+            assert!(outcome.rules.iter().all(|rule| {
+                let mut items = rule.antecedent.clone();
+                items.extend(rule.consequent.iter().cloned());
+                items.sort();
+                itemsets.iter().any(|itemset| itemset.items == items)
+            }));
+
+            // Exactly at the limit nothing is cut
+            let mut miner = progress_miner(algorithm);
+            miner.config.max_frequent_itemsets = Some(all.len());
+            assert_eq!(miner.mine_frequent_itemsets().unwrap().len(), all.len());
+            assert!(!miner.stats().truncated);
+        }
+    }
+
+    #[test]
+    fn test_singleton_support_guardrail() {
+        // 0.001 of 500 baskets is a single occurrence
+        let transactions: Vec<Transaction> = (0..500)
+            .map(|i| {
+                let items = if i == 0 {
+                    vec!["Rare".to_string(), "Odd".to_string()]
+                } else {
+                    vec!["Bread".to_string()]
+                };
+                Transaction::new(format!("tx{}", i), items, Utc::now())
+            })
+            .collect();
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.001).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .build()
+            .unwrap();
+
+        let mut miner = RuleMiner::new(config.clone());
+        miner.add_transactions(transactions.clone()).unwrap();
+        match miner.mine_frequent_itemsets() {
+            Err(MiningError::InvalidConfig(message)) => {
+                assert!(
+                    message.contains("500 transactions is 1 occurrence"),
+                    "{}",
+                    message
+                );
+                assert!(message.contains("0.0040"), "{}", message);
+            }
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        }
+        // The effective count is reported even for the refused run
+        assert_eq!(miner.stats().min_support_count, 1);
+
+        // Overridden: only a warning, and the one-off pair becomes rules
+        let mut miner = RuleMiner::new(
+            MiningConfigBuilder::from(config)
+                .allow_singleton_support(true)
+                .build()
+                .unwrap(),
+        );
+        miner.add_transactions(transactions).unwrap();
+        let outcome = miner.mine().unwrap();
+        assert!(outcome
+            .rules
+            .iter()
+            .any(|rule| rule.antecedent.contains(&"Rare".to_string())
+                || rule.consequent.contains(&"Rare".to_string())));
+        assert_eq!(outcome.stats.min_support_count, 1);
+    }
+
+    #[test]
+    fn test_support_exactly_at_threshold() {
+        // 0.07 * 100 is 7.000000000000001 in f64; X and Y co-occur in exactly 7
+        let transactions: Vec<Transaction> = (0..100)
+            .map(|i| {
+                let items = match i {
+                    0..7 => vec!["X".to_string(), "Y".to_string()],
+                    7..14 => vec!["Z".to_string()],
+                    _ => vec!["Filler".to_string()],
+                };
+                Transaction::new(format!("tx{}", i), items, Utc::now())
+            })
+            .collect();
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.07).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(config.min_support_count(100), 7);
+
+        let pair = vec!["X".to_string(), "Y".to_string()];
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let mut miner = RuleMiner::new(
+                MiningConfigBuilder::from(config.clone())
+                    .algorithm(algorithm)
+                    .build()
+                    .unwrap(),
+            );
+            miner.add_transactions(transactions.clone()).unwrap();
+            let result = miner.mine_with_support_table(1_000).unwrap();
+
+            let table = result.support_table.as_ref().unwrap();
+            assert_eq!(table.count(&pair), Some(7), "{:?}", algorithm);
+            assert_eq!(table.count(&["Z".to_string()]), Some(7), "{:?}", algorithm);
+            let itemset = table.itemsets().iter().find(|i| i.items == pair).unwrap();
+            assert_eq!(itemset.count, 7);
+            assert_eq!(result.rules.len(), 1, "{:?}", algorithm);
+            assert_eq!(result.rules_at(0.5, 1.0, 0.07).unwrap().len(), 1);
+
+            // Nothing sits below the threshold
+            let long_tail = miner.stats().long_tail.as_ref().unwrap();
+            assert_eq!(long_tail.excluded_item_count, 0);
+        }
+
+        let pairs =
+            two_pass::TwoPassMiner::mine(|| transactions.clone().into_iter().map(Ok), &config)
+                .unwrap();
+        assert_eq!(pairs.len(), 1);
+    }
+
+    #[test]
+    fn test_support_universe() {
+        let row = |id: &str, items: &[&str], user: &str| {
+            let items = items.iter().map(|s| s.to_string()).collect();
+            Transaction::with_user(id, items, Utc::now(), user)
+        };
+        // Order o1 was exported one line per row
+        let transactions = vec![
+            row("o1", &["Milk"], "alice"),
+            row("o1", &["Bread"], "alice"),
+            row("o2", &["Eggs"], "alice"),
+            row("o3", &["Milk", "Bread"], "bob"),
+            row("o4", &["Milk"], "carol"),
+            row("o5", &["Eggs"], "carol"),
+        ];
+        let pair = vec!["Bread".to_string(), "Milk".to_string()];
+
+        for (universe, total, pair_count) in [
+            (SupportUniverse::Transactions, 6, 1),
+            (SupportUniverse::DistinctIds, 5, 2),
+            (SupportUniverse::DistinctUsers, 3, 2),
+        ] {
+            for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+                let config = MiningConfig::builder()
+                    .min_support(Support::fraction(0.1).unwrap())
+                    .min_confidence(Confidence::fraction(0.1).unwrap())
+                    .min_lift(Lift::new(0.0).unwrap())
+                    .algorithm(algorithm)
+                    .support_universe(universe)
+                    // Bread + Milk is a single row in the Transactions universe
+                    .allow_singleton_support(true)
+                    .build()
+                    .unwrap();
+                let mut miner = RuleMiner::new(config);
+                miner.add_transactions(transactions.clone()).unwrap();
+                assert_eq!(miner.transaction_count(), 6);
+
+                let result = miner.mine_with_support_table(1_000).unwrap();
+                let table = result.support_table.as_ref().unwrap();
+                let itemset = table.itemsets().iter().find(|i| i.items == pair);
+                assert_eq!(itemset.unwrap().count, pair_count, "{:?}", universe);
+                assert_eq!(itemset.unwrap().support, pair_count as f64 / total as f64);
+
+                // Every metric is measured in the same universe as the itemsets
+                assert!(!result.rules.is_empty());
+                for rule in &result.rules {
+                    let count = |items: &[String]| {
+                        let mut items = items.to_vec();
+                        items.sort();
+                        table.count(&items).unwrap() as f64
+                    };
+                    let both: Vec<String> = rule
+                        .antecedent
+                        .iter()
+                        .chain(&rule.consequent)
+                        .cloned()
+                        .collect();
+                    let expected = count(&both) / total as f64;
+                    assert!(
+                        (rule.metrics.support - expected).abs() < 1e-9,
+                        "{:?}",
+                        universe
+                    );
+                    let confidence = count(&both) / count(&rule.antecedent);
+                    assert!((rule.metrics.confidence - confidence).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_keeps_higher_confidence() {
+        let config = MiningConfig::default();
+        // Weaker directions arrive first, with items in a different order
+        let rules = vec![
+            test_rule(&["Milk"], &["Bread"], 0.6, 1.2),
+            test_rule(&["B", "A"], &["C"], 0.5, 1.5),
+            test_rule(&["Bread"], &["Milk"], 0.9, 1.2),
+            test_rule(&["C"], &["A", "B"], 0.5, 1.8),
+            test_rule(&["X"], &["Y"], 0.7, 1.1),
+        ];
+        let kept = filter_bidirectional_rules(rules.clone(), &config);
+        let names: Vec<String> = kept
+            .iter()
+            .map(|r| format!("{:?} => {:?}", r.antecedent, r.consequent))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                r#"["Bread"] => ["Milk"]"#,
+                r#"["C"] => ["A", "B"]"#,
+                r#"["X"] => ["Y"]"#,
+            ]
+        );
+
+        // The winner does not depend on input order
+        let mut reversed = rules.clone();
+        reversed.reverse();
+        let mut again = filter_bidirectional_rules(reversed, &config);
+        again.reverse();
+        assert_eq!(again.len(), kept.len());
+        for (a, b) in again.iter().zip(&kept) {
+            assert_eq!(a.antecedent, b.antecedent);
+        }
+
+        let config = MiningConfig::builder()
+            .dedup_bidirectional(false)
+            .build()
+            .unwrap();
+        assert_eq!(filter_bidirectional_rules(rules, &config).len(), 5);
+    }
+
+    #[test]
+    fn test_bidirectional_dedup_in_mining() {
+        // Cheese => Wine holds in 2 of 2, Wine => Cheese in 2 of 4
+        let transactions = vec![
+            Transaction::new(
+                "t1",
+                vec!["Wine".to_string(), "Cheese".to_string()],
+                Utc::now(),
+            ),
+            Transaction::new(
+                "t2",
+                vec!["Cheese".to_string(), "Wine".to_string()],
+                Utc::now(),
+            ),
+            Transaction::new("t3", vec!["Wine".to_string()], Utc::now()),
+            Transaction::new("t4", vec!["Wine".to_string()], Utc::now()),
+        ];
+        for (dedup, expected) in [(true, 1), (false, 2)] {
+            let config = MiningConfig::builder()
+                .min_support(Support::fraction(0.5).unwrap())
+                .min_confidence(Confidence::fraction(0.5).unwrap())
+                .dedup_bidirectional(dedup)
+                .build()
+                .unwrap();
+            let mut miner = RuleMiner::new(config);
+            miner.add_transactions(transactions.clone()).unwrap();
+            let result = miner.mine_with_support_table(1_000).unwrap();
+            assert_eq!(result.rules.len(), expected);
+            assert_eq!(result.rules[0].antecedent, ["Cheese"]);
+            assert_eq!(result.rules_at(0.5, 1.0, 0.5).unwrap().len(), expected);
+        }
+    }
+
+    #[test]
+    fn test_prune_redundant_rules() {
+        let rules = vec![
+            test_rule(&["Laptop", "Mouse"], &["Hub"], 0.8, 1.0),
+            test_rule(&["Laptop"], &["Hub"], 0.8, 1.0),
+            test_rule(&["Pad", "Mouse", "Laptop"], &["Hub"], 0.7, 1.0),
+            test_rule(&["Laptop", "Cable"], &["Hub"], 0.9, 1.0),
+            test_rule(&["Cable", "Laptop", "Mouse"], &["Hub"], 0.85, 1.0),
+            test_rule(&["Mouse"], &["Hub"], 0.5, 1.0),
+            test_rule(&["Laptop", "Mouse"], &["Pad"], 0.6, 1.0),
+            test_rule(&["Laptop", "Mouse"], &["Hub", "Pad"], 0.6, 1.0),
+            test_rule(&["Laptop"], &["Pad", "Hub"], 0.7, 1.0),
+        ];
+        let kept: Vec<String> = RuleMiner::prune_redundant_rules(rules)
+            .iter()
+            .map(|r| format!("{} => {}", r.antecedent.join(","), r.consequent.join(",")))
+            .collect();
+        assert_eq!(
+            kept,
+            [
+                // More confident than {Laptop} => {Hub}
+                "Laptop => Hub",
+                "Laptop,Cable => Hub",
+                "Mouse => Hub",
+                "Laptop,Mouse => Pad",
+                "Laptop => Pad,Hub",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prune_redundant_in_mining() {
+        let basket = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        let transactions = vec![
+            Transaction::new("t1", basket(&["Laptop", "Mouse", "Hub"]), Utc::now()),
+            Transaction::new("t2", basket(&["Laptop", "Mouse", "Hub"]), Utc::now()),
+            Transaction::new("t3", basket(&["Laptop", "Hub"]), Utc::now()),
+            Transaction::new("t4", basket(&["Mouse"]), Utc::now()),
+        ];
+        let mine = |prune| {
+            let config = MiningConfig::builder()
+                .min_support(Support::fraction(0.25).unwrap())
+                .min_confidence(Confidence::fraction(0.5).unwrap())
+                .min_lift(Lift::new(0.0).unwrap())
+                .prune_redundant(prune)
+                // Keep Laptop => Hub, which ties with Hub => Laptop
+                .dedup_bidirectional(false)
+                .allow_singleton_support(true)
+                .build()
+                .unwrap();
+            let mut miner = RuleMiner::new(config);
+            miner.add_transactions(transactions.clone()).unwrap();
+            let result = miner.mine_with_support_table(1_000).unwrap();
+            (result, miner.stats().redundant_rules_pruned)
+        };
+
+        let (all, pruned) = mine(false);
+        assert_eq!(pruned, 0);
+        let (kept, pruned) = mine(true);
+        assert!(pruned > 0);
+        assert_eq!(kept.rules.len() + pruned, all.rules.len());
+        // {Laptop} => {Hub} holds always, so {Laptop, Mouse} => {Hub} adds nothing
+        let has = |rules: &[AssociationRule], antecedent: &[&str]| {
+            rules
+                .iter()
+                .any(|r| r.antecedent == antecedent && r.consequent == ["Hub"])
+        };
+        assert!(has(&all.rules, &["Laptop", "Mouse"]));
+        assert!(!has(&kept.rules, &["Laptop", "Mouse"]));
+        assert!(has(&kept.rules, &["Laptop"]));
+        assert_eq!(
+            kept.rules_at(0.5, 0.0, 0.25).unwrap().len(),
+            kept.rules.len()
+        );
     }
 }
@@ -0,0 +1,175 @@
+//! Negative association rules (A => NOT B)
+//!
+//! For every frequent antecedent A and frequent item B outside it, the rule
+//! A => ¬B is scored by treating "B is absent" as the consequent, so the
+//! usual confidence and lift thresholds apply unchanged.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::config::MiningConfig;
+use crate::transaction::Transaction;
+use crate::types::{FrequentItemset, NegativeRule};
+
+use super::metrics_from_counts;
+
+/// Generate negative rules from frequent itemsets, best first
+pub(crate) fn generate_negative_rules(
+    transactions: &[Transaction],
+    itemsets: &[FrequentItemset],
+    config: &MiningConfig,
+) -> Vec<NegativeRule> {
+    let total = transactions.len();
+    let item_counts = count_items(transactions.iter());
+    let candidates: Vec<(&str, usize)> = itemsets
+        .iter()
+        .filter(|itemset| itemset.items.len() == 1)
+        .map(|itemset| {
+            let item = itemset.items[0].as_str();
+            (item, item_counts.get(item).copied().unwrap_or(0))
+        })
+        .collect();
+
+    let mut rules = Vec::new();
+    for antecedent in itemsets {
+        if !config.allows_split(antecedent.items.len(), 1) {
+            continue;
+        }
+        let supporting: Vec<&Transaction> = transactions
+            .iter()
+            .filter(|tx| tx.contains_all(&antecedent.items))
+            .collect();
+        let co_counts = count_items(supporting.iter().copied());
+
+        for &(item, item_count) in &candidates {
+            let absent = [item.to_string()];
+            if antecedent.items.iter().any(|i| i == item)
+                || !config.constraints.allows_rule(&antecedent.items, &absent)
+            {
+                continue;
+            }
+            let both = co_counts.get(item).copied().unwrap_or(0);
+            // Consequent "B is absent": P(¬B) and P(A ∧ ¬B)
+            let metrics = metrics_from_counts(
+                supporting.len(),
+                total - item_count,
+                supporting.len() - both,
+                total as f64,
+                config.metric_set(),
+            );
+            if metrics.confidence >= config.min_confidence()
+                && metrics.lift >= config.min_lift()
+                && config.allows_metrics(&metrics)
+            {
+                rules.push(NegativeRule {
+                    antecedent: antecedent.items.clone(),
+                    absent: item.to_string(),
+                    metrics,
+                });
+            }
+        }
+    }
+
+    rules.sort_by(|a, b| {
+        b.metrics
+            .confidence
+            .total_cmp(&a.metrics.confidence)
+            .then_with(|| b.metrics.lift.total_cmp(&a.metrics.lift))
+            .then_with(|| a.antecedent.cmp(&b.antecedent))
+            .then_with(|| a.absent.cmp(&b.absent))
+    });
+    rules
+}
+
+/// Number of transactions containing each item
+fn count_items<'a>(transactions: impl Iterator<Item = &'a Transaction>) -> HashMap<&'a str, usize> {
+    let mut counts = HashMap::new();
+    for tx in transactions {
+        let distinct: HashSet<&str> = tx.items.iter().map(String::as_str).collect();
+        for item in distinct {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{Confidence, Lift, MiningAlgorithm, MiningConfig, Support};
+    use crate::mining::RuleMiner;
+    use crate::transaction::Transaction;
+    use chrono::Utc;
+
+    /// Gamers and office buyers never overlap; Cable goes with everything
+    fn exclusive_baskets() -> Vec<Transaction> {
+        let baskets: [&[&str]; 10] = [
+            &["Gaming Console", "Controller", "Cable"],
+            &["Gaming Console", "Controller"],
+            &["Gaming Console", "Headset", "Cable"],
+            &["Gaming Console", "Controller", "Headset"],
+            &["Office Chair", "Desk", "Cable"],
+            &["Office Chair", "Desk"],
+            &["Office Chair", "Lamp", "Cable"],
+            &["Office Chair", "Desk", "Lamp"],
+            &["Cable", "Controller"],
+            &["Cable", "Desk"],
+        ];
+        baskets
+            .iter()
+            .enumerate()
+            .map(|(idx, items)| {
+                let items = items.iter().map(|s| s.to_string()).collect();
+                Transaction::new(format!("tx{}", idx), items, Utc::now())
+            })
+            .collect()
+    }
+
+    fn miner(algorithm: MiningAlgorithm) -> RuleMiner {
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.2).unwrap())
+            .min_confidence(Confidence::fraction(0.9).unwrap())
+            .min_lift(Lift::new(1.3).unwrap())
+            .algorithm(algorithm)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(exclusive_baskets()).unwrap();
+        miner
+    }
+
+    #[test]
+    fn test_mutual_exclusion() {
+        for algorithm in [MiningAlgorithm::Apriori, MiningAlgorithm::FPGrowth] {
+            let rules = miner(algorithm).mine_negative_rules().unwrap();
+            let find = |antecedent: &str, absent: &str| {
+                rules
+                    .iter()
+                    .find(|r| r.antecedent == [antecedent] && r.absent == absent)
+            };
+
+            let console = find("Gaming Console", "Office Chair").unwrap();
+            assert_eq!(console.metrics.confidence, 1.0);
+            assert_eq!(console.metrics.support, 0.4);
+            // P(¬Chair) = 0.6
+            assert!((console.metrics.lift - 1.0 / 0.6).abs() < 1e-9);
+            assert!(find("Office Chair", "Gaming Console").is_some());
+
+            // Cable co-occurs with both groups, so nothing excludes it
+            assert!(rules.iter().all(|r| r.absent != "Cable"));
+            // Never negate an item of the antecedent
+            assert!(rules.iter().all(|r| !r.antecedent.contains(&r.absent)));
+        }
+    }
+
+    #[test]
+    fn test_negative_rules_respect_constraints() {
+        let mut miner = miner(MiningAlgorithm::Apriori);
+        miner.config.constraints =
+            crate::config::RuleConstraints::new().with_excluded_items(["Office Chair"]);
+        let rules = miner.mine_negative_rules().unwrap();
+        assert!(!rules.is_empty());
+        assert!(rules
+            .iter()
+            .all(|r| r.absent != "Office Chair"
+                && !r.antecedent.contains(&"Office Chair".to_string())));
+    }
+}
@@ -0,0 +1,158 @@
+//! Progress reporting and cancellation for long mining runs
+//!
+//! `RuleMiner::mine_association_rules_with_progress` calls back from
+//! checkpoints inside Apriori, FP-Growth and rule generation. Each phase
+//! reports at its start, its end and whenever another percent of its work is
+//! done, so a run makes at most about a hundred calls per phase however large
+//! the dataset. Returning `ControlFlow::Break` from the callback stops the
+//! run at the next checkpoint.
+
+use std::ops::ControlFlow;
+
+use crate::errors::{MiningError, Result};
+
+/// Message of the `MiningError::MiningFailed` returned for a cancelled run
+pub const CANCELLED: &str = "cancelled";
+
+/// Where a mining run is, passed to progress callbacks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MiningProgress {
+    pub phase: ProgressPhase,
+    /// Units of `phase` done so far
+    pub processed: usize,
+    /// Units of `phase` in total
+    pub total: usize,
+}
+
+impl MiningProgress {
+    /// Share of the phase done (0.0 - 1.0; an empty phase is done)
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.processed as f64 / self.total as f64
+        }
+    }
+}
+
+/// Mining phase reported by `MiningProgress`, with what its units count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressPhase {
+    /// Apriori: joining frequent (k-1)-itemsets into level-k candidates
+    /// (units: frequent (k-1)-itemsets)
+    CandidateGeneration { level: usize },
+
+    /// Apriori: counting the support of level-k candidates (units: candidates)
+    SupportCounting { level: usize },
+
+    /// FP-Growth: inserting transactions into the FP-Tree (units: transactions)
+    TreeBuilding,
+
+    /// FP-Growth: mining conditional trees (units: frequent items)
+    PatternGrowth,
+
+    /// Splitting frequent itemsets into rules (units: itemsets)
+    RuleGeneration,
+}
+
+type Callback<'a> = &'a mut dyn FnMut(MiningProgress) -> ControlFlow<()>;
+
+/// Optional progress callback threaded through a mining run
+pub(crate) struct Progress<'a> {
+    callback: Option<Callback<'a>>,
+    /// Phase and percent of the last report
+    last: Option<(ProgressPhase, usize)>,
+}
+
+impl<'a> Progress<'a> {
+    /// No reporting; checkpoints are free
+    pub(crate) fn none() -> Self {
+        Self {
+            callback: None,
+            last: None,
+        }
+    }
+
+    pub(crate) fn new(callback: Callback<'a>) -> Self {
+        Self {
+            callback: Some(callback),
+            last: None,
+        }
+    }
+
+    /// Report `processed` of `total` units if another percent is done
+    ///
+    /// Fails with `MiningFailed("cancelled")` when the callback breaks.
+    pub(crate) fn checkpoint(
+        &mut self,
+        phase: ProgressPhase,
+        processed: usize,
+        total: usize,
+    ) -> Result<()> {
+        let Some(callback) = self.callback.as_mut() else {
+            return Ok(());
+        };
+        let percent = (processed * 100).checked_div(total).unwrap_or(100);
+        if self.last == Some((phase, percent)) {
+            return Ok(());
+        }
+        self.last = Some((phase, percent));
+
+        match callback(MiningProgress {
+            phase,
+            processed,
+            total,
+        }) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(()) => Err(MiningError::MiningFailed(CANCELLED.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoints_throttled_to_percent_steps() {
+        let mut reports = Vec::new();
+        let mut callback = |progress: MiningProgress| {
+            reports.push(progress.processed);
+            ControlFlow::Continue(())
+        };
+        let mut progress = Progress::new(&mut callback);
+        for processed in 0..=1_000 {
+            progress
+                .checkpoint(ProgressPhase::TreeBuilding, processed, 1_000)
+                .unwrap();
+        }
+        progress
+            .checkpoint(ProgressPhase::PatternGrowth, 0, 0)
+            .unwrap();
+
+        // Every tenth transaction is another percent, plus the empty phase
+        assert_eq!(reports.len(), 102);
+        assert_eq!(reports[..3], [0, 10, 20]);
+        assert_eq!(reports[100], 1_000);
+    }
+
+    #[test]
+    fn test_break_cancels() {
+        let mut callback = |progress: MiningProgress| {
+            if progress.fraction() >= 0.5 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+        let mut progress = Progress::new(&mut callback);
+        let phase = ProgressPhase::RuleGeneration;
+        assert!(progress.checkpoint(phase, 10, 100).is_ok());
+        match progress.checkpoint(phase, 50, 100) {
+            Err(MiningError::MiningFailed(message)) => assert_eq!(message, CANCELLED),
+            other => panic!("expected cancellation, got {:?}", other),
+        }
+
+        assert!(Progress::none().checkpoint(phase, 50, 100).is_ok());
+    }
+}
@@ -0,0 +1,386 @@
+//! In-memory registry of per-tenant miners
+//!
+//! `MinerRegistry` keeps one `RuleMiner` per tenant id behind its own lock, so
+//! loading or mining for one tenant never blocks another. The registry map
+//! itself is only write-locked when tenants are created or evicted.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::RuleMiner;
+use crate::config::MiningConfig;
+use crate::errors::{MiningError, Result};
+use crate::transaction::Transaction;
+use crate::types::AssociationRule;
+
+/// Memory accounting for a single tenant
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantUsage {
+    /// Transactions held by the tenant's miner
+    pub transaction_count: usize,
+    /// Total items across those transactions
+    pub item_count: usize,
+    /// Rules from the tenant's last mining run
+    pub rule_count: usize,
+}
+
+impl TenantUsage {
+    fn add(self, other: TenantUsage) -> TenantUsage {
+        TenantUsage {
+            transaction_count: self.transaction_count + other.transaction_count,
+            item_count: self.item_count + other.item_count,
+            rule_count: self.rule_count + other.rule_count,
+        }
+    }
+}
+
+struct TenantState {
+    miner: RuleMiner,
+    rules: Vec<AssociationRule>,
+    item_count: usize,
+}
+
+/// Miner and last mined rules for one tenant
+pub struct TenantMiner {
+    state: RwLock<TenantState>,
+    last_access: Mutex<Instant>,
+}
+
+impl TenantMiner {
+    fn new(config: MiningConfig) -> Self {
+        Self {
+            state: RwLock::new(TenantState {
+                miner: RuleMiner::new(config),
+                rules: Vec::new(),
+                item_count: 0,
+            }),
+            last_access: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Add transactions to this tenant's miner
+    pub fn add_transactions(&self, transactions: Vec<Transaction>) -> Result<()> {
+        self.touch();
+        let items: usize = transactions.iter().map(|tx| tx.items.len()).sum();
+
+        let mut state = self.write();
+        state.miner.add_transactions(transactions)?;
+        state.item_count += items;
+        Ok(())
+    }
+
+    /// Mine rules and keep them for later retrieval
+    pub fn mine(&self) -> Result<Vec<AssociationRule>> {
+        self.touch();
+
+        let mut state = self.write();
+        let rules = state.miner.mine()?.rules;
+        state.rules = rules.clone();
+        Ok(rules)
+    }
+
+    /// Rules from the last successful `mine()` call
+    pub fn rules(&self) -> Vec<AssociationRule> {
+        self.touch();
+        self.read().rules.clone()
+    }
+
+    /// Current memory accounting
+    pub fn usage(&self) -> TenantUsage {
+        let state = self.read();
+        TenantUsage {
+            transaction_count: state.miner.transaction_count(),
+            item_count: state.item_count,
+            rule_count: state.rules.len(),
+        }
+    }
+
+    /// Time since the tenant was last used
+    pub fn idle_for(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(*lock_or_recover(&self.last_access))
+    }
+
+    fn touch(&self) {
+        *lock_or_recover(&self.last_access) = Instant::now();
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, TenantState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, TenantState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Thread-safe registry of miners keyed by tenant id
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::mining::registry::MinerRegistry;
+/// use rust_rule_miner::{MiningConfig, Support, Transaction};
+/// use chrono::Utc;
+/// use std::time::Duration;
+///
+/// let registry = MinerRegistry::new().with_idle_timeout(Duration::from_secs(3600));
+/// let config = MiningConfig::builder()
+///     .min_support(Support::fraction(0.5)?)
+///     .build()?;
+/// registry.get_or_create("acme", config);
+///
+/// registry.add_transactions("acme", vec![
+///     Transaction::new("tx1", vec!["A".to_string(), "B".to_string()], Utc::now()),
+///     Transaction::new("tx2", vec!["A".to_string(), "B".to_string()], Utc::now()),
+///     Transaction::new("tx3", vec!["A".to_string(), "B".to_string()], Utc::now()),
+/// ])?;
+/// let rules = registry.mine("acme")?;
+/// assert_eq!(registry.rules("acme")?.len(), rules.len());
+///
+/// // Call periodically to drop tenants idle longer than the timeout
+/// registry.evict_idle();
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+#[derive(Default)]
+pub struct MinerRegistry {
+    tenants: RwLock<HashMap<String, Arc<TenantMiner>>>,
+    idle_timeout: Option<Duration>,
+}
+
+impl MinerRegistry {
+    /// Create an empty registry without idle eviction
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evict tenants idle for longer than `timeout` on `evict_idle()`
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Get a tenant's miner, creating it with `config` if it doesn't exist
+    ///
+    /// `config` is ignored for tenants that already exist.
+    pub fn get_or_create(&self, tenant: &str, config: MiningConfig) -> Arc<TenantMiner> {
+        if let Some(existing) = self.get(tenant) {
+            return existing;
+        }
+
+        let mut tenants = self.tenants.write().unwrap_or_else(|e| e.into_inner());
+        let miner = tenants
+            .entry(tenant.to_string())
+            .or_insert_with(|| Arc::new(TenantMiner::new(config)));
+        miner.touch();
+        Arc::clone(miner)
+    }
+
+    /// Get an existing tenant's miner
+    pub fn get(&self, tenant: &str) -> Option<Arc<TenantMiner>> {
+        let tenants = self.tenants.read().unwrap_or_else(|e| e.into_inner());
+        tenants.get(tenant).map(|miner| {
+            miner.touch();
+            Arc::clone(miner)
+        })
+    }
+
+    /// Add transactions to an existing tenant
+    pub fn add_transactions(&self, tenant: &str, transactions: Vec<Transaction>) -> Result<()> {
+        self.require(tenant)?.add_transactions(transactions)
+    }
+
+    /// Mine rules for an existing tenant
+    pub fn mine(&self, tenant: &str) -> Result<Vec<AssociationRule>> {
+        self.require(tenant)?.mine()
+    }
+
+    /// Rules from a tenant's last mining run
+    pub fn rules(&self, tenant: &str) -> Result<Vec<AssociationRule>> {
+        Ok(self.require(tenant)?.rules())
+    }
+
+    /// Memory accounting for one tenant
+    pub fn usage(&self, tenant: &str) -> Option<TenantUsage> {
+        let tenants = self.tenants.read().unwrap_or_else(|e| e.into_inner());
+        tenants.get(tenant).map(|miner| miner.usage())
+    }
+
+    /// Memory accounting summed over all tenants
+    pub fn total_usage(&self) -> TenantUsage {
+        let tenants = self.tenants.read().unwrap_or_else(|e| e.into_inner());
+        tenants
+            .values()
+            .map(|miner| miner.usage())
+            .fold(TenantUsage::default(), TenantUsage::add)
+    }
+
+    /// Remove a tenant, returning whether it existed
+    pub fn remove(&self, tenant: &str) -> bool {
+        let mut tenants = self.tenants.write().unwrap_or_else(|e| e.into_inner());
+        tenants.remove(tenant).is_some()
+    }
+
+    /// Drop tenants idle for longer than the idle timeout
+    ///
+    /// Returns the evicted tenant ids. Does nothing without a timeout.
+    pub fn evict_idle(&self) -> Vec<String> {
+        self.evict_idle_at(Instant::now())
+    }
+
+    fn evict_idle_at(&self, now: Instant) -> Vec<String> {
+        let Some(timeout) = self.idle_timeout else {
+            return Vec::new();
+        };
+
+        let mut tenants = self.tenants.write().unwrap_or_else(|e| e.into_inner());
+        let idle: Vec<String> = tenants
+            .iter()
+            .filter(|(_, miner)| miner.idle_for(now) > timeout)
+            .map(|(tenant, _)| tenant.clone())
+            .collect();
+
+        for tenant in &idle {
+            tenants.remove(tenant);
+            log::info!("Evicted idle tenant miner: {}", tenant);
+        }
+
+        idle
+    }
+
+    /// Registered tenant ids
+    pub fn tenants(&self) -> Vec<String> {
+        let tenants = self.tenants.read().unwrap_or_else(|e| e.into_inner());
+        tenants.keys().cloned().collect()
+    }
+
+    /// Number of registered tenants
+    pub fn len(&self) -> usize {
+        self.tenants.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Check if no tenants are registered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn require(&self, tenant: &str) -> Result<Arc<TenantMiner>> {
+        self.get(tenant)
+            .ok_or_else(|| MiningError::InvalidConfig(format!("Unknown tenant: {}", tenant)))
+    }
+}
+
+fn lock_or_recover<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::thread;
+
+    fn transactions(tenant: &str, n: usize) -> Vec<Transaction> {
+        (0..n)
+            .map(|i| {
+                Transaction::new(
+                    format!("{}-tx{}", tenant, i),
+                    vec!["Laptop".to_string(), "Mouse".to_string()],
+                    Utc::now(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_concurrent_tenants() {
+        let registry = Arc::new(MinerRegistry::new());
+
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let registry = Arc::clone(&registry);
+                thread::spawn(move || {
+                    let tenant = format!("tenant{}", t);
+                    // Small tenants: 5 transactions at 10% support is 1 occurrence
+                    let config = MiningConfig::builder()
+                        .allow_singleton_support(true)
+                        .build()
+                        .unwrap();
+                    registry.get_or_create(&tenant, config);
+                    for _ in 0..5 {
+                        registry
+                            .add_transactions(&tenant, transactions(&tenant, t + 1))
+                            .unwrap();
+                    }
+                    registry.mine(&tenant).unwrap();
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(registry.len(), 8);
+        for t in 0..8 {
+            let tenant = format!("tenant{}", t);
+            let usage = registry.usage(&tenant).unwrap();
+            assert_eq!(usage.transaction_count, 5 * (t + 1));
+            assert_eq!(usage.item_count, 10 * (t + 1));
+            assert_eq!(usage.rule_count, registry.rules(&tenant).unwrap().len());
+            assert!(usage.rule_count > 0);
+        }
+
+        let total = registry.total_usage();
+        assert_eq!(total.transaction_count, 5 * (1..=8).sum::<usize>());
+    }
+
+    #[test]
+    fn test_get_or_create_is_shared() {
+        let registry = MinerRegistry::new();
+        let a = registry.get_or_create("acme", MiningConfig::default());
+        let b = registry.get_or_create("acme", MiningConfig::default());
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_tenant() {
+        let registry = MinerRegistry::new();
+        assert!(matches!(
+            registry.mine("missing"),
+            Err(MiningError::InvalidConfig(_))
+        ));
+        assert!(registry.usage("missing").is_none());
+    }
+
+    #[test]
+    fn test_evicts_idle_tenants() {
+        let registry = MinerRegistry::new().with_idle_timeout(Duration::from_secs(60));
+        registry.get_or_create("old", MiningConfig::default());
+        registry.get_or_create("fresh", MiningConfig::default());
+
+        // Nothing is idle yet
+        assert!(registry.evict_idle().is_empty());
+
+        let later = Instant::now() + Duration::from_secs(120);
+        // "fresh" was used shortly before `later`
+        let fresh = registry.get("fresh").unwrap();
+        *lock_or_recover(&fresh.last_access) = later - Duration::from_secs(10);
+
+        let evicted = registry.evict_idle_at(later);
+        assert_eq!(evicted, vec!["old".to_string()]);
+        assert!(registry.get("old").is_none());
+        assert!(registry.get("fresh").is_some());
+    }
+
+    #[test]
+    fn test_no_eviction_without_timeout() {
+        let registry = MinerRegistry::new();
+        registry.get_or_create("acme", MiningConfig::default());
+        let later = Instant::now() + Duration::from_secs(3600);
+        assert!(registry.evict_idle_at(later).is_empty());
+        assert_eq!(registry.len(), 1);
+    }
+}
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+
+use crate::config::{MiningConfig, Support};
+use crate::errors::{MiningError, Result};
+use crate::scoring::SharedScorer;
+use crate::types::{AssociationRule, FrequentItemset, ItemSet};
+
+use super::stats::MiningStats;
+
+/// Rules and statistics of one `RuleMiner::mine` run
+#[derive(Debug, Clone)]
+pub struct MiningOutcome {
+    /// Filtered rules, best first
+    pub rules: Vec<AssociationRule>,
+    /// Statistics of the run that produced `rules`
+    pub stats: MiningStats,
+}
+
+/// Result of a mining run that can retain its frequent-itemset support table
+#[derive(Debug, Clone)]
+pub struct MiningResult {
+    /// Rules mined with the original config thresholds
+    pub rules: Vec<AssociationRule>,
+    /// Cached support counts (None if the table exceeded the size cap)
+    pub support_table: Option<SupportTable>,
+}
+
+impl MiningResult {
+    /// Regenerate rules at different thresholds from the cached support counts
+    ///
+    /// No transactions are scanned, so this is cheap enough for interactive
+    /// "what-if" exploration. `min_support` cannot go below the support floor
+    /// used for the original mining run, because itemsets under that floor
+    /// were never counted.
+    pub fn rules_at(
+        &self,
+        min_confidence: f64,
+        min_lift: f64,
+        min_support: f64,
+    ) -> Result<Vec<AssociationRule>> {
+        let table = self.support_table.as_ref().ok_or_else(|| {
+            MiningError::InsufficientData(
+                "Support table was not retained for this mining result".to_string(),
+            )
+        })?;
+
+        table.rules_at(min_confidence, min_lift, min_support)
+    }
+
+    /// Check whether the support table was retained
+    pub fn has_support_table(&self) -> bool {
+        self.support_table.is_some()
+    }
+}
+
+/// Frequent itemsets with their absolute support counts
+#[derive(Debug, Clone)]
+pub struct SupportTable {
+    /// Itemsets rules are generated from (after closed/maximal filtering)
+    itemsets: Vec<FrequentItemset>,
+    counts: HashMap<ItemSet, usize>,
+    total_transactions: usize,
+    config: MiningConfig,
+    scorer: SharedScorer,
+}
+
+impl SupportTable {
+    /// Build a support table from all mined frequent itemsets
+    pub fn new(
+        itemsets: Vec<FrequentItemset>,
+        total_transactions: usize,
+        config: &MiningConfig,
+    ) -> Self {
+        let counts = itemsets
+            .iter()
+            .map(|itemset| {
+                // Itemsets saved before counts were recorded only carry support
+                let count = match itemset.count {
+                    0 => (itemset.support * total_transactions as f64).round() as usize,
+                    count => count,
+                };
+                (canonical(&itemset.items), count)
+            })
+            .collect();
+
+        Self {
+            itemsets: super::closed::filter_itemsets(itemsets, config.itemset_kind),
+            counts,
+            total_transactions,
+            config: config.clone(),
+            scorer: SharedScorer::default(),
+        }
+    }
+
+    /// Rank regenerated rules with the miner's scorer
+    pub(crate) fn with_scorer(mut self, scorer: SharedScorer) -> Self {
+        self.scorer = scorer;
+        self
+    }
+
+    /// Number of cached itemsets
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Check if the table holds no itemsets
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Itemsets rules are generated from (after closed/maximal filtering)
+    pub fn itemsets(&self) -> &[FrequentItemset] {
+        &self.itemsets
+    }
+
+    /// Support floor the table was mined with
+    pub fn min_support(&self) -> f64 {
+        self.config.min_support()
+    }
+
+    /// Absolute support count of an itemset, if it was frequent
+    pub fn count(&self, items: &[String]) -> Option<usize> {
+        self.counts.get(&canonical(items)).copied()
+    }
+
+    /// Generate rules from cached counts (see `MiningResult::rules_at`)
+    pub fn rules_at(
+        &self,
+        min_confidence: f64,
+        min_lift: f64,
+        min_support: f64,
+    ) -> Result<Vec<AssociationRule>> {
+        if min_support < self.config.min_support() {
+            return Err(MiningError::InvalidConfig(format!(
+                "min_support {} is below the cached support floor {}",
+                min_support,
+                self.config.min_support()
+            )));
+        }
+
+        let total = self.total_transactions as f64;
+        let min_support_count = Support::fraction(min_support)?.min_count(self.total_transactions);
+        let mut rules = Vec::new();
+        let mut tested = 0;
+        let single_counts = self
+            .counts
+            .iter()
+            .filter(|(items, _)| items.len() == 1)
+            .map(|(items, count)| (items[0].as_str(), *count));
+        let mut base_rate =
+            super::BaseRateFilter::new(&self.config, single_counts, self.total_transactions);
+
+        for itemset in &self.itemsets {
+            if itemset.items.len() < 2 {
+                continue;
+            }
+
+            let Some(both_count) = self.count(&itemset.items) else {
+                continue;
+            };
+            if both_count < min_support_count {
+                continue;
+            }
+
+            for antecedent in super::generate_non_empty_subsets(&itemset.items) {
+                let consequent: ItemSet = itemset
+                    .items
+                    .iter()
+                    .filter(|item| !antecedent.contains(item))
+                    .cloned()
+                    .collect();
+
+                if !self.config.allows_split(antecedent.len(), consequent.len())
+                    || !self
+                        .config
+                        .constraints
+                        .allows_rule(&antecedent, &consequent)
+                {
+                    continue;
+                }
+
+                // Subsets of a frequent itemset are frequent, so both are cached
+                let (Some(antecedent_count), Some(consequent_count)) =
+                    (self.count(&antecedent), self.count(&consequent))
+                else {
+                    continue;
+                };
+
+                tested += 1;
+                let metrics = super::metrics_from_counts(
+                    antecedent_count,
+                    consequent_count,
+                    both_count,
+                    total,
+                    self.config.metric_set(),
+                );
+
+                if metrics.confidence >= min_confidence
+                    && metrics.lift >= min_lift
+                    && self.config.allows_metrics(&metrics)
+                    && !base_rate.suppresses(&consequent)
+                {
+                    rules.push(AssociationRule {
+                        antecedent,
+                        consequent,
+                        metrics,
+                    });
+                }
+            }
+        }
+        if base_rate.suppressed() > 0 {
+            log::info!(
+                "{} rules dropped by max_consequent_base_rate",
+                base_rate.suppressed()
+            );
+        }
+        let mut rules = super::significance::correct(rules, tested, &self.config);
+
+        // Same ordering, bidirectional filtering and pruning as RuleMiner
+        self.scorer.sort(&mut rules);
+
+        let rules = super::filter_bidirectional_rules(rules, &self.config);
+        if self.config.prune_redundant {
+            return Ok(super::RuleMiner::prune_redundant_rules(rules));
+        }
+        Ok(rules)
+    }
+}
+
+/// Sorted copy of an itemset used as lookup key
+fn canonical(items: &[String]) -> ItemSet {
+    let mut key = items.to_vec();
+    key.sort();
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Confidence, Lift, MiningConfig, RuleMiner, Support, Transaction};
+    use chrono::Utc;
+
+    fn create_miner() -> RuleMiner {
+        let baskets: [&[&str]; 8] = [
+            &["Laptop", "Mouse", "Keyboard"],
+            &["Laptop", "Mouse"],
+            &["Laptop", "Mouse", "Hub"],
+            &["Laptop", "Keyboard", "Hub"],
+            &["Mouse", "Keyboard"],
+            &["Phone", "Case"],
+            &["Phone", "Case", "Charger"],
+            &["Laptop", "Mouse", "Keyboard"],
+        ];
+
+        let transactions = baskets
+            .iter()
+            .enumerate()
+            .map(|(i, items)| {
+                Transaction::new(
+                    format!("tx{}", i),
+                    items.iter().map(|s| s.to_string()).collect(),
+                    Utc::now(),
+                )
+            })
+            .collect();
+
+        let mut miner = RuleMiner::new(
+            MiningConfig::builder()
+                .min_support(Support::fraction(0.2).unwrap())
+                .min_confidence(Confidence::fraction(0.3).unwrap())
+                .min_lift(Lift::new(0.0).unwrap())
+                .build()
+                .unwrap(),
+        );
+        miner.add_transactions(transactions).unwrap();
+        miner
+    }
+
+    #[test]
+    fn test_rules_at_original_thresholds_matches() {
+        let mut miner = create_miner();
+        let result = miner.mine_with_support_table(1000).unwrap();
+        assert!(result.has_support_table());
+
+        let replayed = result.rules_at(0.3, 0.0, 0.2).unwrap();
+
+        assert_eq!(replayed.len(), result.rules.len());
+        for (a, b) in replayed.iter().zip(&result.rules) {
+            assert_eq!(a.antecedent, b.antecedent);
+            assert_eq!(a.consequent, b.consequent);
+            assert_eq!(a.metrics.confidence, b.metrics.confidence);
+            assert_eq!(a.metrics.support, b.metrics.support);
+            assert_eq!(a.metrics.lift, b.metrics.lift);
+        }
+    }
+
+    #[test]
+    fn test_rules_at_tighter_thresholds_is_strict_subset() {
+        let mut miner = create_miner();
+        let result = miner.mine_with_support_table(1000).unwrap();
+
+        let strict = result.rules_at(0.7, 1.2, 0.25).unwrap();
+
+        assert!(strict.len() < result.rules.len());
+        for rule in &strict {
+            assert!(rule.metrics.confidence >= 0.7);
+            assert!(rule.metrics.lift >= 1.2);
+            assert!(rule.metrics.support >= 0.25);
+            assert!(result
+                .rules
+                .iter()
+                .any(|r| r.antecedent == rule.antecedent && r.consequent == rule.consequent));
+        }
+    }
+
+    #[test]
+    fn test_rules_at_rejects_support_below_floor() {
+        let mut miner = create_miner();
+        let result = miner.mine_with_support_table(1000).unwrap();
+
+        assert!(result.rules_at(0.3, 0.0, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_support_table_size_cap() {
+        let mut miner = create_miner();
+        let result = miner.mine_with_support_table(1).unwrap();
+
+        assert!(!result.has_support_table());
+        assert!(!result.rules.is_empty());
+        assert!(result.rules_at(0.3, 0.0, 0.2).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Statistical significance of rules
+//!
+//! A rule seen in 2 of 6 baskets can have a lift of 2 and still be well
+//! within what chance produces. Each rule's antecedent x consequent 2x2
+//! contingency table gets a Pearson chi-square statistic and its p-value
+//! (1 degree of freedom); `MiningConfig::max_p_value` filters on it,
+//! optionally corrected for the number of candidate rules tested.
+
+use crate::config::{MiningConfig, PValueCorrection};
+use crate::types::AssociationRule;
+
+/// Pearson chi-square statistic of a rule's 2x2 contingency table
+///
+/// Tables with an empty row or column (an item in every or no transaction)
+/// carry no evidence and score 0.
+pub fn chi_square(
+    antecedent_count: usize,
+    consequent_count: usize,
+    both_count: usize,
+    total: usize,
+) -> f64 {
+    let n = total as f64;
+    let a = antecedent_count as f64;
+    let b = consequent_count as f64;
+    let ab = both_count as f64;
+    let margins = a * (n - a) * b * (n - b);
+    if margins <= 0.0 {
+        return 0.0;
+    }
+    // n11 * n00 - n10 * n01, expanded over the marginal counts
+    let cross = ab * n - a * b;
+    n * cross * cross / margins
+}
+
+/// P-value of a chi-square statistic with 1 degree of freedom
+pub fn chi_square_p_value(chi_square: f64) -> f64 {
+    if chi_square <= 0.0 {
+        return 1.0;
+    }
+    erfc((chi_square / 2.0).sqrt()).clamp(0.0, 1.0)
+}
+
+/// Complementary error function (Numerical Recipes `erfcc`, relative error
+/// below 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * poly.exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+/// Apply `config.p_value_correction` to rules already within the raw
+/// `max_p_value`, given how many candidate rules were tested
+///
+/// Rules dropped earlier by other thresholds are not re-ranked, which only
+/// makes Benjamini-Hochberg more conservative.
+pub(crate) fn correct(
+    rules: Vec<AssociationRule>,
+    tested: usize,
+    config: &MiningConfig,
+) -> Vec<AssociationRule> {
+    let Some(alpha) = config.max_p_value else {
+        return rules;
+    };
+    let tested = tested.max(rules.len()).max(1) as f64;
+    let p_value = |rule: &AssociationRule| rule.metrics.p_value.unwrap_or(1.0);
+
+    let cutoff = match config.p_value_correction {
+        PValueCorrection::None => return rules,
+        PValueCorrection::Bonferroni => alpha / tested,
+        PValueCorrection::BenjaminiHochberg => {
+            let mut p_values: Vec<f64> = rules.iter().map(p_value).collect();
+            p_values.sort_by(f64::total_cmp);
+            // Largest rank k with p(k) <= k / m * alpha; everything up to it passes
+            p_values
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(rank, p)| **p <= (rank + 1) as f64 / tested * alpha)
+                .map_or(-1.0, |(_, p)| *p)
+        }
+    };
+    rules
+        .into_iter()
+        .filter(|rule| p_value(rule) <= cutoff)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Confidence, Lift, Support};
+    use crate::mining::RuleMiner;
+    use crate::transaction::Transaction;
+    use crate::types::PatternMetrics;
+    use chrono::Utc;
+
+    fn transactions(baskets: &[&[&str]]) -> Vec<Transaction> {
+        baskets
+            .iter()
+            .enumerate()
+            .map(|(idx, items)| {
+                Transaction::new(
+                    format!("tx{}", idx),
+                    items.iter().map(|s| s.to_string()).collect(),
+                    Utc::now(),
+                )
+            })
+            .collect()
+    }
+
+    fn config() -> crate::config::MiningConfigBuilder {
+        MiningConfig::builder()
+            .min_support(Support::fraction(0.3).unwrap())
+            .min_confidence(Confidence::fraction(0.9).unwrap())
+            .min_lift(Lift::new(1.5).unwrap())
+    }
+
+    #[test]
+    fn test_chi_square_p_value() {
+        // 2x2 table [[2, 0], [1, 3]]
+        assert!((chi_square(2, 3, 2, 6) - 3.0).abs() < 1e-12);
+        // Critical values for 1 degree of freedom
+        assert!((chi_square_p_value(3.841_458_8) - 0.05).abs() < 1e-6);
+        assert!((chi_square_p_value(6.634_896_6) - 0.01).abs() < 1e-6);
+        assert_eq!(chi_square_p_value(0.0), 1.0);
+        // Independence and degenerate margins
+        assert_eq!(chi_square(2, 5, 1, 10), 0.0);
+        assert_eq!(chi_square(10, 4, 4, 10), 0.0);
+    }
+
+    #[test]
+    fn test_lift_without_significance() {
+        // A => B has confidence 1.0 and lift 2.0, but chi-square 3.0 (p ~ 0.083)
+        let data = transactions(&[&["A", "B"], &["A", "B"], &["B"], &["C"], &["C"], &["C"]]);
+        let mine = |config: MiningConfig| {
+            let mut miner = RuleMiner::new(config);
+            miner.add_transactions(data.clone()).unwrap();
+            miner.mine().unwrap().rules
+        };
+
+        let rules = mine(config().build().unwrap());
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].metrics.lift, 2.0);
+        assert_eq!(rules[0].metrics.p_value, None);
+
+        assert!(mine(config().max_p_value(0.05).build().unwrap()).is_empty());
+        let lenient = mine(config().max_p_value(0.1).build().unwrap());
+        let p_value = lenient[0].metrics.p_value.unwrap();
+        assert!((p_value - 0.0833).abs() < 1e-3);
+        assert_eq!(lenient[0].metrics.chi_square, Some(3.0));
+    }
+
+    #[test]
+    fn test_corrections() {
+        let rule = |p: f64| AssociationRule {
+            antecedent: vec!["A".to_string()],
+            consequent: vec![format!("{}", p)],
+            metrics: PatternMetrics {
+                p_value: Some(p),
+                ..Default::default()
+            },
+        };
+        let rules: Vec<AssociationRule> = [0.001, 0.008, 0.012, 0.04].map(rule).to_vec();
+        let kept = |correction, tested| {
+            let config = config()
+                .max_p_value(0.05)
+                .p_value_correction(correction)
+                .build()
+                .unwrap();
+            correct(rules.clone(), tested, &config).len()
+        };
+
+        assert_eq!(kept(PValueCorrection::None, 4), 4);
+        // 0.05 / 4 = 0.0125
+        assert_eq!(kept(PValueCorrection::Bonferroni, 4), 3);
+        assert_eq!(kept(PValueCorrection::Bonferroni, 10), 1);
+        // Step-up: 0.04 <= 4/4 * 0.05 keeps everything
+        assert_eq!(kept(PValueCorrection::BenjaminiHochberg, 4), 4);
+        // m = 10: thresholds 0.005, 0.01, 0.015, 0.02
+        assert_eq!(kept(PValueCorrection::BenjaminiHochberg, 10), 3);
+    }
+}
@@ -1,15 +1,428 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::config::{MiningAlgorithm, Support};
+use crate::transaction::Transaction;
+
+/// Number of near-miss items kept in `MiningStats::long_tail`
+pub const DEFAULT_NEAR_MISSES: usize = 10;
+
 /// Mining statistics
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct MiningStats {
     pub frequent_itemsets_count: usize,
     pub rules_generated: usize,
     pub transactions_processed: usize,
+
+    /// Items excluded by min_support (populated by the last mining run)
+    #[serde(default)]
+    pub long_tail: Option<LongTailReport>,
+
+    /// Algorithm attempted and used by the last mining run
+    #[serde(default)]
+    pub algorithm: Option<AlgorithmReport>,
+
+    /// Time spent in each phase of the last mining run
+    #[serde(default)]
+    pub phases: PhaseTimings,
+
+    /// Rules dropped by `MiningConfig::prune_redundant` in the last run
+    #[serde(default)]
+    pub redundant_rules_pruned: usize,
+
+    /// Rules dropped by `MiningConfig::max_consequent_base_rate` in the last
+    /// run, though they passed the other thresholds
+    #[serde(default)]
+    pub base_rate_suppressed: usize,
+
+    /// Loaded transactions the config's exclusions left out of the last run
+    #[serde(default)]
+    pub excluded_transactions: usize,
+
+    /// Exclusions the last run applied, recorded so it can be reproduced
+    /// (None = nothing excluded)
+    #[serde(default)]
+    pub exclusions: Option<ExclusionManifest>,
+
+    /// Apriori only: candidate itemsets counted per level, index 0 being
+    /// the 1-itemsets
+    #[serde(default)]
+    pub apriori_candidates: Vec<usize>,
+
+    /// Occurrences an itemset needed to be frequent in the last run
+    #[serde(default)]
+    pub min_support_count: usize,
+
+    /// Whether `MiningConfig::max_duration` or `max_frequent_itemsets` cut
+    /// the last run short, so its itemsets and rules are partial
+    #[serde(default)]
+    pub truncated: bool,
+
+    /// Whether `RuleMiner::mine` returned a cached result instead of mining;
+    /// the other fields then describe the run that produced it
+    #[serde(default)]
+    pub cached: bool,
 }
 
 impl MiningStats {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Time spent finding frequent itemsets in the last run
+    pub fn itemset_mining_duration(&self) -> Duration {
+        self.phases.itemsets
+    }
+
+    /// Time spent splitting itemsets into rules in the last run
+    pub fn rule_generation_duration(&self) -> Duration {
+        self.phases.rule_generation
+    }
+}
+
+/// Transaction ids and time ranges (start inclusive, end exclusive) a
+/// mining run left out
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExclusionManifest {
+    pub transaction_ids: Vec<String>,
+    #[serde(with = "crate::config::time_ranges")]
+    pub time_ranges: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+/// Which algorithm a mining run attempted and which one produced the result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlgorithmReport {
+    /// Algorithm from the config
+    pub attempted: MiningAlgorithm,
+    /// Algorithm whose itemsets were used
+    pub used: MiningAlgorithm,
+    /// Error of the attempted algorithm, when the run fell back to Apriori
+    pub fallback_reason: Option<String>,
+}
+
+impl AlgorithmReport {
+    /// Whether the run switched algorithms
+    pub fn fell_back(&self) -> bool {
+        self.fallback_reason.is_some()
+    }
+}
+
+/// Wall-clock time per mining phase
+///
+/// Always recorded (a few `Instant::now` calls per run), so a slow run can
+/// be narrowed down to a phase without sharing the data. With the `tracing`
+/// feature each phase is also entered as a span of the same name, which
+/// shows up as a frame in flamegraphs built from the trace.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTimings {
+    /// Counting single-item frequencies (Apriori level 1, FP-Growth's first pass)
+    pub item_counting: Duration,
+
+    /// Finding frequent itemsets, item counting included
+    pub itemsets: Duration,
+
+    /// Apriori only: time per level, index 0 being the 1-itemsets
+    pub itemset_levels: Vec<Duration>,
+
+    /// Splitting itemsets into rules and computing their metrics
+    pub rule_generation: Duration,
+
+    /// Ranking rules by quality score
+    pub sorting: Duration,
+
+    /// Dropping the weaker direction of A => B / B => A pairs and, with
+    /// `MiningConfig::prune_redundant`, redundant rules
+    pub filtering: Duration,
+}
+
+/// Mining phase measured by a `PhaseTimer`
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Phase {
+    ItemCounting,
+    Itemsets,
+    // Level number only feeds the span
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    AprioriLevel(usize),
+    RuleGeneration,
+    Sorting,
+    Filtering,
+}
+
+/// Times a phase, entering its tracing span while alive
+pub(crate) struct PhaseTimer {
+    start: Instant,
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl PhaseTimer {
+    pub(crate) fn start(phase: Phase) -> Self {
+        #[cfg(feature = "tracing")]
+        let span = match phase {
+            Phase::ItemCounting => tracing::info_span!("item_counting"),
+            Phase::Itemsets => tracing::info_span!("itemsets"),
+            Phase::AprioriLevel(level) => tracing::info_span!("apriori_level", level),
+            Phase::RuleGeneration => tracing::info_span!("rule_generation"),
+            Phase::Sorting => tracing::info_span!("sorting"),
+            Phase::Filtering => tracing::info_span!("filtering"),
+        };
+        #[cfg(not(feature = "tracing"))]
+        let _ = phase;
+
+        Self {
+            start: Instant::now(),
+            #[cfg(feature = "tracing")]
+            _span: span.entered(),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Time so far, leaving the span
+    pub(crate) fn stop(self) -> Duration {
+        self.elapsed()
+    }
+
+    /// Time so far, then start timing `phase` (leaving this span first)
+    pub(crate) fn restart(&mut self, phase: Phase) -> Duration {
+        let elapsed = self.elapsed();
+        #[cfg(feature = "tracing")]
+        {
+            self._span = tracing::Span::none().entered();
+        }
+        *self = Self::start(phase);
+        elapsed
+    }
+}
+
+/// Diagnostics on single items that fell below min_support
+///
+/// Helps decide whether lowering min_support is worth the extra cost: a small
+/// `excluded_occurrence_fraction` means little data is being ignored, while
+/// near-misses just under the threshold show what a lower support would add.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LongTailReport {
+    /// Distinct items below min_support
+    pub excluded_item_count: usize,
+
+    /// Share of all item occurrences belonging to excluded items (0.0 - 1.0)
+    pub excluded_occurrence_fraction: f64,
+
+    /// Excluded items closest to the threshold, with their supports (descending)
+    pub near_misses: Vec<(String, f64)>,
+}
+
+impl LongTailReport {
+    /// Build the report from single-item counts
+    ///
+    /// Each item counts at most once per transaction, matching support.
+    pub fn from_transactions(transactions: &[Transaction], min_support: f64, top_n: usize) -> Self {
+        let item_counts = item_counts(transactions);
+        let total = transactions.len() as f64;
+        let total_occurrences: usize = item_counts.values().sum();
+        if total_occurrences == 0 {
+            return Self::default();
+        }
+
+        // Same integer threshold the miners apply
+        let min_count = Support::fraction(min_support)
+            .map_or(0, |support| support.min_count(transactions.len()));
+        let mut excluded: Vec<(String, f64)> = item_counts
+            .iter()
+            .filter(|(_, count)| **count < min_count)
+            .map(|(item, count)| (item.to_string(), *count as f64 / total))
+            .collect();
+
+        let excluded_occurrences: usize = item_counts
+            .values()
+            .filter(|count| **count < min_count)
+            .sum();
+
+        excluded.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        let excluded_item_count = excluded.len();
+        excluded.truncate(top_n);
+
+        Self {
+            excluded_item_count,
+            excluded_occurrence_fraction: excluded_occurrences as f64 / total_occurrences as f64,
+            near_misses: excluded,
+        }
+    }
+}
+
+/// Transactions containing each item, counting an item once per transaction
+pub(crate) fn item_counts(transactions: &[Transaction]) -> HashMap<&str, usize> {
+    let mut item_counts: HashMap<&str, usize> = HashMap::new();
+    for tx in transactions {
+        let unique: HashSet<&str> = tx.items.iter().map(String::as_str).collect();
+        for item in unique {
+            *item_counts.entry(item).or_insert(0) += 1;
+        }
+    }
+    item_counts
+}
+
+/// Each item with its transaction count and support, most frequent first
+/// (ties by item)
+pub fn item_frequencies(transactions: &[Transaction]) -> Vec<(String, usize, f64)> {
+    let total = transactions.len() as f64;
+    let mut frequencies: Vec<(String, usize, f64)> = item_counts(transactions)
+        .into_iter()
+        .map(|(item, count)| (item.to_string(), count, count as f64 / total))
+        .collect();
+    frequencies.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    frequencies
+}
+
+/// Shape of a dataset, for picking `min_support` before mining
+///
+/// An item in a handful of transactions needs a support below
+/// `count / transaction_count` to appear in any rule; `top_items` shows the
+/// supports the common items reach.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatasetProfile {
+    pub transaction_count: usize,
+    pub unique_item_count: usize,
+
+    /// Items per transaction (0 for an empty dataset)
+    pub min_items: usize,
+    pub avg_items: f64,
+    pub max_items: usize,
+
+    /// Earliest and latest transaction timestamps
+    pub time_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+
+    /// Most frequent items with their transaction counts and supports, as in
+    /// `item_frequencies`
+    pub top_items: Vec<(String, usize, f64)>,
+}
+
+impl DatasetProfile {
+    pub fn from_transactions(transactions: &[Transaction], top_n: usize) -> Self {
+        let mut top_items = item_frequencies(transactions);
+        let unique_item_count = top_items.len();
+        top_items.truncate(top_n);
+
+        let sizes = transactions.iter().map(|tx| tx.items.len());
+        let total_items: usize = sizes.clone().sum();
+        let timestamps = transactions.iter().map(|tx| tx.timestamp);
+
+        Self {
+            transaction_count: transactions.len(),
+            unique_item_count,
+            min_items: sizes.clone().min().unwrap_or(0),
+            avg_items: if transactions.is_empty() {
+                0.0
+            } else {
+                total_items as f64 / transactions.len() as f64
+            },
+            max_items: sizes.max().unwrap_or(0),
+            time_range: timestamps.clone().min().zip(timestamps.max()),
+            top_items,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    /// 100 transactions; item `i` (1..=20) appears in round(100 / i) of them
+    fn zipf_transactions() -> (Vec<Transaction>, Vec<usize>) {
+        let counts: Vec<usize> = (1..=20)
+            .map(|i| (100.0 / i as f64).round() as usize)
+            .collect();
+
+        let transactions = (0..100)
+            .map(|tx| {
+                let items = counts
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, count)| tx < **count)
+                    .map(|(i, _)| format!("item{:02}", i + 1))
+                    .collect();
+                Transaction::new(format!("tx{}", tx), items, Utc::now())
+            })
+            .collect();
+
+        (transactions, counts)
+    }
+
+    #[test]
+    fn test_zipf_tail_mass() {
+        let (transactions, counts) = zipf_transactions();
+
+        // Items 11..=20 have fewer than 10 occurrences
+        let report = LongTailReport::from_transactions(&transactions, 0.1, 3);
+
+        let tail: usize = counts[10..].iter().sum();
+        let all: usize = counts.iter().sum();
+        assert_eq!(report.excluded_item_count, 10);
+        assert!((report.excluded_occurrence_fraction - tail as f64 / all as f64).abs() < 1e-12);
+
+        assert_eq!(
+            report.near_misses,
+            vec![
+                ("item11".to_string(), 0.09),
+                ("item12".to_string(), 0.08),
+                ("item13".to_string(), 0.08),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nothing_excluded() {
+        let (transactions, _) = zipf_transactions();
+        let report = LongTailReport::from_transactions(&transactions, 0.01, 5);
+
+        assert_eq!(report.excluded_item_count, 0);
+        assert_eq!(report.excluded_occurrence_fraction, 0.0);
+        assert!(report.near_misses.is_empty());
+    }
+
+    #[test]
+    fn test_dataset_profile() {
+        let (transactions, counts) = zipf_transactions();
+        let frequencies = item_frequencies(&transactions);
+        assert_eq!(frequencies.len(), 20);
+        assert_eq!(frequencies[0], ("item01".to_string(), 100, 1.0));
+        assert_eq!(frequencies[1], ("item02".to_string(), 50, 0.5));
+        // item19 and item20 both appear 5 times
+        assert_eq!(frequencies[18].0, "item19");
+
+        let profile = DatasetProfile::from_transactions(&transactions, 3);
+        assert_eq!(profile.transaction_count, 100);
+        assert_eq!(profile.unique_item_count, 20);
+        assert_eq!(profile.min_items, 1);
+        assert_eq!(profile.max_items, 20);
+        let occurrences: usize = counts.iter().sum();
+        assert_eq!(profile.avg_items, occurrences as f64 / 100.0);
+        assert_eq!(profile.top_items, frequencies[..3]);
+
+        // A repeated item counts once; the range spans the timestamps
+        let start = Utc::now();
+        let end = start + chrono::Duration::days(2);
+        let profile = DatasetProfile::from_transactions(
+            &[
+                Transaction::new("t1", vec!["A".to_string(), "A".to_string()], end),
+                Transaction::new("t2", vec!["B".to_string()], start),
+            ],
+            10,
+        );
+        assert_eq!(profile.top_items[0], ("A".to_string(), 1, 0.5));
+        assert_eq!(profile.time_range, Some((start, end)));
+
+        let empty = DatasetProfile::from_transactions(&[], 10);
+        assert_eq!(empty, DatasetProfile::default());
+    }
 }
@@ -0,0 +1,383 @@
+//! Constant-memory two-pass mining of pairwise rules
+//!
+//! For the common "A => B" case there is no need to hold every transaction in
+//! memory. `TwoPassMiner` reads the data twice:
+//!
+//! 1. **Pass one** counts single-item frequencies (memory bounded by the number
+//!    of unique items).
+//! 2. **Pass two** counts only pairs where both items are frequent (memory
+//!    bounded by the number of frequent-item pairs).
+//!
+//! The resulting rules are exact: they match what `RuleMiner` produces for
+//! 1-item => 1-item rules on the same data and config. Excluded transactions
+//! are skipped in both passes, and `min_weighted_support` applies with every
+//! item weighing 1, as for a `RuleMiner` without item weights. Merging rows
+//! into baskets by `support_universe` needs every row at once, so any
+//! universe but `Transactions` is rejected with `InvalidConfig`.
+
+use std::collections::HashMap;
+
+use crate::config::{MiningConfig, SupportUniverse};
+use crate::errors::{MiningError, Result};
+use crate::transaction::Transaction;
+use crate::types::AssociationRule;
+
+/// Two-pass exact miner for pairwise association rules
+pub struct TwoPassMiner;
+
+impl TwoPassMiner {
+    /// Mine pairwise rules from a re-openable transaction stream
+    ///
+    /// `stream_factory` is called once per pass and must yield the same
+    /// transactions each time (e.g. by re-opening the source file).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::data_loader::TransactionStream;
+    /// use rust_rule_miner::mining::two_pass::TwoPassMiner;
+    /// use rust_rule_miner::{MiningConfig, Support, Transaction};
+    /// use chrono::Utc;
+    ///
+    /// let transactions = vec![
+    ///     Transaction::new("tx1", vec!["Laptop".to_string(), "Mouse".to_string()], Utc::now()),
+    ///     Transaction::new("tx2", vec!["Laptop".to_string(), "Mouse".to_string()], Utc::now()),
+    ///     Transaction::new("tx3", vec!["Phone".to_string()], Utc::now()),
+    /// ];
+    ///
+    /// let config = MiningConfig::builder()
+    ///     .min_support(Support::fraction(0.5)?)
+    ///     .build()?;
+    /// let rules = TwoPassMiner::mine(
+    ///     || Box::new(transactions.clone().into_iter().map(Ok)) as TransactionStream,
+    ///     &config,
+    /// )?;
+    /// assert!(!rules.is_empty());
+    /// # Ok::<(), rust_rule_miner::MiningError>(())
+    /// ```
+    pub fn mine<F, I>(stream_factory: F, config: &MiningConfig) -> Result<Vec<AssociationRule>>
+    where
+        F: Fn() -> I,
+        I: IntoIterator<Item = Result<Transaction>>,
+    {
+        config.validate()?;
+        if config.support_universe != SupportUniverse::Transactions {
+            return Err(MiningError::InvalidConfig(format!(
+                "TwoPassMiner counts every row as a basket; support_universe {:?} needs \
+                 RuleMiner",
+                config.support_universe
+            )));
+        }
+        // Pairs are the smallest itemsets that produce rules
+        if config.max_itemset_size.is_some_and(|max| max < 2) {
+            return Ok(Vec::new());
+        }
+
+        // Pass 1: count single items
+        let mut total_transactions = 0usize;
+        // Transactions with any item, the total weight at unit item weights
+        let mut weighted_total = 0usize;
+        let mut item_counts: HashMap<String, usize> = HashMap::new();
+
+        for tx in stream_factory() {
+            let tx = tx?;
+            if config.excludes(&tx) {
+                continue;
+            }
+            total_transactions += 1;
+            if !tx.items.is_empty() {
+                weighted_total += 1;
+            }
+            for item in
+                unique_items(&tx).filter(|item| !config.constraints.excluded_items.contains(item))
+            {
+                *item_counts.entry(item.clone()).or_insert(0) += 1;
+            }
+        }
+
+        if total_transactions == 0 {
+            return Err(MiningError::InsufficientData(
+                "No transactions to mine".to_string(),
+            ));
+        }
+
+        config.check_support_count(total_transactions)?;
+        let total = total_transactions as f64;
+        let min_support_count = config.min_support_count(total_transactions);
+
+        // Assign compact ids to frequent items only
+        let mut frequent_items: Vec<(String, usize)> = item_counts
+            .into_iter()
+            .filter(|(_, count)| *count >= min_support_count)
+            .collect();
+        frequent_items.sort();
+
+        let item_ids: HashMap<&str, u32> = frequent_items
+            .iter()
+            .enumerate()
+            .map(|(idx, (item, _))| (item.as_str(), idx as u32))
+            .collect();
+
+        // Pass 2: count pairs of frequent items
+        let mut pair_counts: HashMap<(u32, u32), usize> = HashMap::new();
+
+        for tx in stream_factory() {
+            let tx = tx?;
+            if config.excludes(&tx) {
+                continue;
+            }
+            let mut ids: Vec<u32> = unique_items(&tx)
+                .filter_map(|item| item_ids.get(item.as_str()).copied())
+                .collect();
+            ids.sort_unstable();
+
+            for i in 0..ids.len() {
+                for j in (i + 1)..ids.len() {
+                    *pair_counts.entry((ids[i], ids[j])).or_insert(0) += 1;
+                }
+            }
+        }
+
+        // Generate rules in both directions for each frequent pair
+        let mut rules = Vec::new();
+        let mut tested = 0;
+        let mut base_rate = super::BaseRateFilter::new(
+            config,
+            frequent_items
+                .iter()
+                .map(|(item, count)| (item.as_str(), *count)),
+            total_transactions,
+        );
+
+        for ((a, b), both_count) in pair_counts {
+            if both_count < min_support_count {
+                continue;
+            }
+            if config
+                .min_weighted_support
+                .is_some_and(|min| (both_count as f64 / weighted_total as f64) < min)
+            {
+                continue;
+            }
+
+            let (item_a, count_a) = &frequent_items[a as usize];
+            let (item_b, count_b) = &frequent_items[b as usize];
+
+            for (antecedent, antecedent_count, consequent, consequent_count) in [
+                (item_a, *count_a, item_b, *count_b),
+                (item_b, *count_b, item_a, *count_a),
+            ] {
+                if !config.constraints.allows_rule(
+                    std::slice::from_ref(antecedent),
+                    std::slice::from_ref(consequent),
+                ) {
+                    continue;
+                }
+                tested += 1;
+                let metrics = super::metrics_from_counts(
+                    antecedent_count,
+                    consequent_count,
+                    both_count,
+                    total,
+                    config.metric_set(),
+                );
+
+                if metrics.confidence >= config.min_confidence()
+                    && metrics.lift >= config.min_lift()
+                    && config.allows_metrics(&metrics)
+                    && !base_rate.suppresses(std::slice::from_ref(consequent))
+                {
+                    rules.push(AssociationRule {
+                        antecedent: vec![antecedent.clone()],
+                        consequent: vec![consequent.clone()],
+                        metrics,
+                    });
+                }
+            }
+        }
+        if base_rate.suppressed() > 0 {
+            log::info!(
+                "{} rules dropped by max_consequent_base_rate",
+                base_rate.suppressed()
+            );
+        }
+        let mut rules = super::significance::correct(rules, tested, config);
+
+        // Same ordering and bidirectional filtering as RuleMiner
+        rules.sort_by(|a, b| {
+            b.quality_score()
+                .partial_cmp(&a.quality_score())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.antecedent.cmp(&b.antecedent))
+        });
+
+        Ok(super::filter_bidirectional_rules(rules, config))
+    }
+}
+
+/// Items of a transaction with duplicates removed
+fn unique_items(tx: &Transaction) -> impl Iterator<Item = &String> {
+    let mut seen = std::collections::HashSet::new();
+    tx.items
+        .iter()
+        .filter(move |item| seen.insert(item.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Confidence, Lift, MiningConfigBuilder, Support};
+    use crate::mining::RuleMiner;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn fixture() -> Vec<Transaction> {
+        let baskets: [&[&str]; 7] = [
+            &["Laptop", "Mouse", "Keyboard"],
+            &["Laptop", "Mouse"],
+            &["Laptop", "Mouse", "Hub"],
+            &["Laptop", "Keyboard"],
+            &["Phone", "Case"],
+            &["Phone", "Case", "Charger"],
+            &["Phone", "Charger"],
+        ];
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        baskets
+            .iter()
+            .enumerate()
+            .map(|(i, items)| {
+                Transaction::new(
+                    format!("tx{}", i),
+                    items.iter().map(|s| s.to_string()).collect(),
+                    start + Duration::days(i as i64),
+                )
+            })
+            .collect()
+    }
+
+    fn builder() -> MiningConfigBuilder {
+        MiningConfig::builder()
+            .min_support(Support::fraction(0.2).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .min_lift(Lift::new(1.0).unwrap())
+    }
+
+    fn two_pass(
+        transactions: &[Transaction],
+        config: &MiningConfig,
+    ) -> Result<Vec<AssociationRule>> {
+        TwoPassMiner::mine(|| transactions.iter().cloned().map(Ok), config)
+    }
+
+    /// `RuleMiner`'s 1-item => 1-item rules
+    fn in_memory_pairs(
+        transactions: &[Transaction],
+        config: &MiningConfig,
+    ) -> Result<Vec<AssociationRule>> {
+        let mut miner = RuleMiner::new(config.clone());
+        miner.add_transactions(transactions.to_vec())?;
+        Ok(miner
+            .mine()?
+            .rules
+            .into_iter()
+            .filter(|r| r.antecedent.len() == 1 && r.consequent.len() == 1)
+            .collect())
+    }
+
+    fn assert_same_rules(two_pass: &[AssociationRule], in_memory: &[AssociationRule]) {
+        assert_eq!(two_pass.len(), in_memory.len());
+        for rule in in_memory {
+            let other = two_pass
+                .iter()
+                .find(|r| r.antecedent == rule.antecedent && r.consequent == rule.consequent)
+                .expect("rule missing from two-pass output");
+            assert!((other.metrics.confidence - rule.metrics.confidence).abs() < 1e-12);
+            assert!((other.metrics.support - rule.metrics.support).abs() < 1e-12);
+            assert!((other.metrics.lift - rule.metrics.lift).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_two_pass_matches_in_memory_pairs() {
+        let transactions = fixture();
+        let start = transactions[0].timestamp;
+        let configs = [
+            builder().build().unwrap(),
+            // tx1 and the first two phone baskets left out, four remain
+            builder()
+                .min_support(Support::fraction(0.5).unwrap())
+                .exclude_transaction_ids(["tx1"])
+                .exclude_time_range(start + Duration::days(4), start + Duration::days(6))
+                .build()
+                .unwrap(),
+            // Only pairs in 3 of 7 baskets: Laptop and Mouse
+            builder()
+                .min_weighted_support(Support::fraction(0.4).unwrap())
+                .build()
+                .unwrap(),
+        ];
+
+        for config in &configs {
+            let two_pass = two_pass(&transactions, config).unwrap();
+            let in_memory = in_memory_pairs(&transactions, config).unwrap();
+            assert!(!two_pass.is_empty());
+            assert_same_rules(&two_pass, &in_memory);
+        }
+        let excluded = two_pass(&transactions, &configs[1]).unwrap();
+        assert!(excluded.iter().all(|r| r.antecedent != ["Case"]));
+        let weighted = two_pass(&transactions, &configs[2]).unwrap();
+        assert!(weighted.iter().all(|r| {
+            let mut pair = [r.antecedent[0].as_str(), r.consequent[0].as_str()];
+            pair.sort();
+            pair == ["Laptop", "Mouse"]
+        }));
+    }
+
+    #[test]
+    fn test_two_pass_config_guardrails() {
+        let transactions = fixture();
+
+        // One basket of seven meets 10% support: both refuse, or both warn
+        let singleton = builder().min_support(Support::fraction(0.1).unwrap());
+        let config = singleton.clone().build().unwrap();
+        assert!(matches!(
+            two_pass(&transactions, &config),
+            Err(MiningError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            in_memory_pairs(&transactions, &config),
+            Err(MiningError::InvalidConfig(_))
+        ));
+        let config = singleton.allow_singleton_support(true).build().unwrap();
+        assert_same_rules(
+            &two_pass(&transactions, &config).unwrap(),
+            &in_memory_pairs(&transactions, &config).unwrap(),
+        );
+
+        let config = builder()
+            .support_universe(SupportUniverse::DistinctUsers)
+            .build()
+            .unwrap();
+        let error = two_pass(&transactions, &config).unwrap_err();
+        assert!(matches!(error, MiningError::InvalidConfig(_)));
+        assert!(error.to_string().contains("DistinctUsers"));
+    }
+
+    #[test]
+    fn test_two_pass_propagates_stream_errors() {
+        let result = TwoPassMiner::mine(
+            || {
+                vec![Err(MiningError::DataLoadError("bad row".to_string()))]
+                    as Vec<Result<Transaction>>
+            },
+            &MiningConfig::default(),
+        );
+
+        assert!(matches!(result, Err(MiningError::DataLoadError(_))));
+    }
+
+    #[test]
+    fn test_two_pass_empty_stream() {
+        let result = TwoPassMiner::mine(Vec::<Result<Transaction>>::new, &MiningConfig::default());
+        assert!(matches!(result, Err(MiningError::InsufficientData(_))));
+    }
+}
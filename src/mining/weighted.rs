@@ -0,0 +1,110 @@
+//! Weighted support
+//!
+//! With item weights (e.g. prices), an itemset's weighted support is the
+//! sum, over the transactions containing it, of its lightest item's weight,
+//! divided by the total weight of the data: the sum of each transaction's
+//! heaviest item. A basket pairing a laptop with a dock then counts for far
+//! more than one pairing two cables.
+//!
+//! Adding items never raises either the count or the lightest weight, so
+//! weighted support is anti-monotone like classic support and can prune
+//! itemsets after mining.
+
+use std::collections::HashMap;
+
+use crate::transaction::Transaction;
+use crate::types::FrequentItemset;
+
+/// Weight of items missing from the weight map
+pub const DEFAULT_ITEM_WEIGHT: f64 = 1.0;
+
+/// Item weights plus the total weight of a transaction set
+pub(crate) struct WeightTable<'a> {
+    weights: Option<&'a HashMap<String, f64>>,
+    total: f64,
+}
+
+impl<'a> WeightTable<'a> {
+    /// `None` weights every item at `DEFAULT_ITEM_WEIGHT`
+    pub(crate) fn new(
+        weights: Option<&'a HashMap<String, f64>>,
+        transactions: &[Transaction],
+    ) -> Self {
+        let mut table = Self {
+            weights,
+            total: 0.0,
+        };
+        table.total = transactions
+            .iter()
+            .map(|tx| {
+                tx.items
+                    .iter()
+                    .map(|item| table.weight(item))
+                    .fold(0.0, f64::max)
+            })
+            .sum();
+        table
+    }
+
+    fn weight(&self, item: &str) -> f64 {
+        self.weights
+            .and_then(|weights| weights.get(item).copied())
+            .unwrap_or(DEFAULT_ITEM_WEIGHT)
+    }
+
+    /// Weighted support of `items`, contained in `count` transactions
+    pub(crate) fn weighted_support(&self, items: &[String], count: f64) -> f64 {
+        if self.total <= 0.0 {
+            return 0.0;
+        }
+        let lightest = items
+            .iter()
+            .map(|item| self.weight(item))
+            .fold(f64::INFINITY, f64::min);
+        if lightest.is_finite() {
+            lightest * count / self.total
+        } else {
+            0.0
+        }
+    }
+
+    /// Keep itemsets whose weighted support reaches `min_weighted_support`
+    pub(crate) fn retain(&self, itemsets: &mut Vec<FrequentItemset>, min_weighted_support: f64) {
+        itemsets.retain(|itemset| {
+            self.weighted_support(&itemset.items, itemset.count as f64) >= min_weighted_support
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn items(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_weighted_support() {
+        let transactions = vec![
+            Transaction::new("t1", items(&["Laptop", "Cable"]), Utc::now()),
+            Transaction::new("t2", items(&["Cable"]), Utc::now()),
+            Transaction::new("t3", items(&["Pen"]), Utc::now()),
+        ];
+        let weights = HashMap::from([("Laptop".to_string(), 8.0), ("Cable".to_string(), 0.5)]);
+        // 8 + 0.5 + 1 (Pen is unweighted)
+        let table = WeightTable::new(Some(&weights), &transactions);
+
+        assert_eq!(table.weighted_support(&items(&["Laptop"]), 1.0), 8.0 / 9.5);
+        assert_eq!(table.weighted_support(&items(&["Cable"]), 2.0), 1.0 / 9.5);
+        assert_eq!(
+            table.weighted_support(&items(&["Laptop", "Cable"]), 1.0),
+            0.5 / 9.5
+        );
+
+        // Without weights it is classic support
+        let plain = WeightTable::new(None, &transactions);
+        assert_eq!(plain.weighted_support(&items(&["Cable"]), 2.0), 2.0 / 3.0);
+    }
+}
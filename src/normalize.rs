@@ -0,0 +1,405 @@
+//! Item name normalization
+//!
+//! Exports from different systems spell the same product differently: "Cà
+//! phê" typed on macOS arrives decomposed (NFD) while the POS sends it
+//! composed (NFC), Japanese and Chinese keyboards produce full-width Latin
+//! ("ＵＳＢ"), and casing drifts between catalogs. Byte-wise these are
+//! different items, which splits their support. `ItemNormalizer` maps such
+//! variants to one spelling; use the same normalizer for loading, in-memory
+//! data and basket queries so rules and baskets agree.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::transaction::Transaction;
+
+/// Unicode normalization form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UnicodeForm {
+    /// Keep code points as they are
+    #[default]
+    None,
+
+    /// Canonical composition: NFD and NFC spellings become one
+    Nfc,
+
+    /// Compatibility composition: also folds full-width forms, ligatures
+    /// and other compatibility variants (e.g. "ﬁ" -> "fi", "①" -> "1")
+    Nfkc,
+}
+
+/// Case folding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CaseFold {
+    /// Keep case
+    #[default]
+    None,
+
+    /// Locale-independent Unicode lowercasing
+    Unicode,
+
+    /// Turkish/Azerbaijani: "I" -> "ı" and "İ" -> "i", otherwise `Unicode`
+    Turkic,
+}
+
+/// How item names are normalized
+///
+/// The default changes nothing.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::normalize::{CaseFold, ItemNormalizer, UnicodeForm};
+///
+/// let normalizer = ItemNormalizer::default()
+///     .with_form(UnicodeForm::Nfc)
+///     .with_case_fold(CaseFold::Unicode)
+///     .with_width_folding(true);
+///
+/// // NFD input, composed output
+/// assert_eq!(normalizer.normalize("Ca\u{300} phe\u{302}"), "cà phê");
+/// assert_eq!(normalizer.normalize("ＵＳＢ Hub"), "usb hub");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemNormalizer {
+    #[serde(default)]
+    pub form: UnicodeForm,
+    #[serde(default)]
+    pub case_fold: CaseFold,
+    /// Map full-width ASCII (U+FF01 - U+FF5E) and the ideographic space to
+    /// their ASCII forms (implied by `UnicodeForm::Nfkc`)
+    #[serde(default)]
+    pub fold_width: bool,
+    /// Strip surrounding whitespace
+    #[serde(default)]
+    pub trim: bool,
+}
+
+impl ItemNormalizer {
+    /// NFC plus trimming: merges encodings of the same text, keeps case
+    pub fn standard() -> Self {
+        Self {
+            form: UnicodeForm::Nfc,
+            trim: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_form(mut self, form: UnicodeForm) -> Self {
+        self.form = form;
+        self
+    }
+
+    pub fn with_case_fold(mut self, case_fold: CaseFold) -> Self {
+        self.case_fold = case_fold;
+        self
+    }
+
+    pub fn with_width_folding(mut self, fold_width: bool) -> Self {
+        self.fold_width = fold_width;
+        self
+    }
+
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Whether `normalize` returns every item unchanged
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Normalize one item name
+    pub fn normalize(&self, item: &str) -> String {
+        let mut text: String = if self.fold_width {
+            item.chars().map(fold_width).collect()
+        } else {
+            item.to_string()
+        };
+        // Compose before case folding so "I" + U+0307 is seen as "İ"
+        text = self.compose(&text);
+        text = match self.case_fold {
+            CaseFold::None => text,
+            CaseFold::Unicode => text.to_lowercase(),
+            CaseFold::Turkic => text
+                .chars()
+                .flat_map(|c| match c {
+                    'I' => vec!['ı'],
+                    'İ' => vec!['i'],
+                    c => c.to_lowercase().collect(),
+                })
+                .collect(),
+        };
+        if self.case_fold != CaseFold::None {
+            // Lowercasing can leave decomposed sequences behind
+            text = self.compose(&text);
+        }
+        if self.trim {
+            text.trim().to_string()
+        } else {
+            text
+        }
+    }
+
+    fn compose(&self, text: &str) -> String {
+        match self.form {
+            UnicodeForm::None => text.to_string(),
+            UnicodeForm::Nfc => text.nfc().collect(),
+            UnicodeForm::Nfkc => text.nfkc().collect(),
+        }
+    }
+
+    /// Normalize items, dropping empty results and items that merge into an
+    /// earlier one (first occurrence order is kept)
+    pub fn normalize_items(&self, items: &[String]) -> Vec<String> {
+        if self.is_identity() {
+            return items.to_vec();
+        }
+        let mut seen = HashSet::new();
+        items
+            .iter()
+            .map(|item| self.normalize(item))
+            .filter(|item| !item.is_empty() && seen.insert(item.clone()))
+            .collect()
+    }
+
+    /// Normalize the items of transactions built in memory
+    pub fn apply(&self, transactions: &mut [Transaction]) {
+        if self.is_identity() {
+            return;
+        }
+        for tx in transactions {
+            tx.items = self.normalize_items(&tx.items);
+        }
+    }
+}
+
+/// How `RuleMiner` cleans transactions as they are added
+///
+/// The default changes nothing. Set with `RuleMiner::set_preprocessing`.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::normalize::Preprocessing;
+/// use rust_rule_miner::Transaction;
+/// use chrono::Utc;
+///
+/// let mut tx = Transaction::new(
+///     "tx1",
+///     vec!["laptop ".to_string(), "Laptop".to_string(), "Mouse".to_string()],
+///     Utc::now(),
+/// );
+/// Preprocessing::clean().prepare(&mut tx);
+/// assert_eq!(tx.items, ["laptop", "mouse"]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Preprocessing {
+    /// Drop a transaction whose id and items repeat one already loaded
+    pub dedup_transactions: bool,
+    /// Keep only the first occurrence of an item within a transaction
+    pub dedup_items_within_transaction: bool,
+    /// Item normalization: trimming, case folding, Unicode forms
+    pub normalizer: ItemNormalizer,
+    /// Applied to each item after `normalizer`, e.g. to strip a SKU prefix
+    pub custom_normalizer: Option<fn(&str) -> String>,
+}
+
+impl Preprocessing {
+    /// Drop duplicate transactions and items, trim and lowercase items
+    pub fn clean() -> Self {
+        Self {
+            dedup_transactions: true,
+            dedup_items_within_transaction: true,
+            normalizer: ItemNormalizer::default()
+                .with_trim(true)
+                .with_case_fold(CaseFold::Unicode),
+            custom_normalizer: None,
+        }
+    }
+
+    pub fn with_dedup_transactions(mut self, dedup: bool) -> Self {
+        self.dedup_transactions = dedup;
+        self
+    }
+
+    pub fn with_dedup_items(mut self, dedup: bool) -> Self {
+        self.dedup_items_within_transaction = dedup;
+        self
+    }
+
+    pub fn with_normalizer(mut self, normalizer: ItemNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    pub fn with_custom_normalizer(mut self, normalize: fn(&str) -> String) -> Self {
+        self.custom_normalizer = Some(normalize);
+        self
+    }
+
+    /// Normalize and de-duplicate the items of `transaction`
+    ///
+    /// Items that normalize to an empty string are dropped.
+    /// `dedup_transactions` needs the other transactions and is applied by
+    /// `RuleMiner`.
+    pub fn prepare(&self, transaction: &mut Transaction) {
+        if !self.normalizer.is_identity() || self.custom_normalizer.is_some() {
+            transaction.items = transaction
+                .items
+                .iter()
+                .map(|item| {
+                    let item = self.normalizer.normalize(item);
+                    match self.custom_normalizer {
+                        Some(normalize) => normalize(&item),
+                        None => item,
+                    }
+                })
+                .filter(|item| !item.is_empty())
+                .collect();
+        }
+        if self.dedup_items_within_transaction {
+            let mut seen = HashSet::new();
+            transaction.items.retain(|item| seen.insert(item.clone()));
+        }
+    }
+}
+
+/// Full-width ASCII variant -> ASCII
+fn fold_width(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        '\u{3000}' => ' ',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Confidence, MiningConfig, Support};
+    use crate::mining::RuleMiner;
+    use chrono::Utc;
+
+    fn items(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_nfc_nfd_and_width_merge() {
+        let nfc = "C\u{E0} ph\u{EA}";
+        let nfd = "Ca\u{300} phe\u{302}";
+        assert_ne!(nfc, nfd);
+
+        let normalizer = ItemNormalizer::standard();
+        assert_eq!(normalizer.normalize(nfd), nfc);
+        assert_eq!(
+            normalizer.normalize_items(&items(&[nfc, nfd, " B\u{E1}nh m\u{EC} "])),
+            items(&[nfc, "B\u{E1}nh m\u{EC}"])
+        );
+
+        // Full-width Latin needs width folding or NFKC
+        assert_eq!(
+            normalizer.normalize("ＵＳＢ\u{3000}Hub"),
+            "ＵＳＢ\u{3000}Hub"
+        );
+        let width = normalizer.with_width_folding(true);
+        assert_eq!(width.normalize("ＵＳＢ\u{3000}Hub"), "USB Hub");
+        let nfkc = ItemNormalizer::default().with_form(UnicodeForm::Nfkc);
+        assert_eq!(nfkc.normalize("ＵＳＢ\u{3000}Hub"), "USB Hub");
+
+        assert!(ItemNormalizer::default().is_identity());
+        assert_eq!(ItemNormalizer::default().normalize(nfd), nfd);
+    }
+
+    #[test]
+    fn test_case_folding() {
+        let unicode = ItemNormalizer::standard().with_case_fold(CaseFold::Unicode);
+        assert_eq!(unicode.normalize("C\u{C0} PH\u{CA}"), "c\u{E0} ph\u{EA}");
+        assert_eq!(unicode.normalize("ISTANBUL"), "istanbul");
+
+        let turkic = ItemNormalizer::standard().with_case_fold(CaseFold::Turkic);
+        assert_eq!(turkic.normalize("ISPARTA"), "\u{131}sparta");
+        // Decomposed dotted capital I composes first
+        assert_eq!(turkic.normalize("I\u{307}ZM\u{130}R"), "izmir");
+    }
+
+    #[test]
+    fn test_normalized_variants_mine_as_one_item() {
+        let mut transactions: Vec<Transaction> = [
+            ["C\u{E0} ph\u{EA}", "B\u{E1}nh m\u{EC}"],
+            ["Ca\u{300} phe\u{302}", "B\u{E1}nh m\u{EC}"],
+            ["c\u{E0} ph\u{EA}", "Ba\u{301}nh mi\u{300}"],
+        ]
+        .iter()
+        .enumerate()
+        .map(|(idx, basket)| Transaction::new(format!("tx{}", idx), items(basket), Utc::now()))
+        .collect();
+        ItemNormalizer::standard()
+            .with_case_fold(CaseFold::Unicode)
+            .apply(&mut transactions);
+
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.9).unwrap())
+            .min_confidence(Confidence::fraction(0.9).unwrap())
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        let rules = miner.mine().unwrap().rules;
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].metrics.support, 1.0);
+    }
+
+    #[test]
+    fn test_preprocessing_merges_support() {
+        let rows = || {
+            [
+                ("tx1", &["Laptop", "SKU-Mouse"][..]),
+                ("tx2", &["laptop ", "Mouse", "Mouse"][..]),
+                ("tx3", &["LAPTOP", " mouse"][..]),
+                // Exported twice
+                ("tx3", &["LAPTOP", " mouse"][..]),
+                ("tx4", &["Laptop", "Bag"][..]),
+            ]
+            .iter()
+            .map(|(id, basket)| Transaction::new(*id, items(basket), Utc::now()))
+            .collect::<Vec<_>>()
+        };
+        let mine = |preprocessing: Preprocessing| {
+            let config = MiningConfig::builder()
+                .min_support(Support::fraction(0.5).unwrap())
+                .min_confidence(Confidence::fraction(0.5).unwrap())
+                .build()
+                .unwrap();
+            let mut miner = RuleMiner::new(config);
+            miner.set_preprocessing(preprocessing);
+            miner.add_transactions(rows()).unwrap();
+            let profile = miner.dataset_profile(10);
+            (miner.transaction_count(), profile.top_items)
+        };
+
+        // Raw: five spellings split both items
+        let (count, top) = mine(Preprocessing::default());
+        assert_eq!(count, 5);
+        assert_eq!(top[0].1, 2);
+
+        let strip_sku = |item: &str| item.trim_start_matches("sku-").to_string();
+        let (count, top) = mine(Preprocessing::clean().with_custom_normalizer(strip_sku));
+        assert_eq!(count, 4);
+        assert_eq!(
+            top,
+            [
+                ("laptop".to_string(), 4, 1.0),
+                ("mouse".to_string(), 3, 0.75),
+                ("bag".to_string(), 1, 0.25),
+            ]
+        );
+
+        // Without item dedup a repeated item stays
+        let mut tx = Transaction::new("tx", items(&["A", "A"]), Utc::now());
+        Preprocessing::default().prepare(&mut tx);
+        assert_eq!(tx.items, ["A", "A"]);
+    }
+}
@@ -0,0 +1,1380 @@
+//! Filtering and querying of mined rules
+//!
+//! `RuleSet` wraps `Vec<AssociationRule>` with chainable filters. Every method
+//! returns a new `RuleSet`, so filters compose:
+//!
+//! ```
+//! use rust_rule_miner::rules::RuleSet;
+//! # use rust_rule_miner::{AssociationRule, PatternMetrics};
+//! # let rules: Vec<AssociationRule> = Vec::new();
+//!
+//! let basket = vec!["Laptop".to_string(), "Mouse".to_string()];
+//! let recommendations = RuleSet::new(rules)
+//!     .min_confidence(0.9)
+//!     .max_items(3)
+//!     .matching(&basket)
+//!     .top_n(5);
+//! # assert!(recommendations.is_empty());
+//! ```
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::deployment::RuleKey;
+use crate::export::grl::shorten_item;
+use crate::normalize::ItemNormalizer;
+use crate::transaction::Transaction;
+use crate::types::{AssociationRule, ItemSet, PatternMetrics};
+
+/// Composable collection of association rules
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<AssociationRule>,
+}
+
+impl RuleSet {
+    /// Wrap mined rules
+    pub fn new(rules: Vec<AssociationRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Rules whose antecedent contains `item`
+    pub fn filter_by_antecedent_item(&self, item: &str) -> Self {
+        self.filter(|rule| rule.antecedent.iter().any(|i| i == item))
+    }
+
+    /// Rules whose consequent contains `item`
+    pub fn filter_by_consequent_item(&self, item: &str) -> Self {
+        self.filter(|rule| rule.consequent.iter().any(|i| i == item))
+    }
+
+    /// Rules with confidence >= `threshold`
+    pub fn min_confidence(&self, threshold: f64) -> Self {
+        self.filter(|rule| rule.metrics.confidence >= threshold)
+    }
+
+    /// Rules with lift >= `threshold`
+    pub fn min_lift(&self, threshold: f64) -> Self {
+        self.filter(|rule| rule.metrics.lift >= threshold)
+    }
+
+    /// Rules with at most `max` items in antecedent and consequent combined
+    pub fn max_items(&self, max: usize) -> Self {
+        self.filter(|rule| rule.antecedent.len() + rule.consequent.len() <= max)
+    }
+
+    /// The `n` rules with the highest quality score
+    pub fn top_n(&self, n: usize) -> Self {
+        let mut rules = self.rules.clone();
+        rules.sort_by(|a, b| {
+            b.quality_score()
+                .partial_cmp(&a.quality_score())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        rules.truncate(n);
+        Self { rules }
+    }
+
+    /// Rules whose antecedent is a subset of `basket`
+    ///
+    /// These are the rules that fire for a customer holding `basket`.
+    pub fn matching(&self, basket: &[String]) -> Self {
+        let basket: HashSet<&str> = basket.iter().map(String::as_str).collect();
+        self.filter(|rule| rule.antecedent.iter().all(|i| basket.contains(i.as_str())))
+    }
+
+    /// Rules satisfying an arbitrary predicate
+    pub fn filter<P>(&self, predicate: P) -> Self
+    where
+        P: Fn(&AssociationRule) -> bool,
+    {
+        Self {
+            rules: self
+                .rules
+                .iter()
+                .filter(|rule| predicate(rule))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Number of rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Check if the set holds no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Iterate over the rules
+    pub fn iter(&self) -> std::slice::Iter<'_, AssociationRule> {
+        self.rules.iter()
+    }
+
+    /// Borrow the rules as a slice
+    pub fn as_slice(&self) -> &[AssociationRule] {
+        &self.rules
+    }
+
+    /// Unwrap into the underlying rules
+    pub fn into_vec(self) -> Vec<AssociationRule> {
+        self.rules
+    }
+
+    /// Rules in both sets, with each set's metrics
+    ///
+    /// Rules are matched by `RuleKey` (item order does not matter) and keep
+    /// this set's order; `left` holds this set's metrics, `right` the
+    /// other's.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{AssociationRule, PatternMetrics, RuleSet};
+    ///
+    /// let rule = |consequent: &str, confidence| AssociationRule {
+    ///     antecedent: vec!["Laptop".to_string()],
+    ///     consequent: vec![consequent.to_string()],
+    ///     metrics: PatternMetrics::basic(confidence, 0.2, 1.5),
+    /// };
+    /// let web = RuleSet::new(vec![rule("Mouse", 0.9), rule("Hub", 0.7)]);
+    /// let store = RuleSet::new(vec![rule("Mouse", 0.6), rule("Bag", 0.8)]);
+    ///
+    /// let common = web.intersection(&store);
+    /// assert_eq!(common.len(), 1);
+    /// assert_eq!(common[0].consequent, ["Mouse"]);
+    /// assert!((common[0].confidence_delta().unwrap() + 0.3).abs() < 1e-9);
+    ///
+    /// assert_eq!(web.difference(&store).len(), 1); // Laptop => Hub
+    /// assert_eq!(web.union(&store).len(), 3);
+    /// ```
+    pub fn intersection(&self, other: &RuleSet) -> Vec<SourcedRule> {
+        let theirs = first_by_key(&other.rules);
+        self.unique()
+            .filter_map(|(key, rule)| {
+                theirs
+                    .get(&key)
+                    .map(|their| SourcedRule::new(key, Some(rule), Some(their)))
+            })
+            .collect()
+    }
+
+    /// Rules of this set missing from `other`, in this set's order
+    pub fn difference(&self, other: &RuleSet) -> Self {
+        let theirs = first_by_key(&other.rules);
+        self.filter(|rule| !theirs.contains_key(&RuleKey::of(rule)))
+    }
+
+    /// Rules of either set, recording which set holds each
+    ///
+    /// This set's rules come first in its order, then the rules only `other`
+    /// holds in its order.
+    pub fn union(&self, other: &RuleSet) -> Vec<SourcedRule> {
+        let theirs = first_by_key(&other.rules);
+        let mut seen = HashSet::new();
+        let mut union: Vec<SourcedRule> = self
+            .unique()
+            .map(|(key, rule)| {
+                seen.insert(key.clone());
+                let their = theirs.get(&key).copied();
+                SourcedRule::new(key, Some(rule), their)
+            })
+            .collect();
+        union.extend(
+            other
+                .unique()
+                .filter(|(key, _)| !seen.contains(key))
+                .map(|(key, rule)| SourcedRule::new(key, None, Some(rule))),
+        );
+        union
+    }
+
+    /// First rule per `RuleKey`, in order
+    fn unique(&self) -> impl Iterator<Item = (RuleKey, &AssociationRule)> {
+        let mut seen = HashSet::new();
+        self.rules.iter().filter_map(move |rule| {
+            let key = RuleKey::of(rule);
+            seen.insert(key.clone()).then_some((key, rule))
+        })
+    }
+
+    /// Tree of the rules leading to `consequent_item`, for terminal output
+    ///
+    /// The item is the root and the rules proposing it are its children,
+    /// most confident first. Below each rule are the rules proposing its
+    /// antecedent items, down to `max_depth` rule levels; items already on
+    /// the path are not expanded again. Each node lists at most
+    /// `max_children` rules plus a count of the rest. Item names longer than
+    /// `TREE_ITEM_WIDTH` characters are shortened.
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::rules::RuleSet;
+    /// # use rust_rule_miner::{AssociationRule, PatternMetrics};
+    /// # let rule = |antecedent: &str, consequent: &str, confidence| AssociationRule {
+    /// #     antecedent: vec![antecedent.to_string()],
+    /// #     consequent: vec![consequent.to_string()],
+    /// #     metrics: PatternMetrics::basic(confidence, 0.2, 1.5),
+    /// # };
+    /// let rules = RuleSet::new(vec![rule("Laptop", "Mouse", 0.9), rule("Bag", "Laptop", 0.7)]);
+    ///
+    /// assert_eq!(
+    ///     rules.render_tree("Mouse", 2, 5),
+    ///     "Mouse\n\
+    ///      └── Laptop => Mouse (confidence 90.0%, lift 1.50, support 20.0%)\n\
+    ///      \x20   └── Bag => Laptop (confidence 70.0%, lift 1.50, support 20.0%)\n"
+    /// );
+    /// ```
+    pub fn render_tree(
+        &self,
+        consequent_item: &str,
+        max_depth: usize,
+        max_children: usize,
+    ) -> String {
+        let mut out = format!("{}\n", shorten_item(consequent_item, TREE_ITEM_WIDTH));
+        let mut path = vec![consequent_item];
+        self.render_children(&mut path, 1, "", max_depth, max_children, &mut out);
+        out
+    }
+
+    /// Render the rules proposing any of the last `expand` items of `path`
+    fn render_children<'a>(
+        &'a self,
+        path: &mut Vec<&'a str>,
+        expand: usize,
+        indent: &str,
+        depth: usize,
+        max_children: usize,
+        out: &mut String,
+    ) {
+        if depth == 0 || expand == 0 {
+            return;
+        }
+        let targets = &path[path.len() - expand..];
+        let mut children: Vec<&AssociationRule> = self
+            .rules
+            .iter()
+            .filter(|rule| {
+                rule.consequent
+                    .iter()
+                    .any(|i| targets.contains(&i.as_str()))
+            })
+            .collect();
+        children.sort_by(|a, b| {
+            b.metrics
+                .confidence
+                .total_cmp(&a.metrics.confidence)
+                .then(b.metrics.lift.total_cmp(&a.metrics.lift))
+                .then_with(|| a.antecedent.cmp(&b.antecedent))
+        });
+        let hidden = children.len().saturating_sub(max_children);
+        children.truncate(max_children);
+
+        let side = |items: &[String]| {
+            items
+                .iter()
+                .map(|item| shorten_item(item, TREE_ITEM_WIDTH))
+                .collect::<Vec<_>>()
+                .join(" + ")
+        };
+        for (i, rule) in children.iter().enumerate() {
+            let last = i + 1 == children.len() && hidden == 0;
+            let (branch, continuation) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            out.push_str(&format!(
+                "{}{}{} => {} (confidence {:.1}%, lift {:.2}, support {:.1}%)\n",
+                indent,
+                branch,
+                side(&rule.antecedent),
+                side(&rule.consequent),
+                rule.metrics.confidence * 100.0,
+                rule.metrics.lift,
+                rule.metrics.support * 100.0
+            ));
+
+            // Expand antecedent items not already on the path
+            let before = path.len();
+            for item in &rule.antecedent {
+                if !path.contains(&item.as_str()) {
+                    path.push(item);
+                }
+            }
+            let child_indent = format!("{}{}", indent, continuation);
+            let added = path.len() - before;
+            self.render_children(path, added, &child_indent, depth - 1, max_children, out);
+            path.truncate(before);
+        }
+        if hidden > 0 {
+            out.push_str(&format!("{}└── … {} more\n", indent, hidden));
+        }
+    }
+}
+
+/// Longest item name `RuleSet::render_tree` prints in full
+pub const TREE_ITEM_WIDTH: usize = 32;
+
+/// First rule per `RuleKey`
+fn first_by_key(rules: &[AssociationRule]) -> HashMap<RuleKey, &AssociationRule> {
+    let mut by_key = HashMap::new();
+    for rule in rules {
+        by_key.entry(RuleKey::of(rule)).or_insert(rule);
+    }
+    by_key
+}
+
+/// Which operand of a rule-set operation holds a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Provenance {
+    Left,
+    Right,
+    Both,
+}
+
+/// A rule from `RuleSet::intersection` or `union`, with its metrics in each
+/// set that holds it
+///
+/// `left` is the set the method was called on, `right` its argument.
+/// Items are in `RuleKey` (sorted) order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourcedRule {
+    pub antecedent: ItemSet,
+    pub consequent: ItemSet,
+    pub left: Option<PatternMetrics>,
+    pub right: Option<PatternMetrics>,
+}
+
+impl SourcedRule {
+    fn new(key: RuleKey, left: Option<&AssociationRule>, right: Option<&AssociationRule>) -> Self {
+        Self {
+            antecedent: key.antecedent,
+            consequent: key.consequent,
+            left: left.map(|rule| rule.metrics.clone()),
+            right: right.map(|rule| rule.metrics.clone()),
+        }
+    }
+
+    /// Which sets hold the rule
+    pub fn provenance(&self) -> Provenance {
+        match (&self.left, &self.right) {
+            (Some(_), Some(_)) => Provenance::Both,
+            (Some(_), None) => Provenance::Left,
+            _ => Provenance::Right,
+        }
+    }
+
+    /// Right minus left confidence, for rules in both sets
+    pub fn confidence_delta(&self) -> Option<f64> {
+        Some(self.right.as_ref()?.confidence - self.left.as_ref()?.confidence)
+    }
+
+    /// The rule with its left metrics, or its right ones if only there
+    pub fn to_rule(&self) -> AssociationRule {
+        AssociationRule {
+            antecedent: self.antecedent.clone(),
+            consequent: self.consequent.clone(),
+            metrics: self
+                .left
+                .clone()
+                .or_else(|| self.right.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<Vec<AssociationRule>> for RuleSet {
+    fn from(rules: Vec<AssociationRule>) -> Self {
+        Self::new(rules)
+    }
+}
+
+impl IntoIterator for RuleSet {
+    type Item = AssociationRule;
+    type IntoIter = std::vec::IntoIter<AssociationRule>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rules.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a RuleSet {
+    type Item = &'a AssociationRule;
+    type IntoIter = std::slice::Iter<'a, AssociationRule>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rules.iter()
+    }
+}
+
+/// Scoring math for an item proposed by several matching rules
+///
+/// `Custom` receives every matching rule proposing the item.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ScoreAggregation {
+    /// Highest confidence among the rules
+    #[default]
+    Max,
+
+    /// Sum of confidences (rewards items backed by many rules)
+    Sum,
+
+    /// Sum of confidence × lift (favors rules with strong correlation)
+    LiftWeighted,
+
+    /// 1 - Π(1 - confidence): chance at least one rule is right, treating
+    /// rules as independent evidence
+    NoisyOr,
+
+    /// Caller-supplied scoring
+    Custom(fn(&[&AssociationRule]) -> f64),
+}
+
+impl ScoreAggregation {
+    /// Score an item from the rules proposing it
+    pub fn score(&self, rules: &[&AssociationRule]) -> f64 {
+        let confidences = rules.iter().map(|rule| rule.metrics.confidence);
+        match self {
+            ScoreAggregation::Max => confidences.fold(0.0, f64::max),
+            ScoreAggregation::Sum => confidences.sum(),
+            ScoreAggregation::LiftWeighted => rules
+                .iter()
+                .map(|rule| rule.metrics.confidence * rule.metrics.lift)
+                .sum(),
+            ScoreAggregation::NoisyOr => 1.0 - confidences.map(|c| 1.0 - c).product::<f64>(),
+            ScoreAggregation::Custom(score) => score(rules),
+        }
+    }
+
+    /// Short description used in explanations
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScoreAggregation::Max => "max confidence",
+            ScoreAggregation::Sum => "sum of confidence",
+            ScoreAggregation::LiftWeighted => "lift-weighted confidence",
+            ScoreAggregation::NoisyOr => "noisy-OR of confidence",
+            ScoreAggregation::Custom(_) => "custom aggregation",
+        }
+    }
+}
+
+/// A recommended item with the evidence behind its score
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    pub item: String,
+    pub score: f64,
+    /// Matching rules that propose the item, in rule order
+    pub rules: Vec<AssociationRule>,
+    /// Strategy that produced `score`
+    pub aggregation: ScoreAggregation,
+}
+
+impl Recommendation {
+    /// One-line, human-readable justification of the score
+    pub fn explanation(&self) -> String {
+        let rules: Vec<String> = self
+            .rules
+            .iter()
+            .map(|rule| {
+                format!(
+                    "{} => {} ({:.1}%)",
+                    rule.antecedent.join(" + "),
+                    self.item,
+                    rule.metrics.confidence * 100.0
+                )
+            })
+            .collect();
+        format!(
+            "{} scored {:.3} by {} over {} rule(s): {}",
+            self.item,
+            self.score,
+            self.aggregation.name(),
+            self.rules.len(),
+            rules.join(", ")
+        )
+    }
+}
+
+/// Why a basket gets no (or few) recommendations, see
+/// `Recommender::explain_no_match`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoMatchExplanation {
+    /// Basket items that appear in no rule at all
+    pub unknown_items: Vec<String>,
+    /// Basket items that only appear as consequents, so no rule starts from them
+    pub items_without_rules: Vec<String>,
+    /// Closest rules that share items with the basket but need more
+    pub near_misses: Vec<NearMiss>,
+}
+
+impl NoMatchExplanation {
+    /// Near misses reported by `Recommender::explain_no_match`
+    pub const DEFAULT_NEAR_MISSES: usize = 5;
+}
+
+/// A rule whose antecedent the basket covers only in part
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NearMiss {
+    pub rule: AssociationRule,
+    /// Antecedent items the basket lacks, e.g. `["Mouse"]` = "needs Mouse too"
+    pub missing: Vec<String>,
+}
+
+/// Who a recommendation is for, passed to `RecommendationFilter`s
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecContext {
+    /// Region the user shops in, e.g. `"EU"`
+    pub region: Option<String>,
+    /// Other user attributes, e.g. `"age_verified" => "true"`
+    pub attributes: HashMap<String, String>,
+}
+
+impl RecContext {
+    /// Context without region or attributes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Value of attribute `key`, if set
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.get(key).map(String::as_str)
+    }
+}
+
+/// Decides whether an item may be recommended, e.g. from catalog data
+///
+/// Closures `Fn(&str, &RecContext) -> bool` implement it.
+pub trait RecommendationFilter: Send + Sync {
+    fn allow(&self, item: &str, context: &RecContext) -> bool;
+}
+
+impl<F> RecommendationFilter for F
+where
+    F: Fn(&str, &RecContext) -> bool + Send + Sync,
+{
+    fn allow(&self, item: &str, context: &RecContext) -> bool {
+        self(item, context)
+    }
+}
+
+/// Allows only items in stock
+#[derive(Debug, Clone, Default)]
+pub struct InStock(pub HashSet<String>);
+
+impl RecommendationFilter for InStock {
+    fn allow(&self, item: &str, _context: &RecContext) -> bool {
+        self.0.contains(item)
+    }
+}
+
+/// Regions each listed item may be sold in
+///
+/// Items missing from the map are sellable everywhere; a listed item needs
+/// a context region among its regions.
+#[derive(Debug, Clone, Default)]
+pub struct RegionAllowlist(pub HashMap<String, Vec<String>>);
+
+impl RecommendationFilter for RegionAllowlist {
+    fn allow(&self, item: &str, context: &RecContext) -> bool {
+        match self.0.get(item) {
+            None => true,
+            Some(regions) => context
+                .region
+                .as_ref()
+                .is_some_and(|region| regions.contains(region)),
+        }
+    }
+}
+
+/// Never recommends the listed items
+#[derive(Debug, Clone, Default)]
+pub struct Blocklist(pub HashSet<String>);
+
+impl RecommendationFilter for Blocklist {
+    fn allow(&self, item: &str, _context: &RecContext) -> bool {
+        !self.0.contains(item)
+    }
+}
+
+/// Filters of a `Recommender`; an item must pass all of them
+#[derive(Clone, Default)]
+struct Filters(Vec<Arc<dyn RecommendationFilter>>);
+
+impl Filters {
+    fn allow(&self, item: &str, context: &RecContext) -> bool {
+        self.0.iter().all(|filter| filter.allow(item, context))
+    }
+}
+
+impl std::fmt::Debug for Filters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Filters({})", self.0.len())
+    }
+}
+
+/// Basket recommender over mined rules with a pluggable scoring strategy
+///
+/// Items are ranked by score (highest first). Ties are broken by item
+/// frequency from `with_item_counts` (more frequent first), then by item name,
+/// so the ranking is deterministic. Items a `with_filter` filter rejects are
+/// dropped before the ranking is cut to `top_n`, so they don't take slots.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::rules::{Recommender, ScoreAggregation};
+/// # use rust_rule_miner::AssociationRule;
+/// # let rules: Vec<AssociationRule> = Vec::new();
+///
+/// let cart = vec!["Laptop".to_string()];
+/// let recommender = Recommender::new(&rules).with_aggregation(ScoreAggregation::NoisyOr);
+/// for recommendation in recommender.recommend(&cart, 3) {
+///     println!("{}", recommendation.explanation());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recommender<'a> {
+    rules: &'a [AssociationRule],
+    aggregation: ScoreAggregation,
+    item_counts: HashMap<String, usize>,
+    normalizer: ItemNormalizer,
+    filters: Filters,
+}
+
+impl<'a> Recommender<'a> {
+    /// Recommender scoring by max confidence
+    pub fn new(rules: &'a [AssociationRule]) -> Self {
+        Self {
+            rules,
+            aggregation: ScoreAggregation::default(),
+            item_counts: HashMap::new(),
+            normalizer: ItemNormalizer::default(),
+            filters: Filters::default(),
+        }
+    }
+
+    /// Normalize basket items (and items counted by `with_item_counts`)
+    ///
+    /// Use the normalizer the mined data went through, so a basket spelled
+    /// differently still matches the rules.
+    pub fn with_normalizer(mut self, normalizer: ItemNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Set the scoring strategy
+    pub fn with_aggregation(mut self, aggregation: ScoreAggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
+    /// Count item occurrences in `transactions` for tie-breaking
+    pub fn with_item_counts(mut self, transactions: &[Transaction]) -> Self {
+        self.item_counts.clear();
+        for tx in transactions {
+            for item in self.normalizer.normalize_items(&tx.items) {
+                *self.item_counts.entry(item).or_insert(0) += 1;
+            }
+        }
+        self
+    }
+
+    /// Only recommend items `filter` allows; filters add up
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::{HashMap, HashSet};
+    /// use rust_rule_miner::rules::{InStock, RecContext, Recommender, RegionAllowlist};
+    /// # use rust_rule_miner::AssociationRule;
+    /// # let rules: Vec<AssociationRule> = Vec::new();
+    ///
+    /// let recommender = Recommender::new(&rules)
+    ///     .with_filter(InStock(HashSet::from(["Mouse".to_string()])))
+    ///     .with_filter(RegionAllowlist(HashMap::from([(
+    ///         "Adapter".to_string(),
+    ///         vec!["EU".to_string()],
+    ///     )])))
+    ///     .with_filter(|item: &str, context: &RecContext| {
+    ///         item != "Wine" || context.attribute("age_verified") == Some("true")
+    ///     });
+    ///
+    /// let cart = vec!["Laptop".to_string()];
+    /// let context = RecContext::new().with_region("US");
+    /// let recommendations = recommender.recommend_with_context(&cart, 3, &context);
+    /// # assert!(recommendations.is_empty());
+    /// ```
+    pub fn with_filter(mut self, filter: impl RecommendationFilter + 'static) -> Self {
+        self.filters.0.push(Arc::new(filter));
+        self
+    }
+
+    /// The `top_n` best items not already in `basket`, filtered with an
+    /// empty `RecContext`
+    pub fn recommend(&self, basket: &[String], top_n: usize) -> Vec<Recommendation> {
+        self.recommend_with_context(basket, top_n, &RecContext::default())
+    }
+
+    /// The `top_n` best items not already in `basket` that the filters allow
+    /// for `context`
+    pub fn recommend_with_context(
+        &self,
+        basket: &[String],
+        top_n: usize,
+        context: &RecContext,
+    ) -> Vec<Recommendation> {
+        let basket = self.normalizer.normalize_items(basket);
+        rank(
+            self.rules,
+            &basket,
+            top_n,
+            self.aggregation,
+            &self.item_counts,
+            |item| self.filters.allow(item, context),
+        )
+    }
+
+    /// Explain which basket items the rules cannot use, with up to
+    /// `NoMatchExplanation::DEFAULT_NEAR_MISSES` near misses
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::rules::Recommender;
+    /// # use rust_rule_miner::AssociationRule;
+    /// # let rules: Vec<AssociationRule> = Vec::new();
+    ///
+    /// let cart = vec!["Laptop".to_string()];
+    /// let explanation = Recommender::new(&rules).explain_no_match(&cart);
+    /// for miss in &explanation.near_misses {
+    ///     println!("needs {} too", miss.missing.join(", "));
+    /// }
+    /// # assert_eq!(explanation.unknown_items, cart);
+    /// ```
+    pub fn explain_no_match(&self, basket: &[String]) -> NoMatchExplanation {
+        self.explain_no_match_within(basket, NoMatchExplanation::DEFAULT_NEAR_MISSES)
+    }
+
+    /// `explain_no_match` reporting at most `max_near_misses` near misses
+    ///
+    /// Near misses are ordered by fewest missing items, then confidence.
+    pub fn explain_no_match_within(
+        &self,
+        basket: &[String],
+        max_near_misses: usize,
+    ) -> NoMatchExplanation {
+        let basket = self.normalizer.normalize_items(basket);
+        let in_basket: HashSet<&str> = basket.iter().map(String::as_str).collect();
+
+        let mut antecedent_items = HashSet::new();
+        let mut consequent_items = HashSet::new();
+        let mut near_misses: Vec<(usize, &AssociationRule)> = Vec::new();
+        for rule in self.rules {
+            antecedent_items.extend(rule.antecedent.iter().map(String::as_str));
+            consequent_items.extend(rule.consequent.iter().map(String::as_str));
+
+            let missing = rule
+                .antecedent
+                .iter()
+                .filter(|item| !in_basket.contains(item.as_str()))
+                .count();
+            if missing > 0 && missing < rule.antecedent.len() {
+                near_misses.push((missing, rule));
+            }
+        }
+
+        let mut unknown_items = Vec::new();
+        let mut items_without_rules = Vec::new();
+        for item in &basket {
+            if antecedent_items.contains(item.as_str()) {
+                continue;
+            }
+            if consequent_items.contains(item.as_str()) {
+                items_without_rules.push(item.clone());
+            } else {
+                unknown_items.push(item.clone());
+            }
+        }
+
+        near_misses.sort_by(|a, b| {
+            a.0.cmp(&b.0).then_with(|| {
+                b.1.metrics
+                    .confidence
+                    .partial_cmp(&a.1.metrics.confidence)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        });
+        let near_misses = near_misses
+            .into_iter()
+            .take(max_near_misses)
+            .map(|(_, rule)| NearMiss {
+                rule: rule.clone(),
+                missing: rule
+                    .antecedent
+                    .iter()
+                    .filter(|item| !in_basket.contains(item.as_str()))
+                    .cloned()
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect(),
+            })
+            .collect();
+
+        NoMatchExplanation {
+            unknown_items,
+            items_without_rules,
+            near_misses,
+        }
+    }
+}
+
+/// Score and rank the items `rules` propose for `basket`
+///
+/// Non-matching rules are skipped, so callers may pass a superset of the
+/// candidate rules (e.g. from an index). Scored items `allow` rejects are
+/// dropped before the cut to `top_n`.
+pub(crate) fn rank<'r>(
+    rules: impl IntoIterator<Item = &'r AssociationRule>,
+    basket: &[String],
+    top_n: usize,
+    aggregation: ScoreAggregation,
+    item_counts: &HashMap<String, usize>,
+    allow: impl Fn(&str) -> bool,
+) -> Vec<Recommendation> {
+    let mut proposals: HashMap<&str, Vec<&AssociationRule>> = HashMap::new();
+    for rule in rules.into_iter().filter(|rule| rule.matches(basket)) {
+        for item in rule.consequent.iter().filter(|item| !basket.contains(item)) {
+            proposals.entry(item.as_str()).or_default().push(rule);
+        }
+    }
+
+    let mut scored: Vec<(f64, usize, &str, Vec<&AssociationRule>)> = proposals
+        .into_iter()
+        .map(|(item, rules)| {
+            let frequency = item_counts.get(item).copied().unwrap_or(0);
+            (aggregation.score(&rules), frequency, item, rules)
+        })
+        .filter(|(_, _, item, _)| allow(item))
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.1.cmp(&a.1))
+            .then_with(|| a.2.cmp(b.2))
+    });
+    scored.truncate(top_n);
+
+    scored
+        .into_iter()
+        .map(|(score, _, item, rules)| Recommendation {
+            item: item.to_string(),
+            score,
+            rules: rules.into_iter().cloned().collect(),
+            aggregation,
+        })
+        .collect()
+}
+
+/// Recommend items for a basket from mined rules, without the rule engine
+///
+/// Collects consequent items of every rule whose antecedent is contained in
+/// `basket`, scores each item by the highest confidence among the rules
+/// recommending it, drops items already in the basket and returns the
+/// `top_n` best items, highest score first. Use `Recommender` for other
+/// scoring strategies and explanations.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::{recommend, MiningConfig, RuleMiner, Support, Transaction};
+/// use chrono::Utc;
+///
+/// let config = MiningConfig::builder()
+///     .min_support(Support::fraction(0.5)?)
+///     .build()?;
+/// let mut miner = RuleMiner::new(config);
+/// miner.add_transactions(vec![
+///     Transaction::new("tx1", vec!["Laptop".to_string(), "Mouse".to_string()], Utc::now()),
+///     Transaction::new("tx2", vec!["Laptop".to_string(), "Mouse".to_string()], Utc::now()),
+///     Transaction::new("tx3", vec!["Phone".to_string()], Utc::now()),
+/// ])?;
+/// let rules = miner.mine()?.rules;
+///
+/// let cart = vec!["Laptop".to_string()];
+/// let recommendations = recommend(&rules, &cart, 3);
+/// assert_eq!(recommendations[0].0, "Mouse");
+/// # Ok::<(), rust_rule_miner::MiningError>(())
+/// ```
+pub fn recommend(rules: &[AssociationRule], basket: &[String], top_n: usize) -> Vec<(String, f64)> {
+    Recommender::new(rules)
+        .recommend(basket, top_n)
+        .into_iter()
+        .map(|recommendation| (recommendation.item, recommendation.score))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+
+    fn rule_set() -> RuleSet {
+        RuleSet::new(vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.9, 1.5),
+            test_rule(&["Laptop", "Mouse"], &["Keyboard"], 0.8, 2.0),
+            test_rule(&["Laptop", "Hub"], &["Dock"], 0.95, 1.2),
+            test_rule(&["Phone"], &["Case", "Charger"], 0.6, 1.1),
+        ])
+    }
+
+    fn basket(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn tree_rules() -> RuleSet {
+        RuleSet::new(vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.9, 1.5),
+            test_rule(&["Tablet"], &["Mouse"], 0.7, 1.2),
+            test_rule(&["Laptop", "Hub"], &["Mouse"], 0.95, 1.6),
+            test_rule(&["Desk"], &["Mouse"], 0.5, 1.1),
+            test_rule(&["Bag"], &["Laptop"], 0.6, 1.3),
+            test_rule(&["Mouse"], &["Laptop"], 0.8, 1.5),
+            test_rule(&["Cable"], &["Hub"], 0.65, 2.0),
+            test_rule(&["Phone"], &["Case"], 0.6, 1.1),
+        ])
+    }
+
+    #[test]
+    fn test_rule_set_algebra_overlapping() {
+        let web = rule_set();
+        let store = RuleSet::new(vec![
+            // Same rules, items in another order
+            test_rule(&["Mouse", "Laptop"], &["Keyboard"], 0.6, 1.4),
+            test_rule(&["Laptop"], &["Mouse"], 0.7, 1.3),
+            test_rule(&["Tablet"], &["Pen"], 0.9, 3.0),
+        ]);
+
+        let common = web.intersection(&store);
+        assert_eq!(common.len(), 2);
+        // Web order, sorted items
+        assert_eq!(common[0].antecedent, ["Laptop"]);
+        assert_eq!(common[1].antecedent, ["Laptop", "Mouse"]);
+        let paired: Vec<(f64, f64)> = common
+            .iter()
+            .map(|r| {
+                (
+                    r.left.as_ref().unwrap().confidence,
+                    r.right.as_ref().unwrap().confidence,
+                )
+            })
+            .collect();
+        assert_eq!(paired, [(0.9, 0.7), (0.8, 0.6)]);
+        assert!(common.iter().all(|r| r.provenance() == Provenance::Both));
+        assert!((common[0].confidence_delta().unwrap() + 0.2).abs() < 1e-9);
+
+        let web_only = web.difference(&store);
+        assert_eq!(web_only.len(), 2);
+        assert_eq!(web_only.as_slice()[0].consequent, ["Dock"]);
+        let store_only = store.difference(&web);
+        assert_eq!(store_only.len(), 1);
+        assert_eq!(store_only.as_slice()[0].antecedent, ["Tablet"]);
+
+        let union = web.union(&store);
+        let provenance: Vec<Provenance> = union.iter().map(SourcedRule::provenance).collect();
+        assert_eq!(
+            provenance,
+            [
+                Provenance::Both,
+                Provenance::Both,
+                Provenance::Left,
+                Provenance::Left,
+                Provenance::Right,
+            ]
+        );
+        assert_eq!(union[4].right.as_ref().unwrap().lift, 3.0);
+        assert_eq!(union[4].confidence_delta(), None);
+        assert_eq!(union[4].to_rule().metrics.confidence, 0.9);
+    }
+
+    #[test]
+    fn test_rule_set_algebra_disjoint() {
+        let web = rule_set();
+        let store = RuleSet::new(vec![test_rule(&["Tablet"], &["Pen"], 0.9, 3.0)]);
+
+        assert!(web.intersection(&store).is_empty());
+        assert_eq!(web.difference(&store).len(), web.len());
+        assert_eq!(web.union(&store).len(), web.len() + 1);
+        assert!(web.difference(&web).is_empty());
+        assert!(web
+            .union(&RuleSet::default())
+            .iter()
+            .all(|r| r.provenance() == Provenance::Left));
+    }
+
+    #[test]
+    fn test_render_tree_structure() {
+        let tree = tree_rules().render_tree("Mouse", 2, 3);
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(lines[0], "Mouse");
+        let names: Vec<String> = lines[1..]
+            .iter()
+            .map(|line| line.split(" (").next().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "├── Laptop + Hub => Mouse",
+                "│   ├── Mouse => Laptop",
+                "│   ├── Cable => Hub",
+                "│   └── Bag => Laptop",
+                "├── Laptop => Mouse",
+                "│   ├── Mouse => Laptop",
+                "│   └── Bag => Laptop",
+                "├── Tablet => Mouse",
+                "└── … 1 more",
+            ]
+        );
+        assert!(lines[1].ends_with("(confidence 95.0%, lift 1.60, support 20.0%)"));
+    }
+
+    #[test]
+    fn test_render_tree_limits() {
+        let rules = tree_rules();
+
+        // Depth 1: only the rules proposing the root
+        let tree = rules.render_tree("Mouse", 1, 10);
+        assert_eq!(tree.lines().count(), 5);
+        assert_eq!(tree.matches("├── ").count(), 3);
+        assert_eq!(tree.matches("└── ").count(), 1);
+        assert!(!tree.contains("│"));
+
+        assert_eq!(rules.render_tree("Mouse", 0, 10), "Mouse\n");
+        assert_eq!(rules.render_tree("Unknown", 3, 10), "Unknown\n");
+        assert_eq!(rules.render_tree("Mouse", 2, 0), "Mouse\n└── … 4 more\n");
+    }
+
+    #[test]
+    fn test_render_tree_shortens_long_items() {
+        let long = "Ergonomic Wireless Keyboard – Ünicode Édition 2024";
+        let rules = RuleSet::new(vec![test_rule(&[long], &["Mouse"], 0.9, 1.5)]);
+        let tree = rules.render_tree("Mouse", 1, 5);
+        let shown: String = long.chars().take(TREE_ITEM_WIDTH).collect();
+        assert!(tree.contains(&format!("└── {}_", shown)), "{}", tree);
+        assert!(!tree.contains(long));
+    }
+
+    #[test]
+    fn test_matching_multi_item_antecedents() {
+        let rules = rule_set();
+
+        // Both Laptop rules fire; the Laptop+Hub rule does not (Hub missing)
+        let matched = rules.matching(&basket(&["Mouse", "Laptop", "Pen"]));
+        assert_eq!(matched.len(), 2);
+        assert!(matched
+            .iter()
+            .any(|r| r.antecedent == basket(&["Laptop", "Mouse"])));
+        assert!(!matched.iter().any(|r| r.consequent == basket(&["Dock"])));
+
+        // A basket with only one item of a multi-item antecedent doesn't match it
+        let matched = rules.matching(&basket(&["Hub"]));
+        assert!(matched.is_empty());
+
+        assert!(rules.matching(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_item_filters() {
+        let rules = rule_set();
+
+        assert_eq!(rules.filter_by_antecedent_item("Laptop").len(), 3);
+        assert_eq!(rules.filter_by_consequent_item("Charger").len(), 1);
+        assert!(rules.filter_by_antecedent_item("Dock").is_empty());
+    }
+
+    #[test]
+    fn test_chained_filters() {
+        let rules = rule_set();
+
+        let filtered = rules
+            .filter_by_antecedent_item("Laptop")
+            .min_confidence(0.85)
+            .max_items(2);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered.as_slice()[0].consequent, basket(&["Mouse"]));
+
+        assert_eq!(rules.min_lift(1.4).len(), 2);
+        // Original set is untouched
+        assert_eq!(rules.len(), 4);
+    }
+
+    #[test]
+    fn test_top_n() {
+        let top = rule_set().top_n(2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top.as_slice()[0].consequent, basket(&["Keyboard"]));
+        assert!(top.as_slice()[0].quality_score() >= top.as_slice()[1].quality_score());
+
+        assert_eq!(rule_set().top_n(10).len(), 4);
+    }
+
+    #[test]
+    fn test_recommend_overlapping_rules() {
+        let rules = vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.7, 1.5),
+            test_rule(&["Laptop", "Bag"], &["Mouse"], 0.9, 1.8),
+            test_rule(&["Laptop"], &["Keyboard", "Bag"], 0.8, 1.4),
+            test_rule(&["Phone"], &["Case"], 0.95, 2.0),
+        ];
+
+        let cart = basket(&["Laptop", "Bag"]);
+        let recommendations = recommend(&rules, &cart, 10);
+
+        // Mouse takes the best confidence of the two rules recommending it;
+        // Bag is already in the cart and Case's rule doesn't fire
+        assert_eq!(
+            recommendations,
+            vec![("Mouse".to_string(), 0.9), ("Keyboard".to_string(), 0.8)]
+        );
+
+        assert_eq!(recommend(&rules, &cart, 1).len(), 1);
+        assert!(recommend(&rules, &basket(&["Pen"]), 5).is_empty());
+    }
+
+    fn aggregation_rules() -> Vec<AssociationRule> {
+        vec![
+            // Mouse: one strong rule
+            test_rule(&["Laptop"], &["Mouse"], 0.9, 1.2),
+            // Bag: two moderate rules
+            test_rule(&["Laptop"], &["Bag"], 0.6, 2.5),
+            test_rule(&["Charger"], &["Bag"], 0.5, 2.0),
+        ]
+    }
+
+    fn ranking(recommendations: &[Recommendation]) -> Vec<&str> {
+        recommendations.iter().map(|r| r.item.as_str()).collect()
+    }
+
+    #[test]
+    fn test_max_and_sum_rank_differently() {
+        let rules = aggregation_rules();
+        let cart = basket(&["Laptop", "Charger"]);
+
+        let max = Recommender::new(&rules).recommend(&cart, 5);
+        assert_eq!(ranking(&max), vec!["Mouse", "Bag"]);
+        assert_eq!(max[1].score, 0.6);
+
+        let sum = Recommender::new(&rules)
+            .with_aggregation(ScoreAggregation::Sum)
+            .recommend(&cart, 5);
+        assert_eq!(ranking(&sum), vec!["Bag", "Mouse"]);
+        assert!((sum[0].score - 1.1).abs() < 1e-12);
+        assert_eq!(sum[0].rules.len(), 2);
+    }
+
+    #[test]
+    fn test_filtered_item_gives_up_its_slot() {
+        let rules = aggregation_rules();
+        let cart = basket(&["Laptop", "Charger"]);
+        assert_eq!(
+            ranking(&Recommender::new(&rules).recommend(&cart, 1)),
+            ["Mouse"]
+        );
+
+        // Mouse scores highest but is out of stock: Bag takes the one slot
+        let in_stock = InStock(HashSet::from(["Bag".to_string()]));
+        let recommended = Recommender::new(&rules)
+            .with_filter(in_stock)
+            .recommend(&cart, 1);
+        assert_eq!(ranking(&recommended), ["Bag"]);
+        assert_eq!(recommended[0].score, 0.6);
+
+        let blocked = Recommender::new(&rules)
+            .with_filter(Blocklist(HashSet::from(["Bag".to_string()])))
+            .recommend(&cart, 5);
+        assert_eq!(ranking(&blocked), ["Mouse"]);
+    }
+
+    #[test]
+    fn test_region_and_context_filters() {
+        let rules = aggregation_rules();
+        let cart = basket(&["Laptop", "Charger"]);
+        let recommender = Recommender::new(&rules)
+            .with_filter(RegionAllowlist(HashMap::from([(
+                "Mouse".to_string(),
+                vec!["EU".to_string(), "UK".to_string()],
+            )])))
+            .with_filter(|item: &str, context: &RecContext| {
+                item != "Bag" || context.attribute("tier") == Some("gold")
+            });
+
+        let eu = RecContext::new().with_region("EU");
+        assert_eq!(
+            ranking(&recommender.recommend_with_context(&cart, 5, &eu)),
+            ["Mouse"]
+        );
+        let us_gold = RecContext::new()
+            .with_region("US")
+            .with_attribute("tier", "gold");
+        assert_eq!(
+            ranking(&recommender.recommend_with_context(&cart, 5, &us_gold)),
+            ["Bag"]
+        );
+        // Without a region restricted items are withheld
+        assert!(recommender.recommend(&cart, 5).is_empty());
+    }
+
+    #[test]
+    fn test_other_aggregations() {
+        let rules = aggregation_rules();
+        let cart = basket(&["Laptop", "Charger"]);
+        let score = |aggregation: ScoreAggregation, item: &str| {
+            Recommender::new(&rules)
+                .with_aggregation(aggregation)
+                .recommend(&cart, 5)
+                .into_iter()
+                .find(|r| r.item == item)
+                .unwrap()
+                .score
+        };
+
+        assert!((score(ScoreAggregation::LiftWeighted, "Bag") - 2.5).abs() < 1e-12);
+        assert!((score(ScoreAggregation::NoisyOr, "Bag") - 0.8).abs() < 1e-12);
+        assert!((score(ScoreAggregation::NoisyOr, "Mouse") - 0.9).abs() < 1e-12);
+
+        fn rule_count(rules: &[&AssociationRule]) -> f64 {
+            rules.len() as f64
+        }
+        assert_eq!(score(ScoreAggregation::Custom(rule_count), "Bag"), 2.0);
+    }
+
+    #[test]
+    fn test_ties_broken_by_frequency_then_name() {
+        let rules = vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.8, 1.2),
+            test_rule(&["Laptop"], &["Bag"], 0.8, 1.2),
+            test_rule(&["Laptop"], &["Hub"], 0.8, 1.2),
+        ];
+        let cart = basket(&["Laptop"]);
+
+        let by_name = Recommender::new(&rules).recommend(&cart, 5);
+        assert_eq!(ranking(&by_name), vec!["Bag", "Hub", "Mouse"]);
+
+        let transactions = vec![
+            Transaction::new("t1", basket(&["Mouse", "Hub"]), chrono::Utc::now()),
+            Transaction::new("t2", basket(&["Mouse"]), chrono::Utc::now()),
+        ];
+        let by_frequency = Recommender::new(&rules)
+            .with_item_counts(&transactions)
+            .recommend(&cart, 5);
+        assert_eq!(ranking(&by_frequency), vec!["Mouse", "Hub", "Bag"]);
+    }
+
+    #[test]
+    fn test_basket_normalization() {
+        let rules = vec![test_rule(
+            &["C\u{E0} ph\u{EA}"],
+            &["B\u{E1}nh m\u{EC}"],
+            0.8,
+            1.2,
+        )];
+        // NFD spelling of the rule's antecedent
+        let cart = basket(&["Ca\u{300} phe\u{302}"]);
+
+        assert!(Recommender::new(&rules).recommend(&cart, 5).is_empty());
+        let recommendations = Recommender::new(&rules)
+            .with_normalizer(ItemNormalizer::standard())
+            .recommend(&cart, 5);
+        assert_eq!(ranking(&recommendations), vec!["B\u{E1}nh m\u{EC}"]);
+    }
+
+    #[test]
+    fn test_explain_unknown_items() {
+        let rules = rule_set().into_vec();
+        let explanation = Recommender::new(&rules).explain_no_match(&basket(&["Tent", "Stove"]));
+        assert_eq!(explanation.unknown_items, vec!["Tent", "Stove"]);
+        assert!(explanation.items_without_rules.is_empty());
+        assert!(explanation.near_misses.is_empty());
+    }
+
+    #[test]
+    fn test_explain_items_without_rules() {
+        let rules = rule_set().into_vec();
+        // Dock and Charger are only ever recommended, never a starting point
+        let explanation =
+            Recommender::new(&rules).explain_no_match(&basket(&["Dock", "Charger", "Tent"]));
+        assert_eq!(explanation.items_without_rules, vec!["Dock", "Charger"]);
+        assert_eq!(explanation.unknown_items, vec!["Tent"]);
+        assert!(explanation.near_misses.is_empty());
+    }
+
+    #[test]
+    fn test_explain_near_misses() {
+        let rules = vec![
+            test_rule(&["Laptop", "Mouse"], &["Keyboard"], 0.8, 2.0),
+            test_rule(&["Laptop", "Hub"], &["Dock"], 0.95, 1.2),
+            test_rule(&["Laptop", "Mouse", "Hub"], &["Monitor"], 0.99, 3.0),
+            test_rule(&["Phone"], &["Case"], 0.6, 1.1),
+        ];
+        let recommender = Recommender::new(&rules);
+        let cart = basket(&["Laptop"]);
+        assert!(recommender.recommend(&cart, 5).is_empty());
+
+        let explanation = recommender.explain_no_match(&cart);
+        assert!(explanation.unknown_items.is_empty());
+        assert!(explanation.items_without_rules.is_empty());
+        let missing: Vec<&[String]> = explanation
+            .near_misses
+            .iter()
+            .map(|miss| miss.missing.as_slice())
+            .collect();
+        // One missing item first (higher confidence first), then two
+        assert_eq!(
+            missing,
+            vec![
+                &basket(&["Hub"])[..],
+                &basket(&["Mouse"]),
+                &basket(&["Hub", "Mouse"])
+            ]
+        );
+        assert_eq!(explanation.near_misses[1].rule.consequent, vec!["Keyboard"]);
+
+        // Budget caps the report; it round-trips for support tooling
+        let capped = recommender.explain_no_match_within(&cart, 1);
+        assert_eq!(capped.near_misses.len(), 1);
+        let json = serde_json::to_string(&capped).unwrap();
+        let back: NoMatchExplanation = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.near_misses[0].missing, vec!["Hub"]);
+    }
+
+    #[test]
+    fn test_explanation_names_strategy() {
+        let rules = aggregation_rules();
+        let recommendation = Recommender::new(&rules)
+            .with_aggregation(ScoreAggregation::Sum)
+            .recommend(&basket(&["Laptop", "Charger"]), 1)
+            .remove(0);
+
+        let explanation = recommendation.explanation();
+        assert!(explanation.contains("sum of confidence"), "{}", explanation);
+        assert!(
+            explanation.contains("Laptop => Bag (60.0%)"),
+            "{}",
+            explanation
+        );
+        assert!(
+            explanation.contains("Charger => Bag (50.0%)"),
+            "{}",
+            explanation
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Rule quality scoring
+//!
+//! Mined rules are sorted by a quality score. `AssociationRule::quality_score`
+//! is the default; `RuleMiner::set_scorer` swaps in a `WeightedScorer` with
+//! other weights or any `QualityScorer`.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::AssociationRule;
+
+/// Scores a rule; higher is better
+///
+/// Closures `Fn(&AssociationRule) -> f64` implement it.
+pub trait QualityScorer: Send + Sync {
+    fn score(&self, rule: &AssociationRule) -> f64;
+}
+
+impl<F> QualityScorer for F
+where
+    F: Fn(&AssociationRule) -> f64 + Send + Sync,
+{
+    fn score(&self, rule: &AssociationRule) -> f64 {
+        self(rule)
+    }
+}
+
+/// How lift enters a `WeightedScorer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LiftNormalization {
+    /// Raw lift (unbounded, so it can dominate the score)
+    #[default]
+    Raw,
+
+    /// lift / (1 + lift): 0.5 at independence, approaching 1 for strong
+    /// rules, so weights summing to 1 keep the score within 0 - 1
+    Saturating,
+}
+
+/// Weighted sum of confidence, lift and support
+///
+/// The default reproduces `AssociationRule::quality_score`.
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::scoring::{LiftNormalization, WeightedScorer};
+///
+/// // Confidence-led score bounded to 0 - 1
+/// let scorer = WeightedScorer::new(0.7, 0.2, 0.1).with_lift(LiftNormalization::Saturating);
+/// # let _ = scorer;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeightedScorer {
+    pub confidence: f64,
+    pub lift: f64,
+    pub support: f64,
+    #[serde(default)]
+    pub lift_normalization: LiftNormalization,
+}
+
+impl Default for WeightedScorer {
+    fn default() -> Self {
+        Self::new(0.5, 0.3, 0.2)
+    }
+}
+
+impl WeightedScorer {
+    /// Weights for confidence, lift and support, with raw lift
+    pub fn new(confidence: f64, lift: f64, support: f64) -> Self {
+        Self {
+            confidence,
+            lift,
+            support,
+            lift_normalization: LiftNormalization::Raw,
+        }
+    }
+
+    pub fn with_lift(mut self, normalization: LiftNormalization) -> Self {
+        self.lift_normalization = normalization;
+        self
+    }
+}
+
+impl QualityScorer for WeightedScorer {
+    fn score(&self, rule: &AssociationRule) -> f64 {
+        let metrics = &rule.metrics;
+        let lift = match self.lift_normalization {
+            LiftNormalization::Raw => metrics.lift,
+            LiftNormalization::Saturating if metrics.lift.is_infinite() => 1.0,
+            LiftNormalization::Saturating => metrics.lift / (1.0 + metrics.lift),
+        };
+        metrics.confidence * self.confidence + lift * self.lift + metrics.support * self.support
+    }
+}
+
+/// Shareable scorer handle held by the miner and its support tables
+#[derive(Clone)]
+pub(crate) struct SharedScorer(Arc<dyn QualityScorer>);
+
+impl SharedScorer {
+    pub(crate) fn new(scorer: impl QualityScorer + 'static) -> Self {
+        Self(Arc::new(scorer))
+    }
+
+    /// Sort rules best first
+    pub(crate) fn sort(&self, rules: &mut [AssociationRule]) {
+        rules.sort_by(|a, b| {
+            self.0
+                .score(b)
+                .partial_cmp(&self.0.score(a))
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+}
+
+impl Default for SharedScorer {
+    fn default() -> Self {
+        Self::new(WeightedScorer::default())
+    }
+}
+
+impl std::fmt::Debug for SharedScorer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SharedScorer")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Confidence, Lift, MiningConfig, Support};
+    use crate::mining::RuleMiner;
+    use crate::transaction::Transaction;
+    use crate::types::PatternMetrics;
+    use chrono::Utc;
+
+    #[test]
+    fn test_default_matches_quality_score() {
+        let rule = AssociationRule {
+            antecedent: vec!["A".to_string()],
+            consequent: vec!["B".to_string()],
+            metrics: PatternMetrics {
+                confidence: 0.8,
+                support: 0.3,
+                lift: 4.0,
+                ..Default::default()
+            },
+        };
+        assert_eq!(WeightedScorer::default().score(&rule), rule.quality_score());
+        assert!(rule.quality_score() > 1.0);
+
+        let bounded = WeightedScorer::default().with_lift(LiftNormalization::Saturating);
+        assert!((bounded.score(&rule) - (0.4 + 0.3 * 0.8 + 0.06)).abs() < 1e-12);
+    }
+
+    /// Rare pair with high lift vs. common pair with high confidence
+    fn miner() -> RuleMiner {
+        let basket = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        let mut transactions = Vec::new();
+        for idx in 0..10 {
+            let items = match idx {
+                0 | 1 => basket(&["Caviar", "Blini", "Milk", "Bread"]),
+                2..=8 => basket(&["Milk", "Bread"]),
+                _ => basket(&["Milk", "Caviar"]),
+            };
+            transactions.push(Transaction::new(format!("t{}", idx), items, Utc::now()));
+        }
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.2).unwrap())
+            .min_confidence(Confidence::fraction(0.8).unwrap())
+            .min_lift(Lift::new(1.0).unwrap())
+            .max_itemset_size(2)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions).unwrap();
+        miner
+    }
+
+    fn first_rule(miner: &mut RuleMiner) -> String {
+        let rules = miner.mine().unwrap().rules;
+        format!("{} => {}", rules[0].antecedent[0], rules[0].consequent[0])
+    }
+
+    #[test]
+    fn test_custom_scorers_change_ordering() {
+        // Default: the rare pair's lift of 3.3 dominates
+        let mut default = miner();
+        assert_eq!(first_rule(&mut default), "Blini => Caviar");
+
+        // Support-led weights put the common pair first
+        let mut weighted = miner();
+        weighted.set_scorer(WeightedScorer::new(0.1, 0.0, 0.9));
+        assert_eq!(first_rule(&mut weighted), "Bread => Milk");
+
+        // Closures work too
+        let mut custom = miner();
+        custom.set_scorer(|rule: &AssociationRule| -rule.metrics.lift);
+        let rules = custom.mine().unwrap().rules;
+        assert!(rules
+            .windows(2)
+            .all(|pair| pair[0].metrics.lift <= pair[1].metrics.lift));
+    }
+}
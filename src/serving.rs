@@ -0,0 +1,436 @@
+//! Lock-free rule serving (`snapshot` feature)
+//!
+//! Wrapping a `Recommender` in a `RwLock` makes every request contend with
+//! the writer that swaps in freshly mined rules. A `RuleSnapshot` is instead
+//! immutable: rules, an antecedent index and the basket preprocessing are
+//! built once, and a `SnapshotHandle` swaps whole snapshots atomically.
+//! Readers never block; a request keeps using the snapshot it started with
+//! even if a newer one is published mid-request.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use arc_swap::ArcSwap;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+use crate::mining::RuleMiner;
+use crate::rules::{rank, Recommendation, ScoreAggregation};
+use crate::transaction::Transaction;
+use crate::types::AssociationRule;
+
+/// How basket and rule items are normalized before matching
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreprocessSpec {
+    /// Strip surrounding whitespace
+    pub trim: bool,
+    /// Compare items case-insensitively
+    pub lowercase: bool,
+}
+
+impl PreprocessSpec {
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    pub fn with_lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// Normalize one item
+    pub fn apply(&self, item: &str) -> String {
+        let item = if self.trim { item.trim() } else { item };
+        if self.lowercase {
+            item.to_lowercase()
+        } else {
+            item.to_string()
+        }
+    }
+}
+
+/// Immutable, indexed rule set for serving recommendations
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::serving::{PreprocessSpec, RuleSnapshot, SnapshotHandle};
+/// # let rules = Vec::new();
+/// # let newer_rules = Vec::new();
+///
+/// let handle = SnapshotHandle::new(RuleSnapshot::new(
+///     rules,
+///     PreprocessSpec::default().with_trim(true),
+/// ));
+///
+/// // Request threads
+/// let recommendations = handle.recommend(&["Laptop ".to_string()], 3);
+///
+/// // After re-mining
+/// let version = handle.publish(RuleSnapshot::new(newer_rules, PreprocessSpec::default()));
+/// assert_eq!(handle.current().version(), version);
+/// # let _ = recommendations;
+/// ```
+#[derive(Debug)]
+pub struct RuleSnapshot {
+    version: u64,
+    created: Instant,
+    rules: Vec<AssociationRule>,
+    /// Antecedent item -> rules containing it, in rule order
+    index: HashMap<String, Vec<usize>>,
+    /// Rules with an empty antecedent (match every basket)
+    unconditional: Vec<usize>,
+    preprocess: PreprocessSpec,
+    aggregation: ScoreAggregation,
+    item_counts: HashMap<String, usize>,
+}
+
+impl RuleSnapshot {
+    /// Build a snapshot, normalizing rule items with `preprocess`
+    pub fn new(rules: Vec<AssociationRule>, preprocess: PreprocessSpec) -> Self {
+        let rules: Vec<AssociationRule> = rules
+            .into_iter()
+            .map(|mut rule| {
+                for item in rule.antecedent.iter_mut().chain(&mut rule.consequent) {
+                    *item = preprocess.apply(item);
+                }
+                rule
+            })
+            .collect();
+
+        let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut unconditional = Vec::new();
+        for (idx, rule) in rules.iter().enumerate() {
+            if rule.antecedent.is_empty() {
+                unconditional.push(idx);
+            }
+            let distinct: HashSet<&String> = rule.antecedent.iter().collect();
+            for item in distinct {
+                index.entry(item.clone()).or_default().push(idx);
+            }
+        }
+
+        Self {
+            version: 0,
+            created: Instant::now(),
+            rules,
+            index,
+            unconditional,
+            preprocess,
+            aggregation: ScoreAggregation::default(),
+            item_counts: HashMap::new(),
+        }
+    }
+
+    /// Set the scoring strategy
+    pub fn with_aggregation(mut self, aggregation: ScoreAggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
+    /// Count item occurrences in `transactions` for tie-breaking
+    pub fn with_item_counts(mut self, transactions: &[Transaction]) -> Self {
+        self.item_counts.clear();
+        for tx in transactions {
+            let unique: HashSet<String> = tx
+                .items
+                .iter()
+                .map(|item| self.preprocess.apply(item))
+                .collect();
+            for item in unique {
+                *self.item_counts.entry(item).or_insert(0) += 1;
+            }
+        }
+        self
+    }
+
+    /// Version assigned by `SnapshotHandle::publish` (0 before publishing)
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Time since the snapshot was built
+    pub fn age(&self) -> Duration {
+        self.created.elapsed()
+    }
+
+    /// Rules, with items normalized
+    pub fn rules(&self) -> &[AssociationRule] {
+        &self.rules
+    }
+
+    pub fn preprocess(&self) -> &PreprocessSpec {
+        &self.preprocess
+    }
+
+    pub fn aggregation(&self) -> ScoreAggregation {
+        self.aggregation
+    }
+
+    /// The `top_n` best items not already in `basket`
+    ///
+    /// Same ranking as `Recommender::recommend`, but only rules sharing an
+    /// item with the basket are scored.
+    pub fn recommend(&self, basket: &[String], top_n: usize) -> Vec<Recommendation> {
+        let basket: Vec<String> = basket
+            .iter()
+            .map(|item| self.preprocess.apply(item))
+            .collect();
+        let candidates: BTreeSet<usize> = basket
+            .iter()
+            .filter_map(|item| self.index.get(item))
+            .flatten()
+            .chain(&self.unconditional)
+            .copied()
+            .collect();
+
+        rank(
+            candidates.into_iter().map(|idx| &self.rules[idx]),
+            &basket,
+            top_n,
+            self.aggregation,
+            &self.item_counts,
+            |_| true,
+        )
+    }
+}
+
+/// Point-in-time figures about the served snapshot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotMetrics {
+    pub version: u64,
+    pub age: Duration,
+    pub rule_count: usize,
+}
+
+type MetricsHook = Arc<dyn Fn(&SnapshotMetrics) + Send + Sync>;
+
+/// Shared handle to the current `RuleSnapshot`
+///
+/// Clones share the same slot. `current()` and `recommend()` are lock-free
+/// reads; `publish()` replaces the snapshot atomically.
+#[derive(Clone)]
+pub struct SnapshotHandle {
+    current: Arc<ArcSwap<RuleSnapshot>>,
+    next_version: Arc<AtomicU64>,
+    metrics_hook: Option<MetricsHook>,
+}
+
+impl SnapshotHandle {
+    /// Serve `snapshot` as version 1
+    pub fn new(mut snapshot: RuleSnapshot) -> Self {
+        snapshot.version = 1;
+        Self {
+            current: Arc::new(ArcSwap::from_pointee(snapshot)),
+            next_version: Arc::new(AtomicU64::new(2)),
+            metrics_hook: None,
+        }
+    }
+
+    /// Call `hook` from `report_metrics()`, and on every publish with the
+    /// outgoing snapshot (its age is then how stale it got before replacement)
+    pub fn with_metrics_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&SnapshotMetrics) + Send + Sync + 'static,
+    {
+        self.metrics_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// The snapshot being served
+    pub fn current(&self) -> Arc<RuleSnapshot> {
+        self.current.load_full()
+    }
+
+    /// Recommend from the current snapshot
+    pub fn recommend(&self, basket: &[String], top_n: usize) -> Vec<Recommendation> {
+        self.current.load().recommend(basket, top_n)
+    }
+
+    /// Swap in a new snapshot, returning its version
+    pub fn publish(&self, mut snapshot: RuleSnapshot) -> u64 {
+        let version = self.next_version.fetch_add(1, Ordering::Relaxed);
+        snapshot.version = version;
+        let previous = self.current.swap(Arc::new(snapshot));
+        if let Some(hook) = &self.metrics_hook {
+            hook(&Self::metrics_of(&previous));
+        }
+        version
+    }
+
+    /// Re-mine with `miner` and publish the result
+    ///
+    /// The preprocessing and scoring strategy of the current snapshot carry
+    /// over; item counts for tie-breaking do not.
+    pub fn publish_from(&self, miner: &mut RuleMiner) -> Result<u64> {
+        let rules = miner.mine()?.rules;
+        let current = self.current.load();
+        let snapshot = RuleSnapshot::new(rules, current.preprocess.clone())
+            .with_aggregation(current.aggregation);
+        Ok(self.publish(snapshot))
+    }
+
+    /// Figures for the current snapshot
+    pub fn metrics(&self) -> SnapshotMetrics {
+        Self::metrics_of(&self.current.load())
+    }
+
+    /// Pass the current snapshot's figures to the metrics hook
+    pub fn report_metrics(&self) -> SnapshotMetrics {
+        let metrics = self.metrics();
+        if let Some(hook) = &self.metrics_hook {
+            hook(&metrics);
+        }
+        metrics
+    }
+
+    fn metrics_of(snapshot: &RuleSnapshot) -> SnapshotMetrics {
+        SnapshotMetrics {
+            version: snapshot.version,
+            age: snapshot.age(),
+            rule_count: snapshot.rules.len(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SnapshotHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotHandle")
+            .field("metrics", &self.metrics())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_rule;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Mutex;
+
+    /// Every rule of generation `n` recommends `Item{n}` with confidence n/100
+    fn generation(n: u64) -> RuleSnapshot {
+        let rules = (0..50)
+            .map(|i| {
+                test_rule(
+                    &[&format!("A{}", i)],
+                    &[&format!("Item{}", n)],
+                    n as f64 / 100.0,
+                    1.5,
+                )
+            })
+            .collect();
+        RuleSnapshot::new(rules, PreprocessSpec::default())
+    }
+
+    #[test]
+    fn test_index_matches_recommender() {
+        let rules = vec![
+            test_rule(&["Laptop"], &["Mouse"], 0.8, 1.5),
+            test_rule(&["laptop "], &["Bag"], 0.6, 1.5),
+            test_rule(&["Phone"], &["Case"], 0.9, 1.5),
+        ];
+        let spec = PreprocessSpec::default()
+            .with_trim(true)
+            .with_lowercase(true);
+        let snapshot = RuleSnapshot::new(rules, spec);
+
+        let recommendations = snapshot.recommend(&[" LAPTOP".to_string()], 5);
+        let items: Vec<&str> = recommendations.iter().map(|r| r.item.as_str()).collect();
+        assert_eq!(items, vec!["mouse", "bag"]);
+
+        let expected =
+            crate::rules::Recommender::new(snapshot.rules()).recommend(&["laptop".to_string()], 5);
+        assert_eq!(expected.len(), recommendations.len());
+        assert!(snapshot.recommend(&["Tent".to_string()], 5).is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_swaps_never_tear() {
+        let handle = SnapshotHandle::new(generation(1));
+        let stop = Arc::new(AtomicBool::new(false));
+        let basket: Vec<String> = (0..50).map(|i| format!("A{}", i)).collect();
+
+        let reader = {
+            let handle = handle.clone();
+            let stop = Arc::clone(&stop);
+            let basket = basket.clone();
+            std::thread::spawn(move || {
+                let mut reads = 0u64;
+                let mut last_version = 0;
+                while !stop.load(Ordering::Relaxed) || reads == 0 {
+                    let snapshot = handle.current();
+                    assert!(snapshot.version() >= last_version, "version went back");
+                    last_version = snapshot.version();
+
+                    // All 50 rules must come from one generation
+                    let recommendation = &snapshot.recommend(&basket, 1)[0];
+                    let n = snapshot.version();
+                    assert_eq!(recommendation.item, format!("Item{}", n));
+                    assert_eq!(recommendation.rules.len(), 50);
+                    assert!(recommendation
+                        .rules
+                        .iter()
+                        .all(|r| r.metrics.confidence == n as f64 / 100.0));
+                    reads += 1;
+                }
+                reads
+            })
+        };
+
+        for n in 2..=40 {
+            assert_eq!(handle.publish(generation(n)), n);
+            std::thread::yield_now();
+        }
+        stop.store(true, Ordering::Relaxed);
+        assert!(reader.join().unwrap() > 0);
+
+        // New rules are served after the swap
+        let latest = handle.recommend(&basket, 1);
+        assert_eq!(latest[0].item, "Item40");
+    }
+
+    #[test]
+    fn test_metrics_hook_and_publish_from() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+        let handle = SnapshotHandle::new(generation(1))
+            .with_metrics_hook(move |m| sink.lock().unwrap().push(*m));
+
+        std::thread::sleep(Duration::from_millis(5));
+        let metrics = handle.report_metrics();
+        assert_eq!((metrics.version, metrics.rule_count), (1, 50));
+        assert!(metrics.age >= Duration::from_millis(5));
+
+        // Two baskets are too few for the single-transaction guardrail
+        let config = crate::MiningConfig::builder()
+            .allow_singleton_support(true)
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner
+            .add_transactions(vec![
+                Transaction::new(
+                    "t1",
+                    vec!["X".to_string(), "Y".to_string()],
+                    chrono::Utc::now(),
+                ),
+                Transaction::new(
+                    "t2",
+                    vec!["X".to_string(), "Y".to_string()],
+                    chrono::Utc::now(),
+                ),
+            ])
+            .unwrap();
+        assert_eq!(handle.publish_from(&mut miner).unwrap(), 2);
+        assert_eq!(handle.recommend(&["X".to_string()], 1)[0].item, "Y");
+
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), 2);
+        // The outgoing snapshot is reported on publish
+        assert_eq!(reported[1].version, 1);
+        assert!(reported[1].age >= metrics.age);
+    }
+}
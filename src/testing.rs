@@ -0,0 +1,312 @@
+//! Helpers for sharing reproduction datasets
+//!
+//! Bug reports about surprising rules are easiest to act on with the data
+//! that produced them, but real baskets carry product names, customer ids
+//! and free-form metadata. `anonymize` rewrites a dataset so it can be
+//! shared: item names and transaction ids become keyed pseudonyms, user ids
+//! and metadata are dropped, and timestamps can be jittered. Co-occurrence
+//! is preserved exactly, so the anonymized data mines the same rules with
+//! the same metrics, only under different item names. (The one exception is
+//! a tie that mining breaks by item order, such as `A => B` and `B => A`
+//! with equal confidence, where the other direction may be kept.)
+//!
+//! Pseudonyms are derived from a keyed FNV-1a hash; they are stable for a
+//! given seed but are not a cryptographic guarantee. Keep the seed and the
+//! returned `item_map` private.
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::export::grl::fnv1a_hash;
+use crate::rng::SplitMix64;
+use crate::transaction::Transaction;
+
+/// Options for `anonymize_with`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnonymizeOptions {
+    /// Key for pseudonyms and jitter; the same seed gives the same output
+    pub seed: u64,
+    /// Maximum timestamp shift (None = keep timestamps as they are)
+    pub jitter: Option<Duration>,
+}
+
+impl AnonymizeOptions {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, jitter: None }
+    }
+
+    /// Shift each timestamp forward by up to `jitter`, never past the next
+    /// distinct timestamp, so the original ordering (and ties) are kept
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+}
+
+/// An anonymized dataset and the mapping used to produce it
+#[derive(Debug, Clone)]
+pub struct Anonymized {
+    pub transactions: Vec<Transaction>,
+    /// Original item -> pseudonym (do not share alongside the data)
+    pub item_map: HashMap<String, String>,
+}
+
+impl Anonymized {
+    /// Pseudonym -> original item, for reading rules mined from shared data
+    pub fn reverse_item_map(&self) -> HashMap<&str, &str> {
+        self.item_map
+            .iter()
+            .map(|(item, pseudonym)| (pseudonym.as_str(), item.as_str()))
+            .collect()
+    }
+}
+
+/// Anonymize transactions with the default options for `seed`
+///
+/// # Example
+/// ```
+/// use rust_rule_miner::{testing, Transaction};
+/// use chrono::Utc;
+///
+/// let mut tx = Transaction::with_user(
+///     "order-1001",
+///     vec!["Laptop".to_string(), "Mouse".to_string()],
+///     Utc::now(),
+///     "alice@example.com",
+/// );
+/// tx.metadata.insert("store".to_string(), "Berlin".into());
+///
+/// let shared = testing::anonymize(&[tx], 42);
+/// let anon = &shared.transactions[0];
+/// assert_eq!(anon.items.len(), 2);
+/// assert!(!anon.contains("Laptop") && anon.id != "order-1001");
+/// assert!(anon.user_id.is_none() && anon.metadata.is_empty());
+/// ```
+pub fn anonymize(transactions: &[Transaction], seed: u64) -> Anonymized {
+    anonymize_with(transactions, &AnonymizeOptions::new(seed))
+}
+
+/// Anonymize transactions
+///
+/// Items are renamed `item_<hex>` and ids `tx_<hex>`; equal names map to
+/// equal pseudonyms and distinct names to distinct ones. Item order inside
+/// each transaction and the transaction order are kept.
+pub fn anonymize_with(transactions: &[Transaction], options: &AnonymizeOptions) -> Anonymized {
+    let items: BTreeSet<&str> = transactions
+        .iter()
+        .flat_map(|tx| tx.items.iter().map(String::as_str))
+        .collect();
+    let ids: BTreeSet<&str> = transactions.iter().map(|tx| tx.id.as_str()).collect();
+    let item_map = pseudonyms(items, "item", options.seed);
+    let id_map = pseudonyms(ids, "tx", options.seed);
+    let times = options
+        .jitter
+        .map(|jitter| jittered_times(transactions, jitter, options.seed))
+        .unwrap_or_default();
+
+    let transactions = transactions
+        .iter()
+        .map(|tx| {
+            let items = tx.items.iter().map(|item| item_map[item].clone()).collect();
+            let timestamp = times.get(&tx.timestamp).copied().unwrap_or(tx.timestamp);
+            Transaction::new(id_map[&tx.id].clone(), items, timestamp)
+        })
+        .collect();
+
+    Anonymized {
+        transactions,
+        item_map,
+    }
+}
+
+/// Keyed pseudonyms, resolving (unlikely) hash collisions deterministically
+fn pseudonyms(names: BTreeSet<&str>, prefix: &str, seed: u64) -> HashMap<String, String> {
+    let mut taken = BTreeSet::new();
+    names
+        .into_iter()
+        .map(|name| {
+            let mut round = 0u32;
+            let pseudonym = loop {
+                let hash = fnv1a_hash(&format!("{:016x}:{}:{}", seed, round, name));
+                let candidate = format!("{}_{:016x}", prefix, hash);
+                if taken.insert(candidate.clone()) {
+                    break candidate;
+                }
+                round += 1;
+            };
+            (name.to_string(), pseudonym)
+        })
+        .collect()
+}
+
+/// Shift each distinct timestamp by less than the gap to the next one
+fn jittered_times(
+    transactions: &[Transaction],
+    jitter: Duration,
+    seed: u64,
+) -> HashMap<DateTime<Utc>, DateTime<Utc>> {
+    let distinct: Vec<DateTime<Utc>> = transactions
+        .iter()
+        .map(|tx| tx.timestamp)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let max_ms = jitter.as_millis().min(i64::MAX as u128) as i64;
+    let mut rng = SplitMix64::new(seed);
+
+    distinct
+        .iter()
+        .enumerate()
+        .map(|(idx, &time)| {
+            let limit = match distinct.get(idx + 1) {
+                Some(next) => (*next - time).num_milliseconds().min(max_ms),
+                None => max_ms,
+            };
+            // floor(u * limit) < limit, so the shifted time stays before `next`
+            let offset = (rng.next_open01() * limit as f64) as i64;
+            (time, time + chrono::Duration::milliseconds(offset))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Confidence, MiningConfig, Support};
+    use crate::mining::RuleMiner;
+    use crate::types::AssociationRule;
+    use chrono::TimeZone;
+
+    /// No equal-confidence rule pairs, so no name-dependent tie-breaks
+    fn dataset() -> Vec<Transaction> {
+        let baskets: [&[&str]; 8] = [
+            &["Laptop", "Mouse", "Bag"],
+            &["Laptop", "Mouse"],
+            &["Laptop", "Bag"],
+            &["Phone", "Case", "Charger"],
+            &["Phone", "Case", "Charger"],
+            &["Laptop", "Mouse", "Charger"],
+            &["Phone", "Charger"],
+            &["Laptop", "Mouse", "Bag"],
+        ];
+        baskets
+            .iter()
+            .enumerate()
+            .map(|(idx, items)| {
+                let at = Utc
+                    .with_ymd_and_hms(2024, 3, 1, 9, (idx as u32 / 2) * 10, 0)
+                    .unwrap();
+                let mut tx = Transaction::with_user(
+                    format!("order-{}", idx),
+                    items.iter().map(|s| s.to_string()).collect(),
+                    at,
+                    format!("customer-{}", idx % 3),
+                );
+                tx.metadata
+                    .insert("email".to_string(), format!("c{}@shop.test", idx).into());
+                tx
+            })
+            .collect()
+    }
+
+    fn mine(transactions: &[Transaction]) -> Vec<AssociationRule> {
+        let config = MiningConfig::builder()
+            .min_support(Support::fraction(0.2).unwrap())
+            .min_confidence(Confidence::fraction(0.5).unwrap())
+            .build()
+            .unwrap();
+        let mut miner = RuleMiner::new(config);
+        miner.add_transactions(transactions.to_vec()).unwrap();
+        miner.mine().unwrap().rules
+    }
+
+    /// Rules as comparable (antecedent, consequent, metrics) triples
+    fn canonical(
+        rules: &[AssociationRule],
+        rename: impl Fn(&str) -> String,
+    ) -> Vec<(Vec<String>, Vec<String>, String)> {
+        let side = |items: &[String]| {
+            let mut items: Vec<String> = items.iter().map(|i| rename(i)).collect();
+            items.sort();
+            items
+        };
+        let mut rules: Vec<_> = rules
+            .iter()
+            .map(|rule| {
+                (
+                    side(&rule.antecedent),
+                    side(&rule.consequent),
+                    format!("{:?}", rule.metrics),
+                )
+            })
+            .collect();
+        rules.sort();
+        rules
+    }
+
+    #[test]
+    fn test_anonymized_data_mines_isomorphic_rules() {
+        let original = dataset();
+        let shared = anonymize(&original, 7);
+
+        // Bijective item mapping
+        let pseudonyms: BTreeSet<&String> = shared.item_map.values().collect();
+        assert_eq!(shared.item_map.len(), 6);
+        assert_eq!(pseudonyms.len(), shared.item_map.len());
+        assert_eq!(shared.reverse_item_map().len(), 6);
+
+        let rules = mine(&original);
+        assert!(!rules.is_empty());
+        let renamed = canonical(&rules, |item| shared.item_map[item].clone());
+        let anonymized = canonical(&mine(&shared.transactions), str::to_string);
+        assert_eq!(renamed, anonymized);
+    }
+
+    #[test]
+    fn test_strips_identifying_fields() {
+        let original = dataset();
+        let shared = anonymize(&original, 7);
+        let dump = serde_json::to_string(&shared.transactions).unwrap();
+        for needle in ["Laptop", "order-", "customer-", "shop.test"] {
+            assert!(!dump.contains(needle), "{} leaked", needle);
+        }
+        for (anon, tx) in shared.transactions.iter().zip(&original) {
+            assert!(anon.user_id.is_none() && anon.metadata.is_empty());
+            assert_eq!(anon.timestamp, tx.timestamp);
+            assert_eq!(anon.items.len(), tx.items.len());
+        }
+
+        // Stable for a seed, different across seeds
+        let again = anonymize(&original, 7);
+        assert_eq!(again.item_map, shared.item_map);
+        assert_eq!(again.transactions[0].id, shared.transactions[0].id);
+        assert_ne!(anonymize(&original, 8).item_map, shared.item_map);
+    }
+
+    #[test]
+    fn test_jitter_keeps_ordering() {
+        let original = dataset();
+        let options = AnonymizeOptions::new(3).with_jitter(Duration::from_secs(3600));
+        let shared = anonymize_with(&original, &options);
+
+        // Same pairwise order (including ties) as the original timestamps
+        for i in 0..original.len() {
+            for j in 0..original.len() {
+                assert_eq!(
+                    original[i].timestamp.cmp(&original[j].timestamp),
+                    shared.transactions[i]
+                        .timestamp
+                        .cmp(&shared.transactions[j].timestamp)
+                );
+            }
+        }
+        let mut moved = false;
+        for (anon, tx) in shared.transactions.iter().zip(&original) {
+            assert!(anon.timestamp >= tx.timestamp);
+            moved |= anon.timestamp != tx.timestamp;
+        }
+        assert!(moved);
+    }
+}
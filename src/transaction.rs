@@ -1,6 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::aggregate::{GroupBy, WindowAggregator};
 
 /// A transaction (shopping cart, event sequence, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +60,25 @@ impl Transaction {
     }
 }
 
+/// Merge rows into one transaction per `group_by` key and time window
+///
+/// Items are deduplicated and each merged transaction keeps the earliest
+/// timestamp of its rows. Shorthand for `WindowAggregator`, which also
+/// filters merged transactions by item count.
+pub fn aggregate_by_window<I>(
+    transactions: I,
+    window: Duration,
+    group_by: GroupBy,
+) -> Vec<Transaction>
+where
+    I: IntoIterator<Item = Transaction>,
+{
+    WindowAggregator::new(window)
+        .with_group_by(group_by)
+        .with_earliest_timestamps(true)
+        .aggregate(transactions)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,26 +1,59 @@
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+use crate::config::MetricSet;
+use crate::scoring::{QualityScorer, WeightedScorer};
+
 /// An itemset (set of items)
 pub type ItemSet = Vec<String>;
 
 /// Frequent itemset with support value
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FrequentItemset {
     pub items: ItemSet,
     pub support: f64,
+
+    /// Transactions containing every item (`support` is this over the total)
+    #[serde(default)]
+    pub count: usize,
+}
+
+impl FrequentItemset {
+    /// Itemset found in `count` of `total` transactions
+    pub fn from_count(items: ItemSet, count: usize, total: usize) -> Self {
+        Self {
+            items,
+            support: count as f64 / total as f64,
+            count,
+        }
+    }
 }
 
 /// Association rule: A → B
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AssociationRule {
     pub antecedent: ItemSet,
     pub consequent: ItemSet,
     pub metrics: PatternMetrics,
 }
 
+/// Negative association rule: A → ¬B
+///
+/// Baskets containing the antecedent rarely contain `absent`. Metrics are
+/// computed against the absence of `absent`: confidence is P(¬B | A),
+/// support P(A ∧ ¬B) and lift P(¬B | A) / P(¬B). Kept apart from
+/// `AssociationRule` so negative rules are never mistaken for
+/// recommendations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NegativeRule {
+    pub antecedent: ItemSet,
+    /// Item that tends to be missing when the antecedent is present
+    pub absent: String,
+    pub metrics: PatternMetrics,
+}
+
 /// Sequential pattern (ordered itemsets with time constraints)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SequentialPattern {
     pub sequence: Vec<ItemSet>,
     pub time_gaps: Vec<Duration>,
@@ -28,7 +61,14 @@ pub struct SequentialPattern {
 }
 
 /// Pattern quality metrics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Confidence, support and lift are always present. The remaining metrics are
+/// `None` unless requested through `MiningConfig::compute_metrics`.
+///
+/// `#[non_exhaustive]` so metrics can be added in minor releases; outside
+/// this crate, start from `PatternMetrics::basic` or `Default`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct PatternMetrics {
     /// Confidence: P(consequent | antecedent)
     /// How often B happens when A happens
@@ -44,7 +84,44 @@ pub struct PatternMetrics {
 
     /// Conviction: P(A) * P(¬B) / P(A ∧ ¬B)
     /// How much more often A implies B than expected by chance
-    pub conviction: f64,
+    /// (infinite for rules with 100% confidence)
+    #[serde(default)]
+    pub conviction: Option<f64>,
+
+    /// Leverage: P(A ∧ B) - P(A) * P(B) (`MetricSet::EXTENDED`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leverage: Option<f64>,
+
+    /// Jaccard: P(A ∧ B) / P(A ∨ B) (`MetricSet::EXTENDED`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jaccard: Option<f64>,
+
+    /// Cosine: P(A ∧ B) / sqrt(P(A) * P(B)) (`MetricSet::EXTENDED`, null-invariant)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cosine: Option<f64>,
+
+    /// Kulczynski: (P(B | A) + P(A | B)) / 2 (`MetricSet::EXTENDED`, null-invariant)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kulczynski: Option<f64>,
+
+    /// Transaction counts behind the metrics (`MetricSet::COUNTS`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counts: Option<RuleCounts>,
+
+    /// Weighted support of antecedent ∪ consequent (set when the miner has
+    /// item weights, see `RuleMiner::set_item_weights`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weighted_support: Option<f64>,
+
+    /// Chi-square statistic of the antecedent x consequent 2x2 contingency
+    /// table (`MetricSet::SIGNIFICANCE`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chi_square: Option<f64>,
+
+    /// P-value of `chi_square` (1 degree of freedom), before any multiple
+    /// testing correction (`MetricSet::SIGNIFICANCE`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_value: Option<f64>,
 
     /// Optional: time-based metrics
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -54,8 +131,24 @@ pub struct PatternMetrics {
     pub time_variance: Option<Duration>,
 }
 
+/// Transaction counts a rule's metrics are derived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleCounts {
+    /// Transactions containing the antecedent
+    pub antecedent: usize,
+
+    /// Transactions containing the consequent
+    pub consequent: usize,
+
+    /// Transactions containing both
+    pub joint: usize,
+
+    /// All transactions
+    pub total: usize,
+}
+
 /// Discovered pattern
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Pattern {
     pub pattern_type: PatternType,
     pub items: Vec<String>,
@@ -63,7 +156,7 @@ pub struct Pattern {
     pub evidence: Vec<String>, // Transaction IDs
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PatternType {
     /// Frequent itemset (co-occurrence)
     FrequentItemset,
@@ -78,34 +171,176 @@ pub enum PatternType {
     },
 }
 
+impl PatternMetrics {
+    /// Confidence, support and lift, with conviction derived from them
+    ///
+    /// Conviction is infinite at 100% confidence and None when lift is not
+    /// positive (the consequent's frequency is then unknown).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{AssociationRule, PatternMetrics};
+    ///
+    /// let rule = AssociationRule::new(["Laptop"], ["Mouse"], PatternMetrics::basic(0.8, 0.3, 2.0));
+    /// assert_eq!(rule.consequent, ["Mouse"]);
+    /// assert!(rule.metrics.approx_eq(&PatternMetrics::basic(0.8, 0.3, 2.0 + 1e-12), 1e-9));
+    /// ```
+    pub fn basic(confidence: f64, support: f64, lift: f64) -> Self {
+        let conviction = (lift > 0.0).then(|| {
+            let p_consequent = confidence / lift;
+            if confidence < 1.0 && p_consequent < 1.0 {
+                (1.0 - p_consequent) / (1.0 - confidence)
+            } else {
+                f64::INFINITY
+            }
+        });
+        Self {
+            confidence,
+            support,
+            lift,
+            conviction,
+            ..Default::default()
+        }
+    }
+
+    /// Whether every metric present on either side matches within `epsilon`
+    ///
+    /// Optional metrics must be present on both sides or neither; infinite
+    /// values must be equal. Time fields and counts compare exactly.
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        let close = |a: f64, b: f64| a == b || (a - b).abs() <= epsilon;
+        let close_opt = |a: Option<f64>, b: Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => close(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        close(self.confidence, other.confidence)
+            && close(self.support, other.support)
+            && close(self.lift, other.lift)
+            && close_opt(self.conviction, other.conviction)
+            && close_opt(self.leverage, other.leverage)
+            && close_opt(self.jaccard, other.jaccard)
+            && close_opt(self.cosine, other.cosine)
+            && close_opt(self.kulczynski, other.kulczynski)
+            && close_opt(self.weighted_support, other.weighted_support)
+            && close_opt(self.chi_square, other.chi_square)
+            && close_opt(self.p_value, other.p_value)
+            && self.counts == other.counts
+            && self.avg_time_gap == other.avg_time_gap
+            && self.time_variance == other.time_variance
+    }
+
+    /// Optional metrics present on this value
+    pub fn computed(&self) -> MetricSet {
+        let mut set = MetricSet::CORE;
+        if self.conviction.is_some() {
+            set |= MetricSet::CONVICTION;
+        }
+        if self.leverage.is_some() && self.jaccard.is_some() {
+            set |= MetricSet::EXTENDED;
+        }
+        if self.counts.is_some() {
+            set |= MetricSet::COUNTS;
+        }
+        if self.chi_square.is_some() && self.p_value.is_some() {
+            set |= MetricSet::SIGNIFICANCE;
+        }
+        set
+    }
+}
+
 impl AssociationRule {
+    /// Rule `antecedent => consequent` with the given metrics
+    ///
+    /// # Example
+    /// ```
+    /// use rust_rule_miner::{AssociationRule, PatternMetrics};
+    ///
+    /// let metrics = PatternMetrics::basic(0.9, 0.2, 1.5);
+    /// let rule = AssociationRule::new(vec!["Phone", "Case"], ["Charger"], metrics);
+    /// assert!(rule.matches(&["Case".to_string(), "Phone".to_string()]));
+    /// ```
+    pub fn new<A, C>(antecedent: A, consequent: C, metrics: PatternMetrics) -> Self
+    where
+        A: IntoIterator,
+        A::Item: Into<String>,
+        C: IntoIterator,
+        C::Item: Into<String>,
+    {
+        Self {
+            antecedent: antecedent.into_iter().map(Into::into).collect(),
+            consequent: consequent.into_iter().map(Into::into).collect(),
+            metrics,
+        }
+    }
+
     /// Calculate quality score for ranking
+    ///
+    /// 0.5 * confidence + 0.3 * lift + 0.2 * support, the default
+    /// `WeightedScorer`; see `RuleMiner::set_scorer` for other scorings.
     pub fn quality_score(&self) -> f64 {
-        // Weighted combination of metrics
-        self.metrics.confidence * 0.5 + self.metrics.lift * 0.3 + self.metrics.support * 0.2
+        WeightedScorer::default().score(self)
+    }
+
+    /// Check whether the rule fires for a basket (antecedent is a subset of it)
+    ///
+    /// Same check as `edge::Rule::matches` on edge targets.
+    pub fn matches(&self, basket: &[String]) -> bool {
+        crate::edge::contains_all(basket, &self.antecedent)
     }
 }
 
+/// Rule `antecedent => consequent` at support 0.2, the fixture shared by the
+/// crate's tests
+#[cfg(test)]
+pub(crate) fn test_rule(
+    antecedent: &[&str],
+    consequent: &[&str],
+    confidence: f64,
+    lift: f64,
+) -> AssociationRule {
+    AssociationRule::new(
+        antecedent.iter().copied(),
+        consequent.iter().copied(),
+        PatternMetrics::basic(confidence, 0.2, lift),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_quality_score() {
-        let rule = AssociationRule {
-            antecedent: vec!["A".to_string()],
-            consequent: vec!["B".to_string()],
-            metrics: PatternMetrics {
-                confidence: 0.8,
-                support: 0.6,
-                lift: 1.5,
-                conviction: 2.0,
-                avg_time_gap: None,
-                time_variance: None,
-            },
-        };
+        let rule = AssociationRule::new(["A"], ["B"], PatternMetrics::basic(0.8, 0.6, 1.5));
 
         let score = rule.quality_score();
         assert!(score > 0.0 && score <= 1.0);
     }
+
+    #[test]
+    fn test_matches_basket() {
+        let rule = AssociationRule::new(["A", "B"], ["C"], PatternMetrics::basic(0.8, 0.6, 1.5));
+
+        assert!(rule.matches(&["B".to_string(), "A".to_string(), "D".to_string()]));
+        assert!(!rule.matches(&["A".to_string()]));
+    }
+
+    #[test]
+    fn test_basic_metrics_conviction() {
+        // P(B) = 0.8 / 2.0 = 0.4, conviction = 0.6 / 0.2
+        let metrics = PatternMetrics::basic(0.8, 0.3, 2.0);
+        assert!((metrics.conviction.unwrap() - 3.0).abs() < 1e-12);
+        assert_eq!(
+            PatternMetrics::basic(1.0, 0.3, 2.0).conviction,
+            Some(f64::INFINITY)
+        );
+        assert_eq!(PatternMetrics::basic(0.5, 0.3, 0.0).conviction, None);
+
+        let mut close = metrics.clone();
+        close.confidence += 1e-12;
+        assert_ne!(close, metrics);
+        assert!(close.approx_eq(&metrics, 1e-9));
+        close.leverage = Some(0.1);
+        assert!(!close.approx_eq(&metrics, 1e-9));
+    }
 }
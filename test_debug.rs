@@ -1,4 +1,4 @@
-use rust_rule_miner::{MiningConfig, RuleMiner, Transaction};
+use rust_rule_miner::{Confidence, MiningConfig, RuleMiner, Support, Transaction};
 use chrono::Utc;
 
 fn main() {
@@ -8,15 +8,15 @@ fn main() {
         Transaction::new("tx3", vec!["A".to_string(), "B".to_string()], Utc::now()),
     ];
 
-    let config = MiningConfig {
-        min_support: 0.5,
-        min_confidence: 0.8,
-        ..Default::default()
-    };
+    let config = MiningConfig::builder()
+        .min_support(Support::fraction(0.5).unwrap())
+        .min_confidence(Confidence::fraction(0.8).unwrap())
+        .build()
+        .unwrap();
 
     let mut miner = RuleMiner::new(config);
     miner.add_transactions(transactions).unwrap();
-    let rules = miner.mine_association_rules().unwrap();
+    let rules = miner.mine().unwrap().rules;
     
     println!("Total rules mined: {}", rules.len());
     for (i, rule) in rules.iter().enumerate() {